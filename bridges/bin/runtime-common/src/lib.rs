@@ -23,6 +23,7 @@ pub mod extensions;
 pub mod messages_api;
 pub mod messages_benchmarking;
 pub mod parachains_benchmarking;
+pub mod rewards;
 
 mod mock;
 
@@ -0,0 +1,548 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relayer rewards payment procedures, that allow relayers to claim their rewards to
+//! beneficiaries at other chains.
+
+use bp_relayers::{PayRewardFromAccount, PaymentProcedure, RewardsAccountParams};
+use codec::{Decode, Encode};
+use frame_support::traits::{fungible::Mutate, Get};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::TryConvert, DispatchError, RuntimeDebug};
+use sp_std::{fmt::Debug, marker::PhantomData, vec};
+use xcm::{latest::prelude::*, VersionedLocation};
+use xcm_executor::traits::{FeeManager as XcmFeeManager, FeeReason, TransactAsset, TransferType};
+
+/// Beneficiary of the relayer reward.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RewardBeneficiary<AccountId> {
+	/// Account at this chain.
+	Local(AccountId),
+	/// Location at the destination chain of the payment procedure. It is interpreted relative
+	/// to the destination chain.
+	Remote(VersionedLocation),
+}
+
+impl<AccountId> From<AccountId> for RewardBeneficiary<AccountId> {
+	fn from(account: AccountId) -> Self {
+		RewardBeneficiary::Local(account)
+	}
+}
+
+/// Reward payment procedure that pays rewards either to local accounts (using the
+/// [`PayRewardFromAccount`]), or to locations at the `Destination` chain over XCM.
+///
+/// The remote payment withdraws the `RewardAsset` from the rewards account and transfers it to
+/// the `Destination` using the `Transfer` type. Only `TransferType::Teleport` and
+/// `TransferType::LocalReserve` are supported. XCM delivery fees must be paid in the
+/// `RewardAsset` and are deducted from the reward, so the rewards account never pays more than
+/// the relayer has earned. Withdrawn delivery fees are passed to the `FeeManager`. The rest of
+/// the reward is used to buy execution at the destination and what is left after that is
+/// deposited to the beneficiary. Every message carries a unique `SetTopic` instruction, so
+/// transfers may be tracked at the destination.
+pub struct PayRewardFromAccountOrOverXcm<
+	T,
+	Relayer,
+	LaneId,
+	AssetTransactor,
+	Router,
+	FeeManager,
+	UniversalLocation,
+	AccountToLocation,
+	RewardAsset,
+	Destination,
+	Transfer,
+>(
+	PhantomData<(
+		T,
+		Relayer,
+		LaneId,
+		AssetTransactor,
+		Router,
+		FeeManager,
+		UniversalLocation,
+		AccountToLocation,
+		RewardAsset,
+		Destination,
+		Transfer,
+	)>,
+);
+
+impl<
+		T,
+		Relayer,
+		LaneId,
+		AssetTransactor,
+		Router,
+		FeeManager,
+		UniversalLocation,
+		AccountToLocation,
+		RewardAsset,
+		Destination,
+		Transfer,
+	> PaymentProcedure<Relayer, T::Balance>
+	for PayRewardFromAccountOrOverXcm<
+		T,
+		Relayer,
+		LaneId,
+		AssetTransactor,
+		Router,
+		FeeManager,
+		UniversalLocation,
+		AccountToLocation,
+		RewardAsset,
+		Destination,
+		Transfer,
+	>
+where
+	T: Mutate<Relayer>,
+	T::Balance: Into<u128>,
+	Relayer: Clone + Debug + Decode + Encode + Eq + TypeInfo,
+	LaneId: Decode + Encode,
+	AssetTransactor: TransactAsset,
+	Router: SendXcm,
+	FeeManager: XcmFeeManager,
+	UniversalLocation: Get<InteriorLocation>,
+	AccountToLocation: for<'a> TryConvert<&'a Relayer, Location>,
+	RewardAsset: Get<AssetId>,
+	Destination: Get<Location>,
+	Transfer: Get<TransferType>,
+{
+	type Error = DispatchError;
+	type LaneId = LaneId;
+	type Beneficiary = RewardBeneficiary<Relayer>;
+
+	fn pay_reward(
+		relayer: &Relayer,
+		rewards_account_params: RewardsAccountParams<Self::LaneId>,
+		reward: T::Balance,
+		beneficiary: Self::Beneficiary,
+	) -> Result<(), Self::Error> {
+		let beneficiary = match beneficiary {
+			RewardBeneficiary::Local(account) =>
+				return PayRewardFromAccount::<T, Relayer, LaneId>::pay_reward(
+					relayer,
+					rewards_account_params,
+					reward,
+					account,
+				),
+			RewardBeneficiary::Remote(location) => Location::try_from(location)
+				.map_err(|_| DispatchError::Other("Unsupported beneficiary location version"))?,
+		};
+
+		let rewards_account =
+			PayRewardFromAccount::<T, Relayer, LaneId>::rewards_account(rewards_account_params);
+		let rewards_location = AccountToLocation::try_convert(&rewards_account)
+			.map_err(|_| DispatchError::Other("Failed to convert rewards account to location"))?;
+		let destination = Destination::get();
+		let transfer_type = Transfer::get();
+		let reward_asset_id = RewardAsset::get();
+		let reward_asset_id_at_destination = reward_asset_id
+			.clone()
+			.reanchored(&destination, &UniversalLocation::get())
+			.map_err(|_| DispatchError::Other("Failed to reanchor reward asset"))?;
+		let reward: u128 = reward.into();
+
+		// estimate delivery fees using the message that carries the whole reward. The topic is
+		// a placeholder here, so that the estimated message has the same size as the real one
+		let (_, estimated_delivery_fees) = validate_send::<Router>(
+			destination.clone(),
+			reward_transfer_message(
+				&transfer_type,
+				(reward_asset_id_at_destination.clone(), reward).into(),
+				beneficiary.clone(),
+				[0; 32],
+			)?,
+		)
+		.map_err(|_| DispatchError::Other("Failed to validate reward transfer message"))?;
+		let estimated_delivery_fee =
+			delivery_fee_amount(&reward_asset_id, &estimated_delivery_fees)?;
+
+		// delivery fees are paid from the reward, so the rewards account is never charged
+		// more than the relayer has earned
+		let transferred = reward
+			.checked_sub(estimated_delivery_fee)
+			.filter(|transferred| *transferred > 0)
+			.ok_or(DispatchError::Other("Reward does not cover delivery fees"))?;
+		let reward_at_destination: Asset = (reward_asset_id_at_destination, transferred).into();
+		let mut message = reward_transfer_message(
+			&transfer_type,
+			reward_at_destination,
+			beneficiary.clone(),
+			[0; 32],
+		)?;
+		let message_id = frame_system::unique(&message);
+		if let Some(SetTopic(topic)) = message.0.last_mut() {
+			*topic = message_id;
+		}
+		let (ticket, delivery_fees) = validate_send::<Router>(destination.clone(), message)
+			.map_err(|_| DispatchError::Other("Failed to validate reward transfer message"))?;
+		let delivery_fee = delivery_fee_amount(&reward_asset_id, &delivery_fees)?;
+		if transferred.saturating_add(delivery_fee) > reward {
+			return Err(DispatchError::Other("Reward does not cover delivery fees"))
+		}
+
+		// withdraw transferred reward and delivery fees from the rewards account
+		let context = XcmContext::with_message_id(message_id);
+		let transferred: Asset = (reward_asset_id, transferred).into();
+		AssetTransactor::withdraw_asset(&transferred, &rewards_location, Some(&context))
+			.map_err(|_| DispatchError::Other("Failed to withdraw reward"))?;
+		for fee in delivery_fees.inner() {
+			AssetTransactor::withdraw_asset(fee, &rewards_location, Some(&context))
+				.map_err(|_| DispatchError::Other("Failed to withdraw delivery fee"))?;
+		}
+		FeeManager::handle_fee(delivery_fees, Some(&context), FeeReason::ChargeFees);
+
+		// move reward to the destination
+		match transfer_type {
+			TransferType::Teleport => {
+				AssetTransactor::can_check_out(&destination, &transferred, &context)
+					.map_err(|_| DispatchError::Other("Failed to teleport reward"))?;
+				AssetTransactor::check_out(&destination, &transferred, &context);
+			},
+			_ => {
+				AssetTransactor::deposit_asset(&transferred, &destination, Some(&context))
+					.map_err(|_| DispatchError::Other("Failed to deposit reward to reserve"))?;
+			},
+		}
+
+		Router::deliver(ticket)
+			.map_err(|_| DispatchError::Other("Failed to send reward transfer message"))?;
+
+		log::trace!(
+			target: pallet_bridge_relayers::LOG_TARGET,
+			"Paid {:?} reward of relayer {:?} to {:?} at {:?} (delivery fee: {:?}, message: {:?})",
+			transferred,
+			relayer,
+			beneficiary,
+			destination,
+			delivery_fee,
+			message_id,
+		);
+
+		Ok(())
+	}
+}
+
+/// Build the message that transfers reward to the beneficiary at the destination chain.
+fn reward_transfer_message(
+	transfer_type: &TransferType,
+	reward_at_destination: Asset,
+	beneficiary: Location,
+	topic: XcmHash,
+) -> Result<Xcm<()>, DispatchError> {
+	let first_instruction = match transfer_type {
+		TransferType::Teleport => ReceiveTeleportedAsset(reward_at_destination.clone().into()),
+		TransferType::LocalReserve => ReserveAssetDeposited(reward_at_destination.clone().into()),
+		_ => return Err(DispatchError::Other("Unsupported reward transfer type")),
+	};
+	Ok(Xcm(vec![
+		first_instruction,
+		ClearOrigin,
+		BuyExecution { fees: reward_at_destination, weight_limit: Unlimited },
+		DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+		SetTopic(topic),
+	]))
+}
+
+/// Return amount of delivery fees, paid in the reward asset.
+fn delivery_fee_amount(
+	reward_asset_id: &AssetId,
+	delivery_fees: &Assets,
+) -> Result<u128, DispatchError> {
+	match delivery_fees.inner() {
+		[] => Ok(0),
+		[Asset { id, fun: Fungible(amount) }] if id == reward_asset_id => Ok(*amount),
+		_ => Err(DispatchError::Other("Delivery fees must be paid in the reward asset")),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use bp_relayers::RewardsAccountOwner;
+	use frame_support::{parameter_types, traits::fungible::Inspect};
+	use std::cell::RefCell;
+	use xcm_executor::AssetsInHolding;
+
+	const DELIVERY_FEE: u128 = 10;
+
+	thread_local! {
+		static TRANSACTED: RefCell<Vec<(&'static str, Asset, Location)>> = RefCell::new(vec![]);
+		static SENT: RefCell<Vec<(Location, Xcm<()>)>> = RefCell::new(vec![]);
+		static HANDLED_FEES: RefCell<Vec<Assets>> = RefCell::new(vec![]);
+	}
+
+	parameter_types! {
+		pub TestUniversalLocation: InteriorLocation =
+			[GlobalConsensus(NetworkId::Westend), Parachain(1002)].into();
+		pub TestRewardAsset: AssetId = AssetId(Location::parent());
+		pub TestDestination: Location = Location::new(1, [Parachain(1000)]);
+		pub const Teleport: TransferType = TransferType::Teleport;
+		pub const LocalReserve: TransferType = TransferType::LocalReserve;
+		pub const DestinationReserve: TransferType = TransferType::DestinationReserve;
+	}
+
+	struct TestTransactor;
+
+	impl TransactAsset for TestTransactor {
+		fn can_check_out(_dest: &Location, _what: &Asset, _context: &XcmContext) -> XcmResult {
+			Ok(())
+		}
+
+		fn check_out(dest: &Location, what: &Asset, _context: &XcmContext) {
+			TRANSACTED.with(|t| t.borrow_mut().push(("check_out", what.clone(), dest.clone())));
+		}
+
+		fn deposit_asset(what: &Asset, who: &Location, _context: Option<&XcmContext>) -> XcmResult {
+			TRANSACTED.with(|t| t.borrow_mut().push(("deposit", what.clone(), who.clone())));
+			Ok(())
+		}
+
+		fn withdraw_asset(
+			what: &Asset,
+			who: &Location,
+			_maybe_context: Option<&XcmContext>,
+		) -> Result<AssetsInHolding, XcmError> {
+			TRANSACTED.with(|t| t.borrow_mut().push(("withdraw", what.clone(), who.clone())));
+			Ok(what.clone().into())
+		}
+	}
+
+	struct TestRouter;
+
+	impl SendXcm for TestRouter {
+		type Ticket = (Location, Xcm<()>);
+
+		fn validate(
+			dest: &mut Option<Location>,
+			msg: &mut Option<Xcm<()>>,
+		) -> SendResult<Self::Ticket> {
+			let fee: Asset = (Parent, DELIVERY_FEE).into();
+			Ok(((dest.take().unwrap(), msg.take().unwrap()), fee.into()))
+		}
+
+		fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+			SENT.with(|s| s.borrow_mut().push(ticket));
+			Ok([0; 32])
+		}
+	}
+
+	struct TestFeeManager;
+
+	impl XcmFeeManager for TestFeeManager {
+		fn is_waived(_: Option<&Location>, _: FeeReason) -> bool {
+			false
+		}
+
+		fn handle_fee(fees: Assets, _: Option<&XcmContext>, _: FeeReason) {
+			HANDLED_FEES.with(|f| f.borrow_mut().push(fees));
+		}
+	}
+
+	struct AccountIndexToLocation;
+
+	impl<'a> TryConvert<&'a ThisChainAccountId, Location> for AccountIndexToLocation {
+		fn try_convert(who: &'a ThisChainAccountId) -> Result<Location, &'a ThisChainAccountId> {
+			Ok(Location::new(0, [AccountIndex64 { network: None, index: *who }]))
+		}
+	}
+
+	type PayRewardWith<Transfer> = PayRewardFromAccountOrOverXcm<
+		Balances,
+		ThisChainAccountId,
+		TestLaneIdType,
+		TestTransactor,
+		TestRouter,
+		TestFeeManager,
+		TestUniversalLocation,
+		AccountIndexToLocation,
+		TestRewardAsset,
+		TestDestination,
+		Transfer,
+	>;
+
+	fn rewards_account_params() -> RewardsAccountParams<TestLaneIdType> {
+		RewardsAccountParams::new(test_lane_id(), *b"test", RewardsAccountOwner::ThisChain)
+	}
+
+	fn rewards_account_location() -> Location {
+		let rewards_account = TestPaymentProcedure::rewards_account(rewards_account_params());
+		Location::new(0, [AccountIndex64 { network: None, index: rewards_account }])
+	}
+
+	fn remote_beneficiary() -> Location {
+		Location::new(0, [AccountId32 { network: None, id: [42; 32] }])
+	}
+
+	fn expected_message(first_instruction: Instruction<()>, topic: XcmHash) -> Xcm<()> {
+		let reward: Asset = (Parent, 100 - DELIVERY_FEE).into();
+		Xcm(vec![
+			first_instruction,
+			ClearOrigin,
+			BuyExecution { fees: reward, weight_limit: Unlimited },
+			DepositAsset { assets: Wild(AllCounted(1)), beneficiary: remote_beneficiary() },
+			SetTopic(topic),
+		])
+	}
+
+	fn sent_topics() -> Vec<XcmHash> {
+		SENT.with(|s| {
+			s.borrow()
+				.iter()
+				.filter_map(|(_, message)| match message.0.last() {
+					Some(SetTopic(topic)) => Some(*topic),
+					_ => None,
+				})
+				.collect()
+		})
+	}
+
+	#[test]
+	fn pays_reward_to_local_account() {
+		run_test(|| {
+			let rewards_account = TestPaymentProcedure::rewards_account(rewards_account_params());
+			Balances::mint_into(&rewards_account, 1000 + ExistentialDeposit::get()).unwrap();
+
+			PayRewardWith::<Teleport>::pay_reward(
+				&1,
+				rewards_account_params(),
+				1000,
+				RewardBeneficiary::Local(2),
+			)
+			.unwrap();
+
+			assert_eq!(Balances::balance(&2), 1000);
+			assert!(SENT.with(|s| s.borrow().is_empty()));
+		});
+	}
+
+	#[test]
+	fn pays_reward_to_remote_location_using_teleport() {
+		run_test(|| {
+			PayRewardWith::<Teleport>::pay_reward(
+				&1,
+				rewards_account_params(),
+				100,
+				RewardBeneficiary::Remote(remote_beneficiary().into()),
+			)
+			.unwrap();
+
+			// delivery fee is deducted from the reward
+			let reward: Asset = (Parent, 100 - DELIVERY_FEE).into();
+			let fee: Asset = (Parent, DELIVERY_FEE).into();
+			assert_eq!(
+				TRANSACTED.with(|t| t.borrow().clone()),
+				vec![
+					("withdraw", reward.clone(), rewards_account_location()),
+					("withdraw", fee.clone(), rewards_account_location()),
+					("check_out", reward.clone(), TestDestination::get()),
+				],
+			);
+			// withdrawn delivery fee is passed to the fee manager
+			assert_eq!(HANDLED_FEES.with(|f| f.borrow().clone()), vec![fee.into()]);
+			let topic = sent_topics()[0];
+			assert_ne!(topic, [0; 32]);
+			assert_eq!(
+				SENT.with(|s| s.borrow().clone()),
+				vec![(
+					TestDestination::get(),
+					expected_message(ReceiveTeleportedAsset(reward.into()), topic)
+				)],
+			);
+		});
+	}
+
+	#[test]
+	fn pays_reward_to_remote_location_using_reserve_transfer() {
+		run_test(|| {
+			PayRewardWith::<LocalReserve>::pay_reward(
+				&1,
+				rewards_account_params(),
+				100,
+				RewardBeneficiary::Remote(remote_beneficiary().into()),
+			)
+			.unwrap();
+
+			let reward: Asset = (Parent, 100 - DELIVERY_FEE).into();
+			assert_eq!(
+				TRANSACTED.with(|t| t.borrow().last().cloned()),
+				Some(("deposit", reward.clone(), TestDestination::get())),
+			);
+			let topic = sent_topics()[0];
+			assert_eq!(
+				SENT.with(|s| s.borrow().clone()),
+				vec![(
+					TestDestination::get(),
+					expected_message(ReserveAssetDeposited(reward.into()), topic)
+				)],
+			);
+		});
+	}
+
+	#[test]
+	fn fails_to_pay_reward_using_unsupported_transfer_type() {
+		run_test(|| {
+			assert!(PayRewardWith::<DestinationReserve>::pay_reward(
+				&1,
+				rewards_account_params(),
+				100,
+				RewardBeneficiary::Remote(remote_beneficiary().into()),
+			)
+			.is_err());
+
+			assert!(TRANSACTED.with(|t| t.borrow().is_empty()));
+			assert!(SENT.with(|s| s.borrow().is_empty()));
+		});
+	}
+
+	#[test]
+	fn reward_transfer_messages_have_unique_topics() {
+		run_test(|| {
+			for _ in 0..2 {
+				PayRewardWith::<Teleport>::pay_reward(
+					&1,
+					rewards_account_params(),
+					100,
+					RewardBeneficiary::Remote(remote_beneficiary().into()),
+				)
+				.unwrap();
+			}
+
+			let topics = sent_topics();
+			assert_eq!(topics.len(), 2);
+			assert_ne!(topics[0], topics[1]);
+		});
+	}
+
+	#[test]
+	fn fails_to_pay_reward_if_it_does_not_cover_delivery_fees() {
+		run_test(|| {
+			assert!(PayRewardWith::<Teleport>::pay_reward(
+				&1,
+				rewards_account_params(),
+				DELIVERY_FEE as _,
+				RewardBeneficiary::Remote(remote_beneficiary().into()),
+			)
+			.is_err());
+
+			assert!(TRANSACTED.with(|t| t.borrow().is_empty()));
+			assert!(SENT.with(|s| s.borrow().is_empty()));
+			assert!(HANDLED_FEES.with(|f| f.borrow().is_empty()));
+		});
+	}
+}
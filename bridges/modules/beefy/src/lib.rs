@@ -28,6 +28,10 @@
 //!
 //! Given the header hash, other pallets are able to verify header-based proofs
 //! (e.g. storage proofs, transaction inclusion proofs, etc.).
+//!
+//! If the bridged chain is a relay chain, which commits to parachain heads in the MMR leaf
+//! extra data, the [`BeefyRelayHeaders`] may be used as the relay headers source of the
+//! `pallet-bridge-parachains`.
 
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -37,9 +41,16 @@ use sp_std::{boxed::Box, prelude::*};
 
 // Re-export in crate namespace for `construct_runtime!`
 pub use pallet::*;
+pub use parachains::{
+	BeefyRelayHeaders, BridgedParaHeadsProof, ParaHeadProof, ParaHeadsProof, VerifiedParaHeads,
+};
+pub use weights::WeightInfo;
 
+mod parachains;
 mod utils;
 
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -90,7 +101,7 @@ pub type ImportedCommitment<T, I> = bp_beefy::ImportedCommitment<
 pub struct ImportedCommitmentsInfoData<BlockNumber> {
 	/// Best known block number, provided in a BEEFY commitment. However this is not
 	/// the best proven block. The best proven block is this block's parent.
-	best_block_number: BlockNumber,
+	pub best_block_number: BlockNumber,
 	/// The head of the `ImportedBlockNumbers` ring buffer.
 	next_block_number_index: u32,
 }
@@ -123,6 +134,9 @@ pub mod pallet {
 
 		/// The chain we are bridging to here.
 		type BridgedChain: ChainWithBeefy;
+
+		/// Weight information for the parachain heads proofs verification.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
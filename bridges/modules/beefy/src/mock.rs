@@ -27,7 +27,8 @@ use codec::Encode;
 use frame_support::{construct_runtime, derive_impl, weights::Weight};
 use sp_core::{sr25519::Signature, Pair};
 use sp_runtime::{
-	testing::{Header, H256},
+	generic,
+	testing::H256,
 	traits::{BlakeTwo256, Hash},
 	StateVersion,
 };
@@ -37,9 +38,9 @@ use sp_core::crypto::Wraps;
 use sp_runtime::traits::Keccak256;
 
 pub type TestAccountId = u64;
-pub type TestBridgedBlockNumber = u64;
+pub type TestBridgedBlockNumber = u32;
 pub type TestBridgedBlockHash = H256;
-pub type TestBridgedHeader = Header;
+pub type TestBridgedHeader = generic::Header<TestBridgedBlockNumber, BlakeTwo256>;
 pub type TestBridgedAuthoritySetInfo = BridgedBeefyAuthoritySetInfo<TestRuntime, ()>;
 pub type TestBridgedValidatorSet = BridgedBeefyAuthoritySet<TestRuntime, ()>;
 pub type TestBridgedCommitment = BridgedBeefySignedCommitment<TestRuntime, ()>;
@@ -76,6 +77,7 @@ impl beefy::Config for TestRuntime {
 	type MaxRequests = frame_support::traits::ConstU32<16>;
 	type BridgedChain = TestBridgedChain;
 	type CommitmentsToKeep = frame_support::traits::ConstU32<16>;
+	type WeightInfo = ();
 }
 
 #[derive(Debug)]
@@ -87,7 +89,7 @@ impl Chain for TestBridgedChain {
 	type BlockNumber = TestBridgedBlockNumber;
 	type Hash = H256;
 	type Hasher = BlakeTwo256;
-	type Header = sp_runtime::testing::Header;
+	type Header = TestBridgedHeader;
 
 	type AccountId = TestAccountId;
 	type Balance = u64;
@@ -108,7 +110,7 @@ impl ChainWithBeefy for TestBridgedChain {
 	type CommitmentHasher = Keccak256;
 	type MmrHashing = Keccak256;
 	type MmrHash = <Keccak256 as Hash>::Output;
-	type BeefyMmrLeafExtra = ();
+	type BeefyMmrLeafExtra = H256;
 	type AuthorityId = BeefyId;
 	type AuthorityIdToMerkleLeaf = pallet_beefy_mmr::BeefyEcdsaToEthereum;
 }
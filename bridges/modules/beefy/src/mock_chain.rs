@@ -73,6 +73,7 @@ pub struct ChainBuilder {
 	headers: Vec<HeaderAndCommitment>,
 	validator_set_id: ValidatorSetId,
 	validator_keys: Vec<BeefyPair>,
+	para_heads_root: TestBridgedMmrHash,
 	mmr: mmr_lib::MMR<TestBridgedMmrNode, BridgedMmrHashMerge, BridgedMmrStorage>,
 }
 
@@ -100,12 +101,13 @@ impl ChainBuilder {
 			headers: Vec::new(),
 			validator_set_id: 0,
 			validator_keys: validator_pairs(0, initial_validators_count),
+			para_heads_root: Default::default(),
 			mmr: mmr_lib::MMR::new(0, BridgedMmrStorage { nodes: HashMap::new() }),
 		}
 	}
 
 	/// Get header with given number.
-	pub fn header(&self, number: TestBridgedBlockNumber) -> HeaderAndCommitment {
+	pub fn header(&self, number: u64) -> HeaderAndCommitment {
 		self.headers[number as usize - 1].clone()
 	}
 
@@ -120,6 +122,14 @@ impl ChainBuilder {
 		self.headers.clone()
 	}
 
+	/// Sets parachain heads, committed to by MMR leafs of all next headers.
+	pub fn set_para_heads(mut self, para_heads: &[(u32, Vec<u8>)]) -> Self {
+		self.para_heads_root = bp_beefy::merkle_root::<TestBridgedMmrHashing, _>(
+			para_heads.iter().map(|para_head| para_head.encode()),
+		);
+		self
+	}
+
 	/// Appends header, that has been finalized by BEEFY (so it has a linked signed commitment).
 	pub fn append_finalized_header(self) -> Self {
 		let next_validator_set_id = self.validator_set_id;
@@ -196,7 +206,7 @@ impl HeaderBuilder {
 				len: next_validators.len() as u32,
 				keyset_commitment: next_validators_mmr_root,
 			},
-			leaf_extra: (),
+			leaf_extra: chain.para_heads_root,
 		};
 
 		HeaderBuilder {
@@ -224,8 +234,8 @@ impl HeaderBuilder {
 
 		let proof = self.chain.mmr.gen_proof(vec![leaf_position]).unwrap();
 		// genesis has no leaf => leaf index is header number minus 1
-		let leaf_index = *self.header.number() - 1;
-		let leaf_count = *self.header.number();
+		let leaf_index = *self.header.number() as u64 - 1;
+		let leaf_count = *self.header.number() as u64;
 		self.leaf_proof = Some(f(TestBridgedMmrProof {
 			leaf_indices: vec![leaf_index],
 			leaf_count,
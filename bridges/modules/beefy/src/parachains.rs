@@ -0,0 +1,386 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parachain heads, finalized by BEEFY.
+//!
+//! Every MMR leaf of the relay chain contains the merkle root of all parachain heads at the
+//! leaf parent block (see `leaf_extra` field of the leaf). So once the MMR root is imported by
+//! the pallet, we may prove the parachain heads at any of its ancestors.
+
+use crate::{
+	utils, weights::WeightInfo, BridgedBeefyMmrLeaf, BridgedBeefyMmrLeafExtra, BridgedBlockHash,
+	BridgedBlockNumber, BridgedChain, BridgedMmrHash, BridgedMmrHashing, Config,
+	ImportedCommitments, LOG_TARGET,
+};
+
+use bp_beefy::{verify_merkle_proof, MmrProof};
+use bp_header_chain::HeaderChainError;
+use bp_parachains::{
+	ParaHeadsProofError, ParaHeadsReader, RelayBlockHash, RelayBlockNumber, RelayHeadersSource,
+};
+use bp_polkadot_core::parachains::{ParaHead, ParaId};
+use bp_runtime::{Chain, HeaderId, Size, StorageProofError};
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::{RuntimeDebug, SaturatedConversion};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Proof of single parachain head, committed to by the MMR leaf.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaHeadProof<MmrHash> {
+	/// Identifier of the parachain.
+	pub para_id: ParaId,
+	/// Head of the parachain.
+	pub para_head: ParaHead,
+	/// Merkle proof of the `(para_id, para_head)` leaf.
+	pub proof: Vec<MmrHash>,
+	/// Number of leaves in the parachain heads merkle tree.
+	pub number_of_leaves: u32,
+	/// Index of the `(para_id, para_head)` leaf in the parachain heads merkle tree.
+	pub leaf_index: u32,
+}
+
+/// Proof of parachain heads at some relay chain block, finalized by BEEFY.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaHeadsProof<BlockNumber, MmrLeaf, MmrHash> {
+	/// Number of the block with imported commitment, which MMR root is used to verify the leaf.
+	pub commitment_block_number: BlockNumber,
+	/// MMR leaf, which parent is the relay chain block we're proving parachain heads at.
+	pub mmr_leaf: MmrLeaf,
+	/// Proof of the `mmr_leaf`.
+	pub mmr_proof: MmrProof<MmrHash>,
+	/// Proofs of parachain heads.
+	pub para_heads: Vec<ParaHeadProof<MmrHash>>,
+}
+
+impl<BlockNumber: Encode, MmrLeaf: Encode, MmrHash: Encode> Size
+	for ParaHeadsProof<BlockNumber, MmrLeaf, MmrHash>
+{
+	fn size(&self) -> u32 {
+		self.encoded_size().saturated_into()
+	}
+}
+
+/// Parachain heads proof, accepted by the given pallet.
+pub type BridgedParaHeadsProof<T, I> =
+	ParaHeadsProof<BridgedBlockNumber<T, I>, BridgedBeefyMmrLeaf<T, I>, BridgedMmrHash<T, I>>;
+
+/// Verified parachain heads.
+pub struct VerifiedParaHeads {
+	heads: Vec<(ParaId, ParaHead)>,
+}
+
+impl ParaHeadsReader for VerifiedParaHeads {
+	fn read_parachain_head(
+		&mut self,
+		parachain: ParaId,
+	) -> Result<Option<ParaHead>, StorageProofError> {
+		// we can't prove that the parachain has no head, so missing head is an error
+		let position = self
+			.heads
+			.iter()
+			.position(|(para_id, _)| *para_id == parachain)
+			.ok_or(StorageProofError::UnavailableKey)?;
+		Ok(Some(self.heads.swap_remove(position).1))
+	}
+
+	fn ensure_no_unused_heads(self) -> Result<(), StorageProofError> {
+		if !self.heads.is_empty() {
+			return Err(StorageProofError::UnusedKey)
+		}
+
+		Ok(())
+	}
+}
+
+/// Relay headers source, backed by the bridge BEEFY pallet.
+///
+/// Parachain heads are proved by the MMR leaf of the relay chain block child, which is verified
+/// against the MMR root of some imported commitment. The leaf extra data must be the merkle root
+/// of parachain heads.
+pub struct BeefyRelayHeaders<T, I>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> RelayHeadersSource for BeefyRelayHeaders<T, I>
+where
+	BridgedChain<T, I>: Chain<BlockNumber = RelayBlockNumber, Hash = RelayBlockHash>,
+	BridgedBeefyMmrLeafExtra<T, I>: Into<BridgedMmrHash<T, I>>,
+{
+	type ParaHeadsProof = BridgedParaHeadsProof<T, I>;
+	type ParaHeadsReader = VerifiedParaHeads;
+
+	fn is_known_relay_block(at: HeaderId<RelayBlockHash, RelayBlockNumber>) -> bool {
+		// we don't store hashes of all finalized blocks, but we know the parent of every block
+		// with imported commitment. Proofs at other ancestors are still accepted by the
+		// `verify_para_heads_proof`, but they are not considered known before verification
+		at.0.checked_add(1)
+			.and_then(ImportedCommitments::<T, I>::get)
+			.map(|commitment| commitment.parent_number_and_hash == (at.0, at.1))
+			.unwrap_or(false)
+	}
+
+	fn verify_para_heads_proof(
+		at: HeaderId<RelayBlockHash, RelayBlockNumber>,
+		proof: BridgedParaHeadsProof<T, I>,
+	) -> Result<VerifiedParaHeads, ParaHeadsProofError> {
+		let invalid_proof = || {
+			ParaHeadsProofError::HeaderChain(HeaderChainError::StorageProof(
+				StorageProofError::InvalidProof,
+			))
+		};
+
+		// verify the MMR leaf
+		let commitment = ImportedCommitments::<T, I>::get(proof.commitment_block_number)
+			.ok_or(ParaHeadsProofError::UnknownRelayChainBlock)?;
+		utils::verify_beefy_mmr_leaf::<T, I>(&proof.mmr_leaf, proof.mmr_proof, commitment.mmr_root)
+			.map_err(|_| invalid_proof())?;
+
+		// the leaf must be built on top of the relay block
+		let (parent_number, parent_hash): (BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>) =
+			proof.mmr_leaf.parent_number_and_hash;
+		if parent_hash != at.1 {
+			return Err(ParaHeadsProofError::UnknownRelayChainBlock)
+		}
+		if parent_number != at.0 {
+			return Err(ParaHeadsProofError::InvalidRelayChainBlockNumber)
+		}
+
+		// verify parachain heads against the merkle root from the leaf
+		let para_heads_root: BridgedMmrHash<T, I> = proof.mmr_leaf.leaf_extra.into();
+		let mut heads = Vec::with_capacity(proof.para_heads.len());
+		for para_head in proof.para_heads {
+			if heads.iter().any(|(para_id, _)| *para_id == para_head.para_id) {
+				return Err(ParaHeadsProofError::HeaderChain(HeaderChainError::StorageProof(
+					StorageProofError::DuplicateNodes,
+				)))
+			}
+
+			let leaf = (para_head.para_id.0, &para_head.para_head.0).encode();
+			let is_valid = verify_merkle_proof::<BridgedMmrHashing<T, I>, _, _>(
+				&para_heads_root,
+				para_head.proof,
+				para_head.number_of_leaves,
+				para_head.leaf_index,
+				&leaf,
+			);
+			if !is_valid {
+				log::trace!(
+					target: LOG_TARGET,
+					"Head of parachain {:?} is not committed to by the MMR leaf of relay block {:?}",
+					para_head.para_id,
+					at,
+				);
+				return Err(invalid_proof())
+			}
+
+			heads.push((para_head.para_id, para_head.para_head));
+		}
+
+		Ok(VerifiedParaHeads { heads })
+	}
+
+	fn verify_para_heads_proof_weight(proof: &BridgedParaHeadsProof<T, I>) -> Option<Weight> {
+		let para_head_proof_items = proof
+			.para_heads
+			.iter()
+			.fold(0u32, |items, head| items.saturating_add(head.proof.len().saturated_into()));
+		Some(T::WeightInfo::verify_para_heads_proof(
+			proof.mmr_proof.items.len().saturated_into(),
+			proof.para_heads.len().saturated_into(),
+			para_head_proof_items,
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, mock_chain::*};
+	use sp_runtime::traits::Keccak256;
+
+	type Source = BeefyRelayHeaders<TestRuntime, ()>;
+
+	fn para_heads() -> Vec<(u32, Vec<u8>)> {
+		vec![(1000, vec![1; 32]), (2000, vec![2; 64]), (3000, vec![3; 16])]
+	}
+
+	fn para_head_proof(index: u32) -> ParaHeadProof<TestBridgedMmrHash> {
+		let para_heads = para_heads();
+		let proof = binary_merkle_tree::merkle_proof::<Keccak256, _, _>(
+			para_heads.iter().map(|para_head| para_head.encode()),
+			index,
+		);
+		let (para_id, para_head) = para_heads[index as usize].clone();
+		ParaHeadProof {
+			para_id: ParaId(para_id),
+			para_head: ParaHead(para_head),
+			proof: proof.proof,
+			number_of_leaves: proof.number_of_leaves,
+			leaf_index: proof.leaf_index,
+		}
+	}
+
+	/// Imports commitment for header#2 and returns proof of parachain heads at header#1.
+	fn import_and_prepare_proof(
+		para_heads: Vec<ParaHeadProof<TestBridgedMmrHash>>,
+	) -> (HeaderId<RelayBlockHash, RelayBlockNumber>, BridgedParaHeadsProof<TestRuntime, ()>) {
+		let chain = ChainBuilder::new(8)
+			.set_para_heads(&self::para_heads())
+			.append_finalized_headers(2);
+		import_commitment(chain.header(2)).expect("commitment is valid");
+
+		let at = HeaderId(1, chain.header(1).header.hash());
+		let header = chain.header(2);
+		(
+			at,
+			ParaHeadsProof {
+				commitment_block_number: 2,
+				mmr_leaf: header.leaf,
+				mmr_proof: header.leaf_proof,
+				para_heads,
+			},
+		)
+	}
+
+	#[test]
+	fn accepts_valid_para_heads_proof() {
+		run_test_with_initialize(8, || {
+			let (at, proof) =
+				import_and_prepare_proof(vec![para_head_proof(0), para_head_proof(2)]);
+			let mut heads = Source::verify_para_heads_proof(at, proof).unwrap();
+
+			assert_eq!(
+				heads.read_parachain_head(ParaId(2000)),
+				Err(StorageProofError::UnavailableKey)
+			);
+			assert_eq!(heads.read_parachain_head(ParaId(3000)), Ok(Some(ParaHead(vec![3; 16]))));
+			assert_eq!(heads.read_parachain_head(ParaId(1000)), Ok(Some(ParaHead(vec![1; 32]))));
+			assert_eq!(heads.ensure_no_unused_heads(), Ok(()));
+		});
+	}
+
+	#[test]
+	fn rejects_proof_with_unused_heads() {
+		run_test_with_initialize(8, || {
+			let (at, proof) =
+				import_and_prepare_proof(vec![para_head_proof(0), para_head_proof(1)]);
+			let mut heads = Source::verify_para_heads_proof(at, proof).unwrap();
+
+			assert_eq!(heads.read_parachain_head(ParaId(1000)), Ok(Some(ParaHead(vec![1; 32]))));
+			assert_eq!(heads.ensure_no_unused_heads(), Err(StorageProofError::UnusedKey));
+		});
+	}
+
+	#[test]
+	fn rejects_proof_at_unknown_commitment() {
+		run_test_with_initialize(8, || {
+			let (at, mut proof) = import_and_prepare_proof(vec![para_head_proof(0)]);
+			proof.commitment_block_number = 3;
+
+			assert_eq!(
+				Source::verify_para_heads_proof(at, proof).map(drop),
+				Err(ParaHeadsProofError::UnknownRelayChainBlock),
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_proof_with_invalid_mmr_leaf() {
+		run_test_with_initialize(8, || {
+			let (at, mut proof) = import_and_prepare_proof(vec![para_head_proof(0)]);
+			proof.mmr_leaf.leaf_extra = Default::default();
+
+			assert_eq!(
+				Source::verify_para_heads_proof(at, proof).map(drop),
+				Err(ParaHeadsProofError::HeaderChain(HeaderChainError::StorageProof(
+					StorageProofError::InvalidProof
+				))),
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_proof_at_other_relay_block() {
+		run_test_with_initialize(8, || {
+			let (at, proof) = import_and_prepare_proof(vec![para_head_proof(0)]);
+			assert_eq!(
+				Source::verify_para_heads_proof(HeaderId(at.0, Default::default()), proof.clone())
+					.map(drop),
+				Err(ParaHeadsProofError::UnknownRelayChainBlock),
+			);
+			assert_eq!(
+				Source::verify_para_heads_proof(HeaderId(at.0 + 1, at.1), proof).map(drop),
+				Err(ParaHeadsProofError::InvalidRelayChainBlockNumber),
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_invalid_para_head_proof() {
+		run_test_with_initialize(8, || {
+			let mut para_head = para_head_proof(1);
+			para_head.para_head = ParaHead(vec![42; 64]);
+			let (at, proof) = import_and_prepare_proof(vec![para_head_proof(0), para_head]);
+
+			assert_eq!(
+				Source::verify_para_heads_proof(at, proof).map(drop),
+				Err(ParaHeadsProofError::HeaderChain(HeaderChainError::StorageProof(
+					StorageProofError::InvalidProof
+				))),
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_duplicate_para_heads() {
+		run_test_with_initialize(8, || {
+			let (at, proof) =
+				import_and_prepare_proof(vec![para_head_proof(0), para_head_proof(0)]);
+
+			assert_eq!(
+				Source::verify_para_heads_proof(at, proof).map(drop),
+				Err(ParaHeadsProofError::HeaderChain(HeaderChainError::StorageProof(
+					StorageProofError::DuplicateNodes
+				))),
+			);
+		});
+	}
+
+	#[test]
+	fn only_parents_of_imported_commitments_are_known() {
+		run_test_with_initialize(8, || {
+			let (at, _) = import_and_prepare_proof(vec![]);
+
+			assert!(Source::is_known_relay_block(at));
+			assert!(!Source::is_known_relay_block(HeaderId(at.0, Default::default())));
+			assert!(!Source::is_known_relay_block(HeaderId(0, Default::default())));
+			assert!(!Source::is_known_relay_block(HeaderId(2, Default::default())));
+		});
+	}
+
+	#[test]
+	fn para_heads_proof_weight_depends_on_proof() {
+		run_test_with_initialize(8, || {
+			let (_, proof) = import_and_prepare_proof(vec![para_head_proof(0)]);
+			let mut larger_proof = proof.clone();
+			larger_proof.para_heads.push(para_head_proof(1));
+
+			let weight = Source::verify_para_heads_proof_weight(&proof).unwrap();
+			let larger_weight = Source::verify_para_heads_proof_weight(&larger_proof).unwrap();
+			assert!(larger_weight.any_gt(weight));
+		});
+	}
+}
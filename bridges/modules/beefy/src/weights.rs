@@ -0,0 +1,56 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for the BEEFY bridge pallet.
+//!
+//! The pallet has no benchmarks yet (its calls are declared in the dev mode), so the weights of
+//! the `()` implementation are conservative estimates. They must be replaced with benchmarked
+//! weights before the pallet is used in production.
+
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+/// Weight functions needed for the BEEFY bridge pallet.
+pub trait WeightInfo {
+	/// Weight of the parachain heads proof verification (see
+	/// [`crate::BeefyRelayHeaders`]), given the number of MMR proof items, the number of proved
+	/// parachain heads and the total number of items in their merkle proofs.
+	fn verify_para_heads_proof(
+		mmr_proof_items: u32,
+		para_heads: u32,
+		para_head_proof_items: u32,
+	) -> Weight;
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: BridgeBeefy ImportedCommitments (r:1 w:0)
+	fn verify_para_heads_proof(
+		mmr_proof_items: u32,
+		para_heads: u32,
+		para_head_proof_items: u32,
+	) -> Weight {
+		// every proof item is hashed once, and every parachain head is encoded and hashed once
+		Weight::from_parts(20_000_000, 1_024)
+			.saturating_add(
+				Weight::from_parts(1_000_000, 32).saturating_mul(mmr_proof_items.into()),
+			)
+			.saturating_add(Weight::from_parts(2_000_000, 1_024).saturating_mul(para_heads.into()))
+			.saturating_add(
+				Weight::from_parts(1_000_000, 32).saturating_mul(para_head_proof_items.into()),
+			)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
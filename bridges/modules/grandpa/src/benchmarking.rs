@@ -48,10 +48,15 @@ use bp_test_utils::{
 	accounts, make_justification_for_header, JustificationGeneratorParams, TEST_GRANDPA_ROUND,
 	TEST_GRANDPA_SET_ID,
 };
+use codec::Encode;
 use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
-use sp_consensus_grandpa::AuthorityId;
-use sp_runtime::traits::{One, Zero};
+use sp_consensus_grandpa::{AuthorityId, ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_runtime::{
+	traits::{One, Zero},
+	DigestItem,
+};
 use sp_std::vec::Vec;
 
 /// The maximum number of vote ancestries to include in a justification.
@@ -79,11 +84,9 @@ fn precommits_range_end<T: Config<I>, I: 'static>() -> u32 {
 	required_justification_precommits(max_bridged_authorities)
 }
 
-/// Prepare header and its justification to submit using `submit_finality_proof`.
-fn prepare_benchmark_data<T: Config<I>, I: 'static>(
-	precommits: u32,
-	ancestors: u32,
-) -> (BridgedHeader<T, I>, GrandpaJustification<BridgedHeader<T, I>>) {
+/// Initialize the bridge with authorities set, where `precommits` authorities are enough to
+/// finalize a header. Returns the list of authorities.
+fn bootstrap_benchmark_bridge<T: Config<I>, I: 'static>(precommits: u32) -> AuthorityList {
 	// going from precommits to total authorities count
 	let total_authorities_count = (3 * precommits - 1) / 2;
 
@@ -96,7 +99,7 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	let genesis_hash = genesis_header.hash();
 	let init_data = InitializationData {
 		header: Box::new(genesis_header),
-		authority_list,
+		authority_list: authority_list.clone(),
 		set_id: TEST_GRANDPA_SET_ID,
 		operating_mode: BasicOperatingMode::Normal,
 	};
@@ -104,6 +107,16 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	bootstrap_bridge::<T, I>(init_data);
 	assert!(<ImportedHeaders<T, I>>::contains_key(genesis_hash));
 
+	authority_list
+}
+
+/// Prepare header and its justification to submit using `submit_finality_proof`.
+fn prepare_benchmark_data<T: Config<I>, I: 'static>(
+	precommits: u32,
+	ancestors: u32,
+) -> (BridgedHeader<T, I>, GrandpaJustification<BridgedHeader<T, I>>) {
+	bootstrap_benchmark_bridge::<T, I>(precommits);
+
 	let header: BridgedHeader<T, I> = bp_test_utils::test_header(One::one());
 	let params = JustificationGeneratorParams {
 		header: header.clone(),
@@ -117,6 +130,46 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	(header, justification)
 }
 
+/// Prepare warp sync proof to submit using `submit_warp_sync_proof`. Every fragment of the proof
+/// is a mandatory header, that enacts the same authorities with the next set id.
+fn prepare_warp_sync_benchmark_data<T: Config<I>, I: 'static>(
+	fragments: u32,
+	precommits: u32,
+	ancestors: u32,
+) -> BoundedVec<WarpSyncFragment<BridgedHeader<T, I>>, T::MaxWarpSyncFragments> {
+	let authority_list = bootstrap_benchmark_bridge::<T, I>(precommits);
+
+	(1..=fragments)
+		.map(|number| {
+			let mut header: BridgedHeader<T, I> = bp_test_utils::test_header(number.into());
+			let consensus_log =
+				ConsensusLog::<BridgedBlockNumber<T, I>>::ScheduledChange(ScheduledChange {
+					next_authorities: authority_list.clone(),
+					delay: Zero::zero(),
+				});
+			header
+				.digest_mut()
+				.push(DigestItem::Consensus(GRANDPA_ENGINE_ID, consensus_log.encode()));
+
+			let params = JustificationGeneratorParams {
+				header: header.clone(),
+				round: TEST_GRANDPA_ROUND,
+				set_id: TEST_GRANDPA_SET_ID + SetId::from(number) - 1,
+				authorities: accounts(precommits as u16)
+					.iter()
+					.map(|k| (*k, 1))
+					.collect::<Vec<_>>(),
+				ancestors,
+				forks: 1,
+			};
+			let justification = make_justification_for_header(params);
+			WarpSyncFragment { header, justification }
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("number of fragments is limited by the `MaxWarpSyncFragments`; qed")
+}
+
 benchmarks_instance_pallet! {
 	// This is the "gold standard" benchmark for this extrinsic, and it's what should be used to
 	// annotate the weight in the pallet.
@@ -139,6 +192,21 @@ benchmarks_instance_pallet! {
 		assert!(!<ImportedHeaders<T, I>>::contains_key(genesis_header.hash()));
 	}
 
+	// Every fragment of the proof is a mandatory header with justification of the same size, so
+	// `p` and `v` are the number of precommits and vote ancestries in every fragment.
+	submit_warp_sync_proof {
+		let f in 1 .. T::MaxWarpSyncFragments::get();
+		let p in 1 .. precommits_range_end::<T, I>();
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let fragments = prepare_warp_sync_benchmark_data::<T, I>(f, p, v);
+	}: submit_warp_sync_proof(RawOrigin::Signed(caller), fragments, TEST_GRANDPA_SET_ID)
+	verify {
+		let header: BridgedHeader<T, I> = bp_test_utils::test_header(f.into());
+		assert_eq!(<BestFinalized<T, I>>::get().unwrap().0, *header.number());
+		assert_eq!(<CurrentAuthoritySet<T, I>>::get().set_id, TEST_GRANDPA_SET_ID + SetId::from(f));
+	}
+
 	force_set_pallet_state {
 		let set_id = 100;
 		let authorities = accounts(T::BridgedChain::MAX_AUTHORITIES_COUNT as u16)
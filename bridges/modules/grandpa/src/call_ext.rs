@@ -20,7 +20,7 @@ use crate::{
 };
 use bp_header_chain::{
 	justification::GrandpaJustification, submit_finality_proof_limits_extras,
-	SubmitFinalityProofInfo,
+	SubmitFinalityProofInfo, WarpSyncFragment,
 };
 use bp_runtime::{BlockNumberOf, Chain, OwnedBridgeModule};
 use frame_support::{
@@ -32,7 +32,7 @@ use sp_consensus_grandpa::SetId;
 use sp_runtime::{
 	traits::{CheckedSub, Header, Zero},
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
-	RuntimeDebug, SaturatedConversion,
+	RuntimeDebug, SaturatedConversion, Saturating,
 };
 use sp_std::fmt::Debug;
 
@@ -190,6 +190,9 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 	IsSubType<CallableCallFor<Pallet<T, I>, T>>
 {
 	/// Extract finality proof info from a runtime call.
+	///
+	/// The `submit_warp_sync_proof` call is described by the aggregated info of all its
+	/// fragments. See [`submit_warp_sync_proof_info_from_args`] for details.
 	fn submit_finality_proof_info(
 		&self,
 	) -> Option<SubmitFinalityProofInfo<BridgedBlockNumber<T, I>>> {
@@ -215,6 +218,12 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 				Some(*current_set_id),
 				*is_free_execution_expected,
 			))
+		} else if let Some(crate::Call::<T, I>::submit_warp_sync_proof {
+			fragments,
+			current_set_id,
+		}) = self.is_sub_type()
+		{
+			return submit_warp_sync_proof_info_from_args::<T, I>(fragments, *current_set_id)
 		}
 
 		None
@@ -297,18 +306,55 @@ pub(crate) fn submit_finality_proof_info_from_args<T: Config<I>, I: 'static>(
 	}
 }
 
+/// Extract finality proof info from the submitted warp sync proof fragments.
+///
+/// The returned info describes the whole call: it brings the header of the last fragment and
+/// the first fragment must be signed by the `current_set_id`. The call is mandatory if all its
+/// fragments are mandatory. Extra weight and size of all fragments are summed up. The call has
+/// no way to ask for the free execution, so the free execution checks of the transaction
+/// extension are not performed. The call still may be free, if every fragment is free.
+///
+/// Returns `None` if there are no fragments.
+pub(crate) fn submit_warp_sync_proof_info_from_args<T: Config<I>, I: 'static>(
+	fragments: &[WarpSyncFragment<BridgedHeader<T, I>>],
+	current_set_id: SetId,
+) -> Option<SubmitFinalityProofInfo<BridgedBlockNumber<T, I>>> {
+	let last_fragment = fragments.last()?;
+	let mut call_info = SubmitFinalityProofInfo {
+		block_number: *last_fragment.header.number(),
+		current_set_id: Some(current_set_id),
+		is_mandatory: true,
+		is_free_execution_expected: false,
+		extra_weight: Weight::zero(),
+		extra_size: 0,
+	};
+	for fragment in fragments {
+		let fragment_info = submit_finality_proof_info_from_args::<T, I>(
+			&fragment.header,
+			&fragment.justification,
+			None,
+			false,
+		);
+		call_info.is_mandatory &= fragment_info.is_mandatory;
+		call_info.extra_weight.saturating_accrue(fragment_info.extra_weight);
+		call_info.extra_size.saturating_accrue(fragment_info.extra_size);
+	}
+
+	Some(call_info)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
 		call_ext::CallSubType,
 		mock::{
-			run_test, test_header, FreeHeadersInterval, RuntimeCall, TestBridgedChain, TestNumber,
-			TestRuntime,
+			run_test, test_header, FreeHeadersInterval, RuntimeCall, TestBridgedChain, TestHeader,
+			TestNumber, TestRuntime,
 		},
 		BestFinalized, Config, CurrentAuthoritySet, FreeHeadersRemaining, PalletOperatingMode,
 		StoredAuthoritySet, WeightInfo,
 	};
-	use bp_header_chain::{ChainWithGrandpa, SubmitFinalityProofInfo};
+	use bp_header_chain::{ChainWithGrandpa, SubmitFinalityProofInfo, WarpSyncFragment};
 	use bp_runtime::{BasicOperatingMode, HeaderId};
 	use bp_test_utils::{
 		make_default_justification, make_justification_for_header, JustificationGeneratorParams,
@@ -604,6 +650,97 @@ mod tests {
 		);
 	}
 
+	fn warp_sync_fragment(number: u64, is_mandatory: bool) -> WarpSyncFragment<TestHeader> {
+		let mut header = test_header(number);
+		if is_mandatory {
+			let consensus_log = sp_consensus_grandpa::ConsensusLog::<TestNumber>::ScheduledChange(
+				sp_consensus_grandpa::ScheduledChange {
+					next_authorities: bp_test_utils::authority_list(),
+					delay: 0,
+				},
+			);
+			header.digest_mut().push(DigestItem::Consensus(
+				sp_consensus_grandpa::GRANDPA_ENGINE_ID,
+				consensus_log.encode(),
+			));
+		}
+		let justification = make_default_justification(&header);
+		WarpSyncFragment { header, justification }
+	}
+
+	fn warp_sync_proof_call(fragments: Vec<WarpSyncFragment<TestHeader>>) -> RuntimeCall {
+		RuntimeCall::Grandpa(crate::Call::<TestRuntime, ()>::submit_warp_sync_proof {
+			fragments: fragments.try_into().unwrap(),
+			current_set_id: 0,
+		})
+	}
+
+	#[test]
+	fn submit_warp_sync_proof_info_is_parsed() {
+		// when the last fragment is a regular header, the call is not mandatory
+		let call = warp_sync_proof_call(vec![
+			warp_sync_fragment(42, true),
+			warp_sync_fragment(43, true),
+			warp_sync_fragment(44, false),
+		]);
+		assert_eq!(
+			call.submit_finality_proof_info(),
+			Some(SubmitFinalityProofInfo {
+				block_number: 44,
+				current_set_id: Some(0),
+				extra_weight: Weight::zero(),
+				extra_size: 0,
+				is_mandatory: false,
+				is_free_execution_expected: false,
+			})
+		);
+
+		// when all fragments are mandatory headers, the call is mandatory
+		let call =
+			warp_sync_proof_call(vec![warp_sync_fragment(42, true), warp_sync_fragment(43, true)]);
+		assert_eq!(
+			call.submit_finality_proof_info(),
+			Some(SubmitFinalityProofInfo {
+				block_number: 43,
+				current_set_id: Some(0),
+				extra_weight: Weight::zero(),
+				extra_size: 0,
+				is_mandatory: true,
+				is_free_execution_expected: false,
+			})
+		);
+
+		// when there are no fragments, the call is not recognized
+		assert_eq!(warp_sync_proof_call(vec![]).submit_finality_proof_info(), None);
+	}
+
+	#[test]
+	fn extension_checks_warp_sync_proof_for_obsolete_headers() {
+		run_test(|| {
+			sync_to_header_10();
+
+			// when the last fragment is not better than the best finalized header => Err
+			let call = warp_sync_proof_call(vec![
+				warp_sync_fragment(9, true),
+				warp_sync_fragment(10, false),
+			]);
+			assert!(RuntimeCall::check_obsolete_submit_finality_proof(&call).is_err());
+
+			// when the last fragment is better than the best finalized header => Ok
+			let call = warp_sync_proof_call(vec![
+				warp_sync_fragment(11, true),
+				warp_sync_fragment(15, false),
+			]);
+			assert_eq!(
+				RuntimeCall::check_obsolete_submit_finality_proof(&call)
+					.unwrap()
+					.unwrap()
+					.improved_by,
+				5,
+			);
+		})
+	}
+
 	#[test]
 	fn extension_returns_correct_extra_size_if_call_arguments_are_too_large() {
 		// when call arguments are below our limit => no refund
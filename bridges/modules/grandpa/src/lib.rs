@@ -40,7 +40,7 @@ pub use storage_types::StoredAuthoritySet;
 use bp_header_chain::{
 	justification::GrandpaJustification, AuthoritySet, ChainWithGrandpa, GrandpaConsensusLogReader,
 	HeaderChain, InitializationData, StoredHeaderData, StoredHeaderDataBuilder,
-	StoredHeaderGrandpaInfo,
+	StoredHeaderGrandpaInfo, WarpSyncFragment,
 };
 use bp_runtime::{BlockNumberOf, HashOf, HasherOf, HeaderId, HeaderOf, OwnedBridgeModule};
 use frame_support::{dispatch::PostDispatchInfo, ensure, DefaultNoBound};
@@ -130,6 +130,15 @@ pub mod pallet {
 		#[pallet::constant]
 		type FreeHeadersInterval: Get<Option<u32>>;
 
+		/// Maximal number of fragments in the warp sync proof, accepted by the
+		/// `submit_warp_sync_proof` call.
+		///
+		/// Every fragment is verified in the same way as the `submit_finality_proof_ex` call
+		/// arguments, so the weight of the warp sync proof submission grows linearly with the
+		/// number of fragments. The call with `MaxWarpSyncFragments` fragments must fit the block.
+		#[pallet::constant]
+		type MaxWarpSyncFragments: Get<u32>;
+
 		/// Maximal number of finalized headers to keep in the storage.
 		///
 		/// The setting is there to prevent growing the on-chain state indefinitely. Note
@@ -290,36 +299,11 @@ pub mod pallet {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
 			ensure_signed(origin)?;
 
-			let (hash, number) = (finality_target.hash(), *finality_target.number());
-			log::trace!(
-				target: LOG_TARGET,
-				"Going to try and finalize header {:?}",
-				finality_target
-			);
-
-			// it checks whether the `number` is better than the current best block number
-			// and whether the `current_set_id` matches the best known set id
-			let improved_by =
-				SubmitFinalityProofHelper::<T, I>::check_obsolete(number, Some(current_set_id))?;
-
-			let authority_set = <CurrentAuthoritySet<T, I>>::get();
-			let unused_proof_size = authority_set.unused_proof_size();
-			let set_id = authority_set.set_id;
-			let authority_set: AuthoritySet = authority_set.into();
-			verify_justification::<T, I>(&justification, hash, number, authority_set)?;
-
-			let maybe_new_authority_set =
-				try_enact_authority_change::<T, I>(&finality_target, set_id)?;
-			let may_refund_call_fee = may_refund_call_fee::<T, I>(
-				&finality_target,
-				&justification,
-				current_set_id,
-				improved_by,
-			);
-			if may_refund_call_fee {
+			let import_result =
+				verify_and_import_header::<T, I>(*finality_target, justification, current_set_id)?;
+			if import_result.may_refund_call_fee {
 				on_free_header_imported::<T, I>();
 			}
-			insert_header::<T, I>(*finality_target, hash);
 
 			// mandatory header is a header that changes authorities set. The pallet can't go
 			// further without importing this header. So every bridge MUST import mandatory headers.
@@ -329,36 +313,9 @@ pub mod pallet {
 			//
 			// If size/weight of the call is exceeds our estimated limits, the relayer still needs
 			// to pay for the transaction.
-			let pays_fee = if may_refund_call_fee { Pays::No } else { Pays::Yes };
-
-			log::info!(
-				target: LOG_TARGET,
-				"Successfully imported finalized header with hash {:?}! Free: {}",
-				hash,
-				if may_refund_call_fee { "Yes" } else { "No" },
-			);
-
-			// the proof size component of the call weight assumes that there are
-			// `MaxBridgedAuthorities` in the `CurrentAuthoritySet` (we use `MaxEncodedLen`
-			// estimation). But if their number is lower, then we may "refund" some `proof_size`,
-			// making proof smaller and leaving block space to other useful transactions
-			let pre_dispatch_weight = T::WeightInfo::submit_finality_proof(
-				justification.commit.precommits.len().saturated_into(),
-				justification.votes_ancestries.len().saturated_into(),
-			);
-			let actual_weight = pre_dispatch_weight
-				.set_proof_size(pre_dispatch_weight.proof_size().saturating_sub(unused_proof_size));
-
-			Self::deposit_event(Event::UpdatedBestFinalizedHeader {
-				number,
-				hash,
-				grandpa_info: StoredHeaderGrandpaInfo {
-					finality_proof: justification,
-					new_verification_context: maybe_new_authority_set,
-				},
-			});
+			let pays_fee = if import_result.may_refund_call_fee { Pays::No } else { Pays::Yes };
 
-			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+			Ok(PostDispatchInfo { actual_weight: Some(import_result.actual_weight), pays_fee })
 		}
 
 		/// Set current authorities set and best finalized bridged header to given values
@@ -396,6 +353,93 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Verify a chain of GRANDPA authorities set changes and import all headers from the
+		/// given warp sync proof. The first fragment must be signed by the authorities set with
+		/// `current_set_id` id. Every next fragment must be signed by the set, enacted by the
+		/// previous fragment.
+		///
+		/// The call may be used to catch up with the bridged chain after a long pause, when
+		/// there are many mandatory headers to import. Every fragment, except the last one, must
+		/// be a mandatory header. The last fragment may also be a regular header.
+		///
+		/// The call fails if:
+		///
+		/// - there are no fragments in the proof;
+		///
+		/// - any fragment would be rejected by the `submit_finality_proof_ex` call;
+		///
+		/// - any fragment, except the last one, doesn't enact new authorities set.
+		///
+		/// The submitter doesn't pay for the call, if every fragment would be imported for free
+		/// by the `submit_finality_proof_ex` call and there are enough free header slots in the
+		/// current block. Otherwise, the whole call is paid and no free slots are spent.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::submit_warp_sync_proof_weight(
+			fragments.iter().map(|fragment| (
+				fragment.justification.commit.precommits.len().saturated_into(),
+				fragment.justification.votes_ancestries.len().saturated_into(),
+			))
+		))]
+		pub fn submit_warp_sync_proof(
+			origin: OriginFor<T>,
+			fragments: BoundedVec<WarpSyncFragment<BridgedHeader<T, I>>, T::MaxWarpSyncFragments>,
+			current_set_id: SetId,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_signed(origin)?;
+			ensure!(!fragments.is_empty(), <Error<T, I>>::InvalidWarpSyncProof);
+
+			// if any fragment is not free, we restore free slots, spent by other fragments
+			let free_headers_remaining = FreeHeadersRemaining::<T, I>::get();
+			let fragments_count = fragments.len();
+			let pre_dispatch_weight =
+				T::WeightInfo::submit_warp_sync_proof_weight(fragments.iter().map(|fragment| {
+					(
+						fragment.justification.commit.precommits.len().saturated_into(),
+						fragment.justification.votes_ancestries.len().saturated_into(),
+					)
+				}));
+			let mut may_refund_call_fee = true;
+			let mut unused_proof_size = u64::MAX;
+			let mut current_set_id = current_set_id;
+			for (index, fragment) in fragments.into_iter().enumerate() {
+				let import_result = verify_and_import_header::<T, I>(
+					fragment.header,
+					fragment.justification,
+					current_set_id,
+				)?;
+
+				let is_last_fragment = index + 1 == fragments_count;
+				ensure!(
+					is_last_fragment || import_result.is_mandatory,
+					<Error<T, I>>::InvalidWarpSyncProof
+				);
+
+				if import_result.may_refund_call_fee {
+					on_free_header_imported::<T, I>();
+				} else {
+					may_refund_call_fee = false;
+				}
+				if import_result.is_mandatory {
+					current_set_id += 1;
+				}
+				unused_proof_size = unused_proof_size.min(import_result.unused_proof_size);
+			}
+
+			if !may_refund_call_fee {
+				FreeHeadersRemaining::<T, I>::set(free_headers_remaining);
+			}
+			let pays_fee = if may_refund_call_fee { Pays::No } else { Pays::Yes };
+
+			// the same proof size "refund" as in the `submit_finality_proof` call. Every fragment
+			// reads the `CurrentAuthoritySet`, but it is included in the storage proof once, so
+			// we may only refund bytes that are unused by the largest set
+			let actual_weight = pre_dispatch_weight
+				.set_proof_size(pre_dispatch_weight.proof_size().saturating_sub(unused_proof_size));
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+		}
 	}
 
 	/// Number of free header submissions that we may yet accept in the current block.
@@ -543,6 +587,89 @@ pub mod pallet {
 		/// The header (and its finality) submission overflows hardcoded chain limits: size
 		/// and/or weight are larger than expected.
 		HeaderOverflowLimits,
+		/// The warp sync proof is empty or one of its fragments, except the last one, doesn't
+		/// enact new authorities set.
+		InvalidWarpSyncProof,
+	}
+
+	/// Result of the `verify_and_import_header` call.
+	struct HeaderImportResult {
+		/// Whether we may refund the header import cost to the submitter.
+		may_refund_call_fee: bool,
+		/// Whether the header has enacted new authorities set.
+		is_mandatory: bool,
+		/// Actual weight of the header import.
+		actual_weight: Weight,
+		/// Unused proof size of the `CurrentAuthoritySet`, read during the header import.
+		unused_proof_size: u64,
+	}
+
+	/// Verify finality proof of the given header and import it to the storage.
+	///
+	/// The `current_set_id` is the id of the authorities set that has signed the justification.
+	/// It must match the id of the current set, known to the pallet.
+	fn verify_and_import_header<T: Config<I>, I: 'static>(
+		finality_target: BridgedHeader<T, I>,
+		justification: GrandpaJustification<BridgedHeader<T, I>>,
+		current_set_id: SetId,
+	) -> Result<HeaderImportResult, DispatchError> {
+		let (hash, number) = (finality_target.hash(), *finality_target.number());
+		log::trace!(target: LOG_TARGET, "Going to try and finalize header {:?}", finality_target);
+
+		// it checks whether the `number` is better than the current best block number
+		// and whether the `current_set_id` matches the best known set id
+		let improved_by =
+			SubmitFinalityProofHelper::<T, I>::check_obsolete(number, Some(current_set_id))?;
+
+		let authority_set = <CurrentAuthoritySet<T, I>>::get();
+		let unused_proof_size = authority_set.unused_proof_size();
+		let set_id = authority_set.set_id;
+		let authority_set: AuthoritySet = authority_set.into();
+		verify_justification::<T, I>(&justification, hash, number, authority_set)?;
+
+		let maybe_new_authority_set = try_enact_authority_change::<T, I>(&finality_target, set_id)?;
+		let may_refund_call_fee = may_refund_call_fee::<T, I>(
+			&finality_target,
+			&justification,
+			current_set_id,
+			improved_by,
+		);
+		insert_header::<T, I>(finality_target, hash);
+
+		log::info!(
+			target: LOG_TARGET,
+			"Successfully imported finalized header with hash {:?}! Free: {}",
+			hash,
+			if may_refund_call_fee { "Yes" } else { "No" },
+		);
+
+		// the proof size component of the call weight assumes that there are
+		// `MaxBridgedAuthorities` in the `CurrentAuthoritySet` (we use `MaxEncodedLen`
+		// estimation). But if their number is lower, then we may "refund" some `proof_size`,
+		// making proof smaller and leaving block space to other useful transactions
+		let pre_dispatch_weight = T::WeightInfo::submit_finality_proof(
+			justification.commit.precommits.len().saturated_into(),
+			justification.votes_ancestries.len().saturated_into(),
+		);
+		let actual_weight = pre_dispatch_weight
+			.set_proof_size(pre_dispatch_weight.proof_size().saturating_sub(unused_proof_size));
+
+		let is_mandatory = maybe_new_authority_set.is_some();
+		Pallet::<T, I>::deposit_event(Event::UpdatedBestFinalizedHeader {
+			number,
+			hash,
+			grandpa_info: StoredHeaderGrandpaInfo {
+				finality_proof: justification,
+				new_verification_context: maybe_new_authority_set,
+			},
+		});
+
+		Ok(HeaderImportResult {
+			may_refund_call_fee,
+			is_mandatory,
+			actual_weight,
+			unused_proof_size,
+		})
 	}
 
 	/// Called when new free header is imported.
@@ -936,6 +1063,43 @@ mod tests {
 		)
 	}
 
+	fn warp_sync_fragment(
+		number: u8,
+		set_id: u64,
+		is_mandatory: bool,
+	) -> WarpSyncFragment<TestHeader> {
+		let mut header = test_header(number.into());
+		if is_mandatory {
+			let consensus_log = ConsensusLog::<TestNumber>::ScheduledChange(
+				sp_consensus_grandpa::ScheduledChange {
+					next_authorities: authority_list(),
+					delay: 0,
+				},
+			);
+			header.digest = Digest {
+				logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, consensus_log.encode())],
+			};
+		}
+		let justification = make_justification_for_header(JustificationGeneratorParams {
+			header: header.clone(),
+			set_id,
+			..Default::default()
+		});
+
+		WarpSyncFragment { header, justification }
+	}
+
+	fn submit_warp_sync_proof(
+		fragments: Vec<WarpSyncFragment<TestHeader>>,
+		current_set_id: u64,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		Pallet::<TestRuntime>::submit_warp_sync_proof(
+			RuntimeOrigin::signed(1),
+			fragments.try_into().unwrap(),
+			current_set_id,
+		)
+	}
+
 	fn next_block() {
 		use frame_support::traits::OnInitialize;
 
@@ -1632,6 +1796,120 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn warp_sync_proof_imports_all_fragments() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let fragments = vec![
+				warp_sync_fragment(1, 1, true),
+				warp_sync_fragment(2, 2, true),
+				warp_sync_fragment(3, 3, false),
+			];
+			let hashes: Vec<_> = fragments.iter().map(|fragment| fragment.header.hash()).collect();
+			let result = submit_warp_sync_proof(fragments, 1);
+
+			// the last fragment is not free, so the whole call is paid and free slots are
+			// not spent
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+			assert_eq!(
+				FreeHeadersRemaining::<TestRuntime, ()>::get(),
+				Some(mock::MaxFreeHeadersPerBlock::get()),
+			);
+			assert_eq!(CurrentAuthoritySet::<TestRuntime, ()>::get().set_id, 3);
+			assert_eq!(Pallet::<TestRuntime>::best_finalized(), Some(HeaderId(3, hashes[2])));
+			assert!(hashes
+				.iter()
+				.all(|hash| ImportedHeaders::<TestRuntime, ()>::contains_key(hash)));
+			assert_eq!(Pallet::<TestRuntime>::synced_headers_grandpa_info().len(), 3);
+		})
+	}
+
+	#[test]
+	fn warp_sync_proof_is_free_if_all_fragments_are_free() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let result = submit_warp_sync_proof(
+				vec![warp_sync_fragment(1, 1, true), warp_sync_fragment(2, 2, true)],
+				1,
+			);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+			assert_eq!(FreeHeadersRemaining::<TestRuntime, ()>::get(), Some(0));
+
+			// no more free slots in this block
+			let result = submit_warp_sync_proof(vec![warp_sync_fragment(3, 3, true)], 3);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+		})
+	}
+
+	#[test]
+	fn warp_sync_proof_is_paid_if_it_exceeds_free_headers_limit() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let result = submit_warp_sync_proof(
+				vec![
+					warp_sync_fragment(1, 1, true),
+					warp_sync_fragment(2, 2, true),
+					warp_sync_fragment(3, 3, true),
+				],
+				1,
+			);
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+			assert_eq!(
+				FreeHeadersRemaining::<TestRuntime, ()>::get(),
+				Some(mock::MaxFreeHeadersPerBlock::get()),
+			);
+			assert_eq!(CurrentAuthoritySet::<TestRuntime, ()>::get().set_id, 4);
+		})
+	}
+
+	#[test]
+	fn warp_sync_proof_rejects_invalid_proofs() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// empty proof
+			assert_err!(
+				submit_warp_sync_proof(vec![], 1),
+				<Error<TestRuntime>>::InvalidWarpSyncProof,
+			);
+
+			// non-mandatory fragment in the middle of the proof
+			assert_err!(
+				submit_warp_sync_proof(
+					vec![warp_sync_fragment(1, 1, false), warp_sync_fragment(2, 1, true)],
+					1,
+				),
+				<Error<TestRuntime>>::InvalidWarpSyncProof,
+			);
+		})
+	}
+
+	#[test]
+	fn warp_sync_proof_rejects_fragment_signed_by_wrong_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// unexpected `current_set_id`
+			assert_err!(
+				submit_warp_sync_proof(vec![warp_sync_fragment(1, 1, true)], 2),
+				<Error<TestRuntime>>::InvalidAuthoritySetId,
+			);
+
+			// second fragment is signed by the first set instead of the set, enacted by the
+			// first fragment
+			assert_err!(
+				submit_warp_sync_proof(
+					vec![warp_sync_fragment(1, 1, true), warp_sync_fragment(2, 1, true)],
+					1,
+				),
+				<Error<TestRuntime>>::InvalidJustification,
+			);
+		})
+	}
+
 	#[test]
 	fn should_prune_headers_over_headers_to_keep_parameter() {
 		run_test(|| {
@@ -1705,6 +1983,21 @@ mod tests {
 			direct_submit_finality_proof_call.encode(),
 			indirect_submit_finality_proof_call.encode()
 		);
+
+		let fragment = warp_sync_fragment(1, TEST_GRANDPA_SET_ID, true);
+		let direct_submit_warp_sync_proof_call = Call::<TestRuntime>::submit_warp_sync_proof {
+			fragments: vec![fragment.clone()].try_into().unwrap(),
+			current_set_id: TEST_GRANDPA_SET_ID,
+		};
+		let indirect_submit_warp_sync_proof_call =
+			BridgeGrandpaCall::<TestHeader>::submit_warp_sync_proof {
+				fragments: vec![fragment],
+				current_set_id: TEST_GRANDPA_SET_ID,
+			};
+		assert_eq!(
+			direct_submit_warp_sync_proof_call.encode(),
+			indirect_submit_warp_sync_proof_call.encode()
+		);
 	}
 
 	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
@@ -51,6 +51,7 @@ impl frame_system::Config for TestRuntime {
 parameter_types! {
 	pub const MaxFreeHeadersPerBlock: u32 = 2;
 	pub const FreeHeadersInterval: u32 = 32;
+	pub const MaxWarpSyncFragments: u32 = 4;
 	pub const HeadersToKeep: u32 = 5;
 }
 
@@ -59,6 +60,7 @@ impl grandpa::Config for TestRuntime {
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeHeadersPerBlock = MaxFreeHeadersPerBlock;
 	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxWarpSyncFragments = MaxWarpSyncFragments;
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
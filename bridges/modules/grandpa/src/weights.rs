@@ -52,6 +52,7 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn submit_finality_proof(p: u32, v: u32) -> Weight;
 	fn force_set_pallet_state() -> Weight;
+	fn submit_warp_sync_proof(f: u32, p: u32, v: u32) -> Weight;
 }
 
 /// Weights for `pallet_bridge_grandpa` that are generated using one of the Bridge testnets.
@@ -134,6 +135,19 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(RocksDbWeight::get().reads(3))
 			.saturating_add(RocksDbWeight::get().writes(6))
 	}
+	fn submit_warp_sync_proof(f: u32, p: u32, v: u32) -> Weight {
+		// Not benchmarked yet: every fragment is imported like in the `submit_finality_proof` call
+		// and enacts the new `CurrentAuthoritySet`. Fragments read the same storage items, except
+		// the `ImportedHashes` slot, so only that slot is added to the proof size of every next
+		// fragment. To be replaced with the output of the `submit_warp_sync_proof` benchmark.
+		let fragment =
+			Self::submit_finality_proof(p, v).saturating_add(RocksDbWeight::get().writes(1_u64));
+		let next_fragments = u64::from(f.saturating_sub(1));
+		Weight::from_parts(
+			fragment.ref_time().saturating_mul(f.into()),
+			fragment.proof_size().saturating_add(next_fragments.saturating_mul(2016)),
+		)
+	}
 }
 
 // For backwards compatibility and tests
@@ -213,4 +227,17 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3))
 			.saturating_add(RocksDbWeight::get().writes(6))
 	}
+	fn submit_warp_sync_proof(f: u32, p: u32, v: u32) -> Weight {
+		// Not benchmarked yet: every fragment is imported like in the `submit_finality_proof` call
+		// and enacts the new `CurrentAuthoritySet`. Fragments read the same storage items, except
+		// the `ImportedHashes` slot, so only that slot is added to the proof size of every next
+		// fragment. To be replaced with the output of the `submit_warp_sync_proof` benchmark.
+		let fragment =
+			Self::submit_finality_proof(p, v).saturating_add(RocksDbWeight::get().writes(1_u64));
+		let next_fragments = u64::from(f.saturating_sub(1));
+		Weight::from_parts(
+			fragment.ref_time().saturating_mul(f.into()),
+			fragment.proof_size().saturating_add(next_fragments.saturating_mul(2016)),
+		)
+	}
 }
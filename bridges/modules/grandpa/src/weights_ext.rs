@@ -43,6 +43,32 @@ pub trait WeightInfoExt: WeightInfo {
 		let base_weight = Self::submit_finality_proof(precommits_len, votes_ancestries_len);
 		base_weight.saturating_add(Self::submit_finality_proof_overhead_from_runtime())
 	}
+
+	/// Weight of warp sync proof submission extrinsic. Every fragment is described by the
+	/// number of precommits and the number of headers in votes ancestries of its justification.
+	///
+	/// The benchmark uses the same justification for every fragment, so we use the largest
+	/// justification of the proof to compute the weight.
+	fn submit_warp_sync_proof_weight(fragments: impl IntoIterator<Item = (u32, u32)>) -> Weight {
+		let (fragments_len, max_precommits_len, max_votes_ancestries_len) =
+			fragments.into_iter().fold(
+				(0u32, 0u32, 0u32),
+				|(fragments_len, max_precommits_len, max_votes_ancestries_len),
+				 (precommits_len, votes_ancestries_len)| {
+					(
+						fragments_len.saturating_add(1),
+						max_precommits_len.max(precommits_len),
+						max_votes_ancestries_len.max(votes_ancestries_len),
+					)
+				},
+			);
+		let base_weight = Self::submit_warp_sync_proof(
+			fragments_len,
+			max_precommits_len,
+			max_votes_ancestries_len,
+		);
+		base_weight.saturating_add(Self::submit_finality_proof_overhead_from_runtime())
+	}
 }
 
 impl<T: frame_system::Config> WeightInfoExt for BridgeWeight<T> {
@@ -21,11 +21,12 @@ use crate::{BridgedChainOf, Config, InboundLanes, OutboundLanes, Pallet, LOG_TAR
 use bp_messages::{
 	target_chain::MessageDispatch, BaseMessagesProofInfo, ChainWithMessages, InboundLaneData,
 	MessageNonce, MessagesCallInfo, ReceiveMessagesDeliveryProofInfo, ReceiveMessagesProofInfo,
-	UnrewardedRelayerOccupation,
+	UnrewardedRelayerOccupation, UnrewardedRelayersState,
 };
 use bp_runtime::{AccountIdOf, OwnedBridgeModule};
 use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
 use sp_runtime::transaction_validity::TransactionValidity;
+use sp_std::{ops::RangeInclusive, vec::Vec};
 
 /// Helper struct that provides methods for working with a call supported by `MessagesCallInfo`.
 pub struct CallHelper<T: Config<I>, I: 'static> {
@@ -38,35 +39,50 @@ impl<T: Config<I>, I: 'static> CallHelper<T, I> {
 	/// - call is `receive_messages_proof` and all messages have been delivered;
 	///
 	/// - call is `receive_messages_delivery_proof` and all messages confirmations have been
-	///   received.
+	///   received;
+	///
+	/// - call is `receive_multi_lane_messages_proof` or
+	///   `receive_multi_lane_messages_delivery_proof` and above is true for all its lanes.
 	pub fn was_successful(info: &MessagesCallInfo<T::LaneId>) -> bool {
 		match info {
-			MessagesCallInfo::ReceiveMessagesProof(info) => {
-				let inbound_lane_data = match InboundLanes::<T, I>::get(info.base.lane_id) {
-					Some(inbound_lane_data) => inbound_lane_data,
-					None => return false,
-				};
-				if info.base.bundled_range.is_empty() {
-					let post_occupation =
-						unrewarded_relayers_occupation::<T, I>(&inbound_lane_data);
-					// we don't care about `free_relayer_slots` here - it is checked in
-					// `is_obsolete` and every relayer has delivered at least one message,
-					// so if relayer slots are released, then message slots are also
-					// released
-					return post_occupation.free_message_slots >
-						info.unrewarded_relayers.free_message_slots
-				}
-
-				inbound_lane_data.last_delivered_nonce() == *info.base.bundled_range.end()
-			},
-			MessagesCallInfo::ReceiveMessagesDeliveryProof(info) => {
-				let outbound_lane_data = match OutboundLanes::<T, I>::get(info.0.lane_id) {
-					Some(outbound_lane_data) => outbound_lane_data,
-					None => return false,
-				};
-				outbound_lane_data.latest_received_nonce == *info.0.bundled_range.end()
-			},
+			MessagesCallInfo::ReceiveMessagesProof(info) =>
+				Self::was_receive_messages_proof_successful(info),
+			MessagesCallInfo::ReceiveMessagesDeliveryProof(info) =>
+				Self::was_receive_messages_delivery_proof_successful(info),
+			MessagesCallInfo::ReceiveMultiLaneMessagesProof(infos) =>
+				infos.iter().all(Self::was_receive_messages_proof_successful),
+			MessagesCallInfo::ReceiveMultiLaneMessagesDeliveryProof(infos) =>
+				infos.iter().all(Self::was_receive_messages_delivery_proof_successful),
+		}
+	}
+
+	/// Returns true if all messages of the single lane have been delivered.
+	fn was_receive_messages_proof_successful(info: &ReceiveMessagesProofInfo<T::LaneId>) -> bool {
+		let inbound_lane_data = match InboundLanes::<T, I>::get(info.base.lane_id) {
+			Some(inbound_lane_data) => inbound_lane_data,
+			None => return false,
+		};
+		if info.base.bundled_range.is_empty() {
+			let post_occupation = unrewarded_relayers_occupation::<T, I>(&inbound_lane_data);
+			// we don't care about `free_relayer_slots` here - it is checked in
+			// `is_obsolete` and every relayer has delivered at least one message,
+			// so if relayer slots are released, then message slots are also
+			// released
+			return post_occupation.free_message_slots > info.unrewarded_relayers.free_message_slots
 		}
+
+		inbound_lane_data.last_delivered_nonce() == *info.base.bundled_range.end()
+	}
+
+	/// Returns true if all messages confirmations of the single lane have been received.
+	fn was_receive_messages_delivery_proof_successful(
+		info: &ReceiveMessagesDeliveryProofInfo<T::LaneId>,
+	) -> bool {
+		let outbound_lane_data = match OutboundLanes::<T, I>::get(info.0.lane_id) {
+			Some(outbound_lane_data) => outbound_lane_data,
+			None => return false,
+		};
+		outbound_lane_data.latest_received_nonce == *info.0.bundled_range.end()
 	}
 }
 
@@ -83,12 +99,25 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 		&self,
 	) -> Option<ReceiveMessagesDeliveryProofInfo<T::LaneId>>;
 
+	/// Create a new instance of `ReceiveMessagesProofInfo` for every lane of
+	/// a `ReceiveMultiLaneMessagesProof` call.
+	fn receive_multi_lane_messages_proof_info(
+		&self,
+	) -> Option<Vec<ReceiveMessagesProofInfo<T::LaneId>>>;
+
+	/// Create a new instance of `ReceiveMessagesDeliveryProofInfo` for every lane of
+	/// a `ReceiveMultiLaneMessagesDeliveryProof` call.
+	fn receive_multi_lane_messages_delivery_proof_info(
+		&self,
+	) -> Option<Vec<ReceiveMessagesDeliveryProofInfo<T::LaneId>>>;
+
 	/// Create a new instance of `MessagesCallInfo` from a `ReceiveMessagesProof`
-	/// or a `ReceiveMessagesDeliveryProof` call.
+	/// or a `ReceiveMessagesDeliveryProof` call, or from their multi-lane versions.
 	fn call_info(&self) -> Option<MessagesCallInfo<T::LaneId>>;
 
 	/// Create a new instance of `MessagesCallInfo` from a `ReceiveMessagesProof`
-	/// or a `ReceiveMessagesDeliveryProof` call, if the call is for the provided lane.
+	/// or a `ReceiveMessagesDeliveryProof` call, or from their multi-lane versions, if the
+	/// call is for the provided lane.
 	fn call_info_for(&self, lane_id: T::LaneId) -> Option<MessagesCallInfo<T::LaneId>>;
 
 	/// Ensures that a `ReceiveMessagesProof` or a `ReceiveMessagesDeliveryProof` call:
@@ -106,6 +135,8 @@ pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
 	///   submitting a call when a pallet is halted or delivering messages when a dispatcher is
 	///   inactive).
 	///
+	/// Multi-lane calls must satisfy the above rules at every lane.
+	///
 	/// If one of above rules is violated, the transaction is treated as invalid.
 	fn check_obsolete_call(&self) -> TransactionValidity;
 }
@@ -120,18 +151,10 @@ impl<
 		if let Some(crate::Call::<T, I>::receive_messages_proof { ref proof, .. }) =
 			self.is_sub_type()
 		{
-			let inbound_lane_data = InboundLanes::<T, I>::get(proof.lane)?;
-
-			return Some(ReceiveMessagesProofInfo {
-				base: BaseMessagesProofInfo {
-					lane_id: proof.lane,
-					// we want all messages in this range to be new for us. Otherwise transaction
-					// will be considered obsolete.
-					bundled_range: proof.nonces_start..=proof.nonces_end,
-					best_stored_nonce: inbound_lane_data.last_delivered_nonce(),
-				},
-				unrewarded_relayers: unrewarded_relayers_occupation::<T, I>(&inbound_lane_data),
-			})
+			return lane_receive_messages_proof_info::<T, I>(
+				proof.lane,
+				proof.nonces_start..=proof.nonces_end,
+			)
 		}
 
 		None
@@ -146,18 +169,58 @@ impl<
 			..
 		}) = self.is_sub_type()
 		{
-			let outbound_lane_data = OutboundLanes::<T, I>::get(proof.lane)?;
-
-			return Some(ReceiveMessagesDeliveryProofInfo(BaseMessagesProofInfo {
-				lane_id: proof.lane,
-				// there's a time frame between message delivery, message confirmation and reward
-				// confirmation. Because of that, we can't assume that our state has been confirmed
-				// to the bridged chain. So we are accepting any proof that brings new
-				// confirmations.
-				bundled_range: outbound_lane_data.latest_received_nonce + 1..=
-					relayers_state.last_delivered_nonce,
-				best_stored_nonce: outbound_lane_data.latest_received_nonce,
-			}))
+			return lane_receive_messages_delivery_proof_info::<T, I>(proof.lane, relayers_state)
+		}
+
+		None
+	}
+
+	fn receive_multi_lane_messages_proof_info(
+		&self,
+	) -> Option<Vec<ReceiveMessagesProofInfo<T::LaneId>>> {
+		if let Some(crate::Call::<T, I>::receive_multi_lane_messages_proof { ref proof, .. }) =
+			self.is_sub_type()
+		{
+			if proof.lanes.is_empty() {
+				return None
+			}
+
+			return proof
+				.lanes
+				.iter()
+				.map(|range| {
+					lane_receive_messages_proof_info::<T, I>(
+						range.lane,
+						range.nonces_start..=range.nonces_end,
+					)
+				})
+				.collect()
+		}
+
+		None
+	}
+
+	fn receive_multi_lane_messages_delivery_proof_info(
+		&self,
+	) -> Option<Vec<ReceiveMessagesDeliveryProofInfo<T::LaneId>>> {
+		if let Some(crate::Call::<T, I>::receive_multi_lane_messages_delivery_proof {
+			ref proof,
+			ref relayers_states,
+			..
+		}) = self.is_sub_type()
+		{
+			if proof.lanes.is_empty() || proof.lanes.len() != relayers_states.len() {
+				return None
+			}
+
+			return proof
+				.lanes
+				.iter()
+				.zip(relayers_states.iter())
+				.map(|(lane, relayers_state)| {
+					lane_receive_messages_delivery_proof_info::<T, I>(*lane, relayers_state)
+				})
+				.collect()
 		}
 
 		None
@@ -172,17 +235,19 @@ impl<
 			return Some(MessagesCallInfo::ReceiveMessagesDeliveryProof(info))
 		}
 
+		if let Some(infos) = self.receive_multi_lane_messages_proof_info() {
+			return Some(MessagesCallInfo::ReceiveMultiLaneMessagesProof(infos))
+		}
+
+		if let Some(infos) = self.receive_multi_lane_messages_delivery_proof_info() {
+			return Some(MessagesCallInfo::ReceiveMultiLaneMessagesDeliveryProof(infos))
+		}
+
 		None
 	}
 
 	fn call_info_for(&self, lane_id: T::LaneId) -> Option<MessagesCallInfo<T::LaneId>> {
-		self.call_info().filter(|info| {
-			let actual_lane_id = match info {
-				MessagesCallInfo::ReceiveMessagesProof(info) => info.base.lane_id,
-				MessagesCallInfo::ReceiveMessagesDeliveryProof(info) => info.0.lane_id,
-			};
-			actual_lane_id == lane_id
-		})
+		self.call_info().filter(|info| info.lanes().contains(&lane_id))
 	}
 
 	fn check_obsolete_call(&self) -> TransactionValidity {
@@ -220,6 +285,30 @@ impl<
 
 				return sp_runtime::transaction_validity::InvalidTransaction::Stale.into()
 			},
+			Some(MessagesCallInfo::ReceiveMultiLaneMessagesProof(proof_infos))
+				if proof_infos.iter().any(|proof_info| {
+					proof_info.is_obsolete(T::MessageDispatch::is_active(proof_info.base.lane_id))
+				}) =>
+			{
+				log::trace!(
+					target: LOG_TARGET,
+					"Rejecting obsolete multi-lane messages delivery transaction: {:?}",
+					proof_infos
+				);
+
+				return sp_runtime::transaction_validity::InvalidTransaction::Stale.into()
+			},
+			Some(MessagesCallInfo::ReceiveMultiLaneMessagesDeliveryProof(proof_infos))
+				if proof_infos.iter().any(|proof_info| proof_info.is_obsolete()) =>
+			{
+				log::trace!(
+					target: LOG_TARGET,
+					"Rejecting obsolete multi-lane messages confirmation transaction: {:?}",
+					proof_infos,
+				);
+
+				return sp_runtime::transaction_validity::InvalidTransaction::Stale.into()
+			},
 			_ => {},
 		}
 
@@ -227,6 +316,45 @@ impl<
 	}
 }
 
+/// Create a new instance of `ReceiveMessagesProofInfo` for the given lane and range of messages.
+fn lane_receive_messages_proof_info<T: Config<I>, I: 'static>(
+	lane_id: T::LaneId,
+	bundled_range: RangeInclusive<MessageNonce>,
+) -> Option<ReceiveMessagesProofInfo<T::LaneId>> {
+	let inbound_lane_data = InboundLanes::<T, I>::get(lane_id)?;
+
+	Some(ReceiveMessagesProofInfo {
+		base: BaseMessagesProofInfo {
+			lane_id,
+			// we want all messages in this range to be new for us. Otherwise transaction
+			// will be considered obsolete.
+			bundled_range,
+			best_stored_nonce: inbound_lane_data.last_delivered_nonce(),
+		},
+		unrewarded_relayers: unrewarded_relayers_occupation::<T, I>(&inbound_lane_data),
+	})
+}
+
+/// Create a new instance of `ReceiveMessagesDeliveryProofInfo` for the given lane and
+/// unrewarded relayers state.
+fn lane_receive_messages_delivery_proof_info<T: Config<I>, I: 'static>(
+	lane_id: T::LaneId,
+	relayers_state: &UnrewardedRelayersState,
+) -> Option<ReceiveMessagesDeliveryProofInfo<T::LaneId>> {
+	let outbound_lane_data = OutboundLanes::<T, I>::get(lane_id)?;
+
+	Some(ReceiveMessagesDeliveryProofInfo(BaseMessagesProofInfo {
+		lane_id,
+		// there's a time frame between message delivery, message confirmation and reward
+		// confirmation. Because of that, we can't assume that our state has been confirmed
+		// to the bridged chain. So we are accepting any proof that brings new
+		// confirmations.
+		bundled_range: outbound_lane_data.latest_received_nonce + 1..=
+			relayers_state.last_delivered_nonce,
+		best_stored_nonce: outbound_lane_data.latest_received_nonce,
+	}))
+}
+
 /// Returns occupation state of unrewarded relayers vector.
 fn unrewarded_relayers_occupation<T: Config<I>, I: 'static>(
 	inbound_lane_data: &InboundLaneData<AccountIdOf<BridgedChainOf<T, I>>>,
@@ -249,9 +377,15 @@ mod tests {
 	use super::*;
 	use crate::tests::mock::*;
 	use bp_messages::{
-		source_chain::FromBridgedChainMessagesDeliveryProof,
-		target_chain::FromBridgedChainMessagesProof, DeliveredMessages, InboundLaneData, LaneState,
-		OutboundLaneData, UnrewardedRelayer, UnrewardedRelayersState,
+		source_chain::{
+			FromBridgedChainMessagesDeliveryProof, FromBridgedChainMultiLaneMessagesDeliveryProof,
+		},
+		target_chain::{
+			FromBridgedChainMessagesProof, FromBridgedChainMultiLaneMessagesProof,
+			LaneMessagesRange,
+		},
+		DeliveredMessages, InboundLaneData, LaneState, OutboundLaneData, UnrewardedRelayer,
+		UnrewardedRelayersState,
 	};
 	use sp_std::ops::RangeInclusive;
 
@@ -410,6 +544,64 @@ mod tests {
 		});
 	}
 
+	fn validate_multi_lane_message_delivery(
+		lanes: Vec<(TestLaneIdType, bp_messages::MessageNonce, bp_messages::MessageNonce)>,
+	) -> bool {
+		RuntimeCall::Messages(crate::Call::<TestRuntime, ()>::receive_multi_lane_messages_proof {
+			relayer_id_at_bridged_chain: 42,
+			messages_count: lanes
+				.iter()
+				.map(|(_, nonces_start, nonces_end)| {
+					nonces_end.checked_sub(*nonces_start).map(|x| x + 1).unwrap_or(0)
+				})
+				.sum::<MessageNonce>() as u32,
+			dispatch_weight: frame_support::weights::Weight::zero(),
+			proof: Box::new(FromBridgedChainMultiLaneMessagesProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: Default::default(),
+				lanes: lanes
+					.into_iter()
+					.map(|(lane, nonces_start, nonces_end)| LaneMessagesRange {
+						lane,
+						nonces_start,
+						nonces_end,
+					})
+					.collect(),
+			}),
+		})
+		.check_obsolete_call()
+		.is_ok()
+	}
+
+	#[test]
+	fn extension_rejects_multi_lane_call_with_obsolete_messages_at_any_lane() {
+		run_test(|| {
+			InboundLanes::<TestRuntime>::insert(second_test_lane_id(), InboundLaneData::opened());
+
+			// when current best delivered is message#10 at the first lane and we're trying to
+			// deliver messages 8..=9 there => tx is rejected, even if the second lane is fine
+			deliver_message_10();
+			assert!(!validate_multi_lane_message_delivery(vec![
+				(second_test_lane_id(), 1, 5),
+				(test_lane_id(), 8, 9),
+			]));
+		});
+	}
+
+	#[test]
+	fn extension_accepts_multi_lane_call_with_new_messages() {
+		run_test(|| {
+			InboundLanes::<TestRuntime>::insert(second_test_lane_id(), InboundLaneData::opened());
+
+			// when all lanes bring new messages => tx is accepted
+			deliver_message_10();
+			assert!(validate_multi_lane_message_delivery(vec![
+				(test_lane_id(), 11, 15),
+				(second_test_lane_id(), 1, 5),
+			]));
+		});
+	}
+
 	fn confirm_message_10() {
 		OutboundLanes::<TestRuntime>::insert(
 			test_lane_id(),
@@ -435,6 +627,31 @@ mod tests {
 		.is_ok()
 	}
 
+	#[test]
+	fn extension_rejects_multi_lane_confirmation_with_obsolete_confirmations_at_any_lane() {
+		run_test(|| {
+			OutboundLanes::<TestRuntime>::insert(second_test_lane_id(), OutboundLaneData::opened());
+
+			// when current best confirmed is message#10 at the first lane and we're trying to
+			// confirm message#10 there => tx is rejected, even if the second lane is fine
+			confirm_message_10();
+			let call = RuntimeCall::Messages(
+				crate::Call::<TestRuntime>::receive_multi_lane_messages_delivery_proof {
+					proof: FromBridgedChainMultiLaneMessagesDeliveryProof {
+						bridged_header_hash: Default::default(),
+						storage_proof: Default::default(),
+						lanes: vec![second_test_lane_id(), test_lane_id()],
+					},
+					relayers_states: vec![
+						UnrewardedRelayersState { last_delivered_nonce: 5, ..Default::default() },
+						UnrewardedRelayersState { last_delivered_nonce: 10, ..Default::default() },
+					],
+				},
+			);
+			assert!(call.check_obsolete_call().is_err());
+		});
+	}
+
 	#[test]
 	fn extension_rejects_obsolete_confirmations() {
 		run_test(|| {
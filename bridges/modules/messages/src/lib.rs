@@ -53,12 +53,13 @@ pub use weights_ext::{
 use bp_header_chain::HeaderChain;
 use bp_messages::{
 	source_chain::{
-		DeliveryConfirmationPayments, FromBridgedChainMessagesDeliveryProof, OnMessagesDelivered,
-		SendMessageArtifacts,
+		DeliveryConfirmationPayments, FromBridgedChainMessagesDeliveryProof,
+		FromBridgedChainMultiLaneMessagesDeliveryProof, OnMessagesDelivered, SendMessageArtifacts,
 	},
 	target_chain::{
-		DeliveryPayments, DispatchMessage, FromBridgedChainMessagesProof, MessageDispatch,
-		ProvedLaneMessages, ProvedMessages,
+		DeliveryPayments, DispatchMessage, FromBridgedChainMessagesProof,
+		FromBridgedChainMultiLaneMessagesProof, MessageDispatch, ProvedLaneMessages,
+		ProvedMessages,
 	},
 	ChainWithMessages, DeliveredMessages, InboundLaneData, InboundMessageDetails, MessageKey,
 	MessageNonce, MessagePayload, MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails,
@@ -254,81 +255,15 @@ pub mod pallet {
 					},
 				)?;
 
-			// dispatch messages and (optionally) update lane(s) state(s)
-			let mut total_messages = 0;
-			let mut valid_messages = 0;
+			// dispatch messages and (optionally) update lane state
 			let mut dispatch_weight_left = dispatch_weight;
-			let mut lane = active_inbound_lane::<T, I>(lane_id)?;
-
-			// subtract extra storage proof bytes from the actual PoV size - there may be
-			// less unrewarded relayers than the maximal configured value
-			let lane_extra_proof_size_bytes = lane.storage().extra_proof_size_bytes();
-			actual_weight = actual_weight.set_proof_size(
-				actual_weight.proof_size().saturating_sub(lane_extra_proof_size_bytes),
-			);
-
-			if let Some(lane_state) = lane_data.lane_state {
-				let updated_latest_confirmed_nonce = lane.receive_state_update(lane_state);
-				if let Some(updated_latest_confirmed_nonce) = updated_latest_confirmed_nonce {
-					log::trace!(
-						target: LOG_TARGET,
-						"Received lane {:?} state update: latest_confirmed_nonce={}. Unrewarded relayers: {:?}",
-						lane_id,
-						updated_latest_confirmed_nonce,
-						UnrewardedRelayersState::from(&lane.storage().data()),
-					);
-				}
-			}
-
-			let mut messages_received_status =
-				ReceivedMessages::new(lane_id, Vec::with_capacity(lane_data.messages.len()));
-			for mut message in lane_data.messages {
-				debug_assert_eq!(message.key.lane_id, lane_id);
-				total_messages += 1;
-
-				// ensure that relayer has declared enough weight for dispatching next message
-				// on this lane. We can't dispatch lane messages out-of-order, so if declared
-				// weight is not enough, let's move to next lane
-				let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
-				if message_dispatch_weight.any_gt(dispatch_weight_left) {
-					log::trace!(
-						target: LOG_TARGET,
-						"Cannot dispatch any more messages on lane {:?}. Weight: declared={}, left={}",
-						lane_id,
-						message_dispatch_weight,
-						dispatch_weight_left,
-					);
-
-					fail!(Error::<T, I>::InsufficientDispatchWeight);
-				}
-
-				let receival_result = lane.receive_message::<T::MessageDispatch>(
-					&relayer_id_at_bridged_chain,
-					message.key.nonce,
-					message.data,
-				);
-
-				// note that we're returning unspent weight to relayer even if message has been
-				// rejected by the lane. This allows relayers to submit spam transactions with
-				// e.g. the same set of already delivered messages over and over again, without
-				// losing funds for messages dispatch. But keep in mind that relayer pays base
-				// delivery transaction cost anyway. And base cost covers everything except
-				// dispatch, so we have a balance here.
-				let unspent_weight = match &receival_result {
-					ReceptionResult::Dispatched(dispatch_result) => {
-						valid_messages += 1;
-						dispatch_result.unspent_weight
-					},
-					ReceptionResult::InvalidNonce |
-					ReceptionResult::TooManyUnrewardedRelayers |
-					ReceptionResult::TooManyUnconfirmedMessages => message_dispatch_weight,
-				};
-				messages_received_status.push(message.key.nonce, receival_result);
-
-				let unspent_weight = unspent_weight.min(message_dispatch_weight);
-				dispatch_weight_left -= message_dispatch_weight - unspent_weight;
-				actual_weight = actual_weight.saturating_sub(unspent_weight);
-			}
+			let (total_messages, valid_messages) = Self::receive_lane_messages(
+				&relayer_id_at_bridged_chain,
+				lane_id,
+				lane_data,
+				&mut dispatch_weight_left,
+				&mut actual_weight,
+			)?;
 
 			// let's now deal with relayer payments
 			T::DeliveryPayments::pay_reward(
@@ -347,8 +282,6 @@ pub mod pallet {
 				declared_weight,
 			);
 
-			Self::deposit_event(Event::MessagesReceived(messages_received_status));
-
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
 
@@ -377,68 +310,172 @@ pub mod pallet {
 
 					Error::<T, I>::InvalidMessagesDeliveryProof
 				})?;
+
+			// mark messages as delivered
+			Self::confirm_lane_delivery(
+				&confirmation_relayer,
+				lane_id,
+				lane_data,
+				&mut relayers_state,
+			)?;
+
+			// because of lags, the inbound lane state (`lane_data`) may have entries for
+			// already rewarded relayers and messages (if all entries are duplicated, then
+			// this transaction must be filtered out by our signed extension)
+			let actual_weight = T::WeightInfo::receive_messages_delivery_proof_weight(
+				&PreComputedSize(proof_size as usize),
+				&relayers_state,
+			);
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
+
+		/// Receive messages proof from bridged chain, that covers multiple lanes.
+		///
+		/// This is the multi-lane version of the `receive_messages_proof` call. Messages of all
+		/// lanes are proved by the single storage proof. The `messages_count` is the total number
+		/// of messages at all lanes and the `dispatch_weight` is the total dispatch weight of all
+		/// bundled messages.
+		///
+		/// The call fails if any of its lanes is rejected for the reasons, listed in the
+		/// `receive_messages_proof` call documentation.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::receive_multi_lane_messages_proof_weight(
+			&**proof,
+			proof.lanes.len() as u32,
+			*messages_count,
+			*dispatch_weight,
+		))]
+		pub fn receive_multi_lane_messages_proof(
+			origin: OriginFor<T>,
+			relayer_id_at_bridged_chain: AccountIdOf<BridgedChainOf<T, I>>,
+			proof: Box<
+				FromBridgedChainMultiLaneMessagesProof<HashOf<BridgedChainOf<T, I>>, T::LaneId>,
+			>,
+			messages_count: u32,
+			dispatch_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			let relayer_id_at_this_chain = ensure_signed(origin)?;
+
+			// reject transactions that are declaring too many messages
 			ensure!(
-				relayers_state.is_valid(&lane_data),
-				Error::<T, I>::InvalidUnrewardedRelayersState
+				MessageNonce::from(messages_count) <=
+					BridgedChainOf::<T, I>::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+				Error::<T, I>::TooManyMessagesInTheProof
 			);
 
-			// mark messages as delivered
-			let mut lane = any_state_outbound_lane::<T, I>(lane_id)?;
-			let last_delivered_nonce = lane_data.last_delivered_nonce();
-			let confirmed_messages = lane
-				.confirm_delivery(
-					relayers_state.total_messages,
-					last_delivered_nonce,
-					&lane_data.relayers,
-				)
-				.map_err(Error::<T, I>::ReceptionConfirmation)?;
+			// see `receive_messages_proof` for why we need the declared weight here
+			let declared_weight = T::WeightInfo::receive_multi_lane_messages_proof_weight(
+				&*proof,
+				proof.lanes.len() as u32,
+				messages_count,
+				dispatch_weight,
+			);
+			let mut actual_weight = declared_weight;
 
-			if let Some(confirmed_messages) = confirmed_messages {
-				// emit 'delivered' event
-				let received_range = confirmed_messages.begin..=confirmed_messages.end;
-				Self::deposit_event(Event::MessagesDelivered {
-					lane_id: lane_id.into(),
-					messages: confirmed_messages,
-				});
+			// verify messages proof && convert proof into messages
+			let lanes = verify_and_decode_multi_lane_messages_proof::<T, I>(*proof, messages_count)
+				.map_err(|err| {
+					log::trace!(
+						target: LOG_TARGET,
+						"Rejecting invalid multi-lane messages proof: {:?}",
+						err,
+					);
 
-				// if some new messages have been confirmed, reward relayers
-				let actually_rewarded_relayers = T::DeliveryConfirmationPayments::pay_reward(
+					Error::<T, I>::InvalidMessagesProof
+				})?;
+
+			// dispatch messages and (optionally) update lanes states
+			let mut total_messages = 0;
+			let mut valid_messages = 0;
+			let mut dispatch_weight_left = dispatch_weight;
+			for (lane_id, lane_data) in lanes {
+				let (lane_total_messages, lane_valid_messages) = Self::receive_lane_messages(
+					&relayer_id_at_bridged_chain,
 					lane_id,
-					lane_data.relayers,
-					&confirmation_relayer,
-					&received_range,
-				);
+					lane_data,
+					&mut dispatch_weight_left,
+					&mut actual_weight,
+				)?;
+				total_messages += lane_total_messages;
+				valid_messages += lane_valid_messages;
+			}
 
-				// update relayers state with actual numbers to compute actual weight below
-				relayers_state.unrewarded_relayer_entries = sp_std::cmp::min(
-					relayers_state.unrewarded_relayer_entries,
-					actually_rewarded_relayers,
-				);
-				relayers_state.total_messages = sp_std::cmp::min(
-					relayers_state.total_messages,
-					received_range.checked_len().unwrap_or(MessageNonce::MAX),
-				);
-			};
+			// let's now deal with relayer payments
+			T::DeliveryPayments::pay_reward(
+				relayer_id_at_this_chain,
+				total_messages,
+				valid_messages,
+				actual_weight,
+			);
 
-			log::trace!(
+			log::debug!(
 				target: LOG_TARGET,
-				"Received messages delivery proof up to (and including) {} at lane {:?}",
-				last_delivered_nonce,
-				lane_id,
+				"Received multi-lane messages: total={}, valid={}. Weight used: {}/{}.",
+				total_messages,
+				valid_messages,
+				actual_weight,
+				declared_weight,
 			);
 
-			// notify others about messages delivery
-			T::OnMessagesDelivered::on_messages_delivered(
-				lane_id,
-				lane.data().queued_messages().saturating_len(),
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
+
+		/// Receive messages delivery proof from bridged chain, that covers multiple lanes.
+		///
+		/// This is the multi-lane version of the `receive_messages_delivery_proof` call. States
+		/// of all inbound lanes are proved by the single storage proof. The `relayers_states`
+		/// must contain unrewarded relayers state for every lane of the proof, in the same order.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::receive_multi_lane_messages_delivery_proof_weight(
+			proof,
+			relayers_states,
+		))]
+		pub fn receive_multi_lane_messages_delivery_proof(
+			origin: OriginFor<T>,
+			proof: FromBridgedChainMultiLaneMessagesDeliveryProof<
+				HashOf<BridgedChainOf<T, I>>,
+				T::LaneId,
+			>,
+			mut relayers_states: Vec<UnrewardedRelayersState>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+
+			let proof_size = proof.size();
+			let confirmation_relayer = ensure_signed(origin)?;
+			let lanes = proofs::verify_multi_lane_messages_delivery_proof::<T, I>(proof).map_err(
+				|err| {
+					log::trace!(
+						target: LOG_TARGET,
+						"Rejecting invalid multi-lane messages delivery proof: {:?}",
+						err,
+					);
+
+					Error::<T, I>::InvalidMessagesDeliveryProof
+				},
+			)?;
+			ensure!(
+				lanes.len() == relayers_states.len(),
+				Error::<T, I>::InvalidUnrewardedRelayersState
 			);
 
-			// because of lags, the inbound lane state (`lane_data`) may have entries for
-			// already rewarded relayers and messages (if all entries are duplicated, then
-			// this transaction must be filtered out by our signed extension)
-			let actual_weight = T::WeightInfo::receive_messages_delivery_proof_weight(
+			// mark messages as delivered
+			for ((lane_id, lane_data), relayers_state) in
+				lanes.into_iter().zip(relayers_states.iter_mut())
+			{
+				Self::confirm_lane_delivery(
+					&confirmation_relayer,
+					lane_id,
+					lane_data,
+					relayers_state,
+				)?;
+			}
+
+			// see `receive_messages_delivery_proof` for why we recompute the weight here
+			let actual_weight = T::WeightInfo::receive_multi_lane_messages_delivery_proof_weight(
 				&PreComputedSize(proof_size as usize),
-				&relayers_state,
+				&relayers_states,
 			);
 
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
@@ -611,6 +648,165 @@ pub mod pallet {
 		) -> Option<InboundLaneData<AccountIdOf<BridgedChainOf<T, I>>>> {
 			InboundLanes::<T, I>::get(lane).map(|lane| lane.0)
 		}
+
+		/// Receive proved messages and (optional) outbound lane state of the single lane.
+		///
+		/// Returns the total number of bundled messages and the number of messages that have
+		/// been accepted by the lane. Dispatch weight of all messages is subtracted from the
+		/// `dispatch_weight_left` and unspent weight is subtracted from the `actual_weight`.
+		fn receive_lane_messages(
+			relayer_id_at_bridged_chain: &AccountIdOf<BridgedChainOf<T, I>>,
+			lane_id: T::LaneId,
+			lane_data: ProvedLaneMessages<DispatchMessage<T::InboundPayload, T::LaneId>>,
+			dispatch_weight_left: &mut Weight,
+			actual_weight: &mut Weight,
+		) -> Result<(MessageNonce, MessageNonce), Error<T, I>> {
+			let mut total_messages = 0;
+			let mut valid_messages = 0;
+			let mut lane = active_inbound_lane::<T, I>(lane_id)?;
+
+			// subtract extra storage proof bytes from the actual PoV size - there may be
+			// less unrewarded relayers than the maximal configured value
+			let lane_extra_proof_size_bytes = lane.storage().extra_proof_size_bytes();
+			*actual_weight = actual_weight.set_proof_size(
+				actual_weight.proof_size().saturating_sub(lane_extra_proof_size_bytes),
+			);
+
+			if let Some(lane_state) = lane_data.lane_state {
+				let updated_latest_confirmed_nonce = lane.receive_state_update(lane_state);
+				if let Some(updated_latest_confirmed_nonce) = updated_latest_confirmed_nonce {
+					log::trace!(
+						target: LOG_TARGET,
+						"Received lane {:?} state update: latest_confirmed_nonce={}. Unrewarded relayers: {:?}",
+						lane_id,
+						updated_latest_confirmed_nonce,
+						UnrewardedRelayersState::from(&lane.storage().data()),
+					);
+				}
+			}
+
+			let mut messages_received_status =
+				ReceivedMessages::new(lane_id, Vec::with_capacity(lane_data.messages.len()));
+			for mut message in lane_data.messages {
+				debug_assert_eq!(message.key.lane_id, lane_id);
+				total_messages += 1;
+
+				// ensure that relayer has declared enough weight for dispatching next message
+				// on this lane. We can't dispatch lane messages out-of-order, so if declared
+				// weight is not enough, let's move to next lane
+				let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+				if message_dispatch_weight.any_gt(*dispatch_weight_left) {
+					log::trace!(
+						target: LOG_TARGET,
+						"Cannot dispatch any more messages on lane {:?}. Weight: declared={}, left={}",
+						lane_id,
+						message_dispatch_weight,
+						dispatch_weight_left,
+					);
+
+					fail!(Error::<T, I>::InsufficientDispatchWeight);
+				}
+
+				let receival_result = lane.receive_message::<T::MessageDispatch>(
+					relayer_id_at_bridged_chain,
+					message.key.nonce,
+					message.data,
+				);
+
+				// note that we're returning unspent weight to relayer even if message has been
+				// rejected by the lane. This allows relayers to submit spam transactions with
+				// e.g. the same set of already delivered messages over and over again, without
+				// losing funds for messages dispatch. But keep in mind that relayer pays base
+				// delivery transaction cost anyway. And base cost covers everything except
+				// dispatch, so we have a balance here.
+				let unspent_weight = match &receival_result {
+					ReceptionResult::Dispatched(dispatch_result) => {
+						valid_messages += 1;
+						dispatch_result.unspent_weight
+					},
+					ReceptionResult::InvalidNonce |
+					ReceptionResult::TooManyUnrewardedRelayers |
+					ReceptionResult::TooManyUnconfirmedMessages => message_dispatch_weight,
+				};
+				messages_received_status.push(message.key.nonce, receival_result);
+
+				let unspent_weight = unspent_weight.min(message_dispatch_weight);
+				*dispatch_weight_left -= message_dispatch_weight - unspent_weight;
+				*actual_weight = actual_weight.saturating_sub(unspent_weight);
+			}
+
+			Self::deposit_event(Event::MessagesReceived(messages_received_status));
+
+			Ok((total_messages, valid_messages))
+		}
+
+		/// Confirm delivery of messages at the single lane and pay rewards to relayers.
+		///
+		/// The `relayers_state` is updated with actual numbers, so that it may be used to compute
+		/// the actual weight of the call.
+		fn confirm_lane_delivery(
+			confirmation_relayer: &T::AccountId,
+			lane_id: T::LaneId,
+			lane_data: InboundLaneData<T::AccountId>,
+			relayers_state: &mut UnrewardedRelayersState,
+		) -> Result<(), Error<T, I>> {
+			ensure!(
+				relayers_state.is_valid(&lane_data),
+				Error::<T, I>::InvalidUnrewardedRelayersState
+			);
+
+			let mut lane = any_state_outbound_lane::<T, I>(lane_id)?;
+			let last_delivered_nonce = lane_data.last_delivered_nonce();
+			let confirmed_messages = lane
+				.confirm_delivery(
+					relayers_state.total_messages,
+					last_delivered_nonce,
+					&lane_data.relayers,
+				)
+				.map_err(Error::<T, I>::ReceptionConfirmation)?;
+
+			if let Some(confirmed_messages) = confirmed_messages {
+				// emit 'delivered' event
+				let received_range = confirmed_messages.begin..=confirmed_messages.end;
+				Self::deposit_event(Event::MessagesDelivered {
+					lane_id: lane_id.into(),
+					messages: confirmed_messages,
+				});
+
+				// if some new messages have been confirmed, reward relayers
+				let actually_rewarded_relayers = T::DeliveryConfirmationPayments::pay_reward(
+					lane_id,
+					lane_data.relayers,
+					confirmation_relayer,
+					&received_range,
+				);
+
+				// update relayers state with actual numbers to compute actual weight below
+				relayers_state.unrewarded_relayer_entries = sp_std::cmp::min(
+					relayers_state.unrewarded_relayer_entries,
+					actually_rewarded_relayers,
+				);
+				relayers_state.total_messages = sp_std::cmp::min(
+					relayers_state.total_messages,
+					received_range.checked_len().unwrap_or(MessageNonce::MAX),
+				);
+			};
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Received messages delivery proof up to (and including) {} at lane {:?}",
+				last_delivered_nonce,
+				lane_id,
+			);
+
+			// notify others about messages delivery
+			T::OnMessagesDelivered::on_messages_delivered(
+				lane_id,
+				lane.data().queued_messages().saturating_len(),
+			);
+
+			Ok(())
+		}
 	}
 
 	#[cfg(any(feature = "try-runtime", test))]
@@ -787,3 +983,28 @@ fn verify_and_decode_messages_proof<T: Config<I>, I: 'static>(
 		)
 	})
 }
+
+/// Verify multi-lane messages proof and return proved messages with decoded payload.
+fn verify_and_decode_multi_lane_messages_proof<T: Config<I>, I: 'static>(
+	proof: FromBridgedChainMultiLaneMessagesProof<HashOf<BridgedChainOf<T, I>>, T::LaneId>,
+	messages_count: u32,
+) -> Result<
+	Vec<ProvedMessages<T::LaneId, DispatchMessage<T::InboundPayload, T::LaneId>>>,
+	VerificationError,
+> {
+	// see `verify_and_decode_messages_proof` for why the `messages_count` is sane here
+	proofs::verify_multi_lane_messages_proof::<T, I>(proof, messages_count).map(|lanes| {
+		lanes
+			.into_iter()
+			.map(|(lane, lane_data)| {
+				(
+					lane,
+					ProvedLaneMessages {
+						lane_state: lane_data.lane_state,
+						messages: lane_data.messages.into_iter().map(Into::into).collect(),
+					},
+				)
+			})
+			.collect()
+	})
+}
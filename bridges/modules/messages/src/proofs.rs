@@ -20,8 +20,13 @@ use crate::{BridgedChainOf, BridgedHeaderChainOf, Config};
 
 use bp_header_chain::{HeaderChain, HeaderChainError};
 use bp_messages::{
-	source_chain::FromBridgedChainMessagesDeliveryProof,
-	target_chain::{FromBridgedChainMessagesProof, ProvedLaneMessages, ProvedMessages},
+	source_chain::{
+		FromBridgedChainMessagesDeliveryProof, FromBridgedChainMultiLaneMessagesDeliveryProof,
+	},
+	target_chain::{
+		FromBridgedChainMessagesProof, FromBridgedChainMultiLaneMessagesProof, LaneMessagesRange,
+		ProvedLaneMessages, ProvedMessages,
+	},
 	ChainWithMessages, InboundLaneData, Message, MessageKey, MessageNonce, MessagePayload,
 	OutboundLaneData, VerificationError,
 };
@@ -29,7 +34,7 @@ use bp_runtime::{
 	HashOf, HasherOf, RangeInclusiveExt, RawStorageProof, StorageProofChecker, StorageProofError,
 };
 use codec::Decode;
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_set::BTreeSet, ops::RangeInclusive, vec::Vec};
 
 /// 'Parsed' message delivery proof - inbound lane id and its state.
 pub(crate) type ParsedMessagesDeliveryProofFromBridgedChain<T, I> =
@@ -68,37 +73,56 @@ pub fn verify_messages_proof<T: Config<I>, I: 'static>(
 		return Err(VerificationError::MessagesCountMismatch)
 	}
 
-	// Read messages first. All messages that are claimed to be in the proof must
-	// be in the proof. So any error in `read_value`, or even missing value is fatal.
-	//
-	// Mind that we allow proofs with no messages if outbound lane state is proved.
-	let mut messages = Vec::with_capacity(messages_in_the_proof as _);
-	for nonce in nonces_range {
-		let message_key = MessageKey { lane_id: lane, nonce };
-		let message_payload = parser
-			.read_and_decode_message_payload(&message_key)
-			.map_err(VerificationError::MessageStorage)?;
-		messages.push(Message { key: message_key, payload: message_payload });
-	}
+	let proved_lane_messages = read_lane_messages(&mut parser, lane, nonces_range)?;
 
-	// Now let's check if proof contains outbound lane state proof. It is optional, so
-	// we simply ignore `read_value` errors and missing value.
-	let proved_lane_messages = ProvedLaneMessages {
-		lane_state: parser
-			.read_and_decode_outbound_lane_data(&lane)
-			.map_err(VerificationError::OutboundLaneStorage)?,
-		messages,
-	};
+	// Check that the storage proof doesn't have any untouched keys.
+	parser.ensure_no_unused_keys().map_err(VerificationError::StorageProof)?;
 
-	// Now we may actually check if the proof is empty or not.
-	if proved_lane_messages.lane_state.is_none() && proved_lane_messages.messages.is_empty() {
+	Ok((lane, proved_lane_messages))
+}
+
+/// Verify proof of Bridged -> This chain messages, sent over multiple lanes.
+///
+/// The `messages_count` argument verification (sane limits) is supposed to be made
+/// outside of this function. This function only verifies that the proof declares exactly
+/// `messages_count` messages at all lanes.
+pub fn verify_multi_lane_messages_proof<T: Config<I>, I: 'static>(
+	proof: FromBridgedChainMultiLaneMessagesProof<HashOf<BridgedChainOf<T, I>>, T::LaneId>,
+	messages_count: u32,
+) -> Result<Vec<ProvedMessages<T::LaneId, Message<T::LaneId>>>, VerificationError> {
+	let FromBridgedChainMultiLaneMessagesProof { bridged_header_hash, storage_proof, lanes } =
+		proof;
+	if lanes.is_empty() {
 		return Err(VerificationError::EmptyMessageProof)
 	}
+	ensure_no_duplicate_lanes(lanes.iter().map(|range| &range.lane))?;
+
+	let mut parser: MessagesStorageProofAdapter<T, I> =
+		MessagesStorageProofAdapter::try_new_with_verified_storage_proof(
+			bridged_header_hash,
+			storage_proof,
+		)
+		.map_err(VerificationError::HeaderChain)?;
+
+	// receiving proofs where end < begin is ok (if proof includes outbound lane state)
+	let messages_in_the_proof = lanes.iter().fold(0 as MessageNonce, |total, range| {
+		total.saturating_add((range.nonces_start..=range.nonces_end).saturating_len())
+	});
+	if messages_in_the_proof != MessageNonce::from(messages_count) {
+		return Err(VerificationError::MessagesCountMismatch)
+	}
+
+	let mut proved_messages = Vec::with_capacity(lanes.len());
+	for LaneMessagesRange { lane, nonces_start, nonces_end } in lanes {
+		let proved_lane_messages =
+			read_lane_messages(&mut parser, lane, nonces_start..=nonces_end)?;
+		proved_messages.push((lane, proved_lane_messages));
+	}
 
 	// Check that the storage proof doesn't have any untouched keys.
 	parser.ensure_no_unused_keys().map_err(VerificationError::StorageProof)?;
 
-	Ok((lane, proved_lane_messages))
+	Ok(proved_messages)
 }
 
 /// Verify proof of This -> Bridged chain messages delivery.
@@ -114,12 +138,8 @@ pub fn verify_messages_delivery_proof<T: Config<I>, I: 'static>(
 		.map_err(VerificationError::HeaderChain)?;
 	// Messages delivery proof is just proof of single storage key read => any error
 	// is fatal.
-	let storage_inbound_lane_data_key = bp_messages::storage_keys::inbound_lane_data_key(
-		T::ThisChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
-		&lane,
-	);
 	let inbound_lane_data = parser
-		.read_and_decode_mandatory_value(&storage_inbound_lane_data_key)
+		.read_and_decode_inbound_lane_data(&lane)
 		.map_err(VerificationError::InboundLaneStorage)?;
 
 	// check that the storage proof doesn't have any untouched trie nodes
@@ -128,6 +148,91 @@ pub fn verify_messages_delivery_proof<T: Config<I>, I: 'static>(
 	Ok((lane, inbound_lane_data))
 }
 
+/// Verify proof of This -> Bridged chain messages delivery over multiple lanes.
+pub fn verify_multi_lane_messages_delivery_proof<T: Config<I>, I: 'static>(
+	proof: FromBridgedChainMultiLaneMessagesDeliveryProof<HashOf<BridgedChainOf<T, I>>, T::LaneId>,
+) -> Result<Vec<ParsedMessagesDeliveryProofFromBridgedChain<T, I>>, VerificationError> {
+	let FromBridgedChainMultiLaneMessagesDeliveryProof {
+		bridged_header_hash,
+		storage_proof,
+		lanes,
+	} = proof;
+	if lanes.is_empty() {
+		return Err(VerificationError::EmptyMessageProof)
+	}
+	ensure_no_duplicate_lanes(lanes.iter())?;
+
+	let mut parser: MessagesStorageProofAdapter<T, I> =
+		MessagesStorageProofAdapter::try_new_with_verified_storage_proof(
+			bridged_header_hash,
+			storage_proof,
+		)
+		.map_err(VerificationError::HeaderChain)?;
+	// Every lane state must be in the proof => any error is fatal.
+	let mut lanes_data = Vec::with_capacity(lanes.len());
+	for lane in lanes {
+		let inbound_lane_data = parser
+			.read_and_decode_inbound_lane_data(&lane)
+			.map_err(VerificationError::InboundLaneStorage)?;
+		lanes_data.push((lane, inbound_lane_data));
+	}
+
+	// check that the storage proof doesn't have any untouched trie nodes
+	parser.ensure_no_unused_keys().map_err(VerificationError::StorageProof)?;
+
+	Ok(lanes_data)
+}
+
+/// Read messages and (optional) outbound lane state of the single lane from the storage proof.
+fn read_lane_messages<T: Config<I>, I: 'static>(
+	parser: &mut MessagesStorageProofAdapter<T, I>,
+	lane: T::LaneId,
+	nonces_range: RangeInclusive<MessageNonce>,
+) -> Result<ProvedLaneMessages<Message<T::LaneId>>, VerificationError> {
+	// Read messages first. All messages that are claimed to be in the proof must
+	// be in the proof. So any error in `read_value`, or even missing value is fatal.
+	//
+	// Mind that we allow proofs with no messages if outbound lane state is proved.
+	let mut messages = Vec::with_capacity(nonces_range.saturating_len() as _);
+	for nonce in nonces_range {
+		let message_key = MessageKey { lane_id: lane, nonce };
+		let message_payload = parser
+			.read_and_decode_message_payload(&message_key)
+			.map_err(VerificationError::MessageStorage)?;
+		messages.push(Message { key: message_key, payload: message_payload });
+	}
+
+	// Now let's check if proof contains outbound lane state proof. It is optional, so
+	// we simply ignore `read_value` errors and missing value.
+	let proved_lane_messages = ProvedLaneMessages {
+		lane_state: parser
+			.read_and_decode_outbound_lane_data(&lane)
+			.map_err(VerificationError::OutboundLaneStorage)?,
+		messages,
+	};
+
+	// Now we may actually check if the proof is empty or not.
+	if proved_lane_messages.lane_state.is_none() && proved_lane_messages.messages.is_empty() {
+		return Err(VerificationError::EmptyMessageProof)
+	}
+
+	Ok(proved_lane_messages)
+}
+
+/// Ensure that every lane is mentioned at most once in the multi-lane proof.
+fn ensure_no_duplicate_lanes<'a, LaneId: 'a + Ord>(
+	lanes: impl Iterator<Item = &'a LaneId>,
+) -> Result<(), VerificationError> {
+	let mut seen_lanes = BTreeSet::new();
+	for lane in lanes {
+		if !seen_lanes.insert(lane) {
+			return Err(VerificationError::DuplicateLane)
+		}
+	}
+
+	Ok(())
+}
+
 /// Abstraction over storage proof manipulation, hiding implementation details of actual storage
 /// proofs.
 trait StorageProofAdapter<T: Config<I>, I: 'static> {
@@ -141,6 +246,17 @@ trait StorageProofAdapter<T: Config<I>, I: 'static> {
 	) -> Result<Option<D>, StorageProofError>;
 	fn ensure_no_unused_keys(self) -> Result<(), StorageProofError>;
 
+	fn read_and_decode_inbound_lane_data(
+		&mut self,
+		lane_id: &T::LaneId,
+	) -> Result<InboundLaneData<T::AccountId>, StorageProofError> {
+		let storage_inbound_lane_data_key = bp_messages::storage_keys::inbound_lane_data_key(
+			T::ThisChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+			lane_id,
+		);
+		self.read_and_decode_mandatory_value(&storage_inbound_lane_data_key)
+	}
+
 	fn read_and_decode_outbound_lane_data(
 		&mut self,
 		lane_id: &T::LaneId,
@@ -169,3 +169,96 @@ where
 
 	(root, storage_proof)
 }
+
+/// Prepare storage proof of messages, sent over multiple lanes.
+///
+/// Returns state trie root and nodes with prepared messages and (optional) outbound lane states.
+pub fn prepare_multi_lane_messages_storage_proof<
+	BridgedChain: Chain,
+	ThisChain: ChainWithMessages,
+	LaneId: Encode + Copy,
+>(
+	lanes: Vec<(LaneId, RangeInclusive<MessageNonce>, Option<OutboundLaneData>)>,
+	generate_message: impl Fn(LaneId, MessageNonce) -> MessagePayload,
+) -> (HashOf<BridgedChain>, RawStorageProof)
+where
+	HashOf<BridgedChain>: Copy + Default,
+{
+	let mut root = Default::default();
+	let mut mdb = MemoryDB::default();
+	{
+		let mut trie =
+			TrieDBMutBuilderV1::<HasherOf<BridgedChain>>::new(&mut mdb, &mut root).build();
+		for (lane, message_nonces, outbound_lane_data) in lanes {
+			// insert messages
+			for nonce in message_nonces {
+				let storage_key = storage_keys::message_key(
+					ThisChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+					&lane,
+					nonce,
+				)
+				.0;
+				trie.insert(&storage_key, &generate_message(lane, nonce).encode())
+					.map_err(|_| "TrieMut::insert has failed")
+					.expect("TrieMut::insert should not fail in tests");
+			}
+
+			// insert outbound lane state
+			if let Some(outbound_lane_data) = outbound_lane_data {
+				let storage_key = storage_keys::outbound_lane_data_key(
+					ThisChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+					&lane,
+				)
+				.0;
+				trie.insert(&storage_key, &outbound_lane_data.encode())
+					.map_err(|_| "TrieMut::insert has failed")
+					.expect("TrieMut::insert should not fail in tests");
+			}
+		}
+	}
+
+	// generate storage proof to be delivered to This chain
+	let storage_proof = record_all_trie_keys::<LayoutV1<HasherOf<BridgedChain>>, _>(&mdb, &root)
+		.map_err(|_| "record_all_trie_keys has failed")
+		.expect("record_all_trie_keys should not fail in tests");
+
+	(root, storage_proof)
+}
+
+/// Prepare storage proof of messages delivery over multiple lanes.
+///
+/// Returns state trie root and nodes with prepared inbound lane states.
+pub fn prepare_multi_lane_message_delivery_storage_proof<
+	BridgedChain: Chain,
+	ThisChain: ChainWithMessages,
+	LaneId: Encode,
+>(
+	lanes: Vec<(LaneId, InboundLaneData<AccountIdOf<ThisChain>>)>,
+) -> (HashOf<BridgedChain>, RawStorageProof)
+where
+	HashOf<BridgedChain>: Copy + Default,
+{
+	let mut root = Default::default();
+	let mut mdb = MemoryDB::default();
+	{
+		let mut trie =
+			TrieDBMutBuilderV1::<HasherOf<BridgedChain>>::new(&mut mdb, &mut root).build();
+		for (lane, inbound_lane_data) in lanes {
+			let storage_key = storage_keys::inbound_lane_data_key(
+				ThisChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+				&lane,
+			)
+			.0;
+			trie.insert(&storage_key, &inbound_lane_data.encode())
+				.map_err(|_| "TrieMut::insert has failed")
+				.expect("TrieMut::insert should not fail in tests");
+		}
+	}
+
+	// generate storage proof to be delivered to This chain
+	let storage_proof = record_all_trie_keys::<LayoutV1<HasherOf<BridgedChain>>, _>(&mdb, &root)
+		.map_err(|_| "record_all_trie_keys has failed")
+		.expect("record_all_trie_keys should not fail in tests");
+
+	(root, storage_proof)
+}
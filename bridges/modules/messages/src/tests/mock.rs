@@ -20,7 +20,8 @@
 use crate::{
 	tests::messages_generation::{
 		encode_all_messages, encode_lane_data, prepare_message_delivery_storage_proof,
-		prepare_messages_storage_proof,
+		prepare_messages_storage_proof, prepare_multi_lane_message_delivery_storage_proof,
+		prepare_multi_lane_messages_storage_proof,
 	},
 	Config, StoredMessagePayload,
 };
@@ -29,11 +30,12 @@ use bp_header_chain::{ChainWithGrandpa, StoredHeaderData};
 use bp_messages::{
 	calc_relayers_rewards,
 	source_chain::{
-		DeliveryConfirmationPayments, FromBridgedChainMessagesDeliveryProof, OnMessagesDelivered,
+		DeliveryConfirmationPayments, FromBridgedChainMessagesDeliveryProof,
+		FromBridgedChainMultiLaneMessagesDeliveryProof, OnMessagesDelivered,
 	},
 	target_chain::{
 		DeliveryPayments, DispatchMessage, DispatchMessageData, FromBridgedChainMessagesProof,
-		MessageDispatch,
+		FromBridgedChainMultiLaneMessagesProof, LaneMessagesRange, MessageDispatch,
 	},
 	ChainWithMessages, DeliveredMessages, HashedLaneId, InboundLaneData, LaneIdType, LaneState,
 	Message, MessageKey, MessageNonce, OutboundLaneData, UnrewardedRelayer,
@@ -180,6 +182,7 @@ impl pallet_bridge_grandpa::Config for TestRuntime {
 	type BridgedChain = BridgedChain;
 	type MaxFreeHeadersPerBlock = ConstU32<4>;
 	type FreeHeadersInterval = ConstU32<1_024>;
+	type MaxWarpSyncFragments = ConstU32<4>;
 	type HeadersToKeep = ConstU32<8>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<TestRuntime>;
 }
@@ -276,6 +279,11 @@ pub fn closed_lane_id() -> TestLaneIdType {
 	TestLaneIdType::try_new(1, 4).unwrap()
 }
 
+/// Another opened lane, used in multi-lane tests.
+pub fn second_test_lane_id() -> TestLaneIdType {
+	TestLaneIdType::try_new(1, 5).unwrap()
+}
+
 /// Regular message payload.
 pub const REGULAR_PAYLOAD: TestPayload = message_payload(0, 50);
 
@@ -413,6 +421,15 @@ pub fn message(nonce: MessageNonce, payload: TestPayload) -> Message<TestLaneIdT
 	Message { key: MessageKey { lane_id: test_lane_id(), nonce }, payload: payload.encode() }
 }
 
+/// Return message with given nonce and payload, sent over given lane.
+pub fn lane_message(
+	lane_id: TestLaneIdType,
+	nonce: MessageNonce,
+	payload: TestPayload,
+) -> Message<TestLaneIdType> {
+	Message { key: MessageKey { lane_id, nonce }, payload: payload.encode() }
+}
+
 /// Return valid outbound message data, constructed from given payload.
 pub fn outbound_message_data(payload: TestPayload) -> StoredMessagePayload<TestRuntime, ()> {
 	StoredMessagePayload::<TestRuntime, ()>::try_from(payload.encode()).expect("payload too large")
@@ -556,3 +573,76 @@ pub fn prepare_messages_delivery_proof(
 		lane,
 	}
 }
+
+/// Prepare valid storage proof for given messages of multiple lanes and insert appropriate header
+/// to the bridged header chain.
+///
+/// Messages of every lane must be sorted by nonce. Since this function changes the runtime
+/// storage, you can't "inline" it in the `asset_noop` macro calls.
+pub fn prepare_multi_lane_messages_proof(
+	lanes: Vec<Vec<Message<TestLaneIdType>>>,
+) -> Box<FromBridgedChainMultiLaneMessagesProof<BridgedHeaderHash, TestLaneIdType>> {
+	// first - let's generate storage proof
+	let lanes = lanes
+		.into_iter()
+		.map(|messages| {
+			let range = LaneMessagesRange {
+				lane: messages.first().unwrap().key.lane_id,
+				nonces_start: messages.first().unwrap().key.nonce,
+				nonces_end: messages.last().unwrap().key.nonce,
+			};
+			(range, messages)
+		})
+		.collect::<Vec<_>>();
+	let (storage_root, storage_proof) =
+		prepare_multi_lane_messages_storage_proof::<BridgedChain, ThisChain, TestLaneIdType>(
+			lanes
+				.iter()
+				.map(|(range, _)| (range.lane, range.nonces_start..=range.nonces_end, None))
+				.collect(),
+			|lane, nonce| {
+				let (range, messages) = lanes.iter().find(|(range, _)| range.lane == lane).unwrap();
+				messages[(nonce - range.nonces_start) as usize].payload.clone()
+			},
+		);
+
+	// let's now insert bridged chain header into the storage
+	let bridged_header_hash = Default::default();
+	pallet_bridge_grandpa::ImportedHeaders::<TestRuntime>::insert(
+		bridged_header_hash,
+		StoredHeaderData { number: 0, state_root: storage_root },
+	);
+
+	Box::new(FromBridgedChainMultiLaneMessagesProof::<BridgedHeaderHash, TestLaneIdType> {
+		bridged_header_hash,
+		storage_proof,
+		lanes: lanes.into_iter().map(|(range, _)| range).collect(),
+	})
+}
+
+/// Prepare valid storage proof of given inbound lanes states and insert appropriate header to the
+/// bridged header chain.
+pub fn prepare_multi_lane_messages_delivery_proof(
+	lanes: Vec<(TestLaneIdType, InboundLaneData<AccountId>)>,
+) -> FromBridgedChainMultiLaneMessagesDeliveryProof<BridgedHeaderHash, TestLaneIdType> {
+	// first - let's generate storage proof
+	let lane_ids = lanes.iter().map(|(lane, _)| *lane).collect();
+	let (storage_root, storage_proof) = prepare_multi_lane_message_delivery_storage_proof::<
+		BridgedChain,
+		ThisChain,
+		TestLaneIdType,
+	>(lanes);
+
+	// let's now insert bridged chain header into the storage
+	let bridged_header_hash = Default::default();
+	pallet_bridge_grandpa::ImportedHeaders::<TestRuntime>::insert(
+		bridged_header_hash,
+		StoredHeaderData { number: 0, state_root: storage_root },
+	);
+
+	FromBridgedChainMultiLaneMessagesDeliveryProof::<BridgedHeaderHash, TestLaneIdType> {
+		bridged_header_hash,
+		storage_proof,
+		lanes: lane_ids,
+	}
+}
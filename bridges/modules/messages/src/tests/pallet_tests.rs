@@ -1230,3 +1230,152 @@ fn do_try_state_for_outbound_lanes_works() {
 		assert_ok!(Pallet::<TestRuntime>::do_try_state());
 	})
 }
+
+fn open_second_test_lane() {
+	InboundLanes::<TestRuntime>::insert(second_test_lane_id(), InboundLaneData::opened());
+	OutboundLanes::<TestRuntime>::insert(second_test_lane_id(), OutboundLaneData::opened());
+}
+
+fn inbound_lane_data_with_single_delivered_message() -> InboundLaneData<AccountId> {
+	InboundLaneData {
+		state: LaneState::Opened,
+		last_confirmed_nonce: 1,
+		relayers: vec![UnrewardedRelayer { relayer: 0, messages: DeliveredMessages::new(1) }]
+			.into(),
+	}
+}
+
+fn single_delivered_message_relayers_state() -> UnrewardedRelayersState {
+	UnrewardedRelayersState {
+		unrewarded_relayer_entries: 1,
+		messages_in_oldest_entry: 1,
+		total_messages: 1,
+		last_delivered_nonce: 1,
+	}
+}
+
+#[test]
+fn receive_multi_lane_messages_proof_works() {
+	run_test(|| {
+		open_second_test_lane();
+
+		assert_ok!(Pallet::<TestRuntime>::receive_multi_lane_messages_proof(
+			RuntimeOrigin::signed(1),
+			TEST_RELAYER_A,
+			prepare_multi_lane_messages_proof(vec![
+				vec![message(1, REGULAR_PAYLOAD), message(2, REGULAR_PAYLOAD)],
+				vec![lane_message(second_test_lane_id(), 1, REGULAR_PAYLOAD)],
+			]),
+			3,
+			REGULAR_PAYLOAD.declared_weight * 3,
+		));
+
+		assert_eq!(
+			InboundLanes::<TestRuntime>::get(test_lane_id())
+				.unwrap()
+				.0
+				.last_delivered_nonce(),
+			2
+		);
+		assert_eq!(
+			InboundLanes::<TestRuntime>::get(second_test_lane_id())
+				.unwrap()
+				.0
+				.last_delivered_nonce(),
+			1
+		);
+
+		assert!(TestDeliveryPayments::is_reward_paid(1));
+	});
+}
+
+#[test]
+fn receive_multi_lane_messages_proof_rejects_duplicate_lanes() {
+	run_test(|| {
+		let proof = prepare_multi_lane_messages_proof(vec![
+			vec![message(1, REGULAR_PAYLOAD)],
+			vec![message(1, REGULAR_PAYLOAD)],
+		]);
+		assert_noop!(
+			Pallet::<TestRuntime>::receive_multi_lane_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				proof,
+				2,
+				REGULAR_PAYLOAD.declared_weight * 2,
+			),
+			Error::<TestRuntime, ()>::InvalidMessagesProof,
+		);
+	});
+}
+
+#[test]
+fn receive_multi_lane_messages_proof_rejects_proof_if_any_lane_is_not_opened() {
+	run_test(|| {
+		let proof = prepare_multi_lane_messages_proof(vec![
+			vec![lane_message(closed_lane_id(), 1, REGULAR_PAYLOAD)],
+			vec![message(1, REGULAR_PAYLOAD)],
+		]);
+		assert_noop!(
+			Pallet::<TestRuntime>::receive_multi_lane_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				proof,
+				2,
+				REGULAR_PAYLOAD.declared_weight * 2,
+			),
+			Error::<TestRuntime, ()>::LanesManager(LanesManagerError::ClosedInboundLane),
+		);
+	});
+}
+
+#[test]
+fn receive_multi_lane_messages_delivery_proof_works() {
+	run_test(|| {
+		open_second_test_lane();
+		send_regular_message(test_lane_id());
+		send_regular_message(second_test_lane_id());
+
+		assert_ok!(Pallet::<TestRuntime>::receive_multi_lane_messages_delivery_proof(
+			RuntimeOrigin::signed(1),
+			prepare_multi_lane_messages_delivery_proof(vec![
+				(test_lane_id(), inbound_lane_data_with_single_delivered_message()),
+				(second_test_lane_id(), inbound_lane_data_with_single_delivered_message()),
+			]),
+			vec![
+				single_delivered_message_relayers_state(),
+				single_delivered_message_relayers_state()
+			],
+		));
+		assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+		for lane_id in [test_lane_id(), second_test_lane_id()] {
+			assert_eq!(
+				OutboundLanes::<TestRuntime, ()>::get(lane_id).unwrap().latest_received_nonce,
+				1,
+			);
+		}
+	});
+}
+
+#[test]
+fn receive_multi_lane_messages_delivery_proof_rejects_proof_if_relayers_states_are_missing() {
+	run_test(|| {
+		open_second_test_lane();
+		send_regular_message(test_lane_id());
+		send_regular_message(second_test_lane_id());
+
+		let proof = prepare_multi_lane_messages_delivery_proof(vec![
+			(test_lane_id(), inbound_lane_data_with_single_delivered_message()),
+			(second_test_lane_id(), inbound_lane_data_with_single_delivered_message()),
+		]);
+		assert_noop!(
+			Pallet::<TestRuntime>::receive_multi_lane_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				proof,
+				vec![single_delivered_message_relayers_state()],
+			),
+			Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+		);
+	});
+}
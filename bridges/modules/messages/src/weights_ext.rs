@@ -77,6 +77,10 @@ pub fn ensure_weights_are_correct<W: WeightInfoExt>() {
 	assert_ne!(receive_messages_delivery_proof_weight.proof_size(), 0);
 	messages_delivery_proof_size_does_not_affect_proof_size::<W>();
 	total_messages_in_delivery_proof_does_not_affect_proof_size::<W>();
+
+	// verify multi-lane calls weight components
+	ensure_weight_components_are_not_zero(W::receive_messages_proof_lane_overhead());
+	ensure_weight_components_are_not_zero(W::receive_messages_delivery_proof_lane_overhead());
 }
 
 /// Ensure that we are able to dispatch maximal size messages.
@@ -356,6 +360,46 @@ pub trait WeightInfoExt: WeightInfo {
 			.saturating_add(proof_size_overhead)
 	}
 
+	/// Weight of multi-lane message delivery extrinsic.
+	fn receive_multi_lane_messages_proof_weight(
+		proof: &impl Size,
+		lanes_count: u32,
+		messages_count: u32,
+		dispatch_weight: Weight,
+	) -> Weight {
+		// the single lane weight already includes the transaction overhead and the first lane
+		let single_lane_weight =
+			Self::receive_messages_proof_weight(proof, messages_count, dispatch_weight);
+		let extra_lanes_weight = Self::receive_messages_proof_lane_overhead()
+			.saturating_mul(lanes_count.saturating_sub(1) as _);
+
+		single_lane_weight.saturating_add(extra_lanes_weight)
+	}
+
+	/// Weight of multi-lane confirmation delivery extrinsic.
+	fn receive_multi_lane_messages_delivery_proof_weight(
+		proof: &impl Size,
+		relayers_states: &[UnrewardedRelayersState],
+	) -> Weight {
+		// the single lane weight already includes the transaction overhead and the first lane
+		let total_relayers_state =
+			relayers_states.iter().fold(UnrewardedRelayersState::default(), |total, state| {
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: total
+						.unrewarded_relayer_entries
+						.saturating_add(state.unrewarded_relayer_entries),
+					total_messages: total.total_messages.saturating_add(state.total_messages),
+					..Default::default()
+				}
+			});
+		let single_lane_weight =
+			Self::receive_messages_delivery_proof_weight(proof, &total_relayers_state);
+		let extra_lanes_weight = Self::receive_messages_delivery_proof_lane_overhead()
+			.saturating_mul(relayers_states.len().saturating_sub(1) as _);
+
+		single_lane_weight.saturating_add(extra_lanes_weight)
+	}
+
 	// Functions that are used by extrinsics weights formulas.
 
 	/// Returns weight that needs to be accounted when message delivery transaction
@@ -367,6 +411,26 @@ pub trait WeightInfoExt: WeightInfo {
 		weight_of_single_message_and_lane_state.saturating_sub(weight_of_single_message)
 	}
 
+	/// Returns weight that needs to be accounted for every additional lane of the multi-lane
+	/// message delivery transaction (`receive_multi_lane_messages_proof`).
+	///
+	/// We don't have a separate benchmark for that, so we assume that every additional lane
+	/// costs the same as the single message delivery transaction with outbound lane state. It
+	/// overestimates the actual cost, but includes all storage reads and writes of the lane.
+	fn receive_messages_proof_lane_overhead() -> Weight {
+		Self::receive_single_message_proof_with_outbound_lane_state()
+	}
+
+	/// Returns weight that needs to be accounted for every additional lane of the multi-lane
+	/// delivery confirmation transaction (`receive_multi_lane_messages_delivery_proof`).
+	///
+	/// We don't have a separate benchmark for that, so we assume that every additional lane
+	/// costs the same as the confirmation transaction for a single message. It overestimates
+	/// the actual cost, but includes all storage reads and writes of the lane.
+	fn receive_messages_delivery_proof_lane_overhead() -> Weight {
+		Self::receive_delivery_proof_for_single_message()
+	}
+
 	/// Returns weight overhead of delivery confirmation transaction
 	/// (`receive_messages_delivery_proof`).
 	fn receive_messages_delivery_proof_overhead() -> Weight {
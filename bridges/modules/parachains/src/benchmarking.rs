@@ -17,11 +17,11 @@
 //! Parachains finality pallet benchmarking.
 
 use crate::{
-	weights_ext::DEFAULT_PARACHAIN_HEAD_SIZE, Call, RelayBlockHash, RelayBlockHasher,
-	RelayBlockNumber,
+	weights_ext::DEFAULT_PARACHAIN_HEAD_SIZE, Call, ParaHeadsProofOf, RelayBlockHash,
+	RelayBlockHasher, RelayBlockNumber,
 };
 
-use bp_polkadot_core::parachains::{ParaHash, ParaHeadsProof, ParaId};
+use bp_polkadot_core::parachains::{ParaHash, ParaId};
 use bp_runtime::UnverifiedStorageProofParams;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
 use frame_system::RawOrigin;
@@ -31,7 +31,9 @@ use sp_std::prelude::*;
 pub struct Pallet<T: Config<I>, I: 'static = ()>(crate::Pallet<T, I>);
 
 /// Trait that must be implemented by runtime to benchmark the parachains finality pallet.
-pub trait Config<I: 'static>: crate::Config<I> {
+pub trait Config<I: 'static>:
+	crate::Config<I> + pallet_bridge_grandpa::Config<Self::BridgesGrandpaPalletInstance>
+{
 	/// Returns vector of supported parachains.
 	fn parachains() -> Vec<ParaId>;
 	/// Generate parachain heads proof and prepare environment for verifying this proof.
@@ -39,7 +41,7 @@ pub trait Config<I: 'static>: crate::Config<I> {
 		parachains: &[ParaId],
 		parachain_head_size: u32,
 		proof_params: UnverifiedStorageProofParams,
-	) -> (RelayBlockNumber, RelayBlockHash, ParaHeadsProof, Vec<(ParaId, ParaHash)>);
+	) -> (RelayBlockNumber, RelayBlockHash, ParaHeadsProofOf<Self, I>, Vec<(ParaId, ParaHash)>);
 }
 
 benchmarks_instance_pallet! {
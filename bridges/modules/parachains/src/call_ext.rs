@@ -14,15 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{Config, GrandpaPalletOf, Pallet, RelayBlockNumber};
-use bp_header_chain::HeaderChain;
-use bp_parachains::{BestParaHeadHash, SubmitParachainHeadsInfo};
+use crate::{Config, Pallet, RelayBlockNumber};
+use bp_parachains::{BestParaHeadHash, RelayHeadersSource, SubmitParachainHeadsInfo};
 use bp_runtime::{HeaderId, OwnedBridgeModule};
-use frame_support::{
-	dispatch::CallableCallFor,
-	traits::{Get, IsSubType},
-};
-use pallet_bridge_grandpa::SubmitFinalityProofHelper;
+use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
 use sp_runtime::{
 	traits::Zero,
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
@@ -59,8 +54,7 @@ impl<T: Config<I>, I: 'static> SubmitParachainHeadsHelper<T, I> {
 		}
 
 		// reject if no more free slots remaining in the block
-		if !SubmitFinalityProofHelper::<T, T::BridgesGrandpaPalletInstance>::has_free_header_slots()
-		{
+		if !T::RelayHeadersSource::has_free_header_slots() {
 			log::trace!(
 				target: crate::LOG_TARGET,
 				"The free parachain {:?} head can't be updated: no more free slots \
@@ -73,7 +67,7 @@ impl<T: Config<I>, I: 'static> SubmitParachainHeadsHelper<T, I> {
 
 		// if free headers interval is not configured and call is expected to execute
 		// for free => it is a relayer error, it should've been able to detect that.
-		let free_headers_interval = match T::FreeHeadersInterval::get() {
+		let free_headers_interval = match T::RelayHeadersSource::free_headers_interval() {
 			Some(free_headers_interval) => free_headers_interval,
 			None => return Ok(improved_by),
 		};
@@ -142,7 +136,7 @@ impl<T: Config<I>, I: 'static> SubmitParachainHeadsHelper<T, I> {
 
 		// let's check if our chain had no reorgs and we still know the relay chain header
 		// used to craft the proof
-		if GrandpaPalletOf::<T, I>::finalized_header_state_root(update.at_relay_block.1).is_none() {
+		if !T::RelayHeadersSource::is_known_relay_block(update.at_relay_block) {
 			log::trace!(
 				target: crate::LOG_TARGET,
 				"The parachain {:?} head can't be updated. Relay chain header {}/{} used to create \
@@ -20,6 +20,11 @@
 //! chain blocks. The main entry point of this module is `submit_parachain_heads`, which
 //! accepts storage proof of some parachain `Heads` entries from bridged relay chain.
 //! It requires corresponding relay headers to be already synced.
+//!
+//! The source of relay chain headers is configurable (see [`Config::RelayHeadersSource`]).
+//! By default, the [`GrandpaRelayHeaders`] is used, which verifies storage proofs against
+//! relay headers, imported by the GRANDPA module. Other sources may use different proofs -
+//! e.g. parachain heads may be proved by the BEEFY MMR leaf of the relay chain block.
 
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -29,14 +34,12 @@ pub use weights_ext::WeightInfoExt;
 
 use bp_header_chain::{HeaderChain, HeaderChainError};
 use bp_parachains::{
-	ParaInfo, ParaStoredHeaderData, RelayBlockHash, RelayBlockHasher, RelayBlockNumber,
-	SubmitParachainHeadsInfo,
+	ParaHeadsProofError, ParaHeadsReader, ParaInfo, ParaStoredHeaderData, RelayBlockHash,
+	RelayBlockHasher, RelayBlockNumber, RelayHeadersSource, SubmitParachainHeadsInfo,
 };
-use bp_polkadot_core::parachains::{ParaHash, ParaHeadsProof, ParaId};
+use bp_polkadot_core::parachains::{ParaHash, ParaId};
 use bp_runtime::{Chain, HashOf, HeaderId, HeaderIdOf, Parachain};
 use frame_support::{dispatch::PostDispatchInfo, DefaultNoBound};
-use pallet_bridge_grandpa::SubmitFinalityProofHelper;
-use proofs::{ParachainsStorageProofAdapter, StorageProofAdapter};
 use sp_std::{marker::PhantomData, vec::Vec};
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -49,6 +52,7 @@ use codec::Encode;
 // Re-export in crate namespace for `construct_runtime!`.
 pub use call_ext::*;
 pub use pallet::*;
+pub use proofs::GrandpaRelayHeaders;
 
 pub mod weights;
 pub mod weights_ext;
@@ -94,6 +98,9 @@ pub mod pallet {
 	/// Bridge GRANDPA pallet that is used to verify parachain proofs.
 	pub type GrandpaPalletOf<T, I> =
 		pallet_bridge_grandpa::Pallet<T, <T as Config<I>>::BridgesGrandpaPalletInstance>;
+	/// Parachain heads proof, accepted by the given parachains pallet.
+	pub type ParaHeadsProofOf<T, I> =
+		<<T as Config<I>>::RelayHeadersSource as RelayHeadersSource>::ParaHeadsProof;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -178,36 +185,40 @@ pub mod pallet {
 	}
 
 	#[pallet::config]
-	#[pallet::disable_frame_system_supertrait_check]
-	pub trait Config<I: 'static = ()>:
-		BoundedBridgeGrandpaConfig<Self::BridgesGrandpaPalletInstance>
-	{
+	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self, I>>
 			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Benchmarks results from runtime we're plugged into.
 		type WeightInfo: WeightInfoExt;
 
-		/// Instance of bridges GRANDPA pallet (within this runtime) that this pallet is linked to.
+		/// Instance of bridges GRANDPA pallet (within this runtime) that this pallet is paired
+		/// with.
+		///
+		/// The pallet itself only works with the `RelayHeadersSource`. This instance is used by
+		/// the [`GrandpaRelayHeaders`] source and by the relayers signed extension, which handles
+		/// batched relay chain and parachain finality calls.
+		type BridgesGrandpaPalletInstance: 'static;
+
+		/// Source of finalized relay chain blocks, used to verify parachain heads proofs.
 		///
-		/// The GRANDPA pallet instance must be configured to import headers of relay chain that
-		/// we're interested in.
+		/// Use [`GrandpaRelayHeaders`] to verify storage proofs against relay chain headers,
+		/// imported by the `BridgesGrandpaPalletInstance`.
 		///
-		/// The associated GRANDPA pallet is also used to configure free parachain heads
-		/// submissions. The parachain head submission will be free if:
+		/// The source is also used to configure free parachain heads submissions. The parachain
+		/// head submission will be free if:
 		///
 		/// 1) the submission contains exactly one parachain head update that succeeds;
 		///
 		/// 2) the difference between relay chain block numbers, used to prove new parachain head
-		///    and previous best parachain head is larger than the `FreeHeadersInterval`, configured
-		///    at the associated GRANDPA pallet;
+		///    and previous best parachain head is larger than the `free_headers_interval` of the
+		///    source;
 		///
-		/// 3) there are slots for free submissions, remaining at the block. This is also configured
-		///    at the associated GRANDPA pallet using `MaxFreeHeadersPerBlock` parameter.
+		/// 3) there are slots for free submissions, remaining at the block.
 		///
 		/// First parachain head submission is also free for the submitted, if free submissions
 		/// are yet accepted to this block.
-		type BridgesGrandpaPalletInstance: 'static;
+		type RelayHeadersSource: RelayHeadersSource;
 
 		/// Name of the original `paras` pallet in the `construct_runtime!()` call at the bridged
 		/// chain.
@@ -328,20 +339,18 @@ pub mod pallet {
 		/// The proof is supposed to be proof of some `Heads` entries from the
 		/// `polkadot-runtime-parachains::paras` pallet instance, deployed at the bridged chain.
 		/// The proof is supposed to be crafted at the `relay_header_hash` that must already be
-		/// imported by corresponding GRANDPA pallet at this chain.
+		/// known to the `RelayHeadersSource` at this chain.
 		///
 		/// The call fails if:
 		///
 		/// - the pallet is halted;
 		///
-		/// - the relay chain block `at_relay_block` is not imported by the associated bridge
-		///   GRANDPA pallet.
+		/// - the relay chain block `at_relay_block` is not known to the `RelayHeadersSource`.
 		///
 		/// The call may succeed, but some heads may not be updated e.g. because pallet knows
 		/// better head or it isn't tracked by the pallet.
 		#[pallet::call_index(0)]
-		#[pallet::weight(WeightInfoOf::<T, I>::submit_parachain_heads_weight(
-			T::DbWeight::get(),
+		#[pallet::weight(Pallet::<T, I>::submit_parachain_heads_weight(
 			parachain_heads_proof,
 			parachains.len() as _,
 		))]
@@ -349,7 +358,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			at_relay_block: (RelayBlockNumber, RelayBlockHash),
 			parachains: Vec<(ParaId, ParaHash)>,
-			parachain_heads_proof: ParaHeadsProof,
+			parachain_heads_proof: ParaHeadsProofOf<T, I>,
 		) -> DispatchResultWithPostInfo {
 			Self::submit_parachain_heads_ex(
 				origin,
@@ -386,14 +395,13 @@ pub mod pallet {
 		/// The proof is supposed to be proof of some `Heads` entries from the
 		/// `polkadot-runtime-parachains::paras` pallet instance, deployed at the bridged chain.
 		/// The proof is supposed to be crafted at the `relay_header_hash` that must already be
-		/// imported by corresponding GRANDPA pallet at this chain.
+		/// known to the `RelayHeadersSource` at this chain.
 		///
 		/// The call fails if:
 		///
 		/// - the pallet is halted;
 		///
-		/// - the relay chain block `at_relay_block` is not imported by the associated bridge
-		///   GRANDPA pallet.
+		/// - the relay chain block `at_relay_block` is not known to the `RelayHeadersSource`.
 		///
 		/// The call may succeed, but some heads may not be updated e.g. because pallet knows
 		/// better head or it isn't tracked by the pallet.
@@ -404,8 +412,7 @@ pub mod pallet {
 		/// be executed for free. If transaction extension is not used by the runtime, this
 		/// parameter is not used at all.
 		#[pallet::call_index(3)]
-		#[pallet::weight(WeightInfoOf::<T, I>::submit_parachain_heads_weight(
-			T::DbWeight::get(),
+		#[pallet::weight(Pallet::<T, I>::submit_parachain_heads_weight(
 			parachain_heads_proof,
 			parachains.len() as _,
 		))]
@@ -413,7 +420,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			at_relay_block: (RelayBlockNumber, RelayBlockHash),
 			parachains: Vec<(ParaId, ParaHash)>,
-			parachain_heads_proof: ParaHeadsProof,
+			parachain_heads_proof: ParaHeadsProofOf<T, I>,
 			_is_free_execution_expected: bool,
 		) -> DispatchResultWithPostInfo {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
@@ -421,37 +428,30 @@ pub mod pallet {
 
 			let total_parachains = parachains.len();
 			let free_headers_interval =
-				T::FreeHeadersInterval::get().unwrap_or(RelayBlockNumber::MAX);
+				T::RelayHeadersSource::free_headers_interval().unwrap_or(RelayBlockNumber::MAX);
 			// the pallet allows two kind of free submissions
-			// 1) if distance between all parachain heads is gte than the [`T::FreeHeadersInterval`]
+			// 1) if distance between all parachain heads is gte than the free headers interval
 			// 2) if all heads are the first heads of their parachains
 			let mut free_parachain_heads = 0;
 
 			// we'll need relay chain header to verify that parachains heads are always increasing.
 			let (relay_block_number, relay_block_hash) = at_relay_block;
-			let relay_block = pallet_bridge_grandpa::ImportedHeaders::<
-				T,
-				T::BridgesGrandpaPalletInstance,
-			>::get(relay_block_hash)
-			.ok_or(Error::<T, I>::UnknownRelayChainBlock)?;
-			ensure!(
-				relay_block.number == relay_block_number,
-				Error::<T, I>::InvalidRelayChainBlockNumber,
-			);
 
-			// now parse storage proof and read parachain heads
-			let mut actual_weight = WeightInfoOf::<T, I>::submit_parachain_heads_weight(
-				T::DbWeight::get(),
-				&parachain_heads_proof,
-				parachains.len() as _,
-			);
+			// now verify the proof and read parachain heads
+			let mut actual_weight =
+				Self::submit_parachain_heads_weight(&parachain_heads_proof, parachains.len() as _);
 
-			let mut storage: ParachainsStorageProofAdapter<T, I> =
-				ParachainsStorageProofAdapter::try_new_with_verified_storage_proof(
-					relay_block_hash,
-					parachain_heads_proof.storage_proof,
-				)
-				.map_err(Error::<T, I>::HeaderChainStorageProof)?;
+			let mut storage = T::RelayHeadersSource::verify_para_heads_proof(
+				HeaderId(relay_block_number, relay_block_hash),
+				parachain_heads_proof,
+			)
+			.map_err(|e| match e {
+				ParaHeadsProofError::UnknownRelayChainBlock =>
+					Error::<T, I>::UnknownRelayChainBlock,
+				ParaHeadsProofError::InvalidRelayChainBlockNumber =>
+					Error::<T, I>::InvalidRelayChainBlockNumber,
+				ParaHeadsProofError::HeaderChain(e) => Error::<T, I>::HeaderChainStorageProof(e),
+			})?;
 
 			for (parachain, parachain_head_hash) in parachains {
 				let parachain_head = match storage.read_parachain_head(parachain) {
@@ -569,18 +569,17 @@ pub mod pallet {
 			// => treat this as an error
 			//
 			// (we can throw error here, because now all our calls are transactional)
-			storage.ensure_no_unused_keys().map_err(|e| {
+			storage.ensure_no_unused_heads().map_err(|e| {
 				Error::<T, I>::HeaderChainStorageProof(HeaderChainError::StorageProof(e))
 			})?;
 
 			// check if we allow this submission for free
-			let is_free = total_parachains == 1
-				&& free_parachain_heads == total_parachains
-				&& SubmitFinalityProofHelper::<T, T::BridgesGrandpaPalletInstance>::has_free_header_slots();
+			let is_free = total_parachains == 1 &&
+				free_parachain_heads == total_parachains &&
+				T::RelayHeadersSource::has_free_header_slots();
 			let pays_fee = if is_free {
 				log::trace!(target: LOG_TARGET, "Parachain heads update transaction is free");
-				pallet_bridge_grandpa::on_free_header_imported::<T, T::BridgesGrandpaPalletInstance>(
-				);
+				T::RelayHeadersSource::on_free_header_imported();
 				Pays::No
 			} else {
 				log::trace!(target: LOG_TARGET, "Parachain heads update transaction is paid");
@@ -592,6 +591,26 @@ pub mod pallet {
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Weight of the `submit_parachain_heads` call with given proof.
+		pub fn submit_parachain_heads_weight(
+			parachain_heads_proof: &ParaHeadsProofOf<T, I>,
+			parachains_count: u32,
+		) -> Weight {
+			match T::RelayHeadersSource::verify_para_heads_proof_weight(parachain_heads_proof) {
+				Some(proof_weight) =>
+					WeightInfoOf::<T, I>::submit_parachain_heads_weight_with_proof_weight(
+						T::DbWeight::get(),
+						proof_weight,
+						parachains_count,
+					),
+				None => WeightInfoOf::<T, I>::submit_parachain_heads_weight(
+					T::DbWeight::get(),
+					parachain_heads_proof,
+					parachains_count,
+				),
+			}
+		}
+
 		/// Get stored parachain info.
 		pub fn best_parachain_info(parachain: ParaId) -> Option<ParaInfo> {
 			ParasInfo::<T, I>::get(parachain)
@@ -790,28 +809,15 @@ pub fn initialize_for_benchmarks<T: Config<I>, I: 'static, PC: Parachain<Hash =
 	header: HeaderOf<PC>,
 ) {
 	use bp_polkadot_core::parachains::ParaHead;
-	use bp_runtime::HeaderIdProvider;
-	use sp_runtime::traits::Header;
-
-	let relay_head =
-		pallet_bridge_grandpa::BridgedHeader::<T, T::BridgesGrandpaPalletInstance>::new(
-			0,
-			Default::default(),
-			Default::default(),
-			Default::default(),
-			Default::default(),
-		);
+
 	let parachain = ParaId(PC::PARACHAIN_ID);
 	let parachain_head = ParaHead(header.encode());
 	let updated_head_data = T::ParaStoredHeaderDataBuilder::try_build(parachain, &parachain_head)
 		.expect("failed to build stored parachain head in benchmarks");
-	pallet_bridge_grandpa::initialize_for_benchmarks::<T, T::BridgesGrandpaPalletInstance>(
-		relay_head.clone(),
-	);
 	Pallet::<T, I>::update_parachain_head(
 		parachain,
 		None,
-		relay_head.id(),
+		HeaderId(0, Default::default()),
 		updated_head_data,
 		parachain_head.hash(),
 	)
@@ -833,7 +839,7 @@ pub(crate) mod tests {
 	use bp_parachains::{
 		BestParaHeadHash, BridgeParachainCall, ImportedParaHeadsKeyProvider, ParasInfoKeyProvider,
 	};
-	use bp_polkadot_core::parachains::ParaHead;
+	use bp_polkadot_core::parachains::{ParaHead, ParaHeadsProof};
 	use bp_runtime::{
 		BasicOperatingMode, OwnedBridgeModuleError, StorageDoubleMapKeyProvider,
 		StorageMapKeyProvider, StorageProofError,
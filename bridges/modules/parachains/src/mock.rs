@@ -188,6 +188,7 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance1> for TestRun
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeHeadersPerBlock = ConstU32<2>;
 	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxWarpSyncFragments = ConstU32<4>;
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
@@ -197,6 +198,7 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance2> for TestRun
 	type BridgedChain = TestBridgedChain;
 	type MaxFreeHeadersPerBlock = ConstU32<2>;
 	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxWarpSyncFragments = ConstU32<4>;
 	type HeadersToKeep = HeadersToKeep;
 	type WeightInfo = ();
 }
@@ -211,6 +213,7 @@ impl pallet_bridge_parachains::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type BridgesGrandpaPalletInstance = pallet_bridge_grandpa::Instance1;
+	type RelayHeadersSource = crate::GrandpaRelayHeaders<Self, ()>;
 	type ParasPalletName = ParasPalletName;
 	type ParaStoredHeaderDataBuilder = (Parachain1, Parachain2, Parachain3, BigParachain);
 	type HeadsToKeep = HeadsToKeep;
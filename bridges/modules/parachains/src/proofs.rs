@@ -16,13 +16,19 @@
 
 //! Tools for parachain head proof verification.
 
-use crate::{Config, GrandpaPalletOf, RelayBlockHash, RelayBlockHasher};
+use crate::{
+	BoundedBridgeGrandpaConfig, Config, GrandpaPalletOf, RelayBlockHash, RelayBlockHasher,
+	RelayBlockNumber,
+};
 use bp_header_chain::{HeaderChain, HeaderChainError};
-use bp_parachains::parachain_head_storage_key_at_source;
-use bp_polkadot_core::parachains::{ParaHead, ParaId};
-use bp_runtime::{RawStorageProof, StorageProofChecker, StorageProofError};
+use bp_parachains::{
+	parachain_head_storage_key_at_source, ParaHeadsProofError, ParaHeadsReader, RelayHeadersSource,
+};
+use bp_polkadot_core::parachains::{ParaHead, ParaHeadsProof, ParaId};
+use bp_runtime::{HeaderId, RawStorageProof, StorageProofChecker, StorageProofError};
 use codec::Decode;
 use frame_support::traits::Get;
+use pallet_bridge_grandpa::{Config as GrandpaConfig, SubmitFinalityProofHelper};
 
 /// Abstraction over storage proof manipulation, hiding implementation details of actual storage
 /// proofs.
@@ -56,7 +62,10 @@ pub struct RawStorageProofAdapter<T: Config<I>, I: 'static> {
 	_dummy: sp_std::marker::PhantomData<(T, I)>,
 }
 
-impl<T: Config<I>, I: 'static> RawStorageProofAdapter<T, I> {
+impl<T, I: 'static> RawStorageProofAdapter<T, I>
+where
+	T: Config<I> + BoundedBridgeGrandpaConfig<T::BridgesGrandpaPalletInstance>,
+{
 	/// Try to create a new instance of `RawStorageProofAdapter`.
 	pub fn try_new_with_verified_storage_proof(
 		relay_block_hash: RelayBlockHash,
@@ -79,3 +88,65 @@ impl<T: Config<I>, I: 'static> StorageProofAdapter<T, I> for RawStorageProofAdap
 		self.storage.ensure_no_unused_nodes()
 	}
 }
+
+impl<T: Config<I>, I: 'static> ParaHeadsReader for RawStorageProofAdapter<T, I> {
+	fn read_parachain_head(
+		&mut self,
+		parachain: ParaId,
+	) -> Result<Option<ParaHead>, StorageProofError> {
+		StorageProofAdapter::read_parachain_head(self, parachain)
+	}
+
+	fn ensure_no_unused_heads(self) -> Result<(), StorageProofError> {
+		self.ensure_no_unused_keys()
+	}
+}
+
+/// Relay headers source, backed by the bridge GRANDPA pallet.
+///
+/// Relay chain headers are imported by the `BridgesGrandpaPalletInstance` and parachain heads are
+/// proved by the storage proof of the `paras` pallet `Heads` map. Free parachain heads
+/// submissions share the `FreeHeadersInterval` and free header slots of the GRANDPA pallet.
+pub struct GrandpaRelayHeaders<T, I>(sp_std::marker::PhantomData<(T, I)>);
+
+impl<T, I: 'static> RelayHeadersSource for GrandpaRelayHeaders<T, I>
+where
+	T: Config<I> + BoundedBridgeGrandpaConfig<T::BridgesGrandpaPalletInstance>,
+{
+	type ParaHeadsProof = ParaHeadsProof;
+	type ParaHeadsReader = ParachainsStorageProofAdapter<T, I>;
+
+	fn is_known_relay_block(at: HeaderId<RelayBlockHash, RelayBlockNumber>) -> bool {
+		GrandpaPalletOf::<T, I>::finalized_header_state_root(at.1).is_some()
+	}
+
+	fn verify_para_heads_proof(
+		at: HeaderId<RelayBlockHash, RelayBlockNumber>,
+		proof: ParaHeadsProof,
+	) -> Result<Self::ParaHeadsReader, ParaHeadsProofError> {
+		let relay_block =
+			pallet_bridge_grandpa::ImportedHeaders::<T, T::BridgesGrandpaPalletInstance>::get(at.1)
+				.ok_or(ParaHeadsProofError::UnknownRelayChainBlock)?;
+		if relay_block.number != at.0 {
+			return Err(ParaHeadsProofError::InvalidRelayChainBlockNumber)
+		}
+
+		ParachainsStorageProofAdapter::try_new_with_verified_storage_proof(
+			at.1,
+			proof.storage_proof,
+		)
+		.map_err(ParaHeadsProofError::HeaderChain)
+	}
+
+	fn free_headers_interval() -> Option<RelayBlockNumber> {
+		<T as GrandpaConfig<T::BridgesGrandpaPalletInstance>>::FreeHeadersInterval::get()
+	}
+
+	fn has_free_header_slots() -> bool {
+		SubmitFinalityProofHelper::<T, T::BridgesGrandpaPalletInstance>::has_free_header_slots()
+	}
+
+	fn on_free_header_imported() {
+		pallet_bridge_grandpa::on_free_header_imported::<T, T::BridgesGrandpaPalletInstance>()
+	}
+}
@@ -85,6 +85,28 @@ pub trait WeightInfoExt: WeightInfo {
 			.saturating_add(Self::submit_parachain_heads_overhead_from_runtime())
 	}
 
+	/// Weight of the parachain heads delivery extrinsic, when heads are proved by the proof with
+	/// given verification weight, which is not covered by the pallet benchmarks.
+	fn submit_parachain_heads_weight_with_proof_weight(
+		db_weight: RuntimeDbWeight,
+		proof_weight: Weight,
+		parachains_count: u32,
+	) -> Weight {
+		// every parachain head update reads and writes `ParasInfo`, writes the head and its
+		// hash and (potentially, refunded if hasn't happened) prunes the old head
+		let update_weight = db_weight
+			.reads_writes(1, 1)
+			.saturating_add(Self::parachain_head_storage_write_weight(db_weight))
+			.saturating_add(Self::parachain_head_pruning_weight(db_weight))
+			.saturating_mul(parachains_count as u64);
+
+		// `PalletOperatingMode` is read once per call
+		proof_weight
+			.saturating_add(db_weight.reads(1))
+			.saturating_add(update_weight)
+			.saturating_add(Self::submit_parachain_heads_overhead_from_runtime())
+	}
+
 	/// Returns weight of single parachain head storage update.
 	///
 	/// This weight only includes db write operations that happens if parachain head is actually
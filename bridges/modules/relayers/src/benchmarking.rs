@@ -21,7 +21,7 @@
 use crate::*;
 
 use bp_relayers::RewardsAccountOwner;
-use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks_instance_pallet, whitelisted_caller};
 use frame_system::RawOrigin;
 use sp_runtime::traits::One;
 
@@ -44,6 +44,19 @@ pub trait Config<I: 'static = ()>: crate::Config<I> {
 	);
 	/// Give enough balance to given account.
 	fn deposit_account(account: Self::AccountId, balance: Self::Reward);
+	/// Prepare environment for paying reward to the beneficiary in the `claim_rewards_to`
+	/// benchmark and return this beneficiary.
+	///
+	/// If the `PaymentProcedure` supports paying rewards to other chains, the runtime must
+	/// return the remote beneficiary here, because it is the most expensive case.
+	fn bench_beneficiary() -> BeneficiaryOf<Self, I> {
+		account::<Self::AccountId>("beneficiary", 0, 0).into()
+	}
+	/// Reward that is claimed in the `claim_rewards_to` benchmark. It must cover all costs of
+	/// paying reward to the `bench_beneficiary` (e.g. XCM delivery fees).
+	fn bench_reward() -> Self::Reward {
+		Self::Reward::from(REWARD_AMOUNT)
+	}
 }
 
 benchmarks_instance_pallet! {
@@ -64,6 +77,24 @@ benchmarks_instance_pallet! {
 		// also completed successfully
 	}
 
+	// Benchmark `claim_rewards_to` call.
+	claim_rewards_to {
+		let lane = T::bench_lane_id();
+		let account_params =
+			RewardsAccountParams::new(lane, *b"test", RewardsAccountOwner::ThisChain);
+		let relayer: T::AccountId = whitelisted_caller();
+		let reward = T::bench_reward();
+		let beneficiary = T::bench_beneficiary();
+
+		T::prepare_rewards_account(account_params, reward);
+		RelayerRewards::<T, I>::insert(&relayer, account_params, reward);
+	}: _(RawOrigin::Signed(relayer), account_params, beneficiary)
+	verify {
+		// we can't check anything here, because `PaymentProcedure` is responsible for
+		// payment logic, so we assume that if call has succeeded, the procedure has
+		// also completed successfully
+	}
+
 	// Benchmark `register` call.
 	register {
 		let relayer: T::AccountId = whitelisted_caller();
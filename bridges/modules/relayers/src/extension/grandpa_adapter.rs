@@ -160,7 +160,7 @@ where
 			target: LOG_TARGET,
 			"{}.{:?}: relayer {:?} has submitted invalid GRANDPA chain finality proof",
 			C::IdProvider::STR,
-			call_info.messages_call_info().lane_id(),
+			call_info.messages_call_info().lanes(),
 			relayer,
 		);
 		return false
@@ -44,13 +44,16 @@ use pallet_transaction_payment::{
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	traits::{
+		AtLeast32BitUnsigned, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension,
+		Zero,
+	},
 	transaction_validity::{
 		TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
 	},
-	DispatchResult, RuntimeDebug,
+	DispatchResult, Perbill, RuntimeDebug,
 };
-use sp_std::{fmt::Debug, marker::PhantomData};
+use sp_std::{fmt::Debug, marker::PhantomData, vec::Vec};
 
 pub use grandpa_adapter::WithGrandpaChainExtensionConfig;
 pub use messages_adapter::WithMessagesExtensionConfig;
@@ -97,8 +100,9 @@ impl<AccountId, RemoteGrandpaChainBlockNumber: Debug, LaneId: Clone + Copy + Deb
 pub enum RelayerAccountAction<AccountId, Reward, LaneId> {
 	/// Do nothing with relayer account.
 	None,
-	/// Reward the relayer.
-	Reward(AccountId, RewardsAccountParams<LaneId>, Reward),
+	/// Reward the relayer. The reward is registered at given rewards accounts - multi-lane calls
+	/// are rewarded by every lane.
+	Reward(AccountId, Vec<(RewardsAccountParams<LaneId>, Reward)>),
 	/// Slash the relayer.
 	Slash(AccountId, RewardsAccountParams<LaneId>),
 }
@@ -154,7 +158,7 @@ where
 		};
 
 		// compute total number of messages in transaction
-		let bundled_messages = parsed_call.messages_call_info().bundled_messages().saturating_len();
+		let bundled_messages = parsed_call.messages_call_info().total_bundled_messages();
 
 		// a quick check to avoid invalid high-priority transactions
 		let max_unconfirmed_messages_in_confirmation_tx = <R as BridgeMessagesConfig<C::BridgeMessagesPalletInstance>>::BridgedChain
@@ -184,21 +188,32 @@ where
 		};
 
 		// now we know that the call is supported and we may need to reward or slash relayer
-		// => let's prepare the correspondent account that pays reward/receives slashed amount
-		let lane_id = call_info.messages_call_info().lane_id();
-		let reward_account_params = RewardsAccountParams::new(
-			lane_id,
-			<R as BridgeMessagesConfig<C::BridgeMessagesPalletInstance>>::BridgedChain::ID,
-			if call_info.is_receive_messages_proof_call() {
-				RewardsAccountOwner::ThisChain
-			} else {
-				RewardsAccountOwner::BridgedChain
-			},
-		);
+		// => let's prepare the correspondent accounts that pay reward/receive slashed amount
+		let lanes = call_info.messages_call_info().lanes();
+		let rewards_account_owner = if call_info.is_receive_messages_proof_call() {
+			RewardsAccountOwner::ThisChain
+		} else {
+			RewardsAccountOwner::BridgedChain
+		};
+		let lane_rewards_account_params = |lane_id| {
+			RewardsAccountParams::new(
+				lane_id,
+				<R as BridgeMessagesConfig<C::BridgeMessagesPalletInstance>>::BridgedChain::ID,
+				rewards_account_owner,
+			)
+		};
+		// multi-lane calls always have at least one lane (see `CallSubType::call_info`), so
+		// this is not expected to happen
+		let Some(reward_account_params) = lanes.first().copied().map(lane_rewards_account_params)
+		else {
+			return RelayerAccountAction::None
+		};
 
 		// prepare return value for the case if the call has failed or it has not caused
 		// expected side effects (e.g. not all messages have been accepted)
 		//
+		// multi-lane calls are slashed in favor of the first lane
+		//
 		// we are not checking if relayer is registered here - it happens during the slash attempt
 		//
 		// there are couple of edge cases here:
@@ -220,7 +235,7 @@ where
 				target: LOG_TARGET,
 				"{}.{:?}: relayer {:?} has submitted invalid messages transaction: {:?}",
 				Self::IDENTIFIER,
-				lane_id,
+				lanes,
 				relayer,
 				e,
 			);
@@ -259,8 +274,19 @@ where
 		post_info.actual_weight = Some(post_info_weight);
 		let refund = Self::compute_refund(info, &post_info, post_info_len, tip);
 
-		// we can finally reward relayer
-		RelayerAccountAction::Reward(relayer, reward_account_params, refund)
+		// we can finally reward relayer. The refund of multi-lane call is split between lanes
+		let rewards = split_refund(
+			refund,
+			call_info
+				.messages_call_info()
+				.bundled_messages()
+				.into_iter()
+				.map(|(lane_id, range)| {
+					(lane_rewards_account_params(lane_id), range.saturating_len())
+				})
+				.collect(),
+		);
+		RelayerAccountAction::Reward(relayer, rewards)
 	}
 
 	/// Compute refund for the successful relayer transaction
@@ -334,7 +360,7 @@ where
 			"{}.{:?}: has boosted priority of message delivery transaction \
 			of relayer {:?}: {} messages -> {} priority",
 			Self::IDENTIFIER,
-			parsed_call.as_ref().map(|p| p.messages_call_info().lane_id()),
+			parsed_call.as_ref().map(|p| p.messages_call_info().lanes()),
 			who,
 			bundled_messages,
 			priority_boost,
@@ -358,7 +384,7 @@ where
 				target: LOG_TARGET,
 				"{}.{:?}: parsed bridge transaction in pre-dispatch: {:?}",
 				Self::IDENTIFIER,
-				call_info.messages_call_info().lane_id(),
+				call_info.messages_call_info().lanes(),
 				call_info,
 			);
 			PreDispatchData { relayer: who.clone(), call_info }
@@ -372,25 +398,27 @@ where
 		len: usize,
 		result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let lane_id = pre
+		let lanes = pre
 			.as_ref()
 			.and_then(|p| p.as_ref())
-			.map(|p| p.call_info.messages_call_info().lane_id());
+			.map(|p| p.call_info.messages_call_info().lanes());
 		let call_result = Self::analyze_call_result(pre, info, post_info, len, result);
 
 		match call_result {
 			RelayerAccountAction::None => (),
-			RelayerAccountAction::Reward(relayer, reward_account, reward) => {
-				RelayersPallet::<R>::register_relayer_reward(reward_account, &relayer, reward);
-
-				log::trace!(
-					target: LOG_TARGET,
-					"{}.{:?}: has registered reward: {:?} for {:?}",
-					Self::IDENTIFIER,
-					lane_id,
-					reward,
-					relayer,
-				);
+			RelayerAccountAction::Reward(relayer, rewards) => {
+				for (reward_account, reward) in rewards {
+					RelayersPallet::<R>::register_relayer_reward(reward_account, &relayer, reward);
+
+					log::trace!(
+						target: LOG_TARGET,
+						"{}.{:?}: has registered reward: {:?} for {:?}",
+						Self::IDENTIFIER,
+						reward_account.lane_id(),
+						reward,
+						relayer,
+					);
+				}
 			},
 			RelayerAccountAction::Slash(relayer, slash_account) =>
 				RelayersPallet::<R>::slash_and_deregister(
@@ -403,6 +431,38 @@ where
 	}
 }
 
+/// Split the relayer refund between rewards accounts, proportionally to the number of
+/// messages, bundled at every lane. If there are no bundled messages, the refund is split
+/// equally. The remainder goes to the first account.
+fn split_refund<LaneId, Reward: AtLeast32BitUnsigned + Copy>(
+	refund: Reward,
+	lanes: Vec<(RewardsAccountParams<LaneId>, MessageNonce)>,
+) -> Vec<(RewardsAccountParams<LaneId>, Reward)> {
+	let lanes_count = lanes.len() as MessageNonce;
+	let total_messages = lanes
+		.iter()
+		.fold(0 as MessageNonce, |total, (_, messages)| total.saturating_add(*messages));
+	let mut rewards = lanes
+		.into_iter()
+		.map(|(params, messages)| {
+			let share = if total_messages == 0 {
+				Perbill::from_rational(1, lanes_count)
+			} else {
+				Perbill::from_rational(messages, total_messages)
+			};
+			(params, share.mul_floor(refund))
+		})
+		.collect::<Vec<_>>();
+	let distributed = rewards
+		.iter()
+		.fold(Reward::zero(), |total, (_, reward)| total.saturating_add(*reward));
+	if let Some((_, first_reward)) = rewards.first_mut() {
+		*first_reward = first_reward.saturating_add(refund.saturating_sub(distributed));
+	}
+
+	rewards
+}
+
 /// Verify that the messages pallet call, supported by extension has succeeded.
 pub(crate) fn verify_messages_call_succeeded<C>(
 	call_info: &ExtensionCallInfo<
@@ -425,7 +485,7 @@ where
 			target: LOG_TARGET,
 			"{}.{:?}: relayer {:?} has submitted invalid messages call",
 			C::IdProvider::STR,
-			call_info.messages_call_info().lane_id(),
+			call_info.messages_call_info().lanes(),
 			relayer,
 		);
 		return false
@@ -2369,4 +2429,27 @@ mod tests {
 			assert_eq!(priority_of_zero_messages_delivery, priority_of_one_messages_delivery);
 		});
 	}
+
+	#[test]
+	fn split_refund_works() {
+		let first = MsgProofsRewardsAccount::get();
+		let second = RewardsAccountParams::new(
+			TestLaneIdType::try_new(1, 3).unwrap(),
+			TEST_BRIDGED_CHAIN_ID,
+			RewardsAccountOwner::ThisChain,
+		);
+
+		// single lane gets everything
+		assert_eq!(split_refund(1000u64, vec![(first, 10)]), vec![(first, 1000)]);
+		// refund is split proportionally to the number of messages
+		assert_eq!(
+			split_refund(1000u64, vec![(first, 3), (second, 1)]),
+			vec![(first, 750), (second, 250)],
+		);
+		// refund is split equally if there are no messages and the remainder goes to first lane
+		assert_eq!(
+			split_refund(1001u64, vec![(first, 0), (second, 0)]),
+			vec![(first, 501), (second, 500)],
+		);
+	}
 }
@@ -178,7 +178,7 @@ where
 			target: LOG_TARGET,
 			"{}.{:?}: relayer {:?} has submitted invalid parachain finality proof",
 			C::IdProvider::STR,
-			call_info.messages_call_info().lane_id(),
+			call_info.messages_call_info().lanes(),
 			relayer,
 		);
 		return false
@@ -49,6 +49,12 @@ pub mod weights;
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-relayers";
 
+/// Beneficiary of the relayer reward payment, used by the pallet configuration.
+pub type BeneficiaryOf<T, I> = <<T as Config<I>>::PaymentProcedure as PaymentProcedure<
+	<T as frame_system::Config>::AccountId,
+	<T as Config<I>>::Reward,
+>>::Beneficiary;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -98,32 +104,7 @@ pub mod pallet {
 			rewards_account_params: RewardsAccountParams<T::LaneId>,
 		) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
-
-			RelayerRewards::<T, I>::try_mutate_exists(
-				&relayer,
-				rewards_account_params,
-				|maybe_reward| -> DispatchResult {
-					let reward = maybe_reward.take().ok_or(Error::<T, I>::NoRewardForRelayer)?;
-					T::PaymentProcedure::pay_reward(&relayer, rewards_account_params, reward)
-						.map_err(|e| {
-							log::trace!(
-								target: LOG_TARGET,
-								"Failed to pay {:?} rewards to {:?}: {:?}",
-								rewards_account_params,
-								relayer,
-								e,
-							);
-							Error::<T, I>::FailedToPayReward
-						})?;
-
-					Self::deposit_event(Event::<T, I>::RewardPaid {
-						relayer: relayer.clone(),
-						rewards_account_params,
-						reward,
-					});
-					Ok(())
-				},
-			)
+			Self::do_claim_rewards(relayer, rewards_account_params, None)
 		}
 
 		/// Register relayer or update its registration.
@@ -228,6 +209,21 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Claim accumulated rewards and pay them to the given beneficiary.
+		///
+		/// Depending on the `PaymentProcedure`, the beneficiary may be some other account at
+		/// this chain, or e.g. an account at some other chain, reachable over XCM.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::claim_rewards_to())]
+		pub fn claim_rewards_to(
+			origin: OriginFor<T>,
+			rewards_account_params: RewardsAccountParams<T::LaneId>,
+			beneficiary: BeneficiaryOf<T, I>,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			Self::do_claim_rewards(relayer, rewards_account_params, Some(beneficiary))
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -356,6 +352,55 @@ pub mod pallet {
 			);
 		}
 
+		/// Pay all rewards, accumulated by the relayer for serving given rewards account, to
+		/// the beneficiary or to the relayer itself, if the beneficiary is not specified.
+		fn do_claim_rewards(
+			relayer: T::AccountId,
+			rewards_account_params: RewardsAccountParams<T::LaneId>,
+			maybe_beneficiary: Option<BeneficiaryOf<T, I>>,
+		) -> DispatchResult {
+			let beneficiary = maybe_beneficiary.clone().unwrap_or_else(|| relayer.clone().into());
+			RelayerRewards::<T, I>::try_mutate_exists(
+				&relayer,
+				rewards_account_params,
+				|maybe_reward| -> DispatchResult {
+					let reward = maybe_reward.take().ok_or(Error::<T, I>::NoRewardForRelayer)?;
+					T::PaymentProcedure::pay_reward(
+						&relayer,
+						rewards_account_params,
+						reward,
+						beneficiary.clone(),
+					)
+					.map_err(|e| {
+						log::trace!(
+							target: LOG_TARGET,
+							"Failed to pay {:?} rewards of {:?} to {:?}: {:?}",
+							rewards_account_params,
+							relayer,
+							beneficiary,
+							e,
+						);
+						Error::<T, I>::FailedToPayReward
+					})?;
+
+					Self::deposit_event(match maybe_beneficiary {
+						Some(beneficiary) => Event::<T, I>::RewardPaidTo {
+							relayer: relayer.clone(),
+							rewards_account_params,
+							reward,
+							beneficiary,
+						},
+						None => Event::<T, I>::RewardPaid {
+							relayer: relayer.clone(),
+							rewards_account_params,
+							reward,
+						},
+					});
+					Ok(())
+				},
+			)
+		}
+
 		/// Return required registration lease.
 		pub(crate) fn required_registration_lease() -> BlockNumberFor<T> {
 			<T::StakeAndSlash as StakeAndSlash<
@@ -433,6 +478,17 @@ pub mod pallet {
 			/// Registration that was removed.
 			registration: Registration<BlockNumberFor<T>, T::Reward>,
 		},
+		/// Reward has been paid to the beneficiary, selected by the relayer.
+		RewardPaidTo {
+			/// Relayer account that has been rewarded.
+			relayer: T::AccountId,
+			/// Relayer has received reward from this account.
+			rewards_account_params: RewardsAccountParams<T::LaneId>,
+			/// Reward amount.
+			reward: T::Reward,
+			/// Beneficiary of the reward payment.
+			beneficiary: BeneficiaryOf<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -492,7 +548,7 @@ mod tests {
 	use bp_messages::LaneIdType;
 	use mock::{RuntimeEvent as TestEvent, *};
 
-	use crate::Event::{RewardPaid, RewardRegistered};
+	use crate::Event::{RewardPaid, RewardPaidTo, RewardRegistered};
 	use bp_relayers::RewardsAccountOwner;
 	use frame_support::{
 		assert_noop, assert_ok,
@@ -604,7 +660,63 @@ mod tests {
 					event: TestEvent::BridgeRelayers(RewardPaid {
 						relayer: REGULAR_RELAYER,
 						rewards_account_params: test_reward_account_param(),
-						reward: 100
+						reward: 100,
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_cant_claim_to_beneficiary_if_payment_procedure_fails() {
+		run_test(|| {
+			RelayerRewards::<TestRuntime>::insert(
+				REGULAR_RELAYER,
+				test_reward_account_param(),
+				100,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::claim_rewards_to(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					test_reward_account_param(),
+					FAILING_RELAYER,
+				),
+				Error::<TestRuntime>::FailedToPayReward,
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_can_claim_reward_to_beneficiary() {
+		run_test(|| {
+			get_ready_for_events();
+
+			RelayerRewards::<TestRuntime>::insert(
+				REGULAR_RELAYER,
+				test_reward_account_param(),
+				100,
+			);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards_to(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				test_reward_account_param(),
+				REGISTER_RELAYER,
+			));
+			assert_eq!(
+				RelayerRewards::<TestRuntime>::get(REGULAR_RELAYER, test_reward_account_param()),
+				None
+			);
+
+			// Check if the `RewardPaidTo` event was emitted.
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::BridgeRelayers(RewardPaidTo {
+						relayer: REGULAR_RELAYER,
+						rewards_account_params: test_reward_account_param(),
+						reward: 100,
+						beneficiary: REGISTER_RELAYER,
 					}),
 					topics: vec![],
 				}),
@@ -639,15 +751,22 @@ mod tests {
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 100);
 			assert_eq!(Balances::balance(&1), 0);
 
-			PayLaneRewardFromAccount::pay_reward(&1, in_lane_0, 100).unwrap();
+			PayLaneRewardFromAccount::pay_reward(&1, in_lane_0, 100, 1).unwrap();
 			assert_eq!(Balances::balance(&in_lane0_rewards_account), 0);
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 100);
 			assert_eq!(Balances::balance(&1), 100);
 
-			PayLaneRewardFromAccount::pay_reward(&1, out_lane_1, 100).unwrap();
+			PayLaneRewardFromAccount::pay_reward(&1, out_lane_1, 100, 1).unwrap();
 			assert_eq!(Balances::balance(&in_lane0_rewards_account), 0);
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 0);
 			assert_eq!(Balances::balance(&1), 200);
+
+			// the reward may also be paid to other account
+			Balances::mint_into(&in_lane0_rewards_account, 100).unwrap();
+			PayLaneRewardFromAccount::pay_reward(&1, in_lane_0, 100, 2).unwrap();
+			assert_eq!(Balances::balance(&in_lane0_rewards_account), 0);
+			assert_eq!(Balances::balance(&1), 200);
+			assert_eq!(Balances::balance(&2), 100);
 		});
 	}
 
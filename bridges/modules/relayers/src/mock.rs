@@ -240,6 +240,7 @@ impl pallet_bridge_grandpa::Config for TestRuntime {
 	type BridgedChain = BridgedUnderlyingParachain;
 	type MaxFreeHeadersPerBlock = ConstU32<4>;
 	type FreeHeadersInterval = ConstU32<1_024>;
+	type MaxWarpSyncFragments = ConstU32<4>;
 	type HeadersToKeep = ConstU32<8>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<TestRuntime>;
 }
@@ -247,6 +248,7 @@ impl pallet_bridge_grandpa::Config for TestRuntime {
 impl pallet_bridge_parachains::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type BridgesGrandpaPalletInstance = ();
+	type RelayHeadersSource = pallet_bridge_parachains::GrandpaRelayHeaders<Self, ()>;
 	type ParasPalletName = BridgedParasPalletName;
 	type ParaStoredHeaderDataBuilder =
 		SingleParaStoredHeaderDataBuilder<BridgedUnderlyingParachain>;
@@ -308,7 +310,7 @@ impl pallet_bridge_relayers::benchmarking::Config for TestRuntime {
 /// Regular relayer that may receive rewards.
 pub const REGULAR_RELAYER: ThisChainAccountId = 1;
 
-/// Relayer that can't receive rewards.
+/// Relayer (or beneficiary) that can't receive rewards.
 pub const FAILING_RELAYER: ThisChainAccountId = 2;
 
 /// Relayer that is able to register.
@@ -326,13 +328,15 @@ impl TestPaymentProcedure {
 impl PaymentProcedure<ThisChainAccountId, ThisChainBalance> for TestPaymentProcedure {
 	type Error = ();
 	type LaneId = TestLaneIdType;
+	type Beneficiary = ThisChainAccountId;
 
 	fn pay_reward(
-		relayer: &ThisChainAccountId,
+		_relayer: &ThisChainAccountId,
 		_lane_id: RewardsAccountParams<Self::LaneId>,
 		_reward: ThisChainBalance,
+		beneficiary: Self::Beneficiary,
 	) -> Result<(), Self::Error> {
-		match *relayer {
+		match beneficiary {
 			FAILING_RELAYER => Err(()),
 			_ => Ok(()),
 		}
@@ -51,6 +51,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_bridge_relayers.
 pub trait WeightInfo {
 	fn claim_rewards() -> Weight;
+	fn claim_rewards_to() -> Weight;
 	fn register() -> Weight;
 	fn deregister() -> Weight;
 	fn slash_and_deregister() -> Weight;
@@ -85,6 +86,29 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: BridgeRelayers RelayerRewards (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers RelayerRewards (max_values: None, max_size: Some(65), added: 2540,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances TotalIssuance (r:1 w:0)
+	///
+	/// Proof: Balances TotalIssuance (max_values: Some(1), max_size: Some(8), added: 503, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn claim_rewards_to() -> Weight {
+		// Not benchmarked yet: the benchmarked `claim_rewards` call, plus the access to the
+		// account of the beneficiary, which is not the relayer. To be replaced with the output of
+		// the `claim_rewards_to` benchmark.
+		Self::claim_rewards()
+			.saturating_add(Weight::from_parts(0, 2579))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 	/// Storage: BridgeRelayers RegisteredRelayers (r:1 w:1)
 	///
 	/// Proof: BridgeRelayers RegisteredRelayers (max_values: None, max_size: Some(64), added: 2539,
@@ -184,6 +208,29 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: BridgeRelayers RelayerRewards (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers RelayerRewards (max_values: None, max_size: Some(65), added: 2540,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances TotalIssuance (r:1 w:0)
+	///
+	/// Proof: Balances TotalIssuance (max_values: Some(1), max_size: Some(8), added: 503, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn claim_rewards_to() -> Weight {
+		// Not benchmarked yet: the benchmarked `claim_rewards` call, plus the access to the
+		// account of the beneficiary, which is not the relayer. To be replaced with the output of
+		// the `claim_rewards_to` benchmark.
+		Self::claim_rewards()
+			.saturating_add(Weight::from_parts(0, 2579))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 	/// Storage: BridgeRelayers RegisteredRelayers (r:1 w:1)
 	///
 	/// Proof: BridgeRelayers RegisteredRelayers (max_values: None, max_size: Some(64), added: 2539,
@@ -656,7 +656,7 @@ mod tests {
 				XcmBridgeHubRouter::get_messages(),
 				vec![(
 					VersionedLocation::V4((Parent, Parachain(1002)).into()),
-					vec![VersionedXcm::V4(
+					vec![VersionedXcm::V5(
 						Xcm::builder()
 							.withdraw_asset((Parent, 1_002_000))
 							.buy_execution((Parent, 1_002_000), Unlimited)
@@ -22,7 +22,7 @@
 
 use crate::{Config, Pallet, LOG_TARGET};
 
-use crate::{BridgeOf, Bridges};
+use crate::{BridgeOf, Bridges, BridgesUsage, Event};
 
 use bp_messages::{
 	source_chain::{MessagesBridge, OnMessagesDelivered},
@@ -63,6 +63,7 @@ where
 		BridgeId,
 		BridgeOf<T, I>,
 		<MessagesPallet<T, I> as MessagesBridge<T::OutboundPayload, T::LaneId>>::SendMessageArgs,
+		u32,
 		XcmHash,
 	);
 
@@ -140,6 +141,21 @@ where
 			SendError::NotApplicable
 		})?;
 
+		// check if the bridge has not reached any of its limits
+		let limits = Self::bridge_limits(locations.bridge_id());
+		let usage = Self::bridge_usage(locations.bridge_id());
+		if let Some(limit) =
+			usage.reached_limit(&limits, Self::outstanding_messages(bridge.lane_id))
+		{
+			log::error!(
+				target: LOG_TARGET,
+				"XCM message cannot be exported because bridge {:?} has reached its limit {:?}",
+				locations.bridge_id(),
+				limit,
+			);
+			return Err(SendError::Transport("BridgeLimitReached"))
+		}
+
 		// check if we are able to route the message. We use existing `HaulBlobExporter` for that.
 		// It will make all required changes and will encode message properly, so that the
 		// `DispatchBlob` at the bridged bridge hub will be able to decode it
@@ -151,6 +167,7 @@ where
 			message,
 		)?;
 
+		let message_size = blob.len().try_into().unwrap_or(u32::MAX);
 		let bridge_message = MessagesPallet::<T, I>::validate_message(bridge.lane_id, &blob)
 			.map_err(|e| {
 				match e {
@@ -174,11 +191,11 @@ where
 				SendError::Transport("BridgeValidateError")
 			})?;
 
-		Ok(((*locations.bridge_id(), bridge, bridge_message, id), price))
+		Ok(((*locations.bridge_id(), bridge, bridge_message, message_size, id), price))
 	}
 
 	fn deliver(
-		(bridge_id, bridge, bridge_message, id): Self::Ticket,
+		(bridge_id, bridge, bridge_message, message_size, id): Self::Ticket,
 	) -> Result<XcmHash, SendError> {
 		let artifacts = MessagesPallet::<T, I>::send_message(bridge_message);
 
@@ -192,7 +209,12 @@ where
 		);
 
 		// maybe we need switch to congested state
-		Self::on_bridge_message_enqueued(bridge_id, bridge, artifacts.enqueued_messages);
+		Self::on_bridge_message_enqueued(
+			bridge_id,
+			bridge,
+			artifacts.enqueued_messages,
+			message_size,
+		);
 
 		Ok(id)
 	}
@@ -210,10 +232,33 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		bridge_id: BridgeId,
 		bridge: BridgeOf<T, I>,
 		enqueued_messages: MessageNonce,
+		message_size: u32,
 	) {
-		// if the bridge queue is not congested, we don't want to do anything
+		// remember the bridge usage and check if the bridge has reached any of its limits
+		let mut usage = Self::bridge_usage(&bridge_id);
+		usage.note_message(message_size);
+		BridgesUsage::<T, I>::insert(bridge_id, usage);
+		let reached_limit =
+			usage.reached_limit(&Self::bridge_limits(&bridge_id), enqueued_messages);
+		if let Some(limit) = reached_limit {
+			log::debug!(
+				target: LOG_TARGET,
+				"The bridge {:?} has reached its limit {:?}",
+				bridge_id,
+				limit,
+			);
+
+			Self::deposit_event(Event::<T, I>::BridgeLimitReached {
+				bridge_id,
+				lane_id: bridge.lane_id,
+				limit,
+			});
+		}
+
+		// if the bridge queue is not congested and no limits are reached, we don't want to do
+		// anything
 		let is_congested = enqueued_messages > OUTBOUND_LANE_CONGESTED_THRESHOLD;
-		if !is_congested {
+		if !is_congested && reached_limit.is_none() {
 			return
 		}
 
@@ -221,6 +266,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		// to watch this rule violation (suspended, but keep sending new messages), or we need a
 		// hard limit for that like other XCM queues have
 
+		Self::suspend_bridge(bridge_id, &bridge);
+	}
+
+	/// Suspend the bridge, if it is not suspended yet.
+	pub(crate) fn suspend_bridge(bridge_id: BridgeId, bridge: &BridgeOf<T, I>) {
 		// check if the lane is already suspended. If it is, do nothing. We still accept new
 		// messages to the suspended bridge, hoping that it'll be actually resumed soon
 		if bridge.state == BridgeState::Suspended {
@@ -292,6 +342,21 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			},
 		};
 
+		// if the bridge still has too many queued messages or any other limit is still reached,
+		// we don't want to do anything
+		let reached_limit = Self::bridge_usage(&bridge_id)
+			.reached_limit(&Self::bridge_limits(&bridge_id), enqueued_messages);
+		if let Some(limit) = reached_limit {
+			log::debug!(
+				target: LOG_TARGET,
+				"The bridge {:?} is not resumed, because it still has reached its limit {:?}",
+				bridge_id,
+				limit,
+			);
+
+			return
+		}
+
 		// else - resume the bridge
 		let bridge_origin_relative_location = (*bridge.bridge_origin_relative_location).try_into();
 		let bridge_origin_relative_location = match bridge_origin_relative_location {
@@ -363,7 +428,7 @@ mod tests {
 	use crate::{mock::*, Bridges, LaneToBridge, LanesManagerOf};
 
 	use bp_runtime::RangeInclusiveExt;
-	use bp_xcm_bridge_hub::{Bridge, BridgeLocations, BridgeState};
+	use bp_xcm_bridge_hub::{Bridge, BridgeLimit, BridgeLimits, BridgeLocations, BridgeState};
 	use frame_support::assert_ok;
 	use pallet_bridge_messages::InboundLaneStorage;
 	use xcm_builder::{NetworkExportTable, UnpaidRemoteExporter};
@@ -559,6 +624,155 @@ mod tests {
 		});
 	}
 
+	fn set_bridge_limits(bridge_id: BridgeId, limits: BridgeLimits) {
+		crate::BridgesLimits::<TestRuntime, ()>::insert(bridge_id, limits);
+	}
+
+	fn try_send_regular_message() -> Result<XcmHash, SendError> {
+		let (locations, _) = open_lane();
+		export_xcm::<XcmOverBridge>(
+			BridgedRelayNetwork::get(),
+			0,
+			locations.bridge_origin_universal_location().clone(),
+			locations.bridge_destination_universal_location().clone(),
+			vec![Instruction::ClearOrigin].into(),
+		)
+		.map(|(hash, _)| hash)
+	}
+
+	fn limit_reached_events() -> Vec<BridgeLimit> {
+		frame_system::Pallet::<TestRuntime>::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::XcmOverBridge(Event::BridgeLimitReached { limit, .. }) => Some(limit),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn exporter_suspends_the_bridge_when_messages_per_block_limit_is_reached() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			let (locations, _) = open_lane();
+			set_bridge_limits(
+				*locations.bridge_id(),
+				BridgeLimits { max_messages_per_block: 2, ..MaxBridgeLimits::get() },
+			);
+
+			// first message is accepted and nothing happens
+			assert_ok!(try_send_regular_message());
+			assert!(!TestLocalXcmChannelManager::is_bridge_suspened());
+
+			// second message is accepted, but the bridge is suspended
+			assert_ok!(try_send_regular_message());
+			assert!(TestLocalXcmChannelManager::is_bridge_suspened());
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Suspended
+			);
+			assert_eq!(limit_reached_events(), vec![BridgeLimit::MessagesPerBlock]);
+
+			// third message is rejected
+			assert_eq!(try_send_regular_message(), Err(SendError::Transport("BridgeLimitReached")));
+
+			// but it is accepted at the next block
+			frame_system::Pallet::<TestRuntime>::set_block_number(2);
+			assert_ok!(try_send_regular_message());
+		});
+	}
+
+	#[test]
+	fn exporter_rejects_messages_when_bytes_per_period_limit_is_reached() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			let (locations, _) = open_lane();
+			set_bridge_limits(
+				*locations.bridge_id(),
+				BridgeLimits { max_bytes_per_period: 1, ..MaxBridgeLimits::get() },
+			);
+
+			// first message reaches the limit
+			assert_ok!(try_send_regular_message());
+			assert_eq!(limit_reached_events(), vec![BridgeLimit::BytesPerPeriod]);
+
+			// all messages of the same period are rejected
+			frame_system::Pallet::<TestRuntime>::set_block_number(BridgeLimitsPeriod::get() - 1);
+			assert_eq!(try_send_regular_message(), Err(SendError::Transport("BridgeLimitReached")));
+
+			// and accepted when the next period starts
+			frame_system::Pallet::<TestRuntime>::set_block_number(BridgeLimitsPeriod::get());
+			assert_ok!(try_send_regular_message());
+		});
+	}
+
+	#[test]
+	fn bridge_is_not_resumed_until_outstanding_messages_limit_is_lifted() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			let (locations, lane_id) = open_lane();
+			set_bridge_limits(
+				*locations.bridge_id(),
+				BridgeLimits { max_outstanding_messages: 2, ..MaxBridgeLimits::get() },
+			);
+
+			assert_ok!(try_send_regular_message());
+			assert_ok!(try_send_regular_message());
+			assert_eq!(limit_reached_events(), vec![BridgeLimit::OutstandingMessages]);
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Suspended
+			);
+			assert_eq!(try_send_regular_message(), Err(SendError::Transport("BridgeLimitReached")));
+
+			// bridge is not resumed while the outstanding messages limit is still reached
+			XcmOverBridge::on_bridge_messages_delivered(lane_id, 2);
+			assert!(!TestLocalXcmChannelManager::is_bridge_resumed());
+
+			// and it is resumed when it is lifted
+			XcmOverBridge::on_bridge_messages_delivered(lane_id, 1);
+			assert!(TestLocalXcmChannelManager::is_bridge_resumed());
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Opened
+			);
+		});
+	}
+
+	#[test]
+	fn bridge_is_not_resumed_until_bytes_per_period_limit_is_lifted() {
+		run_test(|| {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			let (locations, lane_id) = open_lane();
+			set_bridge_limits(
+				*locations.bridge_id(),
+				BridgeLimits { max_bytes_per_period: 1, ..MaxBridgeLimits::get() },
+			);
+
+			assert_ok!(try_send_regular_message());
+			assert_eq!(limit_reached_events(), vec![BridgeLimit::BytesPerPeriod]);
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Suspended
+			);
+
+			// bridge is not resumed while the bytes limit is still reached, even if all
+			// messages are delivered
+			frame_system::Pallet::<TestRuntime>::set_block_number(BridgeLimitsPeriod::get() - 1);
+			XcmOverBridge::on_bridge_messages_delivered(lane_id, 0);
+			assert!(!TestLocalXcmChannelManager::is_bridge_resumed());
+
+			// and it is resumed when the next period starts
+			frame_system::Pallet::<TestRuntime>::set_block_number(BridgeLimitsPeriod::get());
+			XcmOverBridge::on_bridge_messages_delivered(lane_id, 0);
+			assert!(TestLocalXcmChannelManager::is_bridge_resumed());
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Opened
+			);
+		});
+	}
+
 	#[test]
 	fn export_fails_if_argument_is_missing() {
 		run_test(|| {
@@ -139,13 +139,21 @@
 //!
 //! The opening bridge holds the configured `BridgeDeposit` from the origin's sovereign account, but
 //! this deposit is returned when the bridge is closed with `fn close_bridge`.
+//!
+//! # Bridge Limits
+//!
+//! Every bridge has its own limits: the maximal number of messages per block, the maximal size of
+//! messages per period and the maximal number of queued messages. They're protecting the bridge
+//! hub from the single noisy bridge origin. The bridge owner may adjust limits of its bridge with
+//! the `fn set_bridge_limits`, but they can't exceed the configured `MaxBridgeLimits`. See the
+//! [`limits`] module for details.
 
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bp_messages::{LaneState, MessageNonce};
 use bp_runtime::{AccountIdOf, BalanceOf, RangeInclusiveExt};
-pub use bp_xcm_bridge_hub::{Bridge, BridgeId, BridgeState};
+pub use bp_xcm_bridge_hub::{Bridge, BridgeId, BridgeLimit, BridgeLimits, BridgeState};
 use bp_xcm_bridge_hub::{BridgeLocations, BridgeLocationsError, LocalXcmChannelManager};
 use frame_support::{traits::fungible::MutateHold, DefaultNoBound};
 use frame_system::Config as SystemConfig;
@@ -159,10 +167,12 @@ use xcm_executor::traits::ConvertLocation;
 pub use bp_xcm_bridge_hub::XcmAsPlainPayload;
 pub use dispatcher::XcmBlobMessageDispatchResult;
 pub use exporter::PalletAsHaulBlobExporter;
+pub use limits::{BridgeUsage, BridgeUsageOf};
 pub use pallet::*;
 
 mod dispatcher;
 mod exporter;
+pub mod limits;
 pub mod migration;
 mod mock;
 
@@ -240,6 +250,15 @@ pub mod pallet {
 		/// For example, it is possible to make an exception for a system parachain or relay.
 		type AllowWithoutBridgeDeposit: Contains<Location>;
 
+		/// Limits of every bridge. The bridge owner may lower limits of its bridge, but they can
+		/// never exceed these values.
+		#[pallet::constant]
+		type MaxBridgeLimits: Get<BridgeLimits>;
+		/// Length (in blocks) of the period, used by the
+		/// [`BridgeLimits::max_bytes_per_period`] limit.
+		#[pallet::constant]
+		type BridgeLimitsPeriod: Get<BlockNumberFor<Self>>;
+
 		/// Local XCM channel manager.
 		type LocalXcmChannelManager: LocalXcmChannelManager;
 		/// XCM-level dispatcher for inbound bridge messages.
@@ -406,6 +425,8 @@ pub mod pallet {
 			inbound_lane.purge();
 			outbound_lane.purge();
 			Bridges::<T, I>::remove(locations.bridge_id());
+			BridgesLimits::<T, I>::remove(locations.bridge_id());
+			BridgesUsage::<T, I>::remove(locations.bridge_id());
 			LaneToBridge::<T, I>::remove(bridge.lane_id);
 
 			// return deposit
@@ -447,6 +468,69 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Set limits of the bridge.
+		///
+		/// Can only be called by the "owner" of this side of the bridge. The new limits must
+		/// not exceed the `T::MaxBridgeLimits`. Limits are applied to all following messages,
+		/// including messages of the current block and period.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Weight::zero())] // TODO:(bridges-v2) - https://github.com/paritytech/parity-bridges-common/issues/3046 - add benchmarks impl
+		pub fn set_bridge_limits(
+			origin: OriginFor<T>,
+			bridge_destination_universal_location: Box<VersionedInteriorLocation>,
+			limits: BridgeLimits,
+		) -> DispatchResult {
+			// compute required bridge locations
+			let locations =
+				Self::bridge_locations_from_origin(origin, bridge_destination_universal_location)?;
+
+			// only opened (or suspended) bridges may have their limits updated
+			let bridge =
+				Bridges::<T, I>::get(locations.bridge_id()).ok_or(Error::<T, I>::UnknownBridge)?;
+			ensure!(bridge.state != BridgeState::Closed, Error::<T, I>::BridgeAlreadyClosed);
+			ensure!(
+				limits.fits_into(&T::MaxBridgeLimits::get()),
+				Error::<T, I>::BridgeLimitsTooHigh
+			);
+
+			BridgesLimits::<T, I>::insert(locations.bridge_id(), limits);
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} between {:?} and {:?} has updated its limits: {limits:?}",
+				locations.bridge_id(),
+				locations.bridge_origin_universal_location(),
+				locations.bridge_destination_universal_location(),
+			);
+
+			Self::deposit_event(Event::<T, I>::BridgeLimitsUpdated {
+				bridge_id: *locations.bridge_id(),
+				limits,
+			});
+
+			// if new limits are below the current bridge usage, the bridge must be suspended
+			// right now - otherwise it would stay opened until the next message is sent
+			let reached_limit = Self::bridge_usage(locations.bridge_id())
+				.reached_limit(&limits, Self::outstanding_messages(bridge.lane_id));
+			if let Some(limit) = reached_limit {
+				log::debug!(
+					target: LOG_TARGET,
+					"The bridge {:?} has reached its new limit {:?}",
+					locations.bridge_id(),
+					limit,
+				);
+
+				Self::deposit_event(Event::<T, I>::BridgeLimitReached {
+					bridge_id: *locations.bridge_id(),
+					lane_id: bridge.lane_id,
+					limit,
+				});
+				Self::suspend_bridge(*locations.bridge_id(), &bridge);
+			}
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -720,6 +804,15 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type LaneToBridge<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, T::LaneId, BridgeId>;
+	/// Limits of bridges, set by their owners. If there's no entry for the bridge, the
+	/// `T::MaxBridgeLimits` are used.
+	#[pallet::storage]
+	pub type BridgesLimits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgeId, BridgeLimits>;
+	/// Usage of bridges, tracked to enforce their limits.
+	#[pallet::storage]
+	pub type BridgesUsage<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgeId, BridgeUsageOf<T>>;
 
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
@@ -805,6 +898,23 @@ pub mod pallet {
 			/// Number of pruned messages during the close call.
 			pruned_messages: MessageNonce,
 		},
+		/// Bridge limits have been updated by the bridge owner.
+		BridgeLimitsUpdated {
+			/// Bridge identifier.
+			bridge_id: BridgeId,
+			/// New bridge limits.
+			limits: BridgeLimits,
+		},
+		/// Bridge has reached one of its limits and has been suspended. Following messages are
+		/// rejected until the limit is lifted.
+		BridgeLimitReached {
+			/// Bridge identifier.
+			bridge_id: BridgeId,
+			/// Lane identifier.
+			lane_id: T::LaneId,
+			/// The limit that has been reached.
+			limit: BridgeLimit,
+		},
 	}
 
 	#[pallet::error]
@@ -827,6 +937,8 @@ pub mod pallet {
 		FailedToReserveBridgeDeposit,
 		/// The version of XCM location argument is unsupported.
 		UnsupportedXcmVersion,
+		/// The bridge limits exceed the configured maximal limits.
+		BridgeLimitsTooHigh,
 	}
 }
 
@@ -1437,6 +1549,125 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn set_bridge_limits_fails_if_bridge_is_unknown() {
+		run_test(|| {
+			assert_noop!(
+				XcmOverBridge::set_bridge_limits(
+					OpenBridgeOrigin::sibling_parachain_origin(),
+					Box::new(bridged_asset_hub_universal_location().into()),
+					MaxBridgeLimits::get(),
+				),
+				Error::<TestRuntime, ()>::UnknownBridge,
+			);
+		})
+	}
+
+	#[test]
+	fn set_bridge_limits_fails_if_limits_are_too_high() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (_, locations) = mock_open_bridge_from(origin.clone(), 0);
+			let max_limits = MaxBridgeLimits::get();
+			assert_noop!(
+				XcmOverBridge::set_bridge_limits(
+					origin,
+					Box::new(locations.bridge_destination_universal_location().clone().into()),
+					BridgeLimits {
+						max_messages_per_block: max_limits.max_messages_per_block + 1,
+						..max_limits
+					},
+				),
+				Error::<TestRuntime, ()>::BridgeLimitsTooHigh,
+			);
+		})
+	}
+
+	#[test]
+	fn set_bridge_limits_works() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (_, locations) = mock_open_bridge_from(origin.clone(), 0);
+			System::set_block_number(1);
+			System::reset_events();
+
+			let limits = BridgeLimits {
+				max_messages_per_block: 1,
+				max_bytes_per_period: 1024,
+				max_outstanding_messages: 16,
+			};
+			assert_eq!(XcmOverBridge::bridge_limits(locations.bridge_id()), MaxBridgeLimits::get());
+			assert_ok!(XcmOverBridge::set_bridge_limits(
+				origin,
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				limits,
+			));
+			assert_eq!(XcmOverBridge::bridge_limits(locations.bridge_id()), limits);
+			assert_eq!(
+				System::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: RuntimeEvent::XcmOverBridge(Event::BridgeLimitsUpdated {
+						bridge_id: *locations.bridge_id(),
+						limits,
+					}),
+					topics: vec![],
+				}),
+			);
+		})
+	}
+
+	#[test]
+	fn set_bridge_limits_suspends_the_bridge_if_new_limits_are_already_reached() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (bridge, locations) = mock_open_bridge_from(origin.clone(), 0);
+			System::set_block_number(1);
+			System::reset_events();
+
+			// the bridge has already sent some bytes in the current period
+			let mut usage = XcmOverBridge::bridge_usage(locations.bridge_id());
+			usage.note_message(100);
+			BridgesUsage::<TestRuntime, ()>::insert(locations.bridge_id(), usage);
+
+			// raising the limit doesn't suspend the bridge
+			assert_ok!(XcmOverBridge::set_bridge_limits(
+				origin.clone(),
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				BridgeLimits { max_bytes_per_period: 101, ..MaxBridgeLimits::get() },
+			));
+			assert!(!TestLocalXcmChannelManager::is_bridge_suspened());
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Opened
+			);
+
+			// but lowering it below the current usage does
+			assert_ok!(XcmOverBridge::set_bridge_limits(
+				origin,
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				BridgeLimits { max_bytes_per_period: 100, ..MaxBridgeLimits::get() },
+			));
+			assert!(TestLocalXcmChannelManager::is_bridge_suspened());
+			assert_eq!(
+				XcmOverBridge::bridge(locations.bridge_id()).unwrap().state,
+				BridgeState::Suspended
+			);
+			assert_eq!(
+				System::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: RuntimeEvent::XcmOverBridge(Event::BridgeLimitReached {
+						bridge_id: *locations.bridge_id(),
+						lane_id: bridge.lane_id,
+						limit: BridgeLimit::BytesPerPeriod,
+					}),
+					topics: vec![],
+				}),
+			);
+		})
+	}
+
 	#[test]
 	fn do_try_state_works() {
 		let bridge_origin_relative_location = SiblingLocation::get();
@@ -1697,5 +1928,21 @@ mod tests {
 			}
 			.encode()
 		);
+		assert_eq!(
+			bp_xcm_bridge_hub::XcmBridgeHubCall::set_bridge_limits {
+				bridge_destination_universal_location: Box::new(
+					bridge_destination_universal_location.clone().into()
+				),
+				limits: MaxBridgeLimits::get(),
+			}
+			.encode(),
+			Call::<TestRuntime, ()>::set_bridge_limits {
+				bridge_destination_universal_location: Box::new(
+					bridge_destination_universal_location.clone().into()
+				),
+				limits: MaxBridgeLimits::get(),
+			}
+			.encode()
+		);
 	}
 }
@@ -0,0 +1,176 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-bridge limits, that are protecting the bridge hub from bridge origins, trying to
+//! monopolize it.
+//!
+//! Every bridge has [`BridgeLimits`]. By default, the `Config::MaxBridgeLimits` are used. The
+//! bridge owner may lower them (and raise back up to the `Config::MaxBridgeLimits`) with the
+//! `set_bridge_limits` call. The message that reaches any limit is still accepted, but the bridge
+//! is suspended and the `BridgeLimitReached` event is emitted. All following messages are rejected
+//! until the limit is lifted:
+//!
+//! - the `max_messages_per_block` limit is lifted at the next block;
+//!
+//! - the `max_bytes_per_period` limit is lifted when the current `Config::BridgeLimitsPeriod` ends;
+//!
+//! - the `max_outstanding_messages` limit is lifted when some queued messages are delivered.
+//!
+//! The suspended bridge is resumed when queued messages are delivered, exactly as it happens
+//! with the congested bridge, but only if none of its limits is reached at that moment. If the
+//! bridge owner lowers limits below the current bridge usage, the bridge is suspended right
+//! away.
+
+use crate::{BridgesLimits, BridgesUsage, Config, Pallet};
+
+use bp_messages::MessageNonce;
+use bp_runtime::RangeInclusiveExt;
+use bp_xcm_bridge_hub::{BridgeId, BridgeLimit, BridgeLimits};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Saturating},
+	RuntimeDebug,
+};
+
+/// Bridge usage, that is tracked to enforce [`BridgeLimits`].
+#[derive(
+	Clone, Copy, Decode, Default, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub struct BridgeUsage<BlockNumber> {
+	/// The block, where `messages_in_block` messages have been sent over the bridge.
+	pub block: BlockNumber,
+	/// Number of messages, sent over the bridge in the `block`.
+	pub messages_in_block: u32,
+	/// The first block of the current limits period.
+	pub period_start: BlockNumber,
+	/// Total size of messages, sent over the bridge since the `period_start`.
+	pub bytes_in_period: u32,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> BridgeUsage<BlockNumber> {
+	/// Returns the bridge usage at given block, forgetting about messages that have been sent
+	/// in previous blocks and periods.
+	pub fn at(self, now: BlockNumber, period: BlockNumber) -> Self {
+		let mut usage = self;
+		if usage.block != now {
+			usage.block = now;
+			usage.messages_in_block = 0;
+		}
+		if now >= usage.period_start.saturating_add(period) {
+			usage.period_start = now;
+			usage.bytes_in_period = 0;
+		}
+		usage
+	}
+
+	/// Note that the message of given size has been sent over the bridge.
+	pub fn note_message(&mut self, message_size: u32) {
+		self.messages_in_block = self.messages_in_block.saturating_add(1);
+		self.bytes_in_period = self.bytes_in_period.saturating_add(message_size);
+	}
+
+	/// Returns the first reached limit, if any.
+	pub fn reached_limit(
+		&self,
+		limits: &BridgeLimits,
+		outstanding_messages: MessageNonce,
+	) -> Option<BridgeLimit> {
+		if self.messages_in_block >= limits.max_messages_per_block {
+			return Some(BridgeLimit::MessagesPerBlock)
+		}
+		if self.bytes_in_period >= limits.max_bytes_per_period {
+			return Some(BridgeLimit::BytesPerPeriod)
+		}
+		if outstanding_messages >= limits.max_outstanding_messages {
+			return Some(BridgeLimit::OutstandingMessages)
+		}
+
+		None
+	}
+}
+
+/// An alias for the bridge usage, used by the pallet.
+pub type BridgeUsageOf<T> = BridgeUsage<BlockNumberFor<T>>;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Return limits of the bridge.
+	pub fn bridge_limits(bridge_id: &BridgeId) -> BridgeLimits {
+		BridgesLimits::<T, I>::get(bridge_id).unwrap_or_else(T::MaxBridgeLimits::get)
+	}
+
+	/// Return usage of the bridge at the current block.
+	pub fn bridge_usage(bridge_id: &BridgeId) -> BridgeUsageOf<T> {
+		BridgesUsage::<T, I>::get(bridge_id)
+			.unwrap_or_default()
+			.at(frame_system::Pallet::<T>::block_number(), T::BridgeLimitsPeriod::get())
+	}
+
+	/// Return number of queued messages at the outbound lane of the bridge.
+	pub(crate) fn outstanding_messages(lane_id: T::LaneId) -> MessageNonce {
+		pallet_bridge_messages::OutboundLanes::<T, T::BridgeMessagesPalletInstance>::get(lane_id)
+			.map(|lane| lane.queued_messages().saturating_len())
+			.unwrap_or(0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LIMITS: BridgeLimits = BridgeLimits {
+		max_messages_per_block: 2,
+		max_bytes_per_period: 100,
+		max_outstanding_messages: 10,
+	};
+
+	#[test]
+	fn bridge_usage_forgets_previous_block_messages() {
+		let mut usage = BridgeUsage::<u64>::default().at(1, 10);
+		usage.note_message(10);
+		usage.note_message(10);
+		assert_eq!(usage.reached_limit(&LIMITS, 0), Some(BridgeLimit::MessagesPerBlock));
+
+		let usage = usage.at(2, 10);
+		assert_eq!(usage.messages_in_block, 0);
+		assert_eq!(usage.bytes_in_period, 20);
+		assert_eq!(usage.reached_limit(&LIMITS, 0), None);
+	}
+
+	#[test]
+	fn bridge_usage_forgets_previous_period_bytes() {
+		let mut usage = BridgeUsage::<u64>::default().at(1, 10);
+		usage.note_message(100);
+		assert_eq!(usage.reached_limit(&LIMITS, 0), Some(BridgeLimit::BytesPerPeriod));
+
+		let usage = usage.at(9, 10);
+		assert_eq!(usage.reached_limit(&LIMITS, 0), Some(BridgeLimit::BytesPerPeriod));
+
+		let usage = usage.at(10, 10);
+		assert_eq!(usage.period_start, 10);
+		assert_eq!(usage.bytes_in_period, 0);
+		assert_eq!(usage.reached_limit(&LIMITS, 0), None);
+	}
+
+	#[test]
+	fn bridge_usage_respects_outstanding_messages_limit() {
+		let usage = BridgeUsage::<u64>::default().at(1, 10);
+		assert_eq!(usage.reached_limit(&LIMITS, 9), None);
+		assert_eq!(usage.reached_limit(&LIMITS, 10), Some(BridgeLimit::OutstandingMessages));
+	}
+}
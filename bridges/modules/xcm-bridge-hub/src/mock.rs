@@ -23,7 +23,7 @@ use bp_messages::{
 	ChainWithMessages, HashedLaneId, MessageNonce,
 };
 use bp_runtime::{messages::MessageDispatchResult, Chain, ChainId, HashOf};
-use bp_xcm_bridge_hub::{BridgeId, LocalXcmChannelManager};
+use bp_xcm_bridge_hub::{BridgeId, BridgeLimits, LocalXcmChannelManager};
 use codec::Encode;
 use frame_support::{
 	assert_ok, derive_impl, parameter_types,
@@ -163,6 +163,12 @@ parameter_types! {
 	pub const NonBridgedRelayNetwork: NetworkId = NetworkId::Rococo;
 
 	pub const BridgeDeposit: Balance = 100_000;
+	pub const MaxBridgeLimits: BridgeLimits = BridgeLimits {
+		max_messages_per_block: 16_384,
+		max_bytes_per_period: 16 * 1024 * 1024,
+		max_outstanding_messages: 16_384,
+	};
+	pub const BridgeLimitsPeriod: u64 = 10;
 
 	// configuration for pallet_xcm_bridge_hub_router
 	pub BridgeHubLocation: Location = Here.into();
@@ -203,6 +209,9 @@ impl pallet_xcm_bridge_hub::Config for TestRuntime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type AllowWithoutBridgeDeposit = Equals<ParentRelayChainLocation>;
 
+	type MaxBridgeLimits = MaxBridgeLimits;
+	type BridgeLimitsPeriod = BridgeLimitsPeriod;
+
 	type LocalXcmChannelManager = TestLocalXcmChannelManager;
 
 	type BlobDispatcher = TestBlobDispatcher;
@@ -257,6 +266,7 @@ impl xcm_executor::Config for XcmConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = ();
+	type FeeSponsor = ();
 }
 
 thread_local! {
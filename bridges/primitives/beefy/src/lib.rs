@@ -19,7 +19,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
-pub use binary_merkle_tree::merkle_root;
+pub use binary_merkle_tree::{merkle_root, verify_proof as verify_merkle_proof};
 pub use pallet_beefy_mmr::BeefyEcdsaToEthereum;
 pub use pallet_mmr::{
 	primitives::{DataOrHash as MmrDataOrHash, LeafProof as MmrProof},
@@ -16,7 +16,7 @@
 
 //! Defines structures related to calls of the `pallet-bridge-grandpa` pallet.
 
-use crate::{justification, InitializationData};
+use crate::{justification, InitializationData, WarpSyncFragment};
 
 use bp_runtime::HeaderOf;
 use codec::{Decode, Encode};
@@ -24,7 +24,7 @@ use frame_support::{weights::Weight, RuntimeDebugNoBound};
 use scale_info::TypeInfo;
 use sp_consensus_grandpa::SetId;
 use sp_runtime::traits::{Header as HeaderT, Zero};
-use sp_std::{boxed::Box, fmt::Debug};
+use sp_std::{boxed::Box, fmt::Debug, vec::Vec};
 
 /// A minimized version of `pallet-bridge-grandpa::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
@@ -54,6 +54,14 @@ pub enum BridgeGrandpaCall<Header: HeaderT> {
 		/// An identifier of the validators set, that have signed the justification.
 		current_set_id: SetId,
 	},
+	/// `pallet-bridge-grandpa::Call::submit_warp_sync_proof`
+	#[codec(index = 6)]
+	submit_warp_sync_proof {
+		/// Warp sync fragments, ordered by the header number.
+		fragments: Vec<WarpSyncFragment<Header>>,
+		/// An identifier of the validators set, that have signed the first justification.
+		current_set_id: SetId,
+	},
 }
 
 /// The `BridgeGrandpaCall` for a pallet that bridges with given `C`;
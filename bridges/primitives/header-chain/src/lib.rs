@@ -134,6 +134,19 @@ pub struct InitializationData<H: HeaderT> {
 	pub operating_mode: BasicOperatingMode,
 }
 
+/// A single fragment of the GRANDPA warp sync proof.
+///
+/// Normally it is a header that enacts new GRANDPA authorities set, along with its
+/// justification, signed by the previous authorities set. The last fragment of the proof may
+/// also be a regular header, finalized by the latest set.
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, Clone, TypeInfo)]
+pub struct WarpSyncFragment<H: HeaderT> {
+	/// The header that we are going to finalize.
+	pub header: H,
+	/// Finality justification for the `header`.
+	pub justification: GrandpaJustification<H>,
+}
+
 /// Abstract finality proof that is justifying block finality.
 pub trait FinalityProof<Hash, Number>: Clone + Send + Sync + Debug {
 	/// Return hash of header that this proof is generated for.
@@ -18,11 +18,12 @@
 
 use crate::{MessageNonce, UnrewardedRelayersState};
 
+use bp_runtime::RangeInclusiveExt;
 use codec::{Decode, Encode};
 use frame_support::weights::Weight;
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
-use sp_std::ops::RangeInclusive;
+use sp_std::{ops::RangeInclusive, vec::Vec};
 
 /// A minimized version of `pallet-bridge-messages::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
@@ -135,30 +136,58 @@ impl<LaneId> ReceiveMessagesDeliveryProofInfo<LaneId> {
 	}
 }
 
-/// Info about a `ReceiveMessagesProof` or a `ReceiveMessagesDeliveryProof` call
-/// which tries to update a single lane.
+/// Info about a `ReceiveMessagesProof` or a `ReceiveMessagesDeliveryProof` call, or about
+/// their multi-lane versions.
 #[derive(PartialEq, RuntimeDebug)]
 pub enum MessagesCallInfo<LaneId: Clone + Copy> {
 	/// Messages delivery call info.
 	ReceiveMessagesProof(ReceiveMessagesProofInfo<LaneId>),
 	/// Messages delivery confirmation call info.
 	ReceiveMessagesDeliveryProof(ReceiveMessagesDeliveryProofInfo<LaneId>),
+	/// Multi-lane messages delivery call info. Contains info for every lane of the call.
+	ReceiveMultiLaneMessagesProof(Vec<ReceiveMessagesProofInfo<LaneId>>),
+	/// Multi-lane messages delivery confirmation call info. Contains info for every lane of
+	/// the call.
+	ReceiveMultiLaneMessagesDeliveryProof(Vec<ReceiveMessagesDeliveryProofInfo<LaneId>>),
 }
 
 impl<LaneId: Clone + Copy> MessagesCallInfo<LaneId> {
-	/// Returns lane, used by the call.
-	pub fn lane_id(&self) -> LaneId {
+	/// Returns true if it is a (single or multi-lane) messages delivery call.
+	pub fn is_receive_messages_proof(&self) -> bool {
 		match *self {
-			Self::ReceiveMessagesProof(ref info) => info.base.lane_id,
-			Self::ReceiveMessagesDeliveryProof(ref info) => info.0.lane_id,
+			Self::ReceiveMessagesProof(_) | Self::ReceiveMultiLaneMessagesProof(_) => true,
+			Self::ReceiveMessagesDeliveryProof(_) |
+			Self::ReceiveMultiLaneMessagesDeliveryProof(_) => false,
 		}
 	}
 
-	/// Returns range of messages, bundled with the call.
-	pub fn bundled_messages(&self) -> RangeInclusive<MessageNonce> {
+	/// Returns lanes, used by the call.
+	pub fn lanes(&self) -> Vec<LaneId> {
+		self.bundled_messages().into_iter().map(|(lane_id, _)| lane_id).collect()
+	}
+
+	/// Returns lanes, used by the call, along with ranges of messages, bundled at every lane.
+	pub fn bundled_messages(&self) -> Vec<(LaneId, RangeInclusive<MessageNonce>)> {
 		match *self {
-			Self::ReceiveMessagesProof(ref info) => info.base.bundled_range.clone(),
-			Self::ReceiveMessagesDeliveryProof(ref info) => info.0.bundled_range.clone(),
+			Self::ReceiveMessagesProof(ref info) =>
+				sp_std::vec![(info.base.lane_id, info.base.bundled_range.clone())],
+			Self::ReceiveMessagesDeliveryProof(ref info) =>
+				sp_std::vec![(info.0.lane_id, info.0.bundled_range.clone())],
+			Self::ReceiveMultiLaneMessagesProof(ref infos) => infos
+				.iter()
+				.map(|info| (info.base.lane_id, info.base.bundled_range.clone()))
+				.collect(),
+			Self::ReceiveMultiLaneMessagesDeliveryProof(ref infos) => infos
+				.iter()
+				.map(|info| (info.0.lane_id, info.0.bundled_range.clone()))
+				.collect(),
 		}
 	}
+
+	/// Returns total number of messages, bundled with the call.
+	pub fn total_bundled_messages(&self) -> MessageNonce {
+		self.bundled_messages()
+			.iter()
+			.fold(0, |total, (_, range)| total.saturating_add(range.saturating_len()))
+	}
 }
@@ -532,6 +532,8 @@ pub enum VerificationError {
 	OutboundLaneStorage(StorageProofError),
 	/// Storage proof related error.
 	StorageProof(StorageProofError),
+	/// The same lane is mentioned more than once in the multi-lane proof.
+	DuplicateLane,
 	/// Custom error
 	Other(#[codec(skip)] &'static str),
 }
@@ -26,6 +26,7 @@ use sp_std::{
 	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
 	fmt::Debug,
 	ops::RangeInclusive,
+	vec::Vec,
 };
 
 /// Messages delivery proof from the bridged chain.
@@ -57,6 +58,29 @@ impl<BridgedHeaderHash, LaneId> Size
 	}
 }
 
+/// Messages delivery proof from the bridged chain, which covers multiple lanes.
+///
+/// It is the same as the [`FromBridgedChainMessagesDeliveryProof`], but the single storage proof
+/// contains inbound lane states of several lanes.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FromBridgedChainMultiLaneMessagesDeliveryProof<BridgedHeaderHash, LaneId> {
+	/// Hash of the bridge header the proof is for.
+	pub bridged_header_hash: BridgedHeaderHash,
+	/// Storage trie proof generated for [`Self::bridged_header_hash`].
+	pub storage_proof: RawStorageProof,
+	/// Lanes of which messages were delivered and the proof is for.
+	pub lanes: Vec<LaneId>,
+}
+
+impl<BridgedHeaderHash, LaneId> Size
+	for FromBridgedChainMultiLaneMessagesDeliveryProof<BridgedHeaderHash, LaneId>
+{
+	fn size(&self) -> u32 {
+		use frame_support::sp_runtime::SaturatedConversion;
+		raw_storage_proof_size(&self.storage_proof).saturated_into()
+	}
+}
+
 /// Number of messages, delivered by relayers.
 pub type RelayersRewards<AccountId> = BTreeMap<AccountId, MessageNonce>;
 
@@ -58,6 +58,40 @@ impl<BridgedHeaderHash, Lane> Size for FromBridgedChainMessagesProof<BridgedHead
 	}
 }
 
+/// Range of messages at the single lane of the multi-lane messages proof.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct LaneMessagesRange<Lane> {
+	/// Messages in this range are sent over this lane.
+	pub lane: Lane,
+	/// Nonce of the first message being delivered.
+	pub nonces_start: MessageNonce,
+	/// Nonce of the last message being delivered.
+	pub nonces_end: MessageNonce,
+}
+
+/// Messages proof from bridged chain, which covers multiple lanes.
+///
+/// It is the same as the [`FromBridgedChainMessagesProof`], but the single storage proof
+/// contains messages and (optionally) outbound lane states of several lanes.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct FromBridgedChainMultiLaneMessagesProof<BridgedHeaderHash, Lane> {
+	/// Hash of the finalized bridged header the proof is for.
+	pub bridged_header_hash: BridgedHeaderHash,
+	/// A storage trie proof of messages being delivered.
+	pub storage_proof: RawStorageProof,
+	/// Lanes and nonces of messages which are included in this proof.
+	pub lanes: Vec<LaneMessagesRange<Lane>>,
+}
+
+impl<BridgedHeaderHash, Lane> Size
+	for FromBridgedChainMultiLaneMessagesProof<BridgedHeaderHash, Lane>
+{
+	fn size(&self) -> u32 {
+		use frame_support::sp_runtime::SaturatedConversion;
+		raw_storage_proof_size(&self.storage_proof).saturated_into()
+	}
+}
+
 /// Proved messages from the source chain.
 pub type ProvedMessages<LaneId, Message> = (LaneId, ProvedLaneMessages<Message>);
 
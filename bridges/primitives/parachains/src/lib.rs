@@ -22,13 +22,14 @@
 pub use bp_header_chain::StoredHeaderData;
 pub use call_info::{BridgeParachainCall, SubmitParachainHeadsInfo};
 
+use bp_header_chain::HeaderChainError;
 use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaId};
 use bp_runtime::{
-	BlockNumberOf, Chain, HashOf, HeaderOf, Parachain, StorageDoubleMapKeyProvider,
-	StorageMapKeyProvider,
+	BlockNumberOf, Chain, HashOf, HeaderId, HeaderOf, Parachain, Size, StorageDoubleMapKeyProvider,
+	StorageMapKeyProvider, StorageProofError,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{Blake2_128Concat, Twox64Concat};
+use frame_support::{weights::Weight, Blake2_128Concat, Parameter, Twox64Concat};
 use scale_info::TypeInfo;
 use sp_core::storage::StorageKey;
 use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
@@ -192,3 +193,75 @@ impl ParaStoredHeaderDataBuilder for C {
 		None
 	}
 }
+
+/// Error that may happen when verifying parachain heads proof.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ParaHeadsProofError {
+	/// Relay chain block is unknown to the relay headers source.
+	UnknownRelayChainBlock,
+	/// The number of known relay block is different from what the relayer has provided.
+	InvalidRelayChainBlockNumber,
+	/// The proof is not valid at the given relay chain block.
+	HeaderChain(HeaderChainError),
+}
+
+/// Reader of parachain heads from the verified proof.
+pub trait ParaHeadsReader {
+	/// Read head of given parachain from the proof.
+	fn read_parachain_head(
+		&mut self,
+		parachain: ParaId,
+	) -> Result<Option<ParaHead>, StorageProofError>;
+
+	/// Checks that every head from the proof has been read.
+	fn ensure_no_unused_heads(self) -> Result<(), StorageProofError>;
+}
+
+/// Source of finalized relay chain blocks, which are used to verify parachain heads proofs.
+///
+/// The parachains pallet doesn't care how relay chain blocks are finalized. It is either
+/// the GRANDPA light client, which tracks relay chain headers and verifies storage proofs
+/// of the `paras` pallet `Heads` map, or some other light client (e.g. BEEFY), which is
+/// able to commit to parachain heads at given relay chain block.
+///
+/// The source is also responsible for free parachain heads submissions, since free submissions
+/// are usually shared with the light client, which imports relay chain headers. By default, all
+/// submissions are paid.
+pub trait RelayHeadersSource {
+	/// Type of the parachain heads proof, accepted by the source.
+	type ParaHeadsProof: Parameter + Size;
+	/// Reader of the verified parachain heads proof.
+	type ParaHeadsReader: ParaHeadsReader;
+
+	/// Returns true if relay chain block is known to the source and may be used to craft
+	/// parachain heads proofs.
+	fn is_known_relay_block(at: HeaderId<RelayBlockHash, RelayBlockNumber>) -> bool;
+
+	/// Verify parachain heads proof, crafted at given relay chain block.
+	fn verify_para_heads_proof(
+		at: HeaderId<RelayBlockHash, RelayBlockNumber>,
+		proof: Self::ParaHeadsProof,
+	) -> Result<Self::ParaHeadsReader, ParaHeadsProofError>;
+
+	/// Returns weight of the `verify_para_heads_proof` call, or `None` if the proof is the storage
+	/// proof of the `paras` pallet `Heads` map, which verification is covered by the parachains
+	/// pallet benchmarks.
+	fn verify_para_heads_proof_weight(_proof: &Self::ParaHeadsProof) -> Option<Weight> {
+		None
+	}
+
+	/// Minimal difference between relay chain block numbers, used to prove new and previous best
+	/// parachain heads, which makes the parachain head submission free. `None` if only the first
+	/// head of every parachain may be submitted for free.
+	fn free_headers_interval() -> Option<RelayBlockNumber> {
+		None
+	}
+
+	/// Returns true if free parachain heads submissions are still accepted in the current block.
+	fn has_free_header_slots() -> bool {
+		false
+	}
+
+	/// Called when free parachain head submission has been accepted.
+	fn on_free_header_imported() {}
+}
@@ -61,10 +61,7 @@ impl<RemoteGrandpaChainBlockNumber: Clone + Copy + Debug, LaneId: Clone + Copy +
 {
 	/// Returns true if call is a message delivery call (with optional finality calls).
 	pub fn is_receive_messages_proof_call(&self) -> bool {
-		match self.messages_call_info() {
-			MessagesCallInfo::ReceiveMessagesProof(_) => true,
-			MessagesCallInfo::ReceiveMessagesDeliveryProof(_) => false,
-		}
+		self.messages_call_info().is_receive_messages_proof()
 	}
 
 	/// Returns the pre-dispatch `finality_target` sent to the `SubmitFinalityProof` call.
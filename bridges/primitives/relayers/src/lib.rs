@@ -95,30 +95,41 @@ pub trait PaymentProcedure<Relayer, Reward> {
 	type Error: Debug;
 	/// Lane identifier type.
 	type LaneId: Decode + Encode;
+	/// Beneficiary of the reward payment. The relayer account itself is always a valid
+	/// beneficiary.
+	type Beneficiary: Clone + Debug + Decode + Encode + Eq + TypeInfo + From<Relayer>;
 
-	/// Pay reward to the relayer from the account with provided params.
+	/// Pay reward, earned by the relayer, to the beneficiary from the account with provided
+	/// params.
 	fn pay_reward(
 		relayer: &Relayer,
 		rewards_account_params: RewardsAccountParams<Self::LaneId>,
 		reward: Reward,
+		beneficiary: Self::Beneficiary,
 	) -> Result<(), Self::Error>;
 }
 
-impl<Relayer, Reward> PaymentProcedure<Relayer, Reward> for () {
+impl<Relayer, Reward> PaymentProcedure<Relayer, Reward> for ()
+where
+	Relayer: Clone + Debug + Decode + Encode + Eq + TypeInfo,
+{
 	type Error = &'static str;
 	type LaneId = ();
+	type Beneficiary = Relayer;
 
 	fn pay_reward(
 		_: &Relayer,
 		_: RewardsAccountParams<Self::LaneId>,
 		_: Reward,
+		_: Self::Beneficiary,
 	) -> Result<(), Self::Error> {
 		Ok(())
 	}
 }
 
 /// Reward payment procedure that does `balances::transfer` call from the account, derived from
-/// given params.
+/// given params. The reward is transferred to the beneficiary account, which is the relayer
+/// account by default.
 pub struct PayRewardFromAccount<T, Relayer, LaneId>(PhantomData<(T, Relayer, LaneId)>);
 
 impl<T, Relayer, LaneId> PayRewardFromAccount<T, Relayer, LaneId>
@@ -136,20 +147,22 @@ impl<T, Relayer, LaneId> PaymentProcedure<Relayer, T::Balance>
 	for PayRewardFromAccount<T, Relayer, LaneId>
 where
 	T: frame_support::traits::fungible::Mutate<Relayer>,
-	Relayer: Decode + Encode + Eq,
+	Relayer: Clone + Debug + Decode + Encode + Eq + TypeInfo,
 	LaneId: Decode + Encode,
 {
 	type Error = sp_runtime::DispatchError;
 	type LaneId = LaneId;
+	type Beneficiary = Relayer;
 
 	fn pay_reward(
-		relayer: &Relayer,
+		_: &Relayer,
 		rewards_account_params: RewardsAccountParams<Self::LaneId>,
 		reward: T::Balance,
+		beneficiary: Self::Beneficiary,
 	) -> Result<(), Self::Error> {
 		T::transfer(
 			&Self::rewards_account(rewards_account_params),
-			relayer,
+			&beneficiary,
 			reward,
 			Preservation::Expendable,
 		)
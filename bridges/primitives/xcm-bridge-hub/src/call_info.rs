@@ -16,6 +16,8 @@
 
 //! Defines structures related to calls of the `pallet-xcm-bridge-hub` pallet.
 
+use crate::BridgeLimits;
+
 use bp_messages::MessageNonce;
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -40,4 +42,12 @@ pub enum XcmBridgeHubCall {
 		/// The number of messages that we may prune in a single call.
 		may_prune_messages: MessageNonce,
 	},
+	/// `pallet_xcm_bridge_hub::Call::set_bridge_limits`
+	#[codec(index = 2)]
+	set_bridge_limits {
+		/// Universal `InteriorLocation` from the bridged consensus.
+		bridge_destination_universal_location: Box<VersionedInteriorLocation>,
+		/// New bridge limits.
+		limits: BridgeLimits,
+	},
 }
@@ -19,7 +19,7 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bp_messages::LaneIdType;
+use bp_messages::{LaneIdType, MessageNonce};
 use bp_runtime::{AccountIdOf, BalanceOf, Chain};
 pub use call_info::XcmBridgeHubCall;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -150,6 +150,42 @@ pub enum BridgeState {
 	Closed,
 }
 
+/// Limits that are applied to messages, sent over the bridge.
+///
+/// The limits are protecting the bridge hub from the single bridge origin, that is trying to
+/// monopolize the bridge hub. Once any limit is reached, the bridge is suspended and new messages
+/// are rejected until the limit is lifted.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct BridgeLimits {
+	/// Maximal number of messages that may be sent over the bridge in a single block.
+	pub max_messages_per_block: u32,
+	/// Maximal total size (in bytes) of messages that may be sent over the bridge during the
+	/// single limits period.
+	pub max_bytes_per_period: u32,
+	/// Maximal number of queued (not yet delivered) messages at the bridge outbound lane.
+	pub max_outstanding_messages: MessageNonce,
+}
+
+impl BridgeLimits {
+	/// Returns true if all limits are less than or equal to the `other` limits.
+	pub fn fits_into(&self, other: &BridgeLimits) -> bool {
+		self.max_messages_per_block <= other.max_messages_per_block &&
+			self.max_bytes_per_period <= other.max_bytes_per_period &&
+			self.max_outstanding_messages <= other.max_outstanding_messages
+	}
+}
+
+/// The bridge limit, that may be reached.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum BridgeLimit {
+	/// The [`BridgeLimits::max_messages_per_block`] limit.
+	MessagesPerBlock,
+	/// The [`BridgeLimits::max_bytes_per_period`] limit.
+	BytesPerPeriod,
+	/// The [`BridgeLimits::max_outstanding_messages`] limit.
+	OutstandingMessages,
+}
+
 /// Bridge metadata.
 #[derive(
 	CloneNoBound, Decode, Encode, Eq, PartialEqNoBound, TypeInfo, MaxEncodedLen, RuntimeDebugNoBound,
@@ -0,0 +1,571 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Beacon-API source client.
+//!
+//! Light client updates are built from the standard light client endpoints of the beacon node
+//! API. The Ethereum light client pallet also requires the `block_roots` of the finalized beacon
+//! state, which are not part of the light client API. They're proved against the finalized state,
+//! which is read from the standard debug endpoint (see [`crate::beacon_state`]). So the beacon
+//! node must be able to serve finalized states.
+
+use crate::{
+	beacon_loop::SourceClient,
+	beacon_state::{self, BlockRootsProof},
+	BeaconSyncPipeline,
+};
+
+use async_trait::async_trait;
+use isahc::{AsyncReadResponseExt, HttpClient, Request, Response};
+use relay_utils::{relay_loop::Client as RelayClient, MaybeConnectionError};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use snowbridge_beacon_primitives::{
+	BeaconHeader, CheckpointUpdate, NextSyncCommitteeUpdate, SyncAggregate, SyncCommittee, Update,
+};
+use sp_core::H256;
+use std::marker::PhantomData;
+
+/// Endpoint, returning the node sync status.
+pub const SYNCING_PATH: &str = "eth/v1/node/syncing";
+/// Endpoint, returning the genesis information of the beacon chain.
+pub const GENESIS_PATH: &str = "eth/v1/beacon/genesis";
+/// Endpoint, returning the latest finalized beacon block header.
+pub const FINALIZED_HEADER_PATH: &str = "eth/v1/beacon/headers/finalized";
+/// Endpoint, returning the light client update with the latest finalized header.
+pub const FINALITY_UPDATE_PATH: &str = "eth/v1/beacon/light_client/finality_update";
+
+/// Endpoint, returning the light client bootstrap at the given block.
+pub fn bootstrap_path(block_root: H256) -> String {
+	format!("eth/v1/beacon/light_client/bootstrap/{block_root:#x}")
+}
+
+/// Endpoint, returning the best light client update, attested in the given sync committee period.
+pub fn sync_committee_update_path(period: u64) -> String {
+	format!("eth/v1/beacon/light_client/updates?start_period={period}&count=1")
+}
+
+/// Endpoint, returning the SSZ-encoded beacon state at the given slot.
+pub fn beacon_state_path(slot: u64) -> String {
+	format!("eth/v2/debug/beacon/states/{slot}")
+}
+
+/// Beacon-API client errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Failed to create HTTP client.
+	#[error("Failed to create HTTP client: {0}")]
+	HttpClient(#[source] isahc::Error),
+	/// Failed to build HTTP request.
+	#[error("Failed to build request to {0}: {1}")]
+	InvalidRequest(String, #[source] isahc::http::Error),
+	/// HTTP request has failed.
+	#[error("Request to {0} has failed: {1}")]
+	Request(String, #[source] isahc::Error),
+	/// Beacon-API has responded with an error.
+	#[error("Request to {0} has failed with status {1}")]
+	Status(String, u16),
+	/// Failed to read response body.
+	#[error("Failed to read response from {0}: {1}")]
+	ReadResponse(String, #[source] std::io::Error),
+	/// Failed to decode response body.
+	#[error("Failed to decode response from {0}: {1}")]
+	DecodeResponse(String, #[source] serde_json::Error),
+	/// Beacon-API has responded without the consensus version of the SSZ-encoded object.
+	#[error("Response from {0} has no consensus version")]
+	MissingConsensusVersion(String),
+	/// Beacon-API has no light client update for the sync committee period.
+	#[error("No light client update is available for sync committee period {0}")]
+	MissingSyncCommitteeUpdate(u64),
+	/// The light client update has no finalized header.
+	#[error("Light client update for sync committee period {0} has no finalized header")]
+	MissingFinalizedHeader(u64),
+	/// Failed to prove `block_roots` of the finalized beacon state.
+	#[error("Failed to prove block roots of the state at {0}: {1}")]
+	BlockRootsProof(String, #[source] beacon_state::Error),
+}
+
+impl MaybeConnectionError for Error {
+	fn is_connection_error(&self) -> bool {
+		matches!(*self, Error::Request(..) | Error::ReadResponse(..))
+	}
+}
+
+/// Response of the beacon-API.
+#[derive(Debug, Deserialize)]
+struct DataResponse<T> {
+	data: T,
+}
+
+/// Beacon node sync status.
+#[derive(Debug, Deserialize)]
+struct SyncingData {
+	is_syncing: bool,
+}
+
+/// Genesis information of the beacon chain.
+#[derive(Debug, Deserialize)]
+struct GenesisData {
+	genesis_validators_root: H256,
+}
+
+/// Signed beacon block header along with its root.
+#[derive(Debug, Deserialize)]
+struct BlockHeaderData {
+	root: H256,
+	header: SignedBeaconHeader,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignedBeaconHeader {
+	message: ApiBeaconHeader,
+}
+
+/// Beacon block header, as it is returned by the beacon-API (with quoted integers).
+#[derive(Debug, Deserialize)]
+struct ApiBeaconHeader {
+	#[serde(deserialize_with = "from_quoted_u64")]
+	slot: u64,
+	#[serde(deserialize_with = "from_quoted_u64")]
+	proposer_index: u64,
+	parent_root: H256,
+	state_root: H256,
+	body_root: H256,
+}
+
+impl From<ApiBeaconHeader> for BeaconHeader {
+	fn from(header: ApiBeaconHeader) -> Self {
+		BeaconHeader {
+			slot: header.slot,
+			proposer_index: header.proposer_index,
+			parent_root: header.parent_root,
+			state_root: header.state_root,
+			body_root: header.body_root,
+		}
+	}
+}
+
+/// Light client header. We only need the beacon header, because the execution header is proved
+/// separately, with the messages.
+#[derive(Debug, Deserialize)]
+struct LightClientHeader {
+	beacon: ApiBeaconHeader,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = ""))]
+struct LightClientBootstrap<const COMMITTEE_SIZE: usize> {
+	header: LightClientHeader,
+	current_sync_committee: SyncCommittee<COMMITTEE_SIZE>,
+	current_sync_committee_branch: Vec<H256>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = ""))]
+struct LightClientFinalityUpdate<const COMMITTEE_SIZE: usize, const COMMITTEE_BITS_SIZE: usize> {
+	attested_header: LightClientHeader,
+	finalized_header: LightClientHeader,
+	finality_branch: Vec<H256>,
+	sync_aggregate: SyncAggregate<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>,
+	#[serde(deserialize_with = "from_quoted_u64")]
+	signature_slot: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = ""))]
+struct LightClientUpdate<const COMMITTEE_SIZE: usize, const COMMITTEE_BITS_SIZE: usize> {
+	attested_header: LightClientHeader,
+	next_sync_committee: SyncCommittee<COMMITTEE_SIZE>,
+	next_sync_committee_branch: Vec<H256>,
+	finalized_header: LightClientHeader,
+	finality_branch: Vec<H256>,
+	sync_aggregate: SyncAggregate<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>,
+	#[serde(deserialize_with = "from_quoted_u64")]
+	signature_slot: u64,
+}
+
+fn from_quoted_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+	String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+/// Beacon-API client, that is used as a source of the beacon sync loop.
+pub struct BeaconApiClient<P> {
+	base_url: String,
+	http_client: HttpClient,
+	_phantom: PhantomData<P>,
+}
+
+impl<P> BeaconApiClient<P> {
+	/// Create new beacon-API client, connected to the given endpoint.
+	pub fn new(base_url: impl Into<String>) -> Result<Self, Error> {
+		Ok(BeaconApiClient {
+			base_url: base_url.into().trim_end_matches('/').to_owned(),
+			http_client: HttpClient::new().map_err(Error::HttpClient)?,
+			_phantom: Default::default(),
+		})
+	}
+
+	/// Perform GET request to the beacon-API, accepting the given content type.
+	async fn request(
+		&self,
+		path: &str,
+		accept: &str,
+	) -> Result<(String, Response<isahc::AsyncBody>), Error> {
+		let url = format!("{}/{}", self.base_url, path);
+		let request = Request::get(&url)
+			.header("Accept", accept)
+			.body(())
+			.map_err(|e| Error::InvalidRequest(url.clone(), e))?;
+		let response = self
+			.http_client
+			.send_async(request)
+			.await
+			.map_err(|e| Error::Request(url.clone(), e))?;
+		if !response.status().is_success() {
+			return Err(Error::Status(url, response.status().as_u16()))
+		}
+		Ok((url, response))
+	}
+
+	/// Perform GET request to the beacon-API and decode the JSON response.
+	async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+		let (url, mut response) = self.request(path, "application/json").await?;
+		let body = response.text().await.map_err(|e| Error::ReadResponse(url.clone(), e))?;
+		serde_json::from_str(&body).map_err(|e| Error::DecodeResponse(url, e))
+	}
+
+	/// Perform GET request to the beacon-API and return the SSZ-encoded response, along with
+	/// the consensus version of the encoded object.
+	async fn get_ssz(&self, path: &str) -> Result<(String, Vec<u8>), Error> {
+		let (url, mut response) = self.request(path, "application/octet-stream").await?;
+		let version = response
+			.headers()
+			.get("Eth-Consensus-Version")
+			.and_then(|version| version.to_str().ok())
+			.map(|version| version.to_lowercase())
+			.ok_or_else(|| Error::MissingConsensusVersion(url.clone()))?;
+		let body = response.bytes().await.map_err(|e| Error::ReadResponse(url, e))?;
+		Ok((version, body))
+	}
+
+	/// Read the finalized beacon state of the given header and prove its `block_roots`.
+	async fn block_roots_proof(&self, header: &BeaconHeader) -> Result<BlockRootsProof, Error> {
+		let path = beacon_state_path(header.slot);
+		let (version, state) = self.get_ssz(&path).await?;
+		beacon_state::block_roots_proof(&version, &state, header.state_root)
+			.map_err(|e| Error::BlockRootsProof(path, e))
+	}
+}
+
+impl<P> Clone for BeaconApiClient<P> {
+	fn clone(&self) -> Self {
+		BeaconApiClient {
+			base_url: self.base_url.clone(),
+			http_client: self.http_client.clone(),
+			_phantom: Default::default(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: BeaconSyncPipeline> RelayClient for BeaconApiClient<P> {
+	type Error = Error;
+
+	async fn reconnect(&mut self) -> Result<(), Error> {
+		// every request is using its own connection, so there's nothing to reconnect
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl<P, const COMMITTEE_SIZE: usize, const COMMITTEE_BITS_SIZE: usize> SourceClient<P>
+	for BeaconApiClient<P>
+where
+	P: BeaconSyncPipeline<
+		CheckpointUpdate = CheckpointUpdate<COMMITTEE_SIZE>,
+		Update = Update<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>,
+	>,
+{
+	async fn ensure_synced(&self) -> Result<bool, Error> {
+		let response: DataResponse<SyncingData> = self.get(SYNCING_PATH).await?;
+		Ok(!response.data.is_syncing)
+	}
+
+	async fn finalized_slot(&self) -> Result<u64, Error> {
+		let response: DataResponse<LightClientFinalityUpdate<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>> =
+			self.get(FINALITY_UPDATE_PATH).await?;
+		Ok(response.data.finalized_header.beacon.slot)
+	}
+
+	async fn checkpoint_update(&self) -> Result<CheckpointUpdate<COMMITTEE_SIZE>, Error> {
+		let finalized: DataResponse<BlockHeaderData> = self.get(FINALIZED_HEADER_PATH).await?;
+		let bootstrap: DataResponse<LightClientBootstrap<COMMITTEE_SIZE>> =
+			self.get(&bootstrap_path(finalized.data.root)).await?;
+		let genesis: DataResponse<GenesisData> = self.get(GENESIS_PATH).await?;
+
+		let header: BeaconHeader = bootstrap.data.header.beacon.into();
+		let proof = self.block_roots_proof(&header).await?;
+		Ok(CheckpointUpdate {
+			header,
+			current_sync_committee: bootstrap.data.current_sync_committee,
+			current_sync_committee_branch: bootstrap.data.current_sync_committee_branch,
+			validators_root: genesis.data.genesis_validators_root,
+			block_roots_root: proof.block_roots_root,
+			block_roots_branch: proof.block_roots_branch,
+		})
+	}
+
+	async fn finality_update(&self) -> Result<Update<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>, Error> {
+		let update: DataResponse<LightClientFinalityUpdate<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>> =
+			self.get(FINALITY_UPDATE_PATH).await?;
+		let update = update.data;
+
+		let finalized_header: BeaconHeader = update.finalized_header.beacon.into();
+		let proof = self.block_roots_proof(&finalized_header).await?;
+		Ok(Update {
+			attested_header: update.attested_header.beacon.into(),
+			sync_aggregate: update.sync_aggregate,
+			signature_slot: update.signature_slot,
+			next_sync_committee_update: None,
+			finalized_header,
+			finality_branch: update.finality_branch,
+			block_roots_root: proof.block_roots_root,
+			block_roots_branch: proof.block_roots_branch,
+		})
+	}
+
+	async fn sync_committee_update(
+		&self,
+		period: u64,
+	) -> Result<Update<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>, Error> {
+		let updates: Vec<DataResponse<LightClientUpdate<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>>> =
+			self.get(&sync_committee_update_path(period)).await?;
+		let update = updates
+			.into_iter()
+			.next()
+			.ok_or(Error::MissingSyncCommitteeUpdate(period))?
+			.data;
+
+		// the best update of the period may lack finality, but the light client can't import
+		// the next sync committee without the finalized header
+		let finalized_header: BeaconHeader = update.finalized_header.beacon.into();
+		if finalized_header.slot == 0 {
+			return Err(Error::MissingFinalizedHeader(period))
+		}
+
+		let proof = self.block_roots_proof(&finalized_header).await?;
+		Ok(Update {
+			attested_header: update.attested_header.beacon.into(),
+			sync_aggregate: update.sync_aggregate,
+			signature_slot: update.signature_slot,
+			next_sync_committee_update: Some(NextSyncCommitteeUpdate {
+				next_sync_committee: update.next_sync_committee,
+				next_sync_committee_branch: update.next_sync_committee_branch,
+			}),
+			finalized_header,
+			finality_branch: update.finality_branch,
+			block_roots_root: proof.block_roots_root,
+			block_roots_branch: proof.block_roots_branch,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{beacon_state::test_deneb_state, mock::*};
+	use snowbridge_beacon_primitives::merkle_proof::verify_merkle_branch;
+
+	/// Returns the update fixture, with the finalized header pointing to the test beacon state,
+	/// along with the SSZ-encoded state.
+	fn update_with_test_state(name: &str) -> (TestUpdate, Vec<u8>) {
+		let mut update = update_fixture(name);
+		let (encoded_state, state_root) = test_deneb_state(update.finalized_header.slot);
+		update.finalized_header.state_root = state_root;
+		(update, encoded_state)
+	}
+
+	fn assert_valid_block_roots_proof(update: &TestUpdate) {
+		assert!(verify_merkle_branch(
+			update.block_roots_root,
+			&update.block_roots_branch,
+			beacon_state::BLOCK_ROOTS_FIELD_INDEX,
+			5,
+			update.finalized_header.state_root,
+		));
+	}
+
+	#[test]
+	fn reads_sync_status_and_finalized_slot() {
+		async_std::task::block_on(async {
+			let update = update_fixture("finalized-header-update.json");
+			let source = BeaconApiStandIn::start(vec![
+				(SYNCING_PATH.into(), StandInResponse::json(SYNCED_RESPONSE)),
+				(FINALITY_UPDATE_PATH.into(), StandInResponse::json(api_finality_update(&update))),
+			])
+			.await
+			.client();
+
+			assert!(source.ensure_synced().await.unwrap());
+			assert_eq!(source.finalized_slot().await.unwrap(), 864);
+		});
+	}
+
+	#[test]
+	fn builds_finality_update() {
+		async_std::task::block_on(async {
+			let (expected, encoded_state) = update_with_test_state("finalized-header-update.json");
+			let source = BeaconApiStandIn::start(vec![
+				(
+					FINALITY_UPDATE_PATH.into(),
+					StandInResponse::json(api_finality_update(&expected)),
+				),
+				(beacon_state_path(864), StandInResponse::ssz("deneb", encoded_state)),
+			])
+			.await
+			.client();
+
+			let update = source.finality_update().await.unwrap();
+			assert_eq!(update.attested_header, expected.attested_header);
+			assert_eq!(update.finalized_header, expected.finalized_header);
+			assert_eq!(update.finality_branch, expected.finality_branch);
+			assert_eq!(update.sync_aggregate, expected.sync_aggregate);
+			assert_eq!(update.signature_slot, expected.signature_slot);
+			assert_eq!(update.next_sync_committee_update, None);
+			assert_valid_block_roots_proof(&update);
+		});
+	}
+
+	#[test]
+	fn builds_sync_committee_update() {
+		async_std::task::block_on(async {
+			let (expected, encoded_state) = update_with_test_state("sync-committee-update.json");
+			let source = BeaconApiStandIn::start(vec![
+				(
+					sync_committee_update_path(0),
+					StandInResponse::json(api_sync_committee_updates(&expected)),
+				),
+				(beacon_state_path(64), StandInResponse::ssz("deneb", encoded_state)),
+			])
+			.await
+			.client();
+
+			let update = source.sync_committee_update(0).await.unwrap();
+			assert_eq!(update.finalized_header, expected.finalized_header);
+			assert_eq!(update.next_sync_committee_update, expected.next_sync_committee_update);
+			assert_valid_block_roots_proof(&update);
+		});
+	}
+
+	#[test]
+	fn rejects_sync_committee_update_without_finality() {
+		async_std::task::block_on(async {
+			let mut update = update_fixture("sync-committee-update.json");
+			update.finalized_header = Default::default();
+			let source = BeaconApiStandIn::start(vec![(
+				sync_committee_update_path(0),
+				StandInResponse::json(api_sync_committee_updates(&update)),
+			)])
+			.await
+			.client();
+
+			assert!(matches!(
+				source.sync_committee_update(0).await,
+				Err(Error::MissingFinalizedHeader(0)),
+			));
+			assert!(matches!(source.sync_committee_update(1).await, Err(Error::Status(_, 404)),));
+		});
+	}
+
+	#[test]
+	fn builds_checkpoint_update() {
+		async_std::task::block_on(async {
+			let mut expected = checkpoint_fixture("initial-checkpoint.json");
+			let (encoded_state, state_root) = test_deneb_state(expected.header.slot);
+			expected.header.state_root = state_root;
+			let block_root = expected.header.hash_tree_root().unwrap();
+			let source = BeaconApiStandIn::start(vec![
+				(
+					FINALIZED_HEADER_PATH.into(),
+					StandInResponse::json(
+						serde_json::json!({
+							"data": {
+								"root": block_root,
+								"canonical": true,
+								"header": {
+									"message": api_header(&expected.header),
+									"signature": "0x00",
+								},
+							},
+						})
+						.to_string(),
+					),
+				),
+				(bootstrap_path(block_root), StandInResponse::json(api_bootstrap(&expected))),
+				(
+					GENESIS_PATH.into(),
+					StandInResponse::json(
+						serde_json::json!({
+							"data": {
+								"genesis_time": "1590832934",
+								"genesis_validators_root": expected.validators_root,
+								"genesis_fork_version": "0x00000000",
+							},
+						})
+						.to_string(),
+					),
+				),
+				(beacon_state_path(64), StandInResponse::ssz("deneb", encoded_state)),
+			])
+			.await
+			.client();
+
+			let checkpoint = source.checkpoint_update().await.unwrap();
+			assert_eq!(checkpoint.header, expected.header);
+			assert_eq!(checkpoint.current_sync_committee, expected.current_sync_committee);
+			assert_eq!(
+				checkpoint.current_sync_committee_branch,
+				expected.current_sync_committee_branch
+			);
+			assert_eq!(checkpoint.validators_root, expected.validators_root);
+			assert!(verify_merkle_branch(
+				checkpoint.block_roots_root,
+				&checkpoint.block_roots_branch,
+				beacon_state::BLOCK_ROOTS_FIELD_INDEX,
+				5,
+				state_root,
+			));
+		});
+	}
+
+	#[test]
+	fn rejects_state_of_another_header() {
+		async_std::task::block_on(async {
+			let update = update_fixture("finalized-header-update.json");
+			let (encoded_state, _) = test_deneb_state(864);
+			let source = BeaconApiStandIn::start(vec![
+				(FINALITY_UPDATE_PATH.into(), StandInResponse::json(api_finality_update(&update))),
+				(beacon_state_path(864), StandInResponse::ssz("deneb", encoded_state)),
+			])
+			.await
+			.client();
+
+			assert!(matches!(
+				source.finality_update().await,
+				Err(Error::BlockRootsProof(_, beacon_state::Error::StateRootMismatch { .. })),
+			));
+		});
+	}
+}
@@ -0,0 +1,595 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Beacon light client synchronization loop.
+//!
+//! Every tick the loop compares the latest finalized beacon header at the source with the
+//! light client state at the target and submits at most one update:
+//!
+//! - if the light client is not initialized and bootstrapping is enabled, the checkpoint update at
+//!   the latest finalized header is submitted;
+//!
+//! - if the light client does not know the next sync committee, the sync committee update for the
+//!   current period is submitted;
+//!
+//! - if the source has finalized a header in a later sync committee period, the sync committee
+//!   update for the next period is submitted, so that the light client rotates its committees;
+//!
+//! - otherwise, the latest finality update is submitted if it is newer than the finalized header
+//!   known to the light client.
+
+use crate::{
+	beacon_loop_metrics::BeaconSyncLoopMetrics, sync_committee_period, BeaconCheckpointUpdate,
+	BeaconSyncPipeline, BeaconUpdate,
+};
+
+use async_trait::async_trait;
+use futures::{future::FutureExt, select_biased};
+use relay_utils::{
+	metrics::MetricsParams, relay_loop::Client as RelayClient, FailedClient,
+	TrackedTransactionStatus, TransactionTracker,
+};
+use std::{future::Future, time::Duration};
+
+/// Beacon light client state at the target chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LightClientState {
+	/// Slot of the latest finalized beacon header, imported by the light client.
+	pub finalized_slot: u64,
+	/// Whether the light client knows the sync committee of the next period.
+	pub has_next_sync_committee: bool,
+}
+
+/// Update that needs to be submitted to the target chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequiredUpdate {
+	/// The light client is in sync with the source.
+	None,
+	/// Sync committee update, attested in the given sync committee period.
+	SyncCommittee(u64),
+	/// Update with the latest finalized header at the source.
+	Finality,
+	/// Checkpoint update, bootstrapping the light client.
+	Checkpoint,
+}
+
+/// Beacon sync loop parameters.
+#[derive(Clone, Debug)]
+pub struct BeaconSyncParams {
+	/// Interval at which the loop checks for new finalized headers at the source.
+	pub tick: Duration,
+	/// Whether to bootstrap the light client with the checkpoint at the latest finalized header,
+	/// if it is not yet initialized. The `force_checkpoint` call requires the root origin, so
+	/// the target client must be able to provide it (e.g. through `sudo` at test chains).
+	/// Otherwise, the light client must be bootstrapped by the governance.
+	pub bootstrap: bool,
+}
+
+/// Source client used in beacon light client synchronization loop.
+#[async_trait]
+pub trait SourceClient<P: BeaconSyncPipeline>: RelayClient {
+	/// Returns `Ok(true)` if the beacon node is in synced state.
+	async fn ensure_synced(&self) -> Result<bool, Self::Error>;
+
+	/// Get slot of the latest finalized header.
+	async fn finalized_slot(&self) -> Result<u64, Self::Error>;
+
+	/// Get update, that may be used to bootstrap the light client at the latest finalized
+	/// header.
+	async fn checkpoint_update(&self) -> Result<P::CheckpointUpdate, Self::Error>;
+
+	/// Get update with the latest finalized header.
+	async fn finality_update(&self) -> Result<P::Update, Self::Error>;
+
+	/// Get update, attested in the given sync committee period, that carries the sync committee
+	/// of the next period.
+	async fn sync_committee_update(&self, period: u64) -> Result<P::Update, Self::Error>;
+}
+
+/// Target client used in beacon light client synchronization loop.
+#[async_trait]
+pub trait TargetClient<P: BeaconSyncPipeline>: RelayClient {
+	/// Transaction tracker to track submitted transactions.
+	type TransactionTracker: TransactionTracker;
+
+	/// Get light client state. Returns `None` if the light client is not yet initialized.
+	async fn light_client_state(&self) -> Result<Option<LightClientState>, Self::Error>;
+
+	/// Submit update to the light client.
+	async fn submit_update(
+		&self,
+		update: P::Update,
+	) -> Result<Self::TransactionTracker, Self::Error>;
+
+	/// Submit checkpoint update to bootstrap the light client. Returns `None` if the client is
+	/// unable to dispatch the `force_checkpoint` call with the root origin.
+	async fn submit_checkpoint(
+		&self,
+		update: P::CheckpointUpdate,
+	) -> Result<Option<Self::TransactionTracker>, Self::Error>;
+}
+
+/// Return prefix that will be used by default to expose Prometheus metrics of the beacon sync
+/// loop.
+pub fn metrics_prefix<P: BeaconSyncPipeline>() -> String {
+	format!("{}_to_{}_BeaconSync", P::SOURCE_NAME, P::TARGET_NAME)
+}
+
+/// Run beacon light client synchronization.
+pub async fn run<P: BeaconSyncPipeline>(
+	source_client: impl SourceClient<P>,
+	target_client: impl TargetClient<P>,
+	sync_params: BeaconSyncParams,
+	metrics_params: MetricsParams,
+	exit_signal: impl Future<Output = ()> + 'static + Send,
+) -> Result<(), relay_utils::Error> {
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} beacon light client relay",
+		P::SOURCE_NAME,
+		P::TARGET_NAME,
+	);
+
+	let exit_signal = exit_signal.shared();
+	relay_utils::relay_loop(source_client, target_client)
+		.with_metrics(metrics_params)
+		.loop_metric(BeaconSyncLoopMetrics::new(Some(&metrics_prefix::<P>()))?)?
+		.expose()
+		.await?
+		.run(metrics_prefix::<P>(), move |source_client, target_client, metrics| {
+			run_until_connection_lost(
+				source_client,
+				target_client,
+				sync_params.clone(),
+				metrics,
+				exit_signal.clone(),
+			)
+		})
+		.await
+}
+
+/// Run beacon light client synchronization until connection with either of clients is lost.
+async fn run_until_connection_lost<P: BeaconSyncPipeline>(
+	source_client: impl SourceClient<P>,
+	target_client: impl TargetClient<P>,
+	sync_params: BeaconSyncParams,
+	metrics: Option<BeaconSyncLoopMetrics>,
+	exit_signal: impl Future<Output = ()> + Send,
+) -> Result<(), FailedClient> {
+	let exit_signal = exit_signal.fuse();
+	futures::pin_mut!(exit_signal);
+
+	loop {
+		// Either wait for the next tick, or exit signal.
+		select_biased! {
+			_ = exit_signal => return Ok(()),
+			_ = relay_utils::clock::sleep(sync_params.tick).fuse() => {},
+		}
+
+		sync_step(&source_client, &target_client, &sync_params, metrics.as_ref()).await?;
+	}
+}
+
+/// Perform single synchronization step: select the update that the light client needs, submit it
+/// and wait until the transaction is finalized. Returns the update that has been submitted.
+pub async fn sync_step<P: BeaconSyncPipeline>(
+	source_client: &impl SourceClient<P>,
+	target_client: &impl TargetClient<P>,
+	sync_params: &BeaconSyncParams,
+	metrics: Option<&BeaconSyncLoopMetrics>,
+) -> Result<RequiredUpdate, FailedClient> {
+	// if the beacon node is not yet synced, its finality updates are outdated
+	match source_client.ensure_synced().await {
+		Ok(true) => (),
+		Ok(false) => {
+			log::warn!(
+				target: "bridge",
+				"{} client is syncing. Won't do anything until it is synced",
+				P::SOURCE_NAME,
+			);
+			return Ok(RequiredUpdate::None)
+		},
+		Err(e) => {
+			log::warn!(
+				target: "bridge",
+				"{} client has failed to return its sync status: {:?}",
+				P::SOURCE_NAME,
+				e,
+			);
+			return Err(FailedClient::Source)
+		},
+	}
+
+	let state = match target_client.light_client_state().await {
+		Ok(Some(state)) => state,
+		Ok(None) if sync_params.bootstrap =>
+			return bootstrap_light_client(source_client, target_client).await,
+		Ok(None) => {
+			log::warn!(
+				target: "bridge",
+				"{} light client at {} is not initialized. It must be bootstrapped with a checkpoint update",
+				P::SOURCE_NAME,
+				P::TARGET_NAME,
+			);
+			return Ok(RequiredUpdate::None)
+		},
+		Err(e) => {
+			log::warn!(
+				target: "bridge",
+				"Failed to read {} light client state from {}: {:?}",
+				P::SOURCE_NAME,
+				P::TARGET_NAME,
+				e,
+			);
+			return Err(FailedClient::Target)
+		},
+	};
+	if let Some(metrics) = metrics {
+		metrics.update_best_target_finalized_slot(state.finalized_slot);
+	}
+
+	let source_finalized_slot = source_client.finalized_slot().await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to read {} finalized slot: {:?}",
+			P::SOURCE_NAME,
+			e,
+		);
+		FailedClient::Source
+	})?;
+	if let Some(metrics) = metrics {
+		metrics.update_best_source_finalized_slot(source_finalized_slot);
+	}
+
+	// updates are only read when they're required, because building them involves reading the
+	// finalized beacon state
+	let required_update = select_required_update::<P>(&state, source_finalized_slot);
+	let update = match required_update {
+		RequiredUpdate::None | RequiredUpdate::Checkpoint => return Ok(RequiredUpdate::None),
+		RequiredUpdate::SyncCommittee(period) =>
+			source_client.sync_committee_update(period).await.map_err(|e| {
+				log::warn!(
+					target: "bridge",
+					"Failed to read {} sync committee update for period {}: {:?}",
+					P::SOURCE_NAME,
+					period,
+					e,
+				);
+				FailedClient::Source
+			})?,
+		RequiredUpdate::Finality => source_client.finality_update().await.map_err(|e| {
+			log::warn!(
+				target: "bridge",
+				"Failed to read {} finality update: {:?}",
+				P::SOURCE_NAME,
+				e,
+			);
+			FailedClient::Source
+		})?,
+	};
+
+	log::info!(
+		target: "bridge",
+		"Submitting {} {:?} update to {}. Attested slot: {}, finalized slot: {}",
+		P::SOURCE_NAME,
+		required_update,
+		P::TARGET_NAME,
+		update.attested_slot(),
+		update.finalized_slot(),
+	);
+
+	let tracker = target_client.submit_update(update).await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to submit {} update to {}: {:?}",
+			P::SOURCE_NAME,
+			P::TARGET_NAME,
+			e,
+		);
+		FailedClient::Target
+	})?;
+	wait_update_transaction::<P>(tracker, required_update).await
+}
+
+/// Read the checkpoint at the latest finalized header and submit it to the light client.
+async fn bootstrap_light_client<P: BeaconSyncPipeline>(
+	source_client: &impl SourceClient<P>,
+	target_client: &impl TargetClient<P>,
+) -> Result<RequiredUpdate, FailedClient> {
+	let checkpoint = source_client.checkpoint_update().await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to read {} checkpoint update: {:?}",
+			P::SOURCE_NAME,
+			e,
+		);
+		FailedClient::Source
+	})?;
+	let checkpoint_slot = checkpoint.slot();
+
+	log::info!(
+		target: "bridge",
+		"Bootstrapping {} light client at {} with the checkpoint at slot {}",
+		P::SOURCE_NAME,
+		P::TARGET_NAME,
+		checkpoint_slot,
+	);
+
+	let tracker = target_client.submit_checkpoint(checkpoint).await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to submit {} checkpoint update to {}: {:?}",
+			P::SOURCE_NAME,
+			P::TARGET_NAME,
+			e,
+		);
+		FailedClient::Target
+	})?;
+	match tracker {
+		Some(tracker) => wait_update_transaction::<P>(tracker, RequiredUpdate::Checkpoint).await,
+		None => {
+			log::warn!(
+				target: "bridge",
+				"{} is unable to dispatch the checkpoint update with the root origin. The {} light \
+				client must be bootstrapped by the governance with the checkpoint at slot {}",
+				P::TARGET_NAME,
+				P::SOURCE_NAME,
+				checkpoint_slot,
+			);
+			Ok(RequiredUpdate::None)
+		},
+	}
+}
+
+/// Wait until the update transaction is finalized.
+async fn wait_update_transaction<P: BeaconSyncPipeline>(
+	tracker: impl TransactionTracker,
+	required_update: RequiredUpdate,
+) -> Result<RequiredUpdate, FailedClient> {
+	match tracker.wait().await {
+		TrackedTransactionStatus::Finalized(_) => Ok(required_update),
+		TrackedTransactionStatus::Lost => {
+			log::warn!(
+				target: "bridge",
+				"{} update transaction is considered lost at {}. Going to restart",
+				P::SOURCE_NAME,
+				P::TARGET_NAME,
+			);
+			Err(FailedClient::Both)
+		},
+	}
+}
+
+/// Select the update that the light client needs to follow the source, given the latest
+/// finalized slot at the source.
+pub fn select_required_update<P: BeaconSyncPipeline>(
+	state: &LightClientState,
+	source_finalized_slot: u64,
+) -> RequiredUpdate {
+	let store_period = sync_committee_period::<P>(state.finalized_slot);
+	if !state.has_next_sync_committee {
+		return RequiredUpdate::SyncCommittee(store_period)
+	}
+
+	if sync_committee_period::<P>(source_finalized_slot) > store_period {
+		return RequiredUpdate::SyncCommittee(store_period + 1)
+	}
+
+	if source_finalized_slot > state.finalized_slot {
+		return RequiredUpdate::Finality
+	}
+
+	RequiredUpdate::None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	fn state(finalized_slot: u64, has_next_sync_committee: bool) -> LightClientState {
+		LightClientState { finalized_slot, has_next_sync_committee }
+	}
+
+	#[test]
+	fn selects_sync_committee_update_when_next_sync_committee_is_unknown() {
+		assert_eq!(
+			select_required_update::<TestPipeline>(&state(64, false), 864),
+			RequiredUpdate::SyncCommittee(0),
+		);
+		assert_eq!(
+			select_required_update::<TestPipeline>(&state(8200, false), 8300),
+			RequiredUpdate::SyncCommittee(1),
+		);
+	}
+
+	#[test]
+	fn selects_sync_committee_update_when_source_is_in_next_period() {
+		assert_eq!(
+			select_required_update::<TestPipeline>(&state(864, true), 8192),
+			RequiredUpdate::SyncCommittee(1),
+		);
+	}
+
+	#[test]
+	fn selects_finality_update_within_period() {
+		assert_eq!(
+			select_required_update::<TestPipeline>(&state(64, true), 864),
+			RequiredUpdate::Finality,
+		);
+		assert_eq!(
+			select_required_update::<TestPipeline>(&state(864, true), 864),
+			RequiredUpdate::None
+		);
+	}
+
+	fn sync_params(bootstrap: bool) -> BeaconSyncParams {
+		BeaconSyncParams { tick: Duration::from_secs(1), bootstrap }
+	}
+
+	#[test]
+	fn when_source_is_syncing() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new().syncing();
+			let target = TestTargetClient::new(Some(state(64, false)));
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(true), None).await,
+				Ok(RequiredUpdate::None),
+			);
+			assert!(target.submitted().is_empty());
+		});
+	}
+
+	#[test]
+	fn when_light_client_is_not_initialized() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new().with_checkpoint("initial-checkpoint.json");
+			let target = TestTargetClient::new(None);
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(false), None).await,
+				Ok(RequiredUpdate::None),
+			);
+			assert!(target.checkpoints().is_empty());
+			assert_eq!(target.state(), None);
+		});
+	}
+
+	#[test]
+	fn bootstraps_light_client_with_checkpoint() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new()
+				.with_checkpoint("initial-checkpoint.json")
+				.with_finality_update("finalized-header-update.json")
+				.with_sync_committee_update(0, "sync-committee-update.json");
+			let target = TestTargetClient::new(None);
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(true), None).await,
+				Ok(RequiredUpdate::Checkpoint),
+			);
+			assert_eq!(target.state(), Some(state(64, false)));
+			assert_eq!(
+				target.checkpoints().iter().map(|c| c.header.slot).collect::<Vec<_>>(),
+				vec![64],
+			);
+
+			// once bootstrapped, the light client follows the source
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(true), None).await,
+				Ok(RequiredUpdate::SyncCommittee(0)),
+			);
+		});
+	}
+
+	#[test]
+	fn when_target_is_unable_to_submit_checkpoint() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new().with_checkpoint("initial-checkpoint.json");
+			let target = TestTargetClient::new(None);
+			target.deny_checkpoints();
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(true), None).await,
+				Ok(RequiredUpdate::None),
+			);
+			assert!(target.checkpoints().is_empty());
+			assert_eq!(target.state(), None);
+		});
+	}
+
+	#[test]
+	fn when_source_is_unavailable() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new();
+			let target = TestTargetClient::new(Some(state(64, false)));
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(false), None).await,
+				Err(FailedClient::Source),
+			);
+			assert!(target.submitted().is_empty());
+		});
+	}
+
+	#[test]
+	fn syncs_light_client() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new()
+				.with_finality_update("finalized-header-update.json")
+				.with_sync_committee_update(0, "sync-committee-update.json");
+			let target = TestTargetClient::new(Some(state(64, false)));
+			let params = sync_params(false);
+
+			assert_eq!(
+				sync_step(&source, &target, &params, None).await,
+				Ok(RequiredUpdate::SyncCommittee(0))
+			);
+			assert_eq!(
+				sync_step(&source, &target, &params, None).await,
+				Ok(RequiredUpdate::Finality)
+			);
+			assert_eq!(sync_step(&source, &target, &params, None).await, Ok(RequiredUpdate::None));
+
+			assert_eq!(target.state(), Some(state(864, true)));
+			assert_eq!(
+				target
+					.submitted()
+					.iter()
+					.map(|update| (update.finalized_slot(), update.has_next_sync_committee()))
+					.collect::<Vec<_>>(),
+				vec![(64, true), (864, false)],
+			);
+		});
+	}
+
+	#[test]
+	fn keeps_sync_committee_period_in_step() {
+		async_std::task::block_on(async {
+			let source = TestSourceClient::new()
+				.with_finality_update("next-finalized-header-update.json")
+				.with_sync_committee_update(1, "next-sync-committee-update.json");
+			let params = sync_params(false);
+
+			// the light client has followed the source up to slot 864 in period 0, while the
+			// source has already finalized the first header of period 1
+			let target = TestTargetClient::new(Some(state(864, true)));
+			assert_eq!(
+				sync_step(&source, &target, &params, None).await,
+				Ok(RequiredUpdate::SyncCommittee(1))
+			);
+			assert_eq!(sync_step(&source, &target, &params, None).await, Ok(RequiredUpdate::None));
+
+			assert_eq!(target.state(), Some(state(8192, true)));
+		});
+	}
+
+	#[test]
+	fn restarts_when_update_transaction_is_lost() {
+		async_std::task::block_on(async {
+			let source =
+				TestSourceClient::new().with_finality_update("finalized-header-update.json");
+			let target = TestTargetClient::new(Some(state(64, true)));
+			target.lose_transactions();
+
+			assert_eq!(
+				sync_step(&source, &target, &sync_params(false), None).await,
+				Err(FailedClient::Both)
+			);
+		});
+	}
+}
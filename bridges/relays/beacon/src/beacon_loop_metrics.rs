@@ -0,0 +1,60 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use relay_utils::metrics::{metric_name, register, Gauge, Metric, PrometheusError, Registry, U64};
+
+/// Beacon light client sync metrics.
+#[derive(Clone)]
+pub struct BeaconSyncLoopMetrics {
+	/// Best finalized beacon slot at the source.
+	best_source_finalized_slot: Gauge<U64>,
+	/// Best finalized beacon slot, known to the light client at the target.
+	best_target_finalized_slot: Gauge<U64>,
+}
+
+impl BeaconSyncLoopMetrics {
+	/// Create and register beacon sync loop metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(BeaconSyncLoopMetrics {
+			best_source_finalized_slot: Gauge::new(
+				metric_name(prefix, "best_source_finalized_slot"),
+				"Best finalized slot at the source beacon chain".to_string(),
+			)?,
+			best_target_finalized_slot: Gauge::new(
+				metric_name(prefix, "best_target_finalized_slot"),
+				"Best finalized beacon slot, known to the target chain".to_string(),
+			)?,
+		})
+	}
+
+	/// Update best finalized slot at source.
+	pub fn update_best_source_finalized_slot(&self, slot: u64) {
+		self.best_source_finalized_slot.set(slot);
+	}
+
+	/// Update best finalized slot at target.
+	pub fn update_best_target_finalized_slot(&self, slot: u64) {
+		self.best_target_finalized_slot.set(slot);
+	}
+}
+
+impl Metric for BeaconSyncLoopMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.best_source_finalized_slot.clone(), registry)?;
+		register(self.best_target_finalized_slot.clone(), registry)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,365 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Beacon state proofs.
+//!
+//! The standard light client API doesn't return the `block_roots` of the finalized beacon state,
+//! which the Ethereum light client pallet requires to verify ancestry proofs. So the relay reads
+//! the whole SSZ-encoded state from the beacon node and proves the `block_roots` field against
+//! the state root itself. Only the mainnet preset is supported, same as in the light client.
+
+use snowbridge_beacon_primitives::ssz::{
+	deneb::SSZExecutionPayloadHeader, SSZBeaconBlockHeader, SSZSyncCommittee,
+};
+use sp_core::{hashing::sha2_256, H256};
+use ssz_rs::prelude::*;
+use ssz_rs_derive::SimpleSerialize;
+
+/// Position of the `block_roots` field in the beacon state container.
+pub const BLOCK_ROOTS_FIELD_INDEX: usize = 5;
+
+const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
+const HISTORICAL_ROOTS_LIMIT: usize = 16_777_216;
+const ETH1_DATA_VOTES_LIMIT: usize = 2048;
+const VALIDATOR_REGISTRY_LIMIT: usize = 1_099_511_627_776;
+const EPOCHS_PER_HISTORICAL_VECTOR: usize = 65536;
+const EPOCHS_PER_SLASHINGS_VECTOR: usize = 8192;
+const JUSTIFICATION_BITS_LENGTH: usize = 4;
+const SYNC_COMMITTEE_SIZE: usize = 512;
+const PENDING_DEPOSITS_LIMIT: usize = 134_217_728;
+const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize = 134_217_728;
+const PENDING_CONSOLIDATIONS_LIMIT: usize = 262_144;
+
+/// Beacon state proof errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The state belongs to a fork that the relay doesn't support.
+	#[error("Unsupported beacon state fork: {0}")]
+	UnsupportedFork(String),
+	/// Failed to decode SSZ-encoded state.
+	#[error("Failed to decode beacon state: {0}")]
+	Decode(String),
+	/// Failed to compute the hash tree root of a state field.
+	#[error("Failed to merkleize beacon state: {0}")]
+	Merkleization(String),
+	/// The state doesn't match the header, which it has been requested for.
+	#[error("Beacon state root {actual:?} doesn't match the expected root {expected:?}")]
+	StateRootMismatch {
+		/// State root of the beacon header.
+		expected: H256,
+		/// Root of the state, returned by the beacon node.
+		actual: H256,
+	},
+}
+
+/// Proof of the `block_roots` field of a beacon state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockRootsProof {
+	/// Hash tree root of the `block_roots` vector.
+	pub block_roots_root: H256,
+	/// Merkle branch, proving the `block_roots_root` against the state root.
+	pub block_roots_branch: Vec<H256>,
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct Fork {
+	previous_version: [u8; 4],
+	current_version: [u8; 4],
+	epoch: u64,
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct Eth1Data {
+	deposit_root: [u8; 32],
+	deposit_count: u64,
+	block_hash: [u8; 32],
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct Validator {
+	pubkey: Vector<u8, 48>,
+	withdrawal_credentials: [u8; 32],
+	effective_balance: u64,
+	slashed: bool,
+	activation_eligibility_epoch: u64,
+	activation_epoch: u64,
+	exit_epoch: u64,
+	withdrawable_epoch: u64,
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct Checkpoint {
+	epoch: u64,
+	root: [u8; 32],
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct HistoricalSummary {
+	block_summary_root: [u8; 32],
+	state_summary_root: [u8; 32],
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct PendingDeposit {
+	pubkey: Vector<u8, 48>,
+	withdrawal_credentials: [u8; 32],
+	amount: u64,
+	signature: Vector<u8, 96>,
+	slot: u64,
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct PendingPartialWithdrawal {
+	validator_index: u64,
+	amount: u64,
+	withdrawable_epoch: u64,
+}
+
+#[derive(Default, SimpleSerialize)]
+pub(crate) struct PendingConsolidation {
+	source_index: u64,
+	target_index: u64,
+}
+
+/// Declares beacon state container of given fork, along with the method that returns hash tree
+/// roots of all its fields, in order.
+macro_rules! beacon_state {
+	($(#[$attr:meta])* $name:ident { $($field:ident: $ty:ty,)* }) => {
+		$(#[$attr])*
+		#[derive(Default, SimpleSerialize)]
+		pub(crate) struct $name {
+			$(pub(crate) $field: $ty,)*
+		}
+
+		impl $name {
+			fn field_roots(&mut self) -> Result<Vec<H256>, Error> {
+				Ok(vec![$(node_to_h256(
+					self.$field
+						.hash_tree_root()
+						.map_err(|e| Error::Merkleization(format!("{:?}", e)))?
+				),)*])
+			}
+		}
+	};
+}
+
+beacon_state!(
+	/// Beacon state of the Deneb fork.
+	DenebBeaconState {
+		genesis_time: u64,
+		genesis_validators_root: [u8; 32],
+		slot: u64,
+		fork: Fork,
+		latest_block_header: SSZBeaconBlockHeader,
+		block_roots: Vector<[u8; 32], SLOTS_PER_HISTORICAL_ROOT>,
+		state_roots: Vector<[u8; 32], SLOTS_PER_HISTORICAL_ROOT>,
+		historical_roots: List<[u8; 32], HISTORICAL_ROOTS_LIMIT>,
+		eth1_data: Eth1Data,
+		eth1_data_votes: List<Eth1Data, ETH1_DATA_VOTES_LIMIT>,
+		eth1_deposit_index: u64,
+		validators: List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+		balances: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+		randao_mixes: Vector<[u8; 32], EPOCHS_PER_HISTORICAL_VECTOR>,
+		slashings: Vector<u64, EPOCHS_PER_SLASHINGS_VECTOR>,
+		previous_epoch_participation: List<u8, VALIDATOR_REGISTRY_LIMIT>,
+		current_epoch_participation: List<u8, VALIDATOR_REGISTRY_LIMIT>,
+		justification_bits: Bitvector<JUSTIFICATION_BITS_LENGTH>,
+		previous_justified_checkpoint: Checkpoint,
+		current_justified_checkpoint: Checkpoint,
+		finalized_checkpoint: Checkpoint,
+		inactivity_scores: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+		current_sync_committee: SSZSyncCommittee<SYNC_COMMITTEE_SIZE>,
+		next_sync_committee: SSZSyncCommittee<SYNC_COMMITTEE_SIZE>,
+		latest_execution_payload_header: SSZExecutionPayloadHeader,
+		next_withdrawal_index: u64,
+		next_withdrawal_validator_index: u64,
+		historical_summaries: List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>,
+	}
+);
+
+beacon_state!(
+	/// Beacon state of the Electra fork. The execution payload header in the state is the same
+	/// as in Deneb.
+	ElectraBeaconState {
+		genesis_time: u64,
+		genesis_validators_root: [u8; 32],
+		slot: u64,
+		fork: Fork,
+		latest_block_header: SSZBeaconBlockHeader,
+		block_roots: Vector<[u8; 32], SLOTS_PER_HISTORICAL_ROOT>,
+		state_roots: Vector<[u8; 32], SLOTS_PER_HISTORICAL_ROOT>,
+		historical_roots: List<[u8; 32], HISTORICAL_ROOTS_LIMIT>,
+		eth1_data: Eth1Data,
+		eth1_data_votes: List<Eth1Data, ETH1_DATA_VOTES_LIMIT>,
+		eth1_deposit_index: u64,
+		validators: List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+		balances: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+		randao_mixes: Vector<[u8; 32], EPOCHS_PER_HISTORICAL_VECTOR>,
+		slashings: Vector<u64, EPOCHS_PER_SLASHINGS_VECTOR>,
+		previous_epoch_participation: List<u8, VALIDATOR_REGISTRY_LIMIT>,
+		current_epoch_participation: List<u8, VALIDATOR_REGISTRY_LIMIT>,
+		justification_bits: Bitvector<JUSTIFICATION_BITS_LENGTH>,
+		previous_justified_checkpoint: Checkpoint,
+		current_justified_checkpoint: Checkpoint,
+		finalized_checkpoint: Checkpoint,
+		inactivity_scores: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+		current_sync_committee: SSZSyncCommittee<SYNC_COMMITTEE_SIZE>,
+		next_sync_committee: SSZSyncCommittee<SYNC_COMMITTEE_SIZE>,
+		latest_execution_payload_header: SSZExecutionPayloadHeader,
+		next_withdrawal_index: u64,
+		next_withdrawal_validator_index: u64,
+		historical_summaries: List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>,
+		deposit_requests_start_index: u64,
+		deposit_balance_to_consume: u64,
+		exit_balance_to_consume: u64,
+		earliest_exit_epoch: u64,
+		consolidation_balance_to_consume: u64,
+		earliest_consolidation_epoch: u64,
+		pending_deposits: List<PendingDeposit, PENDING_DEPOSITS_LIMIT>,
+		pending_partial_withdrawals: List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>,
+		pending_consolidations: List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>,
+	}
+);
+
+/// Prove the `block_roots` field of the SSZ-encoded beacon state of the given fork (as named in
+/// the `Eth-Consensus-Version` header of the beacon-API response). Fails if the root of the
+/// decoded state doesn't match the `expected_state_root`.
+pub fn block_roots_proof(
+	fork: &str,
+	encoded_state: &[u8],
+	expected_state_root: H256,
+) -> Result<BlockRootsProof, Error> {
+	let field_roots = match fork {
+		"deneb" => decode::<DenebBeaconState>(encoded_state)?.field_roots()?,
+		"electra" => decode::<ElectraBeaconState>(encoded_state)?.field_roots()?,
+		_ => return Err(Error::UnsupportedFork(fork.into())),
+	};
+
+	let block_roots_root = field_roots[BLOCK_ROOTS_FIELD_INDEX];
+	let (state_root, block_roots_branch) = merkle_branch(field_roots, BLOCK_ROOTS_FIELD_INDEX);
+	if state_root != expected_state_root {
+		return Err(Error::StateRootMismatch { expected: expected_state_root, actual: state_root })
+	}
+
+	Ok(BlockRootsProof { block_roots_root, block_roots_branch })
+}
+
+fn decode<T: Deserialize>(encoded_state: &[u8]) -> Result<T, Error> {
+	T::deserialize(encoded_state).map_err(|e| Error::Decode(format!("{:?}", e)))
+}
+
+fn node_to_h256(node: Node) -> H256 {
+	H256::from_slice(node.as_ref())
+}
+
+/// Build the merkle tree over given leaves (padded with zero leaves to the next power of two)
+/// and return its root along with the branch of the leaf at `index`.
+fn merkle_branch(mut layer: Vec<H256>, mut index: usize) -> (H256, Vec<H256>) {
+	layer.resize(layer.len().next_power_of_two(), H256::zero());
+
+	let mut branch = Vec::new();
+	while layer.len() > 1 {
+		branch.push(layer[index ^ 1]);
+		layer = layer
+			.chunks(2)
+			.map(|pair| {
+				let mut data = [0u8; 64];
+				data[..32].copy_from_slice(pair[0].as_bytes());
+				data[32..].copy_from_slice(pair[1].as_bytes());
+				sha2_256(&data).into()
+			})
+			.collect();
+		index /= 2;
+	}
+
+	(layer[0], branch)
+}
+
+/// Returns SSZ-encoded Deneb beacon state at given slot with some non-default `block_roots`,
+/// along with its root.
+#[cfg(test)]
+pub(crate) fn test_deneb_state(slot: u64) -> (Vec<u8>, H256) {
+	let mut state = DenebBeaconState { slot, ..Default::default() };
+	state.block_roots[0] = [1; 32];
+	state.block_roots[(slot as usize - 1) % SLOTS_PER_HISTORICAL_ROOT] = [2; 32];
+	state.latest_block_header.slot = slot;
+
+	let state_root = node_to_h256(state.hash_tree_root().unwrap());
+	(serialize(&state).unwrap(), state_root)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use snowbridge_beacon_primitives::merkle_proof::verify_merkle_branch;
+
+	#[test]
+	fn proves_block_roots_of_deneb_state() {
+		let (encoded_state, state_root) = test_deneb_state(864);
+		let proof = block_roots_proof("deneb", &encoded_state, state_root).unwrap();
+
+		assert_eq!(proof.block_roots_branch.len(), 5);
+		assert!(verify_merkle_branch(
+			proof.block_roots_root,
+			&proof.block_roots_branch,
+			BLOCK_ROOTS_FIELD_INDEX,
+			5,
+			state_root,
+		));
+	}
+
+	#[test]
+	fn proves_block_roots_of_electra_state() {
+		let mut state = ElectraBeaconState { slot: 64, ..Default::default() };
+		state.block_roots[63] = [3; 32];
+		state.earliest_exit_epoch = 2;
+		let state_root = node_to_h256(state.hash_tree_root().unwrap());
+		let encoded_state = serialize(&state).unwrap();
+
+		let proof = block_roots_proof("electra", &encoded_state, state_root).unwrap();
+		assert_eq!(proof.block_roots_branch.len(), 6);
+		assert!(verify_merkle_branch(
+			proof.block_roots_root,
+			&proof.block_roots_branch,
+			BLOCK_ROOTS_FIELD_INDEX,
+			6,
+			state_root,
+		));
+	}
+
+	#[test]
+	fn rejects_state_with_unexpected_root() {
+		let (encoded_state, state_root) = test_deneb_state(864);
+		assert!(matches!(
+			block_roots_proof("deneb", &encoded_state, H256::repeat_byte(1)),
+			Err(Error::StateRootMismatch { actual, .. }) if actual == state_root,
+		));
+	}
+
+	#[test]
+	fn rejects_state_of_unknown_fork() {
+		let (encoded_state, state_root) = test_deneb_state(864);
+		assert!(matches!(
+			block_roots_proof("capella", &encoded_state, state_root),
+			Err(Error::UnsupportedFork(_)),
+		));
+		// the Deneb state is not a valid Electra state
+		assert!(matches!(
+			block_roots_proof("electra", &encoded_state, state_root),
+			Err(Error::Decode(_)),
+		));
+	}
+}
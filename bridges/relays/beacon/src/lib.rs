@@ -0,0 +1,90 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ethereum beacon chain -> Substrate light client relay. The relay reads finalized beacon
+//! headers and sync committee updates from the light client API of a beacon node and submits
+//! them to the `snowbridge-pallet-ethereum-client` at the target chain.
+
+#![warn(missing_docs)]
+
+use snowbridge_beacon_primitives::{CheckpointUpdate, Update};
+use std::fmt::Debug;
+
+pub mod beacon_api;
+pub mod beacon_loop;
+pub mod beacon_loop_metrics;
+pub mod beacon_state;
+
+#[cfg(test)]
+mod mock;
+
+/// Beacon chain light client synchronization pipeline.
+pub trait BeaconSyncPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Name of the beacon chain.
+	const SOURCE_NAME: &'static str;
+	/// Name of the chain, where the beacon light client is deployed.
+	const TARGET_NAME: &'static str;
+	/// Number of slots in a single sync committee period.
+	const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64;
+
+	/// Update that is used to bootstrap the light client.
+	type CheckpointUpdate: BeaconCheckpointUpdate;
+	/// Finalized header update, that may also carry the next sync committee.
+	type Update: BeaconUpdate;
+}
+
+/// Light client bootstrap data.
+pub trait BeaconCheckpointUpdate: 'static + Clone + Debug + Send + Sync {
+	/// Slot of the checkpoint header.
+	fn slot(&self) -> u64;
+}
+
+/// Finalized beacon header update.
+pub trait BeaconUpdate: 'static + Clone + Debug + Send + Sync {
+	/// Slot of the header that is attested by the sync committee.
+	fn attested_slot(&self) -> u64;
+	/// Slot of the finalized header.
+	fn finalized_slot(&self) -> u64;
+	/// Returns true if the update carries the sync committee of the next period.
+	fn has_next_sync_committee(&self) -> bool;
+}
+
+impl<const COMMITTEE_SIZE: usize> BeaconCheckpointUpdate for CheckpointUpdate<COMMITTEE_SIZE> {
+	fn slot(&self) -> u64 {
+		self.header.slot
+	}
+}
+
+impl<const COMMITTEE_SIZE: usize, const COMMITTEE_BITS_SIZE: usize> BeaconUpdate
+	for Update<COMMITTEE_SIZE, COMMITTEE_BITS_SIZE>
+{
+	fn attested_slot(&self) -> u64 {
+		self.attested_header.slot
+	}
+
+	fn finalized_slot(&self) -> u64 {
+		self.finalized_header.slot
+	}
+
+	fn has_next_sync_committee(&self) -> bool {
+		self.next_sync_committee_update.is_some()
+	}
+}
+
+/// Returns the sync committee period of the given slot.
+pub fn sync_committee_period<P: BeaconSyncPipeline>(slot: u64) -> u64 {
+	slot / P::SLOTS_PER_SYNC_COMMITTEE_PERIOD
+}
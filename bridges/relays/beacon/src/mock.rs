@@ -0,0 +1,446 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Test clients for the beacon sync loop: a local beacon-API stand-in, serving standard
+//! beacon-API responses, an in-memory beacon source, serving the Ethereum light client fixtures,
+//! and an in-memory light client at the target chain.
+
+pub use crate::beacon_api::{
+	beacon_state_path, bootstrap_path, sync_committee_update_path, BeaconApiClient,
+	FINALITY_UPDATE_PATH, FINALIZED_HEADER_PATH, GENESIS_PATH, SYNCING_PATH,
+};
+
+use crate::{
+	beacon_loop::{LightClientState, SourceClient, TargetClient},
+	BeaconCheckpointUpdate, BeaconSyncPipeline, BeaconUpdate,
+};
+
+use async_std::{
+	io::BufReader,
+	net::{TcpListener, TcpStream},
+	prelude::*,
+};
+use async_trait::async_trait;
+use relay_utils::{
+	relay_loop::Client as RelayClient, MaybeConnectionError, TrackedTransactionStatus,
+	TransactionTracker,
+};
+use serde_json::{json, Value};
+use snowbridge_beacon_primitives::{BeaconHeader, SyncAggregate, SyncCommittee};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+/// Beacon node sync status response of a synced node.
+pub const SYNCED_RESPONSE: &str =
+	r#"{"data":{"head_slot":"8260","sync_distance":"0","is_syncing":false}}"#;
+
+/// Checkpoint update type that is used in tests.
+pub type TestCheckpointUpdate = snowbridge_beacon_primitives::CheckpointUpdate<512>;
+/// Update type that is used in tests.
+pub type TestUpdate = snowbridge_beacon_primitives::Update<512, 64>;
+
+#[derive(Clone, Debug)]
+pub struct TestPipeline;
+
+impl BeaconSyncPipeline for TestPipeline {
+	const SOURCE_NAME: &'static str = "Ethereum";
+	const TARGET_NAME: &'static str = "BridgeHub";
+	const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64 = 8192;
+
+	type CheckpointUpdate = TestCheckpointUpdate;
+	type Update = TestUpdate;
+}
+
+/// Returns content of the Ethereum light client fixture file.
+pub fn fixture(name: &str) -> String {
+	let path = format!(
+		"{}/../../snowbridge/pallets/ethereum-client/tests/fixtures/{}",
+		env!("CARGO_MANIFEST_DIR"),
+		name,
+	);
+	std::fs::read_to_string(path).unwrap()
+}
+
+/// Returns the update from the Ethereum light client fixture file.
+pub fn update_fixture(name: &str) -> TestUpdate {
+	serde_json::from_str(&fixture(name)).unwrap()
+}
+
+/// Returns the checkpoint update from the Ethereum light client fixture file.
+pub fn checkpoint_fixture(name: &str) -> TestCheckpointUpdate {
+	serde_json::from_str(&fixture(name)).unwrap()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
+
+/// Beacon header in the beacon-API format.
+pub fn api_header(header: &BeaconHeader) -> Value {
+	json!({
+		"slot": header.slot.to_string(),
+		"proposer_index": header.proposer_index.to_string(),
+		"parent_root": header.parent_root,
+		"state_root": header.state_root,
+		"body_root": header.body_root,
+	})
+}
+
+fn api_light_client_header(header: &BeaconHeader) -> Value {
+	// the relay doesn't use execution payload headers of light client headers
+	json!({ "beacon": api_header(header), "execution": {}, "execution_branch": [] })
+}
+
+fn api_sync_committee(sync_committee: &SyncCommittee<512>) -> Value {
+	json!({
+		"pubkeys": sync_committee.pubkeys.iter().map(|pubkey| to_hex(&pubkey.0)).collect::<Vec<_>>(),
+		"aggregate_pubkey": to_hex(&sync_committee.aggregate_pubkey.0),
+	})
+}
+
+fn api_sync_aggregate(sync_aggregate: &SyncAggregate<512, 64>) -> Value {
+	json!({
+		"sync_committee_bits": to_hex(&sync_aggregate.sync_committee_bits),
+		"sync_committee_signature": to_hex(&sync_aggregate.sync_committee_signature.0),
+	})
+}
+
+/// Light client finality update in the beacon-API format.
+pub fn api_finality_update(update: &TestUpdate) -> String {
+	json!({
+		"version": "deneb",
+		"data": {
+			"attested_header": api_light_client_header(&update.attested_header),
+			"finalized_header": api_light_client_header(&update.finalized_header),
+			"finality_branch": update.finality_branch,
+			"sync_aggregate": api_sync_aggregate(&update.sync_aggregate),
+			"signature_slot": update.signature_slot.to_string(),
+		},
+	})
+	.to_string()
+}
+
+/// Light client updates response in the beacon-API format.
+pub fn api_sync_committee_updates(update: &TestUpdate) -> String {
+	let next_sync_committee_update = update.next_sync_committee_update.as_ref().unwrap();
+	json!([{
+		"version": "deneb",
+		"data": {
+			"attested_header": api_light_client_header(&update.attested_header),
+			"next_sync_committee": api_sync_committee(&next_sync_committee_update.next_sync_committee),
+			"next_sync_committee_branch": next_sync_committee_update.next_sync_committee_branch,
+			"finalized_header": api_light_client_header(&update.finalized_header),
+			"finality_branch": update.finality_branch,
+			"sync_aggregate": api_sync_aggregate(&update.sync_aggregate),
+			"signature_slot": update.signature_slot.to_string(),
+		},
+	}])
+	.to_string()
+}
+
+/// Light client bootstrap in the beacon-API format.
+pub fn api_bootstrap(checkpoint: &TestCheckpointUpdate) -> String {
+	json!({
+		"version": "deneb",
+		"data": {
+			"header": api_light_client_header(&checkpoint.header),
+			"current_sync_committee": api_sync_committee(&checkpoint.current_sync_committee),
+			"current_sync_committee_branch": checkpoint.current_sync_committee_branch,
+		},
+	})
+	.to_string()
+}
+
+/// Response of the beacon-API stand-in.
+#[derive(Clone, Debug)]
+pub struct StandInResponse {
+	content_type: &'static str,
+	consensus_version: Option<&'static str>,
+	body: Vec<u8>,
+}
+
+impl StandInResponse {
+	/// JSON response.
+	pub fn json(body: impl Into<String>) -> Self {
+		StandInResponse {
+			content_type: "application/json",
+			consensus_version: None,
+			body: body.into().into_bytes(),
+		}
+	}
+
+	/// SSZ-encoded object of the given fork.
+	pub fn ssz(consensus_version: &'static str, body: Vec<u8>) -> Self {
+		StandInResponse {
+			content_type: "application/octet-stream",
+			consensus_version: Some(consensus_version),
+			body,
+		}
+	}
+}
+
+/// Local HTTP server, that is serving fixed responses at given paths.
+pub struct BeaconApiStandIn {
+	url: String,
+}
+
+impl BeaconApiStandIn {
+	/// Start serving `(path, response)` routes at a random local port.
+	pub async fn start(routes: Vec<(String, StandInResponse)>) -> Self {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let routes: Arc<HashMap<String, StandInResponse>> = Arc::new(routes.into_iter().collect());
+		async_std::task::spawn(async move {
+			let mut incoming = listener.incoming();
+			while let Some(Ok(stream)) = incoming.next().await {
+				async_std::task::spawn(serve(stream, routes.clone()));
+			}
+		});
+		BeaconApiStandIn { url }
+	}
+
+	/// Returns beacon-API client, connected to this stand-in.
+	pub fn client(&self) -> BeaconApiClient<TestPipeline> {
+		BeaconApiClient::new(self.url.clone()).unwrap()
+	}
+}
+
+async fn serve(mut stream: TcpStream, routes: Arc<HashMap<String, StandInResponse>>) {
+	let mut reader = BufReader::new(stream.clone());
+	let mut request_line = String::new();
+	if reader.read_line(&mut request_line).await.is_err() {
+		return
+	}
+	// we don't care about request headers
+	loop {
+		let mut header = String::new();
+		match reader.read_line(&mut header).await {
+			Ok(0) | Err(_) => break,
+			Ok(_) if header == "\r\n" => break,
+			Ok(_) => continue,
+		}
+	}
+
+	let path = request_line
+		.split_whitespace()
+		.nth(1)
+		.unwrap_or_default()
+		.trim_start_matches('/');
+	let (status, response) = match routes.get(path) {
+		Some(response) => ("200 OK", response.clone()),
+		None => ("404 Not Found", StandInResponse::json("")),
+	};
+	let consensus_version = response
+		.consensus_version
+		.map(|version| format!("Eth-Consensus-Version: {version}\r\n"))
+		.unwrap_or_default();
+	let head = format!(
+		"HTTP/1.1 {status}\r\nContent-Type: {}\r\n{consensus_version}Content-Length: {}\r\nConnection: close\r\n\r\n",
+		response.content_type,
+		response.body.len(),
+	);
+	let _ = stream.write_all(head.as_bytes()).await;
+	let _ = stream.write_all(&response.body).await;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestError {
+	Error,
+}
+
+impl MaybeConnectionError for TestError {
+	fn is_connection_error(&self) -> bool {
+		false
+	}
+}
+
+/// In-memory beacon source, serving the Ethereum light client fixtures.
+#[derive(Clone, Debug, Default)]
+pub struct TestSourceClient {
+	is_syncing: bool,
+	checkpoint: Option<TestCheckpointUpdate>,
+	finality_update: Option<TestUpdate>,
+	sync_committee_updates: HashMap<u64, TestUpdate>,
+}
+
+impl TestSourceClient {
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	pub fn syncing(mut self) -> Self {
+		self.is_syncing = true;
+		self
+	}
+
+	pub fn with_checkpoint(mut self, name: &str) -> Self {
+		self.checkpoint = Some(checkpoint_fixture(name));
+		self
+	}
+
+	pub fn with_finality_update(mut self, name: &str) -> Self {
+		self.finality_update = Some(update_fixture(name));
+		self
+	}
+
+	pub fn with_sync_committee_update(mut self, period: u64, name: &str) -> Self {
+		self.sync_committee_updates.insert(period, update_fixture(name));
+		self
+	}
+}
+
+#[async_trait]
+impl RelayClient for TestSourceClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl SourceClient<TestPipeline> for TestSourceClient {
+	async fn ensure_synced(&self) -> Result<bool, TestError> {
+		Ok(!self.is_syncing)
+	}
+
+	async fn finalized_slot(&self) -> Result<u64, TestError> {
+		self.finality_update().await.map(|update| update.finalized_slot())
+	}
+
+	async fn checkpoint_update(&self) -> Result<TestCheckpointUpdate, TestError> {
+		self.checkpoint.clone().ok_or(TestError::Error)
+	}
+
+	async fn finality_update(&self) -> Result<TestUpdate, TestError> {
+		self.finality_update.clone().ok_or(TestError::Error)
+	}
+
+	async fn sync_committee_update(&self, period: u64) -> Result<TestUpdate, TestError> {
+		self.sync_committee_updates.get(&period).cloned().ok_or(TestError::Error)
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct TestTransactionTracker(TrackedTransactionStatus<()>);
+
+#[async_trait]
+impl TransactionTracker for TestTransactionTracker {
+	type HeaderId = ();
+
+	async fn wait(self) -> TrackedTransactionStatus<()> {
+		self.0
+	}
+}
+
+/// In-memory light client, that is following the slots and sync committees of submitted updates.
+#[derive(Clone, Debug)]
+pub struct TestTargetClient {
+	data: Arc<Mutex<TestTargetData>>,
+}
+
+#[derive(Debug)]
+struct TestTargetData {
+	state: Option<LightClientState>,
+	submitted: Vec<TestUpdate>,
+	checkpoints: Vec<TestCheckpointUpdate>,
+	can_submit_checkpoint: bool,
+	lose_transactions: bool,
+}
+
+impl TestTargetClient {
+	pub fn new(state: Option<LightClientState>) -> Self {
+		TestTargetClient {
+			data: Arc::new(Mutex::new(TestTargetData {
+				state,
+				submitted: vec![],
+				checkpoints: vec![],
+				can_submit_checkpoint: true,
+				lose_transactions: false,
+			})),
+		}
+	}
+
+	pub fn state(&self) -> Option<LightClientState> {
+		self.data.lock().unwrap().state
+	}
+
+	pub fn submitted(&self) -> Vec<TestUpdate> {
+		self.data.lock().unwrap().submitted.clone()
+	}
+
+	pub fn checkpoints(&self) -> Vec<TestCheckpointUpdate> {
+		self.data.lock().unwrap().checkpoints.clone()
+	}
+
+	pub fn deny_checkpoints(&self) {
+		self.data.lock().unwrap().can_submit_checkpoint = false;
+	}
+
+	pub fn lose_transactions(&self) {
+		self.data.lock().unwrap().lose_transactions = true;
+	}
+}
+
+#[async_trait]
+impl RelayClient for TestTargetClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl TargetClient<TestPipeline> for TestTargetClient {
+	type TransactionTracker = TestTransactionTracker;
+
+	async fn light_client_state(&self) -> Result<Option<LightClientState>, TestError> {
+		Ok(self.data.lock().unwrap().state)
+	}
+
+	async fn submit_update(&self, update: TestUpdate) -> Result<TestTransactionTracker, TestError> {
+		let mut data = self.data.lock().unwrap();
+		if data.lose_transactions {
+			return Ok(TestTransactionTracker(TrackedTransactionStatus::Lost))
+		}
+
+		let state = data.state.as_mut().ok_or(TestError::Error)?;
+		state.has_next_sync_committee |= update.has_next_sync_committee();
+		state.finalized_slot = std::cmp::max(state.finalized_slot, update.finalized_slot());
+		data.submitted.push(update);
+		Ok(TestTransactionTracker(TrackedTransactionStatus::Finalized(())))
+	}
+
+	async fn submit_checkpoint(
+		&self,
+		update: TestCheckpointUpdate,
+	) -> Result<Option<TestTransactionTracker>, TestError> {
+		let mut data = self.data.lock().unwrap();
+		if !data.can_submit_checkpoint {
+			return Ok(None)
+		}
+
+		data.state = Some(LightClientState {
+			finalized_slot: update.slot(),
+			has_next_sync_committee: false,
+		});
+		data.checkpoints.push(update);
+		Ok(Some(TestTransactionTracker(TrackedTransactionStatus::Finalized(()))))
+	}
+}
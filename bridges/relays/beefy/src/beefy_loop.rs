@@ -0,0 +1,673 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY finality synchronization loop.
+//!
+//! The loop is subscribed to BEEFY justifications of the source chain and remembers the best
+//! commitment of every validator set. Every tick it reads the light client state at the target
+//! chain and submits the best commitment, signed by the validator set that the light client
+//! expects, if it is newer than the best commitment, imported by the light client. The MMR leaf of
+//! this commitment carries the next validator set, so the light client follows validator set
+//! changes as long as the relay sees at least one commitment of every set. If the relay has missed
+//! all commitments of the expected set (e.g. because it has been offline), it reads the commitment
+//! of the set handoff block from the source chain. BEEFY validators always sign the first block of
+//! every set and the commitment is stored in that block justifications.
+//!
+//! Every received commitment is also checked against other commitments of the same round. If they
+//! conflict, validators that have signed both commitments are reported to the source chain.
+
+use crate::{
+	beefy_loop_metrics::BeefySyncLoopMetrics,
+	equivocations::{double_votes, EquivocationsDetector},
+	BeefySyncPipeline, DoubleVotingProofOf, SignedCommitmentOf, ValidatorSetOf,
+};
+
+use async_trait::async_trait;
+use futures::{
+	future::FutureExt,
+	select_biased,
+	stream::{FuturesUnordered, Stream, StreamExt},
+};
+use relay_substrate_client::{BlockNumberOf, Chain};
+use relay_utils::{
+	interval, metrics::MetricsParams, relay_loop::Client as RelayClient, FailedClient,
+	TrackedTransactionStatus, TransactionTracker,
+};
+use sp_consensus_beefy::ValidatorSetId;
+use std::{collections::BTreeMap, future::Future, time::Duration};
+
+/// BEEFY light client state at the target chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LightClientState<BlockNumber> {
+	/// Number of the source block, whose commitment has been imported by the light client last.
+	pub best_block_number: BlockNumber,
+	/// Identifier of the validator set, that must sign the next imported commitment.
+	pub validator_set_id: ValidatorSetId,
+}
+
+/// BEEFY sync loop parameters.
+#[derive(Clone, Debug)]
+pub struct BeefySyncParams {
+	/// Interval at which the loop checks whether the light client needs a new commitment.
+	pub tick: Duration,
+	/// Number of recent rounds, whose commitments are kept to detect double voting.
+	pub equivocation_detection_rounds: usize,
+}
+
+/// Source client used in BEEFY synchronization loop.
+#[async_trait]
+pub trait SourceClient<P: BeefySyncPipeline>: RelayClient {
+	/// Stream of signed commitments.
+	type CommitmentsStream: Stream<Item = SignedCommitmentOf<P>> + Send + Unpin;
+	/// Transaction tracker to track submitted equivocation reports.
+	type TransactionTracker: TransactionTracker;
+
+	/// Subscribe to new signed commitments.
+	async fn commitments(&self) -> Result<Self::CommitmentsStream, Self::Error>;
+
+	/// Get number of the best finalized block.
+	async fn best_finalized_block_number(
+		&self,
+	) -> Result<BlockNumberOf<P::SourceChain>, Self::Error>;
+
+	/// Get signed commitment, stored in justifications of the given block. Commitments of
+	/// mandatory blocks, where validator set is changed, are always stored.
+	async fn stored_commitment(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<SignedCommitmentOf<P>>, Self::Error>;
+
+	/// Get validator set, that is active at given block.
+	async fn validator_set(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<ValidatorSetOf<P>, Self::Error>;
+
+	/// Prepare proof of the commitment, signed by the given validator set.
+	async fn prepare_commitment_proof(
+		&self,
+		commitment: SignedCommitmentOf<P>,
+		validator_set: ValidatorSetOf<P>,
+	) -> Result<P::CommitmentProof, Self::Error>;
+
+	/// Report double voting to the source chain.
+	async fn report_double_voting(
+		&self,
+		proof: DoubleVotingProofOf<P>,
+	) -> Result<Self::TransactionTracker, Self::Error>;
+}
+
+/// Target client used in BEEFY synchronization loop.
+#[async_trait]
+pub trait TargetClient<P: BeefySyncPipeline>: RelayClient {
+	/// Transaction tracker to track submitted transactions.
+	type TransactionTracker: TransactionTracker;
+
+	/// Get light client state. Returns `None` if the light client is not yet initialized.
+	async fn light_client_state(
+		&self,
+	) -> Result<Option<LightClientState<BlockNumberOf<P::SourceChain>>>, Self::Error>;
+
+	/// Submit commitment proof to the light client.
+	async fn submit_commitment(
+		&self,
+		proof: P::CommitmentProof,
+	) -> Result<Self::TransactionTracker, Self::Error>;
+}
+
+/// Best commitments of recent validator sets, received from the source chain.
+pub struct CommitmentsCache<P: BeefySyncPipeline> {
+	best_commitments: BTreeMap<ValidatorSetId, SignedCommitmentOf<P>>,
+}
+
+impl<P: BeefySyncPipeline> Default for CommitmentsCache<P> {
+	fn default() -> Self {
+		CommitmentsCache { best_commitments: BTreeMap::new() }
+	}
+}
+
+impl<P: BeefySyncPipeline> CommitmentsCache<P> {
+	/// Remember commitment, if it is the best known commitment of its validator set.
+	pub fn note_commitment(&mut self, commitment: &SignedCommitmentOf<P>) {
+		let set_id = commitment.commitment.validator_set_id;
+		let is_better = self
+			.best_commitments
+			.get(&set_id)
+			.map(|best| commitment.commitment.block_number > best.commitment.block_number)
+			.unwrap_or(true);
+		if is_better {
+			self.best_commitments.insert(set_id, commitment.clone());
+		}
+	}
+
+	/// Return best known commitment of the given validator set. Commitments of previous sets are
+	/// forgotten, because the light client will never accept them.
+	pub fn best_commitment(&mut self, set_id: ValidatorSetId) -> Option<&SignedCommitmentOf<P>> {
+		self.best_commitments = self.best_commitments.split_off(&set_id);
+		self.best_commitments.get(&set_id)
+	}
+}
+
+/// Return prefix that will be used by default to expose Prometheus metrics of the BEEFY sync
+/// loop.
+pub fn metrics_prefix<P: BeefySyncPipeline>() -> String {
+	format!("{}_to_{}_BeefySync", P::SourceChain::NAME, P::TargetChain::NAME)
+}
+
+/// Run BEEFY finality synchronization.
+pub async fn run<P: BeefySyncPipeline>(
+	source_client: impl SourceClient<P>,
+	target_client: impl TargetClient<P>,
+	sync_params: BeefySyncParams,
+	metrics_params: MetricsParams,
+	exit_signal: impl Future<Output = ()> + 'static + Send,
+) -> Result<(), relay_utils::Error> {
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} BEEFY finality relay",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+	);
+
+	let exit_signal = exit_signal.shared();
+	relay_utils::relay_loop(source_client, target_client)
+		.with_metrics(metrics_params)
+		.loop_metric(BeefySyncLoopMetrics::new(Some(&metrics_prefix::<P>()))?)?
+		.expose()
+		.await?
+		.run(metrics_prefix::<P>(), move |source_client, target_client, metrics| {
+			run_until_connection_lost(
+				source_client,
+				target_client,
+				sync_params.clone(),
+				metrics,
+				exit_signal.clone(),
+			)
+		})
+		.await
+}
+
+/// Run BEEFY finality synchronization until connection with either of clients is lost.
+async fn run_until_connection_lost<P: BeefySyncPipeline, SC: SourceClient<P>>(
+	source_client: SC,
+	target_client: impl TargetClient<P>,
+	sync_params: BeefySyncParams,
+	metrics: Option<BeefySyncLoopMetrics>,
+	exit_signal: impl Future<Output = ()> + Send,
+) -> Result<(), FailedClient> {
+	let commitments = source_client.commitments().await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to subscribe to {} BEEFY commitments: {:?}",
+			P::SourceChain::NAME,
+			e,
+		);
+		FailedClient::Source
+	})?;
+	let commitments = commitments.fuse();
+	let tick_stream = interval(sync_params.tick).fuse();
+	let exit_signal = exit_signal.fuse();
+	futures::pin_mut!(commitments, tick_stream, exit_signal);
+
+	let mut commitments_cache = CommitmentsCache::<P>::default();
+	let mut equivocations_detector =
+		EquivocationsDetector::<P>::new(sync_params.equivocation_detection_rounds);
+	let mut pending_reports = FuturesUnordered::new();
+
+	loop {
+		select_biased! {
+			_ = exit_signal => return Ok(()),
+			commitment = commitments.next() => {
+				let commitment = commitment.ok_or_else(|| {
+					log::warn!(
+						target: "bridge",
+						"{} BEEFY commitments stream has ended. Going to restart",
+						P::SourceChain::NAME,
+					);
+					FailedClient::Source
+				})?;
+				if let Some(metrics) = metrics.as_ref() {
+					metrics.update_best_source_block_number(commitment.commitment.block_number);
+				}
+
+				commitments_cache.note_commitment(&commitment);
+				let conflicting_commitments =
+					equivocations_detector.note_commitment(commitment.clone());
+				for conflicting_commitment in conflicting_commitments {
+					let trackers = report_double_votes(
+						&source_client,
+						&conflicting_commitment,
+						&commitment,
+						metrics.as_ref(),
+					)
+					.await?;
+					pending_reports.extend(trackers.into_iter().map(|tracker| tracker.wait()));
+				}
+			},
+			status = pending_reports.select_next_some() => {
+				if let TrackedTransactionStatus::Lost = status {
+					log::warn!(
+						target: "bridge",
+						"{} BEEFY double voting report transaction is considered lost",
+						P::SourceChain::NAME,
+					);
+				}
+			},
+			_ = tick_stream.next() => {
+				sync_step(&source_client, &target_client, &mut commitments_cache, metrics.as_ref())
+					.await?;
+			},
+		}
+	}
+}
+
+/// Perform single synchronization step: select the commitment that the light client needs,
+/// submit it and wait until the transaction is finalized. Returns the number of the block, whose
+/// commitment has been submitted.
+pub async fn sync_step<P: BeefySyncPipeline>(
+	source_client: &impl SourceClient<P>,
+	target_client: &impl TargetClient<P>,
+	commitments_cache: &mut CommitmentsCache<P>,
+	metrics: Option<&BeefySyncLoopMetrics>,
+) -> Result<Option<BlockNumberOf<P::SourceChain>>, FailedClient> {
+	let state = match target_client.light_client_state().await {
+		Ok(Some(state)) => state,
+		Ok(None) => {
+			log::warn!(
+				target: "bridge",
+				"{} BEEFY light client at {} is not initialized",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+			);
+			return Ok(None)
+		},
+		Err(e) => {
+			log::warn!(
+				target: "bridge",
+				"Failed to read {} BEEFY light client state from {}: {:?}",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				e,
+			);
+			return Err(FailedClient::Target)
+		},
+	};
+	if let Some(metrics) = metrics {
+		metrics.update_best_target_block_number(state.best_block_number);
+	}
+
+	let commitment = match commitments_cache.best_commitment(state.validator_set_id) {
+		Some(commitment) if commitment.commitment.block_number > state.best_block_number =>
+			commitment.clone(),
+		_ => match handoff_commitment(source_client, &state).await? {
+			Some(commitment) => commitment,
+			None => return Ok(None),
+		},
+	};
+	let block_number = commitment.commitment.block_number;
+
+	let validator_set = read_validator_set(source_client, block_number).await?;
+	if validator_set.id() != commitment.commitment.validator_set_id {
+		log::warn!(
+			target: "bridge",
+			"{} BEEFY commitment at block {} is signed by validator set {}, while set {} is active",
+			P::SourceChain::NAME,
+			block_number,
+			commitment.commitment.validator_set_id,
+			validator_set.id(),
+		);
+		return Ok(None)
+	}
+
+	let proof = source_client
+		.prepare_commitment_proof(commitment, validator_set)
+		.await
+		.map_err(|e| {
+			log::warn!(
+				target: "bridge",
+				"Failed to prepare {} BEEFY commitment proof at block {}: {:?}",
+				P::SourceChain::NAME,
+				block_number,
+				e,
+			);
+			FailedClient::Source
+		})?;
+
+	log::info!(
+		target: "bridge",
+		"Submitting {} BEEFY commitment at block {} to {}",
+		P::SourceChain::NAME,
+		block_number,
+		P::TargetChain::NAME,
+	);
+
+	let tracker = target_client.submit_commitment(proof).await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to submit {} BEEFY commitment to {}: {:?}",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			e,
+		);
+		FailedClient::Target
+	})?;
+	match tracker.wait().await {
+		TrackedTransactionStatus::Finalized(_) => Ok(Some(block_number)),
+		TrackedTransactionStatus::Lost => {
+			log::warn!(
+				target: "bridge",
+				"{} BEEFY commitment transaction is considered lost at {}. Going to restart",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+			);
+			Err(FailedClient::Both)
+		},
+	}
+}
+
+/// Read commitment of the first block of the validator set, that the light client expects.
+///
+/// Returns `None` if the expected set is still active at the source chain. Its commitments will
+/// be received from the commitments stream then.
+async fn handoff_commitment<P: BeefySyncPipeline>(
+	source_client: &impl SourceClient<P>,
+	state: &LightClientState<BlockNumberOf<P::SourceChain>>,
+) -> Result<Option<SignedCommitmentOf<P>>, FailedClient> {
+	let best_finalized_block_number =
+		source_client.best_finalized_block_number().await.map_err(|e| {
+			log::warn!(
+				target: "bridge",
+				"Failed to read best finalized {} block number: {:?}",
+				P::SourceChain::NAME,
+				e,
+			);
+			FailedClient::Source
+		})?;
+	if read_validator_set(source_client, best_finalized_block_number).await?.id() <=
+		state.validator_set_id
+	{
+		return Ok(None)
+	}
+
+	// the set, imported by the light client, is active at `low` and the expected set has been
+	// enacted at or before `high`. Look for the first block of the expected set
+	let (mut low, mut high) = (state.best_block_number, best_finalized_block_number);
+	while low + 1u32.into() < high {
+		let middle = low + (high - low) / 2u32.into();
+		if read_validator_set(source_client, middle).await?.id() >= state.validator_set_id {
+			high = middle;
+		} else {
+			low = middle;
+		}
+	}
+
+	let commitment = source_client.stored_commitment(high).await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to read {} BEEFY justification of block {}: {:?}",
+			P::SourceChain::NAME,
+			high,
+			e,
+		);
+		FailedClient::Source
+	})?;
+	match commitment {
+		Some(commitment) if commitment.commitment.validator_set_id == state.validator_set_id => {
+			log::info!(
+				target: "bridge",
+				"Relay has missed all {} BEEFY commitments of validator set {}. Going to submit \
+				commitment of the handoff block {}",
+				P::SourceChain::NAME,
+				state.validator_set_id,
+				high,
+			);
+			Ok(Some(commitment))
+		},
+		_ => {
+			log::warn!(
+				target: "bridge",
+				"{} BEEFY light client at {} expects commitment of validator set {}, but the \
+				handoff block {} has no such commitment. It must be submitted manually",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				state.validator_set_id,
+				high,
+			);
+			Ok(None)
+		},
+	}
+}
+
+/// Read validator set, that is active at given source block.
+async fn read_validator_set<P: BeefySyncPipeline>(
+	source_client: &impl SourceClient<P>,
+	block_number: BlockNumberOf<P::SourceChain>,
+) -> Result<ValidatorSetOf<P>, FailedClient> {
+	source_client.validator_set(block_number).await.map_err(|e| {
+		log::warn!(
+			target: "bridge",
+			"Failed to read {} BEEFY validator set at block {}: {:?}",
+			P::SourceChain::NAME,
+			block_number,
+			e,
+		);
+		FailedClient::Source
+	})
+}
+
+/// Report double votes of all validators, that have signed both conflicting commitments.
+/// Returns trackers of submitted reports.
+pub async fn report_double_votes<P: BeefySyncPipeline, SC: SourceClient<P>>(
+	source_client: &SC,
+	first: &SignedCommitmentOf<P>,
+	second: &SignedCommitmentOf<P>,
+	metrics: Option<&BeefySyncLoopMetrics>,
+) -> Result<Vec<SC::TransactionTracker>, FailedClient> {
+	let block_number = second.commitment.block_number;
+	let validator_set = read_validator_set(source_client, block_number).await?;
+	if validator_set.id() != second.commitment.validator_set_id {
+		log::warn!(
+			target: "bridge",
+			"Conflicting {} BEEFY commitments at block {} are signed by validator set {}, while \
+			set {} is active. Not reporting them",
+			P::SourceChain::NAME,
+			block_number,
+			second.commitment.validator_set_id,
+			validator_set.id(),
+		);
+		return Ok(Vec::new())
+	}
+
+	let mut trackers = Vec::new();
+	for proof in double_votes::<P>(&validator_set, first, second) {
+		log::info!(
+			target: "bridge",
+			"Reporting {} BEEFY double vote of validator {:?} at block {}",
+			P::SourceChain::NAME,
+			proof.offender_id(),
+			block_number,
+		);
+
+		let tracker = source_client.report_double_voting(proof).await.map_err(|e| {
+			log::warn!(
+				target: "bridge",
+				"Failed to report {} BEEFY double vote: {:?}",
+				P::SourceChain::NAME,
+				e,
+			);
+			FailedClient::Source
+		})?;
+		if let Some(metrics) = metrics {
+			metrics.note_reported_double_vote();
+		}
+		trackers.push(tracker);
+	}
+
+	Ok(trackers)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	fn state(best_block_number: u32, validator_set_id: ValidatorSetId) -> LightClientState<u32> {
+		LightClientState { best_block_number, validator_set_id }
+	}
+
+	fn cache(commitments: &[SignedCommitmentOf<TestPipeline>]) -> CommitmentsCache<TestPipeline> {
+		let mut cache = CommitmentsCache::default();
+		commitments.iter().for_each(|commitment| cache.note_commitment(commitment));
+		cache
+	}
+
+	fn sync(
+		target: &TestTargetClient,
+		cache: &mut CommitmentsCache<TestPipeline>,
+	) -> Result<Option<u32>, FailedClient> {
+		sync_with_source(&TestSourceClient::new(vec![]), target, cache)
+	}
+
+	fn sync_with_source(
+		source: &TestSourceClient,
+		target: &TestTargetClient,
+		cache: &mut CommitmentsCache<TestPipeline>,
+	) -> Result<Option<u32>, FailedClient> {
+		async_std::task::block_on(sync_step(source, target, cache, None))
+	}
+
+	#[test]
+	fn when_light_client_is_not_initialized() {
+		let target = TestTargetClient::new(None);
+		let mut cache = cache(&[commitment(10, 0, &[ALICE, BOB])]);
+
+		assert_eq!(sync(&target, &mut cache), Ok(None));
+		assert!(target.submitted().is_empty());
+	}
+
+	#[test]
+	fn submits_best_commitment_of_expected_validator_set() {
+		let target = TestTargetClient::new(Some(state(5, 0)));
+		let mut cache = cache(&[
+			commitment(10, 0, &[ALICE, BOB]),
+			commitment(20, 0, &[ALICE, BOB]),
+			commitment(15, 0, &[ALICE, BOB]),
+			commitment(110, 1, &[ALICE, BOB]),
+		]);
+
+		assert_eq!(sync(&target, &mut cache), Ok(Some(20)));
+		assert_eq!(sync(&target, &mut cache), Ok(Some(110)));
+		assert_eq!(sync(&target, &mut cache), Ok(None));
+
+		assert_eq!(target.state(), Some(state(110, 2)));
+		assert_eq!(target.submitted(), vec![20, 110]);
+	}
+
+	#[test]
+	fn does_not_submit_commitments_of_other_validator_sets() {
+		let target = TestTargetClient::new(Some(state(20, 1)));
+		let mut cache = cache(&[commitment(10, 0, &[ALICE, BOB]), commitment(210, 2, &[ALICE])]);
+
+		assert_eq!(sync(&target, &mut cache), Ok(None));
+		assert!(target.submitted().is_empty());
+		assert!(cache.best_commitment(0).is_none());
+	}
+
+	#[test]
+	fn submits_stored_commitment_of_missed_validator_set() {
+		let source = TestSourceClient::new(vec![]).with_stored_commitments(
+			250,
+			vec![commitment(100, 1, &[ALICE, BOB]), commitment(200, 2, &[ALICE, BOB])],
+		);
+		let target = TestTargetClient::new(Some(state(20, 1)));
+		let mut cache = cache(&[commitment(210, 2, &[ALICE, BOB])]);
+
+		assert_eq!(sync_with_source(&source, &target, &mut cache), Ok(Some(100)));
+		assert_eq!(sync_with_source(&source, &target, &mut cache), Ok(Some(210)));
+		assert_eq!(target.submitted(), vec![100, 210]);
+	}
+
+	#[test]
+	fn does_not_read_stored_commitments_while_expected_set_is_active() {
+		let source = TestSourceClient::new(vec![])
+			.with_stored_commitments(150, vec![commitment(100, 1, &[ALICE, BOB])]);
+		let target = TestTargetClient::new(Some(state(20, 1)));
+		let mut cache = cache(&[]);
+
+		assert_eq!(sync_with_source(&source, &target, &mut cache), Ok(None));
+		assert!(target.submitted().is_empty());
+	}
+
+	#[test]
+	fn does_not_submit_anything_if_handoff_commitment_is_missing() {
+		let source = TestSourceClient::new(vec![])
+			.with_stored_commitments(250, vec![commitment(200, 2, &[ALICE, BOB])]);
+		let target = TestTargetClient::new(Some(state(20, 1)));
+		let mut cache = cache(&[commitment(210, 2, &[ALICE, BOB])]);
+
+		assert_eq!(sync_with_source(&source, &target, &mut cache), Ok(None));
+		assert!(target.submitted().is_empty());
+	}
+
+	#[test]
+	fn does_not_submit_known_commitment() {
+		let target = TestTargetClient::new(Some(state(20, 0)));
+		let mut cache = cache(&[commitment(20, 0, &[ALICE, BOB])]);
+
+		assert_eq!(sync(&target, &mut cache), Ok(None));
+		assert!(target.submitted().is_empty());
+	}
+
+	#[test]
+	fn restarts_when_commitment_transaction_is_lost() {
+		let target = TestTargetClient::new(Some(state(5, 0)));
+		target.lose_transactions();
+		let mut cache = cache(&[commitment(10, 0, &[ALICE, BOB])]);
+
+		assert_eq!(sync(&target, &mut cache), Err(FailedClient::Both));
+	}
+
+	#[test]
+	fn reports_double_votes_of_conflicting_commitments() {
+		let source = TestSourceClient::new(vec![
+			commitment(10, 0, &[ALICE, BOB]),
+			commitment(10, 0, &[ALICE, BOB, CHARLIE]),
+			conflicting_commitment(10, 0, &[BOB, CHARLIE]),
+			// signed by the wrong validator set, so it can't be reported
+			conflicting_commitment(110, 0, &[ALICE]),
+			commitment(110, 0, &[ALICE]),
+		]);
+		let target = TestTargetClient::new(Some(state(5, 0)));
+
+		// the loop restarts when the commitments stream ends
+		let result = async_std::task::block_on(run_until_connection_lost(
+			source.clone(),
+			target.clone(),
+			BeefySyncParams { tick: Duration::from_secs(3600), equivocation_detection_rounds: 16 },
+			None,
+			futures::future::pending::<()>(),
+		));
+		assert_eq!(result, Err(FailedClient::Source));
+
+		let reported = source.reported();
+		assert_eq!(
+			reported.iter().map(|proof| proof.offender_id().clone()).collect::<Vec<_>>(),
+			vec![validator_set(0).validators()[BOB].clone()],
+		);
+		assert_eq!(reported[0].round_number(), &10);
+		assert!(target.submitted().is_empty());
+	}
+}
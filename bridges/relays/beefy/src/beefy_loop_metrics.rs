@@ -0,0 +1,82 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use relay_utils::{
+	metrics::{metric_name, register, Counter, Gauge, Metric, PrometheusError, Registry, U64},
+	UniqueSaturatedInto,
+};
+
+/// BEEFY sync loop metrics.
+#[derive(Clone)]
+pub struct BeefySyncLoopMetrics {
+	/// Best source block number with a known BEEFY commitment.
+	best_source_block_number: Gauge<U64>,
+	/// Best source block number, whose commitment has been imported by the light client.
+	best_target_block_number: Gauge<U64>,
+	/// Number of reported double votes.
+	reported_double_votes: Counter<U64>,
+}
+
+impl BeefySyncLoopMetrics {
+	/// Create and register BEEFY sync loop metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(BeefySyncLoopMetrics {
+			best_source_block_number: Gauge::new(
+				metric_name(prefix, "best_source_block_number"),
+				"Best source block number with a known BEEFY commitment".to_string(),
+			)?,
+			best_target_block_number: Gauge::new(
+				metric_name(prefix, "best_target_block_number"),
+				"Best source block number, whose BEEFY commitment is known to the target chain"
+					.to_string(),
+			)?,
+			reported_double_votes: Counter::new(
+				metric_name(prefix, "reported_double_votes"),
+				"Number of BEEFY double votes, reported to the source chain".to_string(),
+			)?,
+		})
+	}
+
+	/// Update best source block number with a known commitment.
+	pub fn update_best_source_block_number<Number: UniqueSaturatedInto<u64>>(
+		&self,
+		block_number: Number,
+	) {
+		self.best_source_block_number.set(block_number.unique_saturated_into());
+	}
+
+	/// Update best source block number, known to the target.
+	pub fn update_best_target_block_number<Number: UniqueSaturatedInto<u64>>(
+		&self,
+		block_number: Number,
+	) {
+		self.best_target_block_number.set(block_number.unique_saturated_into());
+	}
+
+	/// Note reported double vote.
+	pub fn note_reported_double_vote(&self) {
+		self.reported_double_votes.inc();
+	}
+}
+
+impl Metric for BeefySyncLoopMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.best_source_block_number.clone(), registry)?;
+		register(self.best_target_block_number.clone(), registry)?;
+		register(self.reported_double_votes.clone(), registry)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,176 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY double voting detection.
+//!
+//! Honest validators sign a single commitment in every round. If the relay sees two different
+//! commitments for the same block, signed by the same validator set, then every validator that
+//! has signed both of them has voted twice and may be reported to the source chain. Commitments
+//! are received from (possibly untrusted) nodes, so both signatures are verified before the double
+//! vote is reported.
+
+use crate::{BeefySyncPipeline, DoubleVotingProofOf, SignedCommitmentOf, ValidatorSetOf};
+
+use relay_substrate_client::BlockNumberOf;
+use sp_consensus_beefy::{
+	check_commitment_signature, Commitment, DoubleVotingProof, ValidatorSetId, VoteMessage,
+};
+use std::collections::BTreeMap;
+
+/// Remembers commitments of recent rounds and looks for conflicting ones.
+pub struct EquivocationsDetector<P: BeefySyncPipeline> {
+	max_rounds: usize,
+	rounds: BTreeMap<(ValidatorSetId, BlockNumberOf<P::SourceChain>), Vec<SignedCommitmentOf<P>>>,
+}
+
+impl<P: BeefySyncPipeline> EquivocationsDetector<P> {
+	/// Create new detector, that keeps commitments of at most `max_rounds` recent rounds.
+	pub fn new(max_rounds: usize) -> Self {
+		EquivocationsDetector { max_rounds, rounds: BTreeMap::new() }
+	}
+
+	/// Remember commitment and return all known commitments of the same round that conflict
+	/// with it.
+	pub fn note_commitment(
+		&mut self,
+		commitment: SignedCommitmentOf<P>,
+	) -> Vec<SignedCommitmentOf<P>> {
+		let round = (commitment.commitment.validator_set_id, commitment.commitment.block_number);
+		let known_commitments = self.rounds.entry(round).or_default();
+		// the same commitment may be seen several times, with different sets of signatures
+		if known_commitments.iter().any(|known| known.commitment == commitment.commitment) {
+			return Vec::new()
+		}
+
+		let conflicting_commitments = known_commitments.clone();
+		known_commitments.push(commitment);
+
+		while self.rounds.len() > self.max_rounds {
+			self.rounds.pop_first();
+		}
+
+		conflicting_commitments
+	}
+}
+
+/// Returns double voting proofs for all validators of the `validator_set`, that have signed both
+/// `first` and `second` commitments. Votes with invalid signatures are ignored.
+pub fn double_votes<P: BeefySyncPipeline>(
+	validator_set: &ValidatorSetOf<P>,
+	first: &SignedCommitmentOf<P>,
+	second: &SignedCommitmentOf<P>,
+) -> Vec<DoubleVotingProofOf<P>> {
+	if first.commitment == second.commitment {
+		return Vec::new()
+	}
+
+	validator_set
+		.validators()
+		.iter()
+		.zip(first.signatures.iter().zip(second.signatures.iter()))
+		.filter_map(|(id, signatures)| match signatures {
+			(Some(first_signature), Some(second_signature))
+				if is_valid_vote::<P>(&first.commitment, id, first_signature) &&
+					is_valid_vote::<P>(&second.commitment, id, second_signature) =>
+				Some(DoubleVotingProof {
+					first: VoteMessage {
+						commitment: first.commitment.clone(),
+						id: id.clone(),
+						signature: first_signature.clone(),
+					},
+					second: VoteMessage {
+						commitment: second.commitment.clone(),
+						id: id.clone(),
+						signature: second_signature.clone(),
+					},
+				}),
+			_ => None,
+		})
+		.collect()
+}
+
+/// Returns true if `signature` is a valid signature of the `commitment` by the validator `id`.
+fn is_valid_vote<P: BeefySyncPipeline>(
+	commitment: &Commitment<BlockNumberOf<P::SourceChain>>,
+	id: &P::AuthorityId,
+	signature: &P::Signature,
+) -> bool {
+	check_commitment_signature::<_, _, P::CommitmentHasher>(commitment, id, signature)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn detector_ignores_duplicate_commitments() {
+		let mut detector = EquivocationsDetector::<TestPipeline>::new(16);
+		assert!(detector.note_commitment(commitment(10, 0, &[ALICE, BOB])).is_empty());
+		assert!(detector.note_commitment(commitment(10, 0, &[ALICE, CHARLIE])).is_empty());
+		assert!(detector.note_commitment(commitment(11, 0, &[ALICE, BOB])).is_empty());
+		assert!(detector.note_commitment(commitment(10, 1, &[ALICE, BOB])).is_empty());
+	}
+
+	#[test]
+	fn detector_returns_conflicting_commitments() {
+		let mut detector = EquivocationsDetector::<TestPipeline>::new(16);
+		let first = commitment(10, 0, &[ALICE, BOB]);
+		let second = conflicting_commitment(10, 0, &[BOB, CHARLIE]);
+		assert!(detector.note_commitment(first.clone()).is_empty());
+		assert_eq!(detector.note_commitment(second), vec![first]);
+	}
+
+	#[test]
+	fn detector_forgets_old_rounds() {
+		let mut detector = EquivocationsDetector::<TestPipeline>::new(2);
+		detector.note_commitment(commitment(10, 0, &[ALICE]));
+		detector.note_commitment(commitment(11, 0, &[ALICE]));
+		detector.note_commitment(commitment(12, 0, &[ALICE]));
+
+		assert!(detector.note_commitment(conflicting_commitment(10, 0, &[ALICE])).is_empty());
+		assert_eq!(detector.note_commitment(conflicting_commitment(12, 0, &[ALICE])).len(), 1);
+	}
+
+	#[test]
+	fn double_votes_are_found_for_validators_that_signed_both_commitments() {
+		let first = commitment(10, 0, &[ALICE, BOB]);
+		let second = conflicting_commitment(10, 0, &[BOB, CHARLIE]);
+		let proofs = double_votes::<TestPipeline>(&validator_set(0), &first, &second);
+
+		assert_eq!(proofs.len(), 1);
+		assert_eq!(*proofs[0].offender_id(), validator_set(0).validators()[BOB].clone());
+		assert_eq!(proofs[0].first.commitment, first.commitment);
+		assert_eq!(proofs[0].second.commitment, second.commitment);
+		assert_eq!(Some(proofs[0].second.signature.clone()), second.signatures[BOB]);
+
+		assert!(double_votes::<TestPipeline>(&validator_set(0), &first, &first).is_empty());
+	}
+
+	#[test]
+	fn double_votes_with_invalid_signatures_are_not_reported() {
+		let first = commitment(10, 0, &[ALICE, BOB, CHARLIE]);
+		let mut second = conflicting_commitment(10, 0, &[ALICE, BOB, CHARLIE]);
+		// Bob's signature of the second commitment is forged
+		second.signatures[BOB] = first.signatures[BOB].clone();
+		// Charlie's signature of the second commitment is Alice's signature
+		second.signatures[CHARLIE] = second.signatures[ALICE].clone();
+
+		let proofs = double_votes::<TestPipeline>(&validator_set(0), &first, &second);
+		assert_eq!(proofs.len(), 1);
+		assert_eq!(*proofs[0].offender_id(), validator_set(0).validators()[ALICE].clone());
+	}
+}
@@ -0,0 +1,73 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY finality relay. The relay subscribes to BEEFY justifications of the source chain and
+//! submits signed commitments, along with the MMR leaf and proof, to the `pallet-bridge-beefy`
+//! at the target chain. It is also watching for conflicting commitments, signed by the same
+//! validators in the same round, and reports such double votes to the source chain. Signatures
+//! of conflicting commitments are verified before reporting, so forged commitments, received from
+//! a malicious node, never lead to reports.
+
+#![warn(missing_docs)]
+
+use relay_substrate_client::{BlockNumberOf, Chain};
+use sp_consensus_beefy::{BeefyAuthorityId, DoubleVotingProof, SignedCommitment, ValidatorSet};
+use sp_runtime::traits::Hash;
+use std::fmt::Debug;
+
+pub mod beefy_loop;
+pub mod beefy_loop_metrics;
+pub mod equivocations;
+
+#[cfg(test)]
+mod mock;
+
+/// BEEFY finality synchronization pipeline.
+pub trait BeefySyncPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Chain with BEEFY finality, whose commitments we are relaying.
+	type SourceChain: Chain;
+	/// Chain, where the BEEFY light client is deployed.
+	type TargetChain: Chain;
+
+	/// Hasher, used by BEEFY validators to sign commitments.
+	type CommitmentHasher: Hash;
+	/// BEEFY validator identifier.
+	type AuthorityId: BeefyAuthorityId<Self::CommitmentHasher, Signature = Self::Signature>
+		+ Clone
+		+ Debug
+		+ PartialEq
+		+ Send
+		+ Sync;
+	/// BEEFY validator signature.
+	type Signature: Clone + Debug + PartialEq + Send + Sync;
+	/// Everything that is required to import a commitment into the light client: the signed
+	/// commitment, the validator set that has signed it, the MMR leaf and its proof.
+	type CommitmentProof: Clone + Debug + Send + Sync;
+}
+
+/// BEEFY signed commitment of the pipeline source chain.
+pub type SignedCommitmentOf<P> = SignedCommitment<
+	BlockNumberOf<<P as BeefySyncPipeline>::SourceChain>,
+	<P as BeefySyncPipeline>::Signature,
+>;
+/// BEEFY validator set of the pipeline source chain.
+pub type ValidatorSetOf<P> = ValidatorSet<<P as BeefySyncPipeline>::AuthorityId>;
+/// Proof of BEEFY double voting at the pipeline source chain.
+pub type DoubleVotingProofOf<P> = DoubleVotingProof<
+	BlockNumberOf<<P as BeefySyncPipeline>::SourceChain>,
+	<P as BeefySyncPipeline>::AuthorityId,
+	<P as BeefySyncPipeline>::Signature,
+>;
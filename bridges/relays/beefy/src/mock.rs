@@ -0,0 +1,309 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Test clients for the BEEFY sync loop. The source chain has three validators and the validator
+//! set is changed every `SESSION_LENGTH` blocks.
+
+use crate::{
+	beefy_loop::{LightClientState, SourceClient, TargetClient},
+	BeefySyncPipeline, DoubleVotingProofOf, SignedCommitmentOf, ValidatorSetOf,
+};
+
+use async_trait::async_trait;
+use codec::Encode;
+use futures::stream::{BoxStream, StreamExt};
+use relay_substrate_client::{test_chain::TestChain, BlockNumberOf};
+use relay_utils::{
+	relay_loop::Client as RelayClient, MaybeConnectionError, TrackedTransactionStatus,
+	TransactionTracker,
+};
+use sp_consensus_beefy::{
+	ecdsa_crypto, known_payloads::MMR_ROOT_ID, test_utils::Keyring, BeefySignatureHasher,
+	Commitment, Payload, SignedCommitment, ValidatorSet, ValidatorSetId,
+};
+use std::sync::{Arc, Mutex};
+
+/// Index of Alice in the validator set.
+pub const ALICE: usize = 0;
+/// Index of Bob in the validator set.
+pub const BOB: usize = 1;
+/// Index of Charlie in the validator set.
+pub const CHARLIE: usize = 2;
+
+/// Number of blocks in a single session of the source chain.
+pub const SESSION_LENGTH: BlockNumberOf<TestChain> = 100;
+
+#[derive(Clone, Debug)]
+pub struct TestPipeline;
+
+impl BeefySyncPipeline for TestPipeline {
+	type SourceChain = TestChain;
+	type TargetChain = TestChain;
+
+	type CommitmentHasher = BeefySignatureHasher;
+	type AuthorityId = ecdsa_crypto::AuthorityId;
+	type Signature = ecdsa_crypto::Signature;
+	type CommitmentProof = (SignedCommitmentOf<TestPipeline>, ValidatorSetOf<TestPipeline>);
+}
+
+fn validators() -> [Keyring<ecdsa_crypto::AuthorityId>; 3] {
+	[Keyring::Alice, Keyring::Bob, Keyring::Charlie]
+}
+
+/// Returns validator set with given id.
+pub fn validator_set(set_id: ValidatorSetId) -> ValidatorSetOf<TestPipeline> {
+	ValidatorSet::new(validators().iter().map(|validator| validator.public()), set_id).unwrap()
+}
+
+fn signed_commitment(
+	mmr_root: u8,
+	block_number: BlockNumberOf<TestChain>,
+	validator_set_id: ValidatorSetId,
+	signers: &[usize],
+) -> SignedCommitmentOf<TestPipeline> {
+	let commitment = Commitment {
+		payload: Payload::from_single_entry(MMR_ROOT_ID, vec![mmr_root; 32]),
+		block_number,
+		validator_set_id,
+	};
+	let encoded_commitment = commitment.encode();
+	let signatures = validators()
+		.iter()
+		.enumerate()
+		.map(|(index, validator)| {
+			signers.contains(&index).then(|| validator.sign(&encoded_commitment))
+		})
+		.collect();
+	SignedCommitment { commitment, signatures }
+}
+
+/// Returns commitment to the canonical MMR root at given block, signed by given validators.
+pub fn commitment(
+	block_number: BlockNumberOf<TestChain>,
+	validator_set_id: ValidatorSetId,
+	signers: &[usize],
+) -> SignedCommitmentOf<TestPipeline> {
+	signed_commitment(1, block_number, validator_set_id, signers)
+}
+
+/// Returns commitment that conflicts with the `commitment` at the same block.
+pub fn conflicting_commitment(
+	block_number: BlockNumberOf<TestChain>,
+	validator_set_id: ValidatorSetId,
+	signers: &[usize],
+) -> SignedCommitmentOf<TestPipeline> {
+	signed_commitment(2, block_number, validator_set_id, signers)
+}
+
+#[derive(Clone, Debug)]
+pub enum TestError {
+	Error,
+}
+
+impl MaybeConnectionError for TestError {
+	fn is_connection_error(&self) -> bool {
+		false
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct TestTransactionTracker(TrackedTransactionStatus<()>);
+
+#[async_trait]
+impl TransactionTracker for TestTransactionTracker {
+	type HeaderId = ();
+
+	async fn wait(self) -> TrackedTransactionStatus<()> {
+		self.0
+	}
+}
+
+/// Source chain, that is streaming given commitments and records reported double votes.
+#[derive(Clone, Debug)]
+pub struct TestSourceClient {
+	commitments: Vec<SignedCommitmentOf<TestPipeline>>,
+	best_finalized_block_number: BlockNumberOf<TestChain>,
+	stored_commitments: Vec<SignedCommitmentOf<TestPipeline>>,
+	reported: Arc<Mutex<Vec<DoubleVotingProofOf<TestPipeline>>>>,
+}
+
+impl TestSourceClient {
+	pub fn new(commitments: Vec<SignedCommitmentOf<TestPipeline>>) -> Self {
+		TestSourceClient {
+			commitments,
+			best_finalized_block_number: 0,
+			stored_commitments: vec![],
+			reported: Arc::new(Mutex::new(Vec::new())),
+		}
+	}
+
+	/// Set best finalized block and commitments, stored in block justifications.
+	pub fn with_stored_commitments(
+		mut self,
+		best_finalized_block_number: BlockNumberOf<TestChain>,
+		stored_commitments: Vec<SignedCommitmentOf<TestPipeline>>,
+	) -> Self {
+		self.best_finalized_block_number = best_finalized_block_number;
+		self.stored_commitments = stored_commitments;
+		self
+	}
+
+	pub fn reported(&self) -> Vec<DoubleVotingProofOf<TestPipeline>> {
+		self.reported.lock().unwrap().clone()
+	}
+}
+
+#[async_trait]
+impl RelayClient for TestSourceClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl SourceClient<TestPipeline> for TestSourceClient {
+	type CommitmentsStream = BoxStream<'static, SignedCommitmentOf<TestPipeline>>;
+	type TransactionTracker = TestTransactionTracker;
+
+	async fn commitments(&self) -> Result<Self::CommitmentsStream, TestError> {
+		Ok(futures::stream::iter(self.commitments.clone()).boxed())
+	}
+
+	async fn best_finalized_block_number(&self) -> Result<BlockNumberOf<TestChain>, TestError> {
+		Ok(self.best_finalized_block_number)
+	}
+
+	async fn stored_commitment(
+		&self,
+		block_number: BlockNumberOf<TestChain>,
+	) -> Result<Option<SignedCommitmentOf<TestPipeline>>, TestError> {
+		Ok(self
+			.stored_commitments
+			.iter()
+			.find(|commitment| commitment.commitment.block_number == block_number)
+			.cloned())
+	}
+
+	async fn validator_set(
+		&self,
+		block_number: BlockNumberOf<TestChain>,
+	) -> Result<ValidatorSetOf<TestPipeline>, TestError> {
+		Ok(validator_set((block_number / SESSION_LENGTH).into()))
+	}
+
+	async fn prepare_commitment_proof(
+		&self,
+		commitment: SignedCommitmentOf<TestPipeline>,
+		validator_set: ValidatorSetOf<TestPipeline>,
+	) -> Result<(SignedCommitmentOf<TestPipeline>, ValidatorSetOf<TestPipeline>), TestError> {
+		Ok((commitment, validator_set))
+	}
+
+	async fn report_double_voting(
+		&self,
+		proof: DoubleVotingProofOf<TestPipeline>,
+	) -> Result<TestTransactionTracker, TestError> {
+		self.reported.lock().unwrap().push(proof);
+		Ok(TestTransactionTracker(TrackedTransactionStatus::Finalized(())))
+	}
+}
+
+/// In-memory BEEFY light client. Like the `pallet-bridge-beefy`, it only accepts commitments of
+/// the current validator set and switches to the next set after every import.
+#[derive(Clone, Debug)]
+pub struct TestTargetClient {
+	data: Arc<Mutex<TestTargetData>>,
+}
+
+#[derive(Debug)]
+struct TestTargetData {
+	state: Option<LightClientState<BlockNumberOf<TestChain>>>,
+	submitted: Vec<BlockNumberOf<TestChain>>,
+	lose_transactions: bool,
+}
+
+impl TestTargetClient {
+	pub fn new(state: Option<LightClientState<BlockNumberOf<TestChain>>>) -> Self {
+		TestTargetClient {
+			data: Arc::new(Mutex::new(TestTargetData {
+				state,
+				submitted: vec![],
+				lose_transactions: false,
+			})),
+		}
+	}
+
+	pub fn state(&self) -> Option<LightClientState<BlockNumberOf<TestChain>>> {
+		self.data.lock().unwrap().state
+	}
+
+	pub fn submitted(&self) -> Vec<BlockNumberOf<TestChain>> {
+		self.data.lock().unwrap().submitted.clone()
+	}
+
+	pub fn lose_transactions(&self) {
+		self.data.lock().unwrap().lose_transactions = true;
+	}
+}
+
+#[async_trait]
+impl RelayClient for TestTargetClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl TargetClient<TestPipeline> for TestTargetClient {
+	type TransactionTracker = TestTransactionTracker;
+
+	async fn light_client_state(
+		&self,
+	) -> Result<Option<LightClientState<BlockNumberOf<TestChain>>>, TestError> {
+		Ok(self.data.lock().unwrap().state)
+	}
+
+	async fn submit_commitment(
+		&self,
+		(commitment, validator_set): (
+			SignedCommitmentOf<TestPipeline>,
+			ValidatorSetOf<TestPipeline>,
+		),
+	) -> Result<TestTransactionTracker, TestError> {
+		let mut data = self.data.lock().unwrap();
+		if data.lose_transactions {
+			return Ok(TestTransactionTracker(TrackedTransactionStatus::Lost))
+		}
+
+		let state = data.state.as_mut().ok_or(TestError::Error)?;
+		let block_number = commitment.commitment.block_number;
+		if commitment.commitment.validator_set_id != state.validator_set_id ||
+			validator_set.id() != state.validator_set_id ||
+			block_number <= state.best_block_number
+		{
+			return Err(TestError::Error)
+		}
+
+		state.best_block_number = block_number;
+		state.validator_set_id += 1;
+		data.submitted.push(block_number);
+		Ok(TestTransactionTracker(TrackedTransactionStatus::Finalized(())))
+	}
+}
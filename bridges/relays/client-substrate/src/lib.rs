@@ -27,6 +27,7 @@ mod transaction_tracker;
 pub mod calls;
 pub mod guard;
 pub mod metrics;
+pub mod simulation;
 pub mod test_chain;
 
 use std::time::Duration;
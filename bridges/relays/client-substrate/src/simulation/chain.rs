@@ -0,0 +1,502 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Simulated chain state.
+
+use crate::{
+	error::{Error, Result},
+	simulation::{
+		RuntimeContext, SimulatedAction, SimulatedBlock, SimulatedClient, SimulatedRuntime,
+		SimulatedStorage, SimulationParticipant, SimulationScript,
+	},
+	BlockNumberOf, Chain, HashOf, HeaderOf, StreamDescription, Subscription, TransactionStatusOf,
+};
+
+use async_std::{
+	channel::{unbounded, Sender},
+	sync::{Arc, Mutex},
+};
+use async_trait::async_trait;
+use codec::Encode;
+use num_traits::AsPrimitive;
+use sp_consensus_grandpa::GRANDPA_ENGINE_ID;
+use sp_core::{Bytes, Hasher};
+use sp_runtime::{
+	traits::Header as HeaderT, transaction_validity::TransactionValidity, Digest, DigestItem,
+	EncodedJustification, FixedPointNumber, FixedU128, Justifications,
+};
+use sp_state_machine::{backend::Backend, InMemoryBackend};
+use sp_trie::StorageProof;
+use std::collections::HashMap;
+
+/// Deterministic in-process stand-in for the Substrate node.
+///
+/// All clones of the `SimulatedChain` are sharing the same chain state. The chain starts at
+/// the genesis block with given storage and then performs actions of its script, when it is
+/// driven by the [`Simulation`](crate::simulation::Simulation).
+#[derive(Clone)]
+pub struct SimulatedChain<C: Chain> {
+	pub(super) state: Arc<Mutex<ChainState<C>>>,
+	genesis_hash: HashOf<C>,
+}
+
+impl<C: Chain> SimulatedChain<C> {
+	/// Create new chain with given runtime, genesis storage and script.
+	pub fn new(
+		runtime: impl SimulatedRuntime,
+		genesis_storage: SimulatedStorage,
+		script: SimulationScript,
+	) -> Self {
+		let state = ChainState::new(Box::new(runtime), genesis_storage, script);
+		let genesis_hash = state.canonical[0];
+		SimulatedChain { state: Arc::new(Mutex::new(state)), genesis_hash }
+	}
+
+	/// Returns hash of the genesis block.
+	pub fn genesis_hash(&self) -> HashOf<C> {
+		self.genesis_hash
+	}
+
+	/// Returns new client, connected to the chain.
+	pub fn client(&self) -> SimulatedClient<C> {
+		SimulatedClient::new(self.clone())
+	}
+
+	/// Perform given action right now, without waiting for the next tick.
+	pub async fn apply(&self, action: SimulatedAction) {
+		self.state.lock().await.apply(action)
+	}
+
+	/// Returns current best header of the chain.
+	pub async fn best_header(&self) -> HeaderOf<C> {
+		self.state.lock().await.best_block().header.clone()
+	}
+
+	/// Returns current best finalized header of the chain.
+	pub async fn best_finalized_header(&self) -> HeaderOf<C> {
+		let state = self.state.lock().await;
+		state.blocks[&state.canonical[state.best_finalized]].header.clone()
+	}
+
+	/// Returns value from the storage of the current best block.
+	pub async fn best_storage_value(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.state.lock().await.best_block().storage.get(key).cloned()
+	}
+
+	/// Returns transactions that are waiting in the pool.
+	pub async fn pending_transactions(&self) -> Vec<Bytes> {
+		self.state.lock().await.pool.clone()
+	}
+
+	/// Returns number of times clients have been reconnected to the chain.
+	pub async fn reconnects(&self) -> u32 {
+		self.state.lock().await.reconnects
+	}
+
+	/// Submit transaction to the pool, without validation, and watch its status.
+	pub async fn submit_and_watch(
+		&self,
+		transaction: Bytes,
+	) -> (HashOf<C>, Subscription<TransactionStatusOf<C>>) {
+		let mut state = self.state.lock().await;
+		let transaction_hash = C::Hasher::hash(&transaction.0);
+		let subscription = state.watch_transaction(transaction_hash);
+		state.pool.push(transaction);
+		(transaction_hash, subscription)
+	}
+}
+
+#[async_trait]
+impl<C: Chain> SimulationParticipant for SimulatedChain<C> {
+	async fn tick(&self, now: u64) {
+		let mut state = self.state.lock().await;
+		state.now = now;
+		for action in state.script.take(now) {
+			log::trace!(target: "bridge", "Simulated {} chain performs {:?} at {}", C::NAME, action, now);
+			state.apply(action);
+		}
+	}
+}
+
+/// Block of the simulated chain.
+pub(super) struct BlockData<C: Chain> {
+	/// Block header.
+	pub header: HeaderOf<C>,
+	/// Block storage.
+	pub storage: SimulatedStorage,
+	/// Encoded block transactions.
+	pub extrinsics: Vec<Bytes>,
+	/// Encoded GRANDPA justification of the block.
+	pub justification: Option<EncodedJustification>,
+}
+
+/// State of the simulated chain.
+pub(super) struct ChainState<C: Chain> {
+	/// Current tick of the simulated time.
+	pub now: u64,
+	/// Chain script.
+	script: SimulationScript,
+	/// Chain runtime.
+	runtime: Box<dyn SimulatedRuntime>,
+	/// All known blocks, including reverted.
+	pub blocks: HashMap<HashOf<C>, BlockData<C>>,
+	/// Hashes of canonical blocks, indexed by block number.
+	pub canonical: Vec<HashOf<C>>,
+	/// Number of the best finalized block.
+	pub best_finalized: usize,
+	/// Number of blocks, produced by the chain. Used to make sure that hashes of blocks at
+	/// different forks are different.
+	produced_blocks: u64,
+	/// Transactions that are waiting for inclusion.
+	pub pool: Vec<Bytes>,
+	/// Status subscribers of watched transactions.
+	watchers: HashMap<HashOf<C>, Sender<TransactionStatusOf<C>>>,
+	/// Next transaction nonce of every (encoded) account.
+	pub nonces: HashMap<Vec<u8>, u32>,
+	/// Current transaction fee multiplier.
+	pub fee_multiplier: FixedU128,
+	/// Runtime API methods that are failing and number of calls to fail.
+	failing_calls: HashMap<String, u32>,
+	/// Current connection number. It is changed when clients are disconnected.
+	pub connection: u64,
+	/// The node is not accepting connections until this tick.
+	pub disconnected_until: u64,
+	/// Number of times clients have been reconnected to the chain.
+	pub reconnects: u32,
+	/// Best headers subscribers.
+	pub best_headers: Vec<Sender<HeaderOf<C>>>,
+	/// Finalized headers subscribers.
+	pub finalized_headers: Vec<Sender<HeaderOf<C>>>,
+	/// GRANDPA justifications subscribers.
+	pub grandpa_justifications: Vec<Sender<Bytes>>,
+	/// BEEFY justifications subscribers.
+	pub beefy_justifications: Vec<Sender<Bytes>>,
+}
+
+impl<C: Chain> ChainState<C> {
+	/// Create state of the chain with the genesis block.
+	fn new(
+		runtime: Box<dyn SimulatedRuntime>,
+		genesis_storage: SimulatedStorage,
+		script: SimulationScript,
+	) -> Self {
+		let mut state = ChainState {
+			now: 0,
+			script,
+			runtime,
+			blocks: HashMap::new(),
+			canonical: Vec::new(),
+			best_finalized: 0,
+			produced_blocks: 0,
+			pool: Vec::new(),
+			watchers: HashMap::new(),
+			nonces: HashMap::new(),
+			fee_multiplier: FixedU128::from_u32(1),
+			failing_calls: HashMap::new(),
+			connection: 0,
+			disconnected_until: 0,
+			reconnects: 0,
+			best_headers: Vec::new(),
+			finalized_headers: Vec::new(),
+			grandpa_justifications: Vec::new(),
+			beefy_justifications: Vec::new(),
+		};
+		state.import_block(Default::default(), genesis_storage, Vec::new(), Vec::new());
+		state
+	}
+
+	/// Returns `true` if the node is not accepting connections.
+	pub fn is_disconnected(&self) -> bool {
+		self.now < self.disconnected_until
+	}
+
+	/// Returns the best block.
+	pub fn best_block(&self) -> &BlockData<C> {
+		&self.blocks[self.canonical.last().expect("chain always has genesis block; qed")]
+	}
+
+	/// Returns block by its hash.
+	pub fn block(&self, hash: HashOf<C>) -> Result<&BlockData<C>> {
+		self.blocks.get(&hash).ok_or_else(|| unknown_block::<C>(hash))
+	}
+
+	/// Returns hash of the canonical block with given number.
+	pub fn canonical_hash(&self, number: BlockNumberOf<C>) -> Result<HashOf<C>> {
+		let index: usize = number.as_();
+		self.canonical
+			.get(index)
+			.copied()
+			.ok_or_else(|| Error::Custom(format!("Unknown {} block #{number}", C::NAME)))
+	}
+
+	/// Execute runtime API method at given block.
+	pub fn call(&mut self, at: HashOf<C>, method: &str, arguments: &[u8]) -> Result<Vec<u8>> {
+		if let Some(failing_calls) = self.failing_calls.get_mut(method) {
+			*failing_calls -= 1;
+			if *failing_calls == 0 {
+				self.failing_calls.remove(method);
+			}
+			return Err(Error::Custom(format!("Simulated failure of {} {method} call", C::NAME)))
+		}
+
+		let fee_multiplier = self.fee_multiplier;
+		let block = self.blocks.get(&at).ok_or_else(|| unknown_block::<C>(at))?;
+		self.runtime.call(
+			RuntimeContext { storage: &block.storage, fee_multiplier },
+			method,
+			arguments,
+		)
+	}
+
+	/// Validate transaction at given block.
+	pub fn validate_transaction(
+		&mut self,
+		at: HashOf<C>,
+		transaction: &[u8],
+	) -> Result<TransactionValidity> {
+		let fee_multiplier = self.fee_multiplier;
+		let block = self.blocks.get(&at).ok_or_else(|| unknown_block::<C>(at))?;
+		Ok(self.runtime.validate_transaction(
+			RuntimeContext { storage: &block.storage, fee_multiplier },
+			transaction,
+		))
+	}
+
+	/// Returns weight of the transaction at given block.
+	pub fn transaction_weight(
+		&mut self,
+		at: HashOf<C>,
+		transaction: &[u8],
+	) -> Result<frame_support::weights::Weight> {
+		let fee_multiplier = self.fee_multiplier;
+		let block = self.blocks.get(&at).ok_or_else(|| unknown_block::<C>(at))?;
+		let weight = self.runtime.transaction_weight(
+			RuntimeContext { storage: &block.storage, fee_multiplier },
+			transaction,
+		);
+		Ok(frame_support::weights::Weight::from_parts(
+			fee_multiplier.saturating_mul_int(weight.ref_time()),
+			fee_multiplier.saturating_mul_int(weight.proof_size()),
+		))
+	}
+
+	/// Returns storage proof of given keys at given block, and the block state root.
+	pub fn prove_storage(
+		&self,
+		at: HashOf<C>,
+		keys: &[Vec<u8>],
+	) -> Result<(StorageProof, HashOf<C>)> {
+		let block = self.block(at)?;
+		let proof = sp_state_machine::prove_read(storage_backend::<C>(&block.storage), keys)
+			.map_err(|e| Error::Custom(format!("Failed to prove {} storage: {e:?}", C::NAME)))?;
+		Ok((proof, *block.header.state_root()))
+	}
+
+	/// Start watching transaction status.
+	pub fn watch_transaction(
+		&mut self,
+		transaction_hash: HashOf<C>,
+	) -> Subscription<TransactionStatusOf<C>> {
+		let (sender, receiver) = unbounded();
+		let _ = sender.try_send(TransactionStatusOf::<C>::Ready);
+		self.watchers.insert(transaction_hash, sender);
+		Subscription::new_broadcasted(
+			StreamDescription::new("transaction events".into(), C::NAME.into()),
+			receiver,
+		)
+	}
+
+	/// Perform the scripted action.
+	fn apply(&mut self, action: SimulatedAction) {
+		match action {
+			SimulatedAction::ProduceBlock(block) => self.produce_block(block),
+			SimulatedAction::Finalize(justification) => self.finalize(justification),
+			SimulatedAction::FinalizeWithJustification => {
+				let justification =
+					self.runtime.grandpa_justification(&self.best_block().header.encode());
+				self.finalize(justification)
+			},
+			SimulatedAction::BeefyJustification(justification) =>
+				notify(&mut self.beefy_justifications, Bytes(justification)),
+			SimulatedAction::Reorg { depth, blocks } => self.reorg(depth, blocks),
+			SimulatedAction::Disconnect { ticks } => self.disconnect(ticks),
+			SimulatedAction::FailRuntimeCalls { method, count } =>
+				if count != 0 {
+					*self.failing_calls.entry(method).or_default() += count;
+				},
+			SimulatedAction::SetFeeMultiplier(fee_multiplier) =>
+				self.fee_multiplier = fee_multiplier,
+		}
+	}
+
+	/// Produce new best block with all transactions from the pool.
+	fn produce_block(&mut self, block: SimulatedBlock) {
+		let parent = self.best_block();
+		let parent_hash = parent.header.hash();
+		let mut storage = parent.storage.clone();
+
+		let extrinsics = std::mem::take(&mut self.pool);
+		for transaction in &extrinsics {
+			let mut transaction_storage = storage.clone();
+			match self.runtime.apply_transaction(&mut transaction_storage, &transaction.0) {
+				Ok(()) => storage = transaction_storage,
+				Err(e) => log::trace!(
+					target: "bridge",
+					"Simulated {} transaction has failed: {:?}",
+					C::NAME,
+					e,
+				),
+			}
+		}
+		for (key, value) in block.storage_changes {
+			match value {
+				Some(value) => storage.insert(key, value),
+				None => storage.remove(&key),
+			};
+		}
+
+		self.produced_blocks += 1;
+		let mut digest = block.digest;
+		digest.push(DigestItem::Other(self.produced_blocks.encode()));
+
+		let hash = self.import_block(parent_hash, storage, extrinsics, digest);
+		let block = &self.blocks[&hash];
+		for (index, transaction) in block.extrinsics.iter().enumerate() {
+			if let Some(watcher) = self.watchers.get(&C::Hasher::hash(&transaction.0)) {
+				let _ = watcher.try_send(TransactionStatusOf::<C>::InBlock((hash, index)));
+			}
+		}
+		let header = block.header.clone();
+		notify(&mut self.best_headers, header);
+	}
+
+	/// Import block on top of the current best block.
+	fn import_block(
+		&mut self,
+		parent_hash: HashOf<C>,
+		storage: SimulatedStorage,
+		extrinsics: Vec<Bytes>,
+		digest: Vec<DigestItem>,
+	) -> HashOf<C> {
+		let number = BlockNumberOf::<C>::from(self.canonical.len() as u32);
+		let extrinsics_root = <C::Hasher as sp_runtime::traits::Hash>::ordered_trie_root(
+			extrinsics.iter().map(|transaction| transaction.0.clone()).collect(),
+			C::STATE_VERSION,
+		);
+		let state_root = storage_backend::<C>(&storage)
+			.storage_root(std::iter::empty::<(&[u8], Option<&[u8]>)>(), C::STATE_VERSION)
+			.0;
+		let header = HeaderOf::<C>::new(
+			number,
+			extrinsics_root,
+			state_root,
+			parent_hash,
+			Digest { logs: digest },
+		);
+		let hash = header.hash();
+
+		self.blocks
+			.insert(hash, BlockData { header, storage, extrinsics, justification: None });
+		self.canonical.push(hash);
+		hash
+	}
+
+	/// Finalize the best block.
+	fn finalize(&mut self, justification: Option<Vec<u8>>) {
+		let best = self.canonical.len() - 1;
+		for number in self.best_finalized + 1..=best {
+			let hash = self.canonical[number];
+			let block = &self.blocks[&hash];
+			for (index, transaction) in block.extrinsics.iter().enumerate() {
+				if let Some(watcher) = self.watchers.remove(&C::Hasher::hash(&transaction.0)) {
+					let _ = watcher.try_send(TransactionStatusOf::<C>::Finalized((hash, index)));
+				}
+			}
+			let header = block.header.clone();
+			notify(&mut self.finalized_headers, header);
+		}
+		self.best_finalized = best;
+
+		if let Some(justification) = justification {
+			let best_hash = self.canonical[best];
+			if let Some(block) = self.blocks.get_mut(&best_hash) {
+				block.justification = Some(justification.clone());
+			}
+			notify(&mut self.grandpa_justifications, Bytes(justification));
+		}
+	}
+
+	/// Revert `depth` best blocks and produce given blocks instead.
+	fn reorg(&mut self, depth: u32, blocks: Vec<SimulatedBlock>) {
+		let depth = depth as usize;
+		assert!(
+			depth < self.canonical.len() - self.best_finalized,
+			"Simulated {} chain can't revert finalized blocks",
+			C::NAME,
+		);
+
+		let new_len = self.canonical.len() - depth;
+		let mut retracted_transactions = Vec::new();
+		for hash in self.canonical.split_off(new_len) {
+			for transaction in &self.blocks[&hash].extrinsics {
+				if let Some(watcher) = self.watchers.get(&C::Hasher::hash(&transaction.0)) {
+					let _ = watcher.try_send(TransactionStatusOf::<C>::Retracted(hash));
+				}
+				retracted_transactions.push(transaction.clone());
+			}
+		}
+		retracted_transactions.append(&mut self.pool);
+		self.pool = retracted_transactions;
+
+		for block in blocks {
+			self.produce_block(block);
+		}
+	}
+
+	/// Disconnect all clients.
+	fn disconnect(&mut self, ticks: u64) {
+		self.connection += 1;
+		self.disconnected_until = self.now.saturating_add(ticks);
+		self.watchers.clear();
+		self.best_headers.clear();
+		self.finalized_headers.clear();
+		self.grandpa_justifications.clear();
+		self.beefy_justifications.clear();
+	}
+}
+
+/// Returns the justifications of the block.
+pub(super) fn block_justifications<C: Chain>(block: &BlockData<C>) -> Option<Justifications> {
+	block
+		.justification
+		.clone()
+		.map(|justification| Justifications::from((GRANDPA_ENGINE_ID, justification)))
+}
+
+/// Send item to all subscribers, forgetting about closed subscriptions.
+fn notify<T: Clone>(subscribers: &mut Vec<Sender<T>>, item: T) {
+	subscribers.retain(|subscriber| subscriber.try_send(item.clone()).is_ok());
+}
+
+/// Returns in-memory backend with given storage.
+fn storage_backend<C: Chain>(storage: &SimulatedStorage) -> InMemoryBackend<C::Hasher> {
+	InMemoryBackend::<C::Hasher>::from((storage.clone(), C::STATE_VERSION))
+}
+
+/// Returns unknown block error.
+fn unknown_block<C: Chain>(hash: HashOf<C>) -> Error {
+	Error::Custom(format!("Unknown {} block {hash:?}", C::NAME))
+}
@@ -0,0 +1,402 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Client implementation that is connected to the simulated chain.
+
+use crate::{
+	error::{Error, Result},
+	simulation::{
+		chain::{block_justifications, ChainState},
+		SimulatedChain,
+	},
+	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain,
+	ChainWithGrandpa, ChainWithTransactions, Client, HashOf, HeaderIdOf, HeaderOf, NonceOf,
+	SignParam, SignedBlockOf, SimpleRuntimeVersion, StreamDescription, Subscription,
+	TransactionTracker, UnsignedTransaction,
+};
+
+use async_std::{
+	channel::{unbounded, Sender},
+	sync::{Arc, MutexGuard},
+};
+use async_trait::async_trait;
+use bp_runtime::HeaderIdProvider;
+use codec::Encode;
+use frame_support::weights::Weight;
+use relay_utils::STALL_TIMEOUT;
+use sp_consensus_grandpa::{AuthorityId, OpaqueKeyOwnershipProof, SetId};
+use sp_core::{
+	storage::{StorageData, StorageKey},
+	Bytes, Hasher, Pair,
+};
+use sp_runtime::{
+	traits::Header as _, transaction_validity::TransactionValidity, DeserializeOwned,
+};
+use sp_trie::StorageProof;
+use sp_version::RuntimeVersion;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Spec and transaction version of the simulated chain runtime.
+const RUNTIME_VERSION: u32 = 1;
+
+/// Client that is connected to the simulated chain.
+///
+/// All clones of the client are sharing the same connection. When the chain disconnects its
+/// clients, all calls are failing with the connection error until the client is reconnected.
+#[derive(Clone)]
+pub struct SimulatedClient<C: Chain> {
+	chain: SimulatedChain<C>,
+	connection: Arc<AtomicU64>,
+}
+
+impl<C: Chain> SimulatedClient<C> {
+	/// Create client, connected to given chain.
+	pub(super) fn new(chain: SimulatedChain<C>) -> Self {
+		SimulatedClient { chain, connection: Arc::new(AtomicU64::new(0)) }
+	}
+
+	/// Returns chain state, if the client is connected to the chain.
+	async fn state(&self) -> Result<MutexGuard<'_, ChainState<C>>> {
+		let state = self.chain.state.lock().await;
+		if state.connection != self.connection.load(Ordering::SeqCst) {
+			return Err(Error::ChannelError(format!("Simulated {} client is disconnected", C::NAME)))
+		}
+		Ok(state)
+	}
+
+	/// Subscribe to the chain events.
+	async fn subscribe<T: 'static + Clone + DeserializeOwned + Send>(
+		&self,
+		stream_name: &str,
+		subscribers: impl FnOnce(&mut ChainState<C>) -> &mut Vec<Sender<T>>,
+	) -> Result<Subscription<T>> {
+		let mut state = self.state().await?;
+		let (sender, receiver) = unbounded();
+		subscribers(&mut state).push(sender);
+		Ok(Subscription::new_broadcasted(
+			StreamDescription::new(stream_name.into(), C::NAME.into()),
+			receiver,
+		))
+	}
+
+	/// Sign transaction and submit it to the pool, optionally watching its status.
+	async fn submit_signed(
+		&self,
+		signer: &AccountKeyPairOf<C>,
+		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, NonceOf<C>) -> Result<UnsignedTransaction<C>>
+			+ Send,
+		watch: bool,
+	) -> Result<(HashOf<C>, Option<Subscription<crate::TransactionStatusOf<C>>>)>
+	where
+		C: ChainWithTransactions,
+		AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	{
+		let mut state = self.state().await?;
+		let best_header_id = state.best_block().header.id();
+		let signer_id = AccountIdOf::<C>::from(signer.public()).encode();
+		let nonce = state.nonces.get(&signer_id).copied().unwrap_or_default();
+
+		let extrinsic = prepare_extrinsic(best_header_id, NonceOf::<C>::from(nonce))?;
+		let signed_extrinsic = C::sign_transaction(
+			SignParam {
+				spec_version: RUNTIME_VERSION,
+				transaction_version: RUNTIME_VERSION,
+				genesis_hash: self.chain.genesis_hash(),
+				signer: signer.clone(),
+			},
+			extrinsic,
+		)?
+		.encode();
+
+		match state.validate_transaction(best_header_id.hash(), &signed_extrinsic)? {
+			Ok(_) => (),
+			Err(e) => return Err(Error::TransactionInvalid(e)),
+		}
+
+		state.nonces.insert(signer_id, nonce + 1);
+		let transaction_hash = C::Hasher::hash(&signed_extrinsic);
+		let subscription = watch.then(|| state.watch_transaction(transaction_hash));
+		state.pool.push(Bytes(signed_extrinsic));
+		Ok((transaction_hash, subscription))
+	}
+}
+
+impl<C: Chain> std::fmt::Debug for SimulatedClient<C> {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fmt.write_fmt(format_args!("SimulatedClient<{}>", C::NAME))
+	}
+}
+
+#[async_trait]
+impl<C: Chain> Client<C> for SimulatedClient<C> {
+	async fn ensure_synced(&self) -> Result<()> {
+		self.state().await.map(drop)
+	}
+
+	async fn reconnect(&self) -> Result<()> {
+		let mut state = self.chain.state.lock().await;
+		if state.is_disconnected() {
+			return Err(Error::ChannelError(format!(
+				"Simulated {} node is not accepting connections",
+				C::NAME
+			)))
+		}
+
+		if self.connection.swap(state.connection, Ordering::SeqCst) != state.connection {
+			state.reconnects += 1;
+		}
+		Ok(())
+	}
+
+	fn genesis_hash(&self) -> HashOf<C> {
+		self.chain.genesis_hash()
+	}
+
+	async fn header_hash_by_number(&self, number: BlockNumberOf<C>) -> Result<HashOf<C>> {
+		self.state()
+			.await
+			.and_then(|state| state.canonical_hash(number))
+			.map_err(|e| Error::failed_to_read_header_hash_by_number::<C>(number, e))
+	}
+
+	async fn header_by_hash(&self, hash: HashOf<C>) -> Result<HeaderOf<C>> {
+		self.state()
+			.await
+			.and_then(|state| state.block(hash).map(|block| block.header.clone()))
+			.map_err(|e| Error::failed_to_read_header_by_hash::<C>(hash, e))
+	}
+
+	async fn block_by_hash(&self, hash: HashOf<C>) -> Result<SignedBlockOf<C>> {
+		self.state()
+			.await
+			.and_then(|state| {
+				let block = state.block(hash)?;
+				// the signed block type is opaque here, so we are constructing it the same way
+				// as the RPC client does - from its JSON representation
+				serde_json::from_value(serde_json::json!({
+					"block": {
+						"header": block.header,
+						"extrinsics": block.extrinsics,
+					},
+					"justifications": block_justifications(block),
+				}))
+				.map_err(|e| Error::Custom(format!("Failed to construct {} block: {e:?}", C::NAME)))
+			})
+			.map_err(|e| Error::failed_to_read_block_by_hash::<C>(hash, e))
+	}
+
+	async fn best_finalized_header_hash(&self) -> Result<HashOf<C>> {
+		self.state()
+			.await
+			.map(|state| state.canonical[state.best_finalized])
+			.map_err(|e| Error::failed_to_read_best_finalized_header_hash::<C>(e))
+	}
+
+	async fn best_header(&self) -> Result<HeaderOf<C>> {
+		self.state()
+			.await
+			.map(|state| state.best_block().header.clone())
+			.map_err(|e| Error::failed_to_read_best_header::<C>(e))
+	}
+
+	async fn subscribe_best_headers(&self) -> Result<Subscription<HeaderOf<C>>> {
+		self.subscribe("best headers", |state| &mut state.best_headers)
+			.await
+			.map_err(|e| Error::failed_to_subscribe_best_headers::<C>(e))
+	}
+
+	async fn subscribe_finalized_headers(&self) -> Result<Subscription<HeaderOf<C>>> {
+		self.subscribe("finalized headers", |state| &mut state.finalized_headers)
+			.await
+			.map_err(|e| Error::failed_to_subscribe_finalized_headers::<C>(e))
+	}
+
+	async fn subscribe_grandpa_finality_justifications(&self) -> Result<Subscription<Bytes>>
+	where
+		C: ChainWithGrandpa,
+	{
+		self.subscribe("GRANDPA justifications", |state| &mut state.grandpa_justifications)
+			.await
+			.map_err(|e| Error::failed_to_subscribe_justification::<C>(e))
+	}
+
+	async fn generate_grandpa_key_ownership_proof(
+		&self,
+		_at: HashOf<C>,
+		_set_id: SetId,
+		_authority_id: AuthorityId,
+	) -> Result<Option<OpaqueKeyOwnershipProof>> {
+		self.state().await.map(|_| None)
+	}
+
+	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>> {
+		self.subscribe("BEEFY justifications", |state| &mut state.beefy_justifications)
+			.await
+			.map_err(|e| Error::failed_to_subscribe_justification::<C>(e))
+	}
+
+	async fn token_decimals(&self) -> Result<Option<u64>> {
+		self.state().await.map(|_| None)
+	}
+
+	async fn runtime_version(&self) -> Result<RuntimeVersion> {
+		self.state()
+			.await
+			.map(|_| RuntimeVersion {
+				spec_version: RUNTIME_VERSION,
+				transaction_version: RUNTIME_VERSION,
+				..Default::default()
+			})
+			.map_err(|e| Error::failed_to_read_runtime_version::<C>(e))
+	}
+
+	async fn simple_runtime_version(&self) -> Result<SimpleRuntimeVersion> {
+		Ok(SimpleRuntimeVersion::from_runtime_version(&self.runtime_version().await?))
+	}
+
+	fn can_start_version_guard(&self) -> bool {
+		false
+	}
+
+	async fn raw_storage_value(
+		&self,
+		at: HashOf<C>,
+		storage_key: StorageKey,
+	) -> Result<Option<StorageData>> {
+		self.state()
+			.await
+			.and_then(|state| {
+				state
+					.block(at)
+					.map(|block| block.storage.get(&storage_key.0).cloned().map(StorageData))
+			})
+			.map_err(|e| Error::failed_to_read_storage_value::<C>(at, storage_key, e))
+	}
+
+	async fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
+		self.state()
+			.await
+			.map(|state| state.pool.clone())
+			.map_err(|e| Error::failed_to_get_pending_extrinsics::<C>(e))
+	}
+
+	async fn submit_unsigned_extrinsic(&self, transaction: Bytes) -> Result<HashOf<C>> {
+		async {
+			let mut state = self.state().await?;
+			let best_hash = state.best_block().header.hash();
+			state
+				.validate_transaction(best_hash, &transaction.0)?
+				.map_err(Error::TransactionInvalid)?;
+
+			let transaction_hash = C::Hasher::hash(&transaction.0);
+			state.pool.push(transaction);
+			Ok(transaction_hash)
+		}
+		.await
+		.map_err(|e| Error::failed_to_submit_transaction::<C>(e))
+	}
+
+	async fn submit_signed_extrinsic(
+		&self,
+		signer: &AccountKeyPairOf<C>,
+		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, NonceOf<C>) -> Result<UnsignedTransaction<C>>
+			+ Send
+			+ 'static,
+	) -> Result<HashOf<C>>
+	where
+		C: ChainWithTransactions,
+		AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	{
+		self.submit_signed(signer, prepare_extrinsic, false)
+			.await
+			.map(|(transaction_hash, _)| transaction_hash)
+			.map_err(|e| Error::failed_to_submit_transaction::<C>(e))
+	}
+
+	async fn submit_and_watch_signed_extrinsic(
+		&self,
+		signer: &AccountKeyPairOf<C>,
+		prepare_extrinsic: impl FnOnce(HeaderIdOf<C>, NonceOf<C>) -> Result<UnsignedTransaction<C>>
+			+ Send
+			+ 'static,
+	) -> Result<TransactionTracker<C, Self>>
+	where
+		C: ChainWithTransactions,
+		AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	{
+		let mut mortality_period = None;
+		let (transaction_hash, subscription) = self
+			.submit_signed(
+				signer,
+				|best_header_id, transaction_nonce| {
+					let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce)?;
+					mortality_period = extrinsic.era.mortality_period();
+					Ok(extrinsic)
+				},
+				true,
+			)
+			.await
+			.map_err(|e| Error::failed_to_submit_transaction::<C>(e))?;
+
+		Ok(TransactionTracker::new(
+			self.clone(),
+			transaction_stall_timeout(mortality_period, C::AVERAGE_BLOCK_INTERVAL, STALL_TIMEOUT),
+			transaction_hash,
+			subscription.expect("we have asked to watch the transaction; qed"),
+		))
+	}
+
+	async fn validate_transaction<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		at: HashOf<C>,
+		transaction: SignedTransaction,
+	) -> Result<TransactionValidity> {
+		self.state().await?.validate_transaction(at, &transaction.encode())
+	}
+
+	async fn estimate_extrinsic_weight<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		at: HashOf<C>,
+		transaction: SignedTransaction,
+	) -> Result<Weight> {
+		self.state().await?.transaction_weight(at, &transaction.encode())
+	}
+
+	async fn raw_state_call<Args: Encode + Send>(
+		&self,
+		at: HashOf<C>,
+		method: String,
+		arguments: Args,
+	) -> Result<Bytes> {
+		let arguments = Bytes(arguments.encode());
+		async { self.state().await?.call(at, &method, &arguments.0).map(Bytes) }
+			.await
+			.map_err(|e| Error::failed_state_call::<C>(at, method, arguments, e))
+	}
+
+	async fn prove_storage(
+		&self,
+		at: HashOf<C>,
+		keys: Vec<StorageKey>,
+	) -> Result<(StorageProof, HashOf<C>)> {
+		async {
+			let raw_keys = keys.iter().map(|key| key.0.clone()).collect::<Vec<_>>();
+			self.state().await?.prove_storage(at, &raw_keys)
+		}
+		.await
+		.map_err(|e| Error::failed_to_prove_storage::<C>(at, keys, e))
+	}
+}
@@ -0,0 +1,682 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic offline simulation of Substrate chains, that may be used to test relays.
+//!
+//! The [`SimulatedChain`] is an in-process stand-in for the Substrate node. It doesn't produce
+//! or finalize blocks on its own. Instead, it follows the [`SimulationScript`], which says what
+//! happens with the chain at every tick of the simulated time: blocks are produced and
+//! finalized, the chain is reorganized, clients are disconnected, runtime calls are failing or
+//! transaction fees are changing. The chain state is fully defined by the script and by
+//! transactions, submitted by relays, so every run of the same scenario gives the same result.
+//!
+//! Relays are talking to the chain using the [`SimulatedClient`], which implements the
+//! [`Client`](crate::Client) trait and may be used instead of the RPC client. Everything that
+//! is specific to the chain runtime (runtime APIs, transactions validation and dispatch) is
+//! provided by the [`SimulatedRuntime`] implementation. Storage proofs are real - they are
+//! generated from the block storage and may be verified against the block header state root.
+//!
+//! The [`Simulation`] drives the simulated time of several chains, so the bridge between
+//! simulated chains may be tested using `cargo test`, without starting any nodes. Relays,
+//! started with [`Simulation::spawn`], are sleeping and reading current time using the same
+//! simulated [clock](relay_utils::clock). Before every tick, the simulation waits until all
+//! relay tasks are blocked, so the time only advances when relays have reacted to the changes
+//! and no real time is spent on waiting.
+
+#![cfg(any(feature = "test-helpers", test))]
+
+mod chain;
+mod client;
+
+pub use chain::SimulatedChain;
+pub use client::SimulatedClient;
+
+use crate::error::{Error, Result};
+
+use async_trait::async_trait;
+use codec::Encode;
+use frame_support::weights::Weight;
+use relay_utils::clock::SimulatedClock;
+use sp_core::storage::StorageKey;
+use sp_runtime::{
+	transaction_validity::{TransactionValidity, ValidTransaction},
+	DigestItem, FixedU128,
+};
+use std::{collections::BTreeMap, future::Future, ops::Range, time::Duration};
+
+/// Simulated time of a single tick, used by default.
+pub const DEFAULT_TICK_DURATION: Duration = Duration::from_secs(6);
+
+/// Storage of the simulated chain block.
+pub type SimulatedStorage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Block, produced by the simulated chain.
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedBlock {
+	/// Changes of the parent block storage, applied after all block transactions. The `None`
+	/// value means that the key is removed from the storage.
+	pub storage_changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+	/// Digest items of the block header.
+	pub digest: Vec<DigestItem>,
+}
+
+impl SimulatedBlock {
+	/// Set storage value at the block.
+	pub fn with_storage_value(mut self, key: StorageKey, value: impl Encode) -> Self {
+		self.storage_changes.push((key.0, Some(value.encode())));
+		self
+	}
+
+	/// Remove storage value at the block.
+	pub fn without_storage_value(mut self, key: StorageKey) -> Self {
+		self.storage_changes.push((key.0, None));
+		self
+	}
+
+	/// Add item to the block header digest.
+	pub fn with_digest_item(mut self, item: DigestItem) -> Self {
+		self.digest.push(item);
+		self
+	}
+}
+
+/// Action of the simulated chain.
+#[derive(Clone, Debug)]
+pub enum SimulatedAction {
+	/// Produce new best block on top of the current best block. All transactions from the pool
+	/// are included into the new block.
+	ProduceBlock(SimulatedBlock),
+	/// Finalize the current best block, optionally providing its encoded GRANDPA justification.
+	/// The justification is stored in the block and is sent to justification subscribers.
+	Finalize(Option<Vec<u8>>),
+	/// Finalize the current best block with the GRANDPA justification, generated by the
+	/// [`SimulatedRuntime::grandpa_justification`].
+	FinalizeWithJustification,
+	/// Send encoded BEEFY justification to justification subscribers.
+	BeefyJustification(Vec<u8>),
+	/// Revert given number of best blocks and produce given blocks instead. Finalized blocks
+	/// can't be reverted. Transactions of reverted blocks are returned to the pool.
+	Reorg {
+		/// Number of best blocks to revert.
+		depth: u32,
+		/// Blocks to produce on top of the new best block.
+		blocks: Vec<SimulatedBlock>,
+	},
+	/// Disconnect all clients. All subscriptions are closed and all client calls are failing
+	/// with the connection error, until the client is reconnected. Reconnect is failing during
+	/// given number of ticks.
+	Disconnect {
+		/// Number of ticks when the node is not accepting connections.
+		ticks: u64,
+	},
+	/// Fail next calls of given runtime API method with the non-connection error.
+	FailRuntimeCalls {
+		/// Runtime API method name.
+		method: String,
+		/// Number of calls to fail.
+		count: u32,
+	},
+	/// Set transaction fee multiplier. The weight of every transaction is multiplied by this
+	/// value and it is also available to the runtime, which may use it to compute fees.
+	SetFeeMultiplier(FixedU128),
+}
+
+/// Script of the simulated chain: actions that are happening at given ticks of the simulated
+/// time.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationScript {
+	actions: BTreeMap<u64, Vec<SimulatedAction>>,
+}
+
+impl SimulationScript {
+	/// Create empty script.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Perform the action at given tick. Actions of the same tick are performed in the same
+	/// order they have been added to the script.
+	pub fn at(mut self, tick: u64, action: SimulatedAction) -> Self {
+		self.actions.entry(tick).or_default().push(action);
+		self
+	}
+
+	/// Perform the action at every tick of given range.
+	pub fn every(self, ticks: Range<u64>, action: SimulatedAction) -> Self {
+		ticks.fold(self, |script, tick| script.at(tick, action.clone()))
+	}
+
+	/// Returns `true` if the script has no actions after given tick.
+	pub fn is_finished_at(&self, tick: u64) -> bool {
+		self.actions.range(tick.saturating_add(1)..).next().is_none()
+	}
+
+	/// Take all actions of given tick.
+	fn take(&mut self, tick: u64) -> Vec<SimulatedAction> {
+		self.actions.remove(&tick).unwrap_or_default()
+	}
+}
+
+/// State of the simulated chain, that is visible to the runtime.
+pub struct RuntimeContext<'a> {
+	/// Storage of the block.
+	pub storage: &'a SimulatedStorage,
+	/// Current transaction fee multiplier.
+	pub fee_multiplier: FixedU128,
+}
+
+/// Runtime of the simulated chain.
+///
+/// By default, the runtime has no runtime API methods and accepts all transactions, without
+/// changing the storage.
+pub trait SimulatedRuntime: Send + 'static {
+	/// Execute runtime API method with given encoded arguments.
+	fn call(
+		&mut self,
+		_context: RuntimeContext,
+		method: &str,
+		_arguments: &[u8],
+	) -> Result<Vec<u8>> {
+		Err(Error::Custom(format!("Simulated runtime has no {method} method")))
+	}
+
+	/// Validate encoded transaction, before it is added to the pool.
+	fn validate_transaction(
+		&mut self,
+		_context: RuntimeContext,
+		_transaction: &[u8],
+	) -> TransactionValidity {
+		Ok(ValidTransaction::default())
+	}
+
+	/// Dispatch encoded transaction. If it fails, changes to the storage are discarded, but the
+	/// transaction is still included into the block.
+	fn apply_transaction(
+		&mut self,
+		_storage: &mut SimulatedStorage,
+		_transaction: &[u8],
+	) -> Result<()> {
+		Ok(())
+	}
+
+	/// Returns weight of encoded transaction, before it is multiplied by the fee multiplier.
+	fn transaction_weight(&mut self, _context: RuntimeContext, _transaction: &[u8]) -> Weight {
+		Weight::zero()
+	}
+
+	/// Returns encoded GRANDPA justification for given encoded header, if the runtime is able
+	/// to generate it.
+	fn grandpa_justification(&mut self, _header: &[u8]) -> Option<Vec<u8>> {
+		None
+	}
+}
+
+impl SimulatedRuntime for () {}
+
+/// Participant of the simulation.
+#[async_trait]
+pub trait SimulationParticipant: Send + Sync {
+	/// Perform all scripted actions of given tick.
+	async fn tick(&self, now: u64);
+}
+
+/// Deterministic simulation of several chains.
+///
+/// All participants share the same simulated time. At every tick, participants are performing
+/// their scripted actions in the order they have been added to the simulation and then the
+/// simulated clock advances by the tick duration.
+pub struct Simulation {
+	now: u64,
+	tick_duration: Duration,
+	clock: SimulatedClock,
+	participants: Vec<Box<dyn SimulationParticipant>>,
+}
+
+impl Default for Simulation {
+	fn default() -> Self {
+		Simulation {
+			now: 0,
+			tick_duration: DEFAULT_TICK_DURATION,
+			clock: SimulatedClock::new(),
+			participants: Vec::new(),
+		}
+	}
+}
+
+impl Simulation {
+	/// Create simulation without participants.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add participant to the simulation.
+	pub fn with_participant(mut self, participant: impl SimulationParticipant + 'static) -> Self {
+		self.participants.push(Box::new(participant));
+		self
+	}
+
+	/// Set simulated time of a single tick.
+	pub fn with_tick_duration(mut self, tick_duration: Duration) -> Self {
+		self.tick_duration = tick_duration;
+		self
+	}
+
+	/// Returns current tick of the simulated time.
+	pub fn now(&self) -> u64 {
+		self.now
+	}
+
+	/// Returns the simulated clock.
+	pub fn clock(&self) -> &SimulatedClock {
+		&self.clock
+	}
+
+	/// Spawn the task (e.g. relay) that is using the simulated clock.
+	pub fn spawn<F>(&self, future: F) -> async_std::task::JoinHandle<F::Output>
+	where
+		F: Future + Send + 'static,
+		F::Output: Send + 'static,
+	{
+		async_std::task::spawn(self.clock.scope(future))
+	}
+
+	/// Advance simulated time by one tick.
+	pub async fn tick(&mut self) {
+		self.now += 1;
+		for participant in &self.participants {
+			participant.tick(self.now).await;
+		}
+		self.clock.advance(self.tick_duration);
+	}
+
+	/// Keep advancing simulated time until the condition is met, or `max_ticks` are passed.
+	/// Before checking the condition, the simulation waits until all spawned tasks are blocked.
+	///
+	/// Returns `true` if the condition has been met.
+	pub async fn run_until<F: Future<Output = bool>>(
+		&mut self,
+		max_ticks: u64,
+		mut condition: impl FnMut() -> F,
+	) -> bool {
+		let deadline = self.now.saturating_add(max_ticks);
+		loop {
+			self.clock.wait_until_idle().await;
+			if condition().await {
+				return true
+			}
+			if self.now >= deadline {
+				return false
+			}
+
+			self.tick().await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{test_chain::TestChain, Client, HeaderOf, TransactionStatusOf};
+
+	use bp_runtime::StorageProofChecker;
+	use futures::StreamExt;
+	use relay_utils::{
+		HeaderId, MaybeConnectionError, TrackedTransactionStatus, TransactionTracker as _,
+	};
+	use sp_core::Bytes;
+	use sp_runtime::traits::Header as _;
+
+	const TEST_KEY: &[u8] = b"test-key";
+	const TEST_METHOD: &str = "TestMethod";
+
+	struct TestRuntime;
+
+	impl SimulatedRuntime for TestRuntime {
+		fn call(
+			&mut self,
+			context: RuntimeContext,
+			method: &str,
+			_arguments: &[u8],
+		) -> Result<Vec<u8>> {
+			match method {
+				TEST_METHOD => Ok(context.storage.get(TEST_KEY).cloned().encode()),
+				_ => Err(Error::Custom(format!("Unknown method {method}"))),
+			}
+		}
+
+		fn apply_transaction(
+			&mut self,
+			storage: &mut SimulatedStorage,
+			transaction: &[u8],
+		) -> Result<()> {
+			storage.insert(TEST_KEY.to_vec(), transaction.to_vec());
+			Ok(())
+		}
+
+		fn transaction_weight(&mut self, _context: RuntimeContext, _transaction: &[u8]) -> Weight {
+			Weight::from_parts(1_000, 100)
+		}
+
+		fn grandpa_justification(&mut self, header: &[u8]) -> Option<Vec<u8>> {
+			Some(header.iter().rev().cloned().collect())
+		}
+	}
+
+	fn test_chain(script: SimulationScript) -> SimulatedChain<TestChain> {
+		SimulatedChain::new(TestRuntime, SimulatedStorage::new(), script)
+	}
+
+	fn produce_block() -> SimulatedAction {
+		SimulatedAction::ProduceBlock(SimulatedBlock::default())
+	}
+
+	fn test_transaction(id: u8) -> Bytes {
+		Bytes(vec![id; 4].encode())
+	}
+
+	fn test_simulation(chain: &SimulatedChain<TestChain>) -> Simulation {
+		Simulation::new().with_participant(chain.clone())
+	}
+
+	async fn run(simulation: &mut Simulation, ticks: u64) {
+		for _ in 0..ticks {
+			simulation.tick().await;
+		}
+	}
+
+	#[async_std::test]
+	async fn simulation_is_deterministic() {
+		async fn run_scenario() -> (HeaderOf<TestChain>, HeaderOf<TestChain>) {
+			let chain = test_chain(
+				SimulationScript::new()
+					.every(1..10, produce_block())
+					.at(5, SimulatedAction::Finalize(None))
+					.at(8, SimulatedAction::Reorg { depth: 2, blocks: vec![Default::default()] }),
+			);
+			let client = chain.client();
+			let mut simulation = test_simulation(&chain);
+			run(&mut simulation, 4).await;
+			client.submit_unsigned_extrinsic(test_transaction(42)).await.unwrap();
+			run(&mut simulation, 6).await;
+			(chain.best_header().await, chain.best_finalized_header().await)
+		}
+
+		let (best_header, best_finalized_header) = run_scenario().await;
+		assert_eq!(*best_header.number(), 8);
+		assert_eq!(*best_finalized_header.number(), 5);
+		assert_eq!(run_scenario().await, (best_header, best_finalized_header));
+	}
+
+	#[async_std::test]
+	async fn transactions_are_dispatched_by_runtime() {
+		let chain = test_chain(SimulationScript::new().at(1, produce_block()));
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		client.submit_unsigned_extrinsic(test_transaction(42)).await.unwrap();
+		assert_eq!(client.pending_extrinsics().await.unwrap(), vec![test_transaction(42)]);
+
+		run(&mut simulation, 1).await;
+		let best_hash = client.best_header_hash().await.unwrap();
+		assert!(client.pending_extrinsics().await.unwrap().is_empty());
+		assert_eq!(
+			client
+				.state_call::<_, Option<Vec<u8>>>(best_hash, TEST_METHOD.into(), ())
+				.await
+				.unwrap(),
+			Some(test_transaction(42).0),
+		);
+		assert_eq!(client.block_by_hash(best_hash).await.unwrap().block.extrinsics.len(), 1);
+	}
+
+	#[async_std::test]
+	async fn storage_proofs_are_verified_using_header_state_root() {
+		let chain = test_chain(
+			SimulationScript::new().at(
+				1,
+				SimulatedAction::ProduceBlock(
+					SimulatedBlock::default()
+						.with_storage_value(StorageKey(b"key1".to_vec()), 1u32)
+						.with_storage_value(StorageKey(b"key2".to_vec()), 2u32),
+				),
+			),
+		);
+		let mut simulation = test_simulation(&chain);
+		run(&mut simulation, 1).await;
+
+		let client = chain.client();
+		let best_header = client.best_header().await.unwrap();
+		let (proof, state_root) = client
+			.prove_storage(best_header.hash(), vec![StorageKey(b"key1".to_vec())])
+			.await
+			.unwrap();
+		assert_eq!(state_root, *best_header.state_root());
+
+		let mut checker = StorageProofChecker::<<TestChain as bp_runtime::Chain>::Hasher>::new(
+			*best_header.state_root(),
+			proof.into_iter_nodes().collect(),
+		)
+		.unwrap();
+		assert_eq!(checker.read_and_decode_value::<u32>(b"key1").unwrap(), Some(1));
+	}
+
+	#[async_std::test]
+	async fn disconnected_client_is_failing_until_reconnected() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, SimulatedAction::Disconnect { ticks: 2 })
+				.at(2, produce_block()),
+		);
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		let mut best_headers = client.subscribe_best_headers().await.unwrap();
+
+		// the node is not accepting connections during two ticks
+		run(&mut simulation, 1).await;
+		assert!(best_headers.next().await.is_none());
+		assert!(client.best_header().await.unwrap_err().is_connection_error());
+		assert!(client.reconnect().await.unwrap_err().is_connection_error());
+		run(&mut simulation, 1).await;
+		assert!(client.reconnect().await.unwrap_err().is_connection_error());
+
+		// and then it is back online
+		run(&mut simulation, 1).await;
+		client.reconnect().await.unwrap();
+		assert_eq!(*client.best_header().await.unwrap().number(), 1);
+		assert_eq!(chain.reconnects().await, 1);
+	}
+
+	#[async_std::test]
+	async fn scripted_runtime_call_failures_are_not_connection_errors() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, SimulatedAction::FailRuntimeCalls { method: TEST_METHOD.into(), count: 1 }),
+		);
+		let mut simulation = test_simulation(&chain);
+		run(&mut simulation, 1).await;
+
+		let client = chain.client();
+		let best_hash = client.best_header_hash().await.unwrap();
+		let error = client.raw_state_call(best_hash, TEST_METHOD.into(), ()).await.unwrap_err();
+		assert!(!error.is_connection_error());
+		client.raw_state_call(best_hash, TEST_METHOD.into(), ()).await.unwrap();
+	}
+
+	#[async_std::test]
+	async fn fee_spike_changes_estimated_weight() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, SimulatedAction::SetFeeMultiplier(FixedU128::from_u32(3))),
+		);
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		let best_hash = client.best_header_hash().await.unwrap();
+		assert_eq!(
+			client.estimate_extrinsic_weight(best_hash, test_transaction(42)).await.unwrap(),
+			Weight::from_parts(1_000, 100),
+		);
+
+		run(&mut simulation, 1).await;
+		assert_eq!(
+			client.estimate_extrinsic_weight(best_hash, test_transaction(42)).await.unwrap(),
+			Weight::from_parts(3_000, 300),
+		);
+	}
+
+	#[async_std::test]
+	async fn justifications_are_provided_by_chain() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, produce_block())
+				.at(1, SimulatedAction::Finalize(Some(vec![1, 2, 3])))
+				.at(2, SimulatedAction::BeefyJustification(vec![4, 5, 6])),
+		);
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		let mut finalized_headers = client.subscribe_finalized_headers().await.unwrap();
+		let mut beefy_justifications =
+			client.subscribe_beefy_finality_justifications().await.unwrap();
+
+		run(&mut simulation, 2).await;
+		let finalized_header = finalized_headers.next().await.unwrap();
+		assert_eq!(*finalized_header.number(), 1);
+		assert_eq!(beefy_justifications.next().await, Some(Bytes(vec![4, 5, 6])));
+
+		let block = client.block_by_hash(finalized_header.hash()).await.unwrap();
+		assert_eq!(
+			block
+				.justifications
+				.and_then(|j| j.into_justification(sp_consensus_grandpa::GRANDPA_ENGINE_ID)),
+			Some(vec![1, 2, 3]),
+		);
+	}
+
+	#[async_std::test]
+	async fn justifications_are_generated_by_runtime() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, produce_block())
+				.at(1, SimulatedAction::FinalizeWithJustification),
+		);
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		let mut justifications = client.subscribe_grandpa_finality_justifications().await.unwrap();
+
+		run(&mut simulation, 1).await;
+		let finalized_header = chain.best_finalized_header().await;
+		let expected_justification: Vec<u8> = finalized_header.encode().into_iter().rev().collect();
+		assert_eq!(*finalized_header.number(), 1);
+		assert_eq!(justifications.next().await, Some(Bytes(expected_justification.clone())));
+		assert_eq!(
+			client
+				.block_by_hash(finalized_header.hash())
+				.await
+				.unwrap()
+				.justifications
+				.and_then(|j| j.into_justification(sp_consensus_grandpa::GRANDPA_ENGINE_ID)),
+			Some(expected_justification),
+		);
+	}
+
+	#[async_std::test]
+	async fn reorg_retracts_transactions() {
+		let chain = test_chain(
+			SimulationScript::new()
+				.at(1, produce_block())
+				.at(2, SimulatedAction::Reorg { depth: 1, blocks: vec![Default::default()] })
+				.at(3, produce_block())
+				.at(4, SimulatedAction::Finalize(None)),
+		);
+		let client = chain.client();
+		let mut simulation = test_simulation(&chain);
+		let (transaction_hash, mut statuses) = chain.submit_and_watch(test_transaction(42)).await;
+
+		// transaction is included into block#1, which is then reverted
+		run(&mut simulation, 2).await;
+		assert_eq!(statuses.next().await, Some(TransactionStatusOf::<TestChain>::Ready));
+		let retracted_block = match statuses.next().await {
+			Some(TransactionStatusOf::<TestChain>::InBlock((block, 0))) => block,
+			status => panic!("Unexpected transaction status: {status:?}"),
+		};
+		assert_eq!(
+			statuses.next().await,
+			Some(TransactionStatusOf::<TestChain>::Retracted(retracted_block)),
+		);
+
+		// it is back to the pool, so it is included into the new block#1 and then finalized
+		let tracker = crate::TransactionTracker::<TestChain, _>::new(
+			client.clone(),
+			Duration::from_secs(60),
+			transaction_hash,
+			statuses,
+		);
+		run(&mut simulation, 2).await;
+		let new_block = client.header_by_number(1).await.unwrap();
+		assert_ne!(new_block.hash(), retracted_block);
+		assert_eq!(*client.best_finalized_header().await.unwrap().number(), 2);
+		assert_eq!(
+			tracker.wait().await,
+			TrackedTransactionStatus::Finalized(HeaderId(1, new_block.hash())),
+		);
+		assert_eq!(
+			client
+				.state_call::<_, Option<Vec<u8>>>(new_block.hash(), TEST_METHOD.into(), ())
+				.await
+				.unwrap(),
+			Some(test_transaction(42).0),
+		);
+	}
+
+	#[async_std::test]
+	async fn run_until_stops_when_condition_is_met() {
+		let chain = test_chain(SimulationScript::new().every(1..100, produce_block()));
+		let client = chain.client();
+		let mut simulation = Simulation::new().with_participant(chain.clone());
+
+		let reached = simulation
+			.run_until(10, || {
+				let client = client.clone();
+				async move { client.best_header().await.map(|h| *h.number() >= 5).unwrap_or(false) }
+			})
+			.await;
+		assert!(reached);
+		assert_eq!(simulation.now(), 5);
+
+		let reached = simulation.run_until(3, || async { false }).await;
+		assert!(!reached);
+		assert_eq!(simulation.now(), 8);
+		assert_eq!(*chain.best_header().await.number(), 8);
+	}
+
+	#[async_std::test]
+	async fn spawned_tasks_are_using_simulated_time() {
+		let chain = test_chain(SimulationScript::new().every(1..100, produce_block()));
+		let client = chain.client();
+		let mut simulation = Simulation::new()
+			.with_participant(chain.clone())
+			.with_tick_duration(Duration::from_secs(60));
+
+		// the task sleeps for an hour of the simulated time, which passes in 60 ticks
+		let best_block_after_hour = simulation.spawn(async move {
+			relay_utils::clock::sleep(Duration::from_secs(3600)).await;
+			client.best_header().await.map(|header| *header.number())
+		});
+		let reached = simulation.run_until(100, || async { false }).await;
+
+		assert!(!reached);
+		assert_eq!(simulation.clock().elapsed(), Duration::from_secs(6000));
+		assert_eq!(best_block_after_hour.await.unwrap(), 60);
+	}
+}
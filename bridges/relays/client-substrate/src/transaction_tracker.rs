@@ -150,7 +150,7 @@ impl<C: Chain, E: Environment<C>> relay_utils::TransactionTracker for Transactio
 	type HeaderId = HeaderIdOf<C>;
 
 	async fn wait(self) -> TrackedTransactionStatus<HeaderIdOf<C>> {
-		let wait_for_stall_timeout = async_std::task::sleep(self.stall_timeout).shared();
+		let wait_for_stall_timeout = relay_utils::clock::sleep(self.stall_timeout).shared();
 		let wait_for_stall_timeout_rest = wait_for_stall_timeout.clone();
 		self.do_wait(wait_for_stall_timeout, wait_for_stall_timeout_rest).await.0
 	}
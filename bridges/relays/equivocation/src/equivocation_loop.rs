@@ -120,7 +120,7 @@ impl<P: EquivocationDetectionPipeline, SC: SourceClient<P>, TC: TargetClient<P>>
 
 			select_biased! {
 				_ = exit_signal => return,
-				_ = async_std::task::sleep(tick).fuse() => {},
+				_ = relay_utils::clock::sleep(tick).fuse() => {},
 			}
 		}
 	}
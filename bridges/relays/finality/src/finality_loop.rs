@@ -278,7 +278,7 @@ impl<P: FinalitySyncPipeline, SC: SourceClient<P>, TC: TargetClient<P>> Finality
 			target_client,
 			sync_params,
 			metrics_sync,
-			progress: (Instant::now(), None),
+			progress: (relay_utils::clock::now(), None),
 			retry_backoff: retry_backoff(),
 			finality_proofs_stream: FinalityProofsStream::new(),
 			finality_proofs_buf: FinalityProofsBuf::new(vec![]),
@@ -288,7 +288,7 @@ impl<P: FinalitySyncPipeline, SC: SourceClient<P>, TC: TargetClient<P>> Finality
 
 	fn update_progress(&mut self, info: &SyncInfo<P>) {
 		let (prev_time, prev_best_number_at_target) = self.progress;
-		let now = Instant::now();
+		let now = relay_utils::clock::now();
 
 		let needs_update = now - prev_time > Duration::from_secs(10) ||
 			prev_best_number_at_target
@@ -457,7 +457,7 @@ impl<P: FinalitySyncPipeline, SC: SourceClient<P>, TC: TargetClient<P>> Finality
 						e.fail_if_connection_error()?;
 					}
 				},
-				_ = async_std::task::sleep(next_tick).fuse() => {},
+				_ = relay_utils::clock::sleep(next_tick).fuse() => {},
 				_ = exit_signal => return Ok(()),
 			}
 		}
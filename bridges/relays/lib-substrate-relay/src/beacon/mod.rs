@@ -0,0 +1,98 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and functions intended to ease adding of new Ethereum -> Substrate beacon light
+//! client synchronization pipelines.
+
+use beacon_relay::BeaconSyncPipeline;
+use relay_substrate_client::{CallOf, ChainWithTransactions};
+use snowbridge_pallet_ethereum_client::{
+	types::{CheckpointUpdate, Update},
+	Call as EthereumClientCall, Config as EthereumClientConfig,
+};
+use std::marker::PhantomData;
+
+pub mod target;
+
+/// Ethereum -> Substrate beacon light client synchronization pipeline.
+pub trait SubstrateBeaconSyncPipeline: BeaconSyncPipeline {
+	/// Target chain, where the Ethereum light client is deployed.
+	type TargetChain: ChainWithTransactions;
+
+	/// Name of the `snowbridge-pallet-ethereum-client` pallet at the target chain runtime.
+	const ETHEREUM_CLIENT_PALLET_NAME: &'static str;
+
+	/// How submit update call is built?
+	type SubmitUpdateCallBuilder: SubmitBeaconUpdateCallBuilder<Self>;
+}
+
+/// Different ways of building `submit` calls.
+pub trait SubmitBeaconUpdateCallBuilder<P: SubstrateBeaconSyncPipeline>:
+	'static + Send + Sync
+{
+	/// Given update, build call of `submit` function of the Ethereum light client pallet at the
+	/// target chain.
+	fn build_submit_update_call(update: P::Update) -> CallOf<P::TargetChain>;
+
+	/// Given checkpoint update, build call that dispatches the `force_checkpoint` function of
+	/// the Ethereum light client pallet at the target chain with the root origin. Returns `None`
+	/// if the relay is unable to do that, so the light client must be bootstrapped by the
+	/// governance.
+	fn build_force_checkpoint_call(update: P::CheckpointUpdate) -> Option<CallOf<P::TargetChain>>;
+}
+
+/// Building `submit` call when you have direct access to the target chain runtime.
+pub struct DirectSubmitBeaconUpdateCallBuilder<P, R> {
+	_phantom: PhantomData<(P, R)>,
+}
+
+impl<P, R> SubmitBeaconUpdateCallBuilder<P> for DirectSubmitBeaconUpdateCallBuilder<P, R>
+where
+	P: SubstrateBeaconSyncPipeline<CheckpointUpdate = CheckpointUpdate, Update = Update>,
+	R: EthereumClientConfig + Send + Sync,
+	CallOf<P::TargetChain>: From<EthereumClientCall<R>>,
+{
+	fn build_submit_update_call(update: Update) -> CallOf<P::TargetChain> {
+		EthereumClientCall::<R>::submit { update: Box::new(update) }.into()
+	}
+
+	fn build_force_checkpoint_call(_update: CheckpointUpdate) -> Option<CallOf<P::TargetChain>> {
+		None
+	}
+}
+
+/// Building `submit` call when you have direct access to the target chain runtime, where the
+/// light client may be bootstrapped through the `pallet-sudo`.
+pub struct DirectSudoSubmitBeaconUpdateCallBuilder<P, R> {
+	_phantom: PhantomData<(P, R)>,
+}
+
+impl<P, R> SubmitBeaconUpdateCallBuilder<P> for DirectSudoSubmitBeaconUpdateCallBuilder<P, R>
+where
+	P: SubstrateBeaconSyncPipeline<CheckpointUpdate = CheckpointUpdate, Update = Update>,
+	R: EthereumClientConfig + pallet_sudo::Config + Send + Sync,
+	<R as pallet_sudo::Config>::RuntimeCall: From<EthereumClientCall<R>>,
+	CallOf<P::TargetChain>: From<EthereumClientCall<R>> + From<pallet_sudo::Call<R>>,
+{
+	fn build_submit_update_call(update: Update) -> CallOf<P::TargetChain> {
+		EthereumClientCall::<R>::submit { update: Box::new(update) }.into()
+	}
+
+	fn build_force_checkpoint_call(update: CheckpointUpdate) -> Option<CallOf<P::TargetChain>> {
+		let call = EthereumClientCall::<R>::force_checkpoint { update: Box::new(update) };
+		Some(pallet_sudo::Call::<R>::sudo { call: Box::new(call.into()) }.into())
+	}
+}
@@ -0,0 +1,163 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ethereum light client target.
+
+use crate::{
+	beacon::{SubmitBeaconUpdateCallBuilder, SubstrateBeaconSyncPipeline},
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use beacon_relay::beacon_loop::{LightClientState, TargetClient};
+use bp_runtime::{storage_map_final_key, storage_value_key};
+use frame_support::Identity;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Client, Error as SubstrateError, TransactionEra,
+	TransactionTracker, UnsignedTransaction,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use snowbridge_beacon_primitives::CompactBeaconState;
+use sp_core::{Pair, H256};
+
+/// Substrate client as Ethereum light client target.
+pub struct BeaconTarget<P: SubstrateBeaconSyncPipeline, TargetClnt> {
+	target_client: TargetClnt,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+}
+
+impl<P: SubstrateBeaconSyncPipeline, TargetClnt: Client<P::TargetChain>>
+	BeaconTarget<P, TargetClnt>
+{
+	/// Creates new beacon target client.
+	pub fn new(
+		target_client: TargetClnt,
+		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	) -> Self {
+		BeaconTarget { target_client, transaction_params }
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn target_client(&self) -> &TargetClnt {
+		&self.target_client
+	}
+}
+
+impl<P: SubstrateBeaconSyncPipeline, TargetClnt: Clone> Clone for BeaconTarget<P, TargetClnt> {
+	fn clone(&self) -> Self {
+		BeaconTarget {
+			target_client: self.target_client.clone(),
+			transaction_params: self.transaction_params.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeaconSyncPipeline, TargetClnt: Client<P::TargetChain>> RelayClient
+	for BeaconTarget<P, TargetClnt>
+{
+	type Error = SubstrateError;
+
+	async fn reconnect(&mut self) -> Result<(), SubstrateError> {
+		self.target_client.reconnect().await
+	}
+}
+
+#[async_trait]
+impl<P, TargetClnt> TargetClient<P> for BeaconTarget<P, TargetClnt>
+where
+	P: SubstrateBeaconSyncPipeline,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	type TransactionTracker = TransactionTracker<P::TargetChain, TargetClnt>;
+
+	async fn light_client_state(&self) -> Result<Option<LightClientState>, Self::Error> {
+		let at_block = self.target_client.best_finalized_header_hash().await?;
+
+		// the light client is initialized when it has the latest finalized header
+		let storage_key =
+			storage_value_key(P::ETHEREUM_CLIENT_PALLET_NAME, "LatestFinalizedBlockRoot");
+		let finalized_block_root: Option<H256> =
+			self.target_client.storage_value(at_block, storage_key).await?;
+		let finalized_block_root = match finalized_block_root {
+			Some(finalized_block_root) if finalized_block_root != H256::zero() =>
+				finalized_block_root,
+			_ => return Ok(None),
+		};
+
+		let storage_key = storage_map_final_key::<Identity>(
+			P::ETHEREUM_CLIENT_PALLET_NAME,
+			"FinalizedBeaconState",
+			finalized_block_root.as_bytes(),
+		);
+		let finalized_state: Option<CompactBeaconState> =
+			self.target_client.storage_value(at_block, storage_key).await?;
+		let finalized_state = match finalized_state {
+			Some(finalized_state) => finalized_state,
+			None => return Ok(None),
+		};
+
+		// we only care whether the next sync committee is known, so there's no need to decode it
+		let storage_key = storage_value_key(P::ETHEREUM_CLIENT_PALLET_NAME, "NextSyncCommittee");
+		let has_next_sync_committee =
+			self.target_client.raw_storage_value(at_block, storage_key).await?.is_some();
+
+		Ok(Some(LightClientState { finalized_slot: finalized_state.slot, has_next_sync_committee }))
+	}
+
+	async fn submit_update(
+		&self,
+		update: P::Update,
+	) -> Result<Self::TransactionTracker, Self::Error> {
+		self.submit_call(P::SubmitUpdateCallBuilder::build_submit_update_call(update))
+			.await
+	}
+
+	async fn submit_checkpoint(
+		&self,
+		update: P::CheckpointUpdate,
+	) -> Result<Option<Self::TransactionTracker>, Self::Error> {
+		match P::SubmitUpdateCallBuilder::build_force_checkpoint_call(update) {
+			Some(call) => self.submit_call(call).await.map(Some),
+			None => Ok(None),
+		}
+	}
+}
+
+impl<P, TargetClnt> BeaconTarget<P, TargetClnt>
+where
+	P: SubstrateBeaconSyncPipeline,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	/// Sign and submit call to the target chain.
+	async fn submit_call(
+		&self,
+		call: CallOf<P::TargetChain>,
+	) -> Result<TransactionTracker<P::TargetChain, TargetClnt>, SubstrateError> {
+		let transaction_params = self.transaction_params.clone();
+		self.target_client
+			.submit_and_watch_signed_extrinsic(
+				&transaction_params.signer,
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
+				},
+			)
+			.await
+	}
+}
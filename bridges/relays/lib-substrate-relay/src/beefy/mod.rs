@@ -0,0 +1,214 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and functions intended to ease adding of new Substrate -> Substrate BEEFY finality
+//! synchronization pipelines.
+
+pub mod source;
+pub mod target;
+
+use crate::{
+	beefy::{source::BeefySource, target::BeefyTarget},
+	TransactionParams,
+};
+
+use beefy_relay::{beefy_loop::BeefySyncParams, BeefySyncPipeline};
+use bp_beefy::{
+	BeefyAuthorityIdOf, BeefyAuthoritySetOf, BeefyCommitmentHasher, BeefyMmrLeafExtraOf,
+	BeefyMmrLeafOf, BeefySignedCommitmentOf, BeefyValidatorSignatureOf, ChainWithBeefy, MmrHashOf,
+	MmrProofOf,
+};
+use codec::Decode;
+use frame_support::{CloneNoBound, DebugNoBound};
+use pallet_beefy::{Call as BeefyCall, Config as BeefyConfig};
+use pallet_bridge_beefy::{Call as BridgeBeefyCall, Config as BridgeBeefyConfig};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, ChainWithTransactions, Client, HashOf,
+};
+use relay_utils::metrics::MetricsParams;
+use sp_consensus_beefy::DoubleVotingProof;
+use sp_core::Pair;
+use sp_runtime::{
+	traits::{Block, Header},
+	RuntimeAppPublic,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Substrate -> Substrate BEEFY finality synchronization pipeline.
+pub trait SubstrateBeefySyncPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Chain with BEEFY finality, whose commitments we are relaying.
+	type SourceChain: ChainWithTransactions
+		+ ChainWithBeefy<
+			AuthorityId: Send + Sync + RuntimeAppPublic<Signature: Send + Sync>,
+			MmrHash: Send + Sync,
+			BeefyMmrLeafExtra: Send + Sync,
+		>;
+	/// Chain, where the BEEFY light client is deployed.
+	type TargetChain: ChainWithTransactions;
+
+	/// Name of the `pallet-bridge-beefy` pallet at the target chain runtime.
+	const BRIDGE_BEEFY_PALLET_NAME: &'static str;
+
+	/// Proof of BEEFY validator key ownership at the source chain.
+	type KeyOwnerProof: Decode + Send;
+
+	/// How submit commitment call is built?
+	type SubmitCommitmentCallBuilder: SubmitBeefyCommitmentCallBuilder<Self>;
+	/// How report double voting call is built?
+	type ReportDoubleVotingCallBuilder: ReportBeefyDoubleVotingCallBuilder<Self>;
+}
+
+/// Everything that is required to import a BEEFY commitment into the `pallet-bridge-beefy`.
+#[derive(CloneNoBound, DebugNoBound)]
+pub struct BeefyCommitmentProof<C: ChainWithBeefy> {
+	/// Signed commitment.
+	pub commitment: BeefySignedCommitmentOf<C>,
+	/// Validator set, that has signed the commitment.
+	pub validator_set: BeefyAuthoritySetOf<C>,
+	/// MMR leaf of the commitment block.
+	pub mmr_leaf: BeefyMmrLeafOf<C>,
+	/// Proof of the MMR leaf against the MMR root from the commitment payload.
+	pub mmr_proof: MmrProofOf<C>,
+}
+
+/// Proof of BEEFY double voting at the given chain.
+pub type BeefyDoubleVotingProofOf<C> =
+	DoubleVotingProof<BlockNumberOf<C>, BeefyAuthorityIdOf<C>, BeefyValidatorSignatureOf<C>>;
+
+/// Adapter that allows a `SubstrateBeefySyncPipeline` to act as a `BeefySyncPipeline`.
+#[derive(Clone, Debug)]
+pub struct BeefySyncPipelineAdapter<P: SubstrateBeefySyncPipeline> {
+	_phantom: PhantomData<P>,
+}
+
+impl<P: SubstrateBeefySyncPipeline> BeefySyncPipeline for BeefySyncPipelineAdapter<P> {
+	type SourceChain = P::SourceChain;
+	type TargetChain = P::TargetChain;
+
+	type CommitmentHasher = BeefyCommitmentHasher<P::SourceChain>;
+	type AuthorityId = BeefyAuthorityIdOf<P::SourceChain>;
+	type Signature = BeefyValidatorSignatureOf<P::SourceChain>;
+	type CommitmentProof = BeefyCommitmentProof<P::SourceChain>;
+}
+
+/// Different ways of building `submit_commitment` calls.
+pub trait SubmitBeefyCommitmentCallBuilder<P: SubstrateBeefySyncPipeline>:
+	'static + Send + Sync
+{
+	/// Given commitment proof, build call of `submit_commitment` function of the BEEFY light
+	/// client pallet at the target chain.
+	fn build_submit_commitment_call(
+		proof: BeefyCommitmentProof<P::SourceChain>,
+	) -> CallOf<P::TargetChain>;
+}
+
+/// Building `submit_commitment` call when you have direct access to the target chain runtime.
+pub struct DirectSubmitBeefyCommitmentCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitBeefyCommitmentCallBuilder<P>
+	for DirectSubmitBeefyCommitmentCallBuilder<P, R, I>
+where
+	P: SubstrateBeefySyncPipeline,
+	R: BridgeBeefyConfig<I> + Send + Sync,
+	I: 'static + Send + Sync,
+	R::BridgedChain: ChainWithBeefy<
+		BlockNumber = BlockNumberOf<P::SourceChain>,
+		Hash = HashOf<P::SourceChain>,
+		AuthorityId = BeefyAuthorityIdOf<P::SourceChain>,
+		MmrHash = MmrHashOf<P::SourceChain>,
+		BeefyMmrLeafExtra = BeefyMmrLeafExtraOf<P::SourceChain>,
+	>,
+	CallOf<P::TargetChain>: From<BridgeBeefyCall<R, I>>,
+{
+	fn build_submit_commitment_call(
+		proof: BeefyCommitmentProof<P::SourceChain>,
+	) -> CallOf<P::TargetChain> {
+		BridgeBeefyCall::<R, I>::submit_commitment {
+			commitment: proof.commitment,
+			validator_set: proof.validator_set,
+			mmr_leaf: Box::new(proof.mmr_leaf),
+			mmr_proof: proof.mmr_proof,
+		}
+		.into()
+	}
+}
+
+/// Different ways of building `report_double_voting` calls.
+pub trait ReportBeefyDoubleVotingCallBuilder<P: SubstrateBeefySyncPipeline>:
+	'static + Send + Sync
+{
+	/// Build a `report_double_voting` call to be executed on the source chain.
+	fn build_report_double_voting_call(
+		proof: BeefyDoubleVotingProofOf<P::SourceChain>,
+		key_owner_proof: P::KeyOwnerProof,
+	) -> CallOf<P::SourceChain>;
+}
+
+/// Building `report_double_voting` call when you have direct access to the source chain runtime.
+pub struct DirectReportBeefyDoubleVotingCallBuilder<P, R> {
+	_phantom: PhantomData<(P, R)>,
+}
+
+impl<P, R> ReportBeefyDoubleVotingCallBuilder<P> for DirectReportBeefyDoubleVotingCallBuilder<P, R>
+where
+	P: SubstrateBeefySyncPipeline,
+	R: BeefyConfig<BeefyId = BeefyAuthorityIdOf<P::SourceChain>, KeyOwnerProof = P::KeyOwnerProof>
+		+ Send
+		+ Sync,
+	<R::Block as Block>::Header: Header<Number = BlockNumberOf<P::SourceChain>>,
+	CallOf<P::SourceChain>: From<BeefyCall<R>>,
+{
+	fn build_report_double_voting_call(
+		proof: BeefyDoubleVotingProofOf<P::SourceChain>,
+		key_owner_proof: P::KeyOwnerProof,
+	) -> CallOf<P::SourceChain> {
+		BeefyCall::<R>::report_double_voting {
+			equivocation_proof: Box::new(proof),
+			key_owner_proof,
+		}
+		.into()
+	}
+}
+
+/// Run Substrate-to-Substrate BEEFY finality relay.
+///
+/// Justifications of `equivocation_source_clients` nodes are only used to detect double votes.
+pub async fn run<P: SubstrateBeefySyncPipeline, SourceClnt: Client<P::SourceChain>>(
+	source_client: SourceClnt,
+	equivocation_source_clients: Vec<SourceClnt>,
+	target_client: impl Client<P::TargetChain>,
+	source_transaction_params: TransactionParams<AccountKeyPairOf<P::SourceChain>>,
+	target_transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	sync_params: BeefySyncParams,
+	metrics_params: MetricsParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	beefy_relay::beefy_loop::run::<BeefySyncPipelineAdapter<P>>(
+		BeefySource::<P, _>::new(source_client, source_transaction_params)
+			.with_equivocation_source_clients(equivocation_source_clients),
+		BeefyTarget::<P, _>::new(target_client, target_transaction_params),
+		sync_params,
+		metrics_params,
+		futures::future::pending(),
+	)
+	.await
+	.map_err(|e| anyhow::format_err!("{}", e))
+}
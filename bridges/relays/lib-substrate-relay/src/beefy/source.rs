@@ -0,0 +1,287 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate node as BEEFY commitments source.
+
+use crate::{
+	beefy::{
+		BeefyCommitmentProof, BeefyDoubleVotingProofOf, BeefySyncPipelineAdapter,
+		ReportBeefyDoubleVotingCallBuilder, SubstrateBeefySyncPipeline,
+	},
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use beefy_relay::beefy_loop::SourceClient;
+use bp_beefy::{
+	BeefyAuthoritySetOf, BeefyMmrLeafOf, BeefySignedCommitmentOf, BeefyValidatorSignatureOf,
+	MmrProofOf,
+};
+use codec::Decode;
+use futures::{
+	future::ready,
+	stream::{self, Stream, StreamExt},
+};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, BlockWithJustification, Chain, Client,
+	Error as SubstrateError, TransactionEra, TransactionTracker, UnsignedTransaction,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use sp_consensus_beefy::{OpaqueKeyOwnershipProof, VersionedFinalityProof, BEEFY_ENGINE_ID};
+use sp_core::Pair;
+use sp_mmr_primitives::{EncodableOpaqueLeaf, Error as MmrError};
+use std::pin::Pin;
+
+/// Name of the runtime method that returns the active BEEFY validator set.
+const BEEFY_API_VALIDATOR_SET_METHOD: &str = "BeefyApi_validator_set";
+/// Name of the runtime method that generates the BEEFY key ownership proof.
+const BEEFY_API_GENERATE_KEY_OWNERSHIP_PROOF_METHOD: &str = "BeefyApi_generate_key_ownership_proof";
+/// Name of the runtime method that generates the MMR proof.
+const MMR_API_GENERATE_PROOF_METHOD: &str = "MmrApi_generate_proof";
+
+/// Stream of BEEFY signed commitments of the source chain.
+pub type SubstrateCommitmentsStream<P> = Pin<
+	Box<
+		dyn Stream<Item = BeefySignedCommitmentOf<<P as SubstrateBeefySyncPipeline>::SourceChain>>
+			+ Send,
+	>,
+>;
+
+/// Substrate node as BEEFY commitments source.
+///
+/// Every node only sees justifications, that it has finalized itself. To detect more double votes,
+/// the source may subscribe to justifications of additional nodes of the same chain. Commitments
+/// of all nodes are merged into a single stream.
+pub struct BeefySource<P: SubstrateBeefySyncPipeline, SourceClnt> {
+	source_client: SourceClnt,
+	equivocation_source_clients: Vec<SourceClnt>,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::SourceChain>>,
+}
+
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Client<P::SourceChain>> BeefySource<P, SourceClnt> {
+	/// Creates new BEEFY source client.
+	pub fn new(
+		source_client: SourceClnt,
+		transaction_params: TransactionParams<AccountKeyPairOf<P::SourceChain>>,
+	) -> Self {
+		BeefySource { source_client, equivocation_source_clients: Vec::new(), transaction_params }
+	}
+
+	/// Additionally subscribe to commitments of given nodes to look for double votes.
+	pub fn with_equivocation_source_clients(mut self, clients: Vec<SourceClnt>) -> Self {
+		self.equivocation_source_clients = clients;
+		self
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn source_client(&self) -> &SourceClnt {
+		&self.source_client
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Clone> Clone for BeefySource<P, SourceClnt> {
+	fn clone(&self) -> Self {
+		BeefySource {
+			source_client: self.source_client.clone(),
+			equivocation_source_clients: self.equivocation_source_clients.clone(),
+			transaction_params: self.transaction_params.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Client<P::SourceChain>> RelayClient
+	for BeefySource<P, SourceClnt>
+{
+	type Error = SubstrateError;
+
+	async fn reconnect(&mut self) -> Result<(), SubstrateError> {
+		self.source_client.reconnect().await?;
+		for client in &mut self.equivocation_source_clients {
+			client.reconnect().await?;
+		}
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl<P, SourceClnt> SourceClient<BeefySyncPipelineAdapter<P>> for BeefySource<P, SourceClnt>
+where
+	P: SubstrateBeefySyncPipeline,
+	SourceClnt: Client<P::SourceChain>,
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+{
+	type CommitmentsStream = SubstrateCommitmentsStream<P>;
+	type TransactionTracker = TransactionTracker<P::SourceChain, SourceClnt>;
+
+	async fn commitments(&self) -> Result<Self::CommitmentsStream, Self::Error> {
+		let mut streams = Vec::with_capacity(1 + self.equivocation_source_clients.len());
+		for client in std::iter::once(&self.source_client).chain(&self.equivocation_source_clients)
+		{
+			let justifications = client.subscribe_beefy_finality_justifications().await?;
+			streams.push(
+				justifications
+					.filter_map(|justification| ready(decode_justification::<P>(&justification)))
+					.map(Some)
+					// the merged stream ends as soon as any subscription ends
+					.chain(stream::once(ready(None)))
+					.boxed(),
+			);
+		}
+
+		Ok(Box::pin(
+			stream::select_all(streams)
+				.take_while(|commitment| ready(commitment.is_some()))
+				.filter_map(ready),
+		))
+	}
+
+	async fn best_finalized_block_number(
+		&self,
+	) -> Result<BlockNumberOf<P::SourceChain>, Self::Error> {
+		self.source_client.best_finalized_header_number().await
+	}
+
+	async fn stored_commitment(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<BeefySignedCommitmentOf<P::SourceChain>>, Self::Error> {
+		let at = self.source_client.header_hash_by_number(block_number).await?;
+		let block = self.source_client.block_by_hash(at).await?;
+		Ok(block
+			.justification(BEEFY_ENGINE_ID)
+			.and_then(|justification| decode_justification::<P>(justification)))
+	}
+
+	async fn validator_set(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<BeefyAuthoritySetOf<P::SourceChain>, Self::Error> {
+		let at = self.source_client.header_hash_by_number(block_number).await?;
+		let validator_set: Option<BeefyAuthoritySetOf<P::SourceChain>> = self
+			.source_client
+			.state_call(at, BEEFY_API_VALIDATOR_SET_METHOD.into(), ())
+			.await?;
+		validator_set.ok_or_else(|| {
+			SubstrateError::Custom(format!(
+				"BEEFY validator set is unknown at {} block {block_number}",
+				P::SourceChain::NAME,
+			))
+		})
+	}
+
+	async fn prepare_commitment_proof(
+		&self,
+		commitment: BeefySignedCommitmentOf<P::SourceChain>,
+		validator_set: BeefyAuthoritySetOf<P::SourceChain>,
+	) -> Result<BeefyCommitmentProof<P::SourceChain>, Self::Error> {
+		// the leaf of the commitment block is the latest leaf in the MMR, whose root is signed
+		let block_number = commitment.commitment.block_number;
+		let at = self.source_client.header_hash_by_number(block_number).await?;
+		let mmr_proof: Result<(Vec<EncodableOpaqueLeaf>, MmrProofOf<P::SourceChain>), MmrError> =
+			self.source_client
+				.state_call(
+					at,
+					MMR_API_GENERATE_PROOF_METHOD.into(),
+					(vec![block_number], Some(block_number)),
+				)
+				.await?;
+		let (mmr_leaves, mmr_proof) = mmr_proof.map_err(|e| {
+			SubstrateError::Custom(format!(
+				"Failed to generate MMR proof for {} block {block_number}: {e:?}",
+				P::SourceChain::NAME,
+			))
+		})?;
+		let mmr_leaf = mmr_leaves
+			.into_iter()
+			.next()
+			.and_then(|mmr_leaf| {
+				BeefyMmrLeafOf::<P::SourceChain>::decode(&mut &mmr_leaf.0[..]).ok()
+			})
+			.ok_or_else(|| {
+				SubstrateError::Custom(format!(
+					"Failed to decode MMR leaf of {} block {block_number}",
+					P::SourceChain::NAME,
+				))
+			})?;
+
+		Ok(BeefyCommitmentProof { commitment, validator_set, mmr_leaf, mmr_proof })
+	}
+
+	async fn report_double_voting(
+		&self,
+		proof: BeefyDoubleVotingProofOf<P::SourceChain>,
+	) -> Result<Self::TransactionTracker, Self::Error> {
+		let block_number = *proof.round_number();
+		let at = self.source_client.header_hash_by_number(block_number).await?;
+		let key_owner_proof: Option<OpaqueKeyOwnershipProof> = self
+			.source_client
+			.state_call(
+				at,
+				BEEFY_API_GENERATE_KEY_OWNERSHIP_PROOF_METHOD.into(),
+				(proof.set_id(), proof.offender_id().clone()),
+			)
+			.await?;
+		let key_owner_proof = key_owner_proof
+			.and_then(|key_owner_proof| key_owner_proof.decode::<P::KeyOwnerProof>())
+			.ok_or_else(|| {
+				SubstrateError::Custom(format!(
+					"Couldn't get BEEFY key ownership proof from {} at block {block_number} \
+					for offender: {:?}, set_id: {}",
+					P::SourceChain::NAME,
+					proof.offender_id(),
+					proof.set_id(),
+				))
+			})?;
+
+		let transaction_params = self.transaction_params.clone();
+		let call = P::ReportDoubleVotingCallBuilder::build_report_double_voting_call(
+			proof,
+			key_owner_proof,
+		);
+		self.source_client
+			.submit_and_watch_signed_extrinsic(
+				&transaction_params.signer,
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
+				},
+			)
+			.await
+	}
+}
+
+/// Decode BEEFY justification of the source chain.
+fn decode_justification<P: SubstrateBeefySyncPipeline>(
+	justification: &[u8],
+) -> Option<BeefySignedCommitmentOf<P::SourceChain>> {
+	let decoded_justification = VersionedFinalityProof::<
+		BlockNumberOf<P::SourceChain>,
+		BeefyValidatorSignatureOf<P::SourceChain>,
+	>::decode(&mut &justification[..]);
+	match decoded_justification {
+		Ok(VersionedFinalityProof::V1(commitment)) => Some(commitment),
+		Err(e) => {
+			log::error!(
+				target: "bridge",
+				"Failed to decode {} BEEFY justification: {:?}",
+				P::SourceChain::NAME,
+				e,
+			);
+			None
+		},
+	}
+}
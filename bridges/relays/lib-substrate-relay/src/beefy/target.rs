@@ -0,0 +1,133 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate node as BEEFY light client target.
+
+use crate::{
+	beefy::{
+		BeefyCommitmentProof, BeefySyncPipelineAdapter, SubmitBeefyCommitmentCallBuilder,
+		SubstrateBeefySyncPipeline,
+	},
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use beefy_relay::beefy_loop::{LightClientState, TargetClient};
+use bp_beefy::BeefyAuthoritySetInfoOf;
+use bp_runtime::storage_value_key;
+use pallet_bridge_beefy::ImportedCommitmentsInfoData;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Client, Error as SubstrateError, TransactionEra,
+	TransactionTracker, UnsignedTransaction,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use sp_core::Pair;
+
+/// Substrate node as BEEFY light client target.
+pub struct BeefyTarget<P: SubstrateBeefySyncPipeline, TargetClnt> {
+	target_client: TargetClnt,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+}
+
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Client<P::TargetChain>> BeefyTarget<P, TargetClnt> {
+	/// Creates new BEEFY target client.
+	pub fn new(
+		target_client: TargetClnt,
+		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	) -> Self {
+		BeefyTarget { target_client, transaction_params }
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn target_client(&self) -> &TargetClnt {
+		&self.target_client
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Clone> Clone for BeefyTarget<P, TargetClnt> {
+	fn clone(&self) -> Self {
+		BeefyTarget {
+			target_client: self.target_client.clone(),
+			transaction_params: self.transaction_params.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Client<P::TargetChain>> RelayClient
+	for BeefyTarget<P, TargetClnt>
+{
+	type Error = SubstrateError;
+
+	async fn reconnect(&mut self) -> Result<(), SubstrateError> {
+		self.target_client.reconnect().await
+	}
+}
+
+#[async_trait]
+impl<P, TargetClnt> TargetClient<BeefySyncPipelineAdapter<P>> for BeefyTarget<P, TargetClnt>
+where
+	P: SubstrateBeefySyncPipeline,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	type TransactionTracker = TransactionTracker<P::TargetChain, TargetClnt>;
+
+	async fn light_client_state(
+		&self,
+	) -> Result<Option<LightClientState<BlockNumberOf<P::SourceChain>>>, Self::Error> {
+		let at_block = self.target_client.best_finalized_header_hash().await?;
+
+		// the light client is initialized when it knows the best imported commitment
+		let storage_key = storage_value_key(P::BRIDGE_BEEFY_PALLET_NAME, "ImportedCommitmentsInfo");
+		let commitments_info: Option<ImportedCommitmentsInfoData<BlockNumberOf<P::SourceChain>>> =
+			self.target_client.storage_value(at_block, storage_key).await?;
+		let commitments_info = match commitments_info {
+			Some(commitments_info) => commitments_info,
+			None => return Ok(None),
+		};
+
+		let storage_key = storage_value_key(P::BRIDGE_BEEFY_PALLET_NAME, "CurrentAuthoritySetInfo");
+		let authority_set_info: Option<BeefyAuthoritySetInfoOf<P::SourceChain>> =
+			self.target_client.storage_value(at_block, storage_key).await?;
+		let authority_set_info = match authority_set_info {
+			Some(authority_set_info) => authority_set_info,
+			None => return Ok(None),
+		};
+
+		Ok(Some(LightClientState {
+			best_block_number: commitments_info.best_block_number,
+			validator_set_id: authority_set_info.id,
+		}))
+	}
+
+	async fn submit_commitment(
+		&self,
+		proof: BeefyCommitmentProof<P::SourceChain>,
+	) -> Result<Self::TransactionTracker, Self::Error> {
+		let transaction_params = self.transaction_params.clone();
+		let call = P::SubmitCommitmentCallBuilder::build_submit_commitment_call(proof);
+		self.target_client
+			.submit_and_watch_signed_extrinsic(
+				&transaction_params.signer,
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
+				},
+			)
+			.await
+	}
+}
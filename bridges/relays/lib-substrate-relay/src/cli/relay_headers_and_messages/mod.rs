@@ -48,7 +48,7 @@ use crate::{
 use bp_runtime::BalanceOf;
 use relay_substrate_client::{
 	AccountIdOf, AccountKeyPairOf, Chain, ChainWithBalances, ChainWithMessages,
-	ChainWithRuntimeVersion, ChainWithTransactions,
+	ChainWithRuntimeVersion, ChainWithTransactions, Client,
 };
 use relay_utils::metrics::MetricsParams;
 use sp_core::Pair;
@@ -87,13 +87,15 @@ impl HeadersAndMessagesSharedParams {
 pub struct Full2WayBridgeCommonParams<
 	Left: ChainWithTransactions + ChainWithRuntimeVersion,
 	Right: ChainWithTransactions + ChainWithRuntimeVersion,
+	LeftClnt = DefaultClient<Left>,
+	RightClnt = DefaultClient<Right>,
 > {
 	/// Shared parameters.
 	pub shared: HeadersAndMessagesSharedParams,
 	/// Parameters of the left chain.
-	pub left: BridgeEndCommonParams<Left>,
+	pub left: BridgeEndCommonParams<Left, LeftClnt>,
 	/// Parameters of the right chain.
-	pub right: BridgeEndCommonParams<Right>,
+	pub right: BridgeEndCommonParams<Right, RightClnt>,
 
 	/// Common metric parameters.
 	pub metrics_params: MetricsParams,
@@ -102,13 +104,15 @@ pub struct Full2WayBridgeCommonParams<
 impl<
 		Left: ChainWithTransactions + ChainWithRuntimeVersion,
 		Right: ChainWithTransactions + ChainWithRuntimeVersion,
-	> Full2WayBridgeCommonParams<Left, Right>
+		LeftClnt: Client<Left>,
+		RightClnt: Client<Right>,
+	> Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt>
 {
 	/// Creates new bridge parameters from its components.
 	pub fn new<L2R: MessagesCliBridge<Source = Left, Target = Right>>(
 		shared: HeadersAndMessagesSharedParams,
-		left: BridgeEndCommonParams<Left>,
-		right: BridgeEndCommonParams<Right>,
+		left: BridgeEndCommonParams<Left, LeftClnt>,
+		right: BridgeEndCommonParams<Right, RightClnt>,
 	) -> anyhow::Result<Self> {
 		// Create metrics registry.
 		let metrics_params = shared.prometheus_params.clone().into_metrics_params()?;
@@ -119,9 +123,12 @@ impl<
 }
 
 /// Parameters that are associated with one side of the bridge.
-pub struct BridgeEndCommonParams<Chain: ChainWithTransactions + ChainWithRuntimeVersion> {
+pub struct BridgeEndCommonParams<
+	Chain: ChainWithTransactions + ChainWithRuntimeVersion,
+	Clnt = DefaultClient<Chain>,
+> {
 	/// Chain client.
-	pub client: DefaultClient<Chain>,
+	pub client: Clnt,
 	/// Params used for sending transactions to the chain.
 	pub tx_params: TransactionParams<AccountKeyPairOf<Chain>>,
 	/// Accounts, which balances are exposed as metrics by the relay process.
@@ -134,9 +141,11 @@ pub struct FullBridge<
 	Source: ChainWithTransactions + ChainWithRuntimeVersion,
 	Target: ChainWithTransactions + ChainWithRuntimeVersion,
 	Bridge: MessagesCliBridge<Source = Source, Target = Target>,
+	SourceClnt = DefaultClient<Source>,
+	TargetClnt = DefaultClient<Target>,
 > {
-	source: &'a mut BridgeEndCommonParams<Source>,
-	target: &'a mut BridgeEndCommonParams<Target>,
+	source: &'a mut BridgeEndCommonParams<Source, SourceClnt>,
+	target: &'a mut BridgeEndCommonParams<Target, TargetClnt>,
 	metrics_params: &'a MetricsParams,
 	_phantom_data: PhantomData<Bridge>,
 }
@@ -146,7 +155,9 @@ impl<
 		Source: ChainWithTransactions + ChainWithRuntimeVersion,
 		Target: ChainWithTransactions + ChainWithRuntimeVersion,
 		Bridge: MessagesCliBridge<Source = Source, Target = Target>,
-	> FullBridge<'a, Source, Target, Bridge>
+		SourceClnt: Client<Source>,
+		TargetClnt: Client<Target>,
+	> FullBridge<'a, Source, Target, Bridge, SourceClnt, TargetClnt>
 where
 	AccountIdOf<Source>: From<<AccountKeyPairOf<Source> as Pair>::Public>,
 	AccountIdOf<Target>: From<<AccountKeyPairOf<Target> as Pair>::Public>,
@@ -154,8 +165,8 @@ where
 {
 	/// Construct complex relay given it components.
 	fn new(
-		source: &'a mut BridgeEndCommonParams<Source>,
-		target: &'a mut BridgeEndCommonParams<Target>,
+		source: &'a mut BridgeEndCommonParams<Source, SourceClnt>,
+		target: &'a mut BridgeEndCommonParams<Target, TargetClnt>,
 		metrics_params: &'a MetricsParams,
 	) -> Self {
 		Self { source, target, metrics_params, _phantom_data: Default::default() }
@@ -168,7 +179,7 @@ where
 		target_to_source_headers_relay: Arc<dyn OnDemandRelay<Target, Source>>,
 		lane_id: MessagesLaneIdOf<Bridge>,
 		maybe_limits: Option<MessagesRelayLimits>,
-	) -> MessagesRelayParams<Bridge::MessagesLane, DefaultClient<Source>, DefaultClient<Target>> {
+	) -> MessagesRelayParams<Bridge::MessagesLane, SourceClnt, TargetClnt> {
 		MessagesRelayParams {
 			source_client: self.source.client.clone(),
 			source_transaction_params: self.source.tx_params.clone(),
@@ -197,12 +208,20 @@ pub trait Full2WayBridgeBase: Sized + Send + Sync {
 	type Left: ChainWithTransactions + ChainWithRuntimeVersion;
 	/// The right destination chain (it can be a relay or a parachain).
 	type Right: ChainWithTransactions + ChainWithRuntimeVersion;
+	/// Client of the left chain.
+	type LeftClient: Client<Self::Left>;
+	/// Client of the right chain.
+	type RightClient: Client<Self::Right>;
 
 	/// Reference to common relay parameters.
-	fn common(&self) -> &Full2WayBridgeCommonParams<Self::Left, Self::Right>;
+	fn common(
+		&self,
+	) -> &Full2WayBridgeCommonParams<Self::Left, Self::Right, Self::LeftClient, Self::RightClient>;
 
 	/// Mutable reference to common relay parameters.
-	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Self::Left, Self::Right>;
+	fn mut_common(
+		&mut self,
+	) -> &mut Full2WayBridgeCommonParams<Self::Left, Self::Right, Self::LeftClient, Self::RightClient>;
 
 	/// Start on-demand headers relays.
 	async fn start_on_demand_headers_relayers(
@@ -251,9 +270,17 @@ where
 	fn mut_base(&mut self) -> &mut Self::Base;
 
 	/// Creates and returns Left to Right complex relay.
-	fn left_to_right(&mut self) -> FullBridge<Self::Left, Self::Right, Self::L2R> {
+	fn left_to_right(
+		&mut self,
+	) -> FullBridge<
+		Self::Left,
+		Self::Right,
+		Self::L2R,
+		<Self::Base as Full2WayBridgeBase>::LeftClient,
+		<Self::Base as Full2WayBridgeBase>::RightClient,
+	> {
 		let common = self.mut_base().mut_common();
-		FullBridge::<_, _, Self::L2R>::new(
+		FullBridge::<_, _, Self::L2R, _, _>::new(
 			&mut common.left,
 			&mut common.right,
 			&common.metrics_params,
@@ -261,9 +288,17 @@ where
 	}
 
 	/// Creates and returns Right to Left complex relay.
-	fn right_to_left(&mut self) -> FullBridge<Self::Right, Self::Left, Self::R2L> {
+	fn right_to_left(
+		&mut self,
+	) -> FullBridge<
+		Self::Right,
+		Self::Left,
+		Self::R2L,
+		<Self::Base as Full2WayBridgeBase>::RightClient,
+		<Self::Base as Full2WayBridgeBase>::LeftClient,
+	> {
 		let common = self.mut_base().mut_common();
-		FullBridge::<_, _, Self::R2L>::new(
+		FullBridge::<_, _, Self::R2L, _, _>::new(
 			&mut common.right,
 			&mut common.left,
 			&common.metrics_params,
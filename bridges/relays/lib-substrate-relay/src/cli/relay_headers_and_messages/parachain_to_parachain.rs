@@ -45,17 +45,25 @@ use sp_core::Pair;
 pub struct ParachainToParachainBridge<
 	L2R: MessagesCliBridge + ParachainToRelayHeadersCliBridge,
 	R2L: MessagesCliBridge + ParachainToRelayHeadersCliBridge,
+	LeftClnt = DefaultClient<<R2L as CliBridgeBase>::Target>,
+	RightClnt = DefaultClient<<L2R as CliBridgeBase>::Target>,
+	LeftRelayClnt = DefaultClient<<L2R as ParachainToRelayHeadersCliBridge>::SourceRelay>,
+	RightRelayClnt = DefaultClient<<R2L as ParachainToRelayHeadersCliBridge>::SourceRelay>,
 > where
 	<L2R as CliBridgeBase>::Source: Parachain,
 	<R2L as CliBridgeBase>::Source: Parachain,
 {
 	/// Parameters that are shared by all bridge types.
-	pub common:
-		Full2WayBridgeCommonParams<<R2L as CliBridgeBase>::Target, <L2R as CliBridgeBase>::Target>,
+	pub common: Full2WayBridgeCommonParams<
+		<R2L as CliBridgeBase>::Target,
+		<L2R as CliBridgeBase>::Target,
+		LeftClnt,
+		RightClnt,
+	>,
 	/// Client of the left relay chain.
-	pub left_relay: DefaultClient<<L2R as ParachainToRelayHeadersCliBridge>::SourceRelay>,
+	pub left_relay: LeftRelayClnt,
 	/// Client of the right relay chain.
-	pub right_relay: DefaultClient<<R2L as ParachainToRelayHeadersCliBridge>::SourceRelay>,
+	pub right_relay: RightRelayClnt,
 }
 
 /// Create set of configuration objects specific to parachain-to-parachain relayer.
@@ -142,20 +150,28 @@ impl<
 		R2L: CliBridgeBase<Source = Right, Target = Left>
 			+ MessagesCliBridge
 			+ ParachainToRelayHeadersCliBridge<SourceRelay = RightRelay>,
-	> Full2WayBridgeBase for ParachainToParachainBridge<L2R, R2L>
+		LeftClnt: Client<Left>,
+		RightClnt: Client<Right>,
+		LeftRelayClnt: Client<LeftRelay>,
+		RightRelayClnt: Client<RightRelay>,
+	> Full2WayBridgeBase
+	for ParachainToParachainBridge<L2R, R2L, LeftClnt, RightClnt, LeftRelayClnt, RightRelayClnt>
 where
 	AccountIdOf<Left>: From<<AccountKeyPairOf<Left> as Pair>::Public>,
 	AccountIdOf<Right>: From<<AccountKeyPairOf<Right> as Pair>::Public>,
 {
-	type Params = ParachainToParachainBridge<L2R, R2L>;
+	type Params =
+		ParachainToParachainBridge<L2R, R2L, LeftClnt, RightClnt, LeftRelayClnt, RightRelayClnt>;
 	type Left = Left;
 	type Right = Right;
+	type LeftClient = LeftClnt;
+	type RightClient = RightClnt;
 
-	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right> {
+	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&self.common
 	}
 
-	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Self::Left, Self::Right> {
+	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&mut self.common
 	}
 
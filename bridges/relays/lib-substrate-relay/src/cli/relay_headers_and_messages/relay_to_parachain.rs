@@ -48,14 +48,21 @@ use sp_core::Pair;
 pub struct RelayToParachainBridge<
 	L2R: MessagesCliBridge + RelayToRelayHeadersCliBridge,
 	R2L: MessagesCliBridge + ParachainToRelayHeadersCliBridge,
+	LeftClnt = DefaultClient<<R2L as CliBridgeBase>::Target>,
+	RightClnt = DefaultClient<<L2R as CliBridgeBase>::Target>,
+	RightRelayClnt = DefaultClient<<R2L as ParachainToRelayHeadersCliBridge>::SourceRelay>,
 > where
 	<R2L as CliBridgeBase>::Source: Parachain,
 {
 	/// Parameters that are shared by all bridge types.
-	pub common:
-		Full2WayBridgeCommonParams<<R2L as CliBridgeBase>::Target, <L2R as CliBridgeBase>::Target>,
+	pub common: Full2WayBridgeCommonParams<
+		<R2L as CliBridgeBase>::Target,
+		<L2R as CliBridgeBase>::Target,
+		LeftClnt,
+		RightClnt,
+	>,
 	/// Client of the right relay chain.
-	pub right_relay: DefaultClient<<R2L as ParachainToRelayHeadersCliBridge>::SourceRelay>,
+	pub right_relay: RightRelayClnt,
 }
 
 /// Create set of configuration objects specific to relay-to-parachain relayer.
@@ -133,20 +140,25 @@ impl<
 		R2L: CliBridgeBase<Source = Right, Target = Left>
 			+ MessagesCliBridge
 			+ ParachainToRelayHeadersCliBridge<SourceRelay = RightRelay>,
-	> Full2WayBridgeBase for RelayToParachainBridge<L2R, R2L>
+		LeftClnt: Client<Left>,
+		RightClnt: Client<Right>,
+		RightRelayClnt: Client<RightRelay>,
+	> Full2WayBridgeBase for RelayToParachainBridge<L2R, R2L, LeftClnt, RightClnt, RightRelayClnt>
 where
 	AccountIdOf<Left>: From<<AccountKeyPairOf<Left> as Pair>::Public>,
 	AccountIdOf<Right>: From<<AccountKeyPairOf<Right> as Pair>::Public>,
 {
-	type Params = RelayToParachainBridge<L2R, R2L>;
+	type Params = RelayToParachainBridge<L2R, R2L, LeftClnt, RightClnt, RightRelayClnt>;
 	type Left = Left;
 	type Right = Right;
+	type LeftClient = LeftClnt;
+	type RightClient = RightClnt;
 
-	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right> {
+	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&self.common
 	}
 
-	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Self::Left, Self::Right> {
+	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&mut self.common
 	}
 
@@ -203,3 +215,1272 @@ where
 		))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		cli::{
+			relay_headers_and_messages::{
+				BridgeEndCommonParams, Full2WayBridge, HeadersAndMessagesSharedParams,
+			},
+			PrometheusParams,
+		},
+		finality::SubmitFinalityProofCallBuilder,
+		finality_base::{engine::Grandpa, SubstrateFinalityPipeline},
+		messages::{
+			source::SubstrateMessagesProof, target::SubstrateMessagesDeliveryProof,
+			MessagesRelayLimits, ReceiveMessagesDeliveryProofCallBuilder,
+			ReceiveMessagesProofCallBuilder, SubstrateMessageLane,
+		},
+		parachains::{SubmitParachainHeadsCallBuilder, SubstrateParachainsPipeline},
+		TransactionParams,
+	};
+
+	use bp_header_chain::{
+		justification::{
+			verify_justification, GrandpaJustification, JustificationVerificationContext,
+		},
+		storage_keys::{best_finalized_key, current_authority_set_key},
+		AuthoritySet, StoredHeaderData,
+	};
+	use bp_messages::{
+		source_chain::FromBridgedChainMessagesDeliveryProof,
+		storage_keys::{inbound_lane_data_key, message_key, outbound_lane_data_key},
+		target_chain::FromBridgedChainMessagesProof,
+		DeliveredMessages, InboundLaneData, InboundMessageDetails, LegacyLaneId, MessageNonce,
+		MessagePayload, OutboundLaneData, OutboundMessageDetails, UnrewardedRelayer,
+	};
+	use bp_parachains::{
+		parachain_head_storage_key_at_source, BestParaHeadHash, ImportedParaHeadsKeyProvider,
+		ParaInfo, ParaStoredHeaderData, ParasInfoKeyProvider,
+	};
+	use bp_polkadot_core::{
+		parachains::{ParaHead, ParaHeadsProof, ParaId},
+		AccountInfoStorageMapKeyProvider,
+	};
+	use bp_runtime::{
+		ChainId, HeaderId, StorageDoubleMapKeyProvider, StorageMapKeyProvider, StorageProofChecker,
+		StorageProofError,
+	};
+	use bp_test_utils::{authority_list, make_default_justification, TEST_GRANDPA_SET_ID};
+	use codec::{Decode, Encode};
+	use frame_support::{weights::Weight, Identity};
+	use relay_substrate_client::{
+		simulation::{
+			RuntimeContext, SimulatedAction, SimulatedBlock, SimulatedChain, SimulatedClient,
+			SimulatedRuntime, SimulatedStorage, Simulation, SimulationParticipant,
+			SimulationScript,
+		},
+		Error as SubstrateError, SignParam, SimpleRuntimeVersion, SyncHeader, UnsignedTransaction,
+	};
+	use sp_core::{sr25519, storage::StorageKey, Bytes, H256};
+	use sp_runtime::{
+		traits::{BlakeTwo256, Header as _},
+		transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
+		AccountId32, FixedU128, MultiSignature, StateVersion,
+	};
+	use std::time::Duration;
+
+	const LEFT_GRANDPA_PALLET_NAME: &str = "BridgeLeftGrandpa";
+	const LEFT_MESSAGES_PALLET_NAME: &str = "BridgeLeftMessages";
+	const LEFT_BEST_FINALIZED_METHOD: &str = "LeftFinalityApi_best_finalized";
+	const TO_LEFT_MESSAGE_DETAILS_METHOD: &str = "ToLeftOutboundLaneApi_message_details";
+	const FROM_LEFT_MESSAGE_DETAILS_METHOD: &str = "FromLeftInboundLaneApi_message_details";
+
+	const RIGHT_PARA_ID: u32 = 2000;
+	const RIGHT_MESSAGES_PALLET_NAME: &str = "BridgeRightMessages";
+	const RIGHT_BEST_FINALIZED_METHOD: &str = "RightFinalityApi_best_finalized";
+	const TO_RIGHT_MESSAGE_DETAILS_METHOD: &str = "ToRightOutboundLaneApi_message_details";
+	const FROM_RIGHT_MESSAGE_DETAILS_METHOD: &str = "FromRightInboundLaneApi_message_details";
+
+	const RIGHT_RELAY_PARAS_PALLET_NAME: &str = "Paras";
+	const RIGHT_RELAY_GRANDPA_PALLET_NAME: &str = "BridgeRightRelayGrandpa";
+	const RIGHT_RELAY_PARACHAINS_PALLET_NAME: &str = "BridgeRightRelayParachains";
+	const RIGHT_RELAY_BEST_FINALIZED_METHOD: &str = "RightRelayFinalityApi_best_finalized";
+
+	const TEST_LANE_ID: LegacyLaneId = LegacyLaneId([0, 0, 0, 0]);
+	const MESSAGES_COUNT: MessageNonce = 3;
+	/// Maximal fee multiplier at which the relayer is able to pay for message delivery
+	/// transactions.
+	const MAX_DELIVERY_FEE_MULTIPLIER: FixedU128 = FixedU128::from_u32(2);
+	const FEE_SPIKE_TICKS: u64 = 100;
+	const MAX_TICKS: u64 = 1_000;
+
+	type TestHeader = sp_runtime::generic::Header<u32, BlakeTwo256>;
+	type SignedBlock = sp_runtime::generic::SignedBlock<
+		sp_runtime::generic::Block<TestHeader, sp_runtime::OpaqueExtrinsic>,
+	>;
+
+	#[derive(Clone, Debug, Decode, Encode)]
+	enum TestCall {
+		SubmitFinalityProof(Box<TestHeader>, GrandpaJustification<TestHeader>),
+		SubmitParachainHeads(HeaderId<H256, u32>, Vec<(ParaId, ParaHash)>, ParaHeadsProof),
+		ReceiveMessagesProof(AccountId32, FromBridgedChainMessagesProof<H256, LegacyLaneId>),
+		ReceiveMessagesDeliveryProof(FromBridgedChainMessagesDeliveryProof<H256, LegacyLaneId>),
+		SendMessage(LegacyLaneId, MessagePayload),
+	}
+
+	#[derive(Clone, Debug, Decode, Encode)]
+	struct TestTransaction {
+		nonce: u32,
+		call: TestCall,
+	}
+
+	/// Standalone chain, bridged with the `Right` parachain.
+	#[derive(Clone, Debug)]
+	struct Left;
+
+	impl bp_runtime::Chain for Left {
+		const ID: ChainId = *b"left";
+
+		type BlockNumber = u32;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = TestHeader;
+
+		type AccountId = AccountId32;
+		type Balance = u64;
+		type Nonce = u32;
+		type Signature = MultiSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			100_000
+		}
+
+		fn max_extrinsic_weight() -> Weight {
+			Weight::MAX
+		}
+	}
+
+	impl bp_header_chain::ChainWithGrandpa for Left {
+		const WITH_CHAIN_GRANDPA_PALLET_NAME: &'static str = LEFT_GRANDPA_PALLET_NAME;
+		const MAX_AUTHORITIES_COUNT: u32 = 16;
+		const REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY: u32 = 8;
+		const MAX_MANDATORY_HEADER_SIZE: u32 = 100_000;
+		const AVERAGE_HEADER_SIZE: u32 = 1_024;
+	}
+
+	impl bp_messages::ChainWithMessages for Left {
+		const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = LEFT_MESSAGES_PALLET_NAME;
+		const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 16;
+		const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 128;
+	}
+
+	impl Chain for Left {
+		const NAME: &'static str = "Left";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str = LEFT_BEST_FINALIZED_METHOD;
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str = "LeftFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = SignedBlock;
+		type Call = TestCall;
+	}
+
+	impl relay_substrate_client::ChainWithGrandpa for Left {
+		const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
+			"LeftFinalityApi_synced_headers_grandpa_info";
+
+		type KeyOwnerProof = ();
+	}
+
+	impl relay_substrate_client::ChainWithMessages for Left {
+		const WITH_CHAIN_RELAYERS_PALLET_NAME: Option<&'static str> = None;
+		const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str = TO_LEFT_MESSAGE_DETAILS_METHOD;
+		const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str = FROM_LEFT_MESSAGE_DETAILS_METHOD;
+	}
+
+	impl relay_substrate_client::ChainWithBalances for Left {
+		fn account_info_storage_key(account_id: &AccountId32) -> StorageKey {
+			AccountInfoStorageMapKeyProvider::final_key(account_id)
+		}
+	}
+
+	impl ChainWithTransactions for Left {
+		type AccountKeyPair = sr25519::Pair;
+		type SignedTransaction = TestTransaction;
+
+		fn sign_transaction(
+			_param: SignParam<Self>,
+			unsigned: UnsignedTransaction<Self>,
+		) -> Result<Self::SignedTransaction, SubstrateError> {
+			Ok(TestTransaction { nonce: unsigned.nonce, call: unsigned.call.into_decoded()? })
+		}
+	}
+
+	impl ChainWithRuntimeVersion for Left {
+		const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = None;
+	}
+
+	/// Relay chain of the `Right` parachain.
+	#[derive(Clone, Debug)]
+	struct RightRelay;
+
+	impl bp_runtime::Chain for RightRelay {
+		const ID: ChainId = *b"rrly";
+
+		type BlockNumber = u32;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = TestHeader;
+
+		type AccountId = AccountId32;
+		type Balance = u64;
+		type Nonce = u32;
+		type Signature = MultiSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			100_000
+		}
+
+		fn max_extrinsic_weight() -> Weight {
+			Weight::MAX
+		}
+	}
+
+	impl bp_header_chain::ChainWithGrandpa for RightRelay {
+		const WITH_CHAIN_GRANDPA_PALLET_NAME: &'static str = RIGHT_RELAY_GRANDPA_PALLET_NAME;
+		const MAX_AUTHORITIES_COUNT: u32 = 16;
+		const REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY: u32 = 8;
+		const MAX_MANDATORY_HEADER_SIZE: u32 = 100_000;
+		const AVERAGE_HEADER_SIZE: u32 = 1_024;
+	}
+
+	impl Chain for RightRelay {
+		const NAME: &'static str = "RightRelay";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str = RIGHT_RELAY_BEST_FINALIZED_METHOD;
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+			"RightRelayFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = SignedBlock;
+		type Call = ();
+	}
+
+	impl relay_substrate_client::ChainWithGrandpa for RightRelay {
+		const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
+			"RightRelayFinalityApi_synced_headers_grandpa_info";
+
+		type KeyOwnerProof = ();
+	}
+
+	impl relay_substrate_client::RelayChain for RightRelay {
+		const PARAS_PALLET_NAME: &'static str = RIGHT_RELAY_PARAS_PALLET_NAME;
+		const WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME: &'static str =
+			RIGHT_RELAY_PARACHAINS_PALLET_NAME;
+	}
+
+	impl ChainWithRuntimeVersion for RightRelay {
+		const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = None;
+	}
+
+	/// Primitives of the `Right` parachain.
+	#[derive(Clone, Debug)]
+	struct RightBase;
+
+	impl bp_runtime::Chain for RightBase {
+		const ID: ChainId = *b"rght";
+
+		type BlockNumber = u32;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = TestHeader;
+
+		type AccountId = AccountId32;
+		type Balance = u64;
+		type Nonce = u32;
+		type Signature = MultiSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			100_000
+		}
+
+		fn max_extrinsic_weight() -> Weight {
+			Weight::MAX
+		}
+	}
+
+	impl bp_runtime::Parachain for RightBase {
+		const PARACHAIN_ID: u32 = RIGHT_PARA_ID;
+		const MAX_HEADER_SIZE: u32 = 1_024;
+	}
+
+	impl bp_messages::ChainWithMessages for RightBase {
+		const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = RIGHT_MESSAGES_PALLET_NAME;
+		const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 16;
+		const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 128;
+	}
+
+	/// Parachain, bridged with the `Left` chain.
+	#[derive(Clone, Debug)]
+	struct Right;
+
+	impl bp_runtime::UnderlyingChainProvider for Right {
+		type Chain = RightBase;
+	}
+
+	impl Chain for Right {
+		const NAME: &'static str = "Right";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str = RIGHT_BEST_FINALIZED_METHOD;
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str = "RightFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = SignedBlock;
+		type Call = TestCall;
+	}
+
+	impl relay_substrate_client::ChainWithMessages for Right {
+		const WITH_CHAIN_RELAYERS_PALLET_NAME: Option<&'static str> = None;
+		const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str = TO_RIGHT_MESSAGE_DETAILS_METHOD;
+		const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str = FROM_RIGHT_MESSAGE_DETAILS_METHOD;
+	}
+
+	impl relay_substrate_client::ChainWithBalances for Right {
+		fn account_info_storage_key(account_id: &AccountId32) -> StorageKey {
+			AccountInfoStorageMapKeyProvider::final_key(account_id)
+		}
+	}
+
+	impl ChainWithTransactions for Right {
+		type AccountKeyPair = sr25519::Pair;
+		type SignedTransaction = TestTransaction;
+
+		fn sign_transaction(
+			_param: SignParam<Self>,
+			unsigned: UnsignedTransaction<Self>,
+		) -> Result<Self::SignedTransaction, SubstrateError> {
+			Ok(TestTransaction { nonce: unsigned.nonce, call: unsigned.call.into_decoded()? })
+		}
+	}
+
+	impl ChainWithRuntimeVersion for Right {
+		const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = None;
+	}
+
+	#[derive(Clone, Debug)]
+	struct LeftToRightFinality;
+
+	impl SubstrateFinalityPipeline for LeftToRightFinality {
+		type SourceChain = Left;
+		type TargetChain = Right;
+		type FinalityEngine = Grandpa<Left>;
+	}
+
+	impl SubstrateFinalitySyncPipeline for LeftToRightFinality {
+		type SubmitFinalityProofCallBuilder = TestCallBuilder;
+	}
+
+	#[derive(Clone, Debug)]
+	struct RightRelayToLeftFinality;
+
+	impl SubstrateFinalityPipeline for RightRelayToLeftFinality {
+		type SourceChain = RightRelay;
+		type TargetChain = Left;
+		type FinalityEngine = Grandpa<RightRelay>;
+	}
+
+	impl SubstrateFinalitySyncPipeline for RightRelayToLeftFinality {
+		type SubmitFinalityProofCallBuilder = TestCallBuilder;
+	}
+
+	#[derive(Clone, Debug)]
+	struct RightToLeftParachains;
+
+	impl SubstrateParachainsPipeline for RightToLeftParachains {
+		type SourceParachain = Right;
+		type SourceRelayChain = RightRelay;
+		type TargetChain = Left;
+		type SubmitParachainHeadsCallBuilder = TestCallBuilder;
+	}
+
+	#[derive(Clone, Debug)]
+	struct LeftToRightMessages;
+
+	impl SubstrateMessageLane for LeftToRightMessages {
+		type SourceChain = Left;
+		type TargetChain = Right;
+		type LaneId = LegacyLaneId;
+
+		type ReceiveMessagesProofCallBuilder = TestCallBuilder;
+		type ReceiveMessagesDeliveryProofCallBuilder = TestCallBuilder;
+
+		type SourceBatchCallBuilder = ();
+		type TargetBatchCallBuilder = ();
+	}
+
+	#[derive(Clone, Debug)]
+	struct RightToLeftMessages;
+
+	impl SubstrateMessageLane for RightToLeftMessages {
+		type SourceChain = Right;
+		type TargetChain = Left;
+		type LaneId = LegacyLaneId;
+
+		type ReceiveMessagesProofCallBuilder = TestCallBuilder;
+		type ReceiveMessagesDeliveryProofCallBuilder = TestCallBuilder;
+
+		type SourceBatchCallBuilder = ();
+		type TargetBatchCallBuilder = ();
+	}
+
+	struct TestCallBuilder;
+
+	impl SubmitFinalityProofCallBuilder<LeftToRightFinality> for TestCallBuilder {
+		fn build_submit_finality_proof_call(
+			header: SyncHeader<TestHeader>,
+			proof: GrandpaJustification<TestHeader>,
+			_is_free_execution_expected: bool,
+			_context: JustificationVerificationContext,
+		) -> TestCall {
+			TestCall::SubmitFinalityProof(Box::new(header.into_inner()), proof)
+		}
+	}
+
+	impl SubmitFinalityProofCallBuilder<RightRelayToLeftFinality> for TestCallBuilder {
+		fn build_submit_finality_proof_call(
+			header: SyncHeader<TestHeader>,
+			proof: GrandpaJustification<TestHeader>,
+			_is_free_execution_expected: bool,
+			_context: JustificationVerificationContext,
+		) -> TestCall {
+			TestCall::SubmitFinalityProof(Box::new(header.into_inner()), proof)
+		}
+	}
+
+	impl SubmitParachainHeadsCallBuilder<RightToLeftParachains> for TestCallBuilder {
+		fn build_submit_parachain_heads_call(
+			at_relay_block: HeaderId<H256, u32>,
+			parachains: Vec<(ParaId, ParaHash)>,
+			parachain_heads_proof: ParaHeadsProof,
+			_is_free_execution_expected: bool,
+		) -> TestCall {
+			TestCall::SubmitParachainHeads(at_relay_block, parachains, parachain_heads_proof)
+		}
+	}
+
+	impl ReceiveMessagesProofCallBuilder<LeftToRightMessages> for TestCallBuilder {
+		fn build_receive_messages_proof_call(
+			relayer_id_at_source: AccountId32,
+			proof: SubstrateMessagesProof<Left, LegacyLaneId>,
+			_messages_count: u32,
+			_dispatch_weight: Weight,
+			_trace_call: bool,
+		) -> TestCall {
+			TestCall::ReceiveMessagesProof(relayer_id_at_source, proof.1)
+		}
+	}
+
+	impl ReceiveMessagesProofCallBuilder<RightToLeftMessages> for TestCallBuilder {
+		fn build_receive_messages_proof_call(
+			relayer_id_at_source: AccountId32,
+			proof: SubstrateMessagesProof<Right, LegacyLaneId>,
+			_messages_count: u32,
+			_dispatch_weight: Weight,
+			_trace_call: bool,
+		) -> TestCall {
+			TestCall::ReceiveMessagesProof(relayer_id_at_source, proof.1)
+		}
+	}
+
+	impl ReceiveMessagesDeliveryProofCallBuilder<LeftToRightMessages> for TestCallBuilder {
+		fn build_receive_messages_delivery_proof_call(
+			proof: SubstrateMessagesDeliveryProof<Right, LegacyLaneId>,
+			_trace_call: bool,
+		) -> TestCall {
+			TestCall::ReceiveMessagesDeliveryProof(proof.1)
+		}
+	}
+
+	impl ReceiveMessagesDeliveryProofCallBuilder<RightToLeftMessages> for TestCallBuilder {
+		fn build_receive_messages_delivery_proof_call(
+			proof: SubstrateMessagesDeliveryProof<Left, LegacyLaneId>,
+			_trace_call: bool,
+		) -> TestCall {
+			TestCall::ReceiveMessagesDeliveryProof(proof.1)
+		}
+	}
+
+	struct LeftToRightCliBridge;
+
+	impl CliBridgeBase for LeftToRightCliBridge {
+		type Source = Left;
+		type Target = Right;
+	}
+
+	impl RelayToRelayHeadersCliBridge for LeftToRightCliBridge {
+		type Finality = LeftToRightFinality;
+	}
+
+	impl MessagesCliBridge for LeftToRightCliBridge {
+		type MessagesLane = LeftToRightMessages;
+
+		fn maybe_messages_limits() -> Option<MessagesRelayLimits> {
+			Some(MessagesRelayLimits {
+				max_messages_in_single_batch: 16,
+				max_messages_weight_in_single_batch: Weight::MAX,
+			})
+		}
+	}
+
+	struct RightToLeftCliBridge;
+
+	impl CliBridgeBase for RightToLeftCliBridge {
+		type Source = Right;
+		type Target = Left;
+	}
+
+	impl ParachainToRelayHeadersCliBridge for RightToLeftCliBridge {
+		type SourceRelay = RightRelay;
+		type ParachainFinality = RightToLeftParachains;
+		type RelayFinality = RightRelayToLeftFinality;
+	}
+
+	impl MessagesCliBridge for RightToLeftCliBridge {
+		type MessagesLane = RightToLeftMessages;
+
+		fn maybe_messages_limits() -> Option<MessagesRelayLimits> {
+			Some(MessagesRelayLimits {
+				max_messages_in_single_batch: 16,
+				max_messages_weight_in_single_batch: Weight::MAX,
+			})
+		}
+	}
+
+	type LeftRightBridgeBase = RelayToParachainBridge<
+		LeftToRightCliBridge,
+		RightToLeftCliBridge,
+		SimulatedClient<Left>,
+		SimulatedClient<Right>,
+		SimulatedClient<RightRelay>,
+	>;
+
+	/// Complex relay between the `Left` chain and the `Right` parachain.
+	struct LeftRightBridge {
+		base: LeftRightBridgeBase,
+	}
+
+	impl Full2WayBridge for LeftRightBridge {
+		type Base = LeftRightBridgeBase;
+		type Left = Left;
+		type Right = Right;
+		type L2R = LeftToRightCliBridge;
+		type R2L = RightToLeftCliBridge;
+
+		fn new(base: Self::Base) -> anyhow::Result<Self> {
+			Ok(LeftRightBridge { base })
+		}
+
+		fn base(&self) -> &Self::Base {
+			&self.base
+		}
+
+		fn mut_base(&mut self) -> &mut Self::Base {
+			&mut self.base
+		}
+	}
+
+	/// Relay chain runtime, which is finalizing blocks with justifications, signed by the test
+	/// authorities.
+	struct RightRelayRuntime;
+
+	impl SimulatedRuntime for RightRelayRuntime {
+		fn grandpa_justification(&mut self, header: &[u8]) -> Option<Vec<u8>> {
+			let header = TestHeader::decode(&mut &header[..]).ok()?;
+			Some(make_default_justification(&header).encode())
+		}
+	}
+
+	/// Bridge pallet and its runtime API method, returning the best finalized bridged header.
+	struct BridgePallet {
+		name: &'static str,
+		best_finalized_method: &'static str,
+	}
+
+	/// Bridge messages pallet and its runtime API methods.
+	struct MessagesPallet {
+		/// Name of the pallet at this chain.
+		name: &'static str,
+		/// Name of the pallet at the bridged chain.
+		bridged_name: &'static str,
+		outbound_details_method: &'static str,
+		inbound_details_method: &'static str,
+	}
+
+	/// Runtime of the bridged chain with minimal bridge pallets. Messages are accepted from and
+	/// confirmed by the bridged chain, using storage proofs, verified against state roots of
+	/// the imported bridged headers.
+	struct BridgeRuntime {
+		/// Bridge GRANDPA pallet, tracking the bridged (relay) chain.
+		grandpa: BridgePallet,
+		/// Bridge parachains pallet, tracking the bridged parachain, if it is the parachain.
+		parachains: Option<BridgePallet>,
+		messages: MessagesPallet,
+	}
+
+	impl SimulatedRuntime for BridgeRuntime {
+		fn call(
+			&mut self,
+			context: RuntimeContext,
+			method: &str,
+			arguments: &[u8],
+		) -> Result<Vec<u8>, SubstrateError> {
+			let storage = context.storage;
+			if method == self.grandpa.best_finalized_method {
+				let best_finalized: Option<HeaderId<H256, u32>> =
+					read_value(storage, &best_finalized_key(self.grandpa.name).0)?;
+				return Ok(best_finalized.encode())
+			}
+			if let Some(ref parachains) = self.parachains {
+				if method == parachains.best_finalized_method {
+					return Ok(best_para_head_id(storage, parachains.name)?.encode())
+				}
+			}
+			if method == self.messages.outbound_details_method {
+				let (lane, begin, end): (LegacyLaneId, MessageNonce, MessageNonce) =
+					Decode::decode(&mut &arguments[..])?;
+				let details = (begin..=end)
+					.filter_map(|nonce| {
+						let message =
+							storage.get(&message_key(self.messages.name, &lane, nonce).0)?;
+						Some(OutboundMessageDetails {
+							nonce,
+							dispatch_weight: Weight::zero(),
+							size: message.len() as u32,
+						})
+					})
+					.collect::<Vec<_>>();
+				return Ok(details.encode())
+			}
+			if method == self.messages.inbound_details_method {
+				let (_lane, messages): (
+					LegacyLaneId,
+					Vec<(MessagePayload, OutboundMessageDetails)>,
+				) = Decode::decode(&mut &arguments[..])?;
+				let details = messages
+					.iter()
+					.map(|_| InboundMessageDetails { dispatch_weight: Weight::zero() })
+					.collect::<Vec<_>>();
+				return Ok(details.encode())
+			}
+
+			Err(SubstrateError::Custom(format!("Unknown method {method}")))
+		}
+
+		fn validate_transaction(
+			&mut self,
+			context: RuntimeContext,
+			transaction: &[u8],
+		) -> TransactionValidity {
+			let transaction = TestTransaction::decode(&mut &transaction[..])
+				.map_err(|_| InvalidTransaction::Call)?;
+			match transaction.call {
+				TestCall::ReceiveMessagesProof(..)
+					if context.fee_multiplier > MAX_DELIVERY_FEE_MULTIPLIER =>
+					Err(InvalidTransaction::Payment.into()),
+				_ => Ok(ValidTransaction::default()),
+			}
+		}
+
+		fn apply_transaction(
+			&mut self,
+			storage: &mut SimulatedStorage,
+			transaction: &[u8],
+		) -> Result<(), SubstrateError> {
+			let TestTransaction { call, .. } = Decode::decode(&mut &transaction[..])?;
+			match call {
+				TestCall::SubmitFinalityProof(header, justification) =>
+					self.submit_finality_proof(storage, *header, justification),
+				TestCall::SubmitParachainHeads(at_relay_block, parachains, proof) =>
+					self.submit_parachain_heads(storage, at_relay_block, parachains, proof),
+				TestCall::ReceiveMessagesProof(relayer, proof) =>
+					self.receive_messages_proof(storage, relayer, proof),
+				TestCall::ReceiveMessagesDeliveryProof(proof) =>
+					self.receive_messages_delivery_proof(storage, proof),
+				TestCall::SendMessage(lane, payload) => self.send_message(storage, lane, payload),
+			}
+		}
+	}
+
+	impl BridgeRuntime {
+		fn submit_finality_proof(
+			&self,
+			storage: &mut SimulatedStorage,
+			header: TestHeader,
+			justification: GrandpaJustification<TestHeader>,
+		) -> Result<(), SubstrateError> {
+			let pallet = self.grandpa.name;
+			let best_finalized: Option<HeaderId<H256, u32>> =
+				read_value(storage, &best_finalized_key(pallet).0)?;
+			if best_finalized.map_or(false, |best_finalized| *header.number() <= best_finalized.0) {
+				return Err(SubstrateError::Custom("Bridged header is not better".into()))
+			}
+
+			let authority_set: AuthoritySet =
+				read_value(storage, &current_authority_set_key(pallet).0)?
+					.ok_or_else(|| SubstrateError::Custom("No authority set".into()))?;
+			let context = JustificationVerificationContext::try_from(authority_set)
+				.map_err(|e| SubstrateError::Custom(format!("Invalid authority set: {e:?}")))?;
+			verify_justification::<TestHeader>(
+				(header.hash(), *header.number()),
+				&context,
+				&justification,
+			)
+			.map_err(|e| SubstrateError::Custom(format!("Invalid justification: {e:?}")))?;
+
+			write_value(
+				storage,
+				best_finalized_key(pallet).0,
+				HeaderId(*header.number(), header.hash()),
+			);
+			write_value(
+				storage,
+				imported_header_key(pallet, header.hash()),
+				StoredHeaderData { number: *header.number(), state_root: *header.state_root() },
+			);
+			Ok(())
+		}
+
+		fn submit_parachain_heads(
+			&self,
+			storage: &mut SimulatedStorage,
+			at_relay_block: HeaderId<H256, u32>,
+			parachains: Vec<(ParaId, ParaHash)>,
+			proof: ParaHeadsProof,
+		) -> Result<(), SubstrateError> {
+			let pallet = self
+				.parachains
+				.as_ref()
+				.ok_or_else(|| SubstrateError::Custom("No parachains pallet".into()))?
+				.name;
+			let relay_state_root =
+				imported_state_root(storage, self.grandpa.name, at_relay_block.1)?;
+			let mut proof =
+				StorageProofChecker::<BlakeTwo256>::new(relay_state_root, proof.storage_proof)
+					.map_err(proof_error)?;
+			for (para_id, para_head_hash) in parachains {
+				let para_head: ParaHead = proof
+					.read_and_decode_mandatory_value(
+						&parachain_head_storage_key_at_source(
+							RIGHT_RELAY_PARAS_PALLET_NAME,
+							para_id,
+						)
+						.0,
+					)
+					.map_err(proof_error)?;
+				if para_head.hash() != para_head_hash {
+					return Err(SubstrateError::Custom("Parachain head hash mismatch".into()))
+				}
+
+				let para_info_key = ParasInfoKeyProvider::final_key(pallet, &para_id).0;
+				let para_info: Option<ParaInfo> = read_value(storage, &para_info_key)?;
+				if para_info.map_or(false, |para_info| {
+					para_info.best_head_hash.at_relay_block_number >= at_relay_block.0
+				}) {
+					return Err(SubstrateError::Custom("Parachain head is not newer".into()))
+				}
+
+				let para_header = TestHeader::decode(&mut &para_head.0[..])?;
+				write_value(
+					storage,
+					para_info_key,
+					ParaInfo {
+						best_head_hash: BestParaHeadHash {
+							at_relay_block_number: at_relay_block.0,
+							head_hash: para_head_hash,
+						},
+						next_imported_hash_position: 0,
+					},
+				);
+				write_value(
+					storage,
+					ImportedParaHeadsKeyProvider::final_key(pallet, &para_id, &para_head_hash).0,
+					ParaStoredHeaderData(
+						StoredHeaderData {
+							number: *para_header.number(),
+							state_root: *para_header.state_root(),
+						}
+						.encode(),
+					),
+				);
+			}
+			Ok(())
+		}
+
+		fn receive_messages_proof(
+			&self,
+			storage: &mut SimulatedStorage,
+			relayer: AccountId32,
+			proof: FromBridgedChainMessagesProof<H256, LegacyLaneId>,
+		) -> Result<(), SubstrateError> {
+			let state_root = self.bridged_state_root(storage, proof.bridged_header_hash)?;
+			let mut checker =
+				StorageProofChecker::<BlakeTwo256>::new(state_root, proof.storage_proof)
+					.map_err(proof_error)?;
+
+			let inbound_lane_key = inbound_lane_data_key(self.messages.name, &proof.lane).0;
+			let mut inbound_lane: InboundLaneData<AccountId32> =
+				read_value(storage, &inbound_lane_key)?.unwrap_or_else(InboundLaneData::opened);
+			if proof.nonces_start != inbound_lane.last_delivered_nonce() + 1 {
+				return Err(SubstrateError::Custom("Unexpected message nonce".into()))
+			}
+
+			for nonce in proof.nonces_start..=proof.nonces_end {
+				let _: MessagePayload = checker
+					.read_and_decode_mandatory_value(
+						&message_key(self.messages.bridged_name, &proof.lane, nonce).0,
+					)
+					.map_err(proof_error)?;
+			}
+			let outbound_lane: Option<OutboundLaneData> = checker
+				.read_and_decode_opt_value(
+					&outbound_lane_data_key(self.messages.bridged_name, &proof.lane).0,
+				)
+				.map_err(proof_error)?;
+			if let Some(outbound_lane) = outbound_lane {
+				// relayers are rewarded at the bridged chain when their messages are confirmed
+				inbound_lane.last_confirmed_nonce =
+					inbound_lane.last_confirmed_nonce.max(outbound_lane.latest_received_nonce);
+				while inbound_lane
+					.relayers
+					.front()
+					.map_or(false, |entry| entry.messages.end <= inbound_lane.last_confirmed_nonce)
+				{
+					inbound_lane.relayers.pop_front();
+				}
+			}
+			if proof.nonces_end >= proof.nonces_start {
+				inbound_lane.relayers.push_back(UnrewardedRelayer {
+					relayer,
+					messages: DeliveredMessages {
+						begin: proof.nonces_start,
+						end: proof.nonces_end,
+					},
+				});
+			}
+
+			write_value(storage, inbound_lane_key, inbound_lane);
+			Ok(())
+		}
+
+		fn receive_messages_delivery_proof(
+			&self,
+			storage: &mut SimulatedStorage,
+			proof: FromBridgedChainMessagesDeliveryProof<H256, LegacyLaneId>,
+		) -> Result<(), SubstrateError> {
+			let state_root = self.bridged_state_root(storage, proof.bridged_header_hash)?;
+			let mut checker =
+				StorageProofChecker::<BlakeTwo256>::new(state_root, proof.storage_proof)
+					.map_err(proof_error)?;
+			let inbound_lane: InboundLaneData<AccountId32> = checker
+				.read_and_decode_mandatory_value(
+					&inbound_lane_data_key(self.messages.bridged_name, &proof.lane).0,
+				)
+				.map_err(proof_error)?;
+
+			let outbound_lane_key = outbound_lane_data_key(self.messages.name, &proof.lane).0;
+			let mut outbound_lane: OutboundLaneData =
+				read_value(storage, &outbound_lane_key)?.unwrap_or_else(OutboundLaneData::opened);
+			let received_nonce = inbound_lane.last_delivered_nonce();
+			if received_nonce <= outbound_lane.latest_received_nonce ||
+				received_nonce > outbound_lane.latest_generated_nonce
+			{
+				return Err(SubstrateError::Custom("Unexpected messages delivery proof".into()))
+			}
+
+			for nonce in outbound_lane.oldest_unpruned_nonce..=received_nonce {
+				storage.remove(&message_key(self.messages.name, &proof.lane, nonce).0);
+			}
+			outbound_lane.latest_received_nonce = received_nonce;
+			outbound_lane.oldest_unpruned_nonce = received_nonce + 1;
+			write_value(storage, outbound_lane_key, outbound_lane);
+			Ok(())
+		}
+
+		fn send_message(
+			&self,
+			storage: &mut SimulatedStorage,
+			lane: LegacyLaneId,
+			payload: MessagePayload,
+		) -> Result<(), SubstrateError> {
+			let outbound_lane_key = outbound_lane_data_key(self.messages.name, &lane).0;
+			let mut outbound_lane: OutboundLaneData =
+				read_value(storage, &outbound_lane_key)?.unwrap_or_else(OutboundLaneData::opened);
+			outbound_lane.latest_generated_nonce += 1;
+
+			write_value(
+				storage,
+				message_key(self.messages.name, &lane, outbound_lane.latest_generated_nonce).0,
+				payload,
+			);
+			write_value(storage, outbound_lane_key, outbound_lane);
+			Ok(())
+		}
+
+		/// Returns state root of the imported bridged header.
+		fn bridged_state_root(
+			&self,
+			storage: &SimulatedStorage,
+			hash: H256,
+		) -> Result<H256, SubstrateError> {
+			let Some(ref parachains) = self.parachains else {
+				return imported_state_root(storage, self.grandpa.name, hash)
+			};
+
+			let para_head: ParaStoredHeaderData = read_value(
+				storage,
+				&ImportedParaHeadsKeyProvider::final_key(
+					parachains.name,
+					&ParaId(RIGHT_PARA_ID),
+					&hash,
+				)
+				.0,
+			)?
+			.ok_or_else(|| SubstrateError::Custom("Unknown bridged parachain header".into()))?;
+			Ok(para_head.decode_parachain_head_data::<Right>()?.state_root)
+		}
+	}
+
+	/// Returns state root of the bridged header, imported by the bridge GRANDPA pallet.
+	fn imported_state_root(
+		storage: &SimulatedStorage,
+		pallet: &str,
+		hash: H256,
+	) -> Result<H256, SubstrateError> {
+		let header: StoredHeaderData<u32, H256> =
+			read_value(storage, &imported_header_key(pallet, hash))?
+				.ok_or_else(|| SubstrateError::Custom("Unknown bridged header".into()))?;
+		Ok(header.state_root)
+	}
+
+	fn imported_header_key(pallet: &str, hash: H256) -> Vec<u8> {
+		bp_runtime::storage_map_final_key::<Identity>(pallet, "ImportedHeaders", &hash.encode()).0
+	}
+
+	/// Returns identifier of the best parachain head, imported by the bridge parachains pallet.
+	fn best_para_head_id(
+		storage: &SimulatedStorage,
+		pallet: &str,
+	) -> Result<Option<HeaderId<H256, u32>>, SubstrateError> {
+		let para_id = ParaId(RIGHT_PARA_ID);
+		let Some(para_info) =
+			read_value::<ParaInfo>(storage, &ParasInfoKeyProvider::final_key(pallet, &para_id).0)?
+		else {
+			return Ok(None)
+		};
+
+		let head_hash = para_info.best_head_hash.head_hash;
+		let para_head: Option<ParaStoredHeaderData> = read_value(
+			storage,
+			&ImportedParaHeadsKeyProvider::final_key(pallet, &para_id, &head_hash).0,
+		)?;
+		para_head
+			.map(|para_head| {
+				Ok(HeaderId(para_head.decode_parachain_head_data::<Right>()?.number, head_hash))
+			})
+			.transpose()
+	}
+
+	fn read_value<T: Decode>(
+		storage: &SimulatedStorage,
+		key: &[u8],
+	) -> Result<Option<T>, SubstrateError> {
+		Ok(storage.get(key).map(|value| T::decode(&mut &value[..])).transpose()?)
+	}
+
+	fn write_value(storage: &mut SimulatedStorage, key: Vec<u8>, value: impl Encode) {
+		storage.insert(key, value.encode());
+	}
+
+	fn proof_error(error: StorageProofError) -> SubstrateError {
+		SubstrateError::Custom(format!("Invalid storage proof: {error:?}"))
+	}
+
+	/// Bridged chain storage with initialized bridge GRANDPA pallet.
+	fn bridge_genesis_storage(
+		grandpa_pallet: &str,
+		bridged_genesis_hash: H256,
+	) -> SimulatedStorage {
+		SimulatedStorage::from([
+			(best_finalized_key(grandpa_pallet).0, HeaderId(0u32, bridged_genesis_hash).encode()),
+			(
+				current_authority_set_key(grandpa_pallet).0,
+				AuthoritySet::new(authority_list(), TEST_GRANDPA_SET_ID).encode(),
+			),
+		])
+	}
+
+	/// Chain is producing and finalizing block at every tick.
+	fn chain_script(finalize: SimulatedAction) -> SimulationScript {
+		SimulationScript::new()
+			.every(1..MAX_TICKS, SimulatedAction::ProduceBlock(Default::default()))
+			.every(1..MAX_TICKS, finalize)
+	}
+
+	/// Relay chain of the `Right` parachain. At every tick, it includes the best parachain head
+	/// into the new block and finalizes it.
+	struct RightRelayChain {
+		relay: SimulatedChain<RightRelay>,
+		para: SimulatedChain<Right>,
+	}
+
+	#[async_trait]
+	impl SimulationParticipant for RightRelayChain {
+		async fn tick(&self, _now: u64) {
+			let para_head = ParaHead(self.para.best_header().await.encode());
+			self.relay
+				.apply(SimulatedAction::ProduceBlock(SimulatedBlock::default().with_storage_value(
+					parachain_head_storage_key_at_source(
+						RIGHT_RELAY_PARAS_PALLET_NAME,
+						ParaId(RIGHT_PARA_ID),
+					),
+					para_head,
+				)))
+				.await;
+			self.relay.apply(SimulatedAction::FinalizeWithJustification).await;
+		}
+	}
+
+	/// Nonces of the test lane in both directions.
+	#[derive(Debug, PartialEq)]
+	struct LanesState {
+		delivered_to_right: MessageNonce,
+		confirmed_at_left: MessageNonce,
+		delivered_to_left: MessageNonce,
+		confirmed_at_right: MessageNonce,
+	}
+
+	impl LanesState {
+		/// All messages up to given nonce are delivered and confirmed in both directions.
+		fn confirmed(nonce: MessageNonce) -> Self {
+			LanesState {
+				delivered_to_right: nonce,
+				confirmed_at_left: nonce,
+				delivered_to_left: nonce,
+				confirmed_at_right: nonce,
+			}
+		}
+
+		async fn read(left: &SimulatedChain<Left>, right: &SimulatedChain<Right>) -> Self {
+			LanesState {
+				delivered_to_right: last_delivered_nonce(right, LEFT_MESSAGES_PALLET_NAME).await,
+				confirmed_at_left: latest_received_nonce(left, RIGHT_MESSAGES_PALLET_NAME).await,
+				delivered_to_left: last_delivered_nonce(left, RIGHT_MESSAGES_PALLET_NAME).await,
+				confirmed_at_right: latest_received_nonce(right, LEFT_MESSAGES_PALLET_NAME).await,
+			}
+		}
+	}
+
+	async fn last_delivered_nonce<C: Chain>(
+		chain: &SimulatedChain<C>,
+		pallet: &str,
+	) -> MessageNonce {
+		chain
+			.best_storage_value(&inbound_lane_data_key(pallet, &TEST_LANE_ID).0)
+			.await
+			.and_then(|lane| InboundLaneData::<AccountId32>::decode(&mut &lane[..]).ok())
+			.map_or(0, |lane| lane.last_delivered_nonce())
+	}
+
+	async fn latest_received_nonce<C: Chain>(
+		chain: &SimulatedChain<C>,
+		pallet: &str,
+	) -> MessageNonce {
+		chain
+			.best_storage_value(&outbound_lane_data_key(pallet, &TEST_LANE_ID).0)
+			.await
+			.and_then(|lane| OutboundLaneData::decode(&mut &lane[..]).ok())
+			.map_or(0, |lane| lane.latest_received_nonce)
+	}
+
+	/// Start the real complex relay between the `Left` chain and the `Right` parachain.
+	async fn run_relay(
+		left: SimulatedClient<Left>,
+		right: SimulatedClient<Right>,
+		right_relay: SimulatedClient<RightRelay>,
+	) -> anyhow::Result<()> {
+		let shared = HeadersAndMessagesSharedParams {
+			lane: vec!["00000000".parse()?],
+			only_mandatory_headers: false,
+			only_free_headers: false,
+			prometheus_params: PrometheusParams {
+				no_prometheus: true,
+				prometheus_host: "127.0.0.1".into(),
+				prometheus_port: 9616,
+			},
+		};
+		let base = RelayToParachainBridge {
+			common: Full2WayBridgeCommonParams::new::<LeftToRightCliBridge>(
+				shared,
+				BridgeEndCommonParams {
+					client: left,
+					tx_params: TransactionParams {
+						signer: sr25519::Pair::from_string("//Left-relayer", None).unwrap(),
+						mortality: None,
+					},
+					accounts: vec![],
+				},
+				BridgeEndCommonParams {
+					client: right,
+					tx_params: TransactionParams {
+						signer: sr25519::Pair::from_string("//Right-relayer", None).unwrap(),
+						mortality: None,
+					},
+					accounts: vec![],
+				},
+			)?,
+			right_relay,
+		};
+
+		LeftRightBridge::new(base)?.run().await
+	}
+
+	struct TestBridge {
+		left: SimulatedChain<Left>,
+		right: SimulatedChain<Right>,
+		simulation: Simulation,
+		relay: async_std::task::JoinHandle<anyhow::Result<()>>,
+	}
+
+	impl TestBridge {
+		/// Start the real complex relay between simulated chains.
+		fn start(right_script: SimulationScript) -> Self {
+			let right_relay = SimulatedChain::new(
+				RightRelayRuntime,
+				SimulatedStorage::new(),
+				SimulationScript::new(),
+			);
+			let left = SimulatedChain::new(
+				BridgeRuntime {
+					grandpa: BridgePallet {
+						name: RIGHT_RELAY_GRANDPA_PALLET_NAME,
+						best_finalized_method: RIGHT_RELAY_BEST_FINALIZED_METHOD,
+					},
+					parachains: Some(BridgePallet {
+						name: RIGHT_RELAY_PARACHAINS_PALLET_NAME,
+						best_finalized_method: RIGHT_BEST_FINALIZED_METHOD,
+					}),
+					messages: MessagesPallet {
+						name: RIGHT_MESSAGES_PALLET_NAME,
+						bridged_name: LEFT_MESSAGES_PALLET_NAME,
+						outbound_details_method: TO_RIGHT_MESSAGE_DETAILS_METHOD,
+						inbound_details_method: FROM_RIGHT_MESSAGE_DETAILS_METHOD,
+					},
+				},
+				bridge_genesis_storage(RIGHT_RELAY_GRANDPA_PALLET_NAME, right_relay.genesis_hash()),
+				chain_script(SimulatedAction::FinalizeWithJustification),
+			);
+			let right = SimulatedChain::new(
+				BridgeRuntime {
+					grandpa: BridgePallet {
+						name: LEFT_GRANDPA_PALLET_NAME,
+						best_finalized_method: LEFT_BEST_FINALIZED_METHOD,
+					},
+					parachains: None,
+					messages: MessagesPallet {
+						name: LEFT_MESSAGES_PALLET_NAME,
+						bridged_name: RIGHT_MESSAGES_PALLET_NAME,
+						outbound_details_method: TO_LEFT_MESSAGE_DETAILS_METHOD,
+						inbound_details_method: FROM_LEFT_MESSAGE_DETAILS_METHOD,
+					},
+				},
+				bridge_genesis_storage(LEFT_GRANDPA_PALLET_NAME, left.genesis_hash()),
+				right_script,
+			);
+			let simulation = Simulation::new()
+				.with_participant(left.clone())
+				.with_participant(right.clone())
+				.with_participant(RightRelayChain {
+					relay: right_relay.clone(),
+					para: right.clone(),
+				});
+			let relay =
+				simulation.spawn(run_relay(left.client(), right.client(), right_relay.client()));
+
+			TestBridge { left, right, simulation, relay }
+		}
+
+		/// Send messages over the test lane in both directions.
+		async fn send_messages(&self) {
+			for nonce in 1..=MESSAGES_COUNT {
+				let transaction = TestTransaction {
+					nonce: 0,
+					call: TestCall::SendMessage(TEST_LANE_ID, nonce.encode()),
+				};
+				self.left
+					.client()
+					.submit_unsigned_extrinsic(Bytes(transaction.encode()))
+					.await
+					.unwrap();
+				self.right
+					.client()
+					.submit_unsigned_extrinsic(Bytes(transaction.encode()))
+					.await
+					.unwrap();
+			}
+		}
+
+		/// Run simulation until lanes state satisfies the condition.
+		async fn run_until(
+			&mut self,
+			max_ticks: u64,
+			condition: impl Fn(LanesState) -> bool,
+		) -> bool {
+			let (left, right) = (self.left.clone(), self.right.clone());
+			let condition = &condition;
+			self.simulation
+				.run_until(max_ticks, || {
+					let (left, right) = (left.clone(), right.clone());
+					async move { condition(LanesState::read(&left, &right).await) }
+				})
+				.await
+		}
+
+		async fn stop(self) {
+			assert!(self.relay.cancel().await.is_none(), "Relay has stopped unexpectedly");
+		}
+	}
+
+	#[async_std::test]
+	async fn relay_delivers_and_confirms_messages_in_both_directions() {
+		let mut bridge = TestBridge::start(chain_script(SimulatedAction::Finalize(None)));
+		bridge.send_messages().await;
+
+		assert!(
+			bridge
+				.run_until(MAX_TICKS / 2, |state| state == LanesState::confirmed(MESSAGES_COUNT))
+				.await
+		);
+		bridge.stop().await;
+	}
+
+	#[async_std::test]
+	async fn relay_waits_until_delivery_fee_is_back_to_normal() {
+		let mut bridge = TestBridge::start(
+			chain_script(SimulatedAction::Finalize(None))
+				.at(1, SimulatedAction::SetFeeMultiplier(FixedU128::from_u32(10)))
+				.at(FEE_SPIKE_TICKS, SimulatedAction::SetFeeMultiplier(FixedU128::from_u32(1))),
+		);
+		bridge.send_messages().await;
+
+		// right chain rejects delivery transactions while the fee is too high
+		assert!(
+			!bridge
+				.run_until(FEE_SPIKE_TICKS - 1, |state| state.delivered_to_right > 0)
+				.await
+		);
+
+		// and then the relay delivers and confirms all messages
+		assert!(
+			bridge
+				.run_until(MAX_TICKS / 2, |state| state == LanesState::confirmed(MESSAGES_COUNT))
+				.await
+		);
+		bridge.stop().await;
+	}
+}
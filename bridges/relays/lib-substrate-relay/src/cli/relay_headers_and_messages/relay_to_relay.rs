@@ -27,6 +27,7 @@ use crate::{
 	cli::{
 		bridge::{CliBridgeBase, MessagesCliBridge, RelayToRelayHeadersCliBridge},
 		relay_headers_and_messages::{Full2WayBridgeBase, Full2WayBridgeCommonParams},
+		DefaultClient,
 	},
 	finality::SubstrateFinalitySyncPipeline,
 	on_demand::{headers::OnDemandHeadersRelay, OnDemandRelay},
@@ -42,10 +43,16 @@ use sp_core::Pair;
 pub struct RelayToRelayBridge<
 	L2R: MessagesCliBridge + RelayToRelayHeadersCliBridge,
 	R2L: MessagesCliBridge + RelayToRelayHeadersCliBridge,
+	LeftClnt = DefaultClient<<R2L as CliBridgeBase>::Target>,
+	RightClnt = DefaultClient<<L2R as CliBridgeBase>::Target>,
 > {
 	/// Parameters that are shared by all bridge types.
-	pub common:
-		Full2WayBridgeCommonParams<<R2L as CliBridgeBase>::Target, <L2R as CliBridgeBase>::Target>,
+	pub common: Full2WayBridgeCommonParams<
+		<R2L as CliBridgeBase>::Target,
+		<L2R as CliBridgeBase>::Target,
+		LeftClnt,
+		RightClnt,
+	>,
 }
 
 /// Create set of configuration objects specific to relay-to-relay relayer.
@@ -113,20 +120,24 @@ impl<
 		R2L: CliBridgeBase<Source = Right, Target = Left>
 			+ MessagesCliBridge
 			+ RelayToRelayHeadersCliBridge,
-	> Full2WayBridgeBase for RelayToRelayBridge<L2R, R2L>
+		LeftClnt: Client<Left>,
+		RightClnt: Client<Right>,
+	> Full2WayBridgeBase for RelayToRelayBridge<L2R, R2L, LeftClnt, RightClnt>
 where
 	AccountIdOf<Left>: From<<AccountKeyPairOf<Left> as Pair>::Public>,
 	AccountIdOf<Right>: From<<AccountKeyPairOf<Right> as Pair>::Public>,
 {
-	type Params = RelayToRelayBridge<L2R, R2L>;
+	type Params = RelayToRelayBridge<L2R, R2L, LeftClnt, RightClnt>;
 	type Left = Left;
 	type Right = Right;
+	type LeftClient = LeftClnt;
+	type RightClient = RightClnt;
 
-	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right> {
+	fn common(&self) -> &Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&self.common
 	}
 
-	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Self::Left, Self::Right> {
+	fn mut_common(&mut self) -> &mut Full2WayBridgeCommonParams<Left, Right, LeftClnt, RightClnt> {
 		&mut self.common
 	}
 
@@ -307,3 +307,371 @@ pub async fn relay_single_header<P: SubstrateFinalitySyncPipeline>(
 		)),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::finality_base::engine::Grandpa;
+
+	use bp_header_chain::{
+		justification::verify_justification,
+		storage_keys::{best_finalized_key, current_authority_set_key},
+		AuthoritySet,
+	};
+	use bp_runtime::{ChainId, HeaderId};
+	use bp_test_utils::{authority_list, make_default_justification, TEST_GRANDPA_SET_ID};
+	use codec::{Decode, Encode};
+	use frame_support::weights::Weight;
+	use relay_substrate_client::{
+		simulation::{
+			RuntimeContext, SimulatedAction, SimulatedChain, SimulatedRuntime, SimulatedStorage,
+			Simulation, SimulationScript,
+		},
+		Error as SubstrateError, HeaderIdOf, SignParam, UnsignedTransaction,
+	};
+	use sp_core::{sr25519, H256};
+	use sp_runtime::{
+		traits::{BlakeTwo256, Header as _},
+		StateVersion,
+	};
+	use std::time::Duration;
+
+	const WITH_SOURCE_GRANDPA_PALLET_NAME: &str = "BridgeSourceGrandpa";
+	const BEST_FINALIZED_SOURCE_HEADER_ID_METHOD: &str = "SourceFinalityApi_best_finalized";
+	const MAX_TICKS: u64 = 1_000;
+
+	type SourceHeader = sp_runtime::generic::Header<u32, BlakeTwo256>;
+	type SignedBlock = sp_runtime::generic::SignedBlock<
+		sp_runtime::generic::Block<SourceHeader, sp_runtime::OpaqueExtrinsic>,
+	>;
+
+	#[derive(Clone, Debug)]
+	struct SourceChain;
+
+	impl bp_runtime::Chain for SourceChain {
+		const ID: ChainId = *b"srce";
+
+		type BlockNumber = u32;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = SourceHeader;
+
+		type AccountId = u32;
+		type Balance = u32;
+		type Nonce = u32;
+		type Signature = sp_runtime::testing::TestSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			100_000
+		}
+
+		fn max_extrinsic_weight() -> Weight {
+			Weight::MAX
+		}
+	}
+
+	impl bp_header_chain::ChainWithGrandpa for SourceChain {
+		const WITH_CHAIN_GRANDPA_PALLET_NAME: &'static str = WITH_SOURCE_GRANDPA_PALLET_NAME;
+		const MAX_AUTHORITIES_COUNT: u32 = 16;
+		const REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY: u32 = 8;
+		const MAX_MANDATORY_HEADER_SIZE: u32 = 100_000;
+		const AVERAGE_HEADER_SIZE: u32 = 1_024;
+	}
+
+	impl Chain for SourceChain {
+		const NAME: &'static str = "Source";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
+			BEST_FINALIZED_SOURCE_HEADER_ID_METHOD;
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+			"SourceFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = SignedBlock;
+		type Call = ();
+	}
+
+	impl relay_substrate_client::ChainWithGrandpa for SourceChain {
+		const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
+			"SourceFinalityApi_synced_headers_grandpa_info";
+
+		type KeyOwnerProof = ();
+	}
+
+	#[derive(Clone, Debug, Decode, Encode, PartialEq)]
+	enum TargetCall {
+		SubmitFinalityProof(Box<SourceHeader>, GrandpaJustification<SourceHeader>),
+	}
+
+	#[derive(Clone, Debug, Decode, Encode)]
+	struct TargetTransaction {
+		nonce: u32,
+		call: TargetCall,
+	}
+
+	#[derive(Clone, Debug)]
+	struct TargetChain;
+
+	impl bp_runtime::Chain for TargetChain {
+		const ID: ChainId = *b"trgt";
+
+		type BlockNumber = u32;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = sp_runtime::generic::Header<u32, BlakeTwo256>;
+
+		type AccountId = sp_runtime::AccountId32;
+		type Balance = u64;
+		type Nonce = u32;
+		type Signature = sp_runtime::MultiSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			100_000
+		}
+
+		fn max_extrinsic_weight() -> Weight {
+			Weight::MAX
+		}
+	}
+
+	impl Chain for TargetChain {
+		const NAME: &'static str = "Target";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str = "TargetFinalityApi_best_finalized";
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+			"TargetFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = SignedBlock;
+		type Call = TargetCall;
+	}
+
+	impl ChainWithTransactions for TargetChain {
+		type AccountKeyPair = sr25519::Pair;
+		type SignedTransaction = TargetTransaction;
+
+		fn sign_transaction(
+			_param: SignParam<Self>,
+			unsigned: UnsignedTransaction<Self>,
+		) -> Result<Self::SignedTransaction, SubstrateError> {
+			Ok(TargetTransaction { nonce: unsigned.nonce, call: unsigned.call.into_decoded()? })
+		}
+	}
+
+	#[derive(Clone, Debug)]
+	struct SourceToTarget;
+
+	impl SubstrateFinalityPipeline for SourceToTarget {
+		type SourceChain = SourceChain;
+		type TargetChain = TargetChain;
+		type FinalityEngine = Grandpa<SourceChain>;
+	}
+
+	impl SubstrateFinalitySyncPipeline for SourceToTarget {
+		type SubmitFinalityProofCallBuilder = SourceToTargetCallBuilder;
+	}
+
+	struct SourceToTargetCallBuilder;
+
+	impl SubmitFinalityProofCallBuilder<SourceToTarget> for SourceToTargetCallBuilder {
+		fn build_submit_finality_proof_call(
+			header: SyncHeader<SourceHeader>,
+			proof: GrandpaJustification<SourceHeader>,
+			_is_free_execution_expected: bool,
+			_context: JustificationVerificationContext,
+		) -> TargetCall {
+			TargetCall::SubmitFinalityProof(Box::new(header.into_inner()), proof)
+		}
+	}
+
+	/// Source chain runtime, which is finalizing blocks with justifications, signed by the
+	/// test authorities.
+	struct SourceRuntime;
+
+	impl SimulatedRuntime for SourceRuntime {
+		fn grandpa_justification(&mut self, header: &[u8]) -> Option<Vec<u8>> {
+			let header = SourceHeader::decode(&mut &header[..]).ok()?;
+			Some(make_default_justification(&header).encode())
+		}
+	}
+
+	/// Target chain runtime with the minimal bridge GRANDPA pallet, which is accepting better
+	/// source headers with valid justifications.
+	struct TargetRuntime;
+
+	impl SimulatedRuntime for TargetRuntime {
+		fn call(
+			&mut self,
+			context: RuntimeContext,
+			method: &str,
+			_arguments: &[u8],
+		) -> Result<Vec<u8>, SubstrateError> {
+			match method {
+				BEST_FINALIZED_SOURCE_HEADER_ID_METHOD =>
+					Ok(best_finalized_source_header_id(context.storage)?.encode()),
+				_ => Err(SubstrateError::Custom(format!("Unknown method {method}"))),
+			}
+		}
+
+		fn apply_transaction(
+			&mut self,
+			storage: &mut SimulatedStorage,
+			transaction: &[u8],
+		) -> Result<(), SubstrateError> {
+			let TargetTransaction {
+				call: TargetCall::SubmitFinalityProof(header, justification),
+				..
+			} = Decode::decode(&mut &transaction[..])?;
+
+			let best_finalized = best_finalized_source_header_id(storage)?;
+			if best_finalized.map_or(false, |best_finalized| *header.number() <= best_finalized.0) {
+				return Err(SubstrateError::Custom("Source header is not better".into()))
+			}
+
+			let authority_set = storage
+				.get(&current_authority_set_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0)
+				.map(|authority_set| AuthoritySet::decode(&mut &authority_set[..]))
+				.transpose()?
+				.ok_or_else(|| SubstrateError::Custom("No authority set".into()))?;
+			let context = JustificationVerificationContext::try_from(authority_set)
+				.map_err(|e| SubstrateError::Custom(format!("Invalid authority set: {e:?}")))?;
+			verify_justification::<SourceHeader>(
+				(header.hash(), *header.number()),
+				&context,
+				&justification,
+			)
+			.map_err(|e| SubstrateError::Custom(format!("Invalid justification: {e:?}")))?;
+
+			storage.insert(
+				best_finalized_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0,
+				HeaderId(*header.number(), header.hash()).encode(),
+			);
+			Ok(())
+		}
+	}
+
+	fn best_finalized_source_header_id(
+		storage: &SimulatedStorage,
+	) -> Result<Option<HeaderIdOf<SourceChain>>, SubstrateError> {
+		Ok(storage
+			.get(&best_finalized_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0)
+			.map(|id| HeaderIdOf::<SourceChain>::decode(&mut &id[..]))
+			.transpose()?)
+	}
+
+	/// Target chain storage with initialized bridge GRANDPA pallet.
+	fn target_genesis_storage(source_genesis_hash: H256) -> SimulatedStorage {
+		SimulatedStorage::from([
+			(
+				best_finalized_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0,
+				HeaderId(0u32, source_genesis_hash).encode(),
+			),
+			(
+				current_authority_set_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0,
+				AuthoritySet::new(authority_list(), TEST_GRANDPA_SET_ID).encode(),
+			),
+		])
+	}
+
+	/// Target chain is producing and finalizing block at every tick.
+	fn target_script() -> SimulationScript {
+		SimulationScript::new()
+			.every(1..MAX_TICKS, SimulatedAction::ProduceBlock(Default::default()))
+			.every(1..MAX_TICKS, SimulatedAction::Finalize(None))
+	}
+
+	struct TestBridge {
+		source: SimulatedChain<SourceChain>,
+		target: SimulatedChain<TargetChain>,
+		simulation: Simulation,
+		relay: async_std::task::JoinHandle<anyhow::Result<()>>,
+	}
+
+	impl TestBridge {
+		/// Start the real finality relay between simulated chains.
+		fn start(source_script: SimulationScript, target_script: SimulationScript) -> Self {
+			let source = SimulatedChain::new(SourceRuntime, SimulatedStorage::new(), source_script);
+			let target = SimulatedChain::new(
+				TargetRuntime,
+				target_genesis_storage(source.genesis_hash()),
+				target_script,
+			);
+			let simulation = Simulation::new()
+				.with_participant(source.clone())
+				.with_participant(target.clone());
+			let relay = simulation.spawn(run::<SourceToTarget>(
+				source.client(),
+				target.client(),
+				HeadersToRelay::All,
+				TransactionParams {
+					signer: sr25519::Pair::from_string("//Relayer", None).unwrap(),
+					mortality: None,
+				},
+				MetricsParams::disabled(),
+			));
+
+			TestBridge { source, target, simulation, relay }
+		}
+
+		/// Run simulation until the target chain knows given source header.
+		async fn run_until_synced(&mut self, source_header_number: u32) -> bool {
+			let target = self.target.clone();
+			self.simulation
+				.run_until(MAX_TICKS, || {
+					let target = target.clone();
+					async move {
+						let storage_key = best_finalized_key(WITH_SOURCE_GRANDPA_PALLET_NAME).0;
+						target
+							.best_storage_value(&storage_key)
+							.await
+							.and_then(|id| HeaderIdOf::<SourceChain>::decode(&mut &id[..]).ok())
+							.map_or(false, |id| id.0 >= source_header_number)
+					}
+				})
+				.await
+		}
+
+		async fn stop(self) {
+			assert!(self.relay.cancel().await.is_none(), "Relay has stopped unexpectedly");
+		}
+	}
+
+	#[async_std::test]
+	async fn relay_syncs_finalized_headers() {
+		let mut bridge = TestBridge::start(
+			SimulationScript::new()
+				.every(1..50, SimulatedAction::ProduceBlock(Default::default()))
+				.at(10, SimulatedAction::FinalizeWithJustification)
+				.at(20, SimulatedAction::FinalizeWithJustification)
+				.at(30, SimulatedAction::FinalizeWithJustification),
+			target_script(),
+		);
+
+		assert!(bridge.run_until_synced(30).await);
+		assert_eq!(*bridge.source.best_finalized_header().await.number(), 30);
+		bridge.stop().await;
+	}
+
+	#[async_std::test]
+	async fn relay_survives_reorgs_and_disconnects() {
+		let mut bridge = TestBridge::start(
+			SimulationScript::new()
+				.every(1..50, SimulatedAction::ProduceBlock(Default::default()))
+				.at(10, SimulatedAction::FinalizeWithJustification)
+				.at(12, SimulatedAction::Reorg { depth: 2, blocks: vec![Default::default(); 3] })
+				.at(20, SimulatedAction::FinalizeWithJustification)
+				.at(25, SimulatedAction::Disconnect { ticks: 0 })
+				.at(30, SimulatedAction::FinalizeWithJustification)
+				.at(40, SimulatedAction::FinalizeWithJustification),
+			target_script().at(35, SimulatedAction::Disconnect { ticks: 0 }),
+		);
+
+		// relay has to reconnect to both chains to sync the header, finalized after disconnects
+		assert!(bridge.run_until_synced(40).await);
+		assert!(bridge.source.reconnects().await > 0);
+		assert!(bridge.target.reconnects().await > 0);
+		bridge.stop().await;
+	}
+}
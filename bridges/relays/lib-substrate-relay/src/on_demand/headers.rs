@@ -92,7 +92,7 @@ impl<
 			source_client: source_client.clone(),
 			target_client: target_client.clone(),
 		};
-		async_std::task::spawn(async move {
+		relay_utils::clock::spawn(async move {
 			background_task::<P>(
 				source_client,
 				target_client,
@@ -232,7 +232,7 @@ async fn background_task<P: SubstrateFinalitySyncPipeline>(
 
 	loop {
 		select! {
-			_ = async_std::task::sleep(P::TargetChain::AVERAGE_BLOCK_INTERVAL).fuse() => {},
+			_ = relay_utils::clock::sleep(P::TargetChain::AVERAGE_BLOCK_INTERVAL).fuse() => {},
 			_ = finality_relay_task => {
 				// this should never happen in practice given the current code
 				restart_relay = true;
@@ -103,7 +103,7 @@ impl<
 			on_demand_source_relay_to_target_headers: on_demand_source_relay_to_target_headers
 				.clone(),
 		};
-		async_std::task::spawn(async move {
+		relay_utils::clock::spawn(async move {
 			background_task::<P>(
 				source_relay_client,
 				target_client,
@@ -305,7 +305,7 @@ async fn background_task<P: SubstrateParachainsPipeline>(
 					required_parachain_header_number = new_required_parachain_header_number;
 				}
 			},
-			_ = async_std::task::sleep(P::TargetChain::AVERAGE_BLOCK_INTERVAL).fuse() => {},
+			_ = relay_utils::clock::sleep(P::TargetChain::AVERAGE_BLOCK_INTERVAL).fuse() => {},
 			_ = parachains_relay_task => {
 				// this should never happen in practice given the current code
 				restart_relay = true;
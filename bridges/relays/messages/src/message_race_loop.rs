@@ -327,7 +327,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 		TargetNoncesData = TC::TargetNoncesData,
 	>,
 ) -> Result<(), FailedClient> {
-	let mut progress_context = Instant::now();
+	let mut progress_context = relay_utils::clock::now();
 	let mut race_state = RaceStateImpl::default();
 
 	let mut source_retry_backoff = retry_backoff();
@@ -417,7 +417,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						strategy.source_nonces_updated(at_block, nonces);
 					},
 					&mut source_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error retrieving nonces from {}", P::source_name()),
 				).fail_if_connection_error(FailedClient::Source)?;
 
@@ -443,7 +443,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						strategy.best_target_nonces_updated(nonces, &mut race_state);
 					},
 					&mut target_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error retrieving best nonces from {}", P::target_name()),
 				).fail_if_connection_error(FailedClient::Target)?;
 			},
@@ -464,7 +464,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						strategy.finalized_target_nonces_updated(nonces, &mut race_state);
 					},
 					&mut target_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error retrieving finalized nonces from {}", P::target_name()),
 				).fail_if_connection_error(FailedClient::Target)?;
 			},
@@ -491,7 +491,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						target_batch_transaction = maybe_batch_transaction;
 					},
 					&mut target_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error asking for source headers at {}", P::target_name()),
 				).fail_if_connection_error(FailedClient::Target)?;
 			},
@@ -511,7 +511,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						race_state.nonces_to_submit_batch = batch_transaction;
 					},
 					&mut source_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error generating proof at {}", P::source_name()),
 				).fail_if_error(FailedClient::Source).map(|_| true)?;
 			},
@@ -531,7 +531,7 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 						target_tx_tracker.set(artifacts.tx_tracker.wait().fuse());
 					},
 					&mut target_go_offline_future,
-					async_std::task::sleep,
+					relay_utils::clock::sleep,
 					|| format!("Error submitting proof {}", P::target_name()),
 				).fail_if_connection_error(FailedClient::Target)?;
 
@@ -729,7 +729,7 @@ where
 	P: MessageRace,
 	S: RaceStrategy<P::SourceHeaderId, P::TargetHeaderId, P::Proof>,
 {
-	let now_time = Instant::now();
+	let now_time = relay_utils::clock::now();
 
 	let need_update = now_time.saturating_duration_since(prev_time) > Duration::from_secs(10);
 	if !need_update {
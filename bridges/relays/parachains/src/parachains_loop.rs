@@ -275,7 +275,7 @@ where
 		// it doesn't make sense to perform one more loop iteration.
 		select_biased! {
 			_ = exit_signal => return Ok(()),
-			_ = async_std::task::sleep(min_block_interval).fuse() => {},
+			_ = relay_utils::clock::sleep(min_block_interval).fuse() => {},
 		}
 
 		// if source client is not yet synced, we'll need to sleep. Otherwise we risk submitting too
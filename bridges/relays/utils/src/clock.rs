@@ -0,0 +1,371 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Time source of relays.
+//!
+//! Relays are reading current time, sleeping and spawning background tasks using functions of
+//! this module. Normally, the real time is used. But if the task is running within the
+//! [`SimulatedClock::scope`], it is using the simulated time instead. The simulated time only
+//! advances when the clock owner calls [`SimulatedClock::advance`], so relays may be tested
+//! without waiting for the real time to pass.
+
+use parking_lot::Mutex;
+use std::{
+	cell::RefCell,
+	collections::{BTreeMap, HashMap},
+	future::Future,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll, Wake, Waker},
+	time::{Duration, Instant},
+};
+
+thread_local! {
+	/// Simulated clock of the task that is currently polled by this thread.
+	static CURRENT_CLOCK: RefCell<Option<SimulatedClock>> = const { RefCell::new(None) };
+}
+
+/// Returns current time.
+pub fn now() -> Instant {
+	match current_clock() {
+		Some(clock) => clock.now(),
+		None => Instant::now(),
+	}
+}
+
+/// Sleep given amount of time.
+pub async fn sleep(duration: Duration) {
+	match current_clock() {
+		Some(clock) => clock.sleep(duration).await,
+		None => async_std::task::sleep(duration).await,
+	}
+}
+
+/// Spawn background task. If it is spawned by the task that is using the simulated time, the
+/// spawned task is using the same simulated time.
+pub fn spawn<F>(future: F) -> async_std::task::JoinHandle<F::Output>
+where
+	F: Future + Send + 'static,
+	F::Output: Send + 'static,
+{
+	match current_clock() {
+		Some(clock) => async_std::task::spawn(clock.scope(future)),
+		None => async_std::task::spawn(future),
+	}
+}
+
+/// Returns simulated clock of the current task.
+fn current_clock() -> Option<SimulatedClock> {
+	CURRENT_CLOCK.with(|clock| clock.borrow().clone())
+}
+
+/// Simulated time.
+///
+/// All clones of the clock are sharing the same time. The clock also keeps track of tasks that
+/// are running within its scope, so the clock owner may wait until all of them are blocked,
+/// before advancing the time.
+#[derive(Clone)]
+pub struct SimulatedClock {
+	start: Instant,
+	state: Arc<Mutex<ClockState>>,
+}
+
+/// State of the simulated clock.
+#[derive(Default)]
+struct ClockState {
+	/// Simulated time, passed since the clock has been created.
+	elapsed: Duration,
+	/// Identifier of the next sleeper or task.
+	next_id: u64,
+	/// Sleeping futures, ordered by their deadline.
+	sleepers: BTreeMap<(Duration, u64), Waker>,
+	/// States of tasks within the clock scope.
+	tasks: HashMap<u64, TaskState>,
+	/// Futures that are waiting until all tasks are blocked.
+	idle_waiters: Vec<Waker>,
+}
+
+/// State of the task within the clock scope.
+#[derive(Clone, Copy, Default)]
+struct TaskState {
+	/// The task has returned `Poll::Pending` and has not been woken since then.
+	is_idle: bool,
+	/// The task has been woken while it has been polled.
+	is_woken: bool,
+}
+
+impl ClockState {
+	/// Allocate new identifier.
+	fn next_id(&mut self) -> u64 {
+		self.next_id += 1;
+		self.next_id
+	}
+
+	/// Returns `true` if all tasks are blocked.
+	fn is_idle(&self) -> bool {
+		self.tasks.values().all(|task| task.is_idle)
+	}
+
+	/// Take idle waiters if all tasks are blocked.
+	fn take_idle_waiters(&mut self) -> Vec<Waker> {
+		if self.is_idle() {
+			std::mem::take(&mut self.idle_waiters)
+		} else {
+			Vec::new()
+		}
+	}
+}
+
+impl Default for SimulatedClock {
+	fn default() -> Self {
+		SimulatedClock { start: Instant::now(), state: Default::default() }
+	}
+}
+
+impl SimulatedClock {
+	/// Create new clock.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns simulated time, passed since the clock has been created.
+	pub fn elapsed(&self) -> Duration {
+		self.state.lock().elapsed
+	}
+
+	/// Returns current simulated time.
+	pub fn now(&self) -> Instant {
+		self.start + self.elapsed()
+	}
+
+	/// Advance the simulated time, waking all futures that are sleeping until then.
+	pub fn advance(&self, duration: Duration) {
+		let due_sleepers = {
+			let mut state = self.state.lock();
+			state.elapsed += duration;
+			let not_due_sleepers = state.sleepers.split_off(&(state.elapsed, u64::MAX));
+			std::mem::replace(&mut state.sleepers, not_due_sleepers)
+		};
+		due_sleepers.into_values().for_each(Waker::wake);
+	}
+
+	/// Sleep given amount of simulated time.
+	pub fn sleep(&self, duration: Duration) -> Sleep {
+		let mut state = self.state.lock();
+		let deadline = state.elapsed + duration;
+		let id = state.next_id();
+		Sleep { clock: self.clone(), deadline, id }
+	}
+
+	/// Wrap the future, so it is using the simulated time. Tasks that are spawned by the future
+	/// are also using the simulated time.
+	pub fn scope<F: Future>(&self, future: F) -> Scoped<F> {
+		let id = {
+			let mut state = self.state.lock();
+			let id = state.next_id();
+			state.tasks.insert(id, TaskState::default());
+			id
+		};
+		Scoped {
+			clock: self.clone(),
+			waker: Arc::new(ScopedWaker { id, state: self.state.clone(), waker: Mutex::new(None) }),
+			future: Box::pin(future),
+		}
+	}
+
+	/// Wait until all tasks within the clock scope are blocked. Tasks are blocked if they are
+	/// waiting for the simulated time to advance, or for some external event.
+	pub fn wait_until_idle(&self) -> impl Future<Output = ()> + '_ {
+		futures::future::poll_fn(move |cx| {
+			let mut state = self.state.lock();
+			if state.is_idle() {
+				return Poll::Ready(())
+			}
+
+			state.idle_waiters.push(cx.waker().clone());
+			Poll::Pending
+		})
+	}
+}
+
+/// Future that completes when the simulated time reaches its deadline.
+pub struct Sleep {
+	clock: SimulatedClock,
+	deadline: Duration,
+	id: u64,
+}
+
+impl Future for Sleep {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+		let mut state = self.clock.state.lock();
+		if state.elapsed >= self.deadline {
+			return Poll::Ready(())
+		}
+
+		state.sleepers.insert((self.deadline, self.id), cx.waker().clone());
+		Poll::Pending
+	}
+}
+
+impl Drop for Sleep {
+	fn drop(&mut self) {
+		self.clock.state.lock().sleepers.remove(&(self.deadline, self.id));
+	}
+}
+
+/// Future that is running within the simulated clock scope.
+pub struct Scoped<F> {
+	clock: SimulatedClock,
+	waker: Arc<ScopedWaker>,
+	future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+	type Output = F::Output;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+		let id = self.waker.id;
+		if let Some(task) = self.clock.state.lock().tasks.get_mut(&id) {
+			*task = TaskState::default();
+		}
+		*self.waker.waker.lock() = Some(cx.waker().clone());
+
+		let previous_clock =
+			CURRENT_CLOCK.with(|clock| clock.borrow_mut().replace(self.clock.clone()));
+		let waker = Waker::from(self.waker.clone());
+		let result = self.future.as_mut().poll(&mut Context::from_waker(&waker));
+		CURRENT_CLOCK.with(|clock| *clock.borrow_mut() = previous_clock);
+
+		let idle_waiters = {
+			let mut state = self.clock.state.lock();
+			match result {
+				Poll::Ready(_) => {
+					state.tasks.remove(&id);
+				},
+				Poll::Pending =>
+					if let Some(task) = state.tasks.get_mut(&id) {
+						task.is_idle = !task.is_woken;
+					},
+			}
+			state.take_idle_waiters()
+		};
+		idle_waiters.into_iter().for_each(Waker::wake);
+
+		result
+	}
+}
+
+impl<F> Drop for Scoped<F> {
+	fn drop(&mut self) {
+		let idle_waiters = {
+			let mut state = self.clock.state.lock();
+			state.tasks.remove(&self.waker.id);
+			state.take_idle_waiters()
+		};
+		idle_waiters.into_iter().for_each(Waker::wake);
+	}
+}
+
+/// Waker of the task within the simulated clock scope. It marks the task as not blocked and
+/// then wakes it using the executor waker.
+struct ScopedWaker {
+	id: u64,
+	state: Arc<Mutex<ClockState>>,
+	waker: Mutex<Option<Waker>>,
+}
+
+impl Wake for ScopedWaker {
+	fn wake(self: Arc<Self>) {
+		self.wake_by_ref()
+	}
+
+	fn wake_by_ref(self: &Arc<Self>) {
+		if let Some(task) = self.state.lock().tasks.get_mut(&self.id) {
+			*task = TaskState { is_idle: false, is_woken: true };
+		}
+		if let Some(waker) = self.waker.lock().as_ref() {
+			waker.wake_by_ref();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_std::task::block_on;
+
+	#[test]
+	fn real_time_is_used_outside_of_simulated_clock_scope() {
+		let before = Instant::now();
+		block_on(sleep(Duration::from_millis(1)));
+		assert!(now() - before >= Duration::from_millis(1));
+	}
+
+	#[test]
+	fn simulated_time_is_used_within_simulated_clock_scope() {
+		let clock = SimulatedClock::new();
+		let task = async_std::task::spawn(clock.scope(async {
+			let before = now();
+			sleep(Duration::from_secs(3600)).await;
+			now() - before
+		}));
+
+		block_on(clock.wait_until_idle());
+		clock.advance(Duration::from_secs(1800));
+		block_on(clock.wait_until_idle());
+		clock.advance(Duration::from_secs(1800));
+		assert_eq!(block_on(task), Duration::from_secs(3600));
+	}
+
+	#[test]
+	fn spawned_tasks_inherit_simulated_clock() {
+		let clock = SimulatedClock::new();
+		let task = async_std::task::spawn(clock.scope(async {
+			spawn(async { sleep(Duration::from_secs(60)).await }).await;
+			now()
+		}));
+
+		block_on(clock.wait_until_idle());
+		clock.advance(Duration::from_secs(60));
+		assert_eq!(block_on(task), clock.now());
+		block_on(clock.wait_until_idle());
+	}
+
+	#[test]
+	fn clock_is_not_idle_until_tasks_are_blocked() {
+		let clock = SimulatedClock::new();
+		let (sender, receiver) = async_std::channel::unbounded::<u32>();
+		let task = async_std::task::spawn(clock.scope(async move {
+			let mut sum = 0;
+			while let Ok(value) = receiver.recv().await {
+				sum += value;
+				sleep(Duration::from_secs(1)).await;
+			}
+			sum
+		}));
+
+		for value in 1..=3 {
+			block_on(sender.send(value)).unwrap();
+			block_on(clock.wait_until_idle());
+			clock.advance(Duration::from_secs(1));
+		}
+		drop(sender);
+		assert_eq!(block_on(task), 6);
+		assert_eq!(clock.elapsed(), Duration::from_secs(3));
+	}
+}
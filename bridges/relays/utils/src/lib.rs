@@ -44,6 +44,7 @@ pub const MAX_BACKOFF_INTERVAL: Duration = Duration::from_secs(60);
 /// reconnection again.
 pub const CONNECTION_ERROR_DELAY: Duration = Duration::from_secs(10);
 
+pub mod clock;
 pub mod error;
 pub mod initialize;
 pub mod metrics;
@@ -208,7 +209,7 @@ pub fn format_ids<Id: std::fmt::Debug>(mut ids: impl ExactSizeIterator<Item = Id
 /// Stream that emits item every `timeout_ms` milliseconds.
 pub fn interval(timeout: Duration) -> impl futures::Stream<Item = ()> {
 	futures::stream::unfold((), move |_| async move {
-		async_std::task::sleep(timeout).await;
+		clock::sleep(timeout).await;
 		Some(((), ()))
 	})
 }
@@ -82,11 +82,11 @@ pub trait StandaloneMetric: Metric {
 
 	/// Spawn the self update task that will keep update metric value at given intervals.
 	fn spawn(self) {
-		async_std::task::spawn(async move {
+		crate::clock::spawn(async move {
 			let update_interval = self.update_interval();
 			loop {
 				self.update().await;
-				async_std::task::sleep(update_interval).await;
+				crate::clock::sleep(update_interval).await;
 			}
 		});
 	}
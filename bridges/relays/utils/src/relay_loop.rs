@@ -49,7 +49,7 @@ pub trait Client: 'static + Clone + Send + Sync {
 						error,
 					);
 
-					async_std::task::sleep(delay).await;
+					crate::clock::sleep(delay).await;
 				},
 			}
 		}
@@ -165,7 +165,7 @@ impl<SC, TC, LM> Loop<SC, TC, LM> {
 			Ok(())
 		};
 
-		async_std::task::spawn(run_loop_task).await
+		crate::clock::spawn(run_loop_task).await
 	}
 }
 
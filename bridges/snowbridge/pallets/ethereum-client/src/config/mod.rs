@@ -6,26 +6,62 @@ use static_assertions::const_assert;
 /// Generalized Indices
 /// related to Merkle proofs
 /// get_generalized_index(BeaconState, 'block_roots')
-pub const BLOCK_ROOTS_INDEX: usize = 37;
+pub const BLOCK_ROOTS_INDEX: usize = altair::BLOCK_ROOTS_INDEX;
 pub const BLOCK_ROOTS_SUBTREE_INDEX: usize = subtree_index(BLOCK_ROOTS_INDEX);
 pub const BLOCK_ROOTS_DEPTH: usize = generalized_index_length(BLOCK_ROOTS_INDEX);
 
 /// get_generalized_index(BeaconState, 'finalized_checkpoint', 'root')
-pub const FINALIZED_ROOT_INDEX: usize = 105;
+pub const FINALIZED_ROOT_INDEX: usize = altair::FINALIZED_ROOT_INDEX;
 pub const FINALIZED_ROOT_SUBTREE_INDEX: usize = subtree_index(FINALIZED_ROOT_INDEX);
 pub const FINALIZED_ROOT_DEPTH: usize = generalized_index_length(FINALIZED_ROOT_INDEX);
 
 /// get_generalized_index(BeaconState, 'current_sync_committee')
-pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = 54;
+pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = altair::CURRENT_SYNC_COMMITTEE_INDEX;
 pub const CURRENT_SYNC_COMMITTEE_SUBTREE_INDEX: usize = subtree_index(CURRENT_SYNC_COMMITTEE_INDEX);
 pub const CURRENT_SYNC_COMMITTEE_DEPTH: usize =
 	generalized_index_length(CURRENT_SYNC_COMMITTEE_INDEX);
 
 /// get_generalized_index(BeaconState, 'next_sync_committee')
-pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 55;
+pub const NEXT_SYNC_COMMITTEE_INDEX: usize = altair::NEXT_SYNC_COMMITTEE_INDEX;
 pub const NEXT_SYNC_COMMITTEE_SUBTREE_INDEX: usize = subtree_index(NEXT_SYNC_COMMITTEE_INDEX);
 pub const NEXT_SYNC_COMMITTEE_DEPTH: usize = generalized_index_length(NEXT_SYNC_COMMITTEE_INDEX);
 
+/// get_generalized_index(BeaconBlockHeader, 'state_root')
+pub const STATE_ROOT_INDEX: usize = 11;
+pub const STATE_ROOT_SUBTREE_INDEX: usize = subtree_index(STATE_ROOT_INDEX);
+pub const STATE_ROOT_DEPTH: usize = generalized_index_length(STATE_ROOT_INDEX);
+
+/// BeaconState generalized indices from Altair up to and including Deneb. The BeaconState
+/// has less than 32 fields, so its fields are leaves at depth 5.
+pub mod altair {
+	/// get_generalized_index(BeaconState, 'block_roots')
+	pub const BLOCK_ROOTS_INDEX: usize = 37;
+	/// get_generalized_index(BeaconState, 'finalized_checkpoint', 'root')
+	pub const FINALIZED_ROOT_INDEX: usize = 105;
+	/// get_generalized_index(BeaconState, 'current_sync_committee')
+	pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = 54;
+	/// get_generalized_index(BeaconState, 'next_sync_committee')
+	pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 55;
+	/// get_generalized_index(BeaconState, 'historical_summaries'), added in Capella
+	pub const HISTORICAL_SUMMARIES_INDEX: usize = 59;
+}
+
+/// BeaconState generalized indices from Electra onwards. Electra grows the BeaconState past 32
+/// fields, so every field moves one level deeper in the tree (depth 6).
+/// <https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/light-client/sync-protocol.md#new-constants>
+pub mod electra {
+	/// get_generalized_index(BeaconState, 'block_roots')
+	pub const BLOCK_ROOTS_INDEX: usize = 69;
+	/// get_generalized_index(BeaconState, 'finalized_checkpoint', 'root')
+	pub const FINALIZED_ROOT_INDEX: usize = 169;
+	/// get_generalized_index(BeaconState, 'current_sync_committee')
+	pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = 86;
+	/// get_generalized_index(BeaconState, 'next_sync_committee')
+	pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 87;
+	/// get_generalized_index(BeaconState, 'historical_summaries')
+	pub const HISTORICAL_SUMMARIES_INDEX: usize = 91;
+}
+
 ///  get_generalized_index(BeaconBlockBody, 'execution_payload')
 pub const EXECUTION_HEADER_INDEX: usize = 25;
 pub const EXECUTION_HEADER_SUBTREE_INDEX: usize = subtree_index(EXECUTION_HEADER_INDEX);
@@ -64,3 +100,8 @@ pub const SYNC_COMMITTEE_BITS_SIZE: usize = SYNC_COMMITTEE_SIZE / 8;
 pub const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
 /// The index of the block_roots field in the beacon state tree.
 pub const BLOCK_ROOT_AT_INDEX_DEPTH: usize = 13;
+/// The maximum length of the `historical_summaries` list in the beacon state.
+pub const HISTORICAL_ROOTS_LIMIT: usize = 16_777_216;
+/// Depth of a `block_summary_root` in the `historical_summaries` list tree: the list chunks,
+/// the length mix-in and the two fields of a `HistoricalSummary`.
+pub const BLOCK_SUMMARY_ROOT_DEPTH: usize = HISTORICAL_ROOTS_LIMIT.ilog2() as usize + 2;
@@ -2,7 +2,7 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 use super::*;
 use frame_support::ensure;
-use snowbridge_beacon_primitives::ExecutionProof;
+use snowbridge_beacon_primitives::{AncestryProof, ExecutionProof, HistoricalSummariesProof};
 
 use snowbridge_core::inbound::{
 	VerificationError::{self, *},
@@ -105,7 +105,7 @@ impl<T: Config> Pallet<T> {
 			.map_err(|_| Error::<T>::HeaderHashTreeRootFailed)?;
 
 		match &execution_proof.ancestry_proof {
-			Some(proof) => {
+			AncestryProof::BlockRoots(proof) => {
 				Self::verify_ancestry_proof(
 					beacon_block_root,
 					execution_proof.header.slot,
@@ -113,7 +113,14 @@ impl<T: Config> Pallet<T> {
 					proof.finalized_block_root,
 				)?;
 			},
-			None => {
+			AncestryProof::HistoricalSummaries(proof) => {
+				Self::verify_historical_summaries_proof(
+					beacon_block_root,
+					execution_proof.header.slot,
+					proof,
+				)?;
+			},
+			AncestryProof::Finalized => {
 				// If the ancestry proof is not provided, we expect this beacon header to be a
 				// finalized beacon header. We need to check that the header hash matches the
 				// finalized header root at the expected slot.
@@ -158,4 +165,92 @@ impl<T: Config> Pallet<T> {
 
 		Ok(())
 	}
+
+	/// Verify that `block_root` is an ancestor of `proof.finalized_block_root` through the
+	/// `historical_summaries` of the finalized BeaconState. Unlike `verify_ancestry_proof`, this
+	/// also works for headers that are older than the `block_roots` of any stored finalized
+	/// state.
+	fn verify_historical_summaries_proof(
+		block_root: H256,
+		block_slot: u64,
+		proof: &HistoricalSummariesProof,
+	) -> DispatchResult {
+		let state = <FinalizedBeaconState<T>>::get(proof.finalized_block_root)
+			.ok_or(Error::<T>::ExpectedFinalizedHeaderNotStored)?;
+
+		// A historical summary is only appended once all the slots of its period are processed,
+		// and the list starts at the Capella fork.
+		let slots_per_historical_root = SLOTS_PER_HISTORICAL_ROOT as u64;
+		let block_period = block_slot / slots_per_historical_root;
+		let capella_slot = T::ForkVersions::get()
+			.capella
+			.epoch
+			.saturating_mul(config::SLOTS_PER_EPOCH as u64);
+		let capella_period = capella_slot / slots_per_historical_root;
+		ensure!(
+			block_period >= capella_period &&
+				block_period.saturating_add(1).saturating_mul(slots_per_historical_root) <=
+					state.slot,
+			Error::<T>::HistoricalSummaryNotAvailable
+		);
+		let summary_index = (block_period - capella_period) as usize;
+		ensure!(
+			summary_index < config::HISTORICAL_ROOTS_LIMIT,
+			Error::<T>::HistoricalSummaryNotAvailable
+		);
+
+		// The header is in the `block_roots` of its period.
+		let index_in_array = block_slot % slots_per_historical_root;
+		let leaf_index = slots_per_historical_root + index_in_array;
+		ensure!(
+			verify_merkle_branch(
+				block_root,
+				&proof.header_branch,
+				leaf_index as usize,
+				config::BLOCK_ROOT_AT_INDEX_DEPTH,
+				proof.block_summary_root
+			),
+			Error::<T>::InvalidAncestryMerkleProof
+		);
+
+		// The `block_roots` of the period are summarized in the `historical_summaries` list.
+		// `block_summary_root` is the first field of the `HistoricalSummary` container.
+		ensure!(
+			verify_merkle_branch(
+				proof.block_summary_root,
+				&proof.block_summary_root_branch,
+				summary_index << 1,
+				config::BLOCK_SUMMARY_ROOT_DEPTH,
+				proof.historical_summaries_root
+			),
+			Error::<T>::InvalidAncestryMerkleProof
+		);
+
+		// The `historical_summaries` list is in the finalized BeaconState.
+		let historical_summaries_gindex = Self::historical_summaries_gindex_at_slot(state.slot);
+		ensure!(
+			verify_merkle_branch(
+				proof.historical_summaries_root,
+				&proof.historical_summaries_branch,
+				subtree_index(historical_summaries_gindex),
+				generalized_index_length(historical_summaries_gindex),
+				proof.finalized_state_root
+			),
+			Error::<T>::InvalidAncestryMerkleProof
+		);
+
+		// The finalized BeaconState belongs to the finalized header.
+		ensure!(
+			verify_merkle_branch(
+				proof.finalized_state_root,
+				&proof.finalized_state_root_branch,
+				config::STATE_ROOT_SUBTREE_INDEX,
+				config::STATE_ROOT_DEPTH,
+				proof.finalized_block_root
+			),
+			Error::<T>::InvalidAncestryMerkleProof
+		);
+
+		Ok(())
+	}
 }
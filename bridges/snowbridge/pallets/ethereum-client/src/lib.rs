@@ -40,8 +40,10 @@ use frame_support::{
 };
 use frame_system::ensure_signed;
 use snowbridge_beacon_primitives::{
-	fast_aggregate_verify, verify_merkle_branch, verify_receipt_proof, BeaconHeader, BlsError,
-	CompactBeaconState, ForkData, ForkVersion, ForkVersions, PublicKeyPrepared, SigningData,
+	fast_aggregate_verify,
+	merkle_proof::{generalized_index_length, subtree_index},
+	verify_merkle_branch, verify_receipt_proof, BeaconHeader, BlsError, CompactBeaconState,
+	ForkData, ForkVersion, ForkVersions, PublicKeyPrepared, SigningData,
 };
 use snowbridge_core::{BasicOperatingMode, RingBufferMap};
 use sp_core::H256;
@@ -88,6 +90,10 @@ pub mod pallet {
 		/// Minimum gap between finalized headers for an update to be free.
 		#[pallet::constant]
 		type FreeHeadersInterval: Get<u32>;
+		/// Maximal number of sync committee updates that may be submitted in a single
+		/// `catch_up` call.
+		#[pallet::constant]
+		type MaxCatchUpPeriods: Get<u32>;
 		type WeightInfo: WeightInfo;
 	}
 
@@ -119,6 +125,9 @@ pub mod pallet {
 		InvalidSyncCommitteeMerkleProof,
 		InvalidExecutionHeaderProof,
 		InvalidAncestryMerkleProof,
+		/// The header precedes Capella, or its historical summary has not been recorded in the
+		/// finalized BeaconState yet.
+		HistoricalSummaryNotAvailable,
 		InvalidBlockRootsRootMerkleProof,
 		/// The gap between the finalized headers is larger than the sync committee period,
 		/// rendering execution headers unprovable using ancestry proofs (blocks root size is
@@ -140,6 +149,8 @@ pub mod pallet {
 		ExecutionHeaderTooFarBehind,
 		ExecutionHeaderSkippedBlock,
 		Halted,
+		/// The `catch_up` call has no updates.
+		EmptyCatchUp,
 	}
 
 	/// Latest imported checkpoint root
@@ -220,6 +231,29 @@ pub mod pallet {
 			Self::process_update(&update)
 		}
 
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::submit_with_sync_committee().saturating_mul(updates.len() as u64)
+		)]
+		#[transactional]
+		/// Submits a chain of consecutive sync committee updates, so that a light client that has
+		/// fallen more than one sync committee period behind may recover without
+		/// `force_checkpoint`. Every update must carry the next sync committee and is verified
+		/// against the sync committee imported by the previous one. The call is free if all
+		/// updates are imported.
+		pub fn catch_up(
+			origin: OriginFor<T>,
+			updates: BoundedVec<Update, T::MaxCatchUpPeriods>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(!Self::operating_mode().is_halted(), Error::<T>::Halted);
+			ensure!(!updates.is_empty(), Error::<T>::EmptyCatchUp);
+			for update in updates.iter() {
+				Self::process_sync_committee_handover(update)?;
+			}
+			Ok(Pays::No.into())
+		}
+
 		/// Halt or resume all pallet operations. May only be called by root.
 		#[pallet::call_index(3)]
 		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
@@ -246,12 +280,14 @@ pub mod pallet {
 				.map_err(|_| Error::<T>::SyncCommitteeHashTreeRootFailed)?;
 
 			// Verifies the sync committee in the Beacon state.
+			let sync_committee_gindex =
+				Self::current_sync_committee_gindex_at_slot(update.header.slot);
 			ensure!(
 				verify_merkle_branch(
 					sync_committee_root,
 					&update.current_sync_committee_branch,
-					config::CURRENT_SYNC_COMMITTEE_SUBTREE_INDEX,
-					config::CURRENT_SYNC_COMMITTEE_DEPTH,
+					subtree_index(sync_committee_gindex),
+					generalized_index_length(sync_committee_gindex),
 					update.header.state_root
 				),
 				Error::<T>::InvalidSyncCommitteeMerkleProof
@@ -265,12 +301,13 @@ pub mod pallet {
 			// This is used for ancestry proofs in ExecutionHeader updates. This verifies the
 			// BeaconState: the beacon state root is the tree root; the `block_roots` hash is the
 			// tree leaf.
+			let block_roots_gindex = Self::block_roots_gindex_at_slot(update.header.slot);
 			ensure!(
 				verify_merkle_branch(
 					update.block_roots_root,
 					&update.block_roots_branch,
-					config::BLOCK_ROOTS_SUBTREE_INDEX,
-					config::BLOCK_ROOTS_DEPTH,
+					subtree_index(block_roots_gindex),
+					generalized_index_length(block_roots_gindex),
 					update.header.state_root
 				),
 				Error::<T>::InvalidBlockRootsRootMerkleProof
@@ -290,15 +327,44 @@ pub mod pallet {
 		}
 
 		pub(crate) fn process_update(update: &Update) -> DispatchResultWithPostInfo {
-			Self::verify_update(update)?;
+			Self::verify_update(update, false)?;
 			Self::apply_update(update)
 		}
 
+		/// Imports a single sync committee update of the `catch_up` chain. If the next sync
+		/// committee is unknown, the update must provide it. Otherwise the update must hand over
+		/// to the next period, i.e. its finalized header must be in the period that follows the
+		/// store period.
+		///
+		/// The finalized header gap is not limited here: execution headers in the gap that are
+		/// older than `SLOTS_PER_HISTORICAL_ROOT` may still be proven using historical summaries.
+		pub(crate) fn process_sync_committee_handover(update: &Update) -> DispatchResult {
+			ensure!(
+				update.next_sync_committee_update.is_some(),
+				Error::<T>::SyncCommitteeUpdateRequired
+			);
+			if <NextSyncCommittee<T>>::exists() {
+				let latest_finalized_state =
+					FinalizedBeaconState::<T>::get(LatestFinalizedBlockRoot::<T>::get())
+						.ok_or(Error::<T>::NotBootstrapped)?;
+				ensure!(
+					compute_period(update.finalized_header.slot) ==
+						compute_period(latest_finalized_state.slot) + 1,
+					Error::<T>::InvalidSyncCommitteeUpdate
+				);
+			}
+
+			Self::verify_update(update, true)?;
+			Self::apply_update(update).map_err(|e| e.error)?;
+			Ok(())
+		}
+
 		/// References and strictly follows <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#validate_light_client_update>
 		/// Verifies that provided next sync committee is valid through a series of checks
 		/// (including checking that a sync committee period isn't skipped and that the header is
-		/// signed by the current sync committee.
-		fn verify_update(update: &Update) -> DispatchResult {
+		/// signed by the current sync committee. The finalized header gap check is skipped if
+		/// `allow_finalized_header_gap` is set.
+		fn verify_update(update: &Update, allow_finalized_header_gap: bool) -> DispatchResult {
 			// Verify sync committee has sufficient participants.
 			let participation =
 				decompress_sync_committee_bits(update.sync_aggregate.sync_committee_bits);
@@ -341,10 +407,11 @@ pub mod pallet {
 			// header is not larger than the sync committee period, otherwise we cannot do
 			// ancestry proofs for execution headers in the gap.
 			ensure!(
-				latest_finalized_state
-					.slot
-					.saturating_add(config::SLOTS_PER_HISTORICAL_ROOT as u64) >=
-					update.finalized_header.slot,
+				allow_finalized_header_gap ||
+					latest_finalized_state
+						.slot
+						.saturating_add(config::SLOTS_PER_HISTORICAL_ROOT as u64) >=
+						update.finalized_header.slot,
 				Error::<T>::InvalidFinalizedHeaderGap
 			);
 
@@ -354,12 +421,14 @@ pub mod pallet {
 				.finalized_header
 				.hash_tree_root()
 				.map_err(|_| Error::<T>::HeaderHashTreeRootFailed)?;
+			let finalized_root_gindex =
+				Self::finalized_root_gindex_at_slot(update.attested_header.slot);
 			ensure!(
 				verify_merkle_branch(
 					finalized_block_root,
 					&update.finality_branch,
-					config::FINALIZED_ROOT_SUBTREE_INDEX,
-					config::FINALIZED_ROOT_DEPTH,
+					subtree_index(finalized_root_gindex),
+					generalized_index_length(finalized_root_gindex),
 					update.attested_header.state_root
 				),
 				Error::<T>::InvalidHeaderMerkleProof
@@ -368,12 +437,13 @@ pub mod pallet {
 			// Though following check does not belong to ALC spec we verify block_roots_root to
 			// match the finalized checkpoint root saved in the state of `finalized_header` so to
 			// cache it for later use in `verify_ancestry_proof`.
+			let block_roots_gindex = Self::block_roots_gindex_at_slot(update.finalized_header.slot);
 			ensure!(
 				verify_merkle_branch(
 					update.block_roots_root,
 					&update.block_roots_branch,
-					config::BLOCK_ROOTS_SUBTREE_INDEX,
-					config::BLOCK_ROOTS_DEPTH,
+					subtree_index(block_roots_gindex),
+					generalized_index_length(block_roots_gindex),
 					update.finalized_header.state_root
 				),
 				Error::<T>::InvalidBlockRootsRootMerkleProof
@@ -393,12 +463,14 @@ pub mod pallet {
 						Error::<T>::InvalidSyncCommitteeUpdate
 					);
 				}
+				let next_sync_committee_gindex =
+					Self::next_sync_committee_gindex_at_slot(update.attested_header.slot);
 				ensure!(
 					verify_merkle_branch(
 						sync_committee_root,
 						&next_sync_committee_update.next_sync_committee_branch,
-						config::NEXT_SYNC_COMMITTEE_SUBTREE_INDEX,
-						config::NEXT_SYNC_COMMITTEE_DEPTH,
+						subtree_index(next_sync_committee_gindex),
+						generalized_index_length(next_sync_committee_gindex),
 						update.attested_header.state_root
 					),
 					Error::<T>::InvalidSyncCommitteeMerkleProof
@@ -604,6 +676,9 @@ pub mod pallet {
 
 		/// Returns the fork version based on the current epoch.
 		pub(super) fn select_fork_version(fork_versions: &ForkVersions, epoch: u64) -> ForkVersion {
+			if epoch >= fork_versions.electra.epoch {
+				return fork_versions.electra.version
+			}
 			if epoch >= fork_versions.deneb.epoch {
 				return fork_versions.deneb.version
 			}
@@ -619,6 +694,76 @@ pub mod pallet {
 			fork_versions.genesis.version
 		}
 
+		/// Returns the generalized index of the finalized checkpoint root in the BeaconState of
+		/// the block at `slot`.
+		pub(super) fn finalized_root_gindex_at_slot(slot: u64) -> usize {
+			Self::select_gindex(
+				&T::ForkVersions::get(),
+				slot,
+				config::altair::FINALIZED_ROOT_INDEX,
+				config::electra::FINALIZED_ROOT_INDEX,
+			)
+		}
+
+		/// Returns the generalized index of the current sync committee in the BeaconState of the
+		/// block at `slot`.
+		pub(super) fn current_sync_committee_gindex_at_slot(slot: u64) -> usize {
+			Self::select_gindex(
+				&T::ForkVersions::get(),
+				slot,
+				config::altair::CURRENT_SYNC_COMMITTEE_INDEX,
+				config::electra::CURRENT_SYNC_COMMITTEE_INDEX,
+			)
+		}
+
+		/// Returns the generalized index of the next sync committee in the BeaconState of the
+		/// block at `slot`.
+		pub(super) fn next_sync_committee_gindex_at_slot(slot: u64) -> usize {
+			Self::select_gindex(
+				&T::ForkVersions::get(),
+				slot,
+				config::altair::NEXT_SYNC_COMMITTEE_INDEX,
+				config::electra::NEXT_SYNC_COMMITTEE_INDEX,
+			)
+		}
+
+		/// Returns the generalized index of the `block_roots` field in the BeaconState of the
+		/// block at `slot`.
+		pub(super) fn block_roots_gindex_at_slot(slot: u64) -> usize {
+			Self::select_gindex(
+				&T::ForkVersions::get(),
+				slot,
+				config::altair::BLOCK_ROOTS_INDEX,
+				config::electra::BLOCK_ROOTS_INDEX,
+			)
+		}
+
+		/// Returns the generalized index of the `historical_summaries` field in the BeaconState
+		/// of the block at `slot`.
+		pub(super) fn historical_summaries_gindex_at_slot(slot: u64) -> usize {
+			Self::select_gindex(
+				&T::ForkVersions::get(),
+				slot,
+				config::altair::HISTORICAL_SUMMARIES_INDEX,
+				config::electra::HISTORICAL_SUMMARIES_INDEX,
+			)
+		}
+
+		/// Returns the BeaconState generalized index that applies at `slot`. The BeaconState
+		/// layout changed in Electra, moving all fields one level deeper in the tree.
+		pub(super) fn select_gindex(
+			fork_versions: &ForkVersions,
+			slot: u64,
+			pre_electra_gindex: usize,
+			electra_gindex: usize,
+		) -> usize {
+			let epoch = compute_epoch(slot, config::SLOTS_PER_EPOCH as u64);
+			if epoch >= fork_versions.electra.epoch {
+				return electra_gindex
+			}
+			pre_electra_gindex
+		}
+
 		/// Returns a vector of public keys that participated in the sync committee block signage.
 		/// Sync committee bits is an array of 0s and 1s, 0 meaning the corresponding sync committee
 		/// member did not participate in the vote, 1 meaning they participated.
@@ -59,6 +59,28 @@ pub fn load_next_finalized_header_update_fixture() -> snowbridge_beacon_primitiv
 	load_fixture("next-finalized-header-update.json".to_string()).unwrap()
 }
 
+/// Checkpoint in the last sync committee period before the Electra fork of the mock.
+pub fn load_electra_checkpoint_update_fixture(
+) -> snowbridge_beacon_primitives::CheckpointUpdate<{ config::SYNC_COMMITTEE_SIZE }> {
+	load_fixture("electra-initial-checkpoint.json".to_string()).unwrap()
+}
+
+/// Deneb sync committee update following `load_electra_checkpoint_update_fixture`.
+pub fn load_electra_sync_committee_update_fixture() -> snowbridge_beacon_primitives::Update<
+	{ config::SYNC_COMMITTEE_SIZE },
+	{ config::SYNC_COMMITTEE_BITS_SIZE },
+> {
+	load_fixture("electra-sync-committee-update.json".to_string()).unwrap()
+}
+
+/// Update finalizing the first Electra epoch, with proofs against the Electra BeaconState.
+pub fn load_electra_finalized_header_update_fixture() -> snowbridge_beacon_primitives::Update<
+	{ config::SYNC_COMMITTEE_SIZE },
+	{ config::SYNC_COMMITTEE_BITS_SIZE },
+> {
+	load_fixture("electra-finalized-header-update.json".to_string()).unwrap()
+}
+
 pub fn load_sync_committee_update_period_0() -> Box<
 	snowbridge_beacon_primitives::Update<
 		{ config::SYNC_COMMITTEE_SIZE },
@@ -132,16 +154,25 @@ parameter_types! {
 		deneb: Fork {
 			version: [4, 0, 0, 0], // 0x90000073
 			epoch: 0,
+		},
+		electra: Fork {
+			version: [5, 0, 0, 0], // 0x05000000
+			epoch: ELECTRA_FORK_EPOCH,
 		}
 	};
 }
 
+/// Electra activates well after the slots used by the Deneb fixtures, so that those keep being
+/// verified against the Deneb BeaconState layout.
+pub const ELECTRA_FORK_EPOCH: u64 = 1024;
+
 pub const FREE_SLOTS_INTERVAL: u32 = config::SLOTS_PER_EPOCH as u32;
 
 impl ethereum_beacon_client::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ForkVersions = ChainForkVersions;
 	type FreeHeadersInterval = ConstU32<FREE_SLOTS_INTERVAL>;
+	type MaxCatchUpPeriods = ConstU32<4>;
 	type WeightInfo = ();
 }
 
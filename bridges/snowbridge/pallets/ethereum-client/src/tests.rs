@@ -2,12 +2,15 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 pub use crate::mock::*;
 use crate::{
+	config,
 	config::{EPOCHS_PER_SYNC_COMMITTEE_PERIOD, SLOTS_PER_EPOCH, SLOTS_PER_HISTORICAL_ROOT},
 	functions::compute_period,
 	mock::{
 		get_message_verification_payload, load_checkpoint_update_fixture,
-		load_finalized_header_update_fixture, load_next_finalized_header_update_fixture,
-		load_next_sync_committee_update_fixture, load_sync_committee_update_fixture,
+		load_electra_checkpoint_update_fixture, load_electra_finalized_header_update_fixture,
+		load_electra_sync_committee_update_fixture, load_finalized_header_update_fixture,
+		load_next_finalized_header_update_fixture, load_next_sync_committee_update_fixture,
+		load_sync_committee_update_fixture,
 	},
 	sync_committee_sum, verify_merkle_branch, BeaconHeader, CompactBeaconState, Error,
 	FinalizedBeaconState, LatestFinalizedBlockRoot, LatestSyncCommitteeUpdatePeriod,
@@ -16,16 +19,133 @@ use crate::{
 use frame_support::{assert_err, assert_noop, assert_ok, pallet_prelude::Pays};
 use hex_literal::hex;
 use snowbridge_beacon_primitives::{
-	types::deneb, Fork, ForkVersions, NextSyncCommitteeUpdate, VersionedExecutionPayloadHeader,
+	merkle_proof::{generalized_index_length, subtree_index},
+	types::{deneb, electra},
+	AncestryProof, ExecutionProof, Fork, ForkVersions, HistoricalSummariesProof,
+	NextSyncCommitteeUpdate, VersionedExecutionPayloadHeader,
 };
 use snowbridge_core::inbound::{VerificationError, Verifier};
 use sp_core::H256;
+use sp_io::hashing::sha2_256;
 use sp_runtime::DispatchError;
 
 /// Arbitrary hash used for tests and invalid hashes.
 const TEST_HASH: [u8; 32] =
 	hex!["5f6f02af29218292d21a69b64a794a7c0873b3e0f54611972863706e8cbdf371"];
 
+/// Merkleizes `leaves` (the count must be a power of two) and returns the tree root together
+/// with the merkle branch of the leaf at `index`.
+fn merkle_root_and_branch(leaves: &[H256], mut index: usize) -> (H256, Vec<H256>) {
+	let mut layer = leaves.to_vec();
+	let mut branch = vec![];
+	while layer.len() > 1 {
+		branch.push(layer[index ^ 1]);
+		layer = layer
+			.chunks(2)
+			.map(|pair| H256::from(sha2_256(&[pair[0].as_bytes(), pair[1].as_bytes()].concat())))
+			.collect();
+		index /= 2;
+	}
+	(layer[0], branch)
+}
+
+/// Computes the root of a merkle tree from a leaf at subtree `index` and its branch.
+fn merkle_root_from_branch(leaf: H256, branch: &[H256], index: usize) -> H256 {
+	branch.iter().enumerate().fold(leaf, |node, (i, sibling)| {
+		let pair = if index & (1 << i) > 0 { [sibling, &node] } else { [&node, sibling] };
+		H256::from(sha2_256(&[pair[0].as_bytes(), pair[1].as_bytes()].concat()))
+	})
+}
+
+/// Builds an execution proof for a Deneb header at `slot`, with an ancestry proof through the
+/// `historical_summaries` of a finalized BeaconState. Returns the proof together with the root
+/// under which the finalized BeaconState must be stored.
+fn make_historical_summaries_execution_proof(slot: u64) -> (ExecutionProof, H256) {
+	let execution_header = VersionedExecutionPayloadHeader::Deneb(deneb::ExecutionPayloadHeader {
+		logs_bloom: vec![0u8; 256],
+		block_number: 42,
+		receipts_root: TEST_HASH.into(),
+		..Default::default()
+	});
+	let execution_payload_position = config::EXECUTION_HEADER_INDEX - 16;
+	let mut body_leaves = vec![H256::zero(); 16];
+	body_leaves[execution_payload_position] = execution_header.hash_tree_root().unwrap();
+	let (body_root, execution_branch) =
+		merkle_root_and_branch(&body_leaves, execution_payload_position);
+	let header = BeaconHeader { slot, body_root, ..Default::default() };
+	let block_root = header.hash_tree_root().unwrap();
+
+	// The `block_roots` of the header's period, summarized in `block_summary_root`.
+	let mut block_roots = vec![H256::zero(); SLOTS_PER_HISTORICAL_ROOT];
+	let index_in_array = slot as usize % SLOTS_PER_HISTORICAL_ROOT;
+	block_roots[index_in_array] = block_root;
+	let (block_summary_root, header_branch) = merkle_root_and_branch(&block_roots, index_in_array);
+
+	// The Capella fork is at genesis in the mock, so the summary index is the period.
+	let summary_index = slot as usize / SLOTS_PER_HISTORICAL_ROOT;
+	let block_summary_root_branch: Vec<H256> = (0..config::BLOCK_SUMMARY_ROOT_DEPTH)
+		.map(|i| H256::repeat_byte(i as u8))
+		.collect();
+	let historical_summaries_root =
+		merkle_root_from_branch(block_summary_root, &block_summary_root_branch, summary_index << 1);
+
+	let historical_summaries_gindex = config::altair::HISTORICAL_SUMMARIES_INDEX;
+	let historical_summaries_branch: Vec<H256> =
+		(0..generalized_index_length(historical_summaries_gindex))
+			.map(|i| H256::repeat_byte(0x10 + i as u8))
+			.collect();
+	let finalized_state_root = merkle_root_from_branch(
+		historical_summaries_root,
+		&historical_summaries_branch,
+		subtree_index(historical_summaries_gindex),
+	);
+
+	let finalized_state_root_branch: Vec<H256> = (0..config::STATE_ROOT_DEPTH)
+		.map(|i| H256::repeat_byte(0x20 + i as u8))
+		.collect();
+	let finalized_block_root = merkle_root_from_branch(
+		finalized_state_root,
+		&finalized_state_root_branch,
+		config::STATE_ROOT_SUBTREE_INDEX,
+	);
+
+	let ancestry_proof = AncestryProof::HistoricalSummaries(HistoricalSummariesProof {
+		header_branch,
+		block_summary_root,
+		block_summary_root_branch,
+		historical_summaries_root,
+		historical_summaries_branch,
+		finalized_state_root,
+		finalized_state_root_branch,
+		finalized_block_root,
+	});
+	let execution_proof =
+		ExecutionProof { header, ancestry_proof, execution_header, execution_branch };
+	(execution_proof, finalized_block_root)
+}
+
+/// Builds a checkpoint whose proofs are against an Electra BeaconState (64 field leaves).
+fn make_electra_checkpoint_update() -> Box<crate::types::CheckpointUpdate> {
+	let mut checkpoint = Box::new(load_checkpoint_update_fixture());
+	let sync_committee_root = checkpoint.current_sync_committee.hash_tree_root().unwrap();
+
+	// Field positions of `block_roots` and `current_sync_committee` in the Electra BeaconState.
+	let block_roots_position = config::electra::BLOCK_ROOTS_INDEX - 64;
+	let sync_committee_position = config::electra::CURRENT_SYNC_COMMITTEE_INDEX - 64;
+	let mut leaves = vec![H256::zero(); 64];
+	leaves[block_roots_position] = checkpoint.block_roots_root;
+	leaves[sync_committee_position] = sync_committee_root;
+
+	let (state_root, block_roots_branch) = merkle_root_and_branch(&leaves, block_roots_position);
+	let (_, sync_committee_branch) = merkle_root_and_branch(&leaves, sync_committee_position);
+
+	checkpoint.header.slot = ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64;
+	checkpoint.header.state_root = state_root;
+	checkpoint.block_roots_branch = block_roots_branch;
+	checkpoint.current_sync_committee_branch = sync_committee_branch;
+	checkpoint
+}
+
 /* UNIT TESTS */
 
 #[test]
@@ -246,6 +366,7 @@ fn compute_fork_version() {
 		bellatrix: Fork { version: [0, 0, 0, 2], epoch: 20 },
 		capella: Fork { version: [0, 0, 0, 3], epoch: 30 },
 		deneb: Fork { version: [0, 0, 0, 4], epoch: 40 },
+		electra: Fork { version: [0, 0, 0, 5], epoch: 50 },
 	};
 	new_tester().execute_with(|| {
 		assert_eq!(EthereumBeaconClient::select_fork_version(&mock_fork_versions, 0), [0, 0, 0, 0]);
@@ -266,6 +387,64 @@ fn compute_fork_version() {
 			EthereumBeaconClient::select_fork_version(&mock_fork_versions, 32),
 			[0, 0, 0, 3]
 		);
+		assert_eq!(
+			EthereumBeaconClient::select_fork_version(&mock_fork_versions, 49),
+			[0, 0, 0, 4]
+		);
+		assert_eq!(
+			EthereumBeaconClient::select_fork_version(&mock_fork_versions, 50),
+			[0, 0, 0, 5]
+		);
+	});
+}
+
+#[test]
+fn select_beacon_state_gindex_across_electra_fork() {
+	let mock_fork_versions = ForkVersions {
+		genesis: Fork { version: [0, 0, 0, 0], epoch: 0 },
+		altair: Fork { version: [0, 0, 0, 1], epoch: 0 },
+		bellatrix: Fork { version: [0, 0, 0, 2], epoch: 0 },
+		capella: Fork { version: [0, 0, 0, 3], epoch: 0 },
+		deneb: Fork { version: [0, 0, 0, 4], epoch: 0 },
+		electra: Fork { version: [0, 0, 0, 5], epoch: 10 },
+	};
+	let last_deneb_slot = 10 * SLOTS_PER_EPOCH as u64 - 1;
+	let first_electra_slot = 10 * SLOTS_PER_EPOCH as u64;
+	let select = |slot| {
+		EthereumBeaconClient::select_gindex(
+			&mock_fork_versions,
+			slot,
+			config::altair::FINALIZED_ROOT_INDEX,
+			config::electra::FINALIZED_ROOT_INDEX,
+		)
+	};
+
+	new_tester().execute_with(|| {
+		assert_eq!(select(0), config::altair::FINALIZED_ROOT_INDEX);
+		assert_eq!(select(last_deneb_slot), config::altair::FINALIZED_ROOT_INDEX);
+		assert_eq!(select(first_electra_slot), config::electra::FINALIZED_ROOT_INDEX);
+
+		// The Electra BeaconState is one level deeper than the Altair one.
+		assert_eq!(config::FINALIZED_ROOT_DEPTH, 6);
+		assert_eq!(generalized_index_length(config::electra::FINALIZED_ROOT_INDEX), 7);
+		assert_eq!(generalized_index_length(config::electra::BLOCK_ROOTS_INDEX), 6);
+		assert_eq!(generalized_index_length(config::electra::CURRENT_SYNC_COMMITTEE_INDEX), 6);
+		assert_eq!(generalized_index_length(config::electra::NEXT_SYNC_COMMITTEE_INDEX), 6);
+
+		// The pallet selects indices according to the configured fork schedule.
+		let electra_slot = ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64;
+		assert_eq!(
+			EthereumBeaconClient::block_roots_gindex_at_slot(electra_slot - 1),
+			config::altair::BLOCK_ROOTS_INDEX
+		);
+		assert_eq!(
+			EthereumBeaconClient::block_roots_gindex_at_slot(electra_slot),
+			config::electra::BLOCK_ROOTS_INDEX
+		);
+		assert_eq!(
+			EthereumBeaconClient::next_sync_committee_gindex_at_slot(electra_slot),
+			config::electra::NEXT_SYNC_COMMITTEE_INDEX
+		);
 	});
 }
 
@@ -361,6 +540,108 @@ fn process_initial_checkpoint_with_invalid_blocks_root_proof() {
 	});
 }
 
+#[test]
+fn process_electra_checkpoint() {
+	let checkpoint = make_electra_checkpoint_update();
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::force_checkpoint(
+			RuntimeOrigin::root(),
+			checkpoint.clone()
+		));
+		let block_root: H256 = checkpoint.header.hash_tree_root().unwrap();
+		assert!(<FinalizedBeaconState<Test>>::contains_key(block_root));
+	});
+}
+
+#[test]
+fn process_electra_checkpoint_before_fork_is_rejected() {
+	let mut checkpoint = make_electra_checkpoint_update();
+	// Proofs against the Electra state layout are not valid for a Deneb header.
+	checkpoint.header.slot -= 1;
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::force_checkpoint(RuntimeOrigin::root(), checkpoint),
+			Error::<Test>::InvalidSyncCommitteeMerkleProof
+		);
+	});
+}
+
+#[test]
+fn process_deneb_checkpoint_after_fork_is_rejected() {
+	let mut checkpoint = Box::new(load_checkpoint_update_fixture());
+	// Proofs against the Deneb state layout are not valid for an Electra header.
+	checkpoint.header.slot = ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64;
+
+	new_tester().execute_with(|| {
+		assert_err!(
+			EthereumBeaconClient::force_checkpoint(RuntimeOrigin::root(), checkpoint),
+			Error::<Test>::InvalidSyncCommitteeMerkleProof
+		);
+	});
+}
+
+/// The Electra fixtures are signed by sync committees of interop validator keys. The checkpoint
+/// and the sync committee update are in the last Deneb period, the second update finalizes the
+/// first Electra epoch, so it is signed with the Electra fork version and proven against the
+/// Electra BeaconState.
+#[test]
+fn submit_update_across_electra_fork() {
+	let checkpoint = Box::new(load_electra_checkpoint_update_fixture());
+	let sync_committee_update = Box::new(load_electra_sync_committee_update_fixture());
+	let electra_update = Box::new(load_electra_finalized_header_update_fixture());
+	let electra_slot = ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64;
+	assert!(sync_committee_update.signature_slot < electra_slot);
+	assert!(electra_update.finalized_header.slot >= electra_slot);
+	let deneb_period = compute_period(checkpoint.header.slot);
+	let electra_period = compute_period(electra_update.finalized_header.slot);
+	assert_eq!(deneb_period + 1, electra_period);
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert_ok!(EthereumBeaconClient::submit(RuntimeOrigin::signed(1), sync_committee_update));
+		assert!(<NextSyncCommittee<Test>>::exists());
+
+		let result = EthereumBeaconClient::submit(RuntimeOrigin::signed(1), electra_update.clone());
+		assert_ok!(result);
+		assert_eq!(result.unwrap().pays_fee, Pays::No);
+
+		let block_root: H256 = electra_update.finalized_header.hash_tree_root().unwrap();
+		assert_eq!(<LatestFinalizedBlockRoot<Test>>::get(), block_root);
+		let finalized_state = <FinalizedBeaconState<Test>>::get(block_root).unwrap();
+		assert_eq!(finalized_state.block_roots_root, electra_update.block_roots_root);
+		assert_eq!(<LatestSyncCommitteeUpdatePeriod<Test>>::get(), electra_period);
+		let next_sync_committee_root = electra_update
+			.next_sync_committee_update
+			.as_ref()
+			.unwrap()
+			.next_sync_committee
+			.hash_tree_root()
+			.unwrap();
+		assert_eq!(<NextSyncCommittee<Test>>::get().root, next_sync_committee_root);
+	});
+}
+
+#[test]
+fn submit_electra_update_attested_before_fork_is_rejected() {
+	let checkpoint = Box::new(load_electra_checkpoint_update_fixture());
+	let sync_committee_update = Box::new(load_electra_sync_committee_update_fixture());
+	let mut electra_update = Box::new(load_electra_finalized_header_update_fixture());
+	// The finality branch is against the Electra BeaconState, which is not valid for a header
+	// attested before the fork.
+	electra_update.attested_header.slot = ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64 - 1;
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert_ok!(EthereumBeaconClient::submit(RuntimeOrigin::signed(1), sync_committee_update));
+		assert_err!(
+			EthereumBeaconClient::submit(RuntimeOrigin::signed(1), electra_update),
+			Error::<Test>::InvalidHeaderMerkleProof
+		);
+	});
+}
+
 #[test]
 fn submit_update_in_current_period() {
 	let checkpoint = Box::new(load_checkpoint_update_fixture());
@@ -763,6 +1044,110 @@ fn sync_committee_update_for_sync_committee_already_imported_are_not_free() {
 	});
 }
 
+#[test]
+fn catch_up_imports_consecutive_sync_committee_updates() {
+	let checkpoint = Box::new(load_checkpoint_update_fixture());
+	let update = load_sync_committee_update_fixture();
+	let next_update = load_next_sync_committee_update_fixture();
+	let next_update_period = compute_period(next_update.finalized_header.slot);
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert!(!<NextSyncCommittee<Test>>::exists());
+
+		let result = EthereumBeaconClient::catch_up(
+			RuntimeOrigin::signed(1),
+			vec![update, next_update].try_into().unwrap(),
+		);
+		assert_ok!(result);
+		assert_eq!(result.unwrap().pays_fee, Pays::No);
+
+		let last_finalized_state =
+			FinalizedBeaconState::<Test>::get(LatestFinalizedBlockRoot::<Test>::get()).unwrap();
+		assert_eq!(compute_period(last_finalized_state.slot), next_update_period);
+		assert_eq!(<LatestSyncCommitteeUpdatePeriod<Test>>::get(), next_update_period);
+		assert!(<NextSyncCommittee<Test>>::exists());
+	});
+}
+
+#[test]
+fn catch_up_without_updates_fails() {
+	let checkpoint = Box::new(load_checkpoint_update_fixture());
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert_noop!(
+			EthereumBeaconClient::catch_up(RuntimeOrigin::signed(1), Default::default()),
+			Error::<Test>::EmptyCatchUp
+		);
+	});
+}
+
+#[test]
+fn catch_up_requires_sync_committee_in_every_update() {
+	let checkpoint = Box::new(load_checkpoint_update_fixture());
+	let update = load_sync_committee_update_fixture();
+	let finalized_update = load_finalized_header_update_fixture();
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert_noop!(
+			EthereumBeaconClient::catch_up(
+				RuntimeOrigin::signed(1),
+				vec![update, finalized_update].try_into().unwrap(),
+			),
+			Error::<Test>::SyncCommitteeUpdateRequired
+		);
+		assert!(!<NextSyncCommittee<Test>>::exists());
+	});
+}
+
+#[test]
+fn catch_up_requires_every_update_to_hand_over_to_next_period() {
+	let checkpoint = Box::new(load_checkpoint_update_fixture());
+	let update = load_sync_committee_update_fixture();
+	let newer_update = *load_sync_committee_update_period_0_newer_fixture();
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		// the second update is valid for `submit`, but it doesn't advance the sync committee
+		// period
+		assert_noop!(
+			EthereumBeaconClient::catch_up(
+				RuntimeOrigin::signed(1),
+				vec![update, newer_update].try_into().unwrap(),
+			),
+			Error::<Test>::InvalidSyncCommitteeUpdate
+		);
+	});
+}
+
+#[test]
+fn catch_up_allows_finalized_header_gap() {
+	let checkpoint = Box::new(load_checkpoint_update_fixture());
+	let update = load_sync_committee_update_fixture();
+	let mut next_update = load_next_sync_committee_update_fixture();
+
+	// Same update as in `submit_finalized_header_update_with_too_large_gap`.
+	let slot_with_large_gap = checkpoint.header.slot + SLOTS_PER_HISTORICAL_ROOT as u64 + 1;
+	next_update.finalized_header.slot = slot_with_large_gap;
+	next_update.attested_header.slot = slot_with_large_gap + 33;
+	next_update.signature_slot = slot_with_large_gap + 43;
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
+		assert_noop!(
+			EthereumBeaconClient::catch_up(
+				RuntimeOrigin::signed(1),
+				vec![update, next_update].try_into().unwrap(),
+			),
+			// The update passes the InvalidFinalizedHeaderGap check, and fails at the next
+			// check, the merkle proof, because we changed the next_update slots.
+			Error::<Test>::InvalidHeaderMerkleProof
+		);
+	});
+}
+
 /* IMPLS */
 
 #[test]
@@ -872,7 +1257,9 @@ fn verify_execution_proof_invalid_ancestry_proof() {
 	let checkpoint = Box::new(load_checkpoint_update_fixture());
 	let finalized_header_update = Box::new(load_finalized_header_update_fixture());
 	let mut execution_header_update = Box::new(load_execution_proof_fixture());
-	if let Some(ref mut ancestry_proof) = execution_header_update.ancestry_proof {
+	if let AncestryProof::BlockRoots(ref mut ancestry_proof) =
+		execution_header_update.ancestry_proof
+	{
 		ancestry_proof.header_branch[0] = TEST_HASH.into()
 	}
 
@@ -908,7 +1295,7 @@ fn verify_execution_proof_that_is_also_finalized_header_which_is_not_stored() {
 	let checkpoint = Box::new(load_checkpoint_update_fixture());
 	let finalized_header_update = Box::new(load_finalized_header_update_fixture());
 	let mut execution_header_update = Box::new(load_execution_proof_fixture());
-	execution_header_update.ancestry_proof = None;
+	execution_header_update.ancestry_proof = AncestryProof::Finalized;
 
 	new_tester().execute_with(|| {
 		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
@@ -925,7 +1312,7 @@ fn submit_execution_proof_that_is_also_finalized_header_which_is_stored_but_slot
 	let checkpoint = Box::new(load_checkpoint_update_fixture());
 	let finalized_header_update = Box::new(load_finalized_header_update_fixture());
 	let mut execution_header_update = Box::new(load_execution_proof_fixture());
-	execution_header_update.ancestry_proof = None;
+	execution_header_update.ancestry_proof = AncestryProof::Finalized;
 
 	new_tester().execute_with(|| {
 		assert_ok!(EthereumBeaconClient::process_checkpoint_update(&checkpoint));
@@ -970,3 +1357,95 @@ fn verify_execution_proof_not_finalized() {
 		);
 	});
 }
+
+#[test]
+fn verify_electra_execution_proof() {
+	let execution_header = electra::ExecutionPayloadHeader {
+		logs_bloom: vec![0u8; 256],
+		block_number: 42,
+		receipts_root: TEST_HASH.into(),
+		..Default::default()
+	};
+	let execution_header = VersionedExecutionPayloadHeader::Electra(execution_header);
+
+	// The execution payload is field 9 of the 16 leaves of the BeaconBlockBody.
+	let execution_payload_position = config::EXECUTION_HEADER_INDEX - 16;
+	let mut body_leaves = vec![H256::zero(); 16];
+	body_leaves[execution_payload_position] = execution_header.hash_tree_root().unwrap();
+	let (body_root, execution_branch) =
+		merkle_root_and_branch(&body_leaves, execution_payload_position);
+
+	let header = BeaconHeader {
+		slot: ELECTRA_FORK_EPOCH * SLOTS_PER_EPOCH as u64,
+		body_root,
+		..Default::default()
+	};
+	let execution_proof = ExecutionProof {
+		header,
+		ancestry_proof: AncestryProof::Finalized,
+		execution_header,
+		execution_branch,
+	};
+
+	new_tester().execute_with(|| {
+		assert_ok!(EthereumBeaconClient::store_finalized_header(header, H256::zero()));
+		assert_ok!(EthereumBeaconClient::verify_execution_proof(&execution_proof));
+
+		let mut invalid_execution_proof = execution_proof.clone();
+		invalid_execution_proof.execution_branch[0] = TEST_HASH.into();
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&invalid_execution_proof),
+			Error::<Test>::InvalidExecutionHeaderProof
+		);
+	});
+}
+
+#[test]
+fn verify_execution_proof_with_historical_summaries_proof() {
+	let slot = 2 * SLOTS_PER_HISTORICAL_ROOT as u64 + 5;
+	let (execution_proof, finalized_block_root) = make_historical_summaries_execution_proof(slot);
+
+	new_tester().execute_with(|| {
+		// The finalized state is well past the `block_roots` window of the header.
+		let finalized_slot = 4 * SLOTS_PER_HISTORICAL_ROOT as u64;
+		<FinalizedBeaconState<Test>>::insert(
+			finalized_block_root,
+			CompactBeaconState { slot: finalized_slot, block_roots_root: H256::zero() },
+		);
+		<LatestFinalizedBlockRoot<Test>>::set(finalized_block_root);
+
+		assert_ok!(EthereumBeaconClient::verify_execution_proof(&execution_proof));
+
+		let mut invalid_execution_proof = execution_proof.clone();
+		if let AncestryProof::HistoricalSummaries(ref mut proof) =
+			invalid_execution_proof.ancestry_proof
+		{
+			proof.block_summary_root_branch[0] = TEST_HASH.into()
+		}
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&invalid_execution_proof),
+			Error::<Test>::InvalidAncestryMerkleProof
+		);
+	});
+}
+
+#[test]
+fn verify_execution_proof_with_historical_summaries_proof_for_incomplete_period() {
+	let slot = 2 * SLOTS_PER_HISTORICAL_ROOT as u64 + 5;
+	let (execution_proof, finalized_block_root) = make_historical_summaries_execution_proof(slot);
+
+	new_tester().execute_with(|| {
+		// The period of the header has not been summarized yet in the finalized state.
+		let finalized_slot = 3 * SLOTS_PER_HISTORICAL_ROOT as u64 - 1;
+		<FinalizedBeaconState<Test>>::insert(
+			finalized_block_root,
+			CompactBeaconState { slot: finalized_slot, block_roots_root: H256::zero() },
+		);
+		<LatestFinalizedBlockRoot<Test>>::set(finalized_block_root);
+
+		assert_err!(
+			EthereumBeaconClient::verify_execution_proof(&execution_proof),
+			Error::<Test>::HistoricalSummaryNotAvailable
+		);
+	});
+}
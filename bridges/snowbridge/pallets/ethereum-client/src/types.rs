@@ -15,7 +15,9 @@ pub type CheckpointUpdate = snowbridge_beacon_primitives::CheckpointUpdate<SC_SI
 pub type Update = snowbridge_beacon_primitives::Update<SC_SIZE, SC_BITS_SIZE>;
 pub type NextSyncCommitteeUpdate = snowbridge_beacon_primitives::NextSyncCommitteeUpdate<SC_SIZE>;
 
-pub use snowbridge_beacon_primitives::{AncestryProof, ExecutionProof};
+pub use snowbridge_beacon_primitives::{
+	AncestryProof, BlockRootsProof, ExecutionProof, HistoricalSummariesProof,
+};
 
 /// FinalizedState ring buffer implementation
 pub type FinalizedBeaconStateBuffer<T> = RingBufferMapImpl<
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+// Generated, do not edit!
+// See ethereum client README.md for instructions to generate
+
+use hex_literal::hex;
+use snowbridge_beacon_primitives::{
+	types::deneb, AncestryProof, BeaconHeader, BlockRootsProof, ExecutionProof,
+	VersionedExecutionPayloadHeader,
+};
+use snowbridge_core::inbound::{InboundQueueFixture, Log, Message, Proof};
+use sp_core::U256;
+use sp_std::vec;
+
+pub fn make_delivery_receipt_message() -> InboundQueueFixture {
+	InboundQueueFixture {
+        message: Message {
+            event_log: 	Log {
+                address: hex!("eda338e4dc46038493b885327842fd3e301cab39").into(),
+                topics: vec![
+                    hex!("617fdb0cb78f01551a192a3673208ec5eb09f20a90acf673c63a0dcb11745a7a").into(),
+                    hex!("c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539").into(),
+                    hex!("34e98f52fb9b93c07219891337aef9c81cbd1e8700898ae4f8184777041af915").into(),
+                ],
+                data: hex!("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001").into(),
+            },
+            proof: Proof {
+                receipt_proof: (vec![
+                    hex!("f04181e0d0bee3cbe2aad0071e32f5fd701fd3adeed2de5a49c71ef697307295").to_vec(),
+                ], vec![
+                    hex!("f901d1822080b901cb02f901c70182cb20b9010000040000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000040004002000000000002000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000002000000000000000200000000000000000000000000000f8bef8bc94eda338e4dc46038493b885327842fd3e301cab39f863a0617fdb0cb78f01551a192a3673208ec5eb09f20a90acf673c63a0dcb11745a7aa0c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539a034e98f52fb9b93c07219891337aef9c81cbd1e8700898ae4f8184777041af915b84000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001").to_vec(),
+                ]),
+                execution_proof: ExecutionProof {
+                    header: BeaconHeader {
+                        slot: 2321,
+                        proposer_index: 5,
+                        parent_root: hex!("2add14727840d3a5ea061e14baa47030bb81380a65999200d119e73b86411d20").into(),
+                        state_root: hex!("d962981467920bb2b7efa4a7a1baf64745582c3250857f49a957c5dae9a0da39").into(),
+                        body_root: hex!("ac3b7db9b48cefc9462a773d44797642c4d56f64fbcb826a42b852a93a68f68c").into(),
+                    },
+                        ancestry_proof: AncestryProof::BlockRoots(BlockRootsProof {
+                        header_branch: vec![
+                            hex!("2add14727840d3a5ea061e14baa47030bb81380a65999200d119e73b86411d20").into(),
+                            hex!("48b2e2f5256906a564e5058698f70e3406765fefd6a2edc064bb5fb88aa2ed0a").into(),
+                            hex!("e5ed7c704e845418219b2fda42cd2f3438ffbe4c4b320935ae49439c6189f7a7").into(),
+                            hex!("4a7ce24526b3f571548ad69679e4e260653a1b3b911a344e7f988f25a5c917a7").into(),
+                            hex!("46fc859727ab0d0e8c344011f7d7a4426ccb537bb51363397e56cc7153f56391").into(),
+                            hex!("f496b6f85a7c6c28a9048f2153550a7c5bcb4b23844ed3b87f6baa646124d8a3").into(),
+                            hex!("7318644e474beb46e595a1875acc7444b937f5208065241911d2a71ac50c2de3").into(),
+                            hex!("5cf48519e518ac64286aef5391319782dd38831d5dcc960578a6b9746d5f8cee").into(),
+                            hex!("efb3e50fa39ca9fe7f76adbfa36fa8451ec2fd5d07b22aaf822137c04cf95a76").into(),
+                            hex!("2206cd50750355ffaef4a67634c21168f2b564c58ffd04f33b0dc7af7dab3291").into(),
+                            hex!("1a4014f6c4fcce9949fba74cb0f9e88df086706f9e05560cc9f0926f8c90e373").into(),
+                            hex!("2df7cc0bcf3060be4132c63da7599c2600d9bbadf37ab001f15629bc2255698e").into(),
+                            hex!("b7d05f875f140027ef5118a2247bbb84ce8f2f0f1123623085daf7960c329f5f").into(),
+                        ],
+                        finalized_block_root: hex!("f869dd1c9598043008a3ac2a5d91b3d6c7b0bb3295b3843bc84c083d70b0e604").into(),
+                        }),
+                    execution_header: VersionedExecutionPayloadHeader::Deneb(deneb::ExecutionPayloadHeader {
+                        parent_hash: hex!("5d7859883dde1eba6c98b20eac18426134b25da2a89e5e360f3343b15e0e0a31").into(),
+                        fee_recipient: hex!("0000000000000000000000000000000000000000").into(),
+                        state_root: hex!("f8fbebed4c84d46231bd293bb9fbc9340d5c28c284d99fdaddb77238b8960ae2").into(),
+                        receipts_root: hex!("f04181e0d0bee3cbe2aad0071e32f5fd701fd3adeed2de5a49c71ef697307295").into(),
+                        logs_bloom: hex!("00040000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000040004002000000000002000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000002000000000000000200000000000000000000000000000").into(),
+                        prev_randao: hex!("15533eeb366c6386bea5aeb8f425871928348c092209e4377f2418a6dedd7fd0").into(),
+                        block_number: 2321,
+                        gas_limit: 30000000,
+                        gas_used: 52000,
+                        timestamp: 1710554741,
+                        extra_data: hex!("d983010d0b846765746888676f312e32312e368664617277696e").into(),
+                        base_fee_per_gas: U256::from(7u64),
+                        block_hash: hex!("9da6db31b0eceaca8cead33e29317ec44594eaf4e4b403acf3bf5861c786278e").into(),
+                        transactions_root: hex!("09cd60ee2207d804397c81f7b7e1e5d3307712b136e5376623a80317a4bdcd7a").into(),
+                        withdrawals_root: hex!("792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535").into(),
+                        blob_gas_used: 0,
+                        excess_blob_gas: 0,
+                    }),
+                    execution_branch: vec![
+                            hex!("9d419471a9a4719b40e7607781fbe32d9a7766b79805505c78c0c58133496ba2").into(),
+                            hex!("b46f0c01805fe212e15907981b757e6c496b0cb06664224655613dcec82505bb").into(),
+                            hex!("db56114e00fdd4c1f85c892bf35ac9a89289aaecb1ebd0a96cde606a748b5d71").into(),
+                            hex!("bee375b8f1bbe4cd0e783c78026c1829ae72741c2dead5cab05d6834c5e5df65").into(),
+                    ],
+                }
+            },
+        },
+        finalized_header: BeaconHeader {
+            slot: 4032,
+            proposer_index: 5,
+            parent_root: hex!("180aaaec59d38c3860e8af203f01f41c9bc41665f4d17916567c80f6cd23e8a2").into(),
+            state_root: hex!("3341790429ed3bf894cafa3004351d0b99e08baf6c38eb2a54d58e69fd2d19c6").into(),
+            body_root: hex!("a221e0c695ac7b7d04ce39b28b954d8a682ecd57961d81b44783527c6295f455").into(),
+        },
+        block_roots_root: hex!("1e0ae19251d38b378f7a964614602fc41c8af9e37b017bb56f4f726b6df1c078").into(),
+    }
+}
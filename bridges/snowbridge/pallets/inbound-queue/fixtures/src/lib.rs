@@ -2,6 +2,7 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod delivery_receipt;
 pub mod register_token;
 pub mod send_token;
 pub mod send_token_to_penpal;
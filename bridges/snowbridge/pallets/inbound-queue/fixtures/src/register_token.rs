@@ -5,7 +5,8 @@
 
 use hex_literal::hex;
 use snowbridge_beacon_primitives::{
-	types::deneb, AncestryProof, BeaconHeader, ExecutionProof, VersionedExecutionPayloadHeader,
+	types::deneb, AncestryProof, BeaconHeader, BlockRootsProof, ExecutionProof,
+	VersionedExecutionPayloadHeader,
 };
 use snowbridge_core::inbound::{InboundQueueFixture, Log, Message, Proof};
 use sp_core::U256;
@@ -39,7 +40,7 @@ pub fn make_register_token_message() -> InboundQueueFixture {
                         state_root: hex!("b62ac34a8cb82497be9542fe2114410c9f6021855b766015406101a1f3d86434").into(),
                         body_root: hex!("04005fe231e11a5b7b1580cb73b177ae8b338bedd745497e6bb7122126a806db").into(),
                     },
-                        ancestry_proof: Some(AncestryProof {
+                        ancestry_proof: AncestryProof::BlockRoots(BlockRootsProof {
                         header_branch: vec![
                             hex!("6545b47a614a1dd4cad042a0cdbbf5be347e8ffcdc02c6c64540d5153acebeef").into(),
                             hex!("fa84cc88ca53a72181599ff4eb07d8b444bce023fe2347c3b4f51004c43439d3").into(),
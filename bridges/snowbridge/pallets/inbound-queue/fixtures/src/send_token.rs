@@ -5,7 +5,8 @@
 
 use hex_literal::hex;
 use snowbridge_beacon_primitives::{
-	types::deneb, AncestryProof, BeaconHeader, ExecutionProof, VersionedExecutionPayloadHeader,
+	types::deneb, AncestryProof, BeaconHeader, BlockRootsProof, ExecutionProof,
+	VersionedExecutionPayloadHeader,
 };
 use snowbridge_core::inbound::{InboundQueueFixture, Log, Message, Proof};
 use sp_core::U256;
@@ -37,7 +38,7 @@ pub fn make_send_token_message() -> InboundQueueFixture {
                         state_root: hex!("d962981467920bb2b7efa4a7a1baf64745582c3250857f49a957c5dae9a0da39").into(),
                         body_root: hex!("18e3f7f51a350f371ad35d166f2683b42af51d1836b295e4093be08acb0dcb7a").into(),
                     },
-                        ancestry_proof: Some(AncestryProof {
+                        ancestry_proof: AncestryProof::BlockRoots(BlockRootsProof {
                         header_branch: vec![
                             hex!("2add14727840d3a5ea061e14baa47030bb81380a65999200d119e73b86411d20").into(),
                             hex!("48b2e2f5256906a564e5058698f70e3406765fefd6a2edc064bb5fb88aa2ed0a").into(),
@@ -5,7 +5,8 @@
 
 use hex_literal::hex;
 use snowbridge_beacon_primitives::{
-	types::deneb, AncestryProof, BeaconHeader, ExecutionProof, VersionedExecutionPayloadHeader,
+	types::deneb, AncestryProof, BeaconHeader, BlockRootsProof, ExecutionProof,
+	VersionedExecutionPayloadHeader,
 };
 use snowbridge_core::inbound::{InboundQueueFixture, Log, Message, Proof};
 use sp_core::U256;
@@ -37,7 +38,7 @@ pub fn make_send_token_to_penpal_message() -> InboundQueueFixture {
                         state_root: hex!("725f51771a0ecf72c647a283ab814ca088f998eb8c203181496b0b8e01f624fa").into(),
                         body_root: hex!("6f1c326d192e7e97e21e27b16fd7f000b8fa09b435ff028849927e382302b0ce").into(),
                     },
-                        ancestry_proof: Some(AncestryProof {
+                        ancestry_proof: AncestryProof::BlockRoots(BlockRootsProof {
                         header_branch: vec![
                             hex!("1b31e6264c19bcad120e434e0aede892e7d7c8ed80ab505cb593d9a4a16bc566").into(),
                             hex!("335eb186c077fa7053ec96dcc5d34502c997713d2d5bc4eb74842118d8cd5a64").into(),
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use snowbridge_core::ChannelId;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait InboundQueueApi
+	{
+		/// Return up to `limit` nonces on `channel_id` which have not been delivered yet, in
+		/// ascending order. Only nonces below the highest delivered nonce are considered missing.
+		fn missing_nonces(channel_id: ChannelId, limit: u32) -> Vec<u64>;
+	}
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+//! Helpers for implementing runtime api
+
+use crate::{bitmap_position, Config, LatestNonce, Nonce, NonceBitmap, NONCES_PER_BUCKET};
+use snowbridge_core::ChannelId;
+use sp_std::vec::Vec;
+
+pub fn missing_nonces<T>(channel_id: ChannelId, limit: u32) -> Vec<u64>
+where
+	T: Config,
+{
+	let latest = LatestNonce::<T>::get(channel_id);
+	let mut nonce = Nonce::<T>::get(channel_id);
+	let mut missing = Vec::new();
+	while nonce < latest {
+		let (bucket, _) = bitmap_position(nonce.saturating_add(1));
+		let bits = NonceBitmap::<T>::get(channel_id, bucket);
+		let end = latest.min(bucket.saturating_mul(NONCES_PER_BUCKET) + (NONCES_PER_BUCKET - 1));
+		for candidate in nonce.saturating_add(1)..=end {
+			if missing.len() >= limit as usize {
+				return missing
+			}
+			let (_, offset) = bitmap_position(candidate);
+			if bits & (1u128 << offset) == 0 {
+				missing.push(candidate);
+			}
+		}
+		nonce = end;
+	}
+	missing
+}
@@ -21,10 +21,20 @@
 //!
 //! * [`Call::submit`]: Submit a message for verification and dispatch the final destination
 //!   parachain.
+//!
+//! ## Message Ordering
+//!
+//! By default, messages on a channel must be submitted in nonce order. When
+//! [`Config::OutOfOrderDelivery`] is enabled, messages may be submitted in any order. Nonces
+//! delivered ahead of the last contiguous nonce are tracked in [`NonceBitmap`] so that a message
+//! can never be delivered twice. A message may be at most [`MAX_BUCKETS_AHEAD`] buckets ahead of
+//! the last contiguous nonce, which bounds the buckets pruned by a single delivery.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod envelope;
 
+pub mod api;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
@@ -59,10 +69,10 @@ use xcm_executor::traits::TransactAsset;
 use snowbridge_core::{
 	inbound::{Message, VerificationError, Verifier},
 	sibling_sovereign_account, BasicOperatingMode, Channel, ChannelId, ParaId, PricingParameters,
-	StaticLookup,
+	StaticLookup, TokenLedger,
 };
 use snowbridge_router_primitives::inbound::{
-	ConvertMessage, ConvertMessageError, VersionedMessage,
+	Command, ConvertMessage, ConvertMessageError, MessageV1, VersionedMessage,
 };
 use sp_runtime::{traits::Saturating, SaturatedConversion, TokenError};
 
@@ -78,6 +88,18 @@ pub use pallet::*;
 
 pub const LOG_TARGET: &str = "snowbridge-inbound-queue";
 
+/// Number of nonces tracked by each bucket of [`NonceBitmap`]
+pub const NONCES_PER_BUCKET: u64 = u128::BITS as u64;
+
+/// Number of [`NonceBitmap`] buckets that a nonce delivered out of order may be ahead of the
+/// current nonce
+pub const MAX_BUCKETS_AHEAD: u64 = 8;
+
+/// The [`NonceBitmap`] bucket and bit offset within it which track `nonce`
+fn bitmap_position(nonce: u64) -> (u64, u32) {
+	(nonce / NONCES_PER_BUCKET, (nonce % NONCES_PER_BUCKET) as u32)
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -139,6 +161,19 @@ pub mod pallet {
 
 		/// To withdraw and deposit an asset.
 		type AssetTransactor: TransactAsset;
+
+		/// Whether messages on a channel may be delivered in any order, rather than strictly in
+		/// nonce order
+		#[pallet::constant]
+		type OutOfOrderDelivery: Get<bool>;
+
+		/// Records Polkadot-native tokens, which are burned on Ethereum and sent back
+		type TokenLedger: TokenLedger;
+
+		/// The AssetHub parachain, where XCM programs sent with `VersionedMessage::V2` are
+		/// executed, regardless of the channel they are sent on
+		#[pallet::constant]
+		type AssetHubParaId: Get<u32>;
 	}
 
 	#[pallet::hooks]
@@ -186,6 +221,8 @@ pub mod pallet {
 		Send(SendError),
 		/// Message conversion error
 		ConvertMessage(ConvertMessageError),
+		/// Message nonce is too far ahead of the current nonce of the channel.
+		NonceTooFarAhead,
 	}
 
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, PalletError)]
@@ -215,10 +252,23 @@ pub mod pallet {
 		}
 	}
 
-	/// The current nonce for each channel
+	/// The current nonce for each channel. All messages up to and including this nonce have
+	/// been delivered.
 	#[pallet::storage]
 	pub type Nonce<T: Config> = StorageMap<_, Twox64Concat, ChannelId, u64, ValueQuery>;
 
+	/// Nonces delivered ahead of [`Nonce`] for each channel, packed into buckets of
+	/// [`NONCES_PER_BUCKET`] bits. A bucket is removed once [`Nonce`] has moved past it.
+	#[pallet::storage]
+	pub type NonceBitmap<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ChannelId, Twox64Concat, u64, u128, ValueQuery>;
+
+	/// The highest nonce delivered out of order for each channel. Not maintained while messages
+	/// are delivered sequentially, so there are no nonces delivered ahead of [`Nonce`] while it is
+	/// not above [`Nonce`].
+	#[pallet::storage]
+	pub type LatestNonce<T: Config> = StorageMap<_, Twox64Concat, ChannelId, u64, ValueQuery>;
+
 	/// The current operating mode of the pallet.
 	#[pallet::storage]
 	#[pallet::getter(fn operating_mode)]
@@ -228,7 +278,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Submit an inbound message originating from the Gateway contract on Ethereum
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::submit())]
+		#[pallet::weight(T::WeightInfo::submit().saturating_add(Pallet::<T>::out_of_order_weight()))]
 		pub fn submit(origin: OriginFor<T>, message: Message) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(!Self::operating_mode().is_halted(), Error::<T>::Halted);
@@ -249,17 +299,7 @@ pub mod pallet {
 				T::ChannelLookup::lookup(envelope.channel_id).ok_or(Error::<T>::InvalidChannel)?;
 
 			// Verify message nonce
-			<Nonce<T>>::try_mutate(envelope.channel_id, |nonce| -> DispatchResult {
-				if *nonce == u64::MAX {
-					return Err(Error::<T>::MaxNonceReached.into())
-				}
-				if envelope.nonce != nonce.saturating_add(1) {
-					Err(Error::<T>::InvalidNonce.into())
-				} else {
-					*nonce = nonce.saturating_add(1);
-					Ok(())
-				}
-			})?;
+			Self::mark_delivered(envelope.channel_id, envelope.nonce)?;
 
 			// Reward relayer from the sovereign account of the destination parachain, only if funds
 			// are available
@@ -289,11 +329,28 @@ pub mod pallet {
 				fee
 			);
 
+			// V1 messages are sent to the parachain of the channel, V2 messages are programs for
+			// AssetHub
+			let dest = match message {
+				VersionedMessage::V1(_) => channel.para_id,
+				VersionedMessage::V2(_) => T::AssetHubParaId::get().into(),
+			};
+
 			// Burning fees for teleport
-			Self::burn_fees(channel.para_id, fee)?;
+			Self::burn_fees(dest, fee)?;
 
 			// Attempt to send XCM to a dest parachain
-			let message_id = Self::send_xcm(xcm, channel.para_id)?;
+			let message_id = Self::send_xcm(xcm, dest)?;
+
+			// The wrapped tokens were burned on Ethereum, so they are no longer backed by the
+			// locked supply
+			if let VersionedMessage::V1(MessageV1 {
+				command: Command::SendNativeToken { token_id, amount, .. },
+				..
+			}) = message
+			{
+				T::TokenLedger::on_burned(&token_id, amount);
+			}
 
 			Self::deposit_event(Event::MessageReceived {
 				channel_id: envelope.channel_id,
@@ -320,6 +377,74 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Verify that the message with `nonce` may be delivered on `channel_id`, and record its
+		/// delivery.
+		pub(crate) fn mark_delivered(channel_id: ChannelId, nonce: u64) -> DispatchResult {
+			let current = <Nonce<T>>::get(channel_id);
+			if current == u64::MAX {
+				return Err(Error::<T>::MaxNonceReached.into())
+			}
+			if !T::OutOfOrderDelivery::get() {
+				ensure!(nonce == current.saturating_add(1), Error::<T>::InvalidNonce);
+				// Sequential delivery never leaves gaps, so the nonce is all there is to track.
+				<Nonce<T>>::insert(channel_id, nonce);
+				return Ok(())
+			}
+			ensure!(
+				nonce > current && !Self::is_delivered(channel_id, nonce),
+				Error::<T>::InvalidNonce
+			);
+			ensure!(
+				nonce - current <= MAX_BUCKETS_AHEAD.saturating_mul(NONCES_PER_BUCKET),
+				Error::<T>::NonceTooFarAhead
+			);
+
+			let (bucket, offset) = bitmap_position(nonce);
+			<NonceBitmap<T>>::mutate(channel_id, bucket, |bits| *bits |= 1u128 << offset);
+			<LatestNonce<T>>::mutate(channel_id, |latest| *latest = (*latest).max(nonce));
+
+			// Move the current nonce past every contiguous nonce which has now been delivered,
+			// removing the buckets it leaves behind.
+			let mut current = current;
+			while current < u64::MAX {
+				let (bucket, offset) = bitmap_position(current.saturating_add(1));
+				let delivered =
+					(<NonceBitmap<T>>::get(channel_id, bucket) >> offset).trailing_ones();
+				current = current.saturating_add(delivered.into());
+				if offset.saturating_add(delivered) < NONCES_PER_BUCKET as u32 {
+					break
+				}
+				<NonceBitmap<T>>::remove(channel_id, bucket);
+			}
+			<Nonce<T>>::insert(channel_id, current);
+
+			Ok(())
+		}
+
+		/// The weight of tracking a nonce delivered out of order, on top of the benchmarked
+		/// weight of `submit`, which only covers sequential delivery: the bitmap bucket of the
+		/// nonce, the latest nonce and every bucket that the current nonce may move past, which
+		/// are removed. The nonce is at most [`MAX_BUCKETS_AHEAD`] buckets ahead of the current
+		/// nonce, so the current nonce moves past at most `MAX_BUCKETS_AHEAD + 1` buckets.
+		pub(crate) fn out_of_order_weight() -> Weight {
+			if T::OutOfOrderDelivery::get() {
+				let buckets = MAX_BUCKETS_AHEAD.saturating_add(1);
+				T::DbWeight::get()
+					.reads_writes(buckets.saturating_add(2), buckets.saturating_add(2))
+			} else {
+				Weight::zero()
+			}
+		}
+
+		/// Whether the message with `nonce` has already been delivered on `channel_id`.
+		pub fn is_delivered(channel_id: ChannelId, nonce: u64) -> bool {
+			if nonce <= <Nonce<T>>::get(channel_id) {
+				return true
+			}
+			let (bucket, offset) = bitmap_position(nonce);
+			<NonceBitmap<T>>::get(channel_id, bucket) & (1u128 << offset) != 0
+		}
+
 		pub fn do_convert(
 			message_id: H256,
 			message: VersionedMessage,
@@ -5,7 +5,8 @@ use super::*;
 use frame_support::{derive_impl, parameter_types, traits::ConstU32, weights::IdentityFee};
 use hex_literal::hex;
 use snowbridge_beacon_primitives::{
-	types::deneb, BeaconHeader, ExecutionProof, Fork, ForkVersions, VersionedExecutionPayloadHeader,
+	types::deneb, AncestryProof, BeaconHeader, ExecutionProof, Fork, ForkVersions,
+	VersionedExecutionPayloadHeader,
 };
 use snowbridge_core::{
 	gwei,
@@ -81,6 +82,10 @@ parameter_types! {
 		deneb: Fork {
 			version: [4, 0, 0, 1], // 0x04000001
 			epoch: 4294967295,
+		},
+		electra: Fork {
+			version: [5, 0, 0, 1], // 0x05000001
+			epoch: 4294967295,
 		}
 	};
 }
@@ -89,6 +94,7 @@ impl snowbridge_pallet_ethereum_client::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ForkVersions = ChainForkVersions;
 	type FreeHeadersInterval = ConstU32<32>;
+	type MaxCatchUpPeriods = ConstU32<4>;
 	type WeightInfo = ();
 }
 
@@ -115,6 +121,7 @@ parameter_types! {
 	pub UniversalLocation: InteriorLocation =
 		[GlobalConsensus(Westend), Parachain(1002)].into();
 	pub AssetHubFromEthereum: Location = Location::new(1,[GlobalConsensus(Westend),Parachain(1000)]);
+	pub storage OutOfOrderDelivery: bool = false;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -167,6 +174,9 @@ impl StaticLookup for MockChannelLookup {
 	type Target = Channel;
 
 	fn lookup(channel_id: Self::Source) -> Option<Self::Target> {
+		if channel_id == SIBLING_CHANNEL_ID.into() {
+			return Some(Channel { agent_id: H256::zero(), para_id: SIBLING_PARAID.into() })
+		}
 		if channel_id !=
 			hex!("c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539").into()
 		{
@@ -243,6 +253,9 @@ impl inbound_queue::Config for Test {
 	type LengthToFee = IdentityFee<u128>;
 	type MaxMessageSize = ConstU32<1024>;
 	type AssetTransactor = SuccessfulTransactor;
+	type OutOfOrderDelivery = OutOfOrderDelivery;
+	type TokenLedger = ();
+	type AssetHubParaId = ConstU32<ASSET_HUB_PARAID>;
 }
 
 pub fn last_events(n: usize) -> Vec<RuntimeEvent> {
@@ -329,10 +342,41 @@ pub fn mock_event_log_invalid_gateway() -> Log {
     }
 }
 
+/// Builds an `OutboundMessageAccepted` event log for the Asset Hub channel with an arbitrary
+/// payload, for messages not covered by the smoketest generated logs above.
+pub fn mock_event_log_with_payload(nonce: u64, payload: Vec<u8>) -> Log {
+	mock_event_log_on_channel(
+		hex!("c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539").into(),
+		nonce,
+		payload,
+	)
+}
+
+/// Builds an `OutboundMessageAccepted` event log for the given channel with an arbitrary payload.
+pub fn mock_event_log_on_channel(channel_id: H256, nonce: u64, payload: Vec<u8>) -> Log {
+	// ABI encoding of the non-indexed `(uint64 nonce, bytes payload)` event fields.
+	let mut data = H256::from_low_u64_be(nonce).as_bytes().to_vec();
+	data.extend_from_slice(H256::from_low_u64_be(64).as_bytes());
+	data.extend_from_slice(H256::from_low_u64_be(payload.len() as u64).as_bytes());
+	data.extend_from_slice(&payload);
+	data.resize(96 + payload.len().div_ceil(32) * 32, 0);
+
+	Log {
+		address: GATEWAY_ADDRESS.into(),
+		topics: vec![
+			hex!("7153f9357c8ea496bba60bf82e67143e27b64462b49041f8e689e1b05728f84f").into(),
+			channel_id,
+			// message id
+			hex!("5f7060e971b0dc81e63f0aa41831091847d97c1a4693ac450cc128c7214e65e0").into(),
+		],
+		data,
+	}
+}
+
 pub fn mock_execution_proof() -> ExecutionProof {
 	ExecutionProof {
 		header: BeaconHeader::default(),
-		ancestry_proof: None,
+		ancestry_proof: AncestryProof::Finalized,
 		execution_header: VersionedExecutionPayloadHeader::Deneb(deneb::ExecutionPayloadHeader {
 			parent_hash: Default::default(),
 			fee_recipient: Default::default(),
@@ -357,4 +401,8 @@ pub fn mock_execution_proof() -> ExecutionProof {
 }
 
 pub const ASSET_HUB_PARAID: u32 = 1000u32;
+/// A parachain which the mock XCM sender cannot deliver to.
+pub const SIBLING_PARAID: u32 = 1001u32;
+pub const SIBLING_CHANNEL_ID: [u8; 32] =
+	hex!("1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809");
 pub const TEMPLATE_PARAID: u32 = 1001u32;
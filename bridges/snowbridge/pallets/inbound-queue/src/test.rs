@@ -10,6 +10,8 @@ use sp_runtime::DispatchError;
 use sp_std::convert::From;
 
 use crate::{Error, Event as InboundQueueEvent};
+use snowbridge_router_primitives::inbound::MessageV2;
+use xcm::{prelude::ClearOrigin, VersionedXcm};
 
 use crate::mock::*;
 
@@ -243,3 +245,238 @@ fn test_submit_no_funds_to_reward_relayers_and_ed_preserved() {
 		assert_eq!(amount, ExistentialDeposit::get());
 	});
 }
+
+#[test]
+fn test_submit_v2_message_with_xcm() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = RuntimeOrigin::signed(relayer);
+
+		let fee = 1_000_000_000;
+		let payload = VersionedMessage::V2(MessageV2 {
+			chain_id: 11155111,
+			origin: H160::repeat_byte(1),
+			fee,
+			xcm: VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])).encode(),
+		})
+		.encode();
+		let message = Message {
+			event_log: mock_event_log_with_payload(1, payload),
+			proof: Proof {
+				receipt_proof: Default::default(),
+				execution_proof: mock_execution_proof(),
+			},
+		};
+
+		assert_ok!(InboundQueue::submit(origin, message));
+		assert!(matches!(
+			last_events(1)[0],
+			RuntimeEvent::InboundQueue(InboundQueueEvent::MessageReceived {
+				nonce: 1,
+				fee_burned,
+				..
+			}) if fee_burned == fee
+		));
+	});
+}
+
+#[test]
+fn test_submit_v2_message_with_invalid_xcm() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = RuntimeOrigin::signed(relayer);
+
+		let payload = VersionedMessage::V2(MessageV2 {
+			chain_id: 11155111,
+			origin: H160::repeat_byte(1),
+			fee: 1_000_000_000,
+			xcm: vec![0xff, 0xff, 0xff],
+		})
+		.encode();
+		let message = Message {
+			event_log: mock_event_log_with_payload(1, payload),
+			proof: Proof {
+				receipt_proof: Default::default(),
+				execution_proof: mock_execution_proof(),
+			},
+		};
+
+		assert_noop!(
+			InboundQueue::submit(origin, message),
+			Error::<Test>::ConvertMessage(ConvertMessageError::InvalidXcm)
+		);
+	});
+}
+
+fn mock_message_with_nonce(nonce: u64) -> Message {
+	let payload = VersionedMessage::V2(MessageV2 {
+		chain_id: 11155111,
+		origin: H160::repeat_byte(1),
+		fee: 1_000_000_000,
+		xcm: VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])).encode(),
+	})
+	.encode();
+	Message {
+		event_log: mock_event_log_with_payload(nonce, payload),
+		proof: Proof { receipt_proof: Default::default(), execution_proof: mock_execution_proof() },
+	}
+}
+
+const ASSET_HUB_CHANNEL: [u8; 32] =
+	hex!("c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539");
+
+#[test]
+fn test_submit_out_of_order_rejected_by_default() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = RuntimeOrigin::signed(relayer);
+
+		assert_noop!(
+			InboundQueue::submit(origin.clone(), mock_message_with_nonce(2)),
+			Error::<Test>::InvalidNonce
+		);
+		assert_ok!(InboundQueue::submit(origin.clone(), mock_message_with_nonce(1)));
+		assert_ok!(InboundQueue::submit(origin, mock_message_with_nonce(2)));
+		let channel_id = ChannelId::from(ASSET_HUB_CHANNEL);
+		assert_eq!(<Nonce<Test>>::get(channel_id), 2);
+
+		// Nothing but the nonce is tracked for sequential delivery
+		assert!(!<LatestNonce<Test>>::contains_key(channel_id));
+		assert_eq!(<NonceBitmap<Test>>::iter_prefix(channel_id).count(), 0);
+		assert!(api::missing_nonces::<Test>(channel_id, 10).is_empty());
+	});
+}
+
+#[test]
+fn test_submit_out_of_order() {
+	new_tester().execute_with(|| {
+		OutOfOrderDelivery::set(&true);
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = RuntimeOrigin::signed(relayer);
+		let channel_id = ChannelId::from(ASSET_HUB_CHANNEL);
+
+		assert_ok!(InboundQueue::submit(origin.clone(), mock_message_with_nonce(4)));
+		assert_ok!(InboundQueue::submit(origin.clone(), mock_message_with_nonce(1)));
+		assert_eq!(<Nonce<Test>>::get(channel_id), 1);
+		assert_eq!(<LatestNonce<Test>>::get(channel_id), 4);
+		assert_eq!(api::missing_nonces::<Test>(channel_id, 10), vec![2, 3]);
+		assert_eq!(api::missing_nonces::<Test>(channel_id, 1), vec![2]);
+
+		// Replays are rejected, whether below or above the current nonce
+		assert_noop!(
+			InboundQueue::submit(origin.clone(), mock_message_with_nonce(1)),
+			Error::<Test>::InvalidNonce
+		);
+		assert_noop!(
+			InboundQueue::submit(origin.clone(), mock_message_with_nonce(4)),
+			Error::<Test>::InvalidNonce
+		);
+
+		assert_ok!(InboundQueue::submit(origin.clone(), mock_message_with_nonce(3)));
+		assert_ok!(InboundQueue::submit(origin, mock_message_with_nonce(2)));
+		assert_eq!(<Nonce<Test>>::get(channel_id), 4);
+		assert!(api::missing_nonces::<Test>(channel_id, 10).is_empty());
+	});
+}
+
+#[test]
+fn test_nonce_bitmap_is_pruned_across_buckets() {
+	new_tester().execute_with(|| {
+		OutOfOrderDelivery::set(&true);
+		let channel_id = ChannelId::from(ASSET_HUB_CHANNEL);
+
+		for nonce in (2..=300).rev() {
+			assert_ok!(InboundQueue::mark_delivered(channel_id, nonce));
+		}
+		assert_eq!(<Nonce<Test>>::get(channel_id), 0);
+		assert_eq!(<NonceBitmap<Test>>::iter_prefix(channel_id).count(), 3);
+		assert_eq!(api::missing_nonces::<Test>(channel_id, 10), vec![1]);
+
+		assert_ok!(InboundQueue::mark_delivered(channel_id, 1));
+		assert_eq!(<Nonce<Test>>::get(channel_id), 300);
+		assert_eq!(<NonceBitmap<Test>>::iter_prefix(channel_id).count(), 1);
+		assert!(InboundQueue::is_delivered(channel_id, 300));
+		assert!(!InboundQueue::is_delivered(channel_id, 301));
+
+		// Switching back to sequential delivery continues from the current nonce
+		OutOfOrderDelivery::set(&false);
+		assert_noop!(InboundQueue::mark_delivered(channel_id, 302), Error::<Test>::InvalidNonce);
+		assert_ok!(InboundQueue::mark_delivered(channel_id, 301));
+	});
+}
+
+#[test]
+fn test_out_of_order_delivery_is_limited_to_buckets_ahead() {
+	new_tester().execute_with(|| {
+		OutOfOrderDelivery::set(&true);
+		let channel_id = ChannelId::from(ASSET_HUB_CHANNEL);
+		let limit = MAX_BUCKETS_AHEAD * NONCES_PER_BUCKET;
+
+		assert_noop!(
+			InboundQueue::mark_delivered(channel_id, limit + 1),
+			Error::<Test>::NonceTooFarAhead
+		);
+		for nonce in (2..=limit).rev() {
+			assert_ok!(InboundQueue::mark_delivered(channel_id, nonce));
+		}
+
+		// The current nonce moves past every bucket at once
+		assert_ok!(InboundQueue::mark_delivered(channel_id, 1));
+		assert_eq!(<Nonce<Test>>::get(channel_id), limit);
+		assert_eq!(<NonceBitmap<Test>>::iter_prefix(channel_id).count(), 1);
+		assert_ok!(InboundQueue::mark_delivered(channel_id, 2 * limit));
+	});
+}
+
+#[test]
+fn test_switching_from_sequential_to_out_of_order_delivery() {
+	new_tester().execute_with(|| {
+		let channel_id = ChannelId::from(ASSET_HUB_CHANNEL);
+		for nonce in 1..=42 {
+			assert_ok!(InboundQueue::mark_delivered(channel_id, nonce));
+		}
+		assert!(!<LatestNonce<Test>>::contains_key(channel_id));
+
+		// Out-of-order delivery picks up from the current nonce
+		OutOfOrderDelivery::set(&true);
+		assert!(api::missing_nonces::<Test>(channel_id, 10).is_empty());
+		assert_noop!(InboundQueue::mark_delivered(channel_id, 42), Error::<Test>::InvalidNonce);
+		assert_ok!(InboundQueue::mark_delivered(channel_id, 44));
+		assert_eq!(api::missing_nonces::<Test>(channel_id, 10), vec![43]);
+	});
+}
+
+#[test]
+fn test_submit_v2_message_is_sent_to_asset_hub() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId = Keyring::Bob.into();
+		let origin = RuntimeOrigin::signed(relayer);
+
+		let payload = VersionedMessage::V2(MessageV2 {
+			chain_id: 11155111,
+			origin: H160::repeat_byte(1),
+			fee: 1_000_000_000,
+			xcm: VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])).encode(),
+		})
+		.encode();
+		// The mock XCM sender cannot deliver to the parachain of this channel, so the message is
+		// only delivered if it is sent to AssetHub.
+		let message = Message {
+			event_log: mock_event_log_on_channel(SIBLING_CHANNEL_ID.into(), 1, payload),
+			proof: Proof {
+				receipt_proof: Default::default(),
+				execution_proof: mock_execution_proof(),
+			},
+		};
+
+		assert_ok!(InboundQueue::submit(origin, message));
+		assert!(matches!(
+			last_events(1)[0],
+			RuntimeEvent::InboundQueue(InboundQueueEvent::MessageReceived {
+				channel_id,
+				nonce: 1,
+				..
+			}) if channel_id == SIBLING_CHANNEL_ID.into()
+		));
+	});
+}
@@ -5,10 +5,13 @@ use super::*;
 use bridge_hub_common::AggregateMessageOrigin;
 use codec::Encode;
 use frame_benchmarking::v2::*;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
 use snowbridge_core::{
 	outbound::{Command, Initializer},
 	ChannelId,
 };
+use snowbridge_pallet_inbound_queue_fixtures::delivery_receipt::make_delivery_receipt_message;
 use sp_core::{H160, H256};
 
 #[allow(unused_imports)]
@@ -39,12 +42,21 @@ mod benchmarks {
 		let origin = AggregateMessageOrigin::Snowbridge([1; 32].into());
 		let encoded_enqueued_message = enqueued_message.encode();
 
+		// Worst case, where the order of the oldest message on the channel is removed
+		Nonce::<T>::insert(enqueued_message.channel_id, T::MaxPendingOrders::get());
+		PendingOrders::<T>::insert(
+			enqueued_message.channel_id,
+			1,
+			PendingOrder { block_number: 0u32.into(), fee: 0u128.into(), mint: None },
+		);
+
 		#[block]
 		{
 			let _ = OutboundQueue::<T>::do_process_message(origin, &encoded_enqueued_message);
 		}
 
 		assert_eq!(MessageLeaves::<T>::decode_len().unwrap(), 1);
+		assert!(!PendingOrders::<T>::contains_key(enqueued_message.channel_id, 1));
 
 		Ok(())
 	}
@@ -81,5 +93,42 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// Benchmark for proving the delivery of a message
+	#[benchmark]
+	fn submit_delivery_receipt() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let receipt_message = make_delivery_receipt_message();
+
+		T::Helper::initialize_storage(
+			receipt_message.finalized_header,
+			receipt_message.block_roots_root,
+		);
+
+		// Worst case, where the delivered message minted a Polkadot-native token
+		let channel_id = ChannelId::from(receipt_message.message.event_log.topics[1]);
+		PendingOrders::<T>::insert(
+			channel_id,
+			1,
+			PendingOrder {
+				block_number: 0u32.into(),
+				fee: 1_000_000_000u128.into(),
+				mint: Some((H256::repeat_byte(1), 1_000_000_000)),
+			},
+		);
+
+		#[block]
+		{
+			assert_ok!(OutboundQueue::<T>::submit_delivery_receipt(
+				RawOrigin::Signed(caller.clone()).into(),
+				receipt_message.message,
+			));
+		}
+
+		assert!(!PendingOrders::<T>::contains_key(channel_id, 1));
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(OutboundQueue, crate::mock::new_tester(), crate::mock::Test,);
 }
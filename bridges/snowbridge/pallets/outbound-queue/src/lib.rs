@@ -79,9 +79,18 @@
 //! Or in plain english, relayers are refunded for gas consumption, using a
 //! price that is a minimum of the actual gas price, or `Message.MaxFeePerGas`.
 //!
+//! # Delivery Receipts
+//!
+//! Once a message has been dispatched on Ethereum, the Gateway contract emits an
+//! `InboundMessageDispatched` event. Relayers can prove this event to the pallet using
+//! [`Call::submit_delivery_receipt`], which marks the message as delivered and credits the relayer
+//! with the remote component of the delivery fee via [`Config::RewardLedger`]. Successfully
+//! dispatched mints of Polkadot-native tokens are recorded in [`Config::TokenLedger`].
+//!
 //! # Extrinsics
 //!
 //! * [`Call::set_operating_mode`]: Set the operating mode
+//! * [`Call::submit_delivery_receipt`]: Prove that a message was dispatched on Ethereum
 //!
 //! # Runtime API
 //!
@@ -90,6 +99,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 pub mod api;
 pub mod process_message_impl;
+mod receipt;
 pub mod send_message_impl;
 pub mod types;
 pub mod weights;
@@ -110,21 +120,28 @@ use frame_support::{
 	traits::{tokens::Balance, Contains, Defensive, EnqueueMessage, Get, ProcessMessageError},
 	weights::{Weight, WeightToFee},
 };
+use receipt::DeliveryReceipt;
 use snowbridge_core::{
-	outbound::{Fee, GasMeter, QueuedMessage, VersionedQueuedMessage, ETHER_DECIMALS},
-	BasicOperatingMode, ChannelId,
+	inbound::{self, VerificationError, Verifier},
+	outbound::{
+		Command, Fee, GasMeter, QueuedMessage, RewardLedger, VersionedQueuedMessage, ETHER_DECIMALS,
+	},
+	BasicOperatingMode, ChannelId, TokenLedger,
 };
 use snowbridge_outbound_queue_merkle_tree::merkle_root;
 pub use snowbridge_outbound_queue_merkle_tree::MerkleProof;
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{CheckedDiv, Hash},
 	DigestItem, Saturating,
 };
 use sp_std::prelude::*;
-pub use types::{CommittedMessage, ProcessMessageOriginOf};
+pub use types::{CommittedMessage, PendingOrder, ProcessMessageOriginOf};
 pub use weights::WeightInfo;
 
+#[cfg(feature = "runtime-benchmarks")]
+use snowbridge_beacon_primitives::BeaconHeader;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -138,6 +155,17 @@ pub mod pallet {
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<T> {
+		fn initialize_storage(beacon_header: BeaconHeader, block_roots_root: H256);
+	}
+
+	// Does nothing, for use with a mock verifier
+	#[cfg(feature = "runtime-benchmarks")]
+	impl<T> BenchmarkHelper<T> for () {
+		fn initialize_storage(_: BeaconHeader, _: H256) {}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -173,6 +201,27 @@ pub mod pallet {
 
 		/// Weight information for extrinsics in this pallet
 		type WeightInfo: WeightInfo;
+
+		/// The verifier for delivery receipts from Ethereum
+		type Verifier: Verifier;
+
+		/// Address of the Gateway contract
+		#[pallet::constant]
+		type GatewayAddress: Get<H160>;
+
+		/// Credits relayers for proving the delivery of messages
+		type RewardLedger: RewardLedger<Self::AccountId, Self::Balance>;
+
+		/// Tracks the supply of Polkadot-native tokens which are minted on Ethereum
+		type TokenLedger: TokenLedger;
+
+		/// Max number of undelivered messages per channel whose delivery can still be proved.
+		/// Once a channel moves further ahead, the order of its oldest message is removed.
+		#[pallet::constant]
+		type MaxPendingOrders: Get<u64>;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type Helper: BenchmarkHelper<Self>;
 	}
 
 	#[pallet::event]
@@ -200,6 +249,24 @@ pub mod pallet {
 		},
 		/// Set OperatingMode
 		OperatingModeChanged { mode: BasicOperatingMode },
+		/// A message was dispatched successfully on Ethereum
+		MessageDelivered {
+			/// The message channel
+			channel_id: ChannelId,
+			/// The message nonce
+			nonce: u64,
+			/// ID of the message
+			id: H256,
+		},
+		/// A message was delivered to Ethereum, but its dispatch failed
+		MessageDeliveryFailed {
+			/// The message channel
+			channel_id: ChannelId,
+			/// The message nonce
+			nonce: u64,
+			/// ID of the message
+			id: H256,
+		},
 	}
 
 	#[pallet::error]
@@ -210,6 +277,14 @@ pub mod pallet {
 		Halted,
 		/// Invalid Channel
 		InvalidChannel,
+		/// Delivery receipt came from an invalid Gateway contract
+		InvalidGateway,
+		/// Delivery receipt could not be decoded
+		InvalidReceipt,
+		/// No undelivered message with this nonce exists on the channel
+		InvalidPendingNonce,
+		/// Delivery receipt verification error
+		Verification(VerificationError),
 	}
 
 	/// Messages to be committed in the current block. This storage value is killed in
@@ -239,6 +314,20 @@ pub mod pallet {
 	#[pallet::getter(fn operating_mode)]
 	pub type OperatingMode<T: Config> = StorageValue<_, BasicOperatingMode, ValueQuery>;
 
+	/// Committed messages for each channel whose delivery has not yet been proved, by nonce.
+	/// Removed once delivery is proved, or once [`Config::MaxPendingOrders`] newer messages have
+	/// been committed on the channel.
+	#[pallet::storage]
+	pub type PendingOrders<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ChannelId,
+		Twox64Concat,
+		u64,
+		PendingOrder<BlockNumberFor<T>, T::Balance>,
+		OptionQuery,
+	>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
 	where
@@ -259,6 +348,7 @@ pub mod pallet {
 		fn integrity_test() {
 			let decimals = T::Decimals::get();
 			assert!(decimals == 10 || decimals == 12, "Decimals should be 10 or 12");
+			assert!(T::MaxPendingOrders::get() > 0, "MaxPendingOrders should be greater than 0");
 		}
 	}
 
@@ -276,6 +366,51 @@ pub mod pallet {
 			Self::deposit_event(Event::OperatingModeChanged { mode });
 			Ok(())
 		}
+
+		/// Submit a receipt proving that a message was dispatched by the Gateway contract on
+		/// Ethereum, and credit the relayer.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::submit_delivery_receipt())]
+		pub fn submit_delivery_receipt(
+			origin: OriginFor<T>,
+			message: inbound::Message,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			ensure!(!Self::operating_mode().is_halted(), Error::<T>::Halted);
+
+			// submit message to verifier for verification
+			T::Verifier::verify(&message.event_log, &message.proof)
+				.map_err(|e| Error::<T>::Verification(e))?;
+
+			// Decode event log into a delivery receipt
+			let receipt = DeliveryReceipt::try_from(&message.event_log)
+				.map_err(|_| Error::<T>::InvalidReceipt)?;
+
+			// Verify that the receipt was emitted by the known Gateway contract
+			ensure!(T::GatewayAddress::get() == receipt.gateway, Error::<T>::InvalidGateway);
+
+			// Mark the message as delivered
+			let order = <PendingOrders<T>>::take(receipt.channel_id, receipt.nonce)
+				.ok_or(Error::<T>::InvalidPendingNonce)?;
+
+			// The relayer is rewarded for delivery even if dispatch failed on Ethereum
+			T::RewardLedger::register_reward(&relayer, order.fee);
+
+			let (channel_id, nonce, id) = (receipt.channel_id, receipt.nonce, receipt.message_id);
+			if receipt.success {
+				if let Some((token_id, amount)) = order.mint {
+					T::TokenLedger::on_minted(&token_id, amount);
+				}
+				Self::deposit_event(Event::MessageDelivered { channel_id, nonce, id });
+			} else {
+				if let Some((token_id, amount)) = order.mint {
+					T::TokenLedger::on_mint_failed(&token_id, amount);
+				}
+				Self::deposit_event(Event::MessageDeliveryFailed { channel_id, nonce, id });
+			}
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -330,6 +465,35 @@ pub mod pallet {
 			)?;
 
 			let pricing_params = T::PricingParameters::get();
+
+			// Record the remote fee, which is paid out once delivery of the message is proved
+			let fee = Self::calculate_fee(
+				T::GasMeter::maximum_gas_used_at_most(&queued_message.command),
+				pricing_params.clone(),
+			);
+
+			// Polkadot-native tokens are locked on AssetHub before the mint command is sent
+			let mint = match queued_message.command {
+				Command::MintForeignToken { token_id, amount, .. } => {
+					T::TokenLedger::on_locked(&token_id, amount);
+					Some((token_id, amount))
+				},
+				_ => None,
+			};
+			<PendingOrders<T>>::insert(
+				queued_message.channel_id,
+				nonce,
+				PendingOrder {
+					block_number: frame_system::Pallet::<T>::block_number(),
+					fee: fee.remote,
+					mint,
+				},
+			);
+			// Delivery of the message which is now too far behind can no longer be proved
+			if let Some(stale_nonce) = nonce.checked_sub(T::MaxPendingOrders::get()) {
+				<PendingOrders<T>>::remove(queued_message.channel_id, stale_nonce);
+			}
+
 			let command = queued_message.command.index();
 			let params = queued_message.command.abi_encode();
 			let max_dispatch_gas =
@@ -9,7 +9,9 @@ use frame_support::{
 };
 
 use snowbridge_core::{
-	gwei, meth,
+	gwei,
+	inbound::{Log, Proof, VerificationError, Verifier},
+	meth,
 	outbound::*,
 	pricing::{PricingParameters, Rewards},
 	ParaId, PRIMARY_GOVERNANCE_CHANNEL,
@@ -29,7 +31,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Storage, Event<T>},
 		MessageQueue: pallet_message_queue::{Pallet, Call, Storage, Event<T>},
-		OutboundQueue: crate::{Pallet, Storage, Event<T>},
+		OutboundQueue: crate::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -80,6 +82,32 @@ parameter_types! {
 
 pub const DOT: u128 = 10_000_000_000;
 
+pub const GATEWAY_ADDRESS: [u8; 20] = hex_literal::hex!["eda338e4dc46038493b885327842fd3e301cab39"];
+
+parameter_types! {
+	pub const GatewayAddress: H160 = H160(GATEWAY_ADDRESS);
+	pub const MaxPendingOrders: u64 = 4;
+	pub storage RegisteredRewards: Vec<(AccountId, u128)> = vec![];
+}
+
+pub struct MockVerifier;
+
+impl Verifier for MockVerifier {
+	fn verify(_: &Log, _: &Proof) -> Result<(), VerificationError> {
+		Ok(())
+	}
+}
+
+pub struct MockRewardLedger;
+
+impl RewardLedger<AccountId, u128> for MockRewardLedger {
+	fn register_reward(relayer: &AccountId, reward: u128) {
+		let mut rewards = RegisteredRewards::get();
+		rewards.push((relayer.clone(), reward));
+		RegisteredRewards::set(&rewards);
+	}
+}
+
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Hashing = Keccak256;
@@ -93,6 +121,13 @@ impl crate::Config for Test {
 	type Channels = Everything;
 	type WeightToFee = IdentityFee<u128>;
 	type WeightInfo = ();
+	type Verifier = MockVerifier;
+	type GatewayAddress = GatewayAddress;
+	type RewardLedger = MockRewardLedger;
+	type TokenLedger = ();
+	type MaxPendingOrders = MaxPendingOrders;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
 }
 
 fn setup() {
@@ -172,3 +207,30 @@ pub fn mock_message(sibling_para_id: u32) -> Message {
 		},
 	}
 }
+
+pub fn mock_delivery_receipt(channel_id: ChannelId, nonce: u64, success: bool) -> Log {
+	Log {
+		address: GATEWAY_ADDRESS.into(),
+		topics: vec![
+			sp_io::hashing::keccak_256(b"InboundMessageDispatched(bytes32,uint64,bytes32,bool)")
+				.into(),
+			H256::from_slice(channel_id.as_ref()),
+			H256::repeat_byte(7),
+		],
+		data: ethabi::encode(&[ethabi::Token::Uint(nonce.into()), ethabi::Token::Bool(success)]),
+	}
+}
+
+pub fn mock_execution_proof() -> Proof {
+	Proof {
+		receipt_proof: Default::default(),
+		execution_proof: snowbridge_beacon_primitives::ExecutionProof {
+			header: Default::default(),
+			ancestry_proof: snowbridge_beacon_primitives::AncestryProof::Finalized,
+			execution_header: snowbridge_beacon_primitives::VersionedExecutionPayloadHeader::Deneb(
+				Default::default(),
+			),
+			execution_branch: vec![],
+		},
+	}
+}
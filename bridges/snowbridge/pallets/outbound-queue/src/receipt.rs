@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+use snowbridge_core::{inbound::Log, ChannelId};
+
+use sp_core::{RuntimeDebug, H160, H256};
+use sp_std::prelude::*;
+
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolEvent};
+
+sol! {
+	event InboundMessageDispatched(bytes32 indexed channel_id, uint64 nonce, bytes32 indexed message_id, bool success);
+}
+
+/// A receipt for a message which was dispatched by the Gateway contract on Ethereum.
+#[derive(Clone, RuntimeDebug)]
+pub struct DeliveryReceipt {
+	/// The address of the Gateway contract that emitted this receipt as an event log
+	pub gateway: H160,
+	/// The message Channel
+	pub channel_id: ChannelId,
+	/// The nonce of the dispatched message
+	pub nonce: u64,
+	/// An id for tracing the message on its route (has no role in bridge consensus)
+	pub message_id: H256,
+	/// Whether the message was dispatched successfully
+	pub success: bool,
+}
+
+#[derive(Copy, Clone, RuntimeDebug)]
+pub struct DeliveryReceiptDecodeError;
+
+impl TryFrom<&Log> for DeliveryReceipt {
+	type Error = DeliveryReceiptDecodeError;
+
+	fn try_from(log: &Log) -> Result<Self, Self::Error> {
+		let topics: Vec<B256> = log.topics.iter().map(|x| B256::from_slice(x.as_ref())).collect();
+
+		let event = InboundMessageDispatched::decode_log(topics, &log.data, true)
+			.map_err(|_| DeliveryReceiptDecodeError)?;
+
+		Ok(Self {
+			gateway: log.address,
+			channel_id: ChannelId::from(event.channel_id.as_ref()),
+			nonce: event.nonce,
+			message_id: H256::from(event.message_id.as_ref()),
+			success: event.success,
+		})
+	}
+}
@@ -14,8 +14,9 @@ use snowbridge_core::{
 	ParaId, PricingParameters, Rewards,
 };
 use sp_arithmetic::FixedU128;
-use sp_core::H256;
-use sp_runtime::FixedPointNumber;
+use sp_core::{H160, H256};
+use sp_keyring::AccountKeyring as Keyring;
+use sp_runtime::{AccountId32, FixedPointNumber};
 
 #[test]
 fn submit_messages_and_commit() {
@@ -316,3 +317,163 @@ fn test_calculate_fees_with_valid_exchange_rate_but_remote_fee_calculated_as_zer
 		assert_eq!(fee.remote, 0);
 	});
 }
+
+#[test]
+fn test_calculate_fee_for_contract_call_covers_gas_limit() {
+	use snowbridge_core::outbound::{AgentExecuteCommand, ConstantGasMeter, GasMeter};
+
+	new_tester().execute_with(|| {
+		let call_contract = |gas_limit: u64| Command::AgentExecute {
+			agent_id: Default::default(),
+			command: AgentExecuteCommand::CallContract {
+				target: Default::default(),
+				calldata: vec![0xde, 0xad, 0xbe, 0xef],
+				value: 0,
+				gas_limit,
+			},
+		};
+
+		assert_eq!(
+			ConstantGasMeter::maximum_gas_used_at_most(&call_contract(500_000)),
+			ConstantGasMeter::MAXIMUM_BASE_GAS + 530_000
+		);
+
+		let small = api::calculate_fee::<Test>(call_contract(100_000), None);
+		let large = api::calculate_fee::<Test>(call_contract(1_000_000), None);
+		assert_eq!(small.local, large.local);
+		assert!(large.remote > small.remote);
+	});
+}
+
+#[test]
+fn submit_delivery_receipt_rewards_relayer() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId32 = Keyring::Bob.into();
+		let channel_id: ChannelId = ParaId::from(1000).into();
+
+		let (ticket, _) = OutboundQueue::validate(&mock_message(1000)).unwrap();
+		assert_ok!(OutboundQueue::deliver(ticket));
+		ServiceWeight::set(Some(Weight::MAX));
+		run_to_end_of_next_block();
+
+		let order = PendingOrders::<Test>::get(channel_id, 1).unwrap();
+
+		let message = inbound::Message {
+			event_log: mock_delivery_receipt(channel_id, 1, true),
+			proof: mock_execution_proof(),
+		};
+		assert_ok!(OutboundQueue::submit_delivery_receipt(
+			RuntimeOrigin::signed(relayer.clone()),
+			message.clone()
+		));
+
+		System::assert_last_event(RuntimeEvent::OutboundQueue(Event::MessageDelivered {
+			channel_id,
+			nonce: 1,
+			id: H256::repeat_byte(7),
+		}));
+		assert!(!PendingOrders::<Test>::contains_key(channel_id, 1));
+		assert_eq!(RegisteredRewards::get(), vec![(relayer.clone(), order.fee)]);
+
+		// A receipt can only be redeemed once
+		assert_noop!(
+			OutboundQueue::submit_delivery_receipt(RuntimeOrigin::signed(relayer), message),
+			Error::<Test>::InvalidPendingNonce
+		);
+	});
+}
+
+#[test]
+fn submit_delivery_receipt_for_failed_dispatch() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId32 = Keyring::Bob.into();
+		let channel_id: ChannelId = ParaId::from(1000).into();
+
+		let (ticket, _) = OutboundQueue::validate(&mock_message(1000)).unwrap();
+		assert_ok!(OutboundQueue::deliver(ticket));
+		ServiceWeight::set(Some(Weight::MAX));
+		run_to_end_of_next_block();
+
+		let message = inbound::Message {
+			event_log: mock_delivery_receipt(channel_id, 1, false),
+			proof: mock_execution_proof(),
+		};
+		assert_ok!(OutboundQueue::submit_delivery_receipt(
+			RuntimeOrigin::signed(relayer.clone()),
+			message
+		));
+
+		System::assert_last_event(RuntimeEvent::OutboundQueue(Event::MessageDeliveryFailed {
+			channel_id,
+			nonce: 1,
+			id: H256::repeat_byte(7),
+		}));
+		// The relayer is still rewarded for delivering the message
+		assert_eq!(RegisteredRewards::get().len(), 1);
+	});
+}
+
+#[test]
+fn submit_delivery_receipt_with_invalid_gateway_fails() {
+	new_tester().execute_with(|| {
+		let relayer: AccountId32 = Keyring::Bob.into();
+		let channel_id: ChannelId = ParaId::from(1000).into();
+
+		let (ticket, _) = OutboundQueue::validate(&mock_message(1000)).unwrap();
+		assert_ok!(OutboundQueue::deliver(ticket));
+		ServiceWeight::set(Some(Weight::MAX));
+		run_to_end_of_next_block();
+
+		let mut event_log = mock_delivery_receipt(channel_id, 1, true);
+		event_log.address = H160::repeat_byte(1);
+		let message = inbound::Message { event_log, proof: mock_execution_proof() };
+		assert_noop!(
+			OutboundQueue::submit_delivery_receipt(RuntimeOrigin::signed(relayer.clone()), message),
+			Error::<Test>::InvalidGateway
+		);
+
+		// Receipts for messages which were never committed are rejected
+		let message = inbound::Message {
+			event_log: mock_delivery_receipt(channel_id, 2, true),
+			proof: mock_execution_proof(),
+		};
+		assert_noop!(
+			OutboundQueue::submit_delivery_receipt(RuntimeOrigin::signed(relayer), message),
+			Error::<Test>::InvalidPendingNonce
+		);
+	});
+}
+
+#[test]
+fn oldest_pending_order_is_removed_when_channel_moves_ahead() {
+	new_tester().execute_with(|| {
+		let channel_id: ChannelId = ParaId::from(1000).into();
+		let max_pending_orders = <Test as Config>::MaxPendingOrders::get();
+
+		for _ in 0..=max_pending_orders {
+			let (ticket, _) = OutboundQueue::validate(&mock_message(1000)).unwrap();
+			assert_ok!(OutboundQueue::deliver(ticket));
+		}
+		ServiceWeight::set(Some(Weight::MAX));
+		run_to_end_of_next_block();
+
+		assert_eq!(Nonce::<Test>::get(channel_id), max_pending_orders + 1);
+		assert!(!PendingOrders::<Test>::contains_key(channel_id, 1));
+		for nonce in 2..=max_pending_orders + 1 {
+			assert!(PendingOrders::<Test>::contains_key(channel_id, nonce));
+		}
+
+		// Delivery of the message can no longer be proved
+		let message = inbound::Message {
+			event_log: mock_delivery_receipt(channel_id, 1, true),
+			proof: mock_execution_proof(),
+		};
+		assert_noop!(
+			OutboundQueue::submit_delivery_receipt(
+				RuntimeOrigin::signed(Keyring::Bob.into()),
+				message
+			),
+			Error::<Test>::InvalidPendingNonce
+		);
+	});
+}
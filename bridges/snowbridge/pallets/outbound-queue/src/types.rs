@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use ethabi::Token;
 use frame_support::traits::ProcessMessage;
 use scale_info::TypeInfo;
@@ -10,7 +10,7 @@ use sp_std::prelude::*;
 
 use super::Pallet;
 
-use snowbridge_core::ChannelId;
+use snowbridge_core::{ChannelId, TokenId};
 pub use snowbridge_outbound_queue_merkle_tree::MerkleProof;
 
 pub type ProcessMessageOriginOf<T> = <Pallet<T> as ProcessMessage>::Origin;
@@ -42,6 +42,18 @@ pub struct CommittedMessage {
 	pub id: H256,
 }
 
+/// Message which has been committed for delivery to Ethereum, and whose delivery receipt has not
+/// yet been submitted
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingOrder<BlockNumber, Balance> {
+	/// The block in which the message was committed
+	pub block_number: BlockNumber,
+	/// The remote component of the delivery fee, paid out to the relayer once delivery is proved
+	pub fee: Balance,
+	/// The Polkadot-native token and amount to be minted on Ethereum by this message, if any
+	pub mint: Option<(TokenId, u128)>,
+}
+
 /// Convert message into an ABI-encoded form for delivery to the InboundQueue contract on Ethereum
 impl From<CommittedMessage> for Token {
 	fn from(x: CommittedMessage) -> Token {
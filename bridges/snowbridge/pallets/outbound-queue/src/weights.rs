@@ -34,6 +34,7 @@ pub trait WeightInfo {
 	fn do_process_message() -> Weight;
 	fn commit() -> Weight;
 	fn commit_single() -> Weight;
+	fn submit_delivery_receipt() -> Weight;
 }
 
 // For backwards compatibility and tests.
@@ -46,6 +47,8 @@ impl WeightInfo for () {
 	/// Proof: EthereumOutboundQueue Nonce (max_values: None, max_size: Some(20), added: 2495, mode: MaxEncodedLen)
 	/// Storage: EthereumOutboundQueue Messages (r:1 w:1)
 	/// Proof Skipped: EthereumOutboundQueue Messages (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: EthereumOutboundQueue PendingOrders (r:0 w:2)
+	/// Proof: EthereumOutboundQueue PendingOrders (max_values: None, max_size: Some(125), added: 2600, mode: MaxEncodedLen)
 	fn do_process_message() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `42`
@@ -53,7 +56,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 39_000_000 picoseconds.
 		Weight::from_parts(39_000_000, 3485)
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
 	/// Storage: EthereumOutboundQueue MessageLeaves (r:1 w:0)
 	/// Proof Skipped: EthereumOutboundQueue MessageLeaves (max_values: Some(1), max_size: None, mode: Measured)
@@ -78,4 +81,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	// Not benchmarked yet: verifies the receipt against a finalized beacon state like the inbound
+	// queue `submit`, so a conservative execution time is used, and the proof size covers the
+	// maximum encoded length of every storage item read. To be replaced with the output of the
+	// `submit_delivery_receipt` benchmark.
+	fn submit_delivery_receipt() -> Weight {
+		Weight::from_parts(200_000_000, 13820)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+#[allow(unused_imports)]
+use crate::Pallet as PriceOracle;
+
+fn feeders<T: Config>() -> BoundedVec<T::AccountId, T::MaxFeeders> {
+	(0..T::MaxFeeders::get())
+		.map(|index| account("feeder", index, 0))
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("the number of feeders is bounded; qed")
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn submit() -> Result<(), BenchmarkError> {
+		let feeders = feeders::<T>();
+		let caller = feeders.last().cloned().ok_or(BenchmarkError::Weightless)?;
+		// The first submission is the worst case, as it is bounded by the reference price
+		Feeders::<T>::put(feeders);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			FixedU128::from_rational(1, 300),
+			U256::from(30_000_000_000u64),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_feeders() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let feeders = feeders::<T>();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, feeders);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(PriceOracle, crate::mock::new_tester(), crate::mock::Test);
+}
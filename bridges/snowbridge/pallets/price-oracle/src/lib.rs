@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+//! Price oracle for bridge fees
+//!
+//! # Overview
+//!
+//! Tracks the ETH/DOT exchange rate and the Ether fee per gas unit, as submitted by a whitelisted
+//! set of feeders. The latest prices are exposed through [`PriceFeed`], so that the fees charged
+//! by the bridge can follow the market between governance updates of the pricing parameters.
+//!
+//! To limit the impact of a faulty or compromised feeder, at most one submission is accepted per
+//! block, and each submission may move a price by no more than [`Config::MaxChangePerBlock`] from
+//! its latest value. The first submission is bounded in the same way by the prices of
+//! [`Config::ReferencePrice`], usually the pricing parameters set by governance. Prices which were
+//! not updated within [`Config::MaxAge`] blocks are stale, and are not reported at all, so that
+//! consumers fall back to the values set by governance.
+//!
+//! # Extrinsics
+//!
+//! * [`Call::submit`]: Submit the latest prices. Free of charge for whitelisted feeders.
+//! * [`Call::set_feeders`]: Replace the set of whitelisted feeders.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use snowbridge_core::PriceFeed;
+use sp_core::{RuntimeDebug, U256};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	FixedPointNumber, FixedU128, Perbill,
+};
+
+pub use pallet::*;
+
+/// Prices, as accepted from the feeders
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Price<BlockNumber> {
+	/// ETH/DOT exchange rate
+	pub exchange_rate: FixedU128,
+	/// Ether (wei) fee per gas unit
+	pub fee_per_gas: U256,
+	/// Block at which the prices were updated
+	pub updated_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin that is allowed to update the set of feeders
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of whitelisted feeders
+		#[pallet::constant]
+		type MaxFeeders: Get<u32>;
+
+		/// Maximum change of a price, relative to its latest value, that is accepted per block
+		#[pallet::constant]
+		type MaxChangePerBlock: Get<Perbill>;
+
+		/// Number of blocks after which prices are considered stale
+		#[pallet::constant]
+		type MaxAge: Get<BlockNumberFor<Self>>;
+
+		/// Prices which bound the first submission, before any price was accepted
+		type ReferencePrice: PriceFeed;
+
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Prices were updated. The values may differ from the submitted ones, if those were out
+		/// of bounds.
+		PriceUpdated { exchange_rate: FixedU128, fee_per_gas: U256 },
+		/// The set of feeders was replaced
+		FeedersUpdated { feeders: BoundedVec<T::AccountId, T::MaxFeeders> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin is not a whitelisted feeder
+		NotFeeder,
+		/// Prices were already updated in this block
+		AlreadyUpdated,
+		/// Prices must be non-zero
+		InvalidPrice,
+		/// No reference price is available to bound the first submission
+		NoReferencePrice,
+	}
+
+	/// Accounts that are allowed to submit prices
+	#[pallet::storage]
+	pub type Feeders<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxFeeders>, ValueQuery>;
+
+	/// Latest accepted prices
+	#[pallet::storage]
+	pub type LatestPrice<T: Config> = StorageValue<_, Price<BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Submit the latest ETH/DOT exchange rate and Ether fee per gas unit. Prices which
+		/// change too much since the latest update are moved as far as allowed towards the
+		/// submitted values.
+		///
+		/// - `origin`: Must be a whitelisted feeder
+		/// - `exchange_rate`: ETH/DOT exchange rate
+		/// - `fee_per_gas`: Ether (wei) fee per gas unit
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::submit())]
+		pub fn submit(
+			origin: OriginFor<T>,
+			exchange_rate: FixedU128,
+			fee_per_gas: U256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Feeders::<T>::get().contains(&who), Error::<T>::NotFeeder);
+			ensure!(!exchange_rate.is_zero() && !fee_per_gas.is_zero(), Error::<T>::InvalidPrice);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let price = match LatestPrice::<T>::get() {
+				Some(latest) => {
+					ensure!(latest.updated_at < now, Error::<T>::AlreadyUpdated);
+					Price {
+						exchange_rate: Self::bound_exchange_rate(
+							latest.exchange_rate,
+							exchange_rate,
+						),
+						fee_per_gas: Self::bound_fee_per_gas(latest.fee_per_gas, fee_per_gas),
+						updated_at: now,
+					}
+				},
+				None => {
+					let (reference_rate, reference_fee) = T::ReferencePrice::exchange_rate()
+						.zip(T::ReferencePrice::fee_per_gas())
+						.ok_or(Error::<T>::NoReferencePrice)?;
+					Price {
+						exchange_rate: Self::bound_exchange_rate(reference_rate, exchange_rate),
+						fee_per_gas: Self::bound_fee_per_gas(reference_fee, fee_per_gas),
+						updated_at: now,
+					}
+				},
+			};
+			LatestPrice::<T>::put(price);
+
+			Self::deposit_event(Event::PriceUpdated {
+				exchange_rate: price.exchange_rate,
+				fee_per_gas: price.fee_per_gas,
+			});
+
+			Ok(Pays::No.into())
+		}
+
+		/// Replace the set of accounts which are allowed to submit prices.
+		///
+		/// - `origin`: Must be `Config::UpdateOrigin`
+		/// - `feeders`: New set of feeders
+		#[pallet::call_index(1)]
+		#[pallet::weight((T::WeightInfo::set_feeders(), DispatchClass::Operational))]
+		pub fn set_feeders(
+			origin: OriginFor<T>,
+			feeders: BoundedVec<T::AccountId, T::MaxFeeders>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Feeders::<T>::put(feeders.clone());
+			Self::deposit_event(Event::FeedersUpdated { feeders });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the latest prices, unless they are stale.
+		pub fn fresh_price() -> Option<Price<BlockNumberFor<T>>> {
+			let price = LatestPrice::<T>::get()?;
+			let now = frame_system::Pallet::<T>::block_number();
+			(now.saturating_sub(price.updated_at) <= T::MaxAge::get()).then_some(price)
+		}
+
+		fn bound_exchange_rate(latest: FixedU128, submitted: FixedU128) -> FixedU128 {
+			let max_change =
+				FixedU128::from_inner(T::MaxChangePerBlock::get().mul_floor(latest.into_inner()));
+			submitted.clamp(latest.saturating_sub(max_change), latest.saturating_add(max_change))
+		}
+
+		fn bound_fee_per_gas(latest: U256, submitted: U256) -> U256 {
+			let max_change = latest
+				.saturating_mul(T::MaxChangePerBlock::get().deconstruct().into()) /
+				U256::from(Perbill::ACCURACY);
+			submitted.clamp(latest.saturating_sub(max_change), latest.saturating_add(max_change))
+		}
+	}
+
+	impl<T: Config> PriceFeed for Pallet<T> {
+		fn exchange_rate() -> Option<FixedU128> {
+			Self::fresh_price().map(|price| price.exchange_rate)
+		}
+
+		fn fee_per_gas() -> Option<U256> {
+			Self::fresh_price().map(|price| price.fee_per_gas)
+		}
+	}
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+use crate as snowbridge_pallet_price_oracle;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use snowbridge_core::PriceFeed;
+use sp_core::U256;
+use sp_runtime::{BuildStorage, FixedU128, Perbill};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		PriceOracle: snowbridge_pallet_price_oracle,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+parameter_types! {
+	pub const MaxChangePerBlock: Perbill = Perbill::from_percent(10);
+	pub storage HasReferencePrice: bool = true;
+}
+
+/// Prices set by governance
+pub struct ReferencePrice;
+impl PriceFeed for ReferencePrice {
+	fn exchange_rate() -> Option<FixedU128> {
+		HasReferencePrice::get().then(|| FixedU128::from_u32(100))
+	}
+
+	fn fee_per_gas() -> Option<U256> {
+		HasReferencePrice::get().then(|| U256::from(1000))
+	}
+}
+
+impl snowbridge_pallet_price_oracle::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureRoot<u64>;
+	type MaxFeeders = ConstU32<4>;
+	type MaxChangePerBlock = MaxChangePerBlock;
+	type MaxAge = ConstU64<10>;
+	type ReferencePrice = ReferencePrice;
+	type WeightInfo = ();
+}
+
+pub const FEEDER: u64 = 1;
+
+pub fn new_tester() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		crate::Feeders::<Test>::put(frame_support::BoundedVec::truncate_from(vec![FEEDER]));
+	});
+	ext
+}
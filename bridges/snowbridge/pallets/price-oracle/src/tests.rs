@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError::BadOrigin;
+
+fn submit(exchange_rate: FixedU128, fee_per_gas: U256) -> DispatchResultWithPostInfo {
+	PriceOracle::submit(RuntimeOrigin::signed(FEEDER), exchange_rate, fee_per_gas)
+}
+
+#[test]
+fn submit_first_price() {
+	new_tester().execute_with(|| {
+		assert_eq!(PriceOracle::exchange_rate(), None);
+		assert_eq!(PriceOracle::fee_per_gas(), None);
+
+		assert_ok!(submit(FixedU128::from_u32(105), U256::from(950)));
+
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(105)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(950)));
+		System::assert_last_event(RuntimeEvent::PriceOracle(Event::PriceUpdated {
+			exchange_rate: FixedU128::from_u32(105),
+			fee_per_gas: U256::from(950),
+		}));
+	});
+}
+
+#[test]
+fn first_price_is_bounded_by_reference_price() {
+	new_tester().execute_with(|| {
+		// The reference prices are 100 and 1000, which may change by at most 10%
+		assert_ok!(submit(FixedU128::from_rational(1, 400), U256::from(20)));
+
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(90)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(900)));
+	});
+}
+
+#[test]
+fn first_price_without_reference_price_fails() {
+	new_tester().execute_with(|| {
+		HasReferencePrice::set(&false);
+		assert_noop!(
+			submit(FixedU128::from_u32(100), U256::from(1000)),
+			Error::<Test>::NoReferencePrice
+		);
+	});
+}
+
+#[test]
+fn submit_by_feeder_is_free() {
+	new_tester().execute_with(|| {
+		let post_info = submit(FixedU128::from_rational(1, 400), U256::from(20)).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+	});
+}
+
+#[test]
+fn submit_by_unknown_account_fails() {
+	new_tester().execute_with(|| {
+		assert_noop!(
+			PriceOracle::submit(RuntimeOrigin::signed(2), FixedU128::from_u32(1), U256::one()),
+			Error::<Test>::NotFeeder
+		);
+		assert_noop!(
+			PriceOracle::submit(RuntimeOrigin::root(), FixedU128::from_u32(1), U256::one()),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn submit_zero_price_fails() {
+	new_tester().execute_with(|| {
+		assert_noop!(submit(FixedU128::zero(), U256::one()), Error::<Test>::InvalidPrice);
+		assert_noop!(submit(FixedU128::from_u32(1), U256::zero()), Error::<Test>::InvalidPrice);
+	});
+}
+
+#[test]
+fn submit_twice_in_a_block_fails() {
+	new_tester().execute_with(|| {
+		assert_ok!(submit(FixedU128::from_u32(100), U256::from(1000)));
+		assert_noop!(
+			submit(FixedU128::from_u32(101), U256::from(1001)),
+			Error::<Test>::AlreadyUpdated
+		);
+
+		System::set_block_number(2);
+		assert_ok!(submit(FixedU128::from_u32(101), U256::from(1001)));
+	});
+}
+
+#[test]
+fn price_change_is_bounded_per_block() {
+	new_tester().execute_with(|| {
+		assert_ok!(submit(FixedU128::from_u32(100), U256::from(1000)));
+
+		// Prices may change by at most 10% per block
+		System::set_block_number(2);
+		assert_ok!(submit(FixedU128::from_u32(200), U256::from(500)));
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(110)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(900)));
+		System::assert_last_event(RuntimeEvent::PriceOracle(Event::PriceUpdated {
+			exchange_rate: FixedU128::from_u32(110),
+			fee_per_gas: U256::from(900),
+		}));
+
+		// Changes within bounds are accepted as is
+		System::set_block_number(3);
+		assert_ok!(submit(FixedU128::from_u32(105), U256::from(950)));
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(105)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(950)));
+	});
+}
+
+#[test]
+fn stale_price_is_not_reported() {
+	new_tester().execute_with(|| {
+		assert_ok!(submit(FixedU128::from_u32(100), U256::from(1000)));
+
+		System::set_block_number(11);
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(100)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(1000)));
+
+		System::set_block_number(12);
+		assert_eq!(PriceOracle::exchange_rate(), None);
+		assert_eq!(PriceOracle::fee_per_gas(), None);
+
+		// A stale price still bounds the next update
+		assert_ok!(submit(FixedU128::from_u32(200), U256::from(2000)));
+		assert_eq!(PriceOracle::exchange_rate(), Some(FixedU128::from_u32(110)));
+		assert_eq!(PriceOracle::fee_per_gas(), Some(U256::from(1100)));
+	});
+}
+
+#[test]
+fn set_feeders() {
+	new_tester().execute_with(|| {
+		let feeders: BoundedVec<u64, _> = BoundedVec::truncate_from(vec![2, 3]);
+		assert_ok!(PriceOracle::set_feeders(RuntimeOrigin::root(), feeders.clone()));
+		System::assert_last_event(RuntimeEvent::PriceOracle(Event::FeedersUpdated { feeders }));
+
+		assert_noop!(
+			PriceOracle::submit(RuntimeOrigin::signed(FEEDER), FixedU128::from_u32(1), U256::one()),
+			Error::<Test>::NotFeeder
+		);
+		assert_ok!(PriceOracle::submit(
+			RuntimeOrigin::signed(2),
+			FixedU128::from_u32(1),
+			U256::one()
+		));
+	});
+}
+
+#[test]
+fn set_feeders_as_signed_fails() {
+	new_tester().execute_with(|| {
+		assert_noop!(
+			PriceOracle::set_feeders(RuntimeOrigin::signed(FEEDER), Default::default()),
+			BadOrigin
+		);
+	});
+}
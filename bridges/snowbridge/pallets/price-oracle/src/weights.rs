@@ -0,0 +1,41 @@
+
+//! Weights for `snowbridge-pallet-price-oracle`
+//!
+//! The pallet is not part of any runtime yet, so these weights were not generated by the
+//! benchmark CLI. They must be regenerated from the pallet's benchmarks before the pallet is
+//! added to a runtime.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `snowbridge-pallet-price-oracle`.
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn set_feeders() -> Weight;
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: EthereumPriceOracle Feeders (r:1 w:0)
+	/// Proof: EthereumPriceOracle Feeders (max_values: Some(1), max_size: Some(1601), added: 2096, mode: MaxEncodedLen)
+	/// Storage: EthereumPriceOracle LatestPrice (r:1 w:1)
+	/// Proof: EthereumPriceOracle LatestPrice (max_values: Some(1), max_size: Some(52), added: 547, mode: MaxEncodedLen)
+	/// Storage: EthereumSystem PricingParameters (r:1 w:0)
+	/// Proof: EthereumSystem PricingParameters (max_values: Some(1), max_size: Some(112), added: 607, mode: MaxEncodedLen)
+	fn submit() -> Weight {
+		Weight::from_parts(18_000_000, 3086)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: EthereumPriceOracle Feeders (r:0 w:1)
+	/// Proof: EthereumPriceOracle Feeders (max_values: Some(1), max_size: Some(1601), added: 2096, mode: MaxEncodedLen)
+	fn set_feeders() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -2,12 +2,19 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use snowbridge_core::AgentId;
+use snowbridge_core::{AgentId, TokenSupply};
 use xcm::VersionedLocation;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait ControlApi
 	{
 		fn agent_id(location: VersionedLocation) -> Option<AgentId>;
+
+		/// Returns the amount of a Polkadot-native token, which is locked on AssetHub, and
+		/// the amount minted on Ethereum, or `None` if the token is not registered or its
+		/// supply is not tracked.
+		#[api_version(2)]
+		fn token_supply(location: VersionedLocation) -> Option<TokenSupply>;
 	}
 }
@@ -2,10 +2,10 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 //! Helpers for implementing runtime api
 
-use snowbridge_core::AgentId;
+use snowbridge_core::{AgentId, TokenSupply};
 use xcm::{prelude::*, VersionedLocation};
 
-use crate::{agent_id_of, Config};
+use crate::{agent_id_of, Config, Pallet};
 
 pub fn agent_id<Runtime>(location: VersionedLocation) -> Option<AgentId>
 where
@@ -14,3 +14,11 @@ where
 	let location: Location = location.try_into().ok()?;
 	agent_id_of::<Runtime>(&location).ok()
 }
+
+pub fn token_supply<Runtime>(location: VersionedLocation) -> Option<TokenSupply>
+where
+	Runtime: Config,
+{
+	let location: Location = location.try_into().ok()?;
+	Pallet::<Runtime>::token_supply(&location)
+}
@@ -182,6 +182,50 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn update_token_metadata() -> Result<(), BenchmarkError> {
+		let relay_token_asset_id: Location = Location::parent();
+		let asset = Box::new(VersionedLocation::V4(relay_token_asset_id.clone()));
+		let asset_metadata = AssetMetadata {
+			name: "wnd".as_bytes().to_vec().try_into().unwrap(),
+			symbol: "wnd".as_bytes().to_vec().try_into().unwrap(),
+			decimals: 12,
+		};
+
+		SnowbridgeControl::<T>::do_register_token(
+			&relay_token_asset_id,
+			asset_metadata.clone(),
+			PaysFee::<T>::No,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, asset, asset_metadata);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deregister_token() -> Result<(), BenchmarkError> {
+		let relay_token_asset_id: Location = Location::parent();
+		let asset = Box::new(VersionedLocation::V4(relay_token_asset_id.clone()));
+		let asset_metadata = AssetMetadata {
+			name: "wnd".as_bytes().to_vec().try_into().unwrap(),
+			symbol: "wnd".as_bytes().to_vec().try_into().unwrap(),
+			decimals: 12,
+		};
+
+		SnowbridgeControl::<T>::do_register_token(
+			&relay_token_asset_id,
+			asset_metadata,
+			PaysFee::<T>::No,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, asset);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		SnowbridgeControl,
 		crate::mock::new_test_ext(true),
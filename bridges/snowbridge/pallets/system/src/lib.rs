@@ -36,12 +36,26 @@
 //! Typically, Polkadot governance will use the `force_transfer_native_from_agent` and
 //! `force_update_channel` and extrinsics to manage agents and channels for system parachains.
 //!
+//! ## Pricing
+//!
+//! Fees on both sides of the bridge are computed from the pricing parameters, which are set by
+//! governance using [`Call::set_pricing_parameters`]. On BridgeHub, the exchange rate and the fee
+//! per gas are taken from [`Config::PriceFeed`] instead, for as long as it provides fresh values.
+//!
 //! ## Polkadot-native tokens on Ethereum
 //!
 //! Tokens deposited on AssetHub pallet can be bridged to Ethereum as wrapped ERC20 tokens. As a
 //! prerequisite, the token should be registered first.
 //!
 //! * [`Call::register_token`]: Register a token location as a wrapped ERC20 contract on Ethereum.
+//! * [`Call::update_token_metadata`]: Update the metadata of the wrapped ERC20 contract.
+//! * [`Call::deregister_token`]: Deregister a token which has no outstanding supply on Ethereum.
+//!
+//! The pallet also keeps track of the supply of each registered token. The amount locked on
+//! AssetHub is recorded when a `MintForeignToken` command is committed by the outbound queue, the
+//! amount minted on Ethereum once the delivery of that command is confirmed, and both are reduced
+//! when tokens are burned on Ethereum and sent back. The totals are exposed via the runtime API,
+//! so that issuers can reconcile them.
 #![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(test)]
 mod mock;
@@ -69,15 +83,15 @@ use frame_system::pallet_prelude::*;
 use snowbridge_core::{
 	meth,
 	outbound::{Command, Initializer, Message, OperatingMode, SendError, SendMessage},
-	sibling_sovereign_account, AgentId, AssetMetadata, Channel, ChannelId, ParaId,
-	PricingParameters as PricingParametersRecord, TokenId, TokenIdOf, PRIMARY_GOVERNANCE_CHANNEL,
-	SECONDARY_GOVERNANCE_CHANNEL,
+	sibling_sovereign_account, AgentId, AssetMetadata, Channel, ChannelId, ParaId, PriceFeed,
+	PricingParameters as PricingParametersRecord, TokenId, TokenIdOf, TokenLedger, TokenSupply,
+	PRIMARY_GOVERNANCE_CHANNEL, SECONDARY_GOVERNANCE_CHANNEL,
 };
 use sp_core::{RuntimeDebug, H160, H256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
 	traits::{BadOrigin, MaybeEquivalence},
-	DispatchError, SaturatedConversion,
+	DispatchError, FixedU128, SaturatedConversion,
 };
 use sp_std::prelude::*;
 use xcm::prelude::*;
@@ -142,7 +156,11 @@ pub mod pallet {
 
 	use super::*;
 
+	/// The in-code storage version.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -168,6 +186,10 @@ pub mod pallet {
 		/// Number of decimal places of local currency
 		type DefaultPricingParameters: Get<PricingParametersOf<Self>>;
 
+		/// Market prices, which take precedence over the exchange rate and fee per gas set by
+		/// governance for as long as they are fresh
+		type PriceFeed: PriceFeed;
+
 		/// Cost of delivering a message from Ethereum
 		#[pallet::constant]
 		type InboundDeliveryCost: Get<BalanceOf<Self>>;
@@ -234,6 +256,20 @@ pub mod pallet {
 			/// ID of Polkadot-native token on Ethereum
 			foreign_token_id: H256,
 		},
+		/// Update metadata of the wrapped ERC20 token of a Polkadot-native token on Ethereum
+		UpdateTokenMetadata {
+			/// Location of Polkadot-native token
+			location: VersionedLocation,
+			/// ID of Polkadot-native token on Ethereum
+			foreign_token_id: H256,
+		},
+		/// Deregister Polkadot-native token on Ethereum
+		DeregisterToken {
+			/// Location of Polkadot-native token
+			location: VersionedLocation,
+			/// ID of Polkadot-native token on Ethereum
+			foreign_token_id: H256,
+		},
 	}
 
 	#[pallet::error]
@@ -249,6 +285,9 @@ pub mod pallet {
 		InvalidTokenTransferFees,
 		InvalidPricingParameters,
 		InvalidUpgradeParameters,
+		TokenNotRegistered,
+		TokenSupplyNotZero,
+		TokenSupplyUnknown,
 	}
 
 	/// The set of registered agents
@@ -276,6 +315,12 @@ pub mod pallet {
 	pub type NativeToForeignId<T: Config> =
 		StorageMap<_, Blake2_128Concat, xcm::v4::Location, TokenId, OptionQuery>;
 
+	/// Locked and minted supply of Polkadot-native tokens, keyed by foreign token ID. Tokens
+	/// registered before supplies were tracked have no entry, unless seeded by a migration.
+	#[pallet::storage]
+	pub type TokenSupplies<T: Config> =
+		StorageMap<_, Blake2_128Concat, TokenId, TokenSupply, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -635,6 +680,87 @@ pub mod pallet {
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Updates the metadata of the wrapped ERC20 token of a registered Polkadot-native token.
+		/// Privileged. Can only be called by root.
+		///
+		/// Fee required: No
+		///
+		/// - `origin`: Must be root
+		/// - `location`: Location of the asset (relative to this chain)
+		/// - `metadata`: New metadata of the ERC20 contract on Ethereum
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::update_token_metadata())]
+		pub fn update_token_metadata(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+			metadata: AssetMetadata,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let location: Location =
+				(*location).try_into().map_err(|_| Error::<T>::UnsupportedLocationVersion)?;
+			let (location, token_id) = Self::registered_token(&location)?;
+
+			let command = Command::UpdateForeignTokenMetadata {
+				token_id,
+				name: metadata.name.into_inner(),
+				symbol: metadata.symbol.into_inner(),
+				decimals: metadata.decimals,
+			};
+			Self::send(SECONDARY_GOVERNANCE_CHANNEL, command, PaysFee::<T>::No)?;
+
+			Self::deposit_event(Event::<T>::UpdateTokenMetadata {
+				location: location.into(),
+				foreign_token_id: token_id,
+			});
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::WeightInfo::update_token_metadata()),
+				pays_fee: Pays::No,
+			})
+		}
+
+		/// Deregisters a Polkadot-native token. The token may only be deregistered once all
+		/// wrapped tokens are burned on Ethereum and nothing is locked on AssetHub.
+		/// Privileged. Can only be called by root.
+		///
+		/// Fee required: No
+		///
+		/// - `origin`: Must be root
+		/// - `location`: Location of the asset (relative to this chain)
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::deregister_token())]
+		pub fn deregister_token(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let location: Location =
+				(*location).try_into().map_err(|_| Error::<T>::UnsupportedLocationVersion)?;
+			let (location, token_id) = Self::registered_token(&location)?;
+
+			let supply = TokenSupplies::<T>::get(token_id).ok_or(Error::<T>::TokenSupplyUnknown)?;
+			ensure!(supply == TokenSupply::default(), Error::<T>::TokenSupplyNotZero);
+
+			NativeToForeignId::<T>::remove(&location);
+			ForeignToNativeId::<T>::remove(token_id);
+			TokenSupplies::<T>::remove(token_id);
+
+			let command = Command::DeregisterForeignToken { token_id };
+			Self::send(SECONDARY_GOVERNANCE_CHANNEL, command, PaysFee::<T>::No)?;
+
+			Self::deposit_event(Event::<T>::DeregisterToken {
+				location: location.into(),
+				foreign_token_id: token_id,
+			});
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(T::WeightInfo::deregister_token()),
+				pays_fee: Pays::No,
+			})
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -730,19 +856,12 @@ pub mod pallet {
 			metadata: AssetMetadata,
 			pays_fee: PaysFee<T>,
 		) -> Result<(), DispatchError> {
-			let ethereum_location = T::EthereumLocation::get();
-			// reanchor to Ethereum context
-			let location = location
-				.clone()
-				.reanchored(&ethereum_location, &T::UniversalLocation::get())
-				.map_err(|_| Error::<T>::LocationConversionFailed)?;
-
-			let token_id = TokenIdOf::convert_location(&location)
-				.ok_or(Error::<T>::LocationConversionFailed)?;
+			let (location, token_id) = Self::foreign_token_id(location)?;
 
 			if !ForeignToNativeId::<T>::contains_key(token_id) {
 				NativeToForeignId::<T>::insert(location.clone(), token_id);
 				ForeignToNativeId::<T>::insert(token_id, location.clone());
+				TokenSupplies::<T>::insert(token_id, TokenSupply::default());
 			}
 
 			let command = Command::RegisterForeignToken {
@@ -760,6 +879,38 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Reanchors the `location` of a Polkadot-native token to the Ethereum context and
+		/// computes its foreign token ID.
+		fn foreign_token_id(location: &Location) -> Result<(Location, TokenId), DispatchError> {
+			let ethereum_location = T::EthereumLocation::get();
+			// reanchor to Ethereum context
+			let location = location
+				.clone()
+				.reanchored(&ethereum_location, &T::UniversalLocation::get())
+				.map_err(|_| Error::<T>::LocationConversionFailed)?;
+
+			let token_id = TokenIdOf::convert_location(&location)
+				.ok_or(Error::<T>::LocationConversionFailed)?;
+
+			Ok((location, token_id))
+		}
+
+		/// Same as [`Self::foreign_token_id`], but fails if the token is not registered.
+		pub(crate) fn registered_token(
+			location: &Location,
+		) -> Result<(Location, TokenId), DispatchError> {
+			let (location, token_id) = Self::foreign_token_id(location)?;
+			ensure!(ForeignToNativeId::<T>::contains_key(token_id), Error::<T>::TokenNotRegistered);
+			Ok((location, token_id))
+		}
+
+		/// Returns the locked and minted supply of a registered Polkadot-native token, if it is
+		/// tracked.
+		pub fn token_supply(location: &Location) -> Option<TokenSupply> {
+			let (_, token_id) = Self::registered_token(location).ok()?;
+			TokenSupplies::<T>::get(token_id)
+		}
 	}
 
 	impl<T: Config> StaticLookup for Pallet<T> {
@@ -778,7 +929,29 @@ pub mod pallet {
 
 	impl<T: Config> Get<PricingParametersOf<T>> for Pallet<T> {
 		fn get() -> PricingParametersOf<T> {
-			PricingParameters::<T>::get()
+			let mut params = PricingParameters::<T>::get();
+			// Fall back to the values set by governance if the feed is unavailable or stale
+			if let Some(exchange_rate) = T::PriceFeed::exchange_rate() {
+				params.exchange_rate = exchange_rate;
+			}
+			if let Some(fee_per_gas) = T::PriceFeed::fee_per_gas() {
+				params.fee_per_gas = fee_per_gas;
+			}
+			params
+		}
+	}
+
+	/// Prices set by governance, ignoring any price feed. Used to bound the prices submitted to
+	/// a price feed.
+	pub struct GovernancePrices<T>(PhantomData<T>);
+
+	impl<T: Config> PriceFeed for GovernancePrices<T> {
+		fn exchange_rate() -> Option<FixedU128> {
+			Some(PricingParameters::<T>::get().exchange_rate)
+		}
+
+		fn fee_per_gas() -> Option<U256> {
+			Some(PricingParameters::<T>::get().fee_per_gas)
 		}
 	}
 
@@ -790,4 +963,33 @@ pub mod pallet {
 			NativeToForeignId::<T>::get(location)
 		}
 	}
+
+	// Supplies are only tracked for tokens which have an entry in `TokenSupplies`, so that
+	// untracked tokens are never reported with a partial supply.
+	impl<T: Config> TokenLedger for Pallet<T> {
+		fn on_locked(token_id: &TokenId, amount: u128) {
+			TokenSupplies::<T>::mutate_extant(token_id, |supply| {
+				supply.locked = supply.locked.saturating_add(amount)
+			});
+		}
+
+		fn on_minted(token_id: &TokenId, amount: u128) {
+			TokenSupplies::<T>::mutate_extant(token_id, |supply| {
+				supply.minted = supply.minted.saturating_add(amount)
+			});
+		}
+
+		fn on_mint_failed(token_id: &TokenId, amount: u128) {
+			TokenSupplies::<T>::mutate_extant(token_id, |supply| {
+				supply.locked = supply.locked.saturating_sub(amount)
+			});
+		}
+
+		fn on_burned(token_id: &TokenId, amount: u128) {
+			TokenSupplies::<T>::mutate_extant(token_id, |supply| {
+				supply.locked = supply.locked.saturating_sub(amount);
+				supply.minted = supply.minted.saturating_sub(amount);
+			});
+		}
+	}
 }
@@ -72,3 +72,98 @@ pub mod v0 {
 		}
 	}
 }
+
+pub mod v1 {
+	use frame_support::{pallet_prelude::*, weights::Weight};
+
+	use super::*;
+
+	const LOG_TARGET: &str = "ethereum_system::migration";
+
+	/// Seeds the supplies of tokens which were registered before supplies were tracked.
+	///
+	/// `InitialSupplies` lists the location of each such token (relative to this chain), with its
+	/// locked and minted supply as reconciled off-chain. Registered tokens which are not listed
+	/// remain untracked, and cannot be deregistered until their supply is known.
+	pub struct InitializeTokenSupplies<T, InitialSupplies>(
+		sp_std::marker::PhantomData<(T, InitialSupplies)>,
+	);
+	impl<T, InitialSupplies> OnRuntimeUpgrade for InitializeTokenSupplies<T, InitialSupplies>
+	where
+		T: Config,
+		InitialSupplies: Get<Vec<(Location, TokenSupply)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Token supplies already initialized. Skipping."
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut weight = T::DbWeight::get().reads(1);
+			for (location, supply) in InitialSupplies::get() {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				match Pallet::<T>::registered_token(&location) {
+					Ok((_, token_id)) => {
+						TokenSupplies::<T>::insert(token_id, supply);
+						weight.saturating_accrue(T::DbWeight::get().writes(1));
+					},
+					Err(_) => log::warn!(
+						target: LOG_TARGET,
+						"Token {:?} is not registered, its supply was not initialized.",
+						location
+					),
+				}
+			}
+
+			let mut untracked = 0u32;
+			for token_id in ForeignToNativeId::<T>::iter_keys() {
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				if !TokenSupplies::<T>::contains_key(token_id) {
+					untracked += 1;
+				}
+			}
+			if untracked > 0 {
+				log::warn!(
+					target: LOG_TARGET,
+					"{} registered tokens have no tracked supply.",
+					untracked
+				);
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"Token supplies initialized."
+			);
+			weight.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Token supplies not initialized. Initialization will run."
+				);
+			} else {
+				log::info!(
+					target: LOG_TARGET,
+					"Token supplies are initialized. Initialization will not run."
+				);
+			}
+			Ok(vec![])
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				StorageVersion::get::<Pallet<T>>() == 1,
+				"Token supplies were not initialized."
+			);
+			Ok(())
+		}
+	}
+}
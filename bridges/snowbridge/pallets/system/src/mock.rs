@@ -3,16 +3,20 @@
 use crate as snowbridge_system;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{tokens::fungible::Mutate, ConstU128, ConstU8},
+	traits::{tokens::fungible::Mutate, ConstU128, ConstU64, ConstU8},
 	weights::IdentityFee,
 	PalletId,
 };
-use sp_core::H256;
+use sp_core::{H160, H256, U256};
 use xcm_executor::traits::ConvertLocation;
 
 use snowbridge_core::{
-	gwei, meth, outbound::ConstantGasMeter, sibling_sovereign_account, AgentId, AllowSiblingsOnly,
-	ParaId, PricingParameters, Rewards,
+	gwei,
+	inbound::{Log, Proof, VerificationError, Verifier},
+	meth,
+	outbound::ConstantGasMeter,
+	sibling_sovereign_account, AgentId, AllowSiblingsOnly, ParaId, PriceFeed, PricingParameters,
+	Rewards,
 };
 use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup, Keccak256},
@@ -146,6 +150,7 @@ parameter_types! {
 	pub const MaxMessagePayloadSize: u32 = 1024;
 	pub const MaxMessagesPerBlock: u32 = 20;
 	pub const OwnParaId: ParaId = ParaId::new(1013);
+	pub const GatewayAddress: H160 = H160::repeat_byte(1);
 }
 
 impl snowbridge_pallet_outbound_queue::Config for Test {
@@ -161,6 +166,21 @@ impl snowbridge_pallet_outbound_queue::Config for Test {
 	type Channels = EthereumSystem;
 	type WeightToFee = IdentityFee<u128>;
 	type WeightInfo = ();
+	type Verifier = MockVerifier;
+	type GatewayAddress = GatewayAddress;
+	type RewardLedger = ();
+	type TokenLedger = EthereumSystem;
+	type MaxPendingOrders = ConstU64<8192>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+}
+
+pub struct MockVerifier;
+
+impl Verifier for MockVerifier {
+	fn verify(_: &Log, _: &Proof) -> Result<(), VerificationError> {
+		Ok(())
+	}
 }
 
 parameter_types! {
@@ -190,6 +210,20 @@ parameter_types! {
 		multiplier: FixedU128::from_rational(4, 3)
 	};
 	pub const InboundDeliveryCost: u128 = 1_000_000_000;
+	pub storage FeedExchangeRate: Option<FixedU128> = None;
+	pub storage FeedFeePerGas: Option<U256> = None;
+}
+
+pub struct MockPriceFeed;
+
+impl PriceFeed for MockPriceFeed {
+	fn exchange_rate() -> Option<FixedU128> {
+		FeedExchangeRate::get()
+	}
+
+	fn fee_per_gas() -> Option<U256> {
+		FeedFeePerGas::get()
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -207,6 +241,7 @@ impl crate::Config for Test {
 	type TreasuryAccount = TreasuryAccount;
 	type Token = Balances;
 	type DefaultPricingParameters = Parameters;
+	type PriceFeed = MockPriceFeed;
 	type WeightInfo = ();
 	type InboundDeliveryCost = InboundDeliveryCost;
 	type UniversalLocation = UniversalLocation;
@@ -3,9 +3,9 @@
 use crate::{mock::*, *};
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
-use snowbridge_core::eth;
+use snowbridge_core::{eth, gwei};
 use sp_core::H256;
-use sp_runtime::{AccountId32, DispatchError::BadOrigin, TokenError};
+use sp_runtime::{AccountId32, DispatchError::BadOrigin, FixedU128, TokenError};
 
 #[test]
 fn create_agent() {
@@ -206,6 +206,33 @@ fn set_pricing_parameters_invalid() {
 	});
 }
 
+#[test]
+fn pricing_parameters_follow_price_feed() {
+	new_test_ext(true).execute_with(|| {
+		let params = Parameters::get();
+
+		// No fresh prices, so the governance-set values are used
+		assert_eq!(<EthereumSystem as Get<PricingParametersOf<Test>>>::get(), params);
+
+		FeedExchangeRate::set(&Some(FixedU128::from_rational(1, 500)));
+		let feed_params = <EthereumSystem as Get<PricingParametersOf<Test>>>::get();
+		assert_eq!(feed_params.exchange_rate, FixedU128::from_rational(1, 500));
+		assert_eq!(feed_params.fee_per_gas, params.fee_per_gas);
+
+		FeedFeePerGas::set(&Some(gwei(30)));
+		let feed_params = <EthereumSystem as Get<PricingParametersOf<Test>>>::get();
+		assert_eq!(feed_params.exchange_rate, FixedU128::from_rational(1, 500));
+		assert_eq!(feed_params.fee_per_gas, gwei(30));
+		assert_eq!(feed_params.rewards, params.rewards);
+		assert_eq!(feed_params.multiplier, params.multiplier);
+
+		// Governance-set values are left untouched
+		assert_eq!(PricingParameters::<Test>::get(), params);
+		assert_eq!(GovernancePrices::<Test>::exchange_rate(), Some(params.exchange_rate));
+		assert_eq!(GovernancePrices::<Test>::fee_per_gas(), Some(params.fee_per_gas));
+	});
+}
+
 #[test]
 fn set_token_transfer_fees() {
 	new_test_ext(true).execute_with(|| {
@@ -744,3 +771,316 @@ fn register_ethereum_native_token_fails() {
 		);
 	});
 }
+
+#[test]
+fn update_token_metadata_succeeds() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.clone().into());
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			versioned_location.clone(),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored.clone()).unwrap();
+
+		assert_ok!(EthereumSystem::update_token_metadata(
+			RuntimeOrigin::root(),
+			versioned_location,
+			Default::default()
+		));
+
+		System::assert_last_event(RuntimeEvent::EthereumSystem(
+			Event::<Test>::UpdateTokenMetadata {
+				location: reanchored.into(),
+				foreign_token_id: token_id,
+			},
+		));
+	});
+}
+
+#[test]
+fn update_token_metadata_of_unregistered_token_fails() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.into());
+
+		assert_noop!(
+			EthereumSystem::update_token_metadata(
+				RuntimeOrigin::root(),
+				versioned_location,
+				Default::default()
+			),
+			Error::<Test>::TokenNotRegistered
+		);
+	});
+}
+
+#[test]
+fn update_token_metadata_with_signed_yields_bad_origin() {
+	new_test_ext(true).execute_with(|| {
+		let origin = RuntimeOrigin::signed([14; 32].into());
+		let location = Location::new(1, [Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.into());
+
+		assert_noop!(
+			EthereumSystem::update_token_metadata(origin, versioned_location, Default::default()),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn deregister_token_succeeds() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.clone().into());
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			versioned_location.clone(),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored.clone()).unwrap();
+
+		// all wrapped tokens are burned and sent back
+		EthereumSystem::on_locked(&token_id, 1000);
+		EthereumSystem::on_minted(&token_id, 1000);
+		EthereumSystem::on_burned(&token_id, 1000);
+
+		assert_ok!(EthereumSystem::deregister_token(RuntimeOrigin::root(), versioned_location));
+
+		assert_eq!(NativeToForeignId::<Test>::get(reanchored.clone()), None);
+		assert_eq!(ForeignToNativeId::<Test>::get(token_id), None);
+		assert!(!TokenSupplies::<Test>::contains_key(token_id));
+
+		System::assert_last_event(RuntimeEvent::EthereumSystem(Event::<Test>::DeregisterToken {
+			location: reanchored.into(),
+			foreign_token_id: token_id,
+		}));
+	});
+}
+
+#[test]
+fn deregister_token_with_outstanding_supply_fails() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.clone().into());
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			versioned_location.clone(),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+
+		// tokens are locked, but the mint is not yet confirmed
+		EthereumSystem::on_locked(&token_id, 1000);
+		assert_noop!(
+			EthereumSystem::deregister_token(RuntimeOrigin::root(), versioned_location.clone()),
+			Error::<Test>::TokenSupplyNotZero
+		);
+
+		EthereumSystem::on_minted(&token_id, 1000);
+		EthereumSystem::on_burned(&token_id, 400);
+		assert_noop!(
+			EthereumSystem::deregister_token(RuntimeOrigin::root(), versioned_location),
+			Error::<Test>::TokenSupplyNotZero
+		);
+	});
+}
+
+#[test]
+fn deregister_token_with_unknown_supply_fails() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.clone().into());
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			versioned_location.clone(),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+
+		// token registered before supplies were tracked
+		TokenSupplies::<Test>::remove(token_id);
+		assert_noop!(
+			EthereumSystem::deregister_token(RuntimeOrigin::root(), versioned_location),
+			Error::<Test>::TokenSupplyUnknown
+		);
+	});
+}
+
+#[test]
+fn deregister_unregistered_token_fails() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let versioned_location: Box<VersionedLocation> = Box::new(location.into());
+
+		assert_noop!(
+			EthereumSystem::deregister_token(RuntimeOrigin::root(), versioned_location),
+			Error::<Test>::TokenNotRegistered
+		);
+	});
+}
+
+#[test]
+fn token_supply_tracks_locked_minted_and_burned_amounts() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: VersionedLocation = location.clone().into();
+
+		// unregistered tokens have no supply
+		assert_eq!(api::token_supply::<Test>(versioned_location.clone()), None);
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			Box::new(versioned_location.clone()),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+		assert_eq!(
+			api::token_supply::<Test>(versioned_location.clone()),
+			Some(TokenSupply { locked: 0, minted: 0 })
+		);
+
+		EthereumSystem::on_locked(&token_id, 1000);
+		EthereumSystem::on_locked(&token_id, 500);
+		EthereumSystem::on_minted(&token_id, 1000);
+		assert_eq!(
+			api::token_supply::<Test>(versioned_location.clone()),
+			Some(TokenSupply { locked: 1500, minted: 1000 })
+		);
+
+		EthereumSystem::on_burned(&token_id, 800);
+		assert_eq!(
+			api::token_supply::<Test>(versioned_location.clone()),
+			Some(TokenSupply { locked: 700, minted: 200 })
+		);
+
+		// a failed mint unlocks the amount again
+		EthereumSystem::on_locked(&token_id, 300);
+		EthereumSystem::on_mint_failed(&token_id, 300);
+		assert_eq!(
+			api::token_supply::<Test>(versioned_location),
+			Some(TokenSupply { locked: 700, minted: 200 })
+		);
+	});
+}
+
+#[test]
+fn token_supply_is_not_tracked_without_entry() {
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let versioned_location: VersionedLocation = location.into();
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			Box::new(versioned_location.clone()),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+		TokenSupplies::<Test>::remove(token_id);
+
+		EthereumSystem::on_locked(&token_id, 1000);
+		assert!(!TokenSupplies::<Test>::contains_key(token_id));
+		assert_eq!(api::token_supply::<Test>(versioned_location), None);
+	});
+}
+
+frame_support::parameter_types! {
+	pub InitialTokenSupplies: Vec<(Location, TokenSupply)> = vec![
+		(Location::new(1, [Parachain(2004)]), TokenSupply { locked: 1000, minted: 900 }),
+		(Location::new(1, [Parachain(2005)]), TokenSupply { locked: 500, minted: 500 }),
+	];
+}
+
+#[test]
+fn migration_initializes_token_supplies() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext(true).execute_with(|| {
+		let location = Location::new(1, [Parachain(2004)]);
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			Box::new(location.into()),
+			Default::default()
+		));
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+
+		// state before supplies were tracked
+		TokenSupplies::<Test>::remove(token_id);
+		StorageVersion::new(0).put::<EthereumSystem>();
+
+		migration::v1::InitializeTokenSupplies::<Test, InitialTokenSupplies>::on_runtime_upgrade();
+
+		// unregistered tokens are skipped
+		assert_eq!(TokenSupplies::<Test>::iter().count(), 1);
+		assert_eq!(
+			TokenSupplies::<Test>::get(token_id),
+			Some(TokenSupply { locked: 1000, minted: 900 })
+		);
+		assert_eq!(EthereumSystem::on_chain_storage_version(), 1);
+
+		// the migration only runs once
+		TokenSupplies::<Test>::remove(token_id);
+		migration::v1::InitializeTokenSupplies::<Test, InitialTokenSupplies>::on_runtime_upgrade();
+		assert!(!TokenSupplies::<Test>::contains_key(token_id));
+	});
+}
+
+#[test]
+fn migration_without_initial_supplies_leaves_registered_tokens_untracked() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	frame_support::parameter_types! {
+		pub NoInitialTokenSupplies: Vec<(Location, TokenSupply)> = vec![];
+	}
+
+	new_test_ext(true).execute_with(|| {
+		let location: VersionedLocation = Location::new(1, [Parachain(2004)]).into();
+		let new_location: VersionedLocation = Location::new(1, [Parachain(2005)]).into();
+
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			Box::new(location.clone()),
+			Default::default()
+		));
+		let reanchored = Location::new(1, [GlobalConsensus(Polkadot), Parachain(2004)]);
+		let token_id = NativeToForeignId::<Test>::get(reanchored).unwrap();
+
+		// state before supplies were tracked
+		TokenSupplies::<Test>::remove(token_id);
+		StorageVersion::new(0).put::<EthereumSystem>();
+
+		migration::v1::InitializeTokenSupplies::<Test, NoInitialTokenSupplies>::on_runtime_upgrade(
+		);
+		assert_eq!(EthereumSystem::on_chain_storage_version(), 1);
+
+		// the token registered before the upgrade is neither tracked nor deregisterable
+		assert_eq!(api::token_supply::<Test>(location.clone()), None);
+		assert_noop!(
+			EthereumSystem::deregister_token(RuntimeOrigin::root(), Box::new(location)),
+			Error::<Test>::TokenSupplyUnknown
+		);
+
+		// while tokens registered after the upgrade are tracked from zero
+		assert_ok!(EthereumSystem::register_token(
+			RuntimeOrigin::root(),
+			Box::new(new_location.clone()),
+			Default::default()
+		));
+		assert_eq!(api::token_supply::<Test>(new_location.clone()), Some(TokenSupply::default()));
+		assert_ok!(EthereumSystem::deregister_token(RuntimeOrigin::root(), Box::new(new_location)));
+	});
+}
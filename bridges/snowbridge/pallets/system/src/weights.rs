@@ -43,6 +43,8 @@ pub trait WeightInfo {
 	fn set_token_transfer_fees() -> Weight;
 	fn set_pricing_parameters() -> Weight;
 	fn register_token() -> Weight;
+	fn update_token_metadata() -> Weight;
+	fn deregister_token() -> Weight;
 }
 
 // For backwards compatibility and tests.
@@ -257,4 +259,18 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+
+	// Not benchmarked yet: sends the command like `register_token`, without writing the token.
+	// To be replaced with the output of the `update_token_metadata` benchmark.
+	fn update_token_metadata() -> Weight {
+		Self::register_token()
+	}
+
+	// Not benchmarked yet: sends the command like `register_token` and removes the token with
+	// its supply. To be replaced with the output of the `deregister_token` benchmark.
+	fn deregister_token() -> Weight {
+		Self::register_token()
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
@@ -15,10 +15,10 @@ pub mod updates;
 mod serde_utils;
 
 pub use types::{
-	AncestryProof, BeaconHeader, CompactBeaconState, ExecutionPayloadHeader, ExecutionProof,
-	FinalizedHeaderState, Fork, ForkData, ForkVersion, ForkVersions, Mode, PublicKey, Signature,
-	SigningData, SyncAggregate, SyncCommittee, SyncCommitteePrepared,
-	VersionedExecutionPayloadHeader,
+	AncestryProof, BeaconHeader, BlockRootsProof, CompactBeaconState, ExecutionPayloadHeader,
+	ExecutionProof, FinalizedHeaderState, Fork, ForkData, ForkVersion, ForkVersions,
+	HistoricalSummariesProof, Mode, PublicKey, Signature, SigningData, SyncAggregate,
+	SyncCommittee, SyncCommitteePrepared, VersionedExecutionPayloadHeader,
 };
 pub use updates::{CheckpointUpdate, NextSyncCommitteeUpdate, Update};
 
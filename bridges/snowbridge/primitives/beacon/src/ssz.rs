@@ -276,3 +276,7 @@ pub mod deneb {
 		}
 	}
 }
+
+pub mod electra {
+	pub use super::deneb::SSZExecutionPayloadHeader;
+}
@@ -36,6 +36,7 @@ pub struct ForkVersions {
 	pub bellatrix: Fork,
 	pub capella: Fork,
 	pub deneb: Fork,
+	pub electra: Fork,
 }
 
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -366,6 +367,7 @@ pub struct CompactBeaconState {
 pub enum VersionedExecutionPayloadHeader {
 	Capella(ExecutionPayloadHeader),
 	Deneb(deneb::ExecutionPayloadHeader),
+	Electra(electra::ExecutionPayloadHeader),
 }
 
 impl VersionedExecutionPayloadHeader {
@@ -379,6 +381,10 @@ impl VersionedExecutionPayloadHeader {
 				hash_tree_root::<crate::ssz::deneb::SSZExecutionPayloadHeader>(
 					execution_payload_header.clone().try_into()?,
 				),
+			VersionedExecutionPayloadHeader::Electra(execution_payload_header) =>
+				hash_tree_root::<crate::ssz::electra::SSZExecutionPayloadHeader>(
+					execution_payload_header.clone().try_into()?,
+				),
 		}
 	}
 
@@ -388,6 +394,8 @@ impl VersionedExecutionPayloadHeader {
 				execution_payload_header.block_hash,
 			VersionedExecutionPayloadHeader::Deneb(execution_payload_header) =>
 				execution_payload_header.block_hash,
+			VersionedExecutionPayloadHeader::Electra(execution_payload_header) =>
+				execution_payload_header.block_hash,
 		}
 	}
 
@@ -397,6 +405,8 @@ impl VersionedExecutionPayloadHeader {
 				execution_payload_header.block_number,
 			VersionedExecutionPayloadHeader::Deneb(execution_payload_header) =>
 				execution_payload_header.block_number,
+			VersionedExecutionPayloadHeader::Electra(execution_payload_header) =>
+				execution_payload_header.block_number,
 		}
 	}
 
@@ -406,6 +416,8 @@ impl VersionedExecutionPayloadHeader {
 				execution_payload_header.receipts_root,
 			VersionedExecutionPayloadHeader::Deneb(execution_payload_header) =>
 				execution_payload_header.receipts_root,
+			VersionedExecutionPayloadHeader::Electra(execution_payload_header) =>
+				execution_payload_header.receipts_root,
 		}
 	}
 }
@@ -420,26 +432,95 @@ pub struct ExecutionProof {
 	/// Header for the beacon block containing the execution payload
 	pub header: BeaconHeader,
 	/// Proof that `header` is an ancestor of a finalized header
-	pub ancestry_proof: Option<AncestryProof>,
+	pub ancestry_proof: AncestryProof,
 	/// The execution header to be verified
 	pub execution_header: VersionedExecutionPayloadHeader,
 	/// Merkle proof that execution payload is contained within `header`
 	pub execution_branch: Vec<H256>,
 }
 
+/// Proof that a beacon header is an ancestor of a finalized header that has already been
+/// imported into the light client.
+///
+/// This replaced an `Option` of what is now [`BlockRootsProof`], and is encoded in the same way
+/// for the variants which existed then, so that proofs encoded for earlier versions remain valid.
+/// In JSON, `null` stands for [`AncestryProof::Finalized`].
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+pub enum AncestryProof {
+	/// No proof is needed, as the header is itself a finalized header
+	#[codec(index = 0)]
+	Finalized,
+	/// Proof through the `block_roots` of the finalized BeaconState
+	#[codec(index = 1)]
+	BlockRoots(BlockRootsProof),
+	/// Proof through the `historical_summaries` of the finalized BeaconState
+	#[codec(index = 2)]
+	HistoricalSummaries(HistoricalSummariesProof),
+}
+
+#[cfg(feature = "std")]
+impl<'de> serde::Deserialize<'de> for AncestryProof {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		#[derive(serde::Deserialize)]
+		#[serde(untagged)]
+		enum Proof {
+			BlockRoots(BlockRootsProof),
+			HistoricalSummaries(HistoricalSummariesProof),
+		}
+
+		Ok(match Option::<Proof>::deserialize(deserializer)? {
+			None => AncestryProof::Finalized,
+			Some(Proof::BlockRoots(proof)) => AncestryProof::BlockRoots(proof),
+			Some(Proof::HistoricalSummaries(proof)) => AncestryProof::HistoricalSummaries(proof),
+		})
+	}
+}
+
+/// Proves a header against the `block_roots` of a finalized BeaconState. Only headers within
+/// the last `SLOTS_PER_HISTORICAL_ROOT` slots before the finalized header can be proven.
 #[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
 #[cfg_attr(
 	feature = "std",
 	derive(serde::Deserialize),
 	serde(deny_unknown_fields, bound(serialize = ""), bound(deserialize = ""))
 )]
-pub struct AncestryProof {
+pub struct BlockRootsProof {
 	/// Merkle proof that `header` is an ancestor of `finalized_header`
 	pub header_branch: Vec<H256>,
 	/// Root of a finalized block that has already been imported into the light client
 	pub finalized_block_root: H256,
 }
 
+/// Proves a header against the `historical_summaries` of a finalized BeaconState. Any header
+/// since Capella can be proven, once its `SLOTS_PER_HISTORICAL_ROOT` period has completed.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(
+	feature = "std",
+	derive(serde::Deserialize),
+	serde(deny_unknown_fields, bound(serialize = ""), bound(deserialize = ""))
+)]
+pub struct HistoricalSummariesProof {
+	/// Merkle proof that `header` is in the `block_roots` summarized by `block_summary_root`
+	pub header_branch: Vec<H256>,
+	/// The `block_summary_root` of the `HistoricalSummary` for the period of `header`
+	pub block_summary_root: H256,
+	/// Merkle proof that `block_summary_root` is in the `historical_summaries` list
+	pub block_summary_root_branch: Vec<H256>,
+	/// Root of the `historical_summaries` list in the finalized BeaconState
+	pub historical_summaries_root: H256,
+	/// Merkle proof that `historical_summaries_root` is in the finalized BeaconState
+	pub historical_summaries_branch: Vec<H256>,
+	/// State root of the finalized header
+	pub finalized_state_root: H256,
+	/// Merkle proof that `finalized_state_root` is in the finalized header
+	pub finalized_state_root_branch: Vec<H256>,
+	/// Root of a finalized block that has already been imported into the light client
+	pub finalized_block_root: H256,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -520,6 +601,21 @@ mod tests {
 		);
 	}
 
+	#[test]
+	pub fn test_ancestry_proof_encoding_is_backwards_compatible() {
+		let proof = BlockRootsProof {
+			header_branch: vec![H256::repeat_byte(1), H256::repeat_byte(2)],
+			finalized_block_root: H256::repeat_byte(3),
+		};
+
+		assert_eq!(None::<BlockRootsProof>.encode(), AncestryProof::Finalized.encode());
+		assert_eq!(Some(proof.clone()).encode(), AncestryProof::BlockRoots(proof.clone()).encode());
+		assert_eq!(
+			AncestryProof::decode(&mut &Some(proof.clone()).encode()[..]).unwrap(),
+			AncestryProof::BlockRoots(proof)
+		);
+	}
+
 	#[test]
 	pub fn test_hash_sync_aggregate() {
 		let hash_root = SyncAggregate::<512, 64>{
@@ -556,6 +652,45 @@ mod tests {
 			}.hash_tree_root();
 		assert!(hash_root.is_ok());
 	}
+
+	#[test]
+	pub fn test_hash_electra_execution_payload() {
+		// The header of the `execution-proof.json` fixture of the Ethereum client pallet, whose
+		// root is proven against the body root of the beacon block there.
+		let execution_payload_header = electra::ExecutionPayloadHeader {
+			parent_hash: hex!("8092290aa21b7751576440f77edd02a94058429ce50e63a92d620951fb25eda2").into(),
+			fee_recipient: hex!("0000000000000000000000000000000000000000").into(),
+			state_root: hex!("96a83e9ddf745346fafcb0b03d57314623df669ed543c110662b21302a0fae8b").into(),
+			receipts_root: hex!("dccdfceea05036f7b61dcdabadc937945d31e68a8d3dfd4dc85684457988c284").into(),
+			logs_bloom: hex!("00000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000080000000400000000000000000000004000000000080000000000000000000000000000000000010100000000000000000000000000000000020000000000000000000000000000000000080000000000000000000000000000040004000000000000002002002000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000080000000000000000000000000000000000100000000000000000200000200000010").to_vec(),
+			prev_randao: hex!("62e309d4f5119d1f5c783abc20fc1a549efbab546d8d0b25ff1cfd58be524e67").into(),
+			block_number: 393,
+			gas_limit: 54492273,
+			gas_used: 199644,
+			timestamp: 1710552813,
+			extra_data: hex!("d983010d0b846765746888676f312e32312e368664617277696e").to_vec(),
+			base_fee_per_gas: U256::from(7u64),
+			block_hash: hex!("6a9810efb9581d30c1a5c9074f27c68ea779a8c1ae31c213241df16225f4e131").into(),
+			transactions_root: hex!("2cfa6ed7327e8807c7973516c5c32a68ef2459e586e8067e113d081c3bd8c07d").into(),
+			withdrawals_root: hex!("792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535").into(),
+			blob_gas_used: 0,
+			excess_blob_gas: 0,
+		};
+
+		let hash_root = VersionedExecutionPayloadHeader::Electra(execution_payload_header.clone())
+			.hash_tree_root()
+			.unwrap();
+		assert_eq!(
+			hash_root,
+			hex!("103b2c1a41311ec044f20dc2a301ba52c9fc12032101e5ddaaeb14abe0c53975").into()
+		);
+		assert_eq!(
+			hash_root,
+			VersionedExecutionPayloadHeader::Deneb(execution_payload_header)
+				.hash_tree_root()
+				.unwrap()
+		);
+	}
 }
 
 /// Operating modes for beacon client
@@ -617,3 +752,12 @@ pub mod deneb {
 		pub excess_blob_gas: u64, // [New in Deneb:EIP4844]
 	}
 }
+
+pub mod electra {
+	/// ExecutionPayloadHeader
+	/// <https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/beacon-chain.md>
+	///
+	/// Electra moves the execution layer requests into the beacon block body, so the execution
+	/// payload header is the same as in Deneb.
+	pub use super::deneb::ExecutionPayloadHeader;
+}
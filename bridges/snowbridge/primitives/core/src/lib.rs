@@ -35,7 +35,7 @@ use xcm::prelude::{Junction::Parachain, Location};
 /// The ID of an agent contract
 pub use operating_mode::BasicOperatingMode;
 
-pub use pricing::{PricingParameters, Rewards};
+pub use pricing::{PriceFeed, PricingParameters, Rewards};
 
 pub fn sibling_sovereign_account<T>(para_id: ParaId) -> T::AccountId
 where
@@ -172,3 +172,35 @@ impl Default for AssetMetadata {
 
 /// Maximum length of a string field in ERC20 token metada
 const METADATA_FIELD_MAX_LEN: u32 = 32;
+
+/// Supply of a Polkadot-native token that is bridged to Ethereum, as seen from BridgeHub
+#[derive(
+	Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct TokenSupply {
+	/// Amount locked on AssetHub, for which mint commands were sent to Ethereum
+	pub locked: u128,
+	/// Amount minted on Ethereum, as proven by delivery receipts
+	pub minted: u128,
+}
+
+/// Records changes in the supply of Polkadot-native tokens on Ethereum, so that the amount locked
+/// on AssetHub can be reconciled with the amount minted on Ethereum
+pub trait TokenLedger {
+	/// `amount` of `token_id` was locked on AssetHub and a mint command was sent to Ethereum
+	fn on_locked(token_id: &TokenId, amount: u128);
+	/// A mint command for `amount` of `token_id` was dispatched successfully on Ethereum
+	fn on_minted(token_id: &TokenId, amount: u128);
+	/// A mint command for `amount` of `token_id` failed on Ethereum, so the amount is no longer
+	/// backing tokens on Ethereum
+	fn on_mint_failed(token_id: &TokenId, amount: u128);
+	/// `amount` of `token_id` was burned on Ethereum, to be unlocked on AssetHub
+	fn on_burned(token_id: &TokenId, amount: u128);
+}
+
+impl TokenLedger for () {
+	fn on_locked(_: &TokenId, _: u128) {}
+	fn on_minted(_: &TokenId, _: u128) {}
+	fn on_mint_failed(_: &TokenId, _: u128) {}
+	fn on_burned(_: &TokenId, _: u128) {}
+}
@@ -170,6 +170,22 @@ mod v1 {
 			/// The amount of tokens to mint
 			amount: u128,
 		},
+		/// Update the metadata of a registered foreign token from Polkadot
+		UpdateForeignTokenMetadata {
+			/// ID for the token
+			token_id: H256,
+			/// Name of the token
+			name: Vec<u8>,
+			/// Short symbol for the token
+			symbol: Vec<u8>,
+			/// Number of decimal places
+			decimals: u8,
+		},
+		/// Deregister a foreign token from Polkadot, which has no remaining supply on Ethereum
+		DeregisterForeignToken {
+			/// ID for the token
+			token_id: H256,
+		},
 	}
 
 	impl Command {
@@ -188,6 +204,8 @@ mod v1 {
 				Command::TransferNativeToken { .. } => 9,
 				Command::RegisterForeignToken { .. } => 10,
 				Command::MintForeignToken { .. } => 11,
+				Command::UpdateForeignTokenMetadata { .. } => 12,
+				Command::DeregisterForeignToken { .. } => 13,
 			}
 		}
 
@@ -265,6 +283,16 @@ mod v1 {
 						Token::Address(*recipient),
 						Token::Uint(U256::from(*amount)),
 					])]),
+				Command::UpdateForeignTokenMetadata { token_id, name, symbol, decimals } =>
+					ethabi::encode(&[Token::Tuple(vec![
+						Token::FixedBytes(token_id.as_bytes().to_owned()),
+						Token::String(name.to_owned()),
+						Token::String(symbol.to_owned()),
+						Token::Uint(U256::from(*decimals)),
+					])]),
+				Command::DeregisterForeignToken { token_id } => ethabi::encode(&[Token::Tuple(
+					vec![Token::FixedBytes(token_id.as_bytes().to_owned())],
+				)]),
 			}
 		}
 	}
@@ -292,12 +320,24 @@ mod v1 {
 			/// The amount of tokens to transfer
 			amount: u128,
 		},
+		/// Call a contract, with the agent as the caller
+		CallContract {
+			/// Address of the contract to call
+			target: H160,
+			/// ABI-encoded calldata, including the function selector
+			calldata: Vec<u8>,
+			/// The amount of ether to send along with the call
+			value: u128,
+			/// The maximum amount of gas the call may consume
+			gas_limit: u64,
+		},
 	}
 
 	impl AgentExecuteCommand {
 		fn index(&self) -> u8 {
 			match self {
 				AgentExecuteCommand::TransferToken { .. } => 0,
+				AgentExecuteCommand::CallContract { .. } => 1,
 			}
 		}
 
@@ -313,6 +353,16 @@ mod v1 {
 							Token::Uint(U256::from(*amount)),
 						])),
 					]),
+				AgentExecuteCommand::CallContract { target, calldata, value, gas_limit } =>
+					ethabi::encode(&[
+						Token::Uint(self.index().into()),
+						Token::Bytes(ethabi::encode(&[
+							Token::Address(*target),
+							Token::Bytes(calldata.to_owned()),
+							Token::Uint(U256::from(*value)),
+							Token::Uint(U256::from(*gas_limit)),
+						])),
+					]),
 			}
 		}
 	}
@@ -399,6 +449,16 @@ pub enum SendError {
 	InvalidChannel,
 }
 
+/// Registers rewards for relayers who prove that messages were delivered to Ethereum
+pub trait RewardLedger<AccountId, Balance> {
+	/// Register `reward` for `relayer`, to be claimed later
+	fn register_reward(relayer: &AccountId, reward: Balance);
+}
+
+impl<AccountId, Balance> RewardLedger<AccountId, Balance> for () {
+	fn register_reward(_: &AccountId, _: Balance) {}
+}
+
 pub trait GasMeter {
 	/// All the gas used for submitting a message to Ethereum, minus the cost of dispatching
 	/// the command within the message
@@ -406,7 +466,7 @@ pub trait GasMeter {
 
 	/// Total gas consumed at most, including verification & dispatch
 	fn maximum_gas_used_at_most(command: &Command) -> u64 {
-		Self::MAXIMUM_BASE_GAS + Self::maximum_dispatch_gas_used_at_most(command)
+		Self::MAXIMUM_BASE_GAS.saturating_add(Self::maximum_dispatch_gas_used_at_most(command))
 	}
 
 	/// Measures the maximum amount of gas a command payload will require to *dispatch*, NOT
@@ -445,6 +505,10 @@ impl GasMeter for ConstantGasMeter {
 				// * Assume dest account in ERC20 contract does not yet have a storage slot
 				// * ERC20.transferFrom possibly does other business logic besides updating balances
 				AgentExecuteCommand::TransferToken { .. } => 100_000,
+				// Execute an arbitrary call from the agent. Besides the gas limit requested for the
+				// call itself, the agent needs gas to forward the call and check its result.
+				AgentExecuteCommand::CallContract { gas_limit, .. } =>
+					gas_limit.saturating_add(30_000),
 			},
 			Command::Upgrade { initializer, .. } => {
 				let initializer_max_gas = match *initializer {
@@ -460,6 +524,8 @@ impl GasMeter for ConstantGasMeter {
 			Command::TransferNativeToken { .. } => 100_000,
 			Command::RegisterForeignToken { .. } => 1_200_000,
 			Command::MintForeignToken { .. } => 100_000,
+			Command::UpdateForeignTokenMetadata { .. } => 100_000,
+			Command::DeregisterForeignToken { .. } => 50_000,
 		}
 	}
 }
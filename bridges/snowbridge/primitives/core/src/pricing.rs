@@ -52,6 +52,26 @@ where
 	}
 }
 
+/// A source of market prices, which may track the ETH/DOT exchange rate and gas prices on
+/// Ethereum more closely than the values set by governance
+pub trait PriceFeed {
+	/// Returns the current ETH/DOT exchange rate, or `None` if no fresh value is available
+	fn exchange_rate() -> Option<FixedU128>;
+	/// Returns the current Ether (wei) fee per gas unit, or `None` if no fresh value is available
+	fn fee_per_gas() -> Option<U256>;
+}
+
+/// No price feed. Governance-set values are always used
+impl PriceFeed for () {
+	fn exchange_rate() -> Option<FixedU128> {
+		None
+	}
+
+	fn fee_per_gas() -> Option<U256> {
+		None
+	}
+}
+
 /// Holder for fixed point number implemented in <https://github.com/PaulRBerg/prb-math>
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(PartialEq))]
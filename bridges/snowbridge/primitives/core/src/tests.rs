@@ -11,3 +11,27 @@ fn generate_channel_id() {
 	let channel_id: ChannelId = para_id.into();
 	assert_eq!(channel_id, EXPECT_CHANNEL_ID.into());
 }
+
+#[test]
+fn encode_call_contract_command() {
+	use crate::outbound::AgentExecuteCommand;
+	use ethabi::Token;
+	use sp_core::{H160, U256};
+
+	let command = AgentExecuteCommand::CallContract {
+		target: H160::repeat_byte(0x11),
+		calldata: vec![0xde, 0xad, 0xbe, 0xef],
+		value: 1_000,
+		gas_limit: 80_000,
+	};
+	let expected = ethabi::encode(&[
+		Token::Uint(U256::from(1u8)),
+		Token::Bytes(ethabi::encode(&[
+			Token::Address(H160::repeat_byte(0x11)),
+			Token::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+			Token::Uint(U256::from(1_000u128)),
+			Token::Uint(U256::from(80_000u64)),
+		])),
+	]);
+	assert_eq!(command.abi_encode(), expected);
+}
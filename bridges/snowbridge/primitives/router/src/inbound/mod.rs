@@ -5,7 +5,7 @@
 #[cfg(test)]
 mod tests;
 
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeLimit, Encode};
 use core::marker::PhantomData;
 use frame_support::{traits::tokens::Balance as BalanceT, weights::Weight, PalletError};
 use scale_info::TypeInfo;
@@ -14,17 +14,20 @@ use sp_core::{Get, RuntimeDebug, H160, H256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{traits::MaybeEquivalence, MultiAddress};
 use sp_std::prelude::*;
-use xcm::prelude::{Junction::AccountKey20, *};
+use xcm::{
+	prelude::{Junction::AccountKey20, *},
+	MAX_XCM_DECODE_DEPTH,
+};
 use xcm_executor::traits::ConvertLocation;
 
 const MINIMUM_DEPOSIT: u128 = 1;
 
-/// Messages from Ethereum are versioned. This is because in future,
-/// we may want to evolve the protocol so that the ethereum side sends XCM messages directly.
-/// Instead having BridgeHub transcode the messages into XCM.
+/// Messages from Ethereum are versioned. With V1, BridgeHub transcodes a fixed set of commands
+/// into XCM. With V2, the ethereum side sends XCM messages directly.
 #[derive(Clone, Encode, Decode, RuntimeDebug)]
 pub enum VersionedMessage {
 	V1(MessageV1),
+	V2(MessageV2),
 }
 
 /// For V1, the ethereum side sends messages which are transcoded into XCM. These messages are
@@ -37,6 +40,20 @@ pub struct MessageV1 {
 	pub command: Command,
 }
 
+/// For V2, the ethereum side sends an arbitrary XCM program, which is executed on AssetHub with
+/// the origin of the Ethereum account which sent the message to the Gateway contract.
+#[derive(Clone, Encode, Decode, RuntimeDebug)]
+pub struct MessageV2 {
+	/// EIP-155 chain id of the origin Ethereum network
+	pub chain_id: u64,
+	/// The Ethereum account (EOA or contract) which sent the message through the Gateway
+	pub origin: H160,
+	/// XCM execution fee on AssetHub
+	pub fee: u128,
+	/// The SCALE-encoded `VersionedXcm<()>` program to execute on AssetHub
+	pub xcm: Vec<u8>,
+}
+
 #[derive(Clone, Encode, Decode, RuntimeDebug)]
 pub enum Command {
 	/// Register a wrapped token on the AssetHub `ForeignAssets` pallet
@@ -134,6 +151,9 @@ pub enum ConvertMessageError {
 	/// The fee asset is not supported for conversion.
 	UnsupportedFeeAsset,
 	CannotReanchor,
+	/// The XCM program supplied with the message could not be decoded, or it replaces the
+	/// appendix which returns the leftover fees to the bridge.
+	InvalidXcm,
 }
 
 /// convert the inbound message to xcm which will be forwarded to the destination chain
@@ -204,6 +224,8 @@ where
 				amount,
 				fee,
 			),
+			V2(MessageV2 { chain_id, origin, fee, xcm }) =>
+				Self::convert_xcm(message_id, chain_id, origin, fee, xcm),
 		}
 	}
 }
@@ -450,6 +472,69 @@ where
 		// teleport fees)
 		Ok((instructions.into(), asset_hub_fee.into()))
 	}
+
+	/// Constructs an XCM message destined for AssetHub which pays for its own execution and then
+	/// runs the XCM program supplied by the Ethereum sender, with the origin of that sender.
+	///
+	/// The program of the sender is embedded in the program built here, after the appendix
+	/// returning the leftover fees to the bridge. Programs which could replace that appendix are
+	/// rejected, so that the leftover fees can never be trapped.
+	fn convert_xcm(
+		message_id: H256,
+		chain_id: u64,
+		origin: H160,
+		asset_hub_fee: u128,
+		xcm: Vec<u8>,
+	) -> Result<(Xcm<()>, Balance), ConvertMessageError> {
+		let network = Ethereum { chain_id };
+		let asset_hub_fee_asset: Asset = (Location::parent(), asset_hub_fee).into();
+		let bridge_location = Location::new(2, GlobalConsensus(network));
+
+		let versioned_xcm =
+			VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &xcm[..])
+				.map_err(|_| ConvertMessageError::InvalidXcm)?;
+		let message_xcm: Xcm<()> =
+			versioned_xcm.try_into().map_err(|_| ConvertMessageError::UnsupportedVersion)?;
+		if Self::sets_appendix(&message_xcm) {
+			return Err(ConvertMessageError::InvalidXcm)
+		}
+
+		let inbound_queue_pallet_index = InboundQueuePalletInstance::get();
+
+		let mut instructions = vec![
+			ReceiveTeleportedAsset(asset_hub_fee_asset.clone().into()),
+			BuyExecution { fees: asset_hub_fee_asset, weight_limit: Unlimited },
+			// Deposit any leftover fee to snowbridge sovereign once the program ends, regardless
+			// of the outcome of the user supplied program.
+			SetAppendix(Xcm(vec![
+				RefundSurplus,
+				DepositAsset {
+					assets: Wild(AllOf { id: AssetId(Location::parent()), fun: WildFungible }),
+					beneficiary: bridge_location,
+				},
+			])),
+			DescendOrigin(PalletInstance(inbound_queue_pallet_index).into()),
+			UniversalOrigin(GlobalConsensus(network)),
+			// Preserve the Ethereum sender as origin of the user supplied program.
+			DescendOrigin(AccountKey20 { network: None, key: origin.into() }.into()),
+		];
+		instructions.extend(message_xcm.0);
+		// Forward message id to Asset Hub.
+		instructions.push(SetTopic(message_id.into()));
+
+		Ok((instructions.into(), asset_hub_fee.into()))
+	}
+
+	/// Whether the program, or an error handler it sets, replaces the appendix of the program it
+	/// is embedded in. Both are executed by the same XCM executor, so an error handler setting an
+	/// appendix replaces it as well.
+	fn sets_appendix(xcm: &Xcm<()>) -> bool {
+		xcm.0.iter().any(|instruction| match instruction {
+			SetAppendix(_) => true,
+			SetErrorHandler(handler) => Self::sets_appendix(handler),
+			_ => false,
+		})
+	}
 }
 
 pub struct GlobalConsensusEthereumConvertsFor<AccountId>(PhantomData<AccountId>);
@@ -461,6 +546,8 @@ where
 		match location.unpack() {
 			(_, [GlobalConsensus(Ethereum { chain_id })]) =>
 				Some(Self::from_chain_id(chain_id).into()),
+			(_, [GlobalConsensus(Ethereum { chain_id }), AccountKey20 { network: _, key }]) =>
+				Some(Self::from_chain_id_with_key(chain_id, *key).into()),
 			_ => None,
 		}
 	}
@@ -470,4 +557,10 @@ impl<AccountId> GlobalConsensusEthereumConvertsFor<AccountId> {
 	pub fn from_chain_id(chain_id: &u64) -> [u8; 32] {
 		(b"ethereum-chain", chain_id).using_encoded(blake2_256)
 	}
+
+	/// The account of an Ethereum account (EOA or contract), used as the origin of XCM programs
+	/// sent with `VersionedMessage::V2`.
+	pub fn from_chain_id_with_key(chain_id: &u64, key: [u8; 20]) -> [u8; 32] {
+		(b"ethereum-chain", chain_id, key).using_encoded(blake2_256)
+	}
 }
@@ -1,7 +1,14 @@
-use super::GlobalConsensusEthereumConvertsFor;
+use super::{
+	ConvertMessage, ConvertMessageError, GlobalConsensusEthereumConvertsFor, MessageToXcm,
+	MessageV2, VersionedMessage,
+};
 use crate::inbound::CallIndex;
-use frame_support::{assert_ok, parameter_types};
+use codec::Encode;
+use frame_support::{assert_ok, parameter_types, weights::Weight};
 use hex_literal::hex;
+use snowbridge_core::TokenId;
+use sp_core::{H160, H256};
+use sp_runtime::traits::MaybeEquivalence;
 use xcm::prelude::*;
 use xcm_executor::traits::ConvertLocation;
 
@@ -14,8 +21,33 @@ parameter_types! {
 	pub const CreateAssetExecutionFee: u128 = 123;
 	pub const CreateAssetDeposit: u128 = 891;
 	pub const SendTokenExecutionFee: u128 = 592;
+	pub const InboundQueuePalletInstance: u8 = 80;
+	pub UniversalLocation: InteriorLocation =
+		[GlobalConsensus(Westend), Parachain(1002)].into();
+	pub AssetHubFromEthereum: Location = Location::new(1, [GlobalConsensus(Westend), Parachain(1000)]);
 }
 
+pub struct MockTokenIdConvert;
+impl MaybeEquivalence<TokenId, Location> for MockTokenIdConvert {
+	fn convert(_id: &TokenId) -> Option<Location> {
+		Some(Location::parent())
+	}
+	fn convert_back(_loc: &Location) -> Option<TokenId> {
+		None
+	}
+}
+
+type Converter = MessageToXcm<
+	CreateAssetCall,
+	CreateAssetDeposit,
+	InboundQueuePalletInstance,
+	[u8; 32],
+	u128,
+	MockTokenIdConvert,
+	UniversalLocation,
+	AssetHubFromEthereum,
+>;
+
 #[test]
 fn test_contract_location_with_network_converts_successfully() {
 	let expected_account: [u8; 32] =
@@ -67,3 +99,93 @@ fn test_reanchor_all_assets() {
 		assert_eq!(reanchored_asset_with_ethereum_context, asset.clone());
 	}
 }
+
+#[test]
+fn test_contract_account_location_converts_successfully() {
+	let key: [u8; 20] = hex!("90a987b944cb1dcce5564e5fdecd7a54d3de27fe");
+	let contract_location =
+		Location::new(2, [GlobalConsensus(NETWORK), AccountKey20 { network: None, key }]);
+
+	let account =
+		GlobalConsensusEthereumConvertsFor::<[u8; 32]>::convert_location(&contract_location)
+			.unwrap();
+
+	assert_eq!(
+		account,
+		GlobalConsensusEthereumConvertsFor::<[u8; 32]>::from_chain_id_with_key(&11155111, key)
+	);
+	assert_ne!(account, GlobalConsensusEthereumConvertsFor::<[u8; 32]>::from_chain_id(&11155111));
+}
+
+#[test]
+fn test_convert_v2_message_wraps_xcm_with_ethereum_origin() {
+	let message_id: H256 = [1; 32].into();
+	let origin: H160 = hex!("90a987b944cb1dcce5564e5fdecd7a54d3de27fe").into();
+	let fee = 1_000_000_000;
+	let remark = Transact {
+		origin_kind: OriginKind::SovereignAccount,
+		require_weight_at_most: Weight::from_parts(1_000_000, 10_000),
+		call: vec![0, 7, 0].into(),
+	};
+	let message = VersionedMessage::V2(MessageV2 {
+		chain_id: 11155111,
+		origin,
+		fee,
+		xcm: VersionedXcm::from(Xcm::<()>(vec![remark.clone()])).encode(),
+	});
+
+	let (xcm, burned_fee) = Converter::convert(message_id, message).unwrap();
+	assert_eq!(burned_fee, fee);
+
+	let instructions = xcm.0;
+	assert_eq!(instructions.len(), 8);
+	assert_eq!(
+		instructions[0],
+		ReceiveTeleportedAsset(Asset::from((Location::parent(), fee)).into())
+	);
+	assert_eq!(instructions[3], DescendOrigin(PalletInstance(80).into()));
+	assert_eq!(instructions[4], UniversalOrigin(GlobalConsensus(NETWORK)));
+	assert_eq!(
+		instructions[5],
+		DescendOrigin(AccountKey20 { network: None, key: origin.into() }.into())
+	);
+	assert_eq!(instructions[6], remark);
+	assert_eq!(instructions[7], SetTopic(message_id.into()));
+}
+
+#[test]
+fn test_convert_v2_message_with_invalid_xcm_fails() {
+	let message = VersionedMessage::V2(MessageV2 {
+		chain_id: 11155111,
+		origin: H160::zero(),
+		fee: 1_000_000_000,
+		xcm: vec![0xff, 0xff, 0xff],
+	});
+
+	assert!(matches!(
+		Converter::convert(H256::zero(), message),
+		Err(ConvertMessageError::InvalidXcm)
+	));
+}
+
+#[test]
+fn test_convert_v2_message_replacing_appendix_fails() {
+	let set_appendix = SetAppendix(Xcm(vec![ClearOrigin]));
+	for program in [
+		vec![ClearOrigin, set_appendix.clone()],
+		// the error handler is executed before the appendix, so it may not replace it either
+		vec![SetErrorHandler(Xcm(vec![set_appendix]))],
+	] {
+		let message = VersionedMessage::V2(MessageV2 {
+			chain_id: 11155111,
+			origin: H160::zero(),
+			fee: 1_000_000_000,
+			xcm: VersionedXcm::from(Xcm::<()>(program)).encode(),
+		});
+
+		assert!(matches!(
+			Converter::convert(H256::zero(), message),
+			Err(ConvertMessageError::InvalidXcm)
+		));
+	}
+}
@@ -7,19 +7,38 @@ mod tests;
 
 use core::slice::Iter;
 
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeAll, Encode};
 
 use frame_support::{ensure, traits::Get};
+use scale_info::TypeInfo;
 use snowbridge_core::{
 	outbound::{AgentExecuteCommand, Command, Message, SendMessage},
 	AgentId, ChannelId, ParaId, TokenId, TokenIdOf,
 };
-use sp_core::{H160, H256};
+use sp_core::{RuntimeDebug, H160, H256};
 use sp_runtime::traits::MaybeEquivalence;
 use sp_std::{iter::Peekable, marker::PhantomData, prelude::*};
 use xcm::prelude::*;
 use xcm_executor::traits::{ConvertLocation, ExportXcm};
 
+/// The maximum amount of gas a contract call exported to Ethereum may request. A call requesting
+/// more could never be dispatched by the Gateway, and would block its channel.
+pub const MAX_CONTRACT_CALL_GAS: u64 = 10_000_000;
+
+/// A call to a contract on Ethereum, made by the agent of the origin. It is carried SCALE-encoded
+/// as the call of an XCM `Transact` instruction.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ContractCall {
+	/// Address of the contract to call
+	pub target: H160,
+	/// ABI-encoded calldata, including the function selector
+	pub calldata: Vec<u8>,
+	/// The amount of ether to send along with the call
+	pub value: u128,
+	/// The maximum amount of gas the call may consume
+	pub gas_limit: u64,
+}
+
 pub struct EthereumBlobExporter<
 	UniversalLocation,
 	EthereumNetwork,
@@ -175,6 +194,10 @@ enum XcmConverterError {
 	ReserveAssetDepositedExpected,
 	InvalidAsset,
 	UnexpectedInstruction,
+	TransactExpected,
+	InvalidOriginKind,
+	TransactDecodeFailed,
+	GasLimitTooHigh,
 }
 
 macro_rules! match_expression {
@@ -210,6 +233,8 @@ where
 			Ok(ReserveAssetDeposited { .. }) => self.send_native_tokens_message(),
 			// Get withdraw/deposit and make native tokens create message.
 			Ok(WithdrawAsset { .. }) => self.send_tokens_message(),
+			// Get transact and make contract call message.
+			Ok(Transact { .. }) => self.call_contract_message(),
 			Err(e) => Err(e),
 			_ => return Err(XcmConverterError::UnexpectedInstruction),
 		}?;
@@ -305,6 +330,40 @@ where
 		))
 	}
 
+	/// Convert a contract call from the agent of the origin into the Command
+	/// To match contract calls, we expect an input of the form:
+	/// # Transact
+	/// # SetTopic
+	fn call_contract_message(&mut self) -> Result<(Command, [u8; 32]), XcmConverterError> {
+		use XcmConverterError::*;
+
+		let (origin_kind, call) = match_expression!(
+			self.next()?,
+			Transact { origin_kind, call, .. },
+			(origin_kind, call)
+		)
+		.ok_or(TransactExpected)?;
+
+		// The call is made by the agent, which is the sovereign account of the origin.
+		ensure!(*origin_kind == OriginKind::SovereignAccount, InvalidOriginKind);
+
+		let ContractCall { target, calldata, value, gas_limit } =
+			ContractCall::decode_all(&mut call.clone().into_encoded().as_slice())
+				.map_err(|_| TransactDecodeFailed)?;
+		ensure!(gas_limit <= MAX_CONTRACT_CALL_GAS, GasLimitTooHigh);
+
+		// Check if there is a SetTopic and skip over it if found.
+		let topic_id = match_expression!(self.next()?, SetTopic(id), id).ok_or(SetTopicExpected)?;
+
+		Ok((
+			Command::AgentExecute {
+				agent_id: self.agent_id,
+				command: AgentExecuteCommand::CallContract { target, calldata, value, gas_limit },
+			},
+			*topic_id,
+		))
+	}
+
 	fn next(&mut self) -> Result<&'a Instruction<Call>, XcmConverterError> {
 		self.iter.next().ok_or(XcmConverterError::UnexpectedEndOfXcm)
 	}
@@ -1267,3 +1267,78 @@ fn exporter_validate_with_invalid_universal_source_does_not_alter_universal_sour
 	assert_eq!(Some(msg), msg_wrapper);
 	assert_eq!(Some(universal_source), universal_source_wrapper);
 }
+
+fn contract_call_message(origin_kind: OriginKind, call: Vec<u8>) -> Xcm<()> {
+	vec![
+		Transact { origin_kind, require_weight_at_most: Weight::zero(), call: call.into() },
+		SetTopic([0; 32]),
+	]
+	.into()
+}
+
+#[test]
+fn xcm_converter_convert_transact_success() {
+	let network = BridgedNetwork::get();
+
+	let contract_call = ContractCall {
+		target: hex!("1000000000000000000000000000000000000000").into(),
+		calldata: hex!("a9059cbb").to_vec(),
+		value: 1000,
+		gas_limit: 80_000,
+	};
+	let message = contract_call_message(OriginKind::SovereignAccount, contract_call.encode());
+	let mut converter =
+		XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
+	let expected_payload = Command::AgentExecute {
+		agent_id: Default::default(),
+		command: AgentExecuteCommand::CallContract {
+			target: contract_call.target,
+			calldata: contract_call.calldata,
+			value: 1000,
+			gas_limit: 80_000,
+		},
+	};
+	assert_eq!(converter.convert(), Ok((expected_payload, [0; 32])));
+}
+
+#[test]
+fn xcm_converter_convert_transact_with_non_sovereign_origin_yields_invalid_origin_kind() {
+	let network = BridgedNetwork::get();
+
+	let contract_call = ContractCall {
+		target: hex!("1000000000000000000000000000000000000000").into(),
+		calldata: vec![],
+		value: 0,
+		gas_limit: 80_000,
+	};
+	let message = contract_call_message(OriginKind::Xcm, contract_call.encode());
+	let mut converter =
+		XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
+	assert_eq!(converter.convert().err(), Some(XcmConverterError::InvalidOriginKind));
+}
+
+#[test]
+fn xcm_converter_convert_transact_with_invalid_call_yields_transact_decode_failed() {
+	let network = BridgedNetwork::get();
+
+	let message = contract_call_message(OriginKind::SovereignAccount, vec![1, 2, 3]);
+	let mut converter =
+		XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
+	assert_eq!(converter.convert().err(), Some(XcmConverterError::TransactDecodeFailed));
+}
+
+#[test]
+fn xcm_converter_convert_transact_with_excessive_gas_yields_gas_limit_too_high() {
+	let network = BridgedNetwork::get();
+
+	let contract_call = ContractCall {
+		target: hex!("1000000000000000000000000000000000000000").into(),
+		calldata: vec![],
+		value: 0,
+		gas_limit: MAX_CONTRACT_CALL_GAS + 1,
+	};
+	let message = contract_call_message(OriginKind::SovereignAccount, contract_call.encode());
+	let mut converter =
+		XcmConverter::<MockTokenIdConvert, ()>::new(&message, network, Default::default());
+	assert_eq!(converter.convert().err(), Some(XcmConverterError::GasLimitTooHigh));
+}
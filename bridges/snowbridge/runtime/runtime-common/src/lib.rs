@@ -8,10 +8,11 @@
 #[cfg(test)]
 mod tests;
 
+use bp_relayers::RewardsAccountParams;
 use codec::FullCodec;
 use core::marker::PhantomData;
 use frame_support::traits::Get;
-use snowbridge_core::outbound::SendMessageFeeProvider;
+use snowbridge_core::outbound::{RewardLedger, SendMessageFeeProvider};
 use sp_arithmetic::traits::{BaseArithmetic, Unsigned};
 use sp_std::fmt::Debug;
 use xcm::prelude::*;
@@ -150,3 +151,25 @@ where
 		modified_fees.into()
 	}
 }
+
+/// A `RewardLedger` implementation that credits relayers of messages to Ethereum through
+/// `pallet-bridge-relayers`, so that rewards can be claimed in the same way as for other
+/// bridges. As with those bridges, the rewards account derived from `RewardsAccount` has to be
+/// funded separately, since export fees are refunded to the sending sibling.
+pub struct RegisterRelayerReward<T, I, RewardsAccount>(PhantomData<(T, I, RewardsAccount)>);
+
+impl<T, I, RewardsAccount> RewardLedger<T::AccountId, T::Reward>
+	for RegisterRelayerReward<T, I, RewardsAccount>
+where
+	T: pallet_bridge_relayers::Config<I>,
+	I: 'static,
+	RewardsAccount: Get<RewardsAccountParams<T::LaneId>>,
+{
+	fn register_reward(relayer: &T::AccountId, reward: T::Reward) {
+		pallet_bridge_relayers::Pallet::<T, I>::register_relayer_reward(
+			RewardsAccount::get(),
+			relayer,
+			reward,
+		);
+	}
+}
@@ -1597,7 +1597,7 @@ fn reserve_withdraw_from_untrusted_reserve_fails() {
 		]);
 		let result = <AssetHubRococo as AssetHubRococoPallet>::PolkadotXcm::execute(
 			signed_origin,
-			bx!(xcm::VersionedXcm::V4(xcm)),
+			bx!(xcm::VersionedXcm::V5(xcm)),
 			Weight::MAX,
 		);
 		assert!(result.is_err());
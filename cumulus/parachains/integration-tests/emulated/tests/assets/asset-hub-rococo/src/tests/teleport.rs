@@ -574,7 +574,7 @@ fn teleport_to_untrusted_chain_fails() {
 		]);
 		let result = <AssetHubRococo as AssetHubRococoPallet>::PolkadotXcm::execute(
 			signed_origin,
-			bx!(xcm::VersionedXcm::V4(xcm)),
+			bx!(xcm::VersionedXcm::V5(xcm)),
 			Weight::MAX,
 		);
 		assert!(result.is_err());
@@ -17,6 +17,7 @@ use crate::imports::*;
 
 mod asset_transfers;
 mod claim_assets;
+mod multi_hop_dry_run;
 mod send_xcm;
 mod teleport;
 
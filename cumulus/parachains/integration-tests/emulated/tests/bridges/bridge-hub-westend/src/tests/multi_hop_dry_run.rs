@@ -0,0 +1,135 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::*;
+use bridge_hub_rococo_runtime::bridge_to_westend_config::BridgeRococoToWestendMessagesPalletInstance;
+use emulated_integration_tests_common::xcm_emulator::dry_run::{
+	DryRunChain, EmulatedChain, MultiHopDryRun,
+};
+use frame_support::dispatch::RawOrigin;
+
+fn roc_balance_on_penpal_rococo(who: &AccountId) -> u128 {
+	PenpalA::execute_with(|| {
+		type ForeignAssets = <PenpalA as PenpalAPallet>::ForeignAssets;
+		<ForeignAssets as Inspect<_>>::balance(Location::parent(), who)
+	})
+}
+
+#[test]
+fn multi_hop_dry_run_follows_rocs_from_asset_hub_westend_over_bridge_to_penpal_rococo() {
+	let prefund_amount = 10_000_000_000_000u128;
+	let amount = ASSET_HUB_ROCOCO_ED * 1_000;
+	let sender = AssetHubWestendSender::get();
+	let receiver = PenpalAReceiver::get();
+	let bridged_roc_at_asset_hub_westend = bridged_roc_at_ah_westend();
+	let prefund_accounts = vec![(sender.clone(), prefund_amount)];
+	create_foreign_on_ah_westend(bridged_roc_at_asset_hub_westend.clone(), true, prefund_accounts);
+
+	// fund the AHW's SA on AHR with the ROC tokens held in reserve
+	let sov_ahw_on_ahr = AssetHubRococo::sovereign_account_of_parachain_on_other_global_consensus(
+		Westend,
+		AssetHubWestend::para_id(),
+	);
+	AssetHubRococo::fund_accounts(vec![(sov_ahw_on_ahr, prefund_amount)]);
+
+	// fund the AHW's SA on BHW for paying bridge transport fees
+	BridgeHubWestend::fund_para_sovereign(AssetHubWestend::para_id(), prefund_amount);
+	AssetHubWestend::force_xcm_version(asset_hub_rococo_location(), XCM_VERSION);
+	BridgeHubWestend::force_xcm_version(bridge_hub_rococo_location(), XCM_VERSION);
+	open_bridge_between_asset_hub_rococo_and_asset_hub_westend();
+
+	// ROCs are withdrawn from the reserve on AHR and then reserve-transferred to Penpal, where
+	// they are used to pay for execution
+	let beneficiary: Location =
+		AccountId32Junction { network: None, id: receiver.clone().into() }.into();
+	let xcm_on_asset_hub_rococo = Xcm::<()>(vec![DepositReserveAsset {
+		assets: Wild(AllCounted(1)),
+		dest: AssetHubRococo::sibling_location_of(PenpalA::para_id()),
+		xcm: Xcm(vec![
+			BuyExecution { fees: (Parent, amount / 2).into(), weight_limit: Unlimited },
+			DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+		]),
+	}]);
+	let assets: Assets = (bridged_roc_at_asset_hub_westend.clone(), amount).into();
+	let call = <AssetHubWestend as Chain>::RuntimeCall::PolkadotXcm(
+		pallet_xcm::Call::transfer_assets_using_type_and_then {
+			dest: bx!(asset_hub_rococo_location().into()),
+			assets: bx!(assets.into()),
+			assets_transfer_type: bx!(TransferType::DestinationReserve),
+			remote_fees_id: bx!(AssetId(bridged_roc_at_asset_hub_westend.clone()).into()),
+			fees_transfer_type: bx!(TransferType::DestinationReserve),
+			custom_xcm_on_dest: bx!(VersionedXcm::from(xcm_on_asset_hub_rococo)),
+			weight_limit: Unlimited,
+		},
+	);
+	let origin =
+		<AssetHubWestend as Chain>::OriginCaller::system(RawOrigin::Signed(sender.clone()));
+	let receiver_rocs_before = roc_balance_on_penpal_rococo(&receiver);
+
+	let asset_hub_westend = EmulatedChain::<AssetHubWestend>::new();
+	let bridge_hub_westend = EmulatedChain::<BridgeHubWestend, WestendRococoMessageHandler>::new();
+	let bridge_hub_rococo = EmulatedChain::<BridgeHubRococo>::new();
+	let asset_hub_rococo = EmulatedChain::<AssetHubRococo>::new();
+	let penpal_rococo = EmulatedChain::<PenpalA>::new();
+	let dry_run = MultiHopDryRun::new()
+		.with_chain(&asset_hub_westend)
+		.with_chain(&bridge_hub_westend)
+		.with_chain(&bridge_hub_rococo)
+		.with_chain(&asset_hub_rococo)
+		.with_chain(&penpal_rococo)
+		.with_bridge(
+			bridge_hub_westend.universal_location(),
+			bridge_hub_rococo.universal_location(),
+			Some(BridgeRococoToWestendMessagesPalletInstance::get()),
+		);
+
+	let effects = dry_run.dry_run_call(&asset_hub_westend, origin, call).unwrap();
+	assert_ok!(effects.execution_result);
+
+	// AHW -> BHW, then the message is exported over the bridge and dispatched by BHR to AHR,
+	// which reserve-transfers ROCs to Penpal
+	let route: Vec<_> = effects
+		.hops
+		.iter()
+		.map(|hop| (hop.sender.clone(), hop.destination.clone()))
+		.collect();
+	assert_eq!(
+		route,
+		vec![
+			(asset_hub_westend.universal_location(), bridge_hub_westend.universal_location()),
+			(bridge_hub_rococo.universal_location(), asset_hub_rococo.universal_location()),
+			(asset_hub_rococo.universal_location(), penpal_rococo.universal_location()),
+		]
+	);
+	for hop in &effects.hops {
+		assert!(matches!(hop.outcome, Some(Outcome::Complete { .. })), "{hop:?}");
+		let (_, execution_fees) = hop.execution_fees.clone().unwrap();
+		assert!(execution_fees > 0);
+	}
+	// ROCs are used to pay for execution on both AHR and Penpal
+	let roc_at_rococo_parachains = AssetId(Location::parent());
+	assert_eq!(effects.hops[1].execution_fees.as_ref().unwrap().0, roc_at_rococo_parachains);
+	assert_eq!(effects.hops[2].execution_fees.as_ref().unwrap().0, roc_at_rococo_parachains);
+	// local delivery fees are paid on AHW and AHR
+	assert!(effects.hops[0].delivery_fees.is_some());
+	assert!(effects.hops[2].delivery_fees.is_some());
+
+	// the dry run doesn't change the state of chains
+	assert_eq!(
+		foreign_balance_on_ah_westend(bridged_roc_at_asset_hub_westend, &sender),
+		prefund_amount
+	);
+	assert_eq!(roc_balance_on_penpal_rococo(&receiver), receiver_rocs_before);
+}
@@ -1027,6 +1027,7 @@ pub type Migrations = (
 		Runtime,
 		TrustBackedAssetsInstance,
 	>,
+	pallet_xcm::migration::v2::MigrateToV2<Runtime>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -1334,19 +1335,40 @@ impl_runtime_apis! {
 
 	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
 		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
-			let acceptable_assets = vec![AssetId(xcm_config::TokenLocation::get())];
+			let native_token = xcm_config::TokenLocation::get();
+			// We accept the native token to pay fees.
+			let mut acceptable_assets = vec![AssetId(native_token.clone())];
+			// We also accept assets in a pool with the native token, that is deep enough to buy at
+			// least the existential deposit within the `MaxSwapSlippage`.
+			let pool_assets = assets_common::PoolAdapter::<Runtime>::get_assets_in_pool_with(native_token)
+				.map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+			for asset in pool_assets {
+				let asset_kind = xcm::v4::Location::try_from(asset.0.clone())
+					.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+				if xcm_config::SwapTrader::quote_amount_in(&asset_kind, ExistentialDeposit::get()).is_ok() {
+					acceptable_assets.push(asset);
+				}
+			}
 			PolkadotXcm::query_acceptable_payment_assets(xcm_version, acceptable_assets)
 		}
 
 		fn query_weight_to_asset_fee(weight: Weight, asset: VersionedAssetId) -> Result<u128, XcmPaymentApiError> {
+			let native_asset = xcm_config::TokenLocation::get();
+			let fee_in_native = WeightToFee::weight_to_fee(&weight);
 			match asset.try_as::<AssetId>() {
-				Ok(asset_id) if asset_id.0 == xcm_config::TokenLocation::get() => {
+				Ok(asset_id) if asset_id.0 == native_asset => {
 					// for native token
-					Ok(WeightToFee::weight_to_fee(&weight))
+					Ok(fee_in_native)
 				},
 				Ok(asset_id) => {
-					log::trace!(target: "xcm::xcm_runtime_apis", "query_weight_to_asset_fee - unhandled asset_id: {asset_id:?}!");
-					Err(XcmPaymentApiError::AssetNotFound)
+					// for any other asset, the amount that is swapped for the fee in native token,
+					// if the pool is deep enough to buy it within the `MaxSwapSlippage`
+					let asset_kind = xcm::v4::Location::try_from(asset_id.0.clone())
+						.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+					xcm_config::SwapTrader::quote_amount_in(&asset_kind, fee_in_native).map_err(|error| {
+						log::trace!(target: "xcm::xcm_runtime_apis", "query_weight_to_asset_fee - unhandled asset_id: {asset_id:?}, error: {error:?}!");
+						XcmPaymentApiError::AssetNotFound
+					})
 				},
 				Err(_) => {
 					log::trace!(target: "xcm::xcm_runtime_apis", "query_weight_to_asset_fee - failed to convert asset: {asset:?}!");
@@ -1386,6 +1408,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::queries::XcmQueriesApi<Block, BlockNumber> for Runtime {
+		fn pending_queries(responder: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::queries::PendingQuery<BlockNumber>>,
+			xcm_runtime_apis::queries::Error
+		> {
+			PolkadotXcm::pending_queries(responder)
+		}
+	}
+
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(origin: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets<BlockNumber>>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			PolkadotXcm::trapped_assets(origin)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -1722,6 +1762,64 @@ impl_runtime_apis! {
 					})
 				}
 
+				fn worst_case_for_trader() -> Result<Asset, BenchmarkError> {
+					// Paying fees with a non-native asset is the worst case, because the
+					// `SwapAssetTrader` swaps it to the native asset in the asset conversion pool.
+					let asset_id: u32 = 1;
+					let asset_location = Location::new(
+						0,
+						[
+							PalletInstance(xcm_config::TrustBackedAssetsPalletIndex::get()),
+							GeneralIndex(asset_id.into()),
+						],
+					);
+					let pool_owner: AccountId = frame_benchmarking::account("pool_owner", 0, 0);
+					let liquidity = 1_000_000 * UNITS;
+
+					<Assets as frame_support::traits::fungibles::Create<AccountId>>::create(
+						asset_id,
+						pool_owner.clone(),
+						true,
+						1,
+					)
+					.map_err(|_| BenchmarkError::Stop("Failed to create asset"))?;
+					<Assets as frame_support::traits::fungibles::Mutate<AccountId>>::mint_into(
+						asset_id,
+						&pool_owner,
+						2 * liquidity,
+					)
+					.map_err(|_| BenchmarkError::Stop("Failed to mint asset"))?;
+					<Balances as frame_support::traits::fungible::Mutate<AccountId>>::mint_into(
+						&pool_owner,
+						2 * liquidity,
+					)
+					.map_err(|_| BenchmarkError::Stop("Failed to mint native asset"))?;
+
+					let native_location = xcm::v4::Location::try_from(TokenLocation::get())
+						.map_err(|_| BenchmarkError::Stop("Failed to convert native location"))?;
+					let pool_asset_location = xcm::v4::Location::try_from(asset_location.clone())
+						.map_err(|_| BenchmarkError::Stop("Failed to convert asset location"))?;
+					AssetConversion::create_pool(
+						RuntimeOrigin::signed(pool_owner.clone()),
+						alloc::boxed::Box::new(native_location.clone()),
+						alloc::boxed::Box::new(pool_asset_location.clone()),
+					)
+					.map_err(|_| BenchmarkError::Stop("Failed to create pool"))?;
+					AssetConversion::add_liquidity(
+						RuntimeOrigin::signed(pool_owner.clone()),
+						alloc::boxed::Box::new(native_location),
+						alloc::boxed::Box::new(pool_asset_location),
+						liquidity,
+						liquidity,
+						1,
+						1,
+						pool_owner,
+					)
+					.map_err(|_| BenchmarkError::Stop("Failed to add liquidity"))?;
+
+					Ok(Asset { id: AssetId(asset_location), fun: Fungible(UNITS) })
+				}
+
 				fn unlockable_asset() -> Result<(Location, Location, Asset), BenchmarkError> {
 					Err(BenchmarkError::Skip)
 				}
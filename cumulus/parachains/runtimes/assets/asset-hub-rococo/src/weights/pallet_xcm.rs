@@ -362,14 +362,13 @@ impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn new_query() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `103`
-		//  Estimated: `1588`
-		// Minimum execution time: 4_871_000 picoseconds.
-		Weight::from_parts(5_066_000, 0)
-			.saturating_add(Weight::from_parts(0, 1588))
+		// Not benchmarked yet: the benchmarked registration of the query, plus the read and
+		// write of `NextQueryTimeoutCheck` and the write of `QueryTimeouts`. To be replaced with
+		// the output of the `new_query` benchmark.
+		Weight::from_parts(5_066_000, 1588)
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 2))
 	}
 	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -43,7 +43,10 @@ use parachains_common::{
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::xcm_sender::ExponentialPrice;
 use snowbridge_router_primitives::inbound::GlobalConsensusEthereumConvertsFor;
-use sp_runtime::traits::{AccountIdConversion, ConvertInto, TryConvertInto};
+use sp_runtime::{
+	traits::{AccountIdConversion, ConvertInto, TryConvertInto},
+	Permill,
+};
 use testnet_parachains_constants::rococo::snowbridge::{
 	EthereumNetwork, INBOUND_QUEUE_PALLET_INDEX,
 };
@@ -252,8 +255,27 @@ parameter_types! {
 	pub const MaxInstructions: u32 = 100;
 	pub const MaxAssetsIntoHolding: u32 = 64;
 	pub XcmAssetFeesReceiver: Option<AccountId> = Authorship::author();
+	/// The maximum price impact, that is accepted when swapping assets to pay for the XCM execution.
+	/// It is relative to the pool's price at the time of the payment, so it doesn't limit the price
+	/// change caused by other swaps earlier in the same block.
+	pub const MaxSwapSlippage: Permill = Permill::from_percent(3);
 }
 
+/// Trader, that swaps assets in pools with the native token to pay for the XCM execution.
+pub type SwapTrader = cumulus_primitives_utility::SwapAssetTrader<
+	TokenLocation,
+	crate::AssetConversion,
+	WeightToFee,
+	crate::NativeAndAssets,
+	(
+		TrustBackedAssetsAsLocation<TrustBackedAssetsPalletLocation, Balance, xcm::v4::Location>,
+		ForeignAssetsConvertedConcreteId,
+	),
+	ResolveAssetTo<StakingPot, crate::NativeAndAssets>,
+	MaxSwapSlippage,
+	AccountId,
+>;
+
 pub struct ParentOrParentsPlurality;
 impl Contains<Location> for ParentOrParentsPlurality {
 	fn contains(location: &Location) -> bool {
@@ -380,22 +402,7 @@ impl xcm_executor::Config for XcmConfig {
 			Balances,
 			ResolveTo<StakingPot, Balances>,
 		>,
-		cumulus_primitives_utility::SwapFirstAssetTrader<
-			TokenLocation,
-			crate::AssetConversion,
-			WeightToFee,
-			crate::NativeAndAssets,
-			(
-				TrustBackedAssetsAsLocation<
-					TrustBackedAssetsPalletLocation,
-					Balance,
-					xcm::v4::Location,
-				>,
-				ForeignAssetsConvertedConcreteId,
-			),
-			ResolveAssetTo<StakingPot, crate::NativeAndAssets>,
-			AccountId,
-		>,
+		SwapTrader,
 		// This trader allows to pay with `is_sufficient=true` "Trust Backed" assets from dedicated
 		// `pallet_assets` instance - `Assets`.
 		cumulus_primitives_utility::TakeFirstAssetTrader<
@@ -446,6 +453,7 @@ impl xcm_executor::Config for XcmConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = PolkadotXcm;
+	type FeeSponsor = ();
 }
 
 /// Converts a local signed origin into an XCM location.
@@ -505,6 +513,8 @@ impl pallet_xcm::Config for Runtime {
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type MaxRemoteLockConsumers = ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type TrappedAssetsExpiry = ();
+	type TrappedAssetsBeneficiary = ();
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
@@ -1134,7 +1134,7 @@ mod asset_hub_rococo_tests {
 			foreign_asset_create_params.clone(),
 			1000000000000,
 			|| {
-				// setup pool for paying fees to touch `SwapFirstAssetTrader`
+				// setup pool for paying fees to touch `SwapAssetTrader`
 				setup_pool_for_paying_fees_with_foreign_assets(foreign_asset_create_params);
 				// staking pot account for collecting local native fees from `BuyExecution`
 				let _ = Balances::force_set_balance(RuntimeOrigin::root(), StakingPot::get().into(), ExistentialDeposit::get());
@@ -1159,7 +1159,7 @@ mod asset_hub_rococo_tests {
 				);
 			},
 			|| {
-				// `SwapFirstAssetTrader` - staking pot receives xcm fees in ROCs
+				// `SwapAssetTrader` - staking pot receives xcm fees in ROCs
 				assert!(
 					Balances::free_balance(&staking_pot) > ExistentialDeposit::get()
 				);
@@ -1231,7 +1231,7 @@ mod asset_hub_rococo_tests {
 						&block_author_account
 					) > 0
 				);
-				// `SwapFirstAssetTrader` did not work
+				// `SwapAssetTrader` did not work
 				assert_eq!(Balances::free_balance(&staking_pot), 0);
 			}
 		)
@@ -195,4 +195,11 @@ impl<T: frame_system::Config> WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(8))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	// Not generated by the benchmark CLI yet: estimated as `deposit_reserve_asset`, because the
+	// worst case of `InitiateTransfer` is reserve-depositing the remote fees, which deposits
+	// them to the destination sovereign account and sends the message, paying delivery fees.
+	// Must be regenerated with the `initiate_transfer` benchmark.
+	pub fn initiate_transfer() -> Weight {
+		Self::deposit_reserve_asset()
+	}
 }
@@ -352,4 +352,20 @@ impl<T: frame_system::Config> WeightInfo<T> {
 		// Minimum execution time: 607_000 picoseconds.
 		Weight::from_parts(683_000, 0)
 	}
+	// Not generated by the benchmark CLI yet: estimated as `buy_execution` plus a two-asset
+	// swap in the asset conversion pool, because the worst case is paying fees with a
+	// non-native asset, which `SwapAssetTrader` swaps to the native asset, reading and writing
+	// the pool and the pool account balances.
+	// Must be regenerated with the `pay_fees` benchmark.
+	pub fn pay_fees() -> Weight {
+		Self::buy_execution().saturating_add(
+			<crate::weights::pallet_asset_conversion::WeightInfo<T> as pallet_asset_conversion::WeightInfo>::swap_exact_tokens_for_tokens(2),
+		)
+	}
+	// Not generated by the benchmark CLI yet: estimated as `set_fees_mode`, because both
+	// instructions only update the executor state.
+	// Must be regenerated with the `asset_claimer` benchmark.
+	pub fn asset_claimer() -> Weight {
+		Self::set_fees_mode()
+	}
 }
@@ -1146,7 +1146,7 @@ fn receive_reserve_asset_deposited_roc_from_asset_hub_rococo_fees_paid_by_pool_s
 			foreign_asset_create_params.clone(),
 			1000000000000,
 			|| {
-				// setup pool for paying fees to touch `SwapFirstAssetTrader`
+				// setup pool for paying fees to touch `SwapAssetTrader`
 				setup_pool_for_paying_fees_with_foreign_assets(foreign_asset_create_params);
 				// staking pot account for collecting local native fees from `BuyExecution`
 				let _ = Balances::force_set_balance(RuntimeOrigin::root(), StakingPot::get().into(), ExistentialDeposit::get());
@@ -1171,7 +1171,7 @@ fn receive_reserve_asset_deposited_roc_from_asset_hub_rococo_fees_paid_by_pool_s
 				);
 			},
 			|| {
-				// `SwapFirstAssetTrader` - staking pot receives xcm fees in ROCs
+				// `SwapAssetTrader` - staking pot receives xcm fees in ROCs
 				assert!(
 					Balances::free_balance(&staking_pot) > ExistentialDeposit::get()
 				);
@@ -1238,7 +1238,7 @@ fn receive_reserve_asset_deposited_roc_from_asset_hub_rococo_fees_paid_by_suffic
 					&block_author_account
 				) > 0
 			);
-			// `SwapFirstAssetTrader` did not work
+			// `SwapAssetTrader` did not work
 			assert_eq!(Balances::free_balance(&staking_pot), 0);
 		}
 	)
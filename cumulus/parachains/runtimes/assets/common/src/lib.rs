@@ -26,6 +26,8 @@ pub mod runtime_api;
 extern crate alloc;
 
 use crate::matching::{LocalLocationPattern, ParentLocation};
+use alloc::vec::Vec;
+use core::{fmt::Debug, marker::PhantomData};
 use frame_support::traits::{Equals, EverythingBut};
 use parachains_common::{AssetIdForTrustBackedAssets, CollectionId, ItemId};
 use sp_runtime::traits::TryConvertInto;
@@ -134,6 +136,39 @@ pub type PoolAssetsConvertedConcreteId<PoolAssetsPalletLocation, Balance> =
 		TryConvertInto,
 	>;
 
+/// Adapter for accessing pools of the `pallet_asset_conversion`, whose `AssetKind` is a location
+/// of some XCM version, which could be different from the `xcm::latest`.
+pub struct PoolAdapter<Runtime>(PhantomData<Runtime>);
+impl<
+		Runtime: pallet_asset_conversion::Config<PoolId = (L, L), AssetKind = L>,
+		L: TryFrom<Location> + TryInto<Location> + Clone + PartialEq + Debug,
+	> PoolAdapter<Runtime>
+{
+	/// Returns all assets that have a pool with the `asset`.
+	///
+	/// Iterates over all pools, so it should only be used in runtime APIs. An error is returned if
+	/// the location can't be converted from or to the latest XCM version.
+	pub fn get_assets_in_pool_with(asset: Location) -> Result<Vec<AssetId>, ()> {
+		let asset: L = asset.try_into().map_err(|_| ())?;
+		Self::iter_assets_in_pool_with(&asset)
+			.map(|location| location.try_into().map_err(|_| ()).map(AssetId))
+			.collect()
+	}
+
+	/// Returns an iterator over all assets that have a pool with the `asset`.
+	pub fn iter_assets_in_pool_with(asset: &L) -> impl Iterator<Item = L> + '_ {
+		pallet_asset_conversion::Pools::<Runtime>::iter_keys().filter_map(|(asset_1, asset_2)| {
+			if asset_1 == *asset {
+				Some(asset_2)
+			} else if asset_2 == *asset {
+				Some(asset_1)
+			} else {
+				None
+			}
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
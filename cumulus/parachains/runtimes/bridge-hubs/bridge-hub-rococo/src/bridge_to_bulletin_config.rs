@@ -20,10 +20,13 @@
 //! are reusing Polkadot Bulletin chain primitives everywhere here.
 
 use crate::{
-	bridge_common_config::RelayersForPermissionlessLanesInstance, weights,
-	xcm_config::UniversalLocation, AccountId, Balance, Balances, BridgeRococoBulletinGrandpa,
-	BridgeRococoBulletinMessages, PolkadotXcm, Runtime, RuntimeEvent, RuntimeHoldReason,
-	XcmOverRococoBulletin, XcmRouter,
+	bridge_common_config::{
+		BridgeLimitsPeriod, MaxBridgeLimits, RelayersForPermissionlessLanesInstance,
+	},
+	weights,
+	xcm_config::UniversalLocation,
+	AccountId, Balance, Balances, BridgeRococoBulletinGrandpa, BridgeRococoBulletinMessages,
+	PolkadotXcm, Runtime, RuntimeEvent, RuntimeHoldReason, XcmOverRococoBulletin, XcmRouter,
 };
 use bp_messages::{
 	source_chain::FromBridgedChainMessagesDeliveryProof,
@@ -155,6 +158,9 @@ impl pallet_xcm_bridge_hub::Config<XcmOverPolkadotBulletinInstance> for Runtime
 	// Do not require deposit from People parachains.
 	type AllowWithoutBridgeDeposit = Equals<PeopleRococoLocation>;
 
+	type MaxBridgeLimits = MaxBridgeLimits;
+	type BridgeLimitsPeriod = BridgeLimitsPeriod;
+
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromRococoBulletinMessageBlobDispatcher;
 }
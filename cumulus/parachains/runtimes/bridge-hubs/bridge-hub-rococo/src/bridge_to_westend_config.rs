@@ -18,8 +18,8 @@
 
 use crate::{
 	bridge_common_config::{
-		BridgeParachainWestendInstance, DeliveryRewardInBalance,
-		RelayersForLegacyLaneIdsMessagesInstance,
+		BridgeLimitsPeriod, BridgeParachainWestendInstance, DeliveryRewardInBalance,
+		MaxBridgeLimits, RelayersForLegacyLaneIdsMessagesInstance,
 	},
 	weights,
 	xcm_config::UniversalLocation,
@@ -155,6 +155,9 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubWestendInstance> for Runtime
 	type AllowWithoutBridgeDeposit =
 		RelayOrOtherSystemParachains<AllSiblingSystemParachains, Runtime>;
 
+	type MaxBridgeLimits = MaxBridgeLimits;
+	type BridgeLimitsPeriod = BridgeLimitsPeriod;
+
 	// TODO:(bridges-v2) - add `LocalXcmChannelManager` impl - https://github.com/paritytech/parity-bridges-common/issues/3047
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromWestendMessageBlobDispatcher;
@@ -90,7 +90,7 @@ use polkadot_runtime_common::{BlockHashCount, SlowAdjustingFeeUpdate};
 use rococo_runtime_constants::system_parachain::{ASSET_HUB_ID, BRIDGE_HUB_ID};
 use snowbridge_core::{
 	outbound::{Command, Fee},
-	AgentId, PricingParameters,
+	AgentId, ChannelId, PricingParameters, TokenSupply,
 };
 use xcm::{latest::prelude::*, prelude::*};
 use xcm_runtime_apis::{
@@ -153,6 +153,10 @@ pub type Migrations = (
 		ConstU32<BRIDGE_HUB_ID>,
 		ConstU32<ASSET_HUB_ID>,
 	>,
+	snowbridge_pallet_system::migration::v1::InitializeTokenSupplies<
+		Runtime,
+		bridge_to_ethereum_config::InitialTokenSupplies,
+	>,
 	pallet_bridge_messages::migration::v1::MigrationToV1<
 		Runtime,
 		bridge_to_westend_config::WithBridgeHubWestendMessagesInstance,
@@ -178,6 +182,7 @@ pub type Migrations = (
 		RocksDbWeight,
 	>,
 	pallet_bridge_relayers::migration::v1::MigrationToV1<Runtime, ()>,
+	pallet_xcm::migration::v2::MigrateToV2<Runtime>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -885,6 +890,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::queries::XcmQueriesApi<Block, BlockNumber> for Runtime {
+		fn pending_queries(responder: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::queries::PendingQuery<BlockNumber>>,
+			xcm_runtime_apis::queries::Error
+		> {
+			PolkadotXcm::pending_queries(responder)
+		}
+	}
+
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(origin: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets<BlockNumber>>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			PolkadotXcm::trapped_assets(origin)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -1003,10 +1026,21 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl snowbridge_inbound_queue_runtime_api::InboundQueueApi<Block> for Runtime {
+		fn missing_nonces(channel_id: ChannelId, limit: u32) -> Vec<u64> {
+			snowbridge_pallet_inbound_queue::api::missing_nonces::<Runtime>(channel_id, limit)
+		}
+	}
+
+	#[api_version(2)]
 	impl snowbridge_system_runtime_api::ControlApi<Block> for Runtime {
 		fn agent_id(location: VersionedLocation) -> Option<AgentId> {
 			snowbridge_pallet_system::api::agent_id::<Runtime>(location)
 		}
+
+		fn token_supply(location: VersionedLocation) -> Option<TokenSupply> {
+			snowbridge_pallet_system::api::token_supply::<Runtime>(location)
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -1466,6 +1500,17 @@ impl_runtime_apis! {
 					use frame_support::traits::fungible::Mutate;
 					Balances::mint_into(&account, balance.saturating_add(ExistentialDeposit::get())).unwrap();
 				}
+
+				fn bench_beneficiary() -> bridge_runtime_common::rewards::RewardBeneficiary<AccountId> {
+					ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ASSET_HUB_ID.into());
+					bridge_runtime_common::rewards::RewardBeneficiary::Remote(
+						Location::new(0, [Junction::AccountId32 { network: None, id: [42; 32] }]).into(),
+					)
+				}
+
+				fn bench_reward() -> Balance {
+					UNITS
+				}
 			}
 
 			impl BridgeRelayersConfig<bridge_common_config::RelayersForPermissionlessLanesInstance> for Runtime {
@@ -1485,6 +1530,17 @@ impl_runtime_apis! {
 					use frame_support::traits::fungible::Mutate;
 					Balances::mint_into(&account, balance.saturating_add(ExistentialDeposit::get())).unwrap();
 				}
+
+				fn bench_beneficiary() -> bridge_runtime_common::rewards::RewardBeneficiary<AccountId> {
+					ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ASSET_HUB_ID.into());
+					bridge_runtime_common::rewards::RewardBeneficiary::Remote(
+						Location::new(0, [Junction::AccountId32 { network: None, id: [42; 32] }]).into(),
+					)
+				}
+
+				fn bench_reward() -> Balance {
+					UNITS
+				}
 			}
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
@@ -21,9 +21,16 @@
 //! For example, the messaging pallet needs to know the sending and receiving chains, but the
 //! GRANDPA tracking pallet only needs to be aware of one chain.
 
-use super::{weights, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent};
+use super::{
+	weights, xcm_config, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent,
+};
 use bp_messages::LegacyLaneId;
+use bridge_runtime_common::rewards::PayRewardFromAccountOrOverXcm;
 use frame_support::parameter_types;
+use pallet_xcm_bridge_hub::BridgeLimits;
+use xcm::latest::prelude::*;
+use xcm_builder::AliasesIntoAccountId32;
+use xcm_executor::traits::TransferType;
 
 parameter_types! {
 	pub storage RequiredStakeForStakeAndSlash: Balance = 1_000_000;
@@ -31,18 +38,46 @@ parameter_types! {
 	pub const RelayerStakeReserveId: [u8; 8] = *b"brdgrlrs";
 
 	pub storage DeliveryRewardInBalance: u64 = 1_000_000;
+
+	pub RelayerRewardAsset: AssetId = AssetId(xcm_config::WestendLocation::get());
+	pub AssetHubLocation: Location =
+		Location::new(1, [Parachain(westend_runtime_constants::system_parachain::ASSET_HUB_ID)]);
+	pub const RelayerRewardTransferType: TransferType = TransferType::Teleport;
+
+	/// Limits of every bridge, opened with the `pallet-xcm-bridge-hub` pallet. The maximal number
+	/// of queued messages is larger than the congestion threshold, so that the bridge origin gets
+	/// the congestion signal before any messages are rejected.
+	pub const MaxBridgeLimits: BridgeLimits = BridgeLimits {
+		max_messages_per_block: 256,
+		max_bytes_per_period: 4 * 1024 * 1024,
+		max_outstanding_messages: 16_384,
+	};
+	/// Period of the `MaxBridgeLimits::max_bytes_per_period` limit (one minute).
+	pub const BridgeLimitsPeriod: BlockNumber = 10;
 }
 
+/// Pays relayer rewards either to local accounts, or to locations at the Asset Hub, where the
+/// reward is teleported to.
+pub type RelayerRewardsPaymentProcedure<LaneId> = PayRewardFromAccountOrOverXcm<
+	Balances,
+	AccountId,
+	LaneId,
+	xcm_config::FungibleTransactor,
+	xcm_config::XcmRouter,
+	<xcm_config::XcmConfig as xcm_executor::Config>::FeeManager,
+	xcm_config::UniversalLocation,
+	AliasesIntoAccountId32<(), AccountId>,
+	RelayerRewardAsset,
+	AssetHubLocation,
+	RelayerRewardTransferType,
+>;
+
 /// Allows collect and claim rewards for relayers
 pub type RelayersForLegacyLaneIdsMessagesInstance = ();
 impl pallet_bridge_relayers::Config<RelayersForLegacyLaneIdsMessagesInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = Balance;
-	type PaymentProcedure = bp_relayers::PayRewardFromAccount<
-		pallet_balances::Pallet<Runtime>,
-		AccountId,
-		Self::LaneId,
-	>;
+	type PaymentProcedure = RelayerRewardsPaymentProcedure<Self::LaneId>;
 	type StakeAndSlash = pallet_bridge_relayers::StakeAndSlashNamed<
 		AccountId,
 		BlockNumber,
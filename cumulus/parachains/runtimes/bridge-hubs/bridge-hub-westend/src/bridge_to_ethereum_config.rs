@@ -17,15 +17,19 @@
 #[cfg(not(feature = "runtime-benchmarks"))]
 use crate::XcmRouter;
 use crate::{
+	bridge_common_config::RelayersForLegacyLaneIdsMessagesInstance,
 	xcm_config,
 	xcm_config::{TreasuryAccount, UniversalLocation},
 	Balances, EthereumInboundQueue, EthereumOutboundQueue, EthereumSystem, MessageQueue, Runtime,
 	RuntimeEvent, TransactionByteFee,
 };
+use bp_messages::LegacyLaneId;
+use bp_relayers::{RewardsAccountOwner, RewardsAccountParams};
 use parachains_common::{AccountId, Balance};
 use snowbridge_beacon_primitives::{Fork, ForkVersions};
 use snowbridge_core::{gwei, meth, AllowSiblingsOnly, PricingParameters, Rewards};
 use snowbridge_router_primitives::{inbound::MessageToXcm, outbound::EthereumBlobExporter};
+use snowbridge_runtime_common::RegisterRelayerReward;
 use sp_core::H160;
 use testnet_parachains_constants::westend::{
 	currency::*,
@@ -39,7 +43,7 @@ use benchmark_helpers::DoNothingRouter;
 use frame_support::{parameter_types, weights::ConstantMultiplier};
 use pallet_xcm::EnsureXcm;
 use sp_runtime::{
-	traits::{ConstU32, ConstU8, Keccak256},
+	traits::{ConstBool, ConstU32, ConstU64, ConstU8, Keccak256},
 	FixedU128,
 };
 use xcm::prelude::{GlobalConsensus, InteriorLocation, Location, Parachain};
@@ -71,6 +75,11 @@ parameter_types! {
 	};
 	pub AssetHubFromEthereum: Location = Location::new(1,[GlobalConsensus(RelayNetwork::get()),Parachain(westend_runtime_constants::system_parachain::ASSET_HUB_ID)]);
 	pub EthereumUniversalLocation: InteriorLocation = [GlobalConsensus(EthereumNetwork::get())].into();
+	pub SnowbridgeRewardsAccountParams: RewardsAccountParams<LegacyLaneId> = RewardsAccountParams::new(
+		LegacyLaneId([0, 0, 0, 0]),
+		*b"ethe",
+		RewardsAccountOwner::ThisChain,
+	);
 }
 impl snowbridge_pallet_inbound_queue::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -100,6 +109,9 @@ impl snowbridge_pallet_inbound_queue::Config for Runtime {
 	type WeightInfo = crate::weights::snowbridge_pallet_inbound_queue::WeightInfo<Runtime>;
 	type PricingParameters = EthereumSystem;
 	type AssetTransactor = <xcm_config::XcmConfig as xcm_executor::Config>::AssetTransactor;
+	type OutOfOrderDelivery = ConstBool<false>;
+	type TokenLedger = EthereumSystem;
+	type AssetHubParaId = ConstU32<{ westend_runtime_constants::system_parachain::ASSET_HUB_ID }>;
 }
 
 impl snowbridge_pallet_outbound_queue::Config for Runtime {
@@ -115,6 +127,17 @@ impl snowbridge_pallet_outbound_queue::Config for Runtime {
 	type WeightInfo = crate::weights::snowbridge_pallet_outbound_queue::WeightInfo<Runtime>;
 	type PricingParameters = EthereumSystem;
 	type Channels = EthereumSystem;
+	type Verifier = snowbridge_pallet_ethereum_client::Pallet<Runtime>;
+	type GatewayAddress = EthereumGatewayAddress;
+	type RewardLedger = RegisterRelayerReward<
+		Runtime,
+		RelayersForLegacyLaneIdsMessagesInstance,
+		SnowbridgeRewardsAccountParams,
+	>;
+	type TokenLedger = EthereumSystem;
+	type MaxPendingOrders = ConstU64<8192>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = Runtime;
 }
 
 #[cfg(any(feature = "std", feature = "fast-runtime", feature = "runtime-benchmarks", test))]
@@ -139,6 +162,10 @@ parameter_types! {
 		deneb: Fork {
 			version: [4, 0, 0, 0], // 0x04000000
 			epoch: 0,
+		},
+		electra: Fork {
+			version: [5, 0, 0, 0], // 0x05000000
+			epoch: 80000000000,
 		}
 	};
 }
@@ -166,6 +193,10 @@ parameter_types! {
 			version: [144, 0, 0, 115], // 0x90000073
 			epoch: 132608,
 		},
+		electra: Fork {
+			version: [144, 0, 0, 116], // 0x90000074
+			epoch: 222464,
+		},
 	};
 }
 
@@ -173,9 +204,23 @@ impl snowbridge_pallet_ethereum_client::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ForkVersions = ChainForkVersions;
 	type FreeHeadersInterval = ConstU32<SLOTS_PER_EPOCH>;
+	// Each sync committee update takes about a quarter of the block weight.
+	type MaxCatchUpPeriods = ConstU32<2>;
 	type WeightInfo = crate::weights::snowbridge_pallet_ethereum_client::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	/// Supplies of the Polkadot-native tokens registered before supplies were tracked, as
+	/// reconciled with the amounts locked on AssetHub and minted on Ethereum.
+	///
+	/// No supplies have been reconciled yet, so tokens registered before this upgrade stay
+	/// untracked: `token_supply` returns `None` for them and `deregister_token` fails with
+	/// `TokenSupplyUnknown`. Tokens registered after the upgrade are tracked from zero. The
+	/// initialization only runs once, so tracking the older tokens needs a dedicated migration.
+	pub InitialTokenSupplies: alloc::vec::Vec<(Location, snowbridge_core::TokenSupply)> =
+		alloc::vec::Vec::new();
+}
+
 impl snowbridge_pallet_system::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OutboundQueue = EthereumOutboundQueue;
@@ -187,6 +232,7 @@ impl snowbridge_pallet_system::Config for Runtime {
 	#[cfg(feature = "runtime-benchmarks")]
 	type Helper = ();
 	type DefaultPricingParameters = Parameters;
+	type PriceFeed = ();
 	type InboundDeliveryCost = EthereumInboundQueue;
 	type UniversalLocation = UniversalLocation;
 	type EthereumLocation = EthereumLocation;
@@ -207,6 +253,14 @@ pub mod benchmark_helpers {
 		}
 	}
 
+	impl<T: snowbridge_pallet_outbound_queue::Config>
+		snowbridge_pallet_outbound_queue::BenchmarkHelper<T> for Runtime
+	{
+		fn initialize_storage(beacon_header: BeaconHeader, block_roots_root: H256) {
+			EthereumBeaconClient::store_finalized_header(beacon_header, block_roots_root).unwrap();
+		}
+	}
+
 	pub struct DoNothingRouter;
 	impl SendXcm for DoNothingRouter {
 		type Ticket = Xcm<()>;
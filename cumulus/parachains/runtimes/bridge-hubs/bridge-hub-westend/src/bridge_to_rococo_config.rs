@@ -17,7 +17,10 @@
 //! Bridge definitions used on BridgeHub with the Westend flavor.
 
 use crate::{
-	bridge_common_config::{DeliveryRewardInBalance, RelayersForLegacyLaneIdsMessagesInstance},
+	bridge_common_config::{
+		BridgeLimitsPeriod, DeliveryRewardInBalance, MaxBridgeLimits,
+		RelayersForLegacyLaneIdsMessagesInstance,
+	},
 	weights,
 	xcm_config::UniversalLocation,
 	AccountId, Balance, Balances, BridgeRococoMessages, PolkadotXcm, Runtime, RuntimeEvent,
@@ -112,6 +115,7 @@ impl pallet_bridge_grandpa::Config<BridgeGrandpaRococoInstance> for Runtime {
 	type BridgedChain = bp_rococo::Rococo;
 	type MaxFreeHeadersPerBlock = ConstU32<4>;
 	type FreeHeadersInterval = ConstU32<5>;
+	type MaxWarpSyncFragments = ConstU32<16>;
 	type HeadersToKeep = RelayChainHeadersToKeep;
 	type WeightInfo = weights::pallet_bridge_grandpa::WeightInfo<Runtime>;
 }
@@ -122,6 +126,8 @@ impl pallet_bridge_parachains::Config<BridgeParachainRococoInstance> for Runtime
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::pallet_bridge_parachains::WeightInfo<Runtime>;
 	type BridgesGrandpaPalletInstance = BridgeGrandpaRococoInstance;
+	type RelayHeadersSource =
+		pallet_bridge_parachains::GrandpaRelayHeaders<Self, BridgeParachainRococoInstance>;
 	type ParasPalletName = RococoBridgeParachainPalletName;
 	type ParaStoredHeaderDataBuilder =
 		SingleParaStoredHeaderDataBuilder<bp_bridge_hub_rococo::BridgeHubRococo>;
@@ -184,6 +190,9 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubRococoInstance> for Runtime {
 	type AllowWithoutBridgeDeposit =
 		RelayOrOtherSystemParachains<AllSiblingSystemParachains, Runtime>;
 
+	type MaxBridgeLimits = MaxBridgeLimits;
+	type BridgeLimitsPeriod = BridgeLimitsPeriod;
+
 	// TODO:(bridges-v2) - add `LocalXcmChannelManager` impl - https://github.com/paritytech/parity-bridges-common/issues/3047
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromRococoMessageBlobDispatcher;
@@ -97,7 +97,7 @@ use parachains_common::{
 };
 use snowbridge_core::{
 	outbound::{Command, Fee},
-	AgentId, PricingParameters,
+	AgentId, ChannelId, PricingParameters, TokenSupply,
 };
 use testnet_parachains_constants::westend::{consensus::*, currency::*, fee::WeightToFee, time::*};
 use xcm::VersionedLocation;
@@ -159,6 +159,11 @@ pub type Migrations = (
 		RocksDbWeight,
 	>,
 	pallet_bridge_relayers::migration::v1::MigrationToV1<Runtime, ()>,
+	snowbridge_pallet_system::migration::v1::InitializeTokenSupplies<
+		Runtime,
+		bridge_to_ethereum_config::InitialTokenSupplies,
+	>,
+	pallet_xcm::migration::v2::MigrateToV2<Runtime>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 	snowbridge_pallet_system::migration::v0::InitializeOnUpgrade<
@@ -817,6 +822,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::queries::XcmQueriesApi<Block, BlockNumber> for Runtime {
+		fn pending_queries(responder: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::queries::PendingQuery<BlockNumber>>,
+			xcm_runtime_apis::queries::Error
+		> {
+			PolkadotXcm::pending_queries(responder)
+		}
+	}
+
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(origin: VersionedLocation) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets<BlockNumber>>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			PolkadotXcm::trapped_assets(origin)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -891,10 +914,21 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl snowbridge_inbound_queue_runtime_api::InboundQueueApi<Block> for Runtime {
+		fn missing_nonces(channel_id: ChannelId, limit: u32) -> Vec<u64> {
+			snowbridge_pallet_inbound_queue::api::missing_nonces::<Runtime>(channel_id, limit)
+		}
+	}
+
+	#[api_version(2)]
 	impl snowbridge_system_runtime_api::ControlApi<Block> for Runtime {
 		fn agent_id(location: VersionedLocation) -> Option<AgentId> {
 			snowbridge_pallet_system::api::agent_id::<Runtime>(location)
 		}
+
+		fn token_supply(location: VersionedLocation) -> Option<TokenSupply> {
+			snowbridge_pallet_system::api::token_supply::<Runtime>(location)
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -1301,6 +1335,17 @@ impl_runtime_apis! {
 					use frame_support::traits::fungible::Mutate;
 					Balances::mint_into(&account, balance.saturating_add(ExistentialDeposit::get())).unwrap();
 				}
+
+				fn bench_beneficiary() -> bridge_runtime_common::rewards::RewardBeneficiary<AccountId> {
+					ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ASSET_HUB_ID.into());
+					bridge_runtime_common::rewards::RewardBeneficiary::Remote(
+						Location::new(0, [Junction::AccountId32 { network: None, id: [42; 32] }]).into(),
+					)
+				}
+
+				fn bench_reward() -> Balance {
+					UNITS
+				}
 			}
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
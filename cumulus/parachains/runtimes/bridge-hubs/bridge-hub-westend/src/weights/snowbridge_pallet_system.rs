@@ -263,4 +263,18 @@ impl<T: frame_system::Config> snowbridge_pallet_system::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+
+	// Not benchmarked yet: sends the command like `register_token`, without writing the token.
+	// To be replaced with the output of the `update_token_metadata` benchmark.
+	fn update_token_metadata() -> Weight {
+		Self::register_token()
+	}
+
+	// Not benchmarked yet: sends the command like `register_token` and removes the token with
+	// its supply. To be replaced with the output of the `deregister_token` benchmark.
+	fn deregister_token() -> Weight {
+		Self::register_token()
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
@@ -32,6 +32,7 @@ use bp_xcm_bridge_hub::XcmAsPlainPayload;
 use frame_support::traits::{OnFinalize, OnInitialize};
 use frame_system::pallet_prelude::BlockNumberFor;
 use pallet_bridge_messages::{BridgedChainOf, LaneIdOf, ThisChainOf};
+use pallet_bridge_parachains::GrandpaRelayHeaders;
 use parachains_runtimes_test_utils::{
 	AccountIdOf, BasicParachainRuntime, CollatorSessionKeys, RuntimeCallOf, SlotDurations,
 };
@@ -48,8 +49,10 @@ pub trait WithRemoteParachainHelper {
 	type Runtime: BasicParachainRuntime
 		+ cumulus_pallet_xcmp_queue::Config
 		+ BridgeGrandpaConfig<Self::GPI>
-		+ BridgeParachainsConfig<Self::PPI>
-		+ BridgeMessagesConfig<
+		+ BridgeParachainsConfig<
+			Self::PPI,
+			RelayHeadersSource = GrandpaRelayHeaders<Self::Runtime, Self::PPI>,
+		> + BridgeMessagesConfig<
 			Self::MPI,
 			InboundPayload = XcmAsPlainPayload,
 			OutboundPayload = XcmAsPlainPayload,
@@ -79,7 +82,7 @@ where
 	Runtime: BasicParachainRuntime
 		+ cumulus_pallet_xcmp_queue::Config
 		+ BridgeGrandpaConfig<GPI>
-		+ BridgeParachainsConfig<PPI>
+		+ BridgeParachainsConfig<PPI, RelayHeadersSource = GrandpaRelayHeaders<Runtime, PPI>>
 		+ BridgeMessagesConfig<
 			MPI,
 			InboundPayload = XcmAsPlainPayload,
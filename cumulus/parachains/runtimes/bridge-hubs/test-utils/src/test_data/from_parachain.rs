@@ -31,6 +31,7 @@ use bp_test_utils::prepare_parachain_heads_proof;
 use bp_xcm_bridge_hub::XcmAsPlainPayload;
 use codec::Encode;
 use pallet_bridge_grandpa::BridgedHeader;
+use pallet_bridge_parachains::GrandpaRelayHeaders;
 use sp_runtime::traits::Header as HeaderT;
 use xcm::latest::prelude::*;
 
@@ -58,8 +59,10 @@ pub fn make_complex_relayer_delivery_batch<Runtime, GPI, PPI, MPI>(
 ) -> pallet_utility::Call<Runtime>
 where
 	Runtime: pallet_bridge_grandpa::Config<GPI>
-		+ pallet_bridge_parachains::Config<PPI>
-		+ pallet_bridge_messages::Config<MPI, InboundPayload = XcmAsPlainPayload>
+		+ pallet_bridge_parachains::Config<
+			PPI,
+			RelayHeadersSource = GrandpaRelayHeaders<Runtime, PPI>,
+		> + pallet_bridge_messages::Config<MPI, InboundPayload = XcmAsPlainPayload>
 		+ pallet_utility::Config,
 	GPI: 'static,
 	PPI: 'static,
@@ -111,8 +114,10 @@ pub fn make_complex_relayer_confirmation_batch<Runtime, GPI, PPI, MPI>(
 ) -> pallet_utility::Call<Runtime>
 where
 	Runtime: pallet_bridge_grandpa::Config<GPI>
-		+ pallet_bridge_parachains::Config<PPI>
-		+ pallet_bridge_messages::Config<MPI, OutboundPayload = XcmAsPlainPayload>
+		+ pallet_bridge_parachains::Config<
+			PPI,
+			RelayHeadersSource = GrandpaRelayHeaders<Runtime, PPI>,
+		> + pallet_bridge_messages::Config<MPI, OutboundPayload = XcmAsPlainPayload>
 		+ pallet_utility::Config,
 	GPI: 'static,
 	PPI: 'static,
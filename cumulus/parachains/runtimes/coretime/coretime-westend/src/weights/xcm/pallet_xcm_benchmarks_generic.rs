@@ -324,4 +324,17 @@ impl<T: frame_system::Config> WeightInfo<T> {
 		// Minimum execution time: 568_000 picoseconds.
 		Weight::from_parts(597_000, 0)
 	}
+	// Not generated by the benchmark CLI yet: estimated as `buy_execution`, because both
+	// instructions buy weight using the same `UsingComponents` trader, which doesn't access
+	// the storage.
+	// Must be regenerated with the `pay_fees` benchmark.
+	pub fn pay_fees() -> Weight {
+		Self::buy_execution()
+	}
+	// Not generated by the benchmark CLI yet: estimated as `set_fees_mode`, because both
+	// instructions only update the executor state.
+	// Must be regenerated with the `asset_claimer` benchmark.
+	pub fn asset_claimer() -> Weight {
+		Self::set_fees_mode()
+	}
 }
@@ -92,6 +92,7 @@ impl xcm_executor::Config for XcmConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = ();
+	type FeeSponsor = ();
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
@@ -148,6 +148,7 @@ pub type UncheckedExtrinsic =
 pub type Migrations = (
 	pallet_balances::migration::MigrateToTrackInactive<Runtime, xcm_config::CheckingAccount>,
 	pallet_collator_selection::migration::v1::MigrateToV1<Runtime>,
+	pallet_xcm::migration::v2::MigrateToV2<Runtime>,
 );
 
 /// Executive: handles dispatch to the various modules.
@@ -1022,6 +1023,7 @@ impl_runtime_apis! {
 
 		fn dry_run_xcm(origin_location: VersionedLocation, program: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			use xcm_builder::InspectMessageQueues;
+			use xcm_executor::RecordXcm;
 			use xcm::prelude::*;
 
 			let origin_location: Location = origin_location.try_into().map_err(|error| {
@@ -1042,6 +1044,7 @@ impl_runtime_apis! {
 			})?;
 			let mut hash = program.using_encoded(sp_core::hashing::blake2_256);
 			frame_system::Pallet::<Runtime>::reset_events(); // To make sure we only record events from current call.
+			PolkadotXcm::set_trace_xcm(true);
 			let result = xcm_executor::XcmExecutor::<xcm_config::XcmConfig>::prepare_and_execute(
 				origin_location,
 				program,
@@ -1049,12 +1052,15 @@ impl_runtime_apis! {
 				Weight::MAX, // Max limit.
 				Weight::zero(),
 			);
+			PolkadotXcm::set_trace_xcm(false);
+			let execution_trace = PolkadotXcm::recorded_trace();
 			let forwarded_xcms = xcm_config::XcmRouter::get_messages();
 			let events: Vec<RuntimeEvent> = System::read_events_no_consensus().map(|record| record.event.clone()).collect();
 			Ok(XcmDryRunEffects {
 				forwarded_xcms,
 				emitted_events: events,
 				execution_result: result,
+				execution_trace,
 			})
 		}
 	}
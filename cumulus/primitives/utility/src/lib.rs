@@ -31,11 +31,12 @@ use frame_support::{
 	weights::{Weight, WeightToFee as WeightToFeeT},
 	CloneNoBound,
 };
-use pallet_asset_conversion::SwapCredit as SwapCreditT;
+use pallet_asset_conversion::{QuotePrice as QuotePriceT, SwapCredit as SwapCreditT};
 use polkadot_runtime_common::xcm_sender::PriceForMessageDelivery;
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
 	traits::{Saturating, Zero},
-	SaturatedConversion,
+	Permill, Rounding, SaturatedConversion,
 };
 use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm, WrapVersion};
 use xcm_builder::{InspectMessageQueues, TakeRevenue};
@@ -559,6 +560,416 @@ where
 	}
 }
 
+/// The fee, paid by swapping the asset for the `Target` asset.
+#[derive(Clone, Debug)]
+struct SwapPayment<AssetKind, Balance> {
+	/// The asset, used to pay the fee.
+	asset: AssetId,
+	/// The asset, used to pay the fee, as it's known to the asset conversion.
+	asset_kind: AssetKind,
+	/// Amount of the `asset`, that has been swapped and has not been refunded yet.
+	paid: Balance,
+	/// Amount of the `Target` asset, that has been bought and has not been refunded yet.
+	fee: Balance,
+}
+
+/// Provides an implementation of [`WeightTrader`] to charge for weight using the first asset
+/// specified in the `payment` argument, which is swapped for the `Target` asset using the asset
+/// conversion pools.
+///
+/// Unlike [`SwapFirstAssetTrader`], this trader:
+///
+/// - swaps only the amount of the asset, that is quoted by `Swap` for the fee, and rejects the
+///   payment, if the quoted amount exceeds the price without the pool's fee and the price impact by
+///   more than `MaxSlippage`. The bound is relative to the current spot price of the pool, so it
+///   doesn't protect against the pool, that has been moved earlier in the same block (e.g. by a
+///   swap, that front-runs the XCM execution): the payment is accepted at the moved price, as long
+///   as the swap itself doesn't move it further by more than `MaxSlippage`;
+///
+/// - tracks fees, paid with every asset, and refunds in the asset that has been used to pay for the
+///   refunded weight. Since the single refund is paid in one asset, it is capped at the fee, paid
+///   with the last asset, and following refunds are paid with earlier assets;
+///
+/// - never refunds more of the asset than has been paid for the refunded weight, even if the pool
+///   price has changed in the meantime. The refund is swapped back to the asset, so it is reduced
+///   by the pool's fee and the price impact.
+///
+/// Assets, that may be used to pay fees with this trader, are assets that have a pool with the
+/// `Target` asset, that is deep enough for the fee to be bought within the `MaxSlippage`. The
+/// runtime may use [`Self::quote_amount_in`] to report them through the
+/// `XcmPaymentApi::query_acceptable_payment_assets`.
+///
+/// ### Parameters:
+/// - `Target`: the asset into which the user's payment will be exchanged using `Swap`.
+/// - `Swap`: the asset conversion, used to quote prices and to exchange the user's payment into the
+///   `Target` asset and back.
+/// - `WeightToFee`: weight to the `Target` asset fee calculator.
+/// - `Fungibles`: registry of fungible assets.
+/// - `FungiblesAssetMatcher`: utility for mapping [`Asset`] to `Fungibles::AssetId` and
+///   `Fungibles::Balance`.
+/// - `OnUnbalanced`: handler for the fee payment.
+/// - `MaxSlippage`: maximal share of the price, that may be paid for the pool's fee and the price
+///   impact of the swap. It must be larger than the pool's fee. It is relative to the spot price at
+///   the time of the payment, not to a reference price, so it doesn't limit the price change caused
+///   by earlier swaps in the same block.
+/// - `AccountId`: the account identifier type.
+pub struct SwapAssetTrader<
+	Target: Get<Fungibles::AssetId>,
+	Swap: SwapCreditT<
+			AccountId,
+			Balance = Fungibles::Balance,
+			AssetKind = Fungibles::AssetId,
+			Credit = fungibles::Credit<AccountId, Fungibles>,
+		> + QuotePriceT<Balance = Fungibles::Balance, AssetKind = Fungibles::AssetId>,
+	WeightToFee: WeightToFeeT<Balance = Fungibles::Balance>,
+	Fungibles: fungibles::Balanced<AccountId>,
+	FungiblesAssetMatcher: MatchesFungibles<Fungibles::AssetId, Fungibles::Balance>,
+	OnUnbalanced: OnUnbalancedT<fungibles::Credit<AccountId, Fungibles>>,
+	MaxSlippage: Get<Permill>,
+	AccountId,
+> where
+	Fungibles::Balance: Into<u128>,
+{
+	/// Accumulated fee paid for XCM execution.
+	total_fee: fungibles::Credit<AccountId, Fungibles>,
+	/// Fees, paid with every asset, in the order of payments.
+	payments: Vec<SwapPayment<Fungibles::AssetId, Fungibles::Balance>>,
+	_phantom_data: PhantomData<(
+		Target,
+		Swap,
+		WeightToFee,
+		Fungibles,
+		FungiblesAssetMatcher,
+		OnUnbalanced,
+		MaxSlippage,
+		AccountId,
+	)>,
+}
+
+impl<
+		Target: Get<Fungibles::AssetId>,
+		Swap: SwapCreditT<
+				AccountId,
+				Balance = Fungibles::Balance,
+				AssetKind = Fungibles::AssetId,
+				Credit = fungibles::Credit<AccountId, Fungibles>,
+			> + QuotePriceT<Balance = Fungibles::Balance, AssetKind = Fungibles::AssetId>,
+		WeightToFee: WeightToFeeT<Balance = Fungibles::Balance>,
+		Fungibles: fungibles::Balanced<AccountId>,
+		FungiblesAssetMatcher: MatchesFungibles<Fungibles::AssetId, Fungibles::Balance>,
+		OnUnbalanced: OnUnbalancedT<fungibles::Credit<AccountId, Fungibles>>,
+		MaxSlippage: Get<Permill>,
+		AccountId,
+	>
+	SwapAssetTrader<
+		Target,
+		Swap,
+		WeightToFee,
+		Fungibles,
+		FungiblesAssetMatcher,
+		OnUnbalanced,
+		MaxSlippage,
+		AccountId,
+	>
+where
+	Fungibles::Balance: Into<u128>,
+{
+	/// Returns the amount of the `asset`, that needs to be swapped to buy the `fee`, if it is
+	/// within the slippage bound.
+	///
+	/// Returns [`XcmError::FeesNotMet`] if there's no pool of the `asset` with the `Target` asset,
+	/// and [`XcmError::TooExpensive`] if the pool is not deep enough to buy the `fee` within the
+	/// slippage bound.
+	pub fn quote_amount_in(
+		asset: &Fungibles::AssetId,
+		fee: Fungibles::Balance,
+	) -> Result<Fungibles::Balance, XcmError> {
+		let quote = |include_fee| {
+			Swap::quote_price_tokens_for_exact_tokens(
+				asset.clone(),
+				Target::get(),
+				fee,
+				include_fee,
+			)
+		};
+		let (Some(amount_in), Some(amount_in_at_spot_price)) = (quote(true), quote(false)) else {
+			log::trace!(
+				target: "xcm::weight",
+				"SwapAssetTrader::buy_weight can't quote the price of {:?} in {:?}",
+				fee,
+				asset,
+			);
+			// current trader is not applicable.
+			return Err(XcmError::FeesNotMet)
+		};
+
+		let max_amount_in = amount_in_at_spot_price
+			.saturating_add(MaxSlippage::get().mul_ceil(amount_in_at_spot_price));
+		if amount_in > max_amount_in {
+			log::trace!(
+				target: "xcm::weight",
+				"SwapAssetTrader::buy_weight slippage is too high: {:?} > {:?}",
+				amount_in,
+				max_amount_in,
+			);
+			return Err(XcmError::TooExpensive)
+		}
+		Ok(amount_in)
+	}
+
+	/// Returns the credit back to the `total_fee`.
+	fn return_to_total_fee(&mut self, credit: fungibles::Credit<AccountId, Fungibles>) {
+		let _ = self.total_fee.subsume(credit).map_err(|credit| {
+			// error may occur if `total_fee.asset` differs from `credit.asset`, which does not
+			// apply in this context.
+			defensive!(
+				"`total_fee.asset` must be equal to `credit.asset`",
+				(self.total_fee.asset(), credit.asset())
+			);
+		});
+	}
+}
+
+impl<
+		Target: Get<Fungibles::AssetId>,
+		Swap: SwapCreditT<
+				AccountId,
+				Balance = Fungibles::Balance,
+				AssetKind = Fungibles::AssetId,
+				Credit = fungibles::Credit<AccountId, Fungibles>,
+			> + QuotePriceT<Balance = Fungibles::Balance, AssetKind = Fungibles::AssetId>,
+		WeightToFee: WeightToFeeT<Balance = Fungibles::Balance>,
+		Fungibles: fungibles::Balanced<AccountId>,
+		FungiblesAssetMatcher: MatchesFungibles<Fungibles::AssetId, Fungibles::Balance>,
+		OnUnbalanced: OnUnbalancedT<fungibles::Credit<AccountId, Fungibles>>,
+		MaxSlippage: Get<Permill>,
+		AccountId,
+	> WeightTrader
+	for SwapAssetTrader<
+		Target,
+		Swap,
+		WeightToFee,
+		Fungibles,
+		FungiblesAssetMatcher,
+		OnUnbalanced,
+		MaxSlippage,
+		AccountId,
+	>
+where
+	Fungibles::Balance: Into<u128>,
+{
+	fn new() -> Self {
+		Self {
+			total_fee: fungibles::Credit::<AccountId, Fungibles>::zero(Target::get()),
+			payments: Vec::new(),
+			_phantom_data: PhantomData,
+		}
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: Weight,
+		mut payment: AssetsInHolding,
+		_context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		log::trace!(
+			target: "xcm::weight",
+			"SwapAssetTrader::buy_weight weight: {:?}, payment: {:?}",
+			weight,
+			payment,
+		);
+		let first_asset: Asset =
+			payment.fungible.pop_first().ok_or(XcmError::AssetNotFound)?.into();
+		let (fungibles_asset, balance) = FungiblesAssetMatcher::matches_fungibles(&first_asset)
+			.map_err(|error| {
+				log::trace!(
+					target: "xcm::weight",
+					"SwapAssetTrader::buy_weight asset {:?} didn't match. Error: {:?}",
+					first_asset,
+					error,
+				);
+				XcmError::AssetNotFound
+			})?;
+
+		if Target::get().eq(&fungibles_asset) {
+			log::trace!(
+				target: "xcm::weight",
+				"SwapAssetTrader::buy_weight Asset was same as Target, swap not needed.",
+			);
+			// current trader is not applicable.
+			return Err(XcmError::FeesNotMet)
+		}
+
+		let fee = WeightToFee::weight_to_fee(&weight);
+		if fee.is_zero() {
+			payment.fungible.insert(first_asset.id, balance.into());
+			return Ok(payment)
+		}
+		let amount_in = Self::quote_amount_in(&fungibles_asset, fee)?;
+		if amount_in > balance {
+			log::trace!(
+				target: "xcm::weight",
+				"SwapAssetTrader::buy_weight not enough to pay {:?} of {:?}, available: {:?}",
+				amount_in,
+				first_asset.id,
+				balance,
+			);
+			return Err(XcmError::TooExpensive)
+		}
+
+		// swap the quoted amount of the user's asset for the `Target` asset.
+		let credit_in = Fungibles::issue(fungibles_asset.clone(), amount_in);
+		let (credit_out, credit_change) = Swap::swap_tokens_for_exact_tokens(
+			vec![fungibles_asset.clone(), Target::get()],
+			credit_in,
+			fee,
+		)
+		.map_err(|(credit_in, error)| {
+			log::trace!(
+				target: "xcm::weight",
+				"SwapAssetTrader::buy_weight swap couldn't be done. Error was: {:?}",
+				error,
+			);
+			drop(credit_in);
+			XcmError::FeesNotMet
+		})?;
+
+		if let Err(credit_out) = self.total_fee.subsume(credit_out) {
+			// error may occur if `total_fee.asset` differs from `credit_out.asset`, which does
+			// not apply in this context.
+			defensive!(
+				"`total_fee.asset` must be equal to `credit_out.asset`",
+				(self.total_fee.asset(), credit_out.asset())
+			);
+			return Err(XcmError::FeesNotMet)
+		}
+
+		let paid = amount_in.saturating_sub(credit_change.peek());
+		drop(credit_change);
+		match self.payments.last_mut() {
+			Some(last) if last.asset == first_asset.id => {
+				last.paid.saturating_accrue(paid);
+				last.fee.saturating_accrue(fee);
+			},
+			_ => self.payments.push(SwapPayment {
+				asset: first_asset.id.clone(),
+				asset_kind: fungibles_asset,
+				paid,
+				fee,
+			}),
+		}
+
+		let unused = balance.saturating_sub(paid);
+		if !unused.is_zero() {
+			payment.fungible.insert(first_asset.id, unused.into());
+		}
+		Ok(payment)
+	}
+
+	fn refund_weight(&mut self, weight: Weight, _context: &XcmContext) -> Option<Asset> {
+		log::trace!(
+			target: "xcm::weight",
+			"SwapAssetTrader::refund_weight weight: {:?}, self.total_fee: {:?}, payments: {:?}",
+			weight,
+			self.total_fee,
+			self.payments,
+		);
+		let payment = self.payments.last()?.clone();
+		let refund_fee =
+			WeightToFee::weight_to_fee(&weight).min(payment.fee).min(self.total_fee.peek());
+		// the refund never exceeds the amount that has been paid for the refunded fee.
+		let max_refund: Fungibles::Balance = multiply_by_rational_with_rounding(
+			payment.paid.into(),
+			refund_fee.into(),
+			payment.fee.into(),
+			Rounding::Down,
+		)?
+		.saturated_into();
+		if max_refund.is_zero() {
+			return None
+		}
+		let refund_quote = Swap::quote_price_exact_tokens_for_tokens(
+			Target::get(),
+			payment.asset_kind.clone(),
+			refund_fee,
+			true,
+		)?;
+
+		let credit = self.total_fee.extract(refund_fee);
+		let path = vec![Target::get(), payment.asset_kind];
+		let refund = if refund_quote > max_refund {
+			Swap::swap_tokens_for_exact_tokens(path, credit, max_refund).map(|(refund, change)| {
+				self.return_to_total_fee(change);
+				refund
+			})
+		} else {
+			Swap::swap_exact_tokens_for_tokens(path, credit, Some(refund_quote))
+		};
+		let refund = match refund {
+			Ok(refund) => refund,
+			Err((credit, error)) => {
+				log::trace!(
+					target: "xcm::weight",
+					"SwapAssetTrader::refund_weight swap couldn't be done. Error was: {:?}",
+					error,
+				);
+				// return an attempted refund back to the `total_fee`.
+				self.return_to_total_fee(credit);
+				return None
+			},
+		};
+
+		if let Some(last) = self.payments.last_mut() {
+			last.fee.saturating_reduce(refund_fee);
+			last.paid.saturating_reduce(max_refund);
+			if last.fee.is_zero() {
+				self.payments.pop();
+			}
+		}
+
+		let refund_asset = Asset { id: payment.asset, fun: Fungible(refund.peek().into()) };
+		drop(refund);
+		Some(refund_asset)
+	}
+}
+
+impl<
+		Target: Get<Fungibles::AssetId>,
+		Swap: SwapCreditT<
+				AccountId,
+				Balance = Fungibles::Balance,
+				AssetKind = Fungibles::AssetId,
+				Credit = fungibles::Credit<AccountId, Fungibles>,
+			> + QuotePriceT<Balance = Fungibles::Balance, AssetKind = Fungibles::AssetId>,
+		WeightToFee: WeightToFeeT<Balance = Fungibles::Balance>,
+		Fungibles: fungibles::Balanced<AccountId>,
+		FungiblesAssetMatcher: MatchesFungibles<Fungibles::AssetId, Fungibles::Balance>,
+		OnUnbalanced: OnUnbalancedT<fungibles::Credit<AccountId, Fungibles>>,
+		MaxSlippage: Get<Permill>,
+		AccountId,
+	> Drop
+	for SwapAssetTrader<
+		Target,
+		Swap,
+		WeightToFee,
+		Fungibles,
+		FungiblesAssetMatcher,
+		OnUnbalanced,
+		MaxSlippage,
+		AccountId,
+	>
+where
+	Fungibles::Balance: Into<u128>,
+{
+	fn drop(&mut self) {
+		if self.total_fee.peek().is_zero() {
+			return
+		}
+		let total_fee = self.total_fee.extract(self.total_fee.peek());
+		OnUnbalanced::on_unbalanced(total_fee);
+	}
+}
+
 #[cfg(test)]
 mod test_xcm_router {
 	use super::*;
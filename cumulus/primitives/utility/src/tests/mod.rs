@@ -14,4 +14,5 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
+mod swap_asset;
 mod swap_first;
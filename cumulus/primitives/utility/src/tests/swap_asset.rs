@@ -0,0 +1,272 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::swap_first::mock::{
+	self, set_price_impact, setup_pool, AccountId, AssetId, Balance, Fungibles,
+};
+use crate::*;
+use frame_support::{assert_ok, parameter_types, traits::fungibles::Inspect};
+use xcm::latest::AssetId as XcmAssetId;
+use xcm_executor::AssetsInHolding;
+
+fn create_holding_asset(asset_id: AssetId, amount: Balance) -> AssetsInHolding {
+	create_asset(asset_id, amount).into()
+}
+
+fn create_asset(asset_id: AssetId, amount: Balance) -> Asset {
+	Asset { id: create_asset_id(asset_id), fun: Fungible(amount) }
+}
+
+fn create_asset_id(asset_id: AssetId) -> XcmAssetId {
+	AssetId(Location::new(0, [GeneralIndex(asset_id.into())]))
+}
+
+fn xcm_context() -> XcmContext {
+	XcmContext { origin: None, message_id: [0u8; 32], topic: None }
+}
+
+fn weight_worth_of(fee: Balance) -> Weight {
+	Weight::from_parts(fee.try_into().unwrap(), 0)
+}
+
+const TARGET_ASSET: AssetId = 1;
+const CLIENT_ASSET: AssetId = 2;
+const CLIENT_ASSET_2: AssetId = 3;
+
+parameter_types! {
+	pub const TargetAsset: AssetId = TARGET_ASSET;
+	pub const MaxSlippage: Permill = Permill::from_percent(10);
+}
+
+pub type Trader = SwapAssetTrader<
+	TargetAsset,
+	mock::Swap,
+	mock::WeightToFee,
+	mock::Fungibles,
+	mock::FungiblesMatcher,
+	(),
+	MaxSlippage,
+	AccountId,
+>;
+
+#[test]
+fn holding_asset_swap_for_target() {
+	let client_asset_total = 15;
+	let fee = 5;
+
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+
+	let holding_asset = create_holding_asset(CLIENT_ASSET, client_asset_total);
+	let holding_change = create_holding_asset(CLIENT_ASSET, client_asset_total - fee);
+
+	let target_total = Fungibles::total_issuance(TARGET_ASSET);
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader.buy_weight(weight_worth_of(fee), holding_asset, &xcm_context()).unwrap(),
+		holding_change
+	);
+
+	assert_eq!(trader.total_fee.peek(), fee);
+	assert_eq!(trader.payments.len(), 1);
+	assert_eq!((trader.payments[0].paid, trader.payments[0].fee), (fee, fee));
+
+	assert_eq!(Fungibles::total_issuance(TARGET_ASSET), target_total);
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total + fee);
+}
+
+#[test]
+fn only_quoted_amount_is_swapped() {
+	let client_asset_total = 15;
+	let fee = 10;
+	let price_impact = 1;
+
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+	set_price_impact(price_impact);
+
+	let holding_asset = create_holding_asset(CLIENT_ASSET, client_asset_total);
+	// the pool of the mock swaps at 1:1, returning the price impact as change.
+	let holding_change = create_holding_asset(CLIENT_ASSET, client_asset_total - fee);
+
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader.buy_weight(weight_worth_of(fee), holding_asset, &xcm_context()).unwrap(),
+		holding_change
+	);
+
+	assert_eq!(trader.total_fee.peek(), fee);
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total + fee);
+}
+
+#[test]
+fn slippage_above_bound_is_rejected() {
+	let fee = 10;
+
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+	// 10% of the price is allowed.
+	set_price_impact(2);
+
+	let target_total = Fungibles::total_issuance(TARGET_ASSET);
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader
+			.buy_weight(
+				weight_worth_of(fee),
+				create_holding_asset(CLIENT_ASSET, 100),
+				&xcm_context()
+			)
+			.unwrap_err(),
+		XcmError::TooExpensive
+	);
+
+	assert_eq!(trader.total_fee.peek(), 0);
+	assert_eq!(Fungibles::total_issuance(TARGET_ASSET), target_total);
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total);
+}
+
+#[test]
+fn not_enough_to_pay() {
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader
+			.buy_weight(weight_worth_of(5), create_holding_asset(CLIENT_ASSET, 4), &xcm_context())
+			.unwrap_err(),
+		XcmError::TooExpensive
+	);
+
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total);
+}
+
+#[test]
+fn buy_with_various_assets_and_refund_in_assets_used() {
+	let client_asset_total = 10;
+	let client_asset_2_total = 15;
+	let fee1 = 5;
+	let fee2 = 6;
+	let refund1 = 8;
+	let refund2 = 2;
+
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+	setup_pool(CLIENT_ASSET_2, 1000, TARGET_ASSET, 1000);
+	// create pools for refund swaps.
+	setup_pool(TARGET_ASSET, 1000, CLIENT_ASSET, 1000);
+	setup_pool(TARGET_ASSET, 1000, CLIENT_ASSET_2, 1000);
+
+	let target_total = Fungibles::total_issuance(TARGET_ASSET);
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+	let client_total_2 = Fungibles::total_issuance(CLIENT_ASSET_2);
+
+	let mut trader = Trader::new();
+	assert_ok!(trader.buy_weight(
+		weight_worth_of(fee1),
+		create_holding_asset(CLIENT_ASSET, client_asset_total),
+		&xcm_context()
+	));
+	assert_ok!(trader.buy_weight(
+		weight_worth_of(fee2),
+		create_holding_asset(CLIENT_ASSET_2, client_asset_2_total),
+		&xcm_context()
+	));
+	assert_eq!(trader.total_fee.peek(), fee1 + fee2);
+
+	// the first refund is capped at the fee, paid with the last asset.
+	assert_eq!(
+		trader.refund_weight(weight_worth_of(refund1), &xcm_context()),
+		Some(create_asset(CLIENT_ASSET_2, fee2))
+	);
+	assert_eq!(trader.total_fee.peek(), fee1);
+	assert_eq!(trader.payments.len(), 1);
+
+	// the second refund is paid in the asset, that has been used to pay the rest of fees.
+	assert_eq!(
+		trader.refund_weight(weight_worth_of(refund2), &xcm_context()),
+		Some(create_asset(CLIENT_ASSET, refund2))
+	);
+	assert_eq!(trader.total_fee.peek(), fee1 - refund2);
+	assert_eq!((trader.payments[0].paid, trader.payments[0].fee), (fee1 - refund2, fee1 - refund2));
+
+	assert_eq!(Fungibles::total_issuance(TARGET_ASSET), target_total);
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total + fee1 - refund2);
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET_2), client_total_2);
+}
+
+#[test]
+fn not_exchangeable_to_refund() {
+	let fee = 5;
+
+	setup_pool(CLIENT_ASSET, 1000, TARGET_ASSET, 1000);
+
+	let mut trader = Trader::new();
+	assert_ok!(trader.buy_weight(
+		weight_worth_of(fee),
+		create_holding_asset(CLIENT_ASSET, 15),
+		&xcm_context()
+	));
+
+	assert_eq!(trader.refund_weight(weight_worth_of(1), &xcm_context()), None);
+
+	assert_eq!(trader.total_fee.peek(), fee);
+	assert_eq!((trader.payments[0].paid, trader.payments[0].fee), (fee, fee));
+}
+
+#[test]
+fn nothing_to_refund() {
+	let mut trader = Trader::new();
+	assert_eq!(trader.refund_weight(weight_worth_of(5), &xcm_context()), None);
+}
+
+#[test]
+fn holding_asset_not_exchangeable_for_target() {
+	let client_total = Fungibles::total_issuance(CLIENT_ASSET);
+
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader
+			.buy_weight(
+				Weight::from_all(10),
+				create_holding_asset(CLIENT_ASSET, 10),
+				&xcm_context()
+			)
+			.unwrap_err(),
+		XcmError::FeesNotMet
+	);
+
+	assert_eq!(Fungibles::total_issuance(CLIENT_ASSET), client_total);
+}
+
+#[test]
+fn holding_asset_equal_to_target_asset() {
+	let mut trader = Trader::new();
+	assert_eq!(
+		trader
+			.buy_weight(
+				Weight::from_all(10),
+				create_holding_asset(TargetAsset::get(), 10),
+				&xcm_context()
+			)
+			.unwrap_err(),
+		XcmError::FeesNotMet
+	);
+}
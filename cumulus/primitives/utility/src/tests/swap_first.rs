@@ -375,6 +375,13 @@ pub mod mock {
 	   pub static TOTAL_ISSUANCE: RefCell<HashMap<AssetId, Balance>> = RefCell::new(HashMap::new());
 	   pub static ACCOUNT: RefCell<HashMap<(AssetId, AccountId), Balance>> = RefCell::new(HashMap::new());
 	   pub static SWAP: RefCell<HashMap<(AssetId, AssetId), AccountId>> = RefCell::new(HashMap::new());
+	   pub static PRICE_IMPACT: RefCell<Balance> = RefCell::new(0);
+	}
+
+	/// Sets the amount, by which quoted prices, that include the pool's fee, differ from the 1:1
+	/// price.
+	pub fn set_price_impact(impact: Balance) {
+		PRICE_IMPACT.with(|i| *i.borrow_mut() = impact);
 	}
 
 	pub struct Swap {}
@@ -445,6 +452,33 @@ pub mod mock {
 		}
 	}
 
+	impl QuotePriceT for Swap {
+		type Balance = Balance;
+		type AssetKind = AssetId;
+		fn quote_price_tokens_for_exact_tokens(
+			asset1: Self::AssetKind,
+			asset2: Self::AssetKind,
+			amount: Self::Balance,
+			include_fee: bool,
+		) -> Option<Self::Balance> {
+			SWAP.with(|b| b.borrow().contains_key(&(asset1, asset2))).then(|| {
+				let impact = if include_fee { PRICE_IMPACT.with(|i| *i.borrow()) } else { 0 };
+				amount + impact
+			})
+		}
+		fn quote_price_exact_tokens_for_tokens(
+			asset1: Self::AssetKind,
+			asset2: Self::AssetKind,
+			amount: Self::Balance,
+			include_fee: bool,
+		) -> Option<Self::Balance> {
+			SWAP.with(|b| b.borrow().contains_key(&(asset1, asset2))).then(|| {
+				let impact = if include_fee { PRICE_IMPACT.with(|i| *i.borrow()) } else { 0 };
+				amount.saturating_sub(impact)
+			})
+		}
+	}
+
 	pub fn pool_account(asset1: AssetId, asset2: AssetId) -> AccountId {
 		(1000 + asset1 * 10 + asset2 * 100).into()
 	}
@@ -0,0 +1,578 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Multi-hop dry run of XCM programs.
+//!
+//! The [`MultiHopDryRun`] dry-runs a call (or an XCM program) on the origin chain and then
+//! follows every message, sent by the dry run, into the `dry_run_xcm` of the destination chain,
+//! until there are no more messages to follow. Messages, exported over a bridge, are followed
+//! into the bridged network through the bridge hubs, registered with
+//! [`MultiHopDryRun::with_bridge`].
+//!
+//! Chains are dry-run through their `DryRunApi` and `XcmPaymentApi` implementations, either in the
+//! externalities of the emulated chain ([`EmulatedChain`]), or in any other externalities, e.g.
+//! loaded from the live chain snapshot with `remote-externalities` ([`SnapshotChain`]). All
+//! changes, made by the dry run, are reverted, so the state of chains is never modified.
+
+use crate::{
+	BridgeMessage, BridgeMessageHandler, Chain, PhantomData, RefCell, TestExt, TestExternalities,
+	VecDeque,
+};
+
+use codec::Decode;
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	storage::{with_transaction_unchecked, TransactionOutcome},
+	traits::{Get, OriginTrait},
+};
+use xcm::{
+	latest::prelude::*, VersionedAssetId, VersionedAssets, VersionedInteriorLocation,
+	VersionedLocation, VersionedXcm,
+};
+use xcm_runtime_apis::{
+	dry_run::{runtime_decl_for_dry_run_api::DryRunApiV2, Error as DryRunApiError},
+	fees::runtime_decl_for_xcm_payment_api::XcmPaymentApiV1,
+};
+
+/// The default maximal number of hops, followed by the [`MultiHopDryRun`].
+pub const DEFAULT_MAX_HOPS: usize = 16;
+
+type BlockOf<R> = <R as frame_system::Config>::Block;
+type RuntimeCallOf<R> = <R as frame_system::Config>::RuntimeCall;
+type RuntimeEventOf<R> = <R as frame_system::Config>::RuntimeEvent;
+type OriginCallerOf<R> = <<R as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+
+/// Error of the multi-hop dry run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DryRunError {
+	/// The dry run API of the chain has returned an error.
+	DryRunApi(DryRunApiError),
+	/// The sent message, or its destination, can't be converted to the latest XCM version.
+	VersionedConversionFailed,
+	/// The universal location of the message destination can't be computed.
+	UnknownDestination(Location),
+	/// The chain has exported a message, but there's no bridge registered for it.
+	NoBridge(InteriorLocation),
+	/// The messages have taken more hops than allowed.
+	TooManyHops,
+}
+
+impl From<DryRunApiError> for DryRunError {
+	fn from(error: DryRunApiError) -> Self {
+		Self::DryRunApi(error)
+	}
+}
+
+/// Messages, sent by the dry-run call or XCM program.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SentMessages {
+	/// Messages, forwarded to other chains of the same consensus system.
+	pub forwarded: Vec<(Location, Xcm<()>)>,
+	/// Messages, exported over the bridge to the universal location of the destination.
+	pub exported: Vec<(InteriorLocation, Xcm<()>)>,
+}
+
+/// Effects of the call, dry-run on a single chain.
+#[derive(Debug)]
+pub struct CallEffects {
+	/// The result of the call dispatch.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// Messages, sent by the call.
+	pub sent: SentMessages,
+}
+
+/// Effects of the XCM program, dry-run on a single chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct XcmEffects {
+	/// The outcome of the program execution.
+	pub outcome: Outcome,
+	/// Messages, sent by the program.
+	pub sent: SentMessages,
+}
+
+/// A chain that may be dry-run by the [`MultiHopDryRun`].
+pub trait DryRunChain {
+	/// Returns the universal location of the chain.
+	fn universal_location(&self) -> InteriorLocation;
+
+	/// Dry-runs the `message`, received from the `origin`.
+	fn dry_run_xcm(&self, origin: Location, message: Xcm<()>) -> Result<XcmEffects, DryRunError>;
+
+	/// Returns fees for delivery of the `message` to the `destination`, or `None` if the fees
+	/// can't be computed.
+	fn delivery_fees(&self, destination: Location, message: Xcm<()>) -> Option<Assets>;
+
+	/// Returns fees for execution of the `message`, paid in the `asset`, or `None` if the fees
+	/// can't be computed.
+	fn execution_fees(&self, message: Xcm<()>, asset: AssetId) -> Option<u128>;
+}
+
+/// A chain that may also dry-run calls, so that the [`MultiHopDryRun`] may start there.
+pub trait CallDryRunChain: DryRunChain {
+	type OriginCaller;
+	type RuntimeCall;
+
+	/// Dry-runs the `call`, dispatched by the `origin`.
+	fn dry_run_call(
+		&self,
+		origin: Self::OriginCaller,
+		call: Self::RuntimeCall,
+	) -> Result<CallEffects, DryRunError>;
+}
+
+/// Externalities, where the chain is dry-run.
+pub trait DryRunExternalities {
+	fn execute_with<R>(&self, execute: impl FnOnce() -> R) -> R;
+}
+
+/// Externalities of the emulated chain `C`.
+pub struct Emulated<C>(PhantomData<C>);
+
+impl<C: TestExt> DryRunExternalities for Emulated<C> {
+	fn execute_with<R>(&self, execute: impl FnOnce() -> R) -> R {
+		C::ext_wrapper(execute)
+	}
+}
+
+impl DryRunExternalities for RefCell<TestExternalities> {
+	fn execute_with<R>(&self, execute: impl FnOnce() -> R) -> R {
+		self.borrow_mut().execute_with(execute)
+	}
+}
+
+/// Dry-runs the chain with the `Runtime` in the externalities `Ext`.
+///
+/// The `Handler` is used to collect messages, exported over the bridge. It is only needed for
+/// bridge hubs, other chains may use the default `()`.
+pub struct ChainDryRunner<Runtime, Ext, Handler = ()> {
+	ext: Ext,
+	_phantom: PhantomData<(Runtime, Handler)>,
+}
+
+/// Dry runner of the emulated chain `C`.
+pub type EmulatedChain<C, Handler = ()> =
+	ChainDryRunner<<C as Chain>::Runtime, Emulated<C>, Handler>;
+
+/// Dry runner of the chain, loaded into the externalities, e.g. from the live chain snapshot.
+pub type SnapshotChain<Runtime, Handler = ()> =
+	ChainDryRunner<Runtime, RefCell<TestExternalities>, Handler>;
+
+impl<C: Chain, Handler> ChainDryRunner<C::Runtime, Emulated<C>, Handler> {
+	/// Creates dry runner of the emulated chain `C`.
+	pub fn new() -> Self {
+		Self { ext: Emulated(PhantomData), _phantom: PhantomData }
+	}
+}
+
+impl<C: Chain, Handler> Default for ChainDryRunner<C::Runtime, Emulated<C>, Handler> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Runtime, Handler> ChainDryRunner<Runtime, RefCell<TestExternalities>, Handler> {
+	/// Creates dry runner of the chain, loaded into the `ext`.
+	pub fn from_externalities(ext: TestExternalities) -> Self {
+		Self { ext: RefCell::new(ext), _phantom: PhantomData }
+	}
+
+	/// Returns externalities of the chain.
+	pub fn into_externalities(self) -> TestExternalities {
+		self.ext.into_inner()
+	}
+}
+
+impl<Runtime, Ext, Handler> ChainDryRunner<Runtime, Ext, Handler>
+where
+	Ext: DryRunExternalities,
+	Handler: BridgeMessageHandler,
+{
+	/// Executes the dry run and returns its result, together with exported messages. All storage
+	/// changes are reverted.
+	fn dry_run<R>(
+		&self,
+		dry_run: impl FnOnce() -> Result<R, DryRunError>,
+	) -> Result<(R, Vec<(InteriorLocation, Xcm<()>)>), DryRunError> {
+		self.ext.execute_with(|| {
+			with_transaction_unchecked(|| {
+				let exported_before = Handler::get_source_outbound_messages();
+				let result = dry_run().and_then(|result| {
+					let exported = Handler::get_source_outbound_messages()
+						.into_iter()
+						.filter(|message| {
+							!exported_before.iter().any(|before| {
+								before.lane_id == message.lane_id && before.nonce == message.nonce
+							})
+						})
+						.map(decode_exported_message)
+						.collect::<Result<Vec<_>, _>>()?;
+					Ok((result, exported))
+				});
+				TransactionOutcome::Rollback(result)
+			})
+		})
+	}
+}
+
+impl<Runtime, Ext, Handler> DryRunChain for ChainDryRunner<Runtime, Ext, Handler>
+where
+	Runtime: pallet_xcm::Config
+		+ DryRunApiV2<
+			BlockOf<Runtime>,
+			RuntimeCallOf<Runtime>,
+			RuntimeEventOf<Runtime>,
+			OriginCallerOf<Runtime>,
+		> + XcmPaymentApiV1<BlockOf<Runtime>>,
+	Ext: DryRunExternalities,
+	Handler: BridgeMessageHandler,
+{
+	fn universal_location(&self) -> InteriorLocation {
+		self.ext
+			.execute_with(|| <Runtime as pallet_xcm::Config>::UniversalLocation::get())
+	}
+
+	fn dry_run_xcm(&self, origin: Location, message: Xcm<()>) -> Result<XcmEffects, DryRunError> {
+		let (effects, exported) = self.dry_run(|| {
+			Runtime::dry_run_xcm(
+				origin.clone().into(),
+				VersionedXcm::from(message.into::<RuntimeCallOf<Runtime>>()),
+			)
+			.map_err(Into::into)
+		})?;
+		if let Some(trace) = effects.render_trace() {
+			log::debug!(
+				target: "xcm::emulator::dry_run",
+				"Execution trace of the message from {:?}:\n{}",
+				origin,
+				trace,
+			);
+		}
+		let forwarded = convert_forwarded_messages(effects.forwarded_xcms)?;
+		Ok(XcmEffects {
+			outcome: effects.execution_result,
+			sent: SentMessages { forwarded, exported },
+		})
+	}
+
+	fn delivery_fees(&self, destination: Location, message: Xcm<()>) -> Option<Assets> {
+		self.ext.execute_with(|| {
+			Runtime::query_delivery_fees(destination.into(), VersionedXcm::from(message))
+				.ok()
+				.and_then(|fees: VersionedAssets| fees.try_into().ok())
+		})
+	}
+
+	fn execution_fees(&self, message: Xcm<()>, asset: AssetId) -> Option<u128> {
+		self.ext.execute_with(|| {
+			Runtime::query_xcm_weight(VersionedXcm::from(message))
+				.and_then(|weight| {
+					Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::from(asset))
+				})
+				.ok()
+		})
+	}
+}
+
+impl<Runtime, Ext, Handler> CallDryRunChain for ChainDryRunner<Runtime, Ext, Handler>
+where
+	Self: DryRunChain,
+	Runtime: frame_system::Config
+		+ DryRunApiV2<
+			BlockOf<Runtime>,
+			RuntimeCallOf<Runtime>,
+			RuntimeEventOf<Runtime>,
+			OriginCallerOf<Runtime>,
+		>,
+	Ext: DryRunExternalities,
+	Handler: BridgeMessageHandler,
+{
+	type OriginCaller = OriginCallerOf<Runtime>;
+	type RuntimeCall = RuntimeCallOf<Runtime>;
+
+	fn dry_run_call(
+		&self,
+		origin: Self::OriginCaller,
+		call: Self::RuntimeCall,
+	) -> Result<CallEffects, DryRunError> {
+		let (effects, exported) =
+			self.dry_run(|| Runtime::dry_run_call(origin, call).map_err(Into::into))?;
+		let forwarded = convert_forwarded_messages(effects.forwarded_xcms)?;
+		Ok(CallEffects {
+			execution_result: effects.execution_result,
+			sent: SentMessages { forwarded, exported },
+		})
+	}
+}
+
+/// A single hop of the message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hop {
+	/// Universal location of the chain, that has sent the message. For messages, exported over
+	/// the bridge, this is the bridge hub, that has dispatched the message in the bridged network.
+	pub sender: InteriorLocation,
+	/// Universal location of the chain, that has received the message.
+	pub destination: InteriorLocation,
+	/// The message, as it has been received by the destination.
+	pub message: Xcm<()>,
+	/// Fees for delivery of the message, paid on the sender. `None` if the fees can't be
+	/// computed, e.g. when the sender chain is not registered.
+	pub delivery_fees: Option<Assets>,
+	/// The outcome of the message execution. `None` if the destination chain is not registered.
+	pub outcome: Option<Outcome>,
+	/// Fees for execution of the message, paid on the destination in the asset, used by the
+	/// first `BuyExecution` or `PayFees` instruction of the message. `None` if the message
+	/// doesn't pay for its execution or if the destination chain is not registered.
+	pub execution_fees: Option<(AssetId, u128)>,
+}
+
+/// Result of the multi-hop dry run.
+#[derive(Debug)]
+pub struct MultiHopDryRunEffects<ExecutionResult> {
+	/// The result of the call or program execution on the origin chain.
+	pub execution_result: ExecutionResult,
+	/// All hops of the sent messages, in the order they have been dry-run.
+	pub hops: Vec<Hop>,
+}
+
+struct BridgeTarget {
+	target: InteriorLocation,
+	instance: Option<InteriorLocation>,
+}
+
+/// Dry-runs the call or XCM program and follows all sent messages through registered chains.
+pub struct MultiHopDryRun<'a> {
+	chains: Vec<(InteriorLocation, &'a dyn DryRunChain)>,
+	bridges: Vec<(InteriorLocation, BridgeTarget)>,
+	max_hops: usize,
+}
+
+impl Default for MultiHopDryRun<'_> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<'a> MultiHopDryRun<'a> {
+	/// Creates the dry run without any registered chains and bridges.
+	pub fn new() -> Self {
+		Self { chains: Vec::new(), bridges: Vec::new(), max_hops: DEFAULT_MAX_HOPS }
+	}
+
+	/// Registers the chain, so that messages to this chain are dry-run.
+	pub fn with_chain(mut self, chain: &'a dyn DryRunChain) -> Self {
+		self.chains.push((chain.universal_location(), chain));
+		self
+	}
+
+	/// Registers the bridge between bridge hubs at `source` and `target` universal locations.
+	///
+	/// Messages, exported by the `source`, are dispatched by the `target` to their destinations,
+	/// the same way the `BridgeBlobDispatcher` does it. The `target_instance` is the bridge
+	/// instance, which is prepended to dispatched messages as `DescendOrigin`.
+	pub fn with_bridge(
+		mut self,
+		source: InteriorLocation,
+		target: InteriorLocation,
+		target_instance: Option<InteriorLocation>,
+	) -> Self {
+		self.bridges.push((source, BridgeTarget { target, instance: target_instance }));
+		self
+	}
+
+	/// Sets the maximal number of followed hops.
+	pub fn with_max_hops(mut self, max_hops: usize) -> Self {
+		self.max_hops = max_hops;
+		self
+	}
+
+	/// Dry-runs the `call` on the `chain` and follows all messages, sent by the call.
+	pub fn dry_run_call<C: CallDryRunChain>(
+		&self,
+		chain: &C,
+		origin: C::OriginCaller,
+		call: C::RuntimeCall,
+	) -> Result<MultiHopDryRunEffects<DispatchResultWithPostInfo>, DryRunError> {
+		let effects = chain.dry_run_call(origin, call)?;
+		let hops = self.follow(chain, effects.sent)?;
+		Ok(MultiHopDryRunEffects { execution_result: effects.execution_result, hops })
+	}
+
+	/// Dry-runs the `message`, received from the `origin`, on the `chain` and follows all
+	/// messages, sent by the program.
+	pub fn dry_run_xcm(
+		&self,
+		chain: &dyn DryRunChain,
+		origin: Location,
+		message: Xcm<()>,
+	) -> Result<MultiHopDryRunEffects<Outcome>, DryRunError> {
+		let effects = chain.dry_run_xcm(origin, message)?;
+		let hops = self.follow(chain, effects.sent)?;
+		Ok(MultiHopDryRunEffects { execution_result: effects.outcome, hops })
+	}
+
+	fn chain(&self, location: &InteriorLocation) -> Option<&'a dyn DryRunChain> {
+		self.chains.iter().find(|(l, _)| l == location).map(|(_, chain)| *chain)
+	}
+
+	fn follow(
+		&self,
+		origin: &dyn DryRunChain,
+		sent: SentMessages,
+	) -> Result<Vec<Hop>, DryRunError> {
+		let mut hops = Vec::new();
+		let mut pending = VecDeque::new();
+		self.enqueue(&mut pending, origin.universal_location(), Some(origin), sent)?;
+
+		while let Some(mut hop) = pending.pop_front() {
+			if hops.len() >= self.max_hops {
+				return Err(DryRunError::TooManyHops)
+			}
+
+			if let Some(chain) = self.chain(&hop.destination) {
+				hop.execution_fees = fee_asset(&hop.message).and_then(|asset| {
+					let fees = chain.execution_fees(hop.message.clone(), asset.clone())?;
+					Some((asset, fees))
+				});
+				let origin = hop.sender.clone().relative_to(&hop.destination);
+				let effects = chain.dry_run_xcm(origin, hop.message.clone())?;
+				hop.outcome = Some(effects.outcome);
+				self.enqueue(&mut pending, hop.destination.clone(), Some(chain), effects.sent)?;
+			}
+			hops.push(hop);
+		}
+
+		Ok(hops)
+	}
+
+	/// Adds messages, sent by the chain at the `sender` universal location, to the `pending` hops.
+	fn enqueue(
+		&self,
+		pending: &mut VecDeque<Hop>,
+		sender: InteriorLocation,
+		sender_chain: Option<&dyn DryRunChain>,
+		sent: SentMessages,
+	) -> Result<(), DryRunError> {
+		for (destination, message) in sent.forwarded {
+			let universal_destination = universal_destination(&sender, &destination)?;
+			let delivery_fees =
+				sender_chain.and_then(|chain| chain.delivery_fees(destination, message.clone()));
+			pending.push_back(Hop::new(
+				sender.clone(),
+				universal_destination,
+				message,
+				delivery_fees,
+			));
+		}
+
+		for (universal_destination, mut message) in sent.exported {
+			let BridgeTarget { target, instance } = self
+				.bridges
+				.iter()
+				.find(|(source, _)| *source == sender)
+				.map(|(_, bridge)| bridge)
+				.ok_or_else(|| DryRunError::NoBridge(sender.clone()))?;
+			if let Some(instance) = instance {
+				message.0.insert(0, DescendOrigin(instance.clone()));
+			}
+			let destination = universal_destination.clone().relative_to(target);
+			let delivery_fees = self
+				.chain(target)
+				.and_then(|chain| chain.delivery_fees(destination, message.clone()));
+			pending.push_back(Hop::new(
+				target.clone(),
+				universal_destination,
+				message,
+				delivery_fees,
+			));
+		}
+
+		Ok(())
+	}
+}
+
+impl Hop {
+	fn new(
+		sender: InteriorLocation,
+		destination: InteriorLocation,
+		message: Xcm<()>,
+		delivery_fees: Option<Assets>,
+	) -> Self {
+		Self { sender, destination, message, delivery_fees, outcome: None, execution_fees: None }
+	}
+}
+
+/// Returns universal location of the `destination`, as it is seen by the `sender`.
+fn universal_destination(
+	sender: &InteriorLocation,
+	destination: &Location,
+) -> Result<InteriorLocation, DryRunError> {
+	match destination.unpack() {
+		(_, [GlobalConsensus(_), ..]) => Ok(destination.interior().clone()),
+		_ => sender
+			.clone()
+			.within_global(destination.clone())
+			.map_err(|()| DryRunError::UnknownDestination(destination.clone())),
+	}
+}
+
+/// Returns the asset, used to pay for execution of the `message`.
+fn fee_asset(message: &Xcm<()>) -> Option<AssetId> {
+	message.0.iter().find_map(|instruction| match instruction {
+		BuyExecution { fees, .. } => Some(fees.id.clone()),
+		PayFees { asset } => Some(asset.id.clone()),
+		_ => None,
+	})
+}
+
+/// Converts messages, forwarded by the dry run, to the latest XCM version.
+///
+/// Routers of the same runtime may share the same message queue (e.g. bridge routers are using
+/// the XCMP queue to reach the bridge hub), so the same message may be reported more than once.
+/// Such duplicates are removed.
+fn convert_forwarded_messages(
+	forwarded: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+) -> Result<Vec<(Location, Xcm<()>)>, DryRunError> {
+	let mut converted = Vec::new();
+	for (destination, messages) in forwarded {
+		let destination: Location =
+			destination.try_into().map_err(|()| DryRunError::VersionedConversionFailed)?;
+		for message in messages {
+			let message =
+				message.try_into().map_err(|()| DryRunError::VersionedConversionFailed)?;
+			let forwarded = (destination.clone(), message);
+			if !converted.contains(&forwarded) {
+				converted.push(forwarded);
+			}
+		}
+	}
+	Ok(converted)
+}
+
+/// Decodes the message, exported over the bridge. The payload is the blob, produced by the
+/// `HaulBlobExporter`, i.e. the encoded universal destination and the message.
+fn decode_exported_message(
+	message: BridgeMessage,
+) -> Result<(InteriorLocation, Xcm<()>), DryRunError> {
+	let (universal_destination, message) =
+		<(VersionedInteriorLocation, VersionedXcm<()>)>::decode(&mut &message.payload[..])
+			.map_err(|_| DryRunError::VersionedConversionFailed)?;
+	Ok((
+		universal_destination
+			.try_into()
+			.map_err(|()| DryRunError::VersionedConversionFailed)?,
+		message.try_into().map_err(|()| DryRunError::VersionedConversionFailed)?,
+	))
+}
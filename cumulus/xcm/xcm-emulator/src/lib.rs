@@ -16,6 +16,8 @@
 
 extern crate alloc;
 
+pub mod dry_run;
+
 pub use array_bytes;
 pub use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
 pub use log;
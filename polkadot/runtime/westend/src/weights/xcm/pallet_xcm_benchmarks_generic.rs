@@ -337,4 +337,17 @@ impl<T: frame_system::Config> WeightInfo<T> {
 		// Minimum execution time: 2_696_000 picoseconds.
 		Weight::from_parts(2_816_000, 0)
 	}
+	/// Not generated by the benchmark CLI yet: estimated as `buy_execution`, because both
+	/// instructions buy weight using the same `UsingComponents` trader, which doesn't access
+	/// the storage.
+	/// Must be regenerated with the `pay_fees` benchmark.
+	pub(crate) fn pay_fees() -> Weight {
+		Self::buy_execution()
+	}
+	/// Not generated by the benchmark CLI yet: estimated as `set_fees_mode`, because both
+	/// instructions only update the executor state.
+	/// Must be regenerated with the `asset_claimer` benchmark.
+	pub(crate) fn asset_claimer() -> Weight {
+		Self::set_fees_mode()
+	}
 }
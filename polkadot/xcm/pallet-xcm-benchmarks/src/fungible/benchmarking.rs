@@ -22,6 +22,7 @@ use frame_support::{
 	pallet_prelude::Get,
 	traits::fungible::{Inspect, Mutate},
 	weights::Weight,
+	BoundedVec,
 };
 use sp_runtime::traits::{Bounded, Zero};
 use xcm::latest::{prelude::*, MAX_ITEMS_IN_ASSETS};
@@ -299,6 +300,59 @@ benchmarks_instance_pallet! {
 		}
 	}
 
+	initiate_transfer {
+		let (sender_account, sender_location) = account_and_location::<T>(1);
+		let asset = T::get_asset();
+		let dest_location = T::valid_destination()?;
+		let dest_account = T::AccountIdConverter::convert_location(&dest_location).unwrap();
+		assert!(T::TransactAsset::balance(&dest_account).is_zero());
+
+		let (expected_fees_mode, expected_assets_in_holding) = T::DeliveryHelper::ensure_successful_delivery(
+			&sender_location,
+			&dest_location,
+			FeeReason::InitiateTransfer,
+		);
+		let sender_account_balance_before = T::TransactAsset::balance(&sender_account);
+
+		// generate holding and add possible required fees
+		let mut holding = if let Some(expected_assets_in_holding) = expected_assets_in_holding {
+			let mut holding = T::worst_case_holding(1 + expected_assets_in_holding.len() as u32);
+			for a in expected_assets_in_holding.into_inner() {
+				holding.push(a);
+			}
+			holding
+		} else {
+			T::worst_case_holding(1)
+		};
+
+		// Add our asset to the holding.
+		holding.push(asset.clone());
+
+		let mut executor = new_executor::<T>(sender_location);
+		executor.set_holding(holding.into());
+		if let Some(expected_fees_mode) = expected_fees_mode {
+			executor.set_fees_mode(expected_fees_mode);
+		}
+
+		let instruction = Instruction::<XcmCallOf<T>>::InitiateTransfer {
+			destination: dest_location,
+			// Worst case is reserve-depositing the remote fees, since it writes into storage.
+			remote_fees: Some(AssetTransferFilter::ReserveDeposit(asset.into())),
+			// Worst case is reanchoring the origin.
+			preserve_origin: true,
+			assets: BoundedVec::new(),
+			remote_xcm: Xcm::new(),
+		};
+		let xcm = Xcm(vec![instruction]);
+	}: {
+		executor.bench_process(xcm)?;
+	} verify {
+		// Check we charged the delivery fees
+		assert!(T::TransactAsset::balance(&sender_account) <= sender_account_balance_before);
+		// dest should have received the remote fees.
+		assert!(!T::TransactAsset::balance(&dest_account).is_zero());
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::fungible::mock::new_test_ext(),
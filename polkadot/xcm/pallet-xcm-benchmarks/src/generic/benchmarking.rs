@@ -19,7 +19,7 @@ use crate::{account_and_location, new_executor, EnsureDelivery, XcmCallOf};
 use alloc::{vec, vec::Vec};
 use codec::Encode;
 use frame_benchmarking::{benchmarks, BenchmarkError};
-use frame_support::{dispatch::GetDispatchInfo, traits::fungible::Inspect};
+use frame_support::{dispatch::GetDispatchInfo, traits::fungible::Inspect, BoundedVec};
 use xcm::{
 	latest::{prelude::*, MaxDispatchErrorLen, MaybeErrorCode, Weight, MAX_ITEMS_IN_ASSETS},
 	DoubleEncoded,
@@ -98,6 +98,31 @@ benchmarks! {
 
 	}
 
+	pay_fees {
+		let fee_asset = T::worst_case_for_trader()?;
+		let mut holding = T::worst_case_holding(0);
+		holding.push(fee_asset.clone());
+
+		let mut executor = new_executor::<T>(Default::default());
+		executor.set_holding(holding.into());
+		// Set some weight to be paid for.
+		executor.set_message_weight(Weight::from_parts(100_000_000, 100_000));
+		let fee_amount_in_holding =
+			executor.holding().fungible.get(&fee_asset.id).copied().unwrap_or_default();
+
+		let instruction = Instruction::<XcmCallOf<T>>::PayFees { asset: fee_asset.clone() };
+
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.bench_process(xcm)?;
+	} verify {
+		// the fee asset has been moved from the holding register to pay for the message weight
+		assert!(
+			executor.holding().fungible.get(&fee_asset.id).copied().unwrap_or_default() <
+				fee_amount_in_holding
+		);
+	}
+
 	query_response {
 		let mut executor = new_executor::<T>(Default::default());
 		let (query_id, response) = T::worst_case_response();
@@ -608,6 +633,22 @@ benchmarks! {
 		assert_eq!(executor.fees_mode(), &FeesMode { jit_withdraw: true });
 	}
 
+	asset_claimer {
+		let mut executor = new_executor::<T>(Default::default());
+		let (_, sender_location) = account_and_location::<T>(1);
+
+		let instruction = Instruction::SetHints {
+			hints: BoundedVec::<Hint, HintNumVariants>::truncate_from(vec![AssetClaimer {
+				location: sender_location.clone(),
+			}]),
+		};
+		let xcm = Xcm(vec![instruction]);
+	}: {
+		executor.bench_process(xcm)?;
+	} verify {
+		assert_eq!(executor.asset_claimer(), &Some(sender_location));
+	}
+
 	lock_asset {
 		let (unlocker, owner, asset) = T::unlockable_asset()?;
 
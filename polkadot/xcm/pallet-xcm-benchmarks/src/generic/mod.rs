@@ -77,6 +77,15 @@ pub mod pallet {
 		/// `refund_surplus`.
 		fn fee_asset() -> Result<Asset, BenchmarkError>;
 
+		/// Asset used to pay for fees in the worst case for the configured `Trader`, e.g. an asset
+		/// that the trader has to swap before buying weight. It is added to the holding register
+		/// in the `pay_fees` benchmark.
+		///
+		/// By default returns the `fee_asset`.
+		fn worst_case_for_trader() -> Result<Asset, BenchmarkError> {
+			Self::fee_asset()
+		}
+
 		/// Return an unlocker, owner and assets that can be locked and unlocked.
 		fn unlockable_asset() -> Result<(Location, Location, Asset), BenchmarkError>;
 
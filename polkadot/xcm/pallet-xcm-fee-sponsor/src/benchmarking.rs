@@ -0,0 +1,171 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use alloc::{vec, vec::Vec};
+use frame_benchmarking::v2::*;
+use xcm::latest::prelude::*;
+
+fn sponsored_location() -> Location {
+	Location::new(1, [Parachain(1000)])
+}
+
+fn sponsored_messages() -> SponsoredMessages {
+	let assets: Vec<_> = (0..MAX_SPONSORED_ASSETS)
+		.map(|index| AssetId(Location::new(1, [Parachain(1000), GeneralIndex(index.into())])))
+		.collect();
+	SponsoredMessages::WithAssets(assets.try_into().expect("assets are bounded; qed"))
+}
+
+fn sponsorship<T: Config>(sponsor: T::AccountId, budget: BalanceOf<T>) -> SponsorshipOf<T> {
+	Sponsorship {
+		sponsor,
+		messages: sponsored_messages(),
+		budget,
+		deposit: T::SponsorshipDeposit::get(),
+	}
+}
+
+/// Funds the `who` with the `amount` on top of the minimum balance.
+fn fund<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) {
+	T::Currency::set_balance(who, T::Currency::minimum_balance().saturating_add(amount));
+}
+
+/// Fills the sponsorships of the sponsored location with other sponsors, leaving one free slot,
+/// that may be taken by the `sponsor`.
+fn fill_sponsorships<T: Config>(sponsor: Option<T::AccountId>) {
+	let budget = 1u32.into();
+	let sponsorships: Vec<_> = (1..T::MaxSponsorships::get())
+		.map(|index| sponsorship::<T>(account("sponsor", index, 0), budget))
+		.chain(sponsor.map(|sponsor| sponsorship::<T>(sponsor, budget)))
+		.collect();
+	Sponsorships::<T>::insert(
+		sponsored_location(),
+		BoundedVec::try_from(sponsorships).expect("at most MaxSponsorships; qed"),
+	);
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_sponsorship() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SponsorOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let sponsor = T::SponsorOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T>(&sponsor, T::SponsorshipDeposit::get());
+		// worst case: the new sponsorship is pushed after all other sponsorships
+		fill_sponsorships::<T>(None);
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(sponsored_location().into()),
+			sponsored_messages(),
+			1u32.into(),
+		);
+
+		assert_eq!(
+			Sponsorships::<T>::get(sponsored_location()).map(|s| s.len()),
+			Some(T::MaxSponsorships::get() as usize)
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_sponsorship() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SponsorOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let sponsor = T::SponsorOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T>(&sponsor, T::SponsorshipDeposit::get());
+		T::Currency::hold(
+			&HoldReason::SponsorshipDeposit.into(),
+			&sponsor,
+			T::SponsorshipDeposit::get(),
+		)?;
+		// worst case: the last of all sponsorships is removed
+		fill_sponsorships::<T>(Some(sponsor.clone()));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(sponsored_location().into()));
+
+		assert!(Sponsorships::<T>::get(sponsored_location())
+			.unwrap_or_default()
+			.iter()
+			.all(|sponsorship| sponsorship.sponsor != sponsor));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sponsor() {
+		// worst case: sponsorships of the origin and all its ancestors match the message, but
+		// their sponsors can't pay, except for the last sponsorship of the most generic location
+		let mut location = Location::new(
+			1,
+			[
+				Parachain(1000),
+				GeneralIndex(1),
+				GeneralIndex(2),
+				GeneralIndex(3),
+				GeneralIndex(4),
+				GeneralIndex(5),
+				GeneralIndex(6),
+				GeneralIndex(7),
+			],
+		);
+		let origin = location.clone();
+		let message = Xcm::<()>(vec![ClearOrigin]);
+		let weight = Weight::from_parts(1_000_000, 1_000);
+		let fee = T::WeightToFee::weight_to_fee(&weight);
+		let budget = fee.saturating_mul(2u32.into());
+		let mut index = 0;
+		loop {
+			let sponsorships: Vec<_> = (0..T::MaxSponsorships::get())
+				.map(|_| {
+					index += 1;
+					sponsorship::<T>(account("sponsor", index, 0), budget)
+				})
+				.collect();
+			Sponsorships::<T>::insert(
+				&location,
+				BoundedVec::try_from(sponsorships).expect("MaxSponsorships; qed"),
+			);
+			if location.take_last().is_none() {
+				break
+			}
+		}
+		let sponsor = account("sponsor", index, 0);
+		fund::<T>(&sponsor, budget);
+
+		#[block]
+		{
+			let (ticket, _) = Pallet::<T>::sponsor(&origin, message.inner(), weight)
+				.expect("the last sponsorship can pay; qed");
+			Pallet::<T>::charge(ticket, Weight::zero());
+		}
+
+		assert_eq!(
+			Sponsorships::<T>::get(Location::parent())
+				.and_then(|sponsorships| sponsorships.last().map(|s| s.budget)),
+			Some(budget.saturating_sub(fee))
+		);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
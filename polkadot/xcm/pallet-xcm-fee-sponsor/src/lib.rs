@@ -0,0 +1,523 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that sponsors execution of XCM programs.
+//!
+//! The `XcmExecutor` requires every message to pay for its execution, unless the `Barrier` allows
+//! unpaid execution for all messages of its origin. This pallet implements the executor's
+//! [`FeeSponsor`] hook, so that sponsors may pay for execution of selected messages instead:
+//!
+//! - the sponsor (an account, allowed by the `Config::SponsorOrigin`) sets the [`Sponsorship`] of
+//!   the origin location, selecting [`SponsoredMessages`] and limiting the total amount of fees it
+//!   is ready to pay. The `Config::SponsorshipDeposit` is held from the sponsor's account until the
+//!   sponsorship is removed;
+//!
+//! - the matching message, whose weight is not covered by the weight credit, is granted additional
+//!   weight credit, that may be taken by the `TakeWeightCredit` barrier;
+//!
+//! - once the credit is taken and the message is executed, the fee for the sponsored weight, that
+//!   has been used, is withdrawn from the sponsor's account, passed to the `Config::OnChargedFees`
+//!   and deducted from the sponsorship budget.
+//!
+//! Sponsorships are looked up for every message, that is not covered by its weight credit, before
+//! it is checked by the `Barrier`. The [`SponsoredWeigher`] must wrap the `Weigher` of the
+//! executor, so that the weight of the lookup is included in the weight of every message.
+//!
+//! The sponsorship of the location also covers all locations interior to it, so e.g. the
+//! sponsorship of the sibling parachain covers messages of all accounts of that parachain. The
+//! most specific location is checked first and sponsorships of the same location are checked in
+//! the order they have been set.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod weigher;
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::{self, Balanced, Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
+		OnUnbalanced,
+	},
+	weights::WeightToFee,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+use xcm::{
+	latest::{AssetId, Instruction, Location},
+	VersionedLocation,
+};
+use xcm_executor::traits::FeeSponsor;
+
+pub use pallet::*;
+pub use weigher::SponsoredWeigher;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::fee-sponsor";
+
+/// Maximal number of assets in [`SponsoredMessages::WithAssets`].
+pub const MAX_SPONSORED_ASSETS: u32 = 16;
+
+/// Messages of the origin, whose execution is sponsored.
+///
+/// Only top-level instructions of the message are inspected.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum SponsoredMessages {
+	/// All messages.
+	All,
+	/// Messages that don't pay for their execution with `BuyExecution` or `PayFees`.
+	Unpaid,
+	/// Messages that bring assets to the holding register (with `WithdrawAsset`,
+	/// `ReserveAssetDeposited`, `ReceiveTeleportedAsset` or `ClaimAsset`) and all of these assets
+	/// are in the list.
+	WithAssets(BoundedVec<AssetId, ConstU32<MAX_SPONSORED_ASSETS>>),
+}
+
+impl SponsoredMessages {
+	/// Returns true if the message, consisting of `instructions`, is sponsored.
+	pub fn matches<RuntimeCall>(&self, instructions: &[Instruction<RuntimeCall>]) -> bool {
+		match self {
+			SponsoredMessages::All => true,
+			SponsoredMessages::Unpaid => !instructions.iter().any(|instruction| {
+				matches!(
+					instruction,
+					Instruction::BuyExecution { .. } | Instruction::PayFees { .. }
+				)
+			}),
+			SponsoredMessages::WithAssets(sponsored_assets) => {
+				let mut assets = instructions
+					.iter()
+					.filter_map(|instruction| match instruction {
+						Instruction::WithdrawAsset(assets) |
+						Instruction::ReserveAssetDeposited(assets) |
+						Instruction::ReceiveTeleportedAsset(assets) |
+						Instruction::ClaimAsset { assets, .. } => Some(assets.inner()),
+						_ => None,
+					})
+					.flatten()
+					.peekable();
+				assets.peek().is_some() && assets.all(|asset| sponsored_assets.contains(&asset.id))
+			},
+		}
+	}
+}
+
+/// Sponsorship of messages of some origin location.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct Sponsorship<AccountId, Balance> {
+	/// The account that pays for execution.
+	pub sponsor: AccountId,
+	/// Messages that are sponsored.
+	pub messages: SponsoredMessages,
+	/// Remaining amount of fees the sponsor is ready to pay.
+	pub budget: Balance,
+	/// The amount held from the sponsor's account until the sponsorship is removed.
+	pub deposit: Balance,
+}
+
+/// Information, needed to charge the sponsor once the granted weight credit has been taken.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct SponsorshipTicket<AccountId, Balance> {
+	/// The location the sponsorship has been set for.
+	pub location: Location,
+	/// The account that pays for execution.
+	pub sponsor: AccountId,
+	/// Origin of the sponsored message.
+	pub origin: Location,
+	/// The granted weight credit.
+	pub weight: Weight,
+	/// The fee for the granted weight credit.
+	pub fee: Balance,
+}
+
+/// Balance type of the `Config::Currency`.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Imbalance of the `Config::Currency`, created when fees are withdrawn from the sponsor.
+pub type CreditOf<T> =
+	fungible::Credit<<T as frame_system::Config>::AccountId, <T as Config>::Currency>;
+
+/// An alias for the sponsorship, used by the pallet.
+pub type SponsorshipOf<T> = Sponsorship<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// An alias for the sponsorship ticket, used by the pallet.
+pub type SponsorshipTicketOf<T> =
+	SponsorshipTicket<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// Weight functions needed for `pallet_xcm_fee_sponsor`.
+pub trait WeightInfo {
+	fn set_sponsorship() -> Weight;
+	fn remove_sponsorship() -> Weight;
+	fn sponsor() -> Weight;
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_sponsorship() -> Weight {
+		Weight::from_parts(20_000_000, 3_600)
+	}
+
+	fn remove_sponsorship() -> Weight {
+		Weight::from_parts(20_000_000, 3_600)
+	}
+
+	fn sponsor() -> Weight {
+		Weight::from_parts(100_000_000, 40_000)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency, used to pay for sponsored execution and to hold sponsorship deposits.
+		type Currency: Balanced<Self::AccountId>
+			+ Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Converts the sponsored weight to the fee, paid by the sponsor.
+		type WeightToFee: WeightToFee<Balance = BalanceOf<Self>>;
+
+		/// Handler of fees, paid by sponsors. Use `()` to burn them.
+		type OnChargedFees: OnUnbalanced<CreditOf<Self>>;
+
+		/// The origin that is allowed to sponsor execution. The returned account pays the fees.
+		///
+		/// Use `EnsureSigned` to let any account sponsor execution, or e.g.
+		/// `EnsureRootWithSuccess` to let the governance sponsor it from the treasury account.
+		type SponsorOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// The amount held from the sponsor's account for every sponsorship, until it is removed.
+		///
+		/// Sponsorships of a location are limited by the `MaxSponsorships`, so the deposit must be
+		/// high enough to prevent anyone from taking all of them.
+		#[pallet::constant]
+		type SponsorshipDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximal number of sponsorships of the single location.
+		///
+		/// Sponsorships of the origin and all its ancestors are inspected before the message,
+		/// that is not paid by its origin, is rejected by the `Barrier`, so it must be small. The
+		/// weight of the inspection is `WeightInfo::sponsor`, that is added to the weight of
+		/// every message by the [`SponsoredWeigher`].
+		#[pallet::constant]
+		type MaxSponsorships: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are held as the deposit of a sponsorship.
+		#[codec(index = 0)]
+		SponsorshipDeposit,
+	}
+
+	/// Sponsorships of origin locations.
+	#[pallet::storage]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, BoundedVec<SponsorshipOf<T>, T::MaxSponsorships>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The sponsorship of the location has been set.
+		SponsorshipSet {
+			location: Location,
+			sponsor: T::AccountId,
+			messages: SponsoredMessages,
+			budget: BalanceOf<T>,
+		},
+		/// The sponsorship of the location has been removed by the sponsor.
+		SponsorshipRemoved { location: Location, sponsor: T::AccountId },
+		/// The sponsorship of the location has spent all its budget and has been removed.
+		SponsorshipExhausted { location: Location, sponsor: T::AccountId },
+		/// Execution of the message has been paid by the sponsor.
+		ExecutionSponsored {
+			origin: Location,
+			location: Location,
+			sponsor: T::AccountId,
+			weight: Weight,
+			fee: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The version of the `Versioned` value used is not able to be interpreted.
+		BadVersion,
+		/// The location already has the maximal number of sponsorships.
+		TooManySponsorships,
+		/// The location is not sponsored by the caller.
+		NotSponsored,
+		/// The sponsorship budget must not be zero.
+		ZeroBudget,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sponsor execution of messages of the location and all locations interior to it.
+		///
+		/// - `origin`: Must be an origin specified by SponsorOrigin. The returned account pays the
+		///   fees.
+		/// - `location`: The origin location of sponsored messages.
+		/// - `messages`: Messages that are sponsored.
+		/// - `budget`: Maximal amount of fees the sponsor is ready to pay. Must not be zero.
+		///
+		/// The previous sponsorship of the location by the same sponsor is replaced. Otherwise,
+		/// the `SponsorshipDeposit` is held from the sponsor's account.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_sponsorship())]
+		pub fn set_sponsorship(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+			messages: SponsoredMessages,
+			budget: BalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+			let location = Location::try_from(*location).map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!budget.is_zero(), Error::<T>::ZeroBudget);
+
+			Sponsorships::<T>::try_mutate(&location, |sponsorships| {
+				let sponsorships = sponsorships.get_or_insert_with(Default::default);
+				match sponsorships.iter_mut().find(|existing| existing.sponsor == sponsor) {
+					Some(existing) => {
+						existing.messages = messages.clone();
+						existing.budget = budget;
+					},
+					None => {
+						let deposit = T::SponsorshipDeposit::get();
+						let sponsorship = Sponsorship {
+							sponsor: sponsor.clone(),
+							messages: messages.clone(),
+							budget,
+							deposit,
+						};
+						sponsorships
+							.try_push(sponsorship)
+							.map_err(|_| Error::<T>::TooManySponsorships)?;
+						T::Currency::hold(
+							&HoldReason::SponsorshipDeposit.into(),
+							&sponsor,
+							deposit,
+						)?;
+					},
+				}
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::SponsorshipSet { location, sponsor, messages, budget });
+			Ok(())
+		}
+
+		/// Stop sponsoring execution of messages of the location and release the deposit.
+		///
+		/// - `origin`: Must be an origin specified by SponsorOrigin.
+		/// - `location`: The origin location of sponsored messages.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_sponsorship())]
+		pub fn remove_sponsorship(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+		) -> DispatchResult {
+			let sponsor = T::SponsorOrigin::ensure_origin(origin)?;
+			let location = Location::try_from(*location).map_err(|()| Error::<T>::BadVersion)?;
+
+			ensure!(Self::remove(&location, &sponsor), Error::<T>::NotSponsored);
+
+			Self::deposit_event(Event::SponsorshipRemoved { location, sponsor });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the first sponsorship, that pays for execution of the message with given
+	/// `instructions` and the `fee`, together with the location it has been set for.
+	pub fn find_sponsorship<RuntimeCall>(
+		origin: &Location,
+		instructions: &[Instruction<RuntimeCall>],
+		fee: BalanceOf<T>,
+	) -> Option<(Location, SponsorshipOf<T>)> {
+		let mut location = origin.clone();
+		loop {
+			let sponsorship = Sponsorships::<T>::get(&location)
+				.into_iter()
+				.flatten()
+				.find(|sponsorship| Self::can_pay(sponsorship, instructions, fee));
+			if let Some(sponsorship) = sponsorship {
+				return Some((location, sponsorship))
+			}
+
+			location.take_last()?;
+		}
+	}
+
+	fn can_pay<RuntimeCall>(
+		sponsorship: &SponsorshipOf<T>,
+		instructions: &[Instruction<RuntimeCall>],
+		fee: BalanceOf<T>,
+	) -> bool {
+		sponsorship.budget >= fee &&
+			sponsorship.messages.matches(instructions) &&
+			T::Currency::reducible_balance(
+				&sponsorship.sponsor,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			) >= fee
+	}
+
+	/// Removes the sponsorship of the `location` by the `sponsor` and releases its deposit.
+	/// Returns false if there's no such sponsorship.
+	fn remove(location: &Location, sponsor: &T::AccountId) -> bool {
+		let removed = Sponsorships::<T>::mutate_exists(location, |maybe_sponsorships| {
+			let sponsorships = maybe_sponsorships.as_mut()?;
+			let index =
+				sponsorships.iter().position(|sponsorship| sponsorship.sponsor == *sponsor)?;
+			let removed = sponsorships.remove(index);
+			if sponsorships.is_empty() {
+				*maybe_sponsorships = None;
+			}
+			Some(removed)
+		});
+		let Some(removed) = removed else { return false };
+
+		if let Err(error) = T::Currency::release(
+			&HoldReason::SponsorshipDeposit.into(),
+			sponsor,
+			removed.deposit,
+			Precision::BestEffort,
+		) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to release the deposit of {sponsor:?} for the sponsorship of \
+				{location:?}: {error:?}",
+			);
+		}
+		true
+	}
+}
+
+impl<T: Config> FeeSponsor for Pallet<T> {
+	type Ticket = SponsorshipTicketOf<T>;
+
+	fn sponsor<RuntimeCall>(
+		origin: &Location,
+		instructions: &[Instruction<RuntimeCall>],
+		weight: Weight,
+	) -> Option<(Self::Ticket, Weight)> {
+		let fee = T::WeightToFee::weight_to_fee(&weight);
+		let (location, sponsorship) = Self::find_sponsorship(origin, instructions, fee)?;
+		log::trace!(
+			target: LOG_TARGET,
+			"Sponsoring {weight:?} of the message from {origin:?} by {:?} (sponsorship of \
+			{location:?})",
+			sponsorship.sponsor,
+		);
+
+		let ticket = SponsorshipTicket {
+			location,
+			sponsor: sponsorship.sponsor,
+			origin: origin.clone(),
+			weight,
+			fee,
+		};
+		Some((ticket, weight))
+	}
+
+	fn charge(ticket: Self::Ticket, unused: Weight) {
+		let SponsorshipTicket { location, sponsor, origin, weight, fee } = ticket;
+		// the unused weight is not charged
+		let weight = weight.saturating_sub(unused);
+		let fee = T::WeightToFee::weight_to_fee(&weight).min(fee);
+		if fee.is_zero() {
+			return
+		}
+
+		// the balance has been checked when the ticket has been issued, but the message may have
+		// spent it in the meantime
+		let credit = match T::Currency::withdraw(
+			&sponsor,
+			fee,
+			Precision::Exact,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		) {
+			Ok(credit) => credit,
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to charge {sponsor:?} {fee:?} for sponsored execution of the message \
+					from {origin:?}: {error:?}",
+				);
+				return
+			},
+		};
+		T::OnChargedFees::on_unbalanced(credit);
+
+		let exhausted = Sponsorships::<T>::mutate(&location, |maybe_sponsorships| {
+			let Some(sponsorship) = maybe_sponsorships
+				.as_mut()
+				.and_then(|sponsorships| sponsorships.iter_mut().find(|s| s.sponsor == sponsor))
+			else {
+				return false
+			};
+			sponsorship.budget = sponsorship.budget.saturating_sub(fee);
+			sponsorship.budget.is_zero()
+		});
+
+		Self::deposit_event(Event::ExecutionSponsored {
+			origin,
+			location: location.clone(),
+			sponsor: sponsor.clone(),
+			weight,
+			fee,
+		});
+
+		if exhausted {
+			Self::remove(&location, &sponsor);
+			Self::deposit_event(Event::SponsorshipExhausted { location, sponsor });
+		}
+	}
+}
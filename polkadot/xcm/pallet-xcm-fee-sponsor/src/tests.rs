@@ -0,0 +1,306 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungible::{Inspect, InspectHold},
+};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+
+const SIBLING: u32 = 1000;
+
+fn sibling() -> Location {
+	Location::new(1, [Parachain(SIBLING)])
+}
+
+fn sibling_account() -> Location {
+	Location::new(1, [Parachain(SIBLING), AccountId32 { network: None, id: [1; 32] }])
+}
+
+fn weight() -> Weight {
+	Weight::from_parts(100, 100)
+}
+
+fn set_sponsorship(sponsor: u64, location: Location, messages: SponsoredMessages, budget: u64) {
+	assert_ok!(XcmFeeSponsor::set_sponsorship(
+		RuntimeOrigin::signed(sponsor),
+		Box::new(location.into()),
+		messages,
+		budget,
+	));
+}
+
+fn sponsor(origin: &Location, message: &Xcm<()>) -> Option<SponsorshipTicketOf<Test>> {
+	XcmFeeSponsor::sponsor(origin, message.inner(), weight()).map(|(ticket, granted)| {
+		assert_eq!(granted, weight());
+		ticket
+	})
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::SponsorshipDeposit.into(), &who)
+}
+
+fn unpaid_message() -> Xcm<()> {
+	Xcm(vec![ClearOrigin])
+}
+
+fn with_assets(assets: Vec<AssetId>) -> SponsoredMessages {
+	SponsoredMessages::WithAssets(assets.try_into().unwrap())
+}
+
+#[test]
+fn set_and_remove_sponsorship_works() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 500);
+		System::assert_last_event(
+			Event::SponsorshipSet {
+				location: sibling(),
+				sponsor: ALICE,
+				messages: SponsoredMessages::All,
+				budget: 500,
+			}
+			.into(),
+		);
+
+		// the sponsorship of the same sponsor is replaced
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::Unpaid, 200);
+		set_sponsorship(BOB, sibling(), SponsoredMessages::All, 300);
+		assert_eq!(
+			Sponsorships::<Test>::get(sibling()).unwrap().into_inner(),
+			vec![
+				Sponsorship {
+					sponsor: ALICE,
+					messages: SponsoredMessages::Unpaid,
+					budget: 200,
+					deposit: 5
+				},
+				Sponsorship {
+					sponsor: BOB,
+					messages: SponsoredMessages::All,
+					budget: 300,
+					deposit: 5
+				},
+			]
+		);
+		// the deposit is held only once for the replaced sponsorship
+		assert_eq!(held(ALICE), 5);
+		assert_eq!(held(BOB), 5);
+
+		assert_noop!(
+			XcmFeeSponsor::set_sponsorship(
+				RuntimeOrigin::signed(CHARLIE),
+				Box::new(sibling().into()),
+				SponsoredMessages::All,
+				1,
+			),
+			Error::<Test>::TooManySponsorships
+		);
+		assert_noop!(
+			XcmFeeSponsor::set_sponsorship(
+				RuntimeOrigin::root(),
+				Box::new(sibling().into()),
+				SponsoredMessages::All,
+				1,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmFeeSponsor::set_sponsorship(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling().into()),
+				SponsoredMessages::All,
+				0,
+			),
+			Error::<Test>::ZeroBudget
+		);
+
+		assert_ok!(XcmFeeSponsor::remove_sponsorship(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(sibling().into()),
+		));
+		System::assert_last_event(
+			Event::SponsorshipRemoved { location: sibling(), sponsor: ALICE }.into(),
+		);
+		assert_eq!(held(ALICE), 0);
+		assert_noop!(
+			XcmFeeSponsor::remove_sponsorship(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling().into()),
+			),
+			Error::<Test>::NotSponsored
+		);
+
+		// the storage entry is removed together with the last sponsorship
+		assert_ok!(XcmFeeSponsor::remove_sponsorship(
+			RuntimeOrigin::signed(BOB),
+			Box::new(sibling().into()),
+		));
+		assert!(!Sponsorships::<Test>::contains_key(sibling()));
+	});
+}
+
+#[test]
+fn sponsorship_covers_interior_locations() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 500);
+
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+		assert_eq!(
+			ticket,
+			SponsorshipTicket {
+				location: sibling(),
+				sponsor: ALICE,
+				origin: sibling_account(),
+				weight: weight(),
+				fee: 100,
+			}
+		);
+		assert!(sponsor(&sibling(), &unpaid_message()).is_some());
+
+		// other chains and the parent are not sponsored
+		assert!(sponsor(&Location::new(1, [Parachain(SIBLING + 1)]), &unpaid_message()).is_none());
+		assert!(sponsor(&Location::parent(), &unpaid_message()).is_none());
+
+		// the most specific location is preferred
+		set_sponsorship(BOB, sibling_account(), SponsoredMessages::All, 500);
+		assert_eq!(sponsor(&sibling_account(), &unpaid_message()).unwrap().sponsor, BOB);
+	});
+}
+
+#[test]
+fn sponsored_messages_are_matched() {
+	let dot = AssetId(Location::parent());
+	let usdt = AssetId(Location::new(1, [Parachain(SIBLING), GeneralIndex(1984)]));
+	let paid: Xcm<()> = Xcm(vec![
+		WithdrawAsset((dot.clone(), 100u128).into()),
+		BuyExecution { fees: (dot.clone(), 100u128).into(), weight_limit: Unlimited },
+	]);
+	let with_dot_and_usdt: Xcm<()> = Xcm(vec![
+		ReserveAssetDeposited((dot.clone(), 100u128).into()),
+		ReceiveTeleportedAsset((usdt.clone(), 100u128).into()),
+		ClearOrigin,
+	]);
+
+	assert!(SponsoredMessages::All.matches(paid.inner()));
+	assert!(SponsoredMessages::Unpaid.matches(unpaid_message().inner()));
+	assert!(SponsoredMessages::Unpaid.matches(with_dot_and_usdt.inner()));
+	assert!(!SponsoredMessages::Unpaid.matches(paid.inner()));
+	assert!(!SponsoredMessages::Unpaid
+		.matches(Xcm::<()>(vec![PayFees { asset: (dot.clone(), 1u128).into() }]).inner()));
+
+	assert!(with_assets(vec![dot.clone()]).matches(paid.inner()));
+	assert!(!with_assets(vec![dot.clone()]).matches(with_dot_and_usdt.inner()));
+	assert!(with_assets(vec![dot.clone(), usdt.clone()]).matches(with_dot_and_usdt.inner()));
+	// messages without assets are not matched
+	assert!(!with_assets(vec![dot, usdt]).matches(unpaid_message().inner()));
+}
+
+#[test]
+fn sponsor_is_charged() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 500);
+
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+		XcmFeeSponsor::charge(ticket, Weight::zero());
+
+		assert_eq!(Balances::balance(&ALICE), 895);
+		assert_eq!(Balances::balance(&FeeCollector::get()), 101);
+		assert_eq!(Sponsorships::<Test>::get(sibling()).unwrap()[0].budget, 400);
+		System::assert_last_event(
+			Event::ExecutionSponsored {
+				origin: sibling_account(),
+				location: sibling(),
+				sponsor: ALICE,
+				weight: weight(),
+				fee: 100,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sponsor_is_not_charged_for_unused_weight() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 500);
+
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+		XcmFeeSponsor::charge(ticket, Weight::from_parts(40, 40));
+
+		assert_eq!(Balances::balance(&ALICE), 935);
+		assert_eq!(Sponsorships::<Test>::get(sibling()).unwrap()[0].budget, 440);
+		System::assert_last_event(
+			Event::ExecutionSponsored {
+				origin: sibling_account(),
+				location: sibling(),
+				sponsor: ALICE,
+				weight: Weight::from_parts(60, 60),
+				fee: 60,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn exhausted_sponsorship_is_removed() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 100);
+
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+		XcmFeeSponsor::charge(ticket, Weight::zero());
+
+		assert!(!Sponsorships::<Test>::contains_key(sibling()));
+		assert_eq!(held(ALICE), 0);
+		System::assert_last_event(
+			Event::SponsorshipExhausted { location: sibling(), sponsor: ALICE }.into(),
+		);
+		assert!(sponsor(&sibling_account(), &unpaid_message()).is_none());
+	});
+}
+
+#[test]
+fn sponsorship_that_cannot_pay_is_skipped() {
+	new_test_ext().execute_with(|| {
+		// CHARLIE has enough budget, but not enough balance and ALICE has not enough budget
+		set_sponsorship(CHARLIE, sibling(), SponsoredMessages::All, 500);
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 50);
+		assert!(sponsor(&sibling_account(), &unpaid_message()).is_none());
+
+		// the sponsorship of the ancestor location is used then
+		set_sponsorship(BOB, Location::parent(), SponsoredMessages::All, 500);
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+		assert_eq!((ticket.location, ticket.sponsor), (Location::parent(), BOB));
+	});
+}
+
+#[test]
+fn sponsor_is_not_charged_if_balance_has_been_spent() {
+	new_test_ext().execute_with(|| {
+		set_sponsorship(ALICE, sibling(), SponsoredMessages::All, 500);
+		let ticket = sponsor(&sibling_account(), &unpaid_message()).unwrap();
+
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(ALICE), BOB, 950));
+		XcmFeeSponsor::charge(ticket, Weight::zero());
+
+		assert_eq!(Balances::balance(&ALICE), 45);
+		assert_eq!(Sponsorships::<Test>::get(sibling()).unwrap()[0].budget, 500);
+	});
+}
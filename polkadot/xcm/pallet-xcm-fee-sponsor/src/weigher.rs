@@ -0,0 +1,41 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM weigher wrapper, that accounts for looking up and charging sponsorships of messages.
+
+use crate::{Config, WeightInfo};
+
+use core::marker::PhantomData;
+use xcm::latest::{Instruction, Weight, Xcm};
+use xcm_executor::traits::WeightBounds;
+
+/// Wraps the `Inner` XCM weigher and adds `Config::WeightInfo::sponsor` to the weight of every
+/// message.
+///
+/// Sponsorships are looked up before the `Barrier` is checked, so the lookup is weighed even if
+/// the message is not sponsored. The origin pays for it if the message pays for its execution,
+/// and the sponsor pays for it otherwise.
+pub struct SponsoredWeigher<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, Inner: WeightBounds<Call>, Call> WeightBounds<Call> for SponsoredWeigher<T, Inner> {
+	fn weight(message: &mut Xcm<Call>) -> Result<Weight, ()> {
+		Inner::weight(message).map(|weight| weight.saturating_add(T::WeightInfo::sponsor()))
+	}
+
+	fn instr_weight(instruction: &Instruction<Call>) -> Result<Weight, ()> {
+		Inner::instr_weight(instruction)
+	}
+}
@@ -0,0 +1,99 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use xcm::latest::prelude::*;
+
+fn flow<T: Config>() -> (AssetId, Location, FlowLimitsOf<T>) {
+	let limits = FlowLimits {
+		period: 10u32.into(),
+		max_inbound: 1_000,
+		max_outbound: 1_000,
+		pause_at: Some(Perbill::one()),
+	};
+	(AssetId(Location::parent()), Location::new(1, [Parachain(1000)]), limits)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_flow_limits() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset, location, limits) = flow::<T>();
+		let key = FlowKey::route(asset.clone(), location.clone());
+		// worst case: limits are removed together with usage and pause
+		Limits::<T>::insert(&key, limits);
+		Usage::<T>::insert(&key, FlowUsage::default());
+		Paused::<T>::insert(&key, BlockNumberFor::<T>::zero());
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(asset.into()),
+			Some(Box::new(location.into())),
+			None,
+		);
+
+		assert!(!Limits::<T>::contains_key(&key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reset_flow() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset, location, limits) = flow::<T>();
+		let key = FlowKey::route(asset.clone(), location.clone());
+		Limits::<T>::insert(&key, limits);
+		Usage::<T>::insert(&key, FlowUsage::default());
+		Paused::<T>::insert(&key, BlockNumberFor::<T>::zero());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset.into()), Some(Box::new(location.into())));
+
+		assert!(!Pallet::<T>::is_paused(&key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn limit_transfer() {
+		let (asset, location, limits) = flow::<T>();
+		// worst case: both matching flows are limited and the transfer pauses both of them
+		Limits::<T>::insert(FlowKey::asset(asset.clone()), limits);
+		Limits::<T>::insert(FlowKey::route(asset.clone(), location.clone()), limits);
+
+		#[block]
+		{
+			assert!(Pallet::<T>::ensure_can_flow(
+				FlowDirection::Outbound,
+				&asset,
+				&location,
+				limits.max_outbound,
+			)
+			.is_ok());
+			Pallet::<T>::note_flow(FlowDirection::Outbound, &asset, &location, limits.max_outbound);
+		}
+
+		assert!(Pallet::<T>::is_paused(&FlowKey::asset(asset.clone())));
+		assert!(Pallet::<T>::is_paused(&FlowKey::route(asset, location)));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,463 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that limits amounts of assets, flowing in and out of the chain over XCM.
+//!
+//! The `IsTeleporter`/`IsReserve` filters and asset transactors decide whether the asset may move
+//! between chains, but not how much of it. This pallet, together with the
+//! [`FlowLimitedTransactor`] wrapper, acts as a circuit breaker:
+//!
+//! - the governance sets [`FlowLimits`] for a [`FlowKey`], which is either the asset flowing from
+//!   and to all remote locations, or the asset flowing from and to the single remote location;
+//!
+//! - the transfer that would exceed the inbound or outbound quota of any matching flow within its
+//!   rolling window is rejected;
+//!
+//! - if the limits have the [`FlowLimits::pause_at`] threshold, the transfer that makes the flow
+//!   reach that share of its quota is still accepted, but the flow is paused and all following
+//!   transfers (in both directions) are rejected, until the `Config::AdminOrigin` resets the flow
+//!   with the `reset_flow` call. Flows without the threshold are never paused, they only reject
+//!   transfers until the rolling window moves on.
+//!
+//! Flows without limits are not tracked at all. See [`FlowLimitedTransactor`] and
+//! [`FlowLimitedRouter`] for what is considered an inbound or outbound flow. Checking and noting
+//! flows accesses the storage, so the XCM weigher of the runtime must be wrapped into the
+//! [`FlowLimitedWeigher`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+mod router;
+mod transactor;
+mod weigher;
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, traits::Contains};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AtLeast32BitUnsigned, One, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, Rounding, RuntimeDebug,
+};
+use xcm::{
+	latest::{AssetId, Error as XcmError, InteriorLocation, Location},
+	VersionedAssetId, VersionedLocation,
+};
+
+pub use pallet::*;
+pub use router::FlowLimitedRouter;
+pub use transactor::FlowLimitedTransactor;
+pub use weigher::FlowLimitedWeigher;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "xcm::flow-limits";
+
+/// Direction of the asset flow.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum FlowDirection {
+	/// The asset comes to this chain from the remote location.
+	Inbound,
+	/// The asset leaves this chain for the remote location.
+	Outbound,
+}
+
+/// Identifier of the limited asset flow.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct FlowKey {
+	/// The asset that flows.
+	pub asset: AssetId,
+	/// The remote location, the asset flows from or to. `None` means that the flow aggregates
+	/// transfers from and to all remote locations.
+	pub location: Option<Location>,
+}
+
+impl FlowKey {
+	/// Flow of the `asset` from and to all remote locations.
+	pub fn asset(asset: AssetId) -> Self {
+		FlowKey { asset, location: None }
+	}
+
+	/// Flow of the `asset` from and to the single remote `location`.
+	pub fn route(asset: AssetId, location: Location) -> Self {
+		FlowKey { asset, location: Some(location) }
+	}
+}
+
+/// Limits of the asset flow.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct FlowLimits<BlockNumber> {
+	/// Length of the rolling window in blocks.
+	pub period: BlockNumber,
+	/// Maximal amount of the asset that may come in during the `period`.
+	pub max_inbound: u128,
+	/// Maximal amount of the asset that may leave during the `period`.
+	pub max_outbound: u128,
+	/// Share of the quota of either direction, reaching which pauses the flow until it is reset
+	/// by the `Config::AdminOrigin`. `None` means that the flow is never paused: transfers that
+	/// would exceed the quota are rejected, but the flow recovers as the rolling window moves on.
+	pub pause_at: Option<Perbill>,
+}
+
+impl<BlockNumber> FlowLimits<BlockNumber> {
+	/// Returns the quota for given direction.
+	pub fn max(&self, direction: FlowDirection) -> u128 {
+		match direction {
+			FlowDirection::Inbound => self.max_inbound,
+			FlowDirection::Outbound => self.max_outbound,
+		}
+	}
+
+	/// Returns true if the flow, that has `flown` in given direction, must be paused.
+	pub fn should_pause(&self, direction: FlowDirection, flown: u128) -> bool {
+		self.pause_at.map_or(false, |pause_at| flown >= pause_at * self.max(direction))
+	}
+}
+
+/// Amounts of the asset that have flown in both directions.
+#[derive(
+	Clone, Copy, Decode, Default, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub struct FlowAmounts {
+	/// Amount of the asset that has come in.
+	pub inbound: u128,
+	/// Amount of the asset that has left.
+	pub outbound: u128,
+}
+
+impl FlowAmounts {
+	/// Returns the amount that has flown in given direction.
+	pub fn get(&self, direction: FlowDirection) -> u128 {
+		match direction {
+			FlowDirection::Inbound => self.inbound,
+			FlowDirection::Outbound => self.outbound,
+		}
+	}
+
+	fn get_mut(&mut self, direction: FlowDirection) -> &mut u128 {
+		match direction {
+			FlowDirection::Inbound => &mut self.inbound,
+			FlowDirection::Outbound => &mut self.outbound,
+		}
+	}
+}
+
+/// Flow usage, that is tracked to enforce [`FlowLimits`].
+///
+/// The rolling window is approximated with two fixed windows of the same length: the amount that
+/// has flown during the previous window is weighted by the share of that window, which is still
+/// covered by the rolling window.
+#[derive(
+	Clone, Copy, Decode, Default, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug,
+)]
+pub struct FlowUsage<BlockNumber> {
+	/// The first block of the current fixed window.
+	pub window_start: BlockNumber,
+	/// Amounts that have flown since the `window_start`.
+	pub current: FlowAmounts,
+	/// Amounts that have flown during the previous fixed window.
+	pub previous: FlowAmounts,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> FlowUsage<BlockNumber> {
+	/// Returns the flow usage at given block, forgetting about transfers that are no longer
+	/// covered by the rolling window.
+	pub fn at(self, now: BlockNumber, period: BlockNumber) -> Self {
+		let period = period.max(One::one());
+		let window_start = now.saturating_sub(now % period);
+		let mut usage = self;
+		if usage.window_start != window_start {
+			usage.previous = if usage.window_start.saturating_add(period) == window_start {
+				usage.current
+			} else {
+				FlowAmounts::default()
+			};
+			usage.current = FlowAmounts::default();
+			usage.window_start = window_start;
+		}
+		usage
+	}
+
+	/// Returns the amount that has flown in given direction during the rolling window, ending at
+	/// `now`. The usage must be brought to `now` with [`Self::at`] first.
+	pub fn flown(&self, direction: FlowDirection, now: BlockNumber, period: BlockNumber) -> u128 {
+		let period = period.max(One::one());
+		let elapsed = now.saturating_sub(self.window_start).min(period);
+		let previous = multiply_by_rational_with_rounding(
+			self.previous.get(direction),
+			period.saturating_sub(elapsed).unique_saturated_into(),
+			period.unique_saturated_into(),
+			Rounding::Up,
+		)
+		.unwrap_or(u128::MAX);
+		self.current.get(direction).saturating_add(previous)
+	}
+
+	/// Note that the `amount` has flown in given direction.
+	pub fn note_flow(&mut self, direction: FlowDirection, amount: u128) {
+		let current = self.current.get_mut(direction);
+		*current = current.saturating_add(amount);
+	}
+}
+
+/// An alias for the flow limits, used by the pallet.
+pub type FlowLimitsOf<T> = FlowLimits<BlockNumberFor<T>>;
+
+/// An alias for the flow usage, used by the pallet.
+pub type FlowUsageOf<T> = FlowUsage<BlockNumberFor<T>>;
+
+/// Weight functions needed for `pallet_xcm_flow_limits`.
+pub trait WeightInfo {
+	fn set_flow_limits() -> Weight;
+	fn reset_flow() -> Weight;
+	fn limit_transfer() -> Weight;
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_flow_limits() -> Weight {
+		Weight::from_parts(20_000_000, 3_600)
+	}
+
+	fn reset_flow() -> Weight {
+		Weight::from_parts(20_000_000, 3_600)
+	}
+
+	fn limit_transfer() -> Weight {
+		Weight::from_parts(40_000_000, 7_200)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin that is allowed to set flow limits and to reset (resume) paused flows.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Locations of remote consensus systems, whose asset flows are limited.
+		///
+		/// Usually it is the parent and sibling chains, i.e. every location that may teleport or
+		/// reserve transfer assets to this chain. It must not match local accounts, otherwise
+		/// local deposits are considered outbound transfers.
+		type RemoteLocations: Contains<Location>;
+
+		/// This chain's Universal Location. Used to express assets, withdrawn from the sovereign
+		/// account of this chain at the remote reserve, from the point of view of this chain.
+		type UniversalLocation: Get<InteriorLocation>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Limits of asset flows.
+	#[pallet::storage]
+	pub type Limits<T: Config> = StorageMap<_, Blake2_128Concat, FlowKey, FlowLimitsOf<T>>;
+
+	/// Usage of asset flows that have limits.
+	#[pallet::storage]
+	pub type Usage<T: Config> =
+		StorageMap<_, Blake2_128Concat, FlowKey, FlowUsageOf<T>, ValueQuery>;
+
+	/// Paused asset flows, mapped to the block where they have been paused.
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageMap<_, Blake2_128Concat, FlowKey, BlockNumberFor<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Limits of the asset flow have been set or removed.
+		FlowLimitsSet { key: FlowKey, limits: Option<FlowLimitsOf<T>> },
+		/// The asset flow has reached its pause threshold and has been paused.
+		FlowPaused { key: FlowKey, direction: FlowDirection },
+		/// Usage of the asset flow has been reset and the flow has been resumed.
+		FlowReset { key: FlowKey },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The version of the `Versioned` value used is not able to be interpreted.
+		BadVersion,
+		/// The limits have zero period.
+		InvalidLimits,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set or remove limits of the asset flow.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `asset`: The asset, whose flow is limited.
+		/// - `location`: The remote location, the asset flows from or to. `None` to limit the flow
+		///   from and to all remote locations.
+		/// - `limits`: New limits of the flow. `None` to stop tracking the flow.
+		///
+		/// Changing limits neither resets usage, nor resumes the paused flow.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_flow_limits())]
+		pub fn set_flow_limits(
+			origin: OriginFor<T>,
+			asset: Box<VersionedAssetId>,
+			location: Option<Box<VersionedLocation>>,
+			limits: Option<FlowLimitsOf<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let key = Self::flow_key(*asset, location.map(|location| *location))?;
+
+			match limits {
+				Some(limits) => {
+					ensure!(!limits.period.is_zero(), Error::<T>::InvalidLimits);
+					Limits::<T>::insert(&key, limits);
+				},
+				None => {
+					Limits::<T>::remove(&key);
+					Usage::<T>::remove(&key);
+					Paused::<T>::remove(&key);
+				},
+			}
+
+			Self::deposit_event(Event::FlowLimitsSet { key, limits });
+			Ok(())
+		}
+
+		/// Reset usage of the asset flow and resume it, if it has been paused.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `asset`: The asset of the flow.
+		/// - `location`: The remote location of the flow or `None` for the flow from and to all
+		///   remote locations.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::reset_flow())]
+		pub fn reset_flow(
+			origin: OriginFor<T>,
+			asset: Box<VersionedAssetId>,
+			location: Option<Box<VersionedLocation>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let key = Self::flow_key(*asset, location.map(|location| *location))?;
+
+			Usage::<T>::remove(&key);
+			Paused::<T>::remove(&key);
+
+			Self::deposit_event(Event::FlowReset { key });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure that the `amount` of the `asset` may flow in given direction from or to the remote
+	/// `location`.
+	pub fn ensure_can_flow(
+		direction: FlowDirection,
+		asset: &AssetId,
+		location: &Location,
+		amount: u128,
+	) -> Result<(), XcmError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		for key in Self::matching_flows(asset, location) {
+			let Some(limits) = Limits::<T>::get(&key) else { continue };
+
+			if Paused::<T>::contains_key(&key) {
+				log::trace!(
+					target: LOG_TARGET,
+					"Rejecting {direction:?} transfer of {amount} {asset:?} from/to {location:?}: \
+					flow {key:?} is paused",
+				);
+				return Err(XcmError::FailedToTransactAsset("Asset flow is paused"))
+			}
+
+			let flown =
+				Usage::<T>::get(&key)
+					.at(now, limits.period)
+					.flown(direction, now, limits.period);
+			if flown.saturating_add(amount) > limits.max(direction) {
+				log::trace!(
+					target: LOG_TARGET,
+					"Rejecting {direction:?} transfer of {amount} {asset:?} from/to {location:?}: \
+					flow {key:?} would exceed its limits {limits:?} (already flown: {flown})",
+				);
+				return Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded"))
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Note that the `amount` of the `asset` has flown in given direction from or to the remote
+	/// `location`. Flows that have reached their pause threshold are paused.
+	pub fn note_flow(direction: FlowDirection, asset: &AssetId, location: &Location, amount: u128) {
+		let now = frame_system::Pallet::<T>::block_number();
+		for key in Self::matching_flows(asset, location) {
+			let Some(limits) = Limits::<T>::get(&key) else { continue };
+
+			let mut usage = Usage::<T>::get(&key).at(now, limits.period);
+			usage.note_flow(direction, amount);
+			let flown = usage.flown(direction, now, limits.period);
+			Usage::<T>::insert(&key, usage);
+
+			if limits.should_pause(direction, flown) && !Paused::<T>::contains_key(&key) {
+				log::info!(
+					target: LOG_TARGET,
+					"Pausing flow {key:?}: {direction:?} pause threshold of {limits:?} is reached \
+					(already flown: {flown})",
+				);
+				Paused::<T>::insert(&key, now);
+				Self::deposit_event(Event::FlowPaused { key, direction });
+			}
+		}
+	}
+
+	/// Returns true if the flow is paused.
+	pub fn is_paused(key: &FlowKey) -> bool {
+		Paused::<T>::contains_key(key)
+	}
+
+	/// Returns keys of all flows, which may limit the transfer of the `asset` from or to the
+	/// remote `location`.
+	fn matching_flows(asset: &AssetId, location: &Location) -> [FlowKey; 2] {
+		[FlowKey::asset(asset.clone()), FlowKey::route(asset.clone(), location.clone())]
+	}
+
+	fn flow_key(
+		asset: VersionedAssetId,
+		location: Option<VersionedLocation>,
+	) -> Result<FlowKey, Error<T>> {
+		let asset = AssetId::try_from(asset).map_err(|()| Error::<T>::BadVersion)?;
+		let location = location
+			.map(Location::try_from)
+			.transpose()
+			.map_err(|()| Error::<T>::BadVersion)?;
+		Ok(FlowKey { asset, location })
+	}
+}
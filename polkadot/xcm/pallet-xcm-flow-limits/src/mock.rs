@@ -0,0 +1,204 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_xcm_flow_limits;
+use crate::{FlowLimitedRouter, FlowLimitedTransactor};
+
+use core::cell::RefCell;
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{Contains, Everything, Nothing},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	AllowTopLevelPaidExecutionFrom, FixedRateOfFungible, FixedWeightBounds,
+	FrameTransactionalProcessor,
+};
+use xcm_executor::{traits::TransactAsset, AssetsInHolding};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		XcmFlowLimits: pallet_xcm_flow_limits,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+/// Parent and sibling chains.
+pub struct RemoteLocations;
+impl Contains<Location> for RemoteLocations {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (1, []) | (1, [Parachain(_)]))
+	}
+}
+
+parameter_types! {
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(Polkadot), Parachain(3000)].into();
+}
+
+impl pallet_xcm_flow_limits::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<u64>;
+	type RemoteLocations = RemoteLocations;
+	type UniversalLocation = UniversalLocation;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	pub static DEPOSITED: RefCell<Vec<(Asset, Location)>> = RefCell::new(Vec::new());
+	pub static WITHDRAWN: RefCell<Vec<(Asset, Location)>> = RefCell::new(Vec::new());
+	pub static CHECKED_OUT: RefCell<Vec<(Asset, Location)>> = RefCell::new(Vec::new());
+	pub static SENT: RefCell<Vec<(Location, Xcm<()>)>> = RefCell::new(Vec::new());
+}
+
+/// Returns all assets that have been deposited by the [`TestTransactor`].
+pub fn deposited() -> Vec<(Asset, Location)> {
+	DEPOSITED.with(|d| d.borrow().clone())
+}
+
+/// Returns all assets that have been withdrawn by the [`TestTransactor`].
+pub fn withdrawn() -> Vec<(Asset, Location)> {
+	WITHDRAWN.with(|w| w.borrow().clone())
+}
+
+/// Returns all assets that have been checked out by the [`TestTransactor`].
+pub fn checked_out() -> Vec<(Asset, Location)> {
+	CHECKED_OUT.with(|c| c.borrow().clone())
+}
+
+/// Returns all messages that have been sent by the [`TestRouter`].
+pub fn sent() -> Vec<(Location, Xcm<()>)> {
+	SENT.with(|s| s.borrow().clone())
+}
+
+/// Asset transactor that accepts any asset and records deposits, withdrawals and check outs.
+pub struct TestTransactor;
+impl TransactAsset for TestTransactor {
+	fn can_check_in(_origin: &Location, _what: &Asset, _context: &XcmContext) -> XcmResult {
+		Ok(())
+	}
+
+	fn can_check_out(_dest: &Location, _what: &Asset, _context: &XcmContext) -> XcmResult {
+		Ok(())
+	}
+
+	fn check_out(dest: &Location, what: &Asset, _context: &XcmContext) {
+		CHECKED_OUT.with(|c| c.borrow_mut().push((what.clone(), dest.clone())));
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, _context: Option<&XcmContext>) -> XcmResult {
+		DEPOSITED.with(|d| d.borrow_mut().push((what.clone(), who.clone())));
+		Ok(())
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<AssetsInHolding, XcmError> {
+		WITHDRAWN.with(|w| w.borrow_mut().push((what.clone(), who.clone())));
+		Ok(what.clone().into())
+	}
+}
+
+/// The flow-limited transactor, used in tests.
+pub type Transactor = FlowLimitedTransactor<Test, TestTransactor>;
+
+/// Router that accepts messages to any destination and records them.
+pub struct TestRouter;
+impl SendXcm for TestRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let ticket = (
+			dest.take().ok_or(SendError::MissingArgument)?,
+			message.take().ok_or(SendError::MissingArgument)?,
+		);
+		Ok((ticket, Assets::new()))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		SENT.with(|s| s.borrow_mut().push(ticket));
+		Ok([0; 32])
+	}
+}
+
+/// The flow-limited router, used in tests.
+pub type Router = FlowLimitedRouter<Test, TestRouter>;
+
+parameter_types! {
+	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+	pub const MaxInstructions: u32 = 100;
+	pub const MaxAssetsIntoHolding: u32 = 64;
+	pub NativePerSecondPerMb: (AssetId, u128, u128) = (AssetId(Location::parent()), 1, 1);
+}
+
+/// XCM executor configuration, that trusts every location to teleport and to be the reserve of
+/// every asset, so that only the flow limits may reject incoming assets.
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = Router;
+	type AssetTransactor = Transactor;
+	type OriginConverter = ();
+	type IsReserve = Everything;
+	type IsTeleporter = Everything;
+	type UniversalLocation = UniversalLocation;
+	type Barrier = AllowTopLevelPaidExecutionFrom<Everything>;
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Trader = FixedRateOfFungible<NativePerSecondPerMb, ()>;
+	type ResponseHandler = ();
+	type AssetTrap = ();
+	type AssetLocker = ();
+	type AssetExchanger = ();
+	type AssetClaims = ();
+	type SubscriptionService = ();
+	type PalletInstancesInfo = ();
+	type FeeManager = ();
+	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Everything;
+	type Aliasers = Nothing;
+	type TransactionalProcessor = FrameTransactionalProcessor;
+	type HrmpNewChannelOpenRequestHandler = ();
+	type HrmpChannelAcceptedHandler = ();
+	type HrmpChannelClosingHandler = ();
+	type XcmRecorder = ();
+	type FeeSponsor = ();
+}
+
+/// The XCM executor, used in tests.
+pub type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,125 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Router wrapper, that enforces limits of asset flows, leaving the chain by reserve withdrawals.
+
+use crate::{Config, FlowDirection, Pallet, LOG_TARGET};
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use frame_support::traits::{Contains, Get};
+use xcm::latest::{
+	AssetId, Error as XcmError, Fungibility, Instruction, Location, SendError, SendResult, SendXcm,
+	Xcm, XcmHash,
+};
+
+/// Wraps the `Inner` router and enforces limits of asset flows, configured in the pallet `T`, for
+/// assets that are withdrawn from the sovereign account of this chain at the remote reserve.
+///
+/// The `InitiateReserveWithdraw` instruction (and reserve withdrawals of the `InitiateTransfer`
+/// instruction) burns assets from the holding register without calling the asset transactor, so
+/// the [`FlowLimitedTransactor`](crate::FlowLimitedTransactor) can't limit such transfers.
+/// Instead, this router inspects messages, sent to locations matched by the
+/// `Config::RemoteLocations`: assets of `WithdrawAsset` instructions, which are executed with the
+/// origin of this chain (i.e. precede the first instruction that is not loading assets or paying
+/// fees), are flowing out to the destination. Sending is rejected if any of the assets would
+/// exceed its outbound quota, and the flow is noted when the message is delivered.
+pub struct FlowLimitedRouter<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, Inner: SendXcm> FlowLimitedRouter<T, Inner> {
+	/// Returns fungible assets and their total amounts, withdrawn by the `message` from the
+	/// sovereign account of this chain at the `dest`, expressed from the point of view of this
+	/// chain.
+	fn withdrawn_assets(dest: &Location, message: &Xcm<()>) -> Vec<(AssetId, u128)> {
+		let universal_location = T::UniversalLocation::get();
+		let (Ok(here), Ok(context)) = (
+			universal_location.invert_target(dest),
+			universal_location.within_global(dest.clone()),
+		) else {
+			log::trace!(
+				target: LOG_TARGET,
+				"Not limiting message to {dest:?}: the destination is outside of our consensus",
+			);
+			return Vec::new()
+		};
+
+		let mut withdrawn = Vec::new();
+		for instruction in &message.0 {
+			match instruction {
+				Instruction::WithdrawAsset(assets) =>
+					for asset in assets.inner() {
+						let Fungibility::Fungible(amount) = asset.fun else { continue };
+						let mut asset = asset.clone();
+						if asset.reanchor(&here, &context).is_err() {
+							continue
+						}
+						// fees and transferred assets may be withdrawn separately
+						match withdrawn.iter_mut().find(|(id, _)| *id == asset.id) {
+							Some((_, total)) => *total = amount.saturating_add(*total),
+							None => withdrawn.push((asset.id, amount)),
+						}
+					},
+				Instruction::ReceiveTeleportedAsset(_) |
+				Instruction::ReserveAssetDeposited(_) |
+				Instruction::PayFees { .. } |
+				Instruction::BuyExecution { .. } |
+				Instruction::UnpaidExecution { .. } => continue,
+				_ => break,
+			}
+		}
+		withdrawn
+	}
+}
+
+impl<T: Config, Inner: SendXcm> SendXcm for FlowLimitedRouter<T, Inner> {
+	type Ticket = (Inner::Ticket, Option<(Location, Vec<(AssetId, u128)>)>);
+
+	fn validate(
+		dest: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let outflow = match (dest.as_ref(), message.as_ref()) {
+			(Some(dest), Some(message)) if T::RemoteLocations::contains(dest) =>
+				Some((dest.clone(), Self::withdrawn_assets(dest, message))),
+			_ => None,
+		};
+
+		let (ticket, price) = Inner::validate(dest, message)?;
+
+		if let Some((dest, withdrawn)) = &outflow {
+			for (asset, amount) in withdrawn {
+				Pallet::<T>::ensure_can_flow(FlowDirection::Outbound, asset, dest, *amount)
+					.map_err(|error| match error {
+						XcmError::FailedToTransactAsset(reason) => SendError::Transport(reason),
+						_ => SendError::Transport("Asset flow limit exceeded"),
+					})?;
+			}
+		}
+
+		Ok(((ticket, outflow), price))
+	}
+
+	fn deliver((ticket, outflow): Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = Inner::deliver(ticket)?;
+
+		if let Some((dest, withdrawn)) = outflow {
+			for (asset, amount) in withdrawn {
+				Pallet::<T>::note_flow(FlowDirection::Outbound, &asset, &dest, amount);
+			}
+		}
+		Ok(hash)
+	}
+}
@@ -0,0 +1,498 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::{TransactAsset, WeightBounds};
+
+const SIBLING: u32 = 1000;
+const OTHER_SIBLING: u32 = 2000;
+
+fn sibling(para_id: u32) -> Location {
+	Location::new(1, [Parachain(para_id)])
+}
+
+fn local_account() -> Location {
+	Location::new(0, [AccountId32 { network: None, id: [1; 32] }])
+}
+
+fn native_asset() -> AssetId {
+	AssetId(Location::parent())
+}
+
+fn context(origin: Location) -> XcmContext {
+	XcmContext { origin: Some(origin), message_id: [0; 32], topic: None }
+}
+
+fn limits(period: u64, max_inbound: u128, max_outbound: u128) -> FlowLimitsOf<Test> {
+	FlowLimits { period, max_inbound, max_outbound, pause_at: None }
+}
+
+fn set_limits(location: Option<Location>, limits: FlowLimitsOf<Test>) {
+	assert_ok!(XcmFlowLimits::set_flow_limits(
+		RuntimeOrigin::root(),
+		Box::new(native_asset().into()),
+		location.map(|location| Box::new(location.into())),
+		Some(limits),
+	));
+}
+
+fn receive_from(origin: Location, amount: u128) -> XcmResult {
+	let asset: Asset = (native_asset(), amount).into();
+	let context = context(origin.clone());
+	Transactor::can_receive_reserve_asset(&origin, &asset, &context)?;
+	Transactor::receive_reserve_asset(&origin, &asset, &context);
+	Ok(())
+}
+
+fn execute_from(origin: Location, message: Xcm<RuntimeCall>) -> Outcome {
+	let mut hash = [0; 32];
+	XcmExecutor::prepare_and_execute(origin, message, &mut hash, Weight::MAX, Weight::zero())
+}
+
+#[test]
+fn flow_usage_forgets_transfers_out_of_rolling_window() {
+	let mut usage = FlowUsage::<u64>::default().at(1, 10);
+	usage.note_flow(FlowDirection::Inbound, 80);
+	assert_eq!(usage.flown(FlowDirection::Inbound, 1, 10), 80);
+	assert_eq!(usage.flown(FlowDirection::Outbound, 1, 10), 0);
+
+	// half of the previous window is still covered by the rolling window
+	let mut usage = usage.at(15, 10);
+	assert_eq!(usage.window_start, 10);
+	assert_eq!(usage.flown(FlowDirection::Inbound, 15, 10), 40);
+	usage.note_flow(FlowDirection::Inbound, 50);
+	assert_eq!(usage.flown(FlowDirection::Inbound, 15, 10), 90);
+
+	// transfers from the first window are forgotten
+	let usage = usage.at(25, 10);
+	assert_eq!(usage.flown(FlowDirection::Inbound, 25, 10), 25);
+
+	// the whole window has passed without any transfers
+	let usage = usage.at(45, 10);
+	assert_eq!(usage.flown(FlowDirection::Inbound, 45, 10), 0);
+}
+
+#[test]
+fn set_flow_limits_works() {
+	new_test_ext().execute_with(|| {
+		let key = FlowKey::route(native_asset(), sibling(SIBLING));
+		let limits = limits(10, 100, 200);
+
+		assert_noop!(
+			XcmFlowLimits::set_flow_limits(
+				RuntimeOrigin::signed(1),
+				Box::new(native_asset().into()),
+				Some(Box::new(sibling(SIBLING).into())),
+				Some(limits),
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			XcmFlowLimits::set_flow_limits(
+				RuntimeOrigin::root(),
+				Box::new(native_asset().into()),
+				Some(Box::new(sibling(SIBLING).into())),
+				Some(FlowLimits { period: 0, ..limits }),
+			),
+			Error::<Test>::InvalidLimits,
+		);
+
+		set_limits(Some(sibling(SIBLING)), limits);
+		assert_eq!(Limits::<Test>::get(&key), Some(limits));
+		System::assert_last_event(
+			Event::FlowLimitsSet { key: key.clone(), limits: Some(limits) }.into(),
+		);
+
+		// removing limits stops tracking the flow
+		Usage::<Test>::insert(&key, FlowUsage::default());
+		Paused::<Test>::insert(&key, 1);
+		assert_ok!(XcmFlowLimits::set_flow_limits(
+			RuntimeOrigin::root(),
+			Box::new(native_asset().into()),
+			Some(Box::new(sibling(SIBLING).into())),
+			None,
+		));
+		assert_eq!(Limits::<Test>::get(&key), None);
+		assert!(!Usage::<Test>::contains_key(&key));
+		assert!(!XcmFlowLimits::is_paused(&key));
+	});
+}
+
+#[test]
+fn inbound_transfers_are_limited() {
+	new_test_ext().execute_with(|| {
+		set_limits(None, limits(10, 100, 100));
+
+		assert_ok!(receive_from(sibling(SIBLING), 60));
+		assert_eq!(
+			receive_from(sibling(OTHER_SIBLING), 50),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+
+		// deposits to local accounts are not limited, whatever the origin is
+		assert_ok!(Transactor::deposit_asset(
+			&(native_asset(), 1_000).into(),
+			&local_account(),
+			Some(&context(sibling(SIBLING))),
+		));
+		assert_eq!(deposited().len(), 1);
+
+		// and the rest of the quota may still be used
+		assert_ok!(receive_from(sibling(OTHER_SIBLING), 39));
+		assert!(!XcmFlowLimits::is_paused(&FlowKey::asset(native_asset())));
+	});
+}
+
+#[test]
+fn route_limits_only_apply_to_its_location() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(sibling(SIBLING)), limits(10, 100, 100));
+
+		assert_eq!(
+			receive_from(sibling(SIBLING), 101),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+		assert_ok!(receive_from(sibling(OTHER_SIBLING), 101));
+		assert!(!Usage::<Test>::contains_key(FlowKey::asset(native_asset())));
+	});
+}
+
+#[test]
+fn outbound_transfers_are_limited() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(sibling(SIBLING)), limits(10, 100, 100));
+		let asset: Asset = (native_asset(), 60).into();
+		let context = context(local_account());
+
+		// teleport
+		assert_ok!(Transactor::can_check_out(&sibling(SIBLING), &asset, &context));
+		Transactor::check_out(&sibling(SIBLING), &asset, &context);
+		assert_eq!(checked_out(), vec![(asset.clone(), sibling(SIBLING))]);
+
+		// reserve transfer to the sovereign account of the sibling
+		assert_eq!(
+			Transactor::deposit_asset(&asset, &sibling(SIBLING), Some(&context)),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+		assert_eq!(
+			Transactor::can_check_out(&sibling(SIBLING), &asset, &context),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+
+		let usage = Usage::<Test>::get(FlowKey::route(native_asset(), sibling(SIBLING)));
+		assert_eq!(usage.current, FlowAmounts { inbound: 0, outbound: 60 });
+	});
+}
+
+#[test]
+fn exhausted_flow_without_pause_threshold_recovers() {
+	new_test_ext().execute_with(|| {
+		let key = FlowKey::asset(native_asset());
+		set_limits(None, limits(10, 100, 100));
+
+		assert_ok!(receive_from(sibling(SIBLING), 100));
+		assert!(!XcmFlowLimits::is_paused(&key));
+		assert_eq!(
+			receive_from(sibling(SIBLING), 1),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+
+		// the flow recovers once the transfers are out of the rolling window
+		System::set_block_number(25);
+		assert_ok!(receive_from(sibling(SIBLING), 100));
+		assert!(!XcmFlowLimits::is_paused(&key));
+	});
+}
+
+#[test]
+fn flow_is_paused_at_threshold_until_reset() {
+	new_test_ext().execute_with(|| {
+		let key = FlowKey::asset(native_asset());
+		set_limits(
+			None,
+			FlowLimits { pause_at: Some(Perbill::from_percent(80)), ..limits(10, 100, 100) },
+		);
+
+		assert_ok!(receive_from(sibling(SIBLING), 79));
+		assert!(!XcmFlowLimits::is_paused(&key));
+		assert_ok!(receive_from(sibling(SIBLING), 1));
+		assert!(XcmFlowLimits::is_paused(&key));
+		System::assert_last_event(
+			Event::FlowPaused { key: key.clone(), direction: FlowDirection::Inbound }.into(),
+		);
+
+		// paused flow rejects transfers in both directions, even when the window has passed
+		System::set_block_number(100);
+		assert_eq!(
+			receive_from(sibling(SIBLING), 1),
+			Err(XcmError::FailedToTransactAsset("Asset flow is paused")),
+		);
+		assert_eq!(
+			Transactor::can_check_out(
+				&sibling(SIBLING),
+				&(native_asset(), 1).into(),
+				&context(local_account()),
+			),
+			Err(XcmError::FailedToTransactAsset("Asset flow is paused")),
+		);
+
+		// only admin may resume the flow
+		assert_noop!(
+			XcmFlowLimits::reset_flow(
+				RuntimeOrigin::signed(1),
+				Box::new(native_asset().into()),
+				None
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(XcmFlowLimits::reset_flow(
+			RuntimeOrigin::root(),
+			Box::new(native_asset().into()),
+			None
+		));
+		System::assert_last_event(Event::FlowReset { key: key.clone() }.into());
+		assert!(!XcmFlowLimits::is_paused(&key));
+		assert_ok!(receive_from(sibling(SIBLING), 1));
+	});
+}
+
+#[test]
+fn teleports_are_limited() {
+	new_test_ext().execute_with(|| {
+		set_limits(None, limits(10, 100, 100));
+		let asset: Asset = (native_asset(), 101).into();
+
+		assert_eq!(
+			Transactor::can_check_in(&sibling(SIBLING), &asset, &context(sibling(SIBLING))),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+
+		let asset: Asset = (native_asset(), 50).into();
+		assert_ok!(Transactor::can_check_in(&sibling(SIBLING), &asset, &context(sibling(SIBLING))));
+		Transactor::check_in(&sibling(SIBLING), &asset, &context(sibling(SIBLING)));
+		assert_eq!(Usage::<Test>::get(FlowKey::asset(native_asset())).current.inbound, 50);
+	});
+}
+
+#[test]
+fn withdrawals_from_remote_locations_are_limited() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(sibling(SIBLING)), limits(10, 100, 100));
+		let key = FlowKey::route(native_asset(), sibling(SIBLING));
+
+		// the sibling returns the reserve-backed asset from its sovereign account
+		assert_ok!(Transactor::withdraw_asset(
+			&(native_asset(), 60).into(),
+			&sibling(SIBLING),
+			None,
+		));
+		assert_eq!(Usage::<Test>::get(&key).current, FlowAmounts { inbound: 60, outbound: 0 });
+		assert_eq!(
+			Transactor::withdraw_asset(&(native_asset(), 41).into(), &sibling(SIBLING), None)
+				.map(|_| ()),
+			Err(XcmError::FailedToTransactAsset("Asset flow limit exceeded")),
+		);
+		assert_eq!(withdrawn().len(), 1);
+
+		// withdrawals from local accounts are not limited
+		assert_ok!(Transactor::withdraw_asset(
+			&(native_asset(), 1_000).into(),
+			&local_account(),
+			Some(&context(sibling(SIBLING))),
+		));
+		assert_eq!(Usage::<Test>::get(&key).current.inbound, 60);
+	});
+}
+
+#[test]
+fn non_fungible_assets_are_not_limited() {
+	new_test_ext().execute_with(|| {
+		set_limits(None, limits(10, 0, 0));
+		let asset: Asset = (native_asset(), Index(1)).into();
+
+		assert_ok!(Transactor::can_receive_reserve_asset(
+			&sibling(SIBLING),
+			&asset,
+			&context(sibling(SIBLING)),
+		));
+		Transactor::receive_reserve_asset(&sibling(SIBLING), &asset, &context(sibling(SIBLING)));
+		assert_ok!(Transactor::deposit_asset(&asset, &sibling(SIBLING), None));
+		assert!(!Usage::<Test>::contains_key(FlowKey::asset(native_asset())));
+	});
+}
+
+fn paid_transfer_message(load: Instruction<RuntimeCall>, amount: u128) -> Xcm<RuntimeCall> {
+	Xcm(vec![
+		load,
+		ClearOrigin,
+		BuyExecution { fees: (Parent, amount).into(), weight_limit: Unlimited },
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: local_account() },
+	])
+}
+
+#[test]
+fn inbound_transfers_are_limited_by_executor() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(sibling(SIBLING)), limits(10, 100, 100));
+		let key = FlowKey::route(native_asset(), sibling(SIBLING));
+
+		// the origin is cleared before the assets are deposited, but the flow is noted when the
+		// assets enter the holding register
+		let message = paid_transfer_message(ReserveAssetDeposited((Parent, 60).into()), 60);
+		assert!(matches!(execute_from(sibling(SIBLING), message), Outcome::Complete { .. }));
+		assert_eq!(deposited(), vec![((native_asset(), 60).into(), local_account())]);
+		assert_eq!(Usage::<Test>::get(&key).current, FlowAmounts { inbound: 60, outbound: 0 });
+
+		let message = paid_transfer_message(ReserveAssetDeposited((Parent, 41).into()), 41);
+		assert!(matches!(
+			execute_from(sibling(SIBLING), message),
+			Outcome::Incomplete {
+				error: XcmError::FailedToTransactAsset("Asset flow limit exceeded"),
+				..
+			}
+		));
+
+		// teleports and withdrawals from the sovereign account share the same quota
+		let message = paid_transfer_message(ReceiveTeleportedAsset((Parent, 40).into()), 40);
+		assert!(matches!(execute_from(sibling(SIBLING), message), Outcome::Complete { .. }));
+		assert_eq!(Usage::<Test>::get(&key).current.inbound, 100);
+
+		let message = paid_transfer_message(WithdrawAsset((Parent, 1).into()), 1);
+		assert!(matches!(
+			execute_from(sibling(SIBLING), message),
+			Outcome::Incomplete {
+				error: XcmError::FailedToTransactAsset("Asset flow limit exceeded"),
+				..
+			}
+		));
+		assert_eq!(deposited().len(), 2);
+	});
+}
+
+fn reserve_withdraw_message(assets: impl Into<Assets>) -> Xcm<()> {
+	Xcm(vec![
+		WithdrawAsset(assets.into()),
+		ClearOrigin,
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: local_account() },
+	])
+}
+
+#[test]
+fn reserve_withdrawals_are_limited_by_router() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(Location::parent()), limits(10, 100, 100));
+		let key = FlowKey::route(native_asset(), Location::parent());
+
+		// the relay chain token is `Here` from the point of view of the relay chain
+		assert_ok!(send_xcm::<Router>(Location::parent(), reserve_withdraw_message((Here, 60))));
+		assert_eq!(Usage::<Test>::get(&key).current, FlowAmounts { inbound: 0, outbound: 60 });
+		assert_eq!(
+			send_xcm::<Router>(Location::parent(), reserve_withdraw_message((Here, 41))),
+			Err(SendError::Transport("Asset flow limit exceeded")),
+		);
+
+		// fees and transferred assets are withdrawn separately, but limited together
+		let message = Xcm(vec![
+			WithdrawAsset((Here, 20).into()),
+			PayFees { asset: (Here, 20).into() },
+			WithdrawAsset((Here, 21).into()),
+			ClearOrigin,
+		]);
+		assert_eq!(
+			send_xcm::<Router>(Location::parent(), message),
+			Err(SendError::Transport("Asset flow limit exceeded")),
+		);
+
+		// assets, withdrawn after the origin is changed, are not withdrawn from our sovereign
+		// account
+		let message = Xcm(vec![
+			DescendOrigin([AccountId32 { network: None, id: [1; 32] }].into()),
+			WithdrawAsset((Here, 1_000).into()),
+		]);
+		assert_ok!(send_xcm::<Router>(Location::parent(), message));
+		assert_eq!(sent().len(), 2);
+		assert_eq!(Usage::<Test>::get(&key).current.outbound, 60);
+	});
+}
+
+#[test]
+fn router_limits_assets_from_the_point_of_view_of_this_chain() {
+	new_test_ext().execute_with(|| {
+		set_limits(Some(sibling(SIBLING)), limits(10, 100, 100));
+
+		assert_ok!(send_xcm::<Router>(
+			sibling(SIBLING),
+			reserve_withdraw_message((Location::parent(), 60)),
+		));
+		let usage = Usage::<Test>::get(FlowKey::route(native_asset(), sibling(SIBLING)));
+		assert_eq!(usage.current.outbound, 60);
+
+		// messages to local locations are not limited
+		assert_ok!(send_xcm::<Router>(local_account(), reserve_withdraw_message((Here, 1_000))));
+	});
+}
+
+/// Weighs every instruction as a unit of weight.
+struct UnitWeigher;
+impl WeightBounds<()> for UnitWeigher {
+	fn weight(message: &mut Xcm<()>) -> Result<Weight, ()> {
+		Ok(Weight::from_parts(message.0.len() as u64, 0))
+	}
+
+	fn instr_weight(_instruction: &Instruction<()>) -> Result<Weight, ()> {
+		Ok(Weight::from_parts(1, 0))
+	}
+}
+
+frame_support::parameter_types! {
+	pub const MaxAssets: u32 = 10;
+}
+
+type Weigher = FlowLimitedWeigher<Test, UnitWeigher, MaxAssets>;
+
+#[test]
+fn weigher_accounts_for_limited_transfers() {
+	let limit_transfer = <() as crate::WeightInfo>::limit_transfer();
+	let mut message = Xcm(vec![
+		WithdrawAsset((Here, 100).into()),
+		ClearOrigin,
+		SetAppendix(Xcm(vec![DepositAsset {
+			assets: AllCounted(3).into(),
+			beneficiary: local_account(),
+		}])),
+		InitiateReserveWithdraw {
+			assets: All.into(),
+			reserve: Location::parent(),
+			xcm: Xcm(vec![]),
+		},
+	]);
+
+	assert_eq!(
+		Weigher::weight(&mut message),
+		Ok(Weight::from_parts(4, 0).saturating_add(limit_transfer.saturating_mul(1 + 3 + 10))),
+	);
+	assert_eq!(
+		Weigher::instr_weight(&TransferAsset {
+			assets: vec![(Here, 1).into(), (Here, Index(1)).into()].into(),
+			beneficiary: local_account(),
+		}),
+		Ok(Weight::from_parts(1, 0).saturating_add(limit_transfer.saturating_mul(2))),
+	);
+}
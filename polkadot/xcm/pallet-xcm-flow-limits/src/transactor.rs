@@ -0,0 +1,181 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Asset transactor wrapper, that enforces limits of asset flows.
+
+use crate::{Config, FlowDirection, Pallet};
+
+use core::marker::PhantomData;
+use frame_support::traits::Contains;
+use xcm::latest::{
+	Asset, Error as XcmError, Fungibility, Location, Result as XcmResult, XcmContext,
+};
+use xcm_executor::{traits::TransactAsset, AssetsInHolding};
+
+/// Wraps the `Inner` asset transactor and enforces limits of asset flows, configured in the
+/// pallet `T`.
+///
+/// Transfers from and to locations, matched by the `Config::RemoteLocations`, are limited:
+///
+/// - the asset is flowing in, when it is checked in (teleported) from the remote location, when it
+///   is received from the remote reserve (the `ReserveAssetDeposited` instruction), or when it is
+///   withdrawn (or transferred) from the remote location, i.e. from its sovereign account when the
+///   remote location returns reserve-backed assets of this chain;
+///
+/// - the asset is flowing out, when it is checked out (teleported) to the remote location, or when
+///   it is deposited (or transferred) to the remote location, i.e. to its sovereign account when
+///   doing the reserve transfer.
+///
+/// Flows are attributed to the location, the assets are moved from or to, and not to the origin
+/// of the XCM program, which is usually cleared before the assets are deposited.
+///
+/// Only fungible assets are limited. Note that the `InitiateReserveWithdraw` instruction burns
+/// assets from the holding register without calling the asset transactor, so such transfers are
+/// limited by the [`FlowLimitedRouter`](crate::FlowLimitedRouter) instead.
+pub struct FlowLimitedTransactor<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, Inner: TransactAsset> FlowLimitedTransactor<T, Inner> {
+	/// Returns flows, caused by moving the asset from `from` (if it is known) to `to`.
+	fn transfer_flows<'a>(
+		from: Option<&'a Location>,
+		to: Option<&'a Location>,
+	) -> impl Iterator<Item = (FlowDirection, &'a Location)> + Clone {
+		let inbound = from.map(|from| (FlowDirection::Inbound, from));
+		let outbound = to.map(|to| (FlowDirection::Outbound, to));
+		inbound
+			.into_iter()
+			.chain(outbound)
+			.filter(|(_, location)| T::RemoteLocations::contains(location))
+	}
+
+	fn ensure_can_flow(direction: FlowDirection, location: &Location, what: &Asset) -> XcmResult {
+		match what.fun {
+			Fungibility::Fungible(amount) =>
+				Pallet::<T>::ensure_can_flow(direction, &what.id, location, amount),
+			Fungibility::NonFungible(_) => Ok(()),
+		}
+	}
+
+	fn note_flow(direction: FlowDirection, location: &Location, what: &Asset) {
+		if let Fungibility::Fungible(amount) = what.fun {
+			Pallet::<T>::note_flow(direction, &what.id, location, amount)
+		}
+	}
+}
+
+impl<T: Config, Inner: TransactAsset> TransactAsset for FlowLimitedTransactor<T, Inner> {
+	fn can_check_in(origin: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_in(origin, what, context)?;
+		if T::RemoteLocations::contains(origin) {
+			Self::ensure_can_flow(FlowDirection::Inbound, origin, what)?;
+		}
+		Ok(())
+	}
+
+	fn check_in(origin: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_in(origin, what, context);
+		if T::RemoteLocations::contains(origin) {
+			Self::note_flow(FlowDirection::Inbound, origin, what);
+		}
+	}
+
+	fn can_check_out(dest: &Location, what: &Asset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_out(dest, what, context)?;
+		if T::RemoteLocations::contains(dest) {
+			Self::ensure_can_flow(FlowDirection::Outbound, dest, what)?;
+		}
+		Ok(())
+	}
+
+	fn check_out(dest: &Location, what: &Asset, context: &XcmContext) {
+		Inner::check_out(dest, what, context);
+		if T::RemoteLocations::contains(dest) {
+			Self::note_flow(FlowDirection::Outbound, dest, what);
+		}
+	}
+
+	fn can_receive_reserve_asset(
+		origin: &Location,
+		what: &Asset,
+		context: &XcmContext,
+	) -> XcmResult {
+		Inner::can_receive_reserve_asset(origin, what, context)?;
+		if T::RemoteLocations::contains(origin) {
+			Self::ensure_can_flow(FlowDirection::Inbound, origin, what)?;
+		}
+		Ok(())
+	}
+
+	fn receive_reserve_asset(origin: &Location, what: &Asset, context: &XcmContext) {
+		Inner::receive_reserve_asset(origin, what, context);
+		if T::RemoteLocations::contains(origin) {
+			Self::note_flow(FlowDirection::Inbound, origin, what);
+		}
+	}
+
+	fn deposit_asset(what: &Asset, who: &Location, context: Option<&XcmContext>) -> XcmResult {
+		let flows = Self::transfer_flows(None, Some(who));
+		for (direction, location) in flows.clone() {
+			Self::ensure_can_flow(direction, location, what)?;
+		}
+
+		Inner::deposit_asset(what, who, context)?;
+
+		for (direction, location) in flows {
+			Self::note_flow(direction, location, what);
+		}
+		Ok(())
+	}
+
+	fn withdraw_asset(
+		what: &Asset,
+		who: &Location,
+		maybe_context: Option<&XcmContext>,
+	) -> Result<AssetsInHolding, XcmError> {
+		let flows = Self::transfer_flows(Some(who), None);
+		for (direction, location) in flows.clone() {
+			Self::ensure_can_flow(direction, location, what)?;
+		}
+
+		let withdrawn = Inner::withdraw_asset(what, who, maybe_context)?;
+
+		for (direction, location) in flows {
+			Self::note_flow(direction, location, what);
+		}
+		Ok(withdrawn)
+	}
+
+	fn internal_transfer_asset(
+		asset: &Asset,
+		from: &Location,
+		to: &Location,
+		context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		let flows = Self::transfer_flows(Some(from), Some(to));
+		for (direction, location) in flows.clone() {
+			Self::ensure_can_flow(direction, location, asset)?;
+		}
+
+		// if the inner transactor fails here, `transfer_asset` may fall back to
+		// `withdraw_asset` + `deposit_asset`, so the flow is noted on success only
+		let transferred = Inner::internal_transfer_asset(asset, from, to, context)?;
+
+		for (direction, location) in flows {
+			Self::note_flow(direction, location, asset);
+		}
+		Ok(transferred)
+	}
+}
@@ -0,0 +1,110 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM weigher wrapper, that accounts for checking and noting limited asset flows.
+
+use crate::{Config, WeightInfo};
+
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use xcm::latest::{
+	AssetFilter, Assets, Fungibility, Instruction, Weight, WildAsset, WildFungibility, Xcm,
+};
+use xcm_executor::traits::WeightBounds;
+
+/// Wraps the `Inner` XCM weigher and adds `Config::WeightInfo::limit_transfer` for every
+/// fungible asset, that may be checked or noted by the
+/// [`FlowLimitedTransactor`](crate::FlowLimitedTransactor) or the
+/// [`FlowLimitedRouter`](crate::FlowLimitedRouter) when executing the message.
+///
+/// Wildcards that match all assets in the holding register are weighed as `MaxAssets` assets.
+pub struct FlowLimitedWeigher<T, Inner, MaxAssets>(PhantomData<(T, Inner, MaxAssets)>);
+
+impl<T: Config, Inner, MaxAssets: Get<u32>> FlowLimitedWeigher<T, Inner, MaxAssets> {
+	/// Returns the number of fungible assets, which flows may be limited when executing the
+	/// `instruction`.
+	fn limited_transfers<Call>(instruction: &Instruction<Call>) -> u32 {
+		match instruction {
+			Instruction::WithdrawAsset(assets) |
+			Instruction::ReserveAssetDeposited(assets) |
+			Instruction::ReceiveTeleportedAsset(assets) => Self::fungible_assets(assets),
+			// the asset may flow both from the origin and to the beneficiary
+			Instruction::TransferAsset { assets, .. } |
+			Instruction::TransferReserveAsset { assets, .. } =>
+				Self::fungible_assets(assets).saturating_mul(2),
+			Instruction::DepositAsset { assets, .. } |
+			Instruction::DepositReserveAsset { assets, .. } |
+			Instruction::InitiateReserveWithdraw { assets, .. } |
+			Instruction::InitiateTeleport { assets, .. } => Self::matched_assets(assets),
+			Instruction::InitiateTransfer { remote_fees, assets, .. } =>
+				remote_fees.iter().chain(assets.iter()).fold(0u32, |count, filter| {
+					count.saturating_add(Self::matched_assets(filter.inner()))
+				}),
+			Instruction::SetErrorHandler(xcm) | Instruction::SetAppendix(xcm) =>
+				Self::message_transfers(xcm),
+			_ => 0,
+		}
+	}
+
+	fn message_transfers<Call>(message: &Xcm<Call>) -> u32 {
+		message.0.iter().fold(0u32, |count, instruction| {
+			count.saturating_add(Self::limited_transfers(instruction))
+		})
+	}
+
+	fn fungible_assets(assets: &Assets) -> u32 {
+		let fungible = assets
+			.inner()
+			.iter()
+			.filter(|asset| matches!(asset.fun, Fungibility::Fungible(_)));
+		fungible.count() as u32
+	}
+
+	fn matched_assets(filter: &AssetFilter) -> u32 {
+		match filter {
+			AssetFilter::Definite(assets) => Self::fungible_assets(assets),
+			AssetFilter::Wild(WildAsset::All) => MaxAssets::get(),
+			AssetFilter::Wild(WildAsset::AllCounted(count)) => MaxAssets::get().min(*count),
+			AssetFilter::Wild(WildAsset::AllOf { fun: WildFungibility::Fungible, .. }) => 1,
+			AssetFilter::Wild(WildAsset::AllOfCounted {
+				fun: WildFungibility::Fungible,
+				count,
+				..
+			}) => 1.min(*count),
+			AssetFilter::Wild(WildAsset::AllOf { .. } | WildAsset::AllOfCounted { .. }) => 0,
+		}
+	}
+
+	fn transfers_weight(transfers: u32) -> Weight {
+		T::WeightInfo::limit_transfer().saturating_mul(transfers as u64)
+	}
+}
+
+impl<T: Config, Inner: WeightBounds<Call>, MaxAssets: Get<u32>, Call> WeightBounds<Call>
+	for FlowLimitedWeigher<T, Inner, MaxAssets>
+{
+	fn weight(message: &mut Xcm<Call>) -> Result<Weight, ()> {
+		let transfers = Self::message_transfers(message);
+		Inner::weight(message)
+			.map(|weight| weight.saturating_add(Self::transfers_weight(transfers)))
+	}
+
+	fn instr_weight(instruction: &Instruction<Call>) -> Result<Weight, ()> {
+		let transfers = Self::limited_transfers(instruction);
+		Inner::instr_weight(instruction)
+			.map(|weight| weight.saturating_add(Self::transfers_weight(transfers)))
+	}
+}
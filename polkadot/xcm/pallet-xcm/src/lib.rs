@@ -37,10 +37,12 @@ use frame_support::{
 		DispatchErrorWithPostInfo, GetDispatchInfo, PostDispatchInfo, WithPostDispatchInfo,
 	},
 	pallet_prelude::*,
+	storage::{transactional::with_transaction_opaque_err, TransactionOutcome},
 	traits::{
 		Contains, ContainsPair, Currency, Defensive, EnsureOrigin, Get, LockableCurrency,
 		OriginTrait, WithdrawReasons,
 	},
+	weights::WeightMeter,
 	PalletId,
 };
 use frame_system::pallet_prelude::{BlockNumberFor, *};
@@ -65,11 +67,13 @@ use xcm_executor::{
 		RecordXcm, TransactAsset, TransferType, VersionChangeNotifier, WeightBounds,
 		XcmAssetTransfers,
 	},
-	AssetsInHolding,
+	AssetsInHolding, InstructionTrace,
 };
 use xcm_runtime_apis::{
 	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
 	fees::Error as XcmPaymentApiError,
+	queries::{Error as XcmQueriesApiError, PendingQuery},
+	trapped_assets::{Error as TrappedAssetsApiError, TrappedAssets},
 };
 
 #[cfg(any(feature = "try-runtime", test))]
@@ -200,7 +204,7 @@ pub mod pallet {
 		pub const CurrentXcmVersion: u32 = XCM_VERSION;
 	}
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -290,6 +294,14 @@ pub mod pallet {
 		/// The ID type for local consumers of remote locks.
 		type RemoteLockConsumerIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
 
+		/// The number of blocks after which unclaimed trapped assets are swept to the
+		/// `TrappedAssetsBeneficiary`. If `None`, trapped assets are kept until they are claimed.
+		type TrappedAssetsExpiry: Get<Option<BlockNumberFor<Self>>>;
+
+		/// The location where expired trapped assets are deposited, e.g. the treasury account.
+		/// If `None`, trapped assets are kept until they are claimed.
+		type TrappedAssetsBeneficiary: Get<Option<Location>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -500,6 +512,24 @@ pub mod pallet {
 		AssetsClaimed { hash: H256, origin: Location, assets: VersionedAssets },
 		/// A XCM version migration finished.
 		VersionMigrationFinished { version: XcmVersion },
+		/// Query has timed out before the response has been received and is removed. There is no
+		/// registered notification call.
+		QueryTimedOut { query_id: QueryId },
+		/// Query has timed out before the response has been received and is removed. The
+		/// registered notification has been dispatched with the `Response::Null` response and
+		/// executed successfully.
+		NotifyTimedOut { query_id: QueryId, pallet_index: u8, call_index: u8 },
+		/// Assets of an expired asset trap have been deposited to the `TrappedAssetsBeneficiary`
+		/// and the trap is removed.
+		AssetsSwept {
+			hash: H256,
+			origin: VersionedLocation,
+			assets: VersionedAssets,
+			beneficiary: Location,
+		},
+		/// Assets of an expired asset trap could not be deposited to the
+		/// `TrappedAssetsBeneficiary`. The assets remain trapped and may still be claimed.
+		AssetsSweepFailed { hash: H256, origin: VersionedLocation, error: XcmError },
 	}
 
 	#[pallet::origin]
@@ -613,6 +643,17 @@ pub mod pallet {
 		Ready { response: VersionedResponse, at: BlockNumber },
 	}
 
+	/// Details of assets that have been trapped.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	pub struct AssetTrap<BlockNumber> {
+		/// The origin of the XCM program that has trapped the assets.
+		pub origin: VersionedLocation,
+		/// The trapped assets, in the version they have been hashed with.
+		pub assets: VersionedAssets,
+		/// The block where the assets have been trapped for the last time.
+		pub trapped_at: BlockNumber,
+	}
+
 	#[derive(Copy, Clone)]
 	pub(crate) struct LatestVersionedLocation<'a>(pub(crate) &'a Location);
 	impl<'a> EncodeLike<VersionedLocation> for LatestVersionedLocation<'a> {}
@@ -649,6 +690,27 @@ pub mod pallet {
 	pub(super) type Queries<T: Config> =
 		StorageMap<_, Blake2_128Concat, QueryId, QueryStatus<BlockNumberFor<T>>, OptionQuery>;
 
+	/// Pending queries, indexed by the block where they should be checked for expiration.
+	///
+	/// The first key is the query timeout or, if the timeout has already passed when the query
+	/// has been registered, the block of registration. Entries of answered queries are removed
+	/// lazily, when their block is checked.
+	#[pallet::storage]
+	pub(super) type QueryTimeouts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		QueryId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The next block of [`QueryTimeouts`] to check for expired queries.
+	#[pallet::storage]
+	pub(super) type NextQueryTimeoutCheck<T: Config> =
+		StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
 	/// The existing asset traps.
 	///
 	/// Key is the blake2 256 hash of (origin, versioned `Assets`) pair. Value is the number of
@@ -657,6 +719,26 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// Details of the existing asset traps.
+	///
+	/// Key is the same hash as in [`AssetTraps`]. Traps created before the details have been
+	/// recorded have no entry here; they can still be claimed, but they are never swept.
+	#[pallet::storage]
+	pub(super) type AssetTrapDetails<T: Config> =
+		StorageMap<_, Identity, H256, AssetTrap<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Asset traps, indexed by the block where they have been created.
+	///
+	/// Entries of traps that have been claimed, or trapped again later, are removed lazily, when
+	/// their block is swept.
+	#[pallet::storage]
+	pub(super) type AssetTrapsByBlock<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Identity, H256, (), OptionQuery>;
+
+	/// The next block of [`AssetTrapsByBlock`] to check for expired asset traps.
+	#[pallet::storage]
+	pub(super) type NextAssetTrapSweep<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -792,6 +874,24 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type RecordedXcm<T: Config> = StorageValue<_, Xcm<()>>;
 
+	/// Whether or not the state of the XCM executor should be recorded after every instruction of
+	/// the XCMs, executed locally. Like [`ShouldRecordXcm`], this is meant to be used in runtime
+	/// APIs only.
+	///
+	/// Only relevant if this pallet is being used as the [`xcm_executor::traits::RecordXcm`]
+	/// implementation in the XCM executor configuration.
+	#[pallet::storage]
+	pub(crate) type ShouldTraceXcm<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// If [`ShouldTraceXcm`] is set to true, then the execution trace of XCM programs, executed
+	/// locally since the tracing has been enabled, will be stored here.
+	///
+	/// Only relevant if this pallet is being used as the [`xcm_executor::traits::RecordXcm`]
+	/// implementation in the XCM executor configuration.
+	#[pallet::storage]
+	pub(crate) type RecordedXcmTrace<T: Config> =
+		StorageValue<_, Vec<InstructionTrace>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[serde(skip)]
@@ -851,6 +951,11 @@ pub mod pallet {
 			weight_used
 		}
 
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = Self::expire_queries(n, remaining_weight);
+			used.saturating_add(Self::sweep_asset_traps(n, remaining_weight.saturating_sub(used)))
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
 			Self::do_try_state()
@@ -1460,6 +1565,8 @@ impl<T: Config> QueryHandler for Pallet<T> {
 	type UniversalLocation = T::UniversalLocation;
 
 	/// Attempt to create a new query ID and register it as a query that is yet to respond.
+	///
+	/// Callers must account for [`WeightInfo::new_query`] in their weight.
 	fn new_query(
 		responder: impl Into<Location>,
 		timeout: BlockNumberFor<T>,
@@ -1470,6 +1577,8 @@ impl<T: Config> QueryHandler for Pallet<T> {
 
 	/// To check the status of the query, use `fn query()` passing the resultant `QueryId`
 	/// value.
+	///
+	/// Callers must account for [`WeightInfo::new_query`] in their weight.
 	fn report_outcome(
 		message: &mut Xcm<()>,
 		responder: impl Into<Location>,
@@ -2500,7 +2609,8 @@ impl<T: Config> Pallet<T> {
 
 	/// Dry-runs `xcm` with the given `origin_location`.
 	///
-	/// Returns execution result, events, and any forwarded XCMs to other locations.
+	/// Returns execution result, events, any forwarded XCMs to other locations and the execution
+	/// trace, if it is recorded by the `XcmRecorder` of the executor.
 	/// Meant to be used in the `xcm_runtime_apis::dry_run::DryRunApi` runtime API.
 	pub fn dry_run_xcm<Runtime, Router, RuntimeCall, XcmConfig>(
 		origin_location: VersionedLocation,
@@ -2529,6 +2639,7 @@ impl<T: Config> Pallet<T> {
 		})?;
 		let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
 		frame_system::Pallet::<Runtime>::reset_events(); // To make sure we only record events from current call.
+		XcmConfig::XcmRecorder::set_trace_xcm(true);
 		let result = xcm_executor::XcmExecutor::<XcmConfig>::prepare_and_execute(
 			origin_location,
 			xcm,
@@ -2536,12 +2647,19 @@ impl<T: Config> Pallet<T> {
 			Weight::MAX, // Max limit available for execution.
 			Weight::zero(),
 		);
+		XcmConfig::XcmRecorder::set_trace_xcm(false);
+		let execution_trace = XcmConfig::XcmRecorder::recorded_trace();
 		let forwarded_xcms = Router::get_messages();
 		let events: Vec<<Runtime as frame_system::Config>::RuntimeEvent> =
 			frame_system::Pallet::<Runtime>::read_events_no_consensus()
 				.map(|record| record.event.clone())
 				.collect();
-		Ok(XcmDryRunEffects { forwarded_xcms, emitted_events: events, execution_result: result })
+		Ok(XcmDryRunEffects {
+			forwarded_xcms,
+			emitted_events: events,
+			execution_result: result,
+			execution_trace,
+		})
 	}
 
 	/// Given a list of asset ids, returns the correct API response for
@@ -2602,6 +2720,11 @@ impl<T: Config> Pallet<T> {
 		QueryCounter::<T>::mutate(|q| {
 			let r = *q;
 			q.saturating_inc();
+			let now = frame_system::Pallet::<T>::block_number();
+			NextQueryTimeoutCheck::<T>::mutate(|next| {
+				next.get_or_insert(now);
+			});
+			QueryTimeouts::<T>::insert(timeout.max(now), r, ());
 			Queries::<T>::insert(
 				r,
 				QueryStatus::Pending {
@@ -2626,7 +2749,9 @@ impl<T: Config> Pallet<T> {
 	///   and in that order. It should expect that the origin is `Origin::Response` and will contain
 	///   the responder's location.
 	/// - `timeout`: The block number after which it is permissible for `notify` not to be called
-	///   even if a response is received.
+	///   even if a response is received. If no response is received until then, the query is
+	///   removed and `notify` is called (in `on_idle`) with the `Response::Null` response and the
+	///   `Origin::Response` origin, containing the `Here` location.
 	///
 	/// `report_outcome_notify` may return an error if the `responder` is not invertible.
 	///
@@ -2637,6 +2762,9 @@ impl<T: Config> Pallet<T> {
 	/// weighing `ReportOutcome` on the way back. If it turns out to be heavier once it returns
 	/// then reporting the outcome will fail. Futhermore if the estimate is too high, then it
 	/// may be put in the overweight queue and need to be manually executed.
+	///
+	/// Callers must account for [`WeightInfo::new_query`] in their weight, which includes
+	/// indexing the query for expiration.
 	pub fn report_outcome_notify(
 		message: &mut Xcm<()>,
 		responder: impl Into<Location>,
@@ -2657,7 +2785,10 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Attempt to create a new query ID and register it as a query that is yet to respond, and
-	/// which will call a dispatchable when a response happens.
+	/// which will call a dispatchable when a response happens or when the query times out (see
+	/// [`Self::report_outcome_notify`]).
+	///
+	/// Callers must account for [`WeightInfo::new_query`] in their weight.
 	pub fn new_notify_query(
 		responder: impl Into<Location>,
 		notify: impl Into<<T as Config>::RuntimeCall>,
@@ -2670,6 +2801,271 @@ impl<T: Config> Pallet<T> {
 		Self::do_new_query(responder, Some(notify), timeout, match_querier)
 	}
 
+	/// Returns all pending queries that expect a response from the `responder`.
+	///
+	/// This iterates over all queries, so it is meant to be used in runtime APIs only.
+	pub fn pending_queries(
+		responder: VersionedLocation,
+	) -> Result<Vec<PendingQuery<BlockNumberFor<T>>>, XcmQueriesApiError> {
+		let responder = Location::try_from(responder)
+			.map_err(|()| XcmQueriesApiError::VersionedConversionFailed)?;
+		Ok(Queries::<T>::iter()
+			.filter_map(|(query_id, status)| match status {
+				QueryStatus::Pending {
+					responder: query_responder,
+					maybe_match_querier,
+					maybe_notify,
+					timeout,
+				} => (Location::try_from(query_responder).ok()? == responder)
+					.then(|| PendingQuery { query_id, maybe_match_querier, maybe_notify, timeout }),
+				_ => None,
+			})
+			.collect())
+	}
+
+	/// Returns all asset traps of the `origin`.
+	///
+	/// This iterates over all asset traps, so it is meant to be used in runtime APIs only.
+	pub fn trapped_assets(
+		origin: VersionedLocation,
+	) -> Result<Vec<TrappedAssets<BlockNumberFor<T>>>, TrappedAssetsApiError> {
+		let origin = Location::try_from(origin)
+			.map_err(|()| TrappedAssetsApiError::VersionedConversionFailed)?;
+		Ok(AssetTrapDetails::<T>::iter()
+			.filter_map(|(hash, trap)| {
+				(Location::try_from(trap.origin).ok()? == origin).then(|| TrappedAssets {
+					assets: trap.assets,
+					count: AssetTraps::<T>::get(hash),
+					trapped_at: trap.trapped_at,
+				})
+			})
+			.collect())
+	}
+
+	/// Remove pending queries, whose timeout has passed, and dispatch their timeout
+	/// notifications. At most `limit` weight is used and the rest of expired queries is handled
+	/// later.
+	fn expire_queries(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut meter = WeightMeter::with_limit(limit);
+		// read and write of the `NextQueryTimeoutCheck`
+		if meter.try_consume(db_weight.reads_writes(1, 1)).is_err() {
+			return meter.consumed()
+		}
+
+		let mut block = NextQueryTimeoutCheck::<T>::get().unwrap_or(now);
+		'blocks: while block < now {
+			// read of the first `QueryTimeouts` key
+			if meter.try_consume(db_weight.reads(1)).is_err() {
+				break
+			}
+			for query_id in QueryTimeouts::<T>::iter_key_prefix(block) {
+				// read of the query and the next `QueryTimeouts` key, removal of both entries
+				if meter.try_consume(db_weight.reads_writes(2, 2)).is_err() ||
+					!Self::expire_query(block, query_id, &mut meter)
+				{
+					break 'blocks
+				}
+			}
+			block.saturating_inc();
+		}
+		NextQueryTimeoutCheck::<T>::put(block);
+
+		meter.consumed()
+	}
+
+	/// Expire the query, that is registered in the `QueryTimeouts` at `block`.
+	///
+	/// Returns `false` if there is not enough weight left to dispatch the timeout notification.
+	fn expire_query(block: BlockNumberFor<T>, query_id: QueryId, meter: &mut WeightMeter) -> bool {
+		let Some(QueryStatus::Pending { maybe_notify, .. }) = Queries::<T>::get(query_id) else {
+			// the response has already been received
+			QueryTimeouts::<T>::remove(block, query_id);
+			return true
+		};
+
+		let Some((pallet_index, call_index)) = maybe_notify else {
+			Queries::<T>::remove(query_id);
+			QueryTimeouts::<T>::remove(block, query_id);
+			Self::deposit_event(Event::QueryTimedOut { query_id });
+			return true
+		};
+
+		// The same trick as in `on_response`: the `Call` is built by
+		// `(pallet_index: u8, call_index: u8, QueryId, Response)`.
+		let bare = (pallet_index, call_index, query_id, Response::Null);
+		let Ok(call) =
+			bare.using_encoded(|mut bytes| <T as Config>::RuntimeCall::decode(&mut bytes))
+		else {
+			Queries::<T>::remove(query_id);
+			QueryTimeouts::<T>::remove(block, query_id);
+			Self::deposit_event(Event::NotifyDecodeFailed { query_id, pallet_index, call_index });
+			return true
+		};
+
+		let weight = call.get_dispatch_info().weight;
+		let max_weight = T::BlockWeights::get().max_block;
+		if weight.any_gt(max_weight) {
+			// it would never fit into the idle weight, so we don't want it to block other queries
+			Queries::<T>::remove(query_id);
+			QueryTimeouts::<T>::remove(block, query_id);
+			Self::deposit_event(Event::NotifyOverweight {
+				query_id,
+				pallet_index,
+				call_index,
+				actual_weight: weight,
+				max_budgeted_weight: max_weight,
+			});
+			return true
+		}
+		if !meter.can_consume(weight) {
+			return false
+		}
+
+		Queries::<T>::remove(query_id);
+		QueryTimeouts::<T>::remove(block, query_id);
+		let dispatch_origin = Origin::Response(Location::here()).into();
+		let actual_weight = match call.dispatch(dispatch_origin) {
+			Ok(post_info) => {
+				Self::deposit_event(Event::NotifyTimedOut { query_id, pallet_index, call_index });
+				post_info.actual_weight
+			},
+			Err(error_and_info) => {
+				Self::deposit_event(Event::NotifyDispatchError {
+					query_id,
+					pallet_index,
+					call_index,
+				});
+				error_and_info.post_info.actual_weight
+			},
+		}
+		.unwrap_or(weight);
+		meter.consume(actual_weight);
+
+		true
+	}
+
+	/// Returns the `TrappedAssetsExpiry` and the `TrappedAssetsBeneficiary`, if sweeping of
+	/// expired asset traps is enabled, i.e. both of them are configured.
+	fn asset_trap_sweep() -> Option<(BlockNumberFor<T>, Location)> {
+		Some((T::TrappedAssetsExpiry::get()?, T::TrappedAssetsBeneficiary::get()?))
+	}
+
+	/// Deposit assets of asset traps, that are older than `TrappedAssetsExpiry`, to the
+	/// `TrappedAssetsBeneficiary`. At most `limit` weight is used and the rest of expired traps is
+	/// swept later.
+	fn sweep_asset_traps(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let Some((expiry, beneficiary)) = Self::asset_trap_sweep() else { return Weight::zero() };
+		let db_weight = T::DbWeight::get();
+		let mut meter = WeightMeter::with_limit(limit);
+		// read and write of the `NextAssetTrapSweep`
+		if meter.try_consume(db_weight.reads_writes(1, 1)).is_err() {
+			return meter.consumed()
+		}
+
+		let mut block = NextAssetTrapSweep::<T>::get().unwrap_or(now);
+		'blocks: while block.saturating_add(expiry) < now {
+			// read of the first `AssetTrapsByBlock` key
+			if meter.try_consume(db_weight.reads(1)).is_err() {
+				break
+			}
+			for hash in AssetTrapsByBlock::<T>::iter_key_prefix(block) {
+				// read of the trap details, its counter and the next `AssetTrapsByBlock` key,
+				// removal of the entry
+				if meter.try_consume(db_weight.reads_writes(3, 1)).is_err() ||
+					!Self::sweep_asset_trap(block, hash, &beneficiary, &mut meter)
+				{
+					break 'blocks
+				}
+			}
+			block.saturating_inc();
+		}
+		NextAssetTrapSweep::<T>::put(block);
+
+		meter.consumed()
+	}
+
+	/// Sweep the asset trap, that is registered in the `AssetTrapsByBlock` at `block`.
+	///
+	/// Returns `false` if there is not enough weight left to deposit the trapped assets.
+	fn sweep_asset_trap(
+		block: BlockNumberFor<T>,
+		hash: H256,
+		beneficiary: &Location,
+		meter: &mut WeightMeter,
+	) -> bool {
+		let Some(trap) = AssetTrapDetails::<T>::get(hash).filter(|trap| trap.trapped_at == block)
+		else {
+			// the assets have already been claimed, or they have been trapped again later
+			AssetTrapsByBlock::<T>::remove(block, hash);
+			return true
+		};
+
+		// depositing the assets costs about the same as claiming them, plus removal of the trap
+		let count = AssetTraps::<T>::get(hash);
+		let weight = T::WeightInfo::claim_assets()
+			.saturating_mul(count.into())
+			.saturating_add(T::DbWeight::get().writes(2));
+		if weight.any_gt(T::BlockWeights::get().max_block) {
+			// it would never fit into the idle weight, so we don't want it to block other traps
+			AssetTrapsByBlock::<T>::remove(block, hash);
+			Self::deposit_event(Event::AssetsSweepFailed {
+				hash,
+				origin: trap.origin,
+				error: XcmError::WeightLimitReached(weight),
+			});
+			return true
+		}
+		if meter.try_consume(weight).is_err() {
+			return false
+		}
+
+		AssetTrapsByBlock::<T>::remove(block, hash);
+		let deposited = Assets::try_from(trap.assets.clone())
+			.map_err(|()| XcmError::UnhandledXcmVersion)
+			.and_then(|assets| Self::deposit_trapped_assets(&assets, count, beneficiary));
+		match deposited {
+			Ok(()) => {
+				AssetTraps::<T>::remove(hash);
+				AssetTrapDetails::<T>::remove(hash);
+				Self::deposit_event(Event::AssetsSwept {
+					hash,
+					origin: trap.origin,
+					assets: trap.assets,
+					beneficiary,
+				});
+			},
+			Err(error) =>
+				Self::deposit_event(Event::AssetsSweepFailed { hash, origin: trap.origin, error }),
+		}
+		true
+	}
+
+	/// Deposit the `assets`, that have been trapped `count` times, to the `beneficiary`. Either
+	/// all of them are deposited, or none.
+	fn deposit_trapped_assets(
+		assets: &Assets,
+		count: u32,
+		beneficiary: &Location,
+	) -> Result<(), XcmError> {
+		with_transaction_opaque_err(|| {
+			let result = (0..count).try_for_each(|_| {
+				assets.inner().iter().try_for_each(|asset| {
+					<T::XcmExecutor as XcmAssetTransfers>::AssetTransactor::deposit_asset(
+						asset,
+						beneficiary,
+						None,
+					)
+				})
+			});
+			match result {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(error) => TransactionOutcome::Rollback(Err(error)),
+			}
+		})
+		.map_err(|()| XcmError::ExceedsStackLimit)?
+	}
+
 	/// Note that a particular destination to whom we would like to send a message is unknown
 	/// and queue it for version discovery.
 	fn note_unknown_version(dest: &Location) {
@@ -3016,14 +3412,29 @@ impl<T: Config> DropAssets for Pallet<T> {
 		}
 		let versioned = VersionedAssets::from(Assets::from(assets));
 		let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
+		let now = frame_system::Pallet::<T>::block_number();
 		AssetTraps::<T>::mutate(hash, |n| *n += 1);
+		AssetTrapDetails::<T>::insert(
+			hash,
+			AssetTrap { origin: origin.clone().into(), assets: versioned.clone(), trapped_at: now },
+		);
+		// write of the `AssetTrapDetails`
+		let mut weight = T::DbWeight::get().writes(1);
+		if Self::asset_trap_sweep().is_some() {
+			NextAssetTrapSweep::<T>::mutate(|next| {
+				next.get_or_insert(now);
+			});
+			AssetTrapsByBlock::<T>::insert(now, hash, ());
+			// read and write of the `NextAssetTrapSweep` and write of the `AssetTrapsByBlock`
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+		}
 		Self::deposit_event(Event::AssetsTrapped {
 			hash,
 			origin: origin.clone(),
 			assets: versioned,
 		});
-		// TODO #3735: Put the real weight in there.
-		Weight::zero()
+		// TODO #3735: Put the real weight of `AssetTraps` in there.
+		weight
 	}
 }
 
@@ -3047,7 +3458,10 @@ impl<T: Config> ClaimAssets for Pallet<T> {
 		let hash = BlakeTwo256::hash_of(&(origin.clone(), versioned.clone()));
 		match AssetTraps::<T>::get(hash) {
 			0 => return false,
-			1 => AssetTraps::<T>::remove(hash),
+			1 => {
+				AssetTraps::<T>::remove(hash);
+				AssetTrapDetails::<T>::remove(hash);
+			},
 			n => AssetTraps::<T>::insert(hash, n - 1),
 		}
 		Self::deposit_event(Event::AssetsClaimed {
@@ -3269,6 +3683,25 @@ impl<T: Config> RecordXcm for Pallet<T> {
 	fn record(xcm: Xcm<()>) {
 		RecordedXcm::<T>::put(xcm);
 	}
+
+	fn should_trace() -> bool {
+		ShouldTraceXcm::<T>::get()
+	}
+
+	fn set_trace_xcm(enabled: bool) {
+		if enabled {
+			RecordedXcmTrace::<T>::kill();
+		}
+		ShouldTraceXcm::<T>::put(enabled);
+	}
+
+	fn recorded_trace() -> Option<Vec<InstructionTrace>> {
+		Some(RecordedXcmTrace::<T>::get())
+	}
+
+	fn record_instruction(trace: InstructionTrace) {
+		RecordedXcmTrace::<T>::append(trace);
+	}
 }
 
 /// Ensure that the origin `o` represents an XCM (`Transact`) origin.
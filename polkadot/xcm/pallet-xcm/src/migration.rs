@@ -76,6 +76,65 @@ pub mod v1 {
 	>;
 }
 
+pub mod v2 {
+	use super::*;
+	use crate::{NextQueryTimeoutCheck, Queries, QueryStatus, QueryTimeouts};
+
+	/// Indexes queries, that are pending before the upgrade, in the [`QueryTimeouts`], so they
+	/// expire the same way as queries registered after the upgrade.
+	///
+	/// Use [`MigrateToV2`] instead.
+	pub struct VersionUncheckedMigrateToV2<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// read of the block number and read and write of the `NextQueryTimeoutCheck`
+			let mut weight = T::DbWeight::get().reads_writes(2, 1);
+			let now = frame_system::Pallet::<T>::block_number();
+			NextQueryTimeoutCheck::<T>::mutate(|next| {
+				next.get_or_insert(now);
+			});
+
+			let mut indexed = 0u32;
+			for (query_id, status) in Queries::<T>::iter() {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if let QueryStatus::Pending { timeout, .. } = status {
+					weight.saturating_accrue(T::DbWeight::get().writes(1));
+					QueryTimeouts::<T>::insert(timeout.max(now), query_id, ());
+					indexed.saturating_inc();
+				}
+			}
+
+			log::info!("v2 applied successfully, {} pending queries indexed", indexed);
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let unindexed = Queries::<T>::iter().any(|(query_id, status)| match status {
+				QueryStatus::Pending { timeout, .. } => !QueryTimeouts::<T>::contains_key(
+					timeout.max(frame_system::Pallet::<T>::block_number()),
+					query_id,
+				),
+				_ => false,
+			});
+			ensure!(!unindexed, "pending query is not indexed in `QueryTimeouts`");
+			Ok(())
+		}
+	}
+
+	/// Version checked migration to v2.
+	///
+	/// Wrapped in [`frame_support::migrations::VersionedMigration`] so the pre/post checks don't
+	/// begin failing after the upgrade is enacted on-chain.
+	pub type MigrateToV2<T> = frame_support::migrations::VersionedMigration<
+		1,
+		2,
+		VersionUncheckedMigrateToV2<T>,
+		crate::pallet::Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
 /// When adding a new XCM version, we need to run this migration for `pallet_xcm` to ensure that all
 /// previously stored data with subkey prefix `XCM_VERSION-1` (and below) are migrated to the
 /// `XCM_VERSION`.
@@ -452,9 +452,13 @@ parameter_types! {
 	pub const MaxInstructions: u32 = 100;
 	pub const MaxAssetsIntoHolding: u32 = 64;
 	pub XcmFeesTargetAccount: AccountId = AccountId::new([167u8; 32]);
+	pub const TrappedAssetsExpiry: Option<u64> = Some(10);
+	pub TrappedAssetsBeneficiary: Option<Location> =
+		Some(Junction::AccountId32 { network: None, id: TRAPPED_ASSETS_BENEFICIARY }.into());
 }
 
 pub const XCM_FEES_NOT_WAIVED_USER_ACCOUNT: [u8; 32] = [37u8; 32];
+pub const TRAPPED_ASSETS_BENEFICIARY: [u8; 32] = [187u8; 32];
 
 pub struct XcmFeesNotWaivedLocations;
 impl Contains<Location> for XcmFeesNotWaivedLocations {
@@ -516,6 +520,7 @@ impl xcm_executor::Config for XcmConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = XcmPallet;
+	type FeeSponsor = ();
 }
 
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, AnyNetwork>;
@@ -555,6 +560,8 @@ impl pallet_xcm::Config for Test {
 	type MaxLockers = frame_support::traits::ConstU32<8>;
 	type MaxRemoteLockConsumers = frame_support::traits::ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type TrappedAssetsExpiry = TrappedAssetsExpiry;
+	type TrappedAssetsBeneficiary = TrappedAssetsBeneficiary;
 	type WeightInfo = TestWeightInfo;
 }
 
@@ -19,9 +19,10 @@
 pub(crate) mod assets_transfer;
 
 use crate::{
-	mock::*, pallet::SupportedVersion, AssetTraps, Config, CurrentMigration, Error,
-	ExecuteControllerWeightInfo, LatestVersionedLocation, Pallet, Queries, QueryStatus,
-	RecordedXcm, ShouldRecordXcm, VersionDiscoveryQueue, VersionMigrationStage, VersionNotifiers,
+	mock::*, pallet::SupportedVersion, AssetTraps, AssetTrapsByBlock, Config, CurrentMigration,
+	Error, ExecuteControllerWeightInfo, LatestVersionedLocation, NextQueryTimeoutCheck, Pallet,
+	Queries, QueryStatus, QueryTimeouts, RecordedXcm, RecordedXcmTrace, ShouldRecordXcm,
+	ShouldTraceXcm, VersionDiscoveryQueue, VersionMigrationStage, VersionNotifiers,
 	VersionNotifyTargets, WeightInfo,
 };
 use frame_support::{
@@ -34,9 +35,13 @@ use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::AllowKnownQueryResponses;
 use xcm_executor::{
-	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
+	traits::{
+		ClaimAssets, DropAssets, Properties, QueryHandler, QueryResponseStatus, RecordXcm,
+		ShouldExecute,
+	},
 	XcmExecutor,
 };
+use xcm_runtime_apis::trapped_assets::TrappedAssets;
 
 const ALICE: AccountId = AccountId::new([0u8; 32]);
 const BOB: AccountId = AccountId::new([1u8; 32]);
@@ -185,6 +190,193 @@ fn report_outcome_works() {
 	});
 }
 
+#[test]
+fn expired_query_is_removed() {
+	let mut message = Xcm(vec![ClearOrigin]);
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		XcmPallet::report_outcome(&mut message, Parachain(OTHER_PARA_ID).into_location(), 100)
+			.unwrap();
+		assert_eq!(QueryTimeouts::<Test>::iter_keys().collect::<Vec<_>>(), vec![(100, 0)]);
+
+		// the query is still valid at its timeout block
+		XcmPallet::on_idle(100, Weight::MAX);
+		assert_eq!(XcmPallet::take_response(0), QueryResponseStatus::Pending { timeout: 100 });
+
+		XcmPallet::on_idle(101, Weight::MAX);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::QueryTimedOut { query_id: 0 })
+		);
+		assert_eq!(XcmPallet::take_response(0), QueryResponseStatus::NotFound);
+		assert_eq!(QueryTimeouts::<Test>::iter_keys().count(), 0);
+		assert_eq!(NextQueryTimeoutCheck::<Test>::get(), Some(101));
+	});
+}
+
+#[test]
+fn expired_notify_query_dispatches_timeout_notification() {
+	let mut message = Xcm(vec![ClearOrigin]);
+	let call = pallet_test_notifier::Call::notification_received {
+		query_id: 0,
+		response: Default::default(),
+	};
+	let notify = RuntimeCall::TestNotifier(call);
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		XcmPallet::report_outcome_notify(
+			&mut message,
+			Parachain(OTHER_PARA_ID).into_location(),
+			notify,
+			100,
+		)
+		.unwrap();
+
+		XcmPallet::on_idle(101, Weight::MAX);
+		assert_eq!(
+			last_events(2),
+			vec![
+				RuntimeEvent::TestNotifier(pallet_test_notifier::Event::ResponseReceived(
+					Here.into(),
+					0,
+					Response::Null,
+				)),
+				RuntimeEvent::XcmPallet(crate::Event::NotifyTimedOut {
+					query_id: 0,
+					pallet_index: 5,
+					call_index: 2
+				}),
+			]
+		);
+		assert_eq!(crate::Queries::<Test>::iter().collect::<Vec<_>>(), vec![]);
+		assert_eq!(QueryTimeouts::<Test>::iter_keys().count(), 0);
+	});
+}
+
+#[test]
+fn query_expiration_respects_weight_limit() {
+	let notify_weight = Weight::from_parts(1_000_000, 1_000_000);
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		for _ in 0..2 {
+			let call = pallet_test_notifier::Call::notification_received {
+				query_id: 0,
+				response: Default::default(),
+			};
+			XcmPallet::new_notify_query(
+				Parachain(OTHER_PARA_ID),
+				RuntimeCall::TestNotifier(call),
+				100,
+				Here,
+			);
+		}
+
+		// there's only enough weight to dispatch a single notification
+		assert_eq!(XcmPallet::on_idle(101, notify_weight * 3 / 2), notify_weight);
+		assert_eq!(crate::Queries::<Test>::iter_keys().count(), 1);
+		assert_eq!(NextQueryTimeoutCheck::<Test>::get(), Some(100));
+
+		// and the rest is expired later
+		assert_eq!(XcmPallet::on_idle(102, notify_weight * 3 / 2), notify_weight);
+		assert_eq!(crate::Queries::<Test>::iter_keys().count(), 0);
+		assert_eq!(NextQueryTimeoutCheck::<Test>::get(), Some(102));
+	});
+}
+
+#[test]
+fn answered_query_is_not_expired() {
+	let mut message = Xcm(vec![ClearOrigin]);
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		XcmPallet::report_outcome(&mut message, Parachain(OTHER_PARA_ID).into_location(), 100)
+			.unwrap();
+		let message = Xcm(vec![QueryResponse {
+			query_id: 0,
+			response: Response::ExecutionResult(None),
+			max_weight: Weight::zero(),
+			querier: Some(Here.into()),
+		}]);
+		let mut hash = fake_message_hash(&message);
+		let r = XcmExecutor::<XcmConfig>::prepare_and_execute(
+			Parachain(OTHER_PARA_ID),
+			message,
+			&mut hash,
+			Weight::from_parts(1_000_000_000, 1_000_000_000),
+			Weight::zero(),
+		);
+		assert_eq!(r, Outcome::Complete { used: Weight::from_parts(1_000, 1_000) });
+
+		XcmPallet::on_idle(101, Weight::MAX);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::ResponseReady {
+				query_id: 0,
+				response: Response::ExecutionResult(None),
+			})
+		);
+		assert_eq!(QueryTimeouts::<Test>::iter_keys().count(), 0);
+
+		let response =
+			QueryResponseStatus::Ready { response: Response::ExecutionResult(None), at: 1 };
+		assert_eq!(XcmPallet::take_response(0), response);
+	});
+}
+
+#[test]
+fn migration_to_v2_indexes_pending_queries() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		StorageVersion::new(1).put::<XcmPallet>();
+		let querier: VersionedLocation = Here.into();
+		// queries registered before the upgrade, one of them already answered
+		Queries::<Test>::insert(
+			0,
+			QueryStatus::Pending {
+				responder: Parachain(OTHER_PARA_ID).into_location().into(),
+				maybe_match_querier: Some(querier.clone()),
+				maybe_notify: None,
+				timeout: 100,
+			},
+		);
+		Queries::<Test>::insert(
+			1,
+			QueryStatus::Pending {
+				responder: Parachain(OTHER_PARA_ID).into_location().into(),
+				maybe_match_querier: Some(querier),
+				maybe_notify: None,
+				timeout: 0,
+			},
+		);
+		Queries::<Test>::insert(2, QueryStatus::Ready { response: Response::Null.into(), at: 1 });
+		frame_system::Pallet::<Test>::set_block_number(10);
+
+		crate::migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<XcmPallet>(), 2);
+		let mut indexed = QueryTimeouts::<Test>::iter_keys().collect::<Vec<_>>();
+		indexed.sort();
+		assert_eq!(indexed, vec![(10, 1), (100, 0)]);
+		assert_eq!(NextQueryTimeoutCheck::<Test>::get(), Some(10));
+
+		XcmPallet::on_idle(101, Weight::MAX);
+		assert_eq!(crate::Queries::<Test>::iter_keys().collect::<Vec<_>>(), vec![2]);
+	});
+}
+
+#[test]
+fn pending_queries_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		XcmPallet::new_query(Parachain(OTHER_PARA_ID), 100, Here);
+		XcmPallet::new_query(Parachain(OTHER_PARA_ID + 1), 100, Here);
+		XcmPallet::new_query(Parachain(OTHER_PARA_ID), 200, Parent);
+
+		let pending = XcmPallet::pending_queries(Location::from(Parachain(OTHER_PARA_ID)).into())
+			.unwrap()
+			.into_iter()
+			.map(|query| (query.query_id, query.timeout, query.maybe_match_querier))
+			.collect::<Vec<_>>();
+		assert_eq!(pending.len(), 2);
+		assert!(pending.contains(&(0, 100, Some(Location::here().into()))));
+		assert!(pending.contains(&(2, 200, Some(Location::parent().into()))));
+	});
+}
+
 #[test]
 fn custom_querier_works() {
 	let balances = vec![
@@ -478,7 +670,7 @@ fn claim_assets_works() {
 		// Even though assets are trapped, the extrinsic returns success.
 		assert_ok!(XcmPallet::execute(
 			RuntimeOrigin::signed(ALICE),
-			Box::new(VersionedXcm::V4(trapping_program)),
+			Box::new(VersionedXcm::V5(trapping_program)),
 			BaseXcmWeight::get() * 2,
 		));
 		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
@@ -518,6 +710,111 @@ fn claim_assets_works() {
 	});
 }
 
+#[test]
+fn expired_asset_traps_are_swept() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let trapping_program =
+			Xcm::<RuntimeCall>::builder_unsafe().withdraw_asset((Here, SEND_AMOUNT)).build();
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(trapping_program)),
+			BaseXcmWeight::get() * 2,
+		));
+
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let assets = VersionedAssets::from(Assets::from((Here, SEND_AMOUNT)));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), assets.clone()));
+		assert_eq!(
+			XcmPallet::trapped_assets(source.clone().into()),
+			Ok(vec![TrappedAssets { assets: assets.clone(), count: 1, trapped_at: 1 }]),
+		);
+		assert_eq!(XcmPallet::trapped_assets(Location::parent().into()), Ok(vec![]));
+
+		// the trap is kept until the expiry has passed
+		XcmPallet::on_idle(11, Weight::MAX);
+		assert_eq!(AssetTraps::<Test>::get(hash), 1);
+
+		XcmPallet::on_idle(12, Weight::MAX);
+		let beneficiary: Location =
+			Junction::AccountId32 { network: None, id: TRAPPED_ASSETS_BENEFICIARY }.into();
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::AssetsSwept {
+				hash,
+				origin: source.clone().into(),
+				assets,
+				beneficiary
+			})
+		);
+		assert_eq!(Balances::total_balance(&TRAPPED_ASSETS_BENEFICIARY.into()), SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::iter().count(), 0);
+		assert_eq!(AssetTrapsByBlock::<Test>::iter().count(), 0);
+		assert_eq!(XcmPallet::trapped_assets(source.into()), Ok(vec![]));
+	});
+}
+
+#[test]
+fn asset_trap_is_swept_after_its_last_trap_expires() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let asset: Asset = (Here, SEND_AMOUNT).into();
+		let assets = VersionedAssets::from(Assets::from(asset.clone()));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), assets.clone()));
+		let context = XcmContext::with_message_id([0; 32]);
+
+		XcmPallet::drop_assets(&source, asset.clone().into(), &context);
+		System::set_block_number(5);
+		XcmPallet::drop_assets(&source, asset.clone().into(), &context);
+		assert_eq!(
+			XcmPallet::trapped_assets(source.clone().into()),
+			Ok(vec![TrappedAssets { assets, count: 2, trapped_at: 5 }]),
+		);
+
+		// the first trap has expired, but the same assets have been trapped again later
+		XcmPallet::on_idle(12, Weight::MAX);
+		assert_eq!(AssetTraps::<Test>::get(hash), 2);
+		assert_eq!(AssetTrapsByBlock::<Test>::iter_keys().collect::<Vec<_>>(), vec![(5, hash)]);
+
+		// one of them is claimed, the other one is swept
+		assert!(<XcmPallet as ClaimAssets>::claim_assets(
+			&source,
+			&Here.into(),
+			&asset.into(),
+			&context
+		));
+		XcmPallet::on_idle(16, Weight::MAX);
+		assert_eq!(Balances::total_balance(&TRAPPED_ASSETS_BENEFICIARY.into()), SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::get(hash), 0);
+	});
+}
+
+#[test]
+fn asset_trap_is_kept_when_sweep_fails() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		// no asset transactor handles this asset
+		let asset: Asset =
+			(Location::new(2, [GlobalConsensus(ByGenesis([9; 32]))]), SEND_AMOUNT).into();
+		let assets = VersionedAssets::from(Assets::from(asset.clone()));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), assets.clone()));
+		XcmPallet::drop_assets(&source, asset.into(), &XcmContext::with_message_id([0; 32]));
+
+		XcmPallet::on_idle(12, Weight::MAX);
+		assert!(matches!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::AssetsSweepFailed { hash: h, .. }) if h == hash
+		));
+
+		// the assets may still be claimed, but they are not swept again
+		assert_eq!(
+			XcmPallet::trapped_assets(source.into()),
+			Ok(vec![TrappedAssets { assets, count: 1, trapped_at: 1 }]),
+		);
+		assert_eq!(AssetTrapsByBlock::<Test>::iter().count(), 0);
+	});
+}
+
 /// Test failure to complete execution reverts intermediate side-effects.
 ///
 /// XCM program will withdraw and deposit some assets, then fail execution of a further withdraw.
@@ -1289,3 +1586,47 @@ fn record_xcm_works() {
 		assert_eq!(RecordedXcm::<Test>::get(), Some(message.into()));
 	});
 }
+
+#[test]
+fn trace_xcm_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let message = Xcm::<RuntimeCall>::builder()
+			.withdraw_asset((Here, SEND_AMOUNT))
+			.buy_execution((Here, SEND_AMOUNT), Unlimited)
+			.deposit_asset(AllCounted(1), Junction::AccountId32 { network: None, id: BOB.into() })
+			.build();
+		// Test default values.
+		assert_eq!(ShouldTraceXcm::<Test>::get(), false);
+		assert_eq!(RecordedXcmTrace::<Test>::get(), vec![]);
+
+		// By default the execution trace won't be recorded.
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(message.clone())),
+			BaseXcmWeight::get() * 3,
+		));
+		assert_eq!(RecordedXcmTrace::<Test>::get(), vec![]);
+
+		// We explicitly enable tracing so we record the state after every instruction.
+		XcmPallet::set_trace_xcm(true);
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(message.clone())),
+			BaseXcmWeight::get() * 3,
+		));
+		let trace = XcmPallet::recorded_trace().unwrap();
+		assert_eq!(trace.iter().map(|t| t.index).collect::<Vec<_>>(), vec![0, 1, 2]);
+		assert!(trace.iter().all(|t| t.error.is_none()));
+		assert_eq!(trace[0].holding, VersionedAssets::from(Assets::from((Here, SEND_AMOUNT))));
+		assert_eq!(
+			trace[1].instruction,
+			VersionedXcm::from(Xcm::<()>(vec![message.0[1].clone().into()]))
+		);
+		assert_eq!(trace[2].holding, VersionedAssets::from(Assets::new()));
+
+		// Enabling tracing again drops the previously recorded trace.
+		XcmPallet::set_trace_xcm(true);
+		assert_eq!(XcmPallet::recorded_trace(), Some(vec![]));
+	});
+}
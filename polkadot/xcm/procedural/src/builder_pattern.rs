@@ -300,10 +300,43 @@ fn generate_builder_impl(name: &Ident, data_enum: &DataEnum) -> Result<TokenStre
 			},
 		)?;
 
+	// Since XCM v5, fees may also be paid with `PayFees`
+	let pay_fees_method = data_enum
+		.variants
+		.iter()
+		.find(|variant| variant.ident == "PayFees")
+		.map(|variant| {
+			let variant_name = &variant.ident;
+			let method_name_string = &variant_name.to_string().to_snake_case();
+			let method_name = syn::Ident::new(method_name_string, variant_name.span());
+			let docs = get_doc_comments(variant);
+			match &variant.fields {
+				Fields::Named(fields) => {
+					let arg_names: Vec<_> = fields.named.iter().map(|field| &field.ident).collect();
+					let arg_types: Vec<_> = fields.named.iter().map(|field| &field.ty).collect();
+					Ok(quote! {
+						#(#docs)*
+						pub fn #method_name(self, #(#arg_names: impl Into<#arg_types>),*) -> XcmBuilder<Call, AnythingGoes> {
+							let mut new_instructions = self.instructions;
+							#(let #arg_names = #arg_names.into();)*
+							new_instructions.push(#name::<Call>::#variant_name { #(#arg_names),* });
+							XcmBuilder {
+								instructions: new_instructions,
+								state: core::marker::PhantomData,
+							}
+						}
+					})
+				},
+				_ => Err(Error::new_spanned(variant, "PayFees should have named fields")),
+			}
+		})
+		.transpose()?;
+
 	let second_impl = quote! {
 		impl<Call> XcmBuilder<Call, LoadedHolding> {
 			#(#allowed_after_load_holding_methods)*
 			#buy_execution_method
+			#pay_fees_method
 		}
 	};
 
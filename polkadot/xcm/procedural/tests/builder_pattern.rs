@@ -38,6 +38,25 @@ fn builder_pattern_works() {
 	);
 }
 
+#[test]
+fn builder_pattern_allows_pay_fees() {
+	let asset: Asset = (Here, 100u128).into();
+	let beneficiary: Location = [0u8; 32].into();
+	let message: Xcm<()> = Xcm::builder()
+		.withdraw_asset(asset.clone())
+		.pay_fees(asset.clone())
+		.deposit_asset(asset.clone(), beneficiary.clone())
+		.build();
+	assert_eq!(
+		message,
+		Xcm(vec![
+			WithdrawAsset(asset.clone().into()),
+			PayFees { asset: asset.clone() },
+			DepositAsset { assets: asset.into(), beneficiary },
+		])
+	);
+}
+
 #[test]
 fn default_builder_requires_buy_execution() {
 	let asset: Asset = (Here, 100u128).into();
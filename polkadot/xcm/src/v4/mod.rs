@@ -17,9 +17,12 @@
 //! Version 4 of the Cross-Consensus Message format data structures.
 
 pub use super::v3::GetWeight;
-use super::v3::{
-	Instruction as OldInstruction, PalletInfo as OldPalletInfo,
-	QueryResponseInfo as OldQueryResponseInfo, Response as OldResponse, Xcm as OldXcm,
+use super::{
+	v3::{
+		Instruction as OldInstruction, PalletInfo as OldPalletInfo,
+		QueryResponseInfo as OldQueryResponseInfo, Response as OldResponse, Xcm as OldXcm,
+	},
+	v5::{Instruction as NewInstruction, Xcm as NewXcm},
 };
 use crate::DoubleEncoded;
 use alloc::{vec, vec::Vec};
@@ -1362,6 +1365,88 @@ impl<Call> TryFrom<OldInstruction<Call>> for Instruction<Call> {
 	}
 }
 
+// Convert from a v5 XCM to a v4 XCM
+impl<Call> TryFrom<NewXcm<Call>> for Xcm<Call> {
+	type Error = ();
+	fn try_from(new_xcm: NewXcm<Call>) -> result::Result<Self, Self::Error> {
+		Ok(Xcm(new_xcm.0.into_iter().map(TryInto::try_into).collect::<result::Result<_, _>>()?))
+	}
+}
+
+// Convert from a v5 instruction to a v4 instruction
+impl<Call> TryFrom<NewInstruction<Call>> for Instruction<Call> {
+	type Error = ();
+	fn try_from(new_instruction: NewInstruction<Call>) -> result::Result<Self, Self::Error> {
+		use NewInstruction::*;
+		Ok(match new_instruction {
+			WithdrawAsset(assets) => Self::WithdrawAsset(assets),
+			ReserveAssetDeposited(assets) => Self::ReserveAssetDeposited(assets),
+			ReceiveTeleportedAsset(assets) => Self::ReceiveTeleportedAsset(assets),
+			QueryResponse { query_id, response, max_weight, querier } =>
+				Self::QueryResponse { query_id, response, max_weight, querier },
+			TransferAsset { assets, beneficiary } => Self::TransferAsset { assets, beneficiary },
+			TransferReserveAsset { assets, dest, xcm } =>
+				Self::TransferReserveAsset { assets, dest, xcm: xcm.try_into()? },
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				Self::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => Self::HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				Self::HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_kind, require_weight_at_most, call } =>
+				Self::Transact { origin_kind, require_weight_at_most, call: call.into() },
+			ReportError(response_info) => Self::ReportError(response_info),
+			DepositAsset { assets, beneficiary } => Self::DepositAsset { assets, beneficiary },
+			DepositReserveAsset { assets, dest, xcm } =>
+				Self::DepositReserveAsset { assets, dest, xcm: xcm.try_into()? },
+			ExchangeAsset { give, want, maximal } => Self::ExchangeAsset { give, want, maximal },
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				Self::InitiateReserveWithdraw { assets, reserve, xcm: xcm.try_into()? },
+			InitiateTeleport { assets, dest, xcm } =>
+				Self::InitiateTeleport { assets, dest, xcm: xcm.try_into()? },
+			ReportHolding { response_info, assets } =>
+				Self::ReportHolding { response_info, assets },
+			BuyExecution { fees, weight_limit } => Self::BuyExecution { fees, weight_limit },
+			ClearOrigin => Self::ClearOrigin,
+			DescendOrigin(who) => Self::DescendOrigin(who),
+			RefundSurplus => Self::RefundSurplus,
+			SetErrorHandler(xcm) => Self::SetErrorHandler(xcm.try_into()?),
+			SetAppendix(xcm) => Self::SetAppendix(xcm.try_into()?),
+			ClearError => Self::ClearError,
+			ClaimAsset { assets, ticket } => Self::ClaimAsset { assets, ticket },
+			Trap(code) => Self::Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				Self::SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => Self::UnsubscribeVersion,
+			BurnAsset(assets) => Self::BurnAsset(assets),
+			ExpectAsset(assets) => Self::ExpectAsset(assets),
+			ExpectOrigin(origin) => Self::ExpectOrigin(origin),
+			ExpectError(error) => Self::ExpectError(error),
+			ExpectTransactStatus(transact_status) => Self::ExpectTransactStatus(transact_status),
+			QueryPallet { module_name, response_info } =>
+				Self::QueryPallet { module_name, response_info },
+			ExpectPallet { index, name, module_name, crate_major, min_crate_minor } =>
+				Self::ExpectPallet { index, name, module_name, crate_major, min_crate_minor },
+			ReportTransactStatus(response_info) => Self::ReportTransactStatus(response_info),
+			ClearTransactStatus => Self::ClearTransactStatus,
+			UniversalOrigin(junction) => Self::UniversalOrigin(junction),
+			ExportMessage { network, destination, xcm } =>
+				Self::ExportMessage { network, destination, xcm: xcm.try_into()? },
+			LockAsset { asset, unlocker } => Self::LockAsset { asset, unlocker },
+			UnlockAsset { asset, target } => Self::UnlockAsset { asset, target },
+			NoteUnlockable { asset, owner } => Self::NoteUnlockable { asset, owner },
+			RequestUnlock { asset, locker } => Self::RequestUnlock { asset, locker },
+			SetFeesMode { jit_withdraw } => Self::SetFeesMode { jit_withdraw },
+			SetTopic(topic) => Self::SetTopic(topic),
+			ClearTopic => Self::ClearTopic,
+			AliasOrigin(location) => Self::AliasOrigin(location),
+			UnpaidExecution { weight_limit, check_origin } =>
+				Self::UnpaidExecution { weight_limit, check_origin },
+			// Instructions, that have been introduced in XCM v5, can't be expressed in XCM v4.
+			PayFees { .. } | InitiateTransfer { .. } | SetHints { .. } => return Err(()),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{prelude::*, *};
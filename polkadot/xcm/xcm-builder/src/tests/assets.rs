@@ -537,3 +537,179 @@ fn max_assets_limit_should_work() {
 		}
 	);
 }
+
+#[test]
+fn pay_fees_should_work() {
+	AllowPaidFrom::set(vec![Parent.into()]);
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+	WeightPrice::set((Parent.into(), 1_000_000_000_000, 1024 * 1024));
+
+	// The whole message weighs 30 and costs 60, so 10 of the fees are left unused.
+	let message = Xcm(vec![
+		ReserveAssetDeposited((Parent, 100u128).into()),
+		PayFees { asset: (Parent, 70u128).into() },
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: Here.into() },
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parent,
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(35, 35) });
+	assert_eq!(asset_list(Here), vec![(Parent, 30u128).into()]);
+	// Unused fees are trapped at the end of execution.
+	assert_eq!(TrappedAssets::get(), vec![(Parent.into(), (Parent, 10u128).into())]);
+}
+
+#[test]
+fn pay_fees_with_too_little_should_fail() {
+	AllowPaidFrom::set(vec![Parent.into()]);
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+	WeightPrice::set((Parent.into(), 1_000_000_000_000, 1024 * 1024));
+
+	let message = Xcm(vec![
+		ReserveAssetDeposited((Parent, 100u128).into()),
+		PayFees { asset: (Parent, 50u128).into() },
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: Here.into() },
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parent,
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(
+		r,
+		Outcome::Incomplete { used: Weight::from_parts(25, 25), error: XcmError::TooExpensive }
+	);
+	assert_eq!(asset_list(Here), vec![]);
+	assert_eq!(TrappedAssets::get(), vec![(Parent.into(), (Parent, 100u128).into())]);
+}
+
+#[test]
+fn initiate_transfer_should_work() {
+	AllowUnpaidFrom::set(vec![[Parachain(1)].into()]);
+	// Child parachain #1 owns 1000 of our tokens and 1000 relay tokens.
+	add_asset(Parachain(1), (Here, 1000));
+	add_asset(Parachain(1), (Parent, 1000));
+	// Relay tokens may be teleported to the sibling parachain #2.
+	let dest: Location = (Parent, Parachain(2)).into();
+	add_teleporter(dest.clone(), (Parent, WildFungible).into());
+	let three: Location = [AccountIndex64 { index: 3, network: None }].into();
+
+	// They want to send our tokens (reserve-based, also used to pay remote fees) and relay tokens
+	// (teleported) to #2, preserving their origin there.
+	let message = Xcm(vec![
+		WithdrawAsset(vec![(Here, 100u128).into(), (Parent, 100u128).into()].into()),
+		InitiateTransfer {
+			destination: dest.clone(),
+			remote_fees: Some(AssetTransferFilter::ReserveDeposit(Definite((Here, 10u128).into()))),
+			preserve_origin: true,
+			assets: vec![
+				AssetTransferFilter::ReserveDeposit(Definite((Here, 90u128).into())),
+				AssetTransferFilter::Teleport(Definite((Parent, 100u128).into())),
+			]
+			.try_into()
+			.unwrap(),
+			remote_xcm: Xcm::<()>(vec![DepositAsset {
+				assets: AllCounted(2).into(),
+				beneficiary: three.clone(),
+			}]),
+		},
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parachain(1),
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(20, 20) });
+
+	let expected_msg = Xcm::<()>(vec![
+		ReserveAssetDeposited(((Parent, Parachain(42)), 10u128).into()),
+		PayFees { asset: ((Parent, Parachain(42)), 10u128).into() },
+		ReserveAssetDeposited(((Parent, Parachain(42)), 90u128).into()),
+		ReceiveTeleportedAsset((Parent, 100u128).into()),
+		AliasOrigin(Location::new(1, [Parachain(42), Parachain(1)])),
+		DepositAsset { assets: AllCounted(2).into(), beneficiary: three },
+	]);
+	let expected_hash = fake_message_hash(&expected_msg);
+	assert_eq!(asset_list(Parachain(1)), vec![(Here, 900u128).into(), (Parent, 900u128).into()]);
+	assert_eq!(asset_list(dest.clone()), vec![(Here, 100u128).into()]);
+	assert_eq!(sent_xcm(), vec![(dest, expected_msg, expected_hash)]);
+}
+
+#[test]
+fn initiate_transfer_without_remote_fees_should_work() {
+	AllowUnpaidFrom::set(vec![[Parachain(1)].into()]);
+	// Child parachain #1 owns 1000 relay tokens, reserved by the relay chain.
+	add_asset(Parachain(1), (Parent, 1000));
+	add_reserve(Parent.into(), (Parent, WildFungible).into());
+
+	let message = Xcm(vec![
+		WithdrawAsset((Parent, 100u128).into()),
+		InitiateTransfer {
+			destination: Parent.into(),
+			remote_fees: None,
+			preserve_origin: false,
+			assets: vec![AssetTransferFilter::ReserveWithdraw(Wild(AllCounted(1)))]
+				.try_into()
+				.unwrap(),
+			remote_xcm: Xcm::<()>(vec![DepositAsset {
+				assets: AllCounted(1).into(),
+				beneficiary: Parachain(2).into(),
+			}]),
+		},
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parachain(1),
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(20, 20) });
+
+	let expected_msg = Xcm::<()>(vec![
+		UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+		WithdrawAsset((Here, 100u128).into()),
+		ClearOrigin,
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: Parachain(2).into() },
+	]);
+	let expected_hash = fake_message_hash(&expected_msg);
+	assert_eq!(asset_list(Parachain(1)), vec![(Parent, 900u128).into()]);
+	assert_eq!(sent_xcm(), vec![(Parent.into(), expected_msg, expected_hash)]);
+}
+
+#[test]
+fn asset_claimer_hint_should_work() {
+	AllowUnpaidFrom::set(vec![[Parachain(1)].into()]);
+	// Child parachain #1 owns 1000 tokens held by us in reserve.
+	add_asset(Parachain(1), (Here, 1000));
+	let claimer: Location = [AccountIndex64 { index: 3, network: None }].into();
+
+	// The withdrawn assets are never deposited, so they are trapped for the claimer.
+	let message = Xcm(vec![
+		SetHints { hints: vec![AssetClaimer { location: claimer.clone() }].try_into().unwrap() },
+		WithdrawAsset((Here, 100u128).into()),
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parachain(1),
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(25, 25) });
+	assert_eq!(asset_list(Parachain(1)), vec![(Here, 900u128).into()]);
+	assert_eq!(TrappedAssets::get(), vec![(claimer, (Here, 100u128).into())]);
+}
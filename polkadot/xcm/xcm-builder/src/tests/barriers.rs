@@ -283,6 +283,34 @@ fn allow_paid_should_work() {
 	assert_eq!(r, Ok(()))
 }
 
+#[test]
+fn allow_paid_with_pay_fees_should_work() {
+	AllowPaidFrom::set(vec![Parent.into()]);
+
+	let mut paying_message = Xcm::<()>(vec![
+		ReserveAssetDeposited((Parent, 100).into()),
+		PayFees { asset: (Parent, 1).into() },
+		DepositAsset { assets: AllCounted(1).into(), beneficiary: Here.into() },
+	]);
+
+	let r = AllowTopLevelPaidExecutionFrom::<IsInVec<AllowPaidFrom>>::should_execute(
+		&Parachain(1).into(),
+		paying_message.inner_mut(),
+		Weight::from_parts(30, 30),
+		&mut props(Weight::zero()),
+	);
+	assert_eq!(r, Err(ProcessMessageError::Unsupported));
+
+	// `PayFees` has no weight limit, so it is accepted for any message weight.
+	let r = AllowTopLevelPaidExecutionFrom::<IsInVec<AllowPaidFrom>>::should_execute(
+		&Parent.into(),
+		paying_message.inner_mut(),
+		Weight::from_parts(30, 30),
+		&mut props(Weight::zero()),
+	);
+	assert_eq!(r, Ok(()));
+}
+
 #[test]
 fn allow_paid_should_deprivilege_origin() {
 	AllowPaidFrom::set(vec![Parent.into()]);
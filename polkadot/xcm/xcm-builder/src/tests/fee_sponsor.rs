@@ -0,0 +1,109 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+fn transfer_message() -> Xcm<TestCall> {
+	Xcm(vec![TransferAsset {
+		assets: (Here, 1u128).into(),
+		beneficiary: [AccountIndex64 { index: 3, network: None }].into(),
+	}])
+}
+
+fn execute(origin: impl Into<Location>, weight_credit: Weight) -> Outcome {
+	let message = transfer_message();
+	let mut hash = fake_message_hash(&message);
+	XcmExecutor::<TestConfig>::prepare_and_execute(
+		origin,
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		weight_credit,
+	)
+}
+
+#[test]
+fn sponsored_execution_should_work() {
+	SponsoredFrom::set(vec![Parent.into()]);
+	add_asset(Parent, (Here, 10u128));
+
+	// unpaid message from the sponsored origin is executed and the sponsor is charged
+	let r = execute(Parent, Weight::zero());
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(10, 10) });
+	assert_eq!(asset_list(AccountIndex64 { index: 3, network: None }), vec![(Here, 1u128).into()]);
+	assert_eq!(SponsorCharges::get(), vec![(Parent.into(), Weight::from_parts(10, 10))]);
+
+	// only the weight, which is not covered by the weight credit, is sponsored
+	let r = execute(Parent, Weight::from_parts(4, 4));
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(10, 10) });
+	assert_eq!(SponsorCharges::get()[1], (Parent.into(), Weight::from_parts(6, 6)));
+}
+
+#[test]
+fn sponsor_should_not_be_charged_for_unused_weight() {
+	SponsoredFrom::set(vec![Parent.into()]);
+
+	// the claim fails, so the weight of the deposit is not used
+	let message = Xcm(vec![
+		ClaimAsset { assets: (Here, 100u128).into(), ticket: GeneralIndex(1).into() },
+		DepositAsset {
+			assets: Wild(AllCounted(1)),
+			beneficiary: AccountIndex64 { index: 3, network: None }.into(),
+		},
+	]);
+	let mut hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::prepare_and_execute(
+		Parent,
+		message,
+		&mut hash,
+		Weight::from_parts(50, 50),
+		Weight::zero(),
+	);
+	assert_eq!(
+		r,
+		Outcome::Incomplete { used: Weight::from_parts(10, 10), error: XcmError::UnknownClaim }
+	);
+	assert_eq!(SponsorCharges::get(), vec![(Parent.into(), Weight::from_parts(10, 10))]);
+}
+
+#[test]
+fn unsponsored_execution_should_be_blocked() {
+	SponsoredFrom::set(vec![Parent.into()]);
+	add_asset((Parent, Parachain(1)), (Here, 10u128));
+
+	let r = execute((Parent, Parachain(1)), Weight::zero());
+	assert_eq!(r, Outcome::Error { error: XcmError::Barrier });
+	assert_eq!(SponsorCharges::get(), vec![]);
+}
+
+#[test]
+fn sponsor_should_not_be_charged_when_credit_is_not_taken() {
+	SponsoredFrom::set(vec![Parent.into(), Here.into()]);
+	add_asset(Parent, (Here, 10u128));
+	add_asset(Here, (Here, 10u128));
+
+	// the barrier blocks execution
+	TestSuspender::set_suspended(true);
+	let r = execute(Parent, Weight::zero());
+	assert_eq!(r, Outcome::Error { error: XcmError::Barrier });
+	TestSuspender::set_suspended(false);
+
+	// the weight credit covers the whole message
+	let r = execute(Here, Weight::from_parts(10, 10));
+	assert_eq!(r, Outcome::Complete { used: Weight::from_parts(10, 10) });
+
+	assert_eq!(SponsorCharges::get(), vec![]);
+}
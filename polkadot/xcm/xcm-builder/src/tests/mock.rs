@@ -44,7 +44,7 @@ use xcm_executor::traits::{Properties, QueryHandler, QueryResponseStatus};
 pub use xcm_executor::{
 	traits::{
 		AssetExchange, AssetLock, CheckSuspension, ConvertOrigin, Enact, ExportXcm, FeeManager,
-		FeeReason, LockError, OnResponse, TransactAsset,
+		FeeReason, FeeSponsor, LockError, OnResponse, TransactAsset,
 	},
 	AssetsInHolding, Config,
 };
@@ -529,6 +529,35 @@ impl FeeManager for TestFeeManager {
 	fn handle_fee(_: Assets, _: Option<&XcmContext>, _: FeeReason) {}
 }
 
+parameter_types! {
+	// Nothing is sponsored by default.
+	pub static SponsoredFrom: Vec<Location> = vec![];
+	pub static SponsorCharges: Vec<(Location, Weight)> = vec![];
+}
+
+/// Sponsors all messages from origins in `SponsoredFrom` and records charged weights in
+/// `SponsorCharges`.
+pub struct TestFeeSponsor;
+impl FeeSponsor for TestFeeSponsor {
+	type Ticket = (Location, Weight);
+
+	fn sponsor<Call>(
+		origin: &Location,
+		_instructions: &[Instruction<Call>],
+		weight: Weight,
+	) -> Option<(Self::Ticket, Weight)> {
+		SponsoredFrom::get()
+			.contains(origin)
+			.then(|| ((origin.clone(), weight), weight))
+	}
+
+	fn charge((origin, weight): Self::Ticket, unused: Weight) {
+		let mut charges = SponsorCharges::get();
+		charges.push((origin, weight.saturating_sub(unused)));
+		SponsorCharges::set(charges);
+	}
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum LockTraceItem {
 	Lock { unlocker: Location, asset: Asset, owner: Location },
@@ -763,6 +792,7 @@ impl Config for TestConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = ();
+	type FeeSponsor = TestFeeSponsor;
 }
 
 pub fn fungible_multi_asset(location: Location, amount: u128) -> Asset {
@@ -16,7 +16,7 @@
 
 use crate::traits::{
 	AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin, DropAssets, ExportXcm,
-	FeeManager, HandleHrmpChannelAccepted, HandleHrmpChannelClosing,
+	FeeManager, FeeSponsor, HandleHrmpChannelAccepted, HandleHrmpChannelClosing,
 	HandleHrmpNewChannelOpenRequest, OnResponse, ProcessTransaction, RecordXcm, ShouldExecute,
 	TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
 };
@@ -131,4 +131,8 @@ pub trait Config {
 	type HrmpChannelClosingHandler: HandleHrmpChannelClosing;
 	/// Allows recording the last executed XCM (used by dry-run runtime APIs).
 	type XcmRecorder: RecordXcm;
+	/// Sponsors execution of messages, which are not paid by their origins.
+	///
+	/// Use `()` if execution is never sponsored.
+	type FeeSponsor: FeeSponsor;
 }
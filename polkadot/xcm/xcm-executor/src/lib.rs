@@ -34,13 +34,13 @@ use xcm::latest::prelude::*;
 pub mod traits;
 use traits::{
 	validate_export, AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin,
-	DropAssets, Enact, ExportXcm, FeeManager, FeeReason, HandleHrmpChannelAccepted,
+	DropAssets, Enact, ExportXcm, FeeManager, FeeReason, FeeSponsor, HandleHrmpChannelAccepted,
 	HandleHrmpChannelClosing, HandleHrmpNewChannelOpenRequest, OnResponse, ProcessTransaction,
 	Properties, ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
 	XcmAssetTransfers,
 };
 
-pub use traits::RecordXcm;
+pub use traits::{InstructionTrace, RecordXcm};
 
 mod assets;
 pub use assets::AssetsInHolding;
@@ -83,9 +83,24 @@ pub struct XcmExecutor<Config: config::Config> {
 	appendix_weight: Weight,
 	transact_status: MaybeErrorCode,
 	fees_mode: FeesMode,
-	/// Asset provided in last `BuyExecution` instruction (if any) in current XCM program. Same
-	/// asset type will be used for paying any potential delivery fees incurred by the program.
+	/// Asset provided in last `BuyExecution` or `PayFees` instruction (if any) in current XCM
+	/// program. Same asset type will be used for paying any potential delivery fees incurred by
+	/// the program.
 	asset_used_for_fees: Option<AssetId>,
+	/// The Fees Register. It is loaded by the `PayFees` instruction and is used to pay for the
+	/// execution and for any delivery fees of the program.
+	fees: AssetsInHolding,
+	/// The weight of the message that is executed. It is paid for by the `PayFees` instruction.
+	message_weight: Weight,
+	/// The location that is able to claim assets, trapped at the end of the execution. Set by the
+	/// `AssetClaimer` hint. If `None`, the assets are trapped for the origin.
+	asset_claimer: Option<Location>,
+	/// Whether the state of the executor is recorded after every executed instruction. Only
+	/// enabled if the `XcmRecorder` records the execution trace.
+	trace_instructions: bool,
+	/// Fees, paid by the instruction that is currently executed. Only tracked if
+	/// `trace_instructions` is set.
+	fees_paid: Assets,
 	_config: PhantomData<Config>,
 }
 
@@ -181,6 +196,24 @@ impl<Config: config::Config> XcmExecutor<Config> {
 	pub fn set_topic(&mut self, v: Option<[u8; 32]>) {
 		self.context.topic = v;
 	}
+	pub fn fees(&self) -> &AssetsInHolding {
+		&self.fees
+	}
+	pub fn set_fees(&mut self, v: AssetsInHolding) {
+		self.fees = v
+	}
+	pub fn message_weight(&self) -> &Weight {
+		&self.message_weight
+	}
+	pub fn set_message_weight(&mut self, v: Weight) {
+		self.message_weight = v
+	}
+	pub fn asset_claimer(&self) -> &Option<Location> {
+		&self.asset_claimer
+	}
+	pub fn set_asset_claimer(&mut self, v: Option<Location>) {
+		self.asset_claimer = v
+	}
 }
 
 pub struct WeighedMessage<Call>(Weight, Xcm<Call>);
@@ -229,6 +262,21 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			Config::XcmRecorder::record(message.clone().into());
 		}
 
+		// The weight, which is not covered by the weight credit, may be sponsored.
+		let sponsorship = if weight_credit.all_gte(xcm_weight) {
+			None
+		} else {
+			Config::FeeSponsor::sponsor(
+				&origin,
+				message.inner(),
+				xcm_weight.saturating_sub(weight_credit),
+			)
+		};
+		if let Some((_, sponsored)) = &sponsorship {
+			properties.weight_credit.saturating_accrue(*sponsored);
+		}
+		let credit_before_barrier = properties.weight_credit;
+
 		if let Err(e) = Config::Barrier::should_execute(
 			&origin,
 			message.inner_mut(),
@@ -246,9 +294,25 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			return Outcome::Error { error: XcmError::Barrier }
 		}
 
+		// the sponsor is only charged if the barrier has taken the weight credit
+		let sponsorship = sponsorship.filter(|(_, sponsored)| {
+			let taken = properties.weight_credit.any_lt(credit_before_barrier);
+			if taken {
+				tracing::trace!(
+					target: "xcm::execute",
+					?origin,
+					?sponsored,
+					"Execution has been sponsored",
+				);
+			}
+			taken
+		});
+
 		*id = properties.message_id.unwrap_or(*id);
 
 		let mut vm = Self::new(origin, *id);
+		vm.message_weight = xcm_weight;
+		vm.trace_instructions = Config::XcmRecorder::should_trace();
 
 		while !message.0.is_empty() {
 			let result = vm.process(message);
@@ -263,7 +327,11 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			}
 		}
 
-		vm.post_process(xcm_weight)
+		let outcome = vm.post_process(xcm_weight);
+		if let Some((ticket, _)) = sponsorship {
+			Config::FeeSponsor::charge(ticket, xcm_weight.saturating_sub(outcome.weight_used()));
+		}
+		outcome
 	}
 
 	fn charge_fees(origin: impl Into<Location>, fees: Assets) -> XcmResult {
@@ -323,6 +391,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			transact_status: Default::default(),
 			fees_mode: FeesMode { jit_withdraw: false },
 			asset_used_for_fees: None,
+			fees: AssetsInHolding::new(),
+			message_weight: Weight::zero(),
+			asset_claimer: None,
+			trace_instructions: false,
+			fees_paid: Assets::new(),
 			_config: PhantomData,
 		}
 	}
@@ -338,15 +411,23 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 		let mut weight_used = xcm_weight.saturating_sub(self.total_surplus);
 
+		// Unused fees are returned to the holding register.
+		self.holding.subsume_assets(core::mem::take(&mut self.fees));
+
 		if !self.holding.is_empty() {
 			tracing::trace!(
 				target: "xcm::post_process",
 				holding_register = ?self.holding,
 				context = ?self.context,
 				original_origin = ?self.original_origin,
+				asset_claimer = ?self.asset_claimer,
 				"Trapping assets in holding register",
 			);
-			let effective_origin = self.context.origin.as_ref().unwrap_or(&self.original_origin);
+			let effective_origin = self
+				.asset_claimer
+				.as_ref()
+				.or(self.context.origin.as_ref())
+				.unwrap_or(&self.original_origin);
 			let trap_weight =
 				Config::AssetTrap::drop_assets(effective_origin, self.holding, &self.context);
 			weight_used.saturating_accrue(trap_weight);
@@ -494,8 +575,18 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let asset_to_pay_for_fees =
 			self.calculate_asset_for_delivery_fees(asset_needed_for_fees.clone());
 		tracing::trace!(target: "xcm::fees", ?asset_to_pay_for_fees);
-		// We withdraw or take from holding the asset the user wants to use for fee payment.
-		let withdrawn_fee_asset = if self.fees_mode.jit_withdraw {
+		// We withdraw or take from the fees register or holding the asset the user wants to use
+		// for fee payment.
+		let withdrawn_fee_asset = if !self.fees.is_empty() {
+			let assets_taken_from_fees = self
+				.fees
+				.try_take(asset_to_pay_for_fees.clone().into())
+				.map_err(|_| XcmError::NotHoldingFees)?;
+			tracing::trace!(target: "xcm::fees", ?assets_taken_from_fees);
+			let mut iter = assets_taken_from_fees.fungible_assets_iter();
+			let asset = iter.next().ok_or(XcmError::NotHoldingFees)?;
+			asset.into()
+		} else if self.fees_mode.jit_withdraw {
 			let origin = self.origin_ref().ok_or(XcmError::BadOrigin)?;
 			Config::AssetTransactor::withdraw_asset(
 				&asset_to_pay_for_fees,
@@ -538,10 +629,53 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			// We just use the assets withdrawn or taken from holding.
 			withdrawn_fee_asset.into()
 		};
+		self.note_fees_paid(&paid);
 		Config::FeeManager::handle_fee(paid, Some(&self.context), reason);
 		Ok(())
 	}
 
+	/// Records the state of the executor after executing the `index`-th `instruction` of the
+	/// current program.
+	fn trace_instruction(
+		&mut self,
+		index: usize,
+		instruction: Instruction<()>,
+		result: &XcmResult,
+	) {
+		let holding: Assets = self.holding.clone().into();
+		Config::XcmRecorder::record_instruction(InstructionTrace {
+			index: index as u32,
+			instruction: Xcm(vec![instruction]).into(),
+			holding: holding.into(),
+			origin: self.context.origin.clone().map(Into::into),
+			fees_paid: core::mem::take(&mut self.fees_paid).into(),
+			error: result.clone().err(),
+		});
+	}
+
+	/// Notes `paid` fees in the execution trace of the current instruction, if it is recorded.
+	fn note_fees_paid(&mut self, paid: &Assets) {
+		if self.trace_instructions {
+			for asset in paid.inner() {
+				self.fees_paid.push(asset.clone());
+			}
+		}
+	}
+
+	/// Notes the part of `max_fee`, that has not been returned as `unspent` by the trader, in the
+	/// execution trace of the current instruction. `max_fee` is only `Some(_)` if the trace is
+	/// recorded.
+	fn note_weight_fees_paid(
+		&mut self,
+		max_fee: Option<AssetsInHolding>,
+		unspent: &AssetsInHolding,
+	) {
+		if let Some(mut paid) = max_fee {
+			paid.saturating_take(Assets::from(unspent.clone()).into());
+			self.note_fees_paid(&paid.into());
+		}
+	}
+
 	/// Calculates the amount of `self.asset_used_for_fees` required to swap for
 	/// `asset_needed_for_fees`.
 	///
@@ -651,6 +785,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		for (i, instr) in xcm.0.into_iter().enumerate() {
 			match &mut result {
 				r @ Ok(()) => {
+					let traced_instruction = self.trace_instructions.then(|| instr.clone().into());
 					// Initialize the recursion count only the first time we hit this code in our
 					// potential recursive execution.
 					let inst_res = recursion_count::using_once(&mut 1, || {
@@ -674,6 +809,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 						self.process_instruction(instr)
 					});
+					if let Some(instruction) = traced_instruction {
+						self.trace_instruction(i, instruction, &inst_res);
+					}
 					if let Err(e) = inst_res {
 						tracing::trace!(target: "xcm::execute", "!!! ERROR: {:?}", e);
 						*r = Err(ExecutorError {
@@ -734,6 +872,14 @@ impl<Config: config::Config> XcmExecutor<Config> {
 						Config::IsReserve::contains(asset, origin),
 						XcmError::UntrustedReserveLocation
 					);
+					Config::AssetTransactor::can_receive_reserve_asset(
+						origin,
+						asset,
+						&self.context,
+					)?;
+				}
+				for asset in assets.inner() {
+					Config::AssetTransactor::receive_reserve_asset(origin, asset, &self.context);
 				}
 				self.holding.subsume_assets(assets.into());
 				Ok(())
@@ -1088,8 +1234,10 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				// pay for `weight` using up to `fees` of the holding register.
 				let max_fee =
 					self.holding.try_take(fees.into()).map_err(|_| XcmError::NotHoldingFees)?;
+				let traced_max_fee = self.trace_instructions.then(|| max_fee.clone());
 				let result = || -> Result<(), XcmError> {
 					let unspent = self.trader.buy_weight(weight, max_fee, &self.context)?;
+					self.note_weight_fees_paid(traced_max_fee, &unspent);
 					self.holding.subsume_assets(unspent);
 					Ok(())
 				}();
@@ -1372,6 +1520,97 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				);
 				Ok(())
 			},
+			PayFees { asset } => {
+				// There is nothing to pay for if the message has not been weighed (e.g. if the
+				// instruction is executed directly, bypassing `ExecuteXcm::execute`).
+				if self.message_weight == Weight::zero() {
+					tracing::warn!(
+						target: "xcm::executor::PayFees",
+						"Message was not weighed or weight was 0. Nothing will be charged.",
+					);
+					return Ok(())
+				}
+				let old_holding = self.holding.clone();
+				// Save the asset being used for execution fees, so we later know what should be
+				// used for delivery fees.
+				self.asset_used_for_fees = Some(asset.id.clone());
+				tracing::trace!(target: "xcm::executor::PayFees", asset_used_for_fees = ?self.asset_used_for_fees);
+				// pay for the weight of the whole message using up to `asset` of the holding
+				// register.
+				let max_fee =
+					self.holding.try_take(asset.into()).map_err(|_| XcmError::NotHoldingFees)?;
+				let traced_max_fee = self.trace_instructions.then(|| max_fee.clone());
+				let result = || -> Result<(), XcmError> {
+					let unspent =
+						self.trader.buy_weight(self.message_weight, max_fee, &self.context)?;
+					self.note_weight_fees_paid(traced_max_fee, &unspent);
+					// unspent fees are kept in the fees register to pay for any further fees.
+					self.fees.subsume_assets(unspent);
+					Ok(())
+				}();
+				if result.is_err() {
+					self.holding = old_holding;
+				}
+				result
+			},
+			InitiateTransfer { destination, remote_fees, preserve_origin, assets, remote_xcm } => {
+				let old_holding = self.holding.clone();
+				let result = Config::TransactionalProcessor::process(|| {
+					let mut message = Vec::with_capacity(assets.len() + remote_xcm.len() + 3);
+					// Fees are transferred first, so that the remote execution is paid for before
+					// any other assets are loaded into the remote holding register.
+					if let Some(remote_fees) = remote_fees {
+						let fees = self
+							.holding
+							.try_take(remote_fees.inner().clone())
+							.map_err(|_| XcmError::NotHoldingFees)?;
+						let fees = self.transfer_assets_to(&remote_fees, fees, &destination)?;
+						let fee_asset = match fees.inner().as_slice() {
+							[fee_asset] => fee_asset.clone(),
+							_ => return Err(XcmError::NotHoldingFees),
+						};
+						message.push(Self::receive_transferred_assets(&remote_fees, fees));
+						message.push(PayFees { asset: fee_asset });
+					} else {
+						message
+							.push(UnpaidExecution { weight_limit: Unlimited, check_origin: None });
+					}
+					for transfer_filter in assets.into_iter() {
+						let to_transfer =
+							self.holding.saturating_take(transfer_filter.inner().clone());
+						if to_transfer.is_empty() {
+							continue
+						}
+						let transferred =
+							self.transfer_assets_to(&transfer_filter, to_transfer, &destination)?;
+						message
+							.push(Self::receive_transferred_assets(&transfer_filter, transferred));
+					}
+					if preserve_origin {
+						// The destination must allow us to alias the reanchored origin.
+						let origin = self.cloned_origin().ok_or(XcmError::BadOrigin)?;
+						let reanchored_origin = Self::try_reanchor(origin, &destination)?.0;
+						message.push(AliasOrigin(reanchored_origin));
+					} else {
+						message.push(ClearOrigin);
+					}
+					message.extend(remote_xcm.0.into_iter());
+					self.send(destination, Xcm(message), FeeReason::InitiateTransfer)?;
+					Ok(())
+				});
+				if Config::TransactionalProcessor::IS_TRANSACTIONAL && result.is_err() {
+					self.holding = old_holding;
+				}
+				result
+			},
+			SetHints { hints } => {
+				for hint in hints.into_iter() {
+					match hint {
+						AssetClaimer { location } => self.asset_claimer = Some(location),
+					}
+				}
+				Ok(())
+			},
 			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
 				Config::TransactionalProcessor::process(|| {
 					Config::HrmpNewChannelOpenRequestHandler::handle(
@@ -1390,6 +1629,70 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		}
 	}
 
+	/// Transfer `assets`, taken from the holding register, to the `destination` in the way, given
+	/// by the `transfer_filter`. Returns the transferred assets, reanchored to the `destination`.
+	///
+	/// This function can write into storage and also return an error at the same time, it should
+	/// always be called within a transactional context.
+	fn transfer_assets_to(
+		&mut self,
+		transfer_filter: &AssetTransferFilter,
+		assets: AssetsInHolding,
+		destination: &Location,
+	) -> Result<Assets, XcmError> {
+		Ok(match transfer_filter {
+			AssetTransferFilter::Teleport(_) => {
+				// Must ensure that we have teleport trust with destination for these assets.
+				#[cfg(not(feature = "runtime-benchmarks"))]
+				for asset in assets.assets_iter() {
+					ensure!(
+						Config::IsTeleporter::contains(&asset, destination),
+						XcmError::UntrustedTeleportLocation
+					);
+				}
+				for asset in assets.assets_iter() {
+					Config::AssetTransactor::can_check_out(destination, &asset, &self.context)?;
+					Config::AssetTransactor::check_out(destination, &asset, &self.context);
+				}
+				// Note that we pass `None` as `maybe_failed_bin` and drop any assets which
+				// cannot be reanchored because we have already checked all assets out.
+				Self::reanchored(assets, destination, None)
+			},
+			AssetTransferFilter::ReserveDeposit(_) => {
+				self.deposit_assets_with_retry(&assets, destination)?;
+				// Note that we pass `None` as `maybe_failed_bin` and drop any assets which
+				// cannot be reanchored because we have already deposited all assets.
+				Self::reanchored(assets, destination, None)
+			},
+			AssetTransferFilter::ReserveWithdraw(_) => {
+				// Must ensure that we recognise the assets as being managed by the destination.
+				#[cfg(not(feature = "runtime-benchmarks"))]
+				for asset in assets.assets_iter() {
+					ensure!(
+						Config::IsReserve::contains(&asset, destination),
+						XcmError::UntrustedReserveLocation
+					);
+				}
+				// Note that here we are able to place any assets which could not be reanchored
+				// back into Holding.
+				Self::reanchored(assets, destination, Some(&mut self.holding))
+			},
+		})
+	}
+
+	/// Returns the instruction that loads the `assets`, transferred in the way, given by the
+	/// `transfer_filter`, into the holding register of the destination.
+	fn receive_transferred_assets(
+		transfer_filter: &AssetTransferFilter,
+		assets: Assets,
+	) -> Instruction<()> {
+		match transfer_filter {
+			AssetTransferFilter::Teleport(_) => ReceiveTeleportedAsset(assets),
+			AssetTransferFilter::ReserveDeposit(_) => ReserveAssetDeposited(assets),
+			AssetTransferFilter::ReserveWithdraw(_) => WithdrawAsset(assets),
+		}
+	}
+
 	/// Deposit `to_deposit` assets to `beneficiary`, without giving up on the first (transient)
 	/// error, and retrying once just in case one of the subsequently deposited assets satisfy some
 	/// requirement.
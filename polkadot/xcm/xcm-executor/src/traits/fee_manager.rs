@@ -49,6 +49,8 @@ pub enum FeeReason {
 	LockAsset,
 	/// When the `RequestUnlock` instruction is called.
 	RequestUnlock,
+	/// When the `InitiateTransfer` instruction is called.
+	InitiateTransfer,
 }
 
 impl FeeManager for () {
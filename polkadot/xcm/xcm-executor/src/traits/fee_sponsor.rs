@@ -0,0 +1,61 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::{Instruction, Location, Weight};
+
+/// Sponsors execution of XCM programs, so that their origins don't need to pay for it.
+///
+/// When the message is not covered by the weight credit it is executed with, the executor asks
+/// the sponsor for additional weight credit, before the `Barrier` is checked. The granted credit
+/// may then be taken by the `TakeWeightCredit` barrier. The sponsor is charged only if the
+/// barrier has allowed the execution and has taken the credit, once the message has been executed,
+/// so the weight that has not been used by the message is not charged.
+///
+/// Note that the weight credit is taken for the whole weight of the message, so the sponsor is
+/// charged for the weight of all instructions, even if the message also pays for its execution
+/// with `BuyExecution` or `PayFees`.
+pub trait FeeSponsor {
+	/// Information, needed to charge the sponsor.
+	type Ticket;
+
+	/// Returns the weight credit, granted to the `origin` for execution of the `instructions`, that
+	/// weigh `weight`, together with the ticket, used to charge the sponsor. Returns `None` if the
+	/// execution is not sponsored.
+	fn sponsor<RuntimeCall>(
+		origin: &Location,
+		instructions: &[Instruction<RuntimeCall>],
+		weight: Weight,
+	) -> Option<(Self::Ticket, Weight)>;
+
+	/// Charges the sponsor for the granted weight credit, once the credit has been taken and the
+	/// message has been executed. The `unused` weight of the message is deducted from the granted
+	/// weight credit first.
+	fn charge(ticket: Self::Ticket, unused: Weight);
+}
+
+impl FeeSponsor for () {
+	type Ticket = ();
+
+	fn sponsor<RuntimeCall>(
+		_origin: &Location,
+		_instructions: &[Instruction<RuntimeCall>],
+		_weight: Weight,
+	) -> Option<(Self::Ticket, Weight)> {
+		None
+	}
+
+	fn charge(_ticket: Self::Ticket, _unused: Weight) {}
+}
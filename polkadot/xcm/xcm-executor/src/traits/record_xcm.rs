@@ -16,7 +16,35 @@
 
 //! Trait for recording XCMs and a dummy implementation.
 
-use xcm::latest::Xcm;
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use xcm::{
+	latest::{Error as XcmError, Xcm},
+	VersionedAssets, VersionedLocation, VersionedXcm,
+};
+
+/// State of the XCM executor, recorded right after executing a single instruction.
+///
+/// The instruction, assets and locations are versioned, so that the trace can be decoded by
+/// clients which don't know the XCM version, used by the runtime. The `error` is in the latest
+/// version, like the `Outcome` of the execution.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct InstructionTrace {
+	/// Index of the instruction in the executed program. The appendix and the error handler are
+	/// executed as separate programs, so indices start from zero for them.
+	pub index: u32,
+	/// The executed instruction, as a program with this single instruction.
+	pub instruction: VersionedXcm<()>,
+	/// Contents of the holding register after the instruction has been executed.
+	pub holding: VersionedAssets,
+	/// The origin after the instruction has been executed.
+	pub origin: Option<VersionedLocation>,
+	/// Execution and delivery fees, paid by the instruction.
+	pub fees_paid: VersionedAssets,
+	/// The error, returned by the instruction, if any.
+	pub error: Option<XcmError>,
+}
 
 /// Trait for recording XCMs.
 pub trait RecordXcm {
@@ -29,6 +57,20 @@ pub trait RecordXcm {
 	fn recorded_xcm() -> Option<Xcm<()>>;
 	/// Record `xcm`.
 	fn record(xcm: Xcm<()>);
+	/// Whether or not we should record the execution trace of incoming XCMs.
+	fn should_trace() -> bool {
+		false
+	}
+	/// Enable or disable recording of the execution trace. Enabling it drops the previously
+	/// recorded trace.
+	fn set_trace_xcm(_enabled: bool) {}
+	/// Get the execution trace, recorded since the tracing has been enabled.
+	/// Returns `None` if tracing is not supported.
+	fn recorded_trace() -> Option<Vec<InstructionTrace>> {
+		None
+	}
+	/// Record the state of the executor after executing a single instruction.
+	fn record_instruction(_trace: InstructionTrace) {}
 }
 
 impl RecordXcm for () {
@@ -76,6 +76,29 @@ pub trait TransactAsset {
 	/// type-items.
 	fn check_out(_dest: &Location, _what: &Asset, _context: &XcmContext) {}
 
+	/// Ensure that the reserve-backed asset may be received from the given origin.
+	///
+	/// Called for every asset of the `ReserveAssetDeposited` instruction, after the origin has been
+	/// recognised as the reserve of the asset. Returning an error rejects the instruction.
+	///
+	/// When composed as a tuple, all type-items are called and all must result in `Ok`.
+	fn can_receive_reserve_asset(
+		_origin: &Location,
+		_what: &Asset,
+		_context: &XcmContext,
+	) -> XcmResult {
+		Ok(())
+	}
+
+	/// A reserve-backed asset has been received from the given origin and is about to be placed
+	/// into the holding register. This should do whatever housekeeping is needed.
+	///
+	/// The caller should ensure that `can_receive_reserve_asset` has returned with `Ok` for all
+	/// received assets.
+	///
+	/// When composed as a tuple, all type-items are called.
+	fn receive_reserve_asset(_origin: &Location, _what: &Asset, _context: &XcmContext) {}
+
 	/// Deposit the `what` asset into the account of `who`.
 	///
 	/// Implementations should return `XcmError::FailedToTransactAsset` if deposit failed.
@@ -187,6 +210,23 @@ impl TransactAsset for Tuple {
 		)* );
 	}
 
+	fn can_receive_reserve_asset(
+		origin: &Location,
+		what: &Asset,
+		context: &XcmContext,
+	) -> XcmResult {
+		for_tuples!( #(
+			Tuple::can_receive_reserve_asset(origin, what, context)?;
+		)* );
+		Ok(())
+	}
+
+	fn receive_reserve_asset(origin: &Location, what: &Asset, context: &XcmContext) {
+		for_tuples!( #(
+			Tuple::receive_reserve_asset(origin, what, context);
+		)* );
+	}
+
 	fn deposit_asset(what: &Asset, who: &Location, context: Option<&XcmContext>) -> XcmResult {
 		for_tuples!( #(
 			match Tuple::deposit_asset(what, who, context) {
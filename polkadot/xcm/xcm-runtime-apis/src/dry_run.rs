@@ -18,10 +18,12 @@
 //! This API can be used to simulate XCMs and, for example, find the fees
 //! that need to be paid.
 
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 use codec::{Decode, Encode};
+use core::fmt::Write;
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, TypeInfo};
 use xcm::prelude::*;
+use xcm_executor::InstructionTrace;
 
 /// Effects of dry-running an extrinsic.
 #[derive(Encode, Decode, Debug, TypeInfo)]
@@ -36,6 +38,31 @@ pub struct CallDryRunEffects<Event> {
 	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
 }
 
+/// Effects of dry-running an XCM program.
+///
+/// This was used in version 1 of the [`DryRunApi`] runtime api.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct XcmDryRunEffectsV1<Event> {
+	/// The outcome of the XCM program execution.
+	pub execution_result: Outcome,
+	/// List of events fired by the XCM program execution.
+	pub emitted_events: Vec<Event>,
+	/// List of queued messages for sending.
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+impl<Event> XcmDryRunEffectsV1<Event> {
+	/// Convert into the latest version of the [`XcmDryRunEffects`] struct.
+	pub fn into_latest(self) -> XcmDryRunEffects<Event> {
+		XcmDryRunEffects {
+			execution_result: self.execution_result,
+			emitted_events: self.emitted_events,
+			forwarded_xcms: self.forwarded_xcms,
+			execution_trace: None,
+		}
+	}
+}
+
 /// Effects of dry-running an XCM program.
 #[derive(Encode, Decode, Debug, TypeInfo)]
 pub struct XcmDryRunEffects<Event> {
@@ -45,6 +72,43 @@ pub struct XcmDryRunEffects<Event> {
 	pub emitted_events: Vec<Event>,
 	/// List of queued messages for sending.
 	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+	/// State of the XCM executor after every executed instruction, in execution order. `None` if
+	/// the `XcmRecorder` of the runtime doesn't record the execution trace.
+	pub execution_trace: Option<Vec<InstructionTrace>>,
+}
+
+impl<Event> XcmDryRunEffects<Event> {
+	/// Renders the execution trace in a human readable form, one entry per executed instruction.
+	///
+	/// Returns `None` if the execution trace has not been recorded.
+	pub fn render_trace(&self) -> Option<String> {
+		self.execution_trace.as_ref().map(|trace| render_trace(trace))
+	}
+}
+
+/// Renders the execution `trace` in a human readable form, one entry per executed instruction.
+///
+/// Every entry has the index of the instruction, the instruction, the error, if the instruction
+/// has failed, the fees, paid by the instruction, and the origin and the holding register after
+/// the instruction has been executed.
+pub fn render_trace(trace: &[InstructionTrace]) -> String {
+	let mut rendered = String::new();
+	for item in trace {
+		let instruction = match Xcm::<()>::try_from(item.instruction.clone()) {
+			Ok(xcm) if xcm.0.len() == 1 => format!("{:?}", xcm.0[0]),
+			_ => format!("{:?}", item.instruction),
+		};
+		let _ = write!(rendered, "#{} {}", item.index, instruction);
+		if let Some(error) = &item.error {
+			let _ = write!(rendered, " failed: {:?}", error);
+		}
+		let _ = writeln!(
+			rendered,
+			"\n\tfees paid: {:?}\n\torigin: {:?}\n\tholding: {:?}",
+			item.fees_paid, item.origin, item.holding,
+		);
+	}
+	rendered
 }
 
 sp_api::decl_runtime_apis! {
@@ -57,6 +121,7 @@ sp_api::decl_runtime_apis! {
 	/// Calls or XCMs might fail when executed, this doesn't mean the result of these calls will be an `Err`.
 	/// In those cases, there might still be a valid result, with the execution error inside it.
 	/// The only reasons why these calls might return an error are listed in the [`Error`] enum.
+	#[api_version(2)]
 	pub trait DryRunApi<Call, Event, OriginCaller>
 	where
 		Call: Encode,
@@ -67,6 +132,13 @@ sp_api::decl_runtime_apis! {
 		fn dry_run_call(origin: OriginCaller, call: Call) -> Result<CallDryRunEffects<Event>, Error>;
 
 		/// Dry run XCM program
+		#[changed_in(2)]
+		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<XcmDryRunEffectsV1<Event>, Error>;
+
+		/// Dry run XCM program.
+		///
+		/// Since version 2, the effects contain the execution trace of the program, if the
+		/// runtime records it.
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<XcmDryRunEffects<Event>, Error>;
 	}
 }
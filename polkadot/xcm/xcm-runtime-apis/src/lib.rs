@@ -30,3 +30,11 @@ pub mod dry_run;
 /// Fee estimation API.
 /// Given an XCM program, it will return the fees needed to execute it properly or send it.
 pub mod fees;
+
+/// Queries API.
+/// Given a location, it returns the queries that wait for responses from it.
+pub mod queries;
+
+/// Trapped assets API.
+/// Given a location, it returns the assets that have been trapped by it and may be claimed.
+pub mod trapped_assets;
@@ -0,0 +1,57 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for inspecting XCM queries, that wait for responses.
+
+use alloc::vec::Vec;
+use codec::{Codec, Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use xcm::{latest::QueryId, VersionedLocation};
+
+sp_api::decl_runtime_apis! {
+	/// API for inspecting queries that have been registered on this chain and are still waiting
+	/// for responses.
+	pub trait XcmQueriesApi<BlockNumber> where BlockNumber: Codec {
+		/// Returns all pending queries that expect a response from the `responder`.
+		///
+		/// # Arguments
+		///
+		/// * `responder`: The location that is expected to respond to queries.
+		fn pending_queries(responder: VersionedLocation) -> Result<Vec<PendingQuery<BlockNumber>>, Error>;
+	}
+}
+
+/// A query that waits for a response.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct PendingQuery<BlockNumber> {
+	/// The identifier of the query.
+	pub query_id: QueryId,
+	/// The `querier` the response must have to be accepted. If `None`, then the querier is
+	/// ignored.
+	pub maybe_match_querier: Option<VersionedLocation>,
+	/// Pallet and call indices of the call that is dispatched when the response is received, or
+	/// when the query times out.
+	pub maybe_notify: Option<(u8, u8)>,
+	/// The block number after which the query is considered expired.
+	pub timeout: BlockNumber,
+}
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 0)]
+	VersionedConversionFailed,
+}
@@ -0,0 +1,56 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for inspecting assets, that have been trapped on this chain.
+
+use alloc::vec::Vec;
+use codec::{Codec, Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use xcm::{VersionedAssets, VersionedLocation};
+
+sp_api::decl_runtime_apis! {
+	/// API for inspecting assets, that have been trapped by XCM programs executed on this chain
+	/// and have not been claimed yet.
+	pub trait TrappedAssetsApi<BlockNumber> where BlockNumber: Codec {
+		/// Returns all asset traps of the `origin`.
+		///
+		/// # Arguments
+		///
+		/// * `origin`: The origin of XCM programs that have trapped the assets. The returned assets
+		///   may be claimed by the same origin.
+		fn trapped_assets(origin: VersionedLocation) -> Result<Vec<TrappedAssets<BlockNumber>>, Error>;
+	}
+}
+
+/// Assets that have been trapped and may be claimed with the `ClaimAsset` instruction.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct TrappedAssets<BlockNumber> {
+	/// The trapped assets. They must be claimed exactly in this version, i.e. the version must be
+	/// passed in the `ticket` of the `ClaimAsset` instruction.
+	pub assets: VersionedAssets,
+	/// The number of times these assets have been trapped by the same origin. Each of them must
+	/// be claimed separately.
+	pub count: u32,
+	/// The block where the assets have been trapped for the last time.
+	pub trapped_at: BlockNumber,
+}
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 0)]
+	VersionedConversionFailed,
+}
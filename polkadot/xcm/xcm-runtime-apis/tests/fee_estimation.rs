@@ -351,5 +351,72 @@ fn dry_run_xcm() {
 				RuntimeEvent::Balances(pallet_balances::Event::Minted { who: 2100, amount: 520 }),
 			]
 		);
+
+		// The execution trace has an item for every instruction.
+		let rendered_trace = dry_run_effects.render_trace().unwrap();
+		assert_eq!(rendered_trace.matches("\n\tholding: ").count(), 4);
+		assert!(rendered_trace.starts_with("#0 WithdrawAsset"));
+		let trace = dry_run_effects.execution_trace.unwrap();
+		assert_eq!(trace.len(), 4);
+		assert!(trace.iter().enumerate().all(|(i, t)| t.index == i as u32 && t.error.is_none()));
+		assert_eq!(
+			trace[0].origin,
+			Some(VersionedLocation::from(Location::new(
+				0,
+				[AccountIndex64 { index: 1, network: None }]
+			)))
+		);
+		assert_eq!(trace[1].origin, None);
+		assert_eq!(
+			trace[2].holding,
+			VersionedAssets::from(Assets::from((Here, transfer_amount + execution_fees)))
+		);
+		// Delivery fees are paid by `DepositReserveAsset`.
+		assert_eq!(
+			trace[3].fees_paid,
+			VersionedAssets::from(Assets::from((Here, DeliveryFees::get())))
+		);
+		assert_eq!(trace[3].holding, VersionedAssets::from(Assets::new()));
+	});
+}
+
+#[test]
+fn dry_run_xcm_traces_failed_instruction() {
+	sp_tracing::init_for_tests();
+	let who = 1; // AccountId = u64.
+	let transfer_amount = 100u128;
+	let xcm = Xcm::<RuntimeCall>::builder_unsafe()
+		.withdraw_asset((Here, transfer_amount))
+		.buy_execution((Here, 1u128), Unlimited)
+		.expect_origin(None)
+		.deposit_asset(AllCounted(1), [0u8; 32])
+		.build();
+	let balances = vec![(who, transfer_amount + ExistentialDeposit::get())];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let client = TestClient;
+		let runtime_api = client.runtime_api();
+		let dry_run_effects = runtime_api
+			.dry_run_xcm(
+				H256::zero(),
+				VersionedLocation::from([AccountIndex64 { index: 1, network: None }]),
+				VersionedXcm::from(xcm),
+			)
+			.unwrap()
+			.unwrap();
+		assert!(matches!(
+			dry_run_effects.execution_result,
+			Outcome::Incomplete { error: XcmError::ExpectationFalse, .. }
+		));
+
+		// Instructions after the failed one are not executed and not traced.
+		let rendered_trace = dry_run_effects.render_trace().unwrap();
+		assert!(rendered_trace.contains("#2 ExpectOrigin(None) failed: ExpectationFalse"));
+		let trace = dry_run_effects.execution_trace.unwrap();
+		assert_eq!(trace.len(), 3);
+		assert!(trace[..2].iter().all(|t| t.error.is_none()));
+		assert_eq!(trace[2].index, 2);
+		assert_eq!(trace[2].instruction, VersionedXcm::from(Xcm::<()>(vec![ExpectOrigin(None)])));
+		assert_eq!(trace[2].error, Some(XcmError::ExpectationFalse));
+		assert_eq!(trace[2].holding, VersionedAssets::from(Assets::from((Here, transfer_amount))));
 	});
 }
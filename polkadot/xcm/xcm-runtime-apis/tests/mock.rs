@@ -302,6 +302,7 @@ impl xcm_executor::Config for XcmConfig {
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
 	type XcmRecorder = XcmPallet;
+	type FeeSponsor = ();
 }
 
 /// Converts a signed origin of a u64 account into a location with only the `AccountIndex64`
@@ -350,6 +351,8 @@ impl pallet_xcm::Config for TestRuntime {
 	type MaxLockers = ConstU32<0>;
 	type MaxRemoteLockConsumers = ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
+	type TrappedAssetsExpiry = ();
+	type TrappedAssetsBeneficiary = ();
 	type WeightInfo = TestWeightInfo;
 }
 
@@ -501,7 +504,9 @@ sp_api::mock_impl_runtime_apis! {
 				);
 				XcmDryRunApiError::VersionedConversionFailed
 			})?;
+			use xcm_executor::RecordXcm;
 			let mut hash = fake_message_hash(&xcm);
+			pallet_xcm::Pallet::<TestRuntime>::set_trace_xcm(true);
 			let result = XcmExecutor::<XcmConfig>::prepare_and_execute(
 				origin_location,
 				xcm,
@@ -509,6 +514,8 @@ sp_api::mock_impl_runtime_apis! {
 				Weight::MAX, // Max limit available for execution.
 				Weight::zero(),
 			);
+			pallet_xcm::Pallet::<TestRuntime>::set_trace_xcm(false);
+			let execution_trace = pallet_xcm::Pallet::<TestRuntime>::recorded_trace();
 			let forwarded_xcms = sent_xcm()
 				.into_iter()
 				.map(|(location, message)| (
@@ -520,6 +527,7 @@ sp_api::mock_impl_runtime_apis! {
 				forwarded_xcms,
 				emitted_events: events,
 				execution_result: result,
+				execution_trace,
 			})
 		}
 	}
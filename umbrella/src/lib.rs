@@ -1180,6 +1180,10 @@ pub use snowbridge_core;
 #[cfg(feature = "snowbridge-ethereum")]
 pub use snowbridge_ethereum;
 
+/// Snowbridge Inbound Queue Runtime API.
+#[cfg(feature = "snowbridge-inbound-queue-runtime-api")]
+pub use snowbridge_inbound_queue_runtime_api;
+
 /// Snowbridge Outbound Queue Merkle Tree.
 #[cfg(feature = "snowbridge-outbound-queue-merkle-tree")]
 pub use snowbridge_outbound_queue_merkle_tree;